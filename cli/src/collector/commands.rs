@@ -37,6 +37,9 @@ pub(crate) enum CommandArgs {
         /// Depth for file listing. Max is 255
         #[arg(long, default_value_t = 2)]
         depth: u8,
+        /// Follow symbolic links and junctions
+        #[arg(long)]
+        follow_links: bool,
         /// Regex to only include entries that match
         #[arg(long, default_value = None)]
         regex_filter: Option<String>,
@@ -57,6 +60,12 @@ pub(crate) enum CommandArgs {
         /// Alternative Prefetch directory to use
         #[arg(long, default_value = None)]
         alt_dir: Option<String>,
+        /// Flag executables with high entropy (possibly packed)
+        #[arg(long)]
+        entropy: bool,
+        /// Max number of last run times to output for each prefetch file
+        #[arg(long, default_value = None)]
+        max_last_run_times: Option<usize>,
     },
     /// windows: Parse EventLogs
     Eventlogs {
@@ -141,6 +150,9 @@ pub(crate) enum CommandArgs {
         /// Alternative full path to NTUSER.DAT or UsrClass.dat Registry file
         #[arg(long, default_value = None)]
         alt_file: Option<String>,
+        /// Alternative drive letter to use
+        #[arg(long, default_value = None)]
+        alt_drive: Option<char>,
     },
     /// windows: Parse Amcache
     Amcache {
@@ -150,9 +162,9 @@ pub(crate) enum CommandArgs {
     },
     /// windows: Parse Shortcuts
     Shortcuts {
-        /// Path to directory containing Shortcut files
-        #[arg(long)]
-        path: String,
+        /// Path to directory containing Shortcut files. Defaults to the Recent and Desktop directories
+        #[arg(long, default_value = None)]
+        path: Option<String>,
     },
     /// windows: Parse UsnJrnl
     Usnjrnl {
@@ -162,6 +174,9 @@ pub(crate) enum CommandArgs {
         /// Alternative path to UsnJrnl
         #[arg(long, default_value = None)]
         alt_path: Option<String>,
+        /// Collect from all mounted NTFS drives
+        #[arg(long)]
+        all_drives: bool,
     },
     /// windows: Parse BITS
     Bits {
@@ -171,6 +186,15 @@ pub(crate) enum CommandArgs {
         /// Alternative BITS file to use
         #[arg(long, default_value = None)]
         alt_file: Option<String>,
+        /// Maximum number of carved BITS entries to return
+        #[arg(long, default_value = None)]
+        max_carved: Option<usize>,
+        /// Number of threads used to scan the BITS data when carving
+        #[arg(long, default_value = None)]
+        carve_threads: Option<usize>,
+        /// Size in bytes of each chunk scanned when carving. Rounded up to a page boundary
+        #[arg(long, default_value = None)]
+        carve_chunk_size: Option<usize>,
     },
     /// windows: Parse SRUM
     Srum {
@@ -195,12 +219,21 @@ pub(crate) enum CommandArgs {
         /// Alternative full path to Schedule Task file
         #[arg(long, default_value = None)]
         alt_file: Option<String>,
+        /// Alternative drive letter to use
+        #[arg(long, default_value = None)]
+        alt_drive: Option<char>,
+        /// Collect from all mounted NTFS drives
+        #[arg(long)]
+        all_drives: bool,
     },
     /// windows: Parse Windows Services
     Services {
         /// Alternative full path to SYSTEM Registry file
         #[arg(long, default_value = None)]
         alt_file: Option<String>,
+        /// Alternative drive letter to use
+        #[arg(long, default_value = None)]
+        alt_drive: Option<char>,
     },
     /// windows: Parse Jumplists
     Jumplists {
@@ -220,6 +253,18 @@ pub(crate) enum CommandArgs {
         #[arg(long, default_value = None)]
         alt_dir: Option<String>,
     },
+    /// windows: Merged program execution timeline
+    Execution {
+        /// Artifacts to check. Can be combination of: prefetch, amcache, userassist, bits, or srum. Default is all
+        #[arg(long, value_delimiter = ',')]
+        sources: Vec<String>,
+    },
+    /// windows: Parse autostart Registry locations
+    Autoruns {
+        /// Alternative drive letter to use
+        #[arg(long, default_value = None)]
+        alt_drive: Option<char>,
+    },
     /// macos: Parse ExecPolicy
     Execpolicy {
         /// Alternative file path
@@ -13,11 +13,11 @@ use core::{
             },
             processes::ProcessOptions,
             windows::{
-                AmcacheOptions, BitsOptions, EventLogsOptions, JumplistsOptions, PrefetchOptions,
-                RawFilesOptions, RecycleBinOptions, RegistryOptions, SearchOptions,
-                ServicesOptions, ShellbagsOptions, ShimcacheOptions, ShimdbOptions,
-                ShortcutOptions, SrumOptions, TasksOptions, UserAssistOptions, UsnJrnlOptions,
-                WindowsUserOptions, WmiPersistOptions,
+                AmcacheOptions, AutorunsOptions, BitsOptions, EventLogsOptions, ExecutionOptions,
+                JumplistsOptions, PrefetchOptions, RawFilesOptions, RecycleBinOptions,
+                RegistryOptions, SearchOptions, ServicesOptions, ShellbagsOptions,
+                ShimcacheOptions, ShimdbOptions, ShortcutOptions, SrumOptions, TasksOptions,
+                UserAssistOptions, UsnJrnlOptions, WindowsUserOptions, WmiPersistOptions,
             },
         },
         toml::{ArtemisToml, Artifacts, Output},
@@ -30,7 +30,7 @@ pub(crate) enum Commands {
     Acquire {
         #[command(subcommand)]
         artifact: Option<CommandArgs>,
-        /// Output format. JSON or JSONL.
+        /// Output format. JSON, JSONL, or PARQUET (requires the parquet feature).
         #[arg(long, default_value_t = String::from("JSON"))]
         format: String,
     },
@@ -49,6 +49,7 @@ pub(crate) fn run_collector(command: &Commands, output: Output) {
         system,
         output,
         artifacts: Vec::new(),
+        workers: None,
     };
     println!(
         "[artemis] Writing output to: {}",
@@ -79,6 +80,12 @@ fn setup_artifact(artifact: &CommandArgs) -> Artifacts {
     let mut collect = Artifacts {
         artifact_name: String::new(),
         filter: None,
+        timeout: None,
+        priority: None,
+        metadata_only: None,
+        fields: None,
+        label: None,
+        transforms: None,
         processes: None,
         files: None,
         unifiedlogs: None,
@@ -115,6 +122,8 @@ fn setup_artifact(artifact: &CommandArgs) -> Artifacts {
         jumplists: None,
         recyclebin: None,
         wmipersist: None,
+        execution: None,
+        autoruns: None,
     };
     match artifact {
         CommandArgs::Processes {
@@ -139,12 +148,14 @@ fn setup_artifact(artifact: &CommandArgs) -> Artifacts {
             metadata,
             start_path,
             depth,
+            follow_links,
             regex_filter,
         } => {
             let options = FileOptions {
                 md5: Some(*md5),
                 start_path: start_path.to_string(),
                 depth: Some(*depth),
+                follow_links: Some(*follow_links),
                 metadata: Some(*metadata),
                 sha1: Some(*sha1),
                 sha256: Some(*sha256),
@@ -272,10 +283,19 @@ fn setup_artifact(artifact: &CommandArgs) -> Artifacts {
             collect.amcache = Some(options);
             collect.artifact_name = String::from("amcache");
         }
-        CommandArgs::Bits { carve, alt_file } => {
+        CommandArgs::Bits {
+            carve,
+            alt_file,
+            max_carved,
+            carve_threads,
+            carve_chunk_size,
+        } => {
             let options = BitsOptions {
                 carve: *carve,
                 alt_file: alt_file.clone(),
+                max_carved: *max_carved,
+                carve_threads: *carve_threads,
+                carve_chunk_size: *carve_chunk_size,
             };
             collect.bits = Some(options);
             collect.artifact_name = String::from("bits");
@@ -283,6 +303,9 @@ fn setup_artifact(artifact: &CommandArgs) -> Artifacts {
         CommandArgs::Eventlogs { alt_file } => {
             let options = EventLogsOptions {
                 alt_file: alt_file.clone(),
+                alt_drive: None,
+                channels: None,
+                event_ids: None,
             };
             collect.eventlogs = Some(options);
             collect.artifact_name = String::from("eventlogs");
@@ -294,9 +317,15 @@ fn setup_artifact(artifact: &CommandArgs) -> Artifacts {
             collect.jumplists = Some(options);
             collect.artifact_name = String::from("jumplists");
         }
-        CommandArgs::Prefetch { alt_dir } => {
+        CommandArgs::Prefetch {
+            alt_dir,
+            entropy,
+            max_last_run_times,
+        } => {
             let options = PrefetchOptions {
                 alt_dir: alt_dir.clone(),
+                entropy: Some(*entropy),
+                max_last_run_times: *max_last_run_times,
             };
             collect.prefetch = Some(options);
             collect.artifact_name = String::from("prefetch");
@@ -357,9 +386,13 @@ fn setup_artifact(artifact: &CommandArgs) -> Artifacts {
             collect.search = Some(options);
             collect.artifact_name = String::from("search");
         }
-        CommandArgs::Services { alt_file } => {
+        CommandArgs::Services {
+            alt_file,
+            alt_drive,
+        } => {
             let options = ServicesOptions {
                 alt_file: alt_file.clone(),
+                alt_drive: *alt_drive,
             };
             collect.services = Some(options);
             collect.artifact_name = String::from("services");
@@ -367,10 +400,12 @@ fn setup_artifact(artifact: &CommandArgs) -> Artifacts {
         CommandArgs::Shellbags {
             resolve_guids,
             alt_file,
+            alt_drive,
         } => {
             let options = ShellbagsOptions {
                 resolve_guids: *resolve_guids,
                 alt_file: alt_file.clone(),
+                alt_drive: *alt_drive,
             };
             collect.shellbags = Some(options);
             collect.artifact_name = String::from("shellbags");
@@ -401,9 +436,16 @@ fn setup_artifact(artifact: &CommandArgs) -> Artifacts {
             collect.srum = Some(options);
             collect.artifact_name = String::from("srum");
         }
-        CommandArgs::Tasks { alt_file } => {
+        CommandArgs::Tasks {
+            alt_file,
+            alt_drive,
+            all_drives,
+        } => {
             let options = TasksOptions {
                 alt_file: alt_file.clone(),
+                alt_drive: *alt_drive,
+                all_drives: Some(*all_drives),
+                removable_drives: None,
             };
             collect.tasks = Some(options);
             collect.artifact_name = String::from("tasks");
@@ -429,10 +471,13 @@ fn setup_artifact(artifact: &CommandArgs) -> Artifacts {
         CommandArgs::Usnjrnl {
             alt_drive,
             alt_path,
+            all_drives,
         } => {
             let options = UsnJrnlOptions {
                 alt_drive: *alt_drive,
                 alt_path: alt_path.clone(),
+                all_drives: Some(*all_drives),
+                removable_drives: None,
             };
             collect.usnjrnl = Some(options);
             collect.artifact_name = String::from("usnjrnl");
@@ -444,6 +489,20 @@ fn setup_artifact(artifact: &CommandArgs) -> Artifacts {
             collect.wmipersist = Some(options);
             collect.artifact_name = String::from("wmipersist");
         }
+        CommandArgs::Execution { sources } => {
+            let options = ExecutionOptions {
+                sources: sources.clone(),
+            };
+            collect.execution = Some(options);
+            collect.artifact_name = String::from("execution");
+        }
+        CommandArgs::Autoruns { alt_drive } => {
+            let options = AutorunsOptions {
+                alt_drive: *alt_drive,
+            };
+            collect.autoruns = Some(options);
+            collect.artifact_name = String::from("autoruns");
+        }
     }
     collect
 }
@@ -469,11 +528,7 @@ mod tests {
             output: String::from("local"),
             format: String::from("json"),
             compress: false,
-            filter_name: None,
-            filter_script: None,
-            url: None,
-            api_key: None,
-            logging: None,
+            ..Default::default()
         };
 
         out
@@ -505,6 +560,7 @@ mod tests {
                 metadata: false,
                 start_path: String::from("/"),
                 depth: 1,
+                follow_links: false,
                 regex_filter: None,
             }),
             format: String::from("json"),
@@ -740,7 +796,11 @@ mod tests {
     #[test]
     fn test_run_collector_prefetch() {
         let command = Commands::Acquire {
-            artifact: Some(Prefetch { alt_dir: None }),
+            artifact: Some(Prefetch {
+                alt_dir: None,
+                entropy: false,
+                max_last_run_times: None,
+            }),
             format: String::from("json"),
         };
 
@@ -751,7 +811,10 @@ mod tests {
     #[test]
     fn test_run_collector_alts() {
         let command = Commands::Acquire {
-            artifact: Some(Services { alt_file: None }),
+            artifact: Some(Services {
+                alt_file: None,
+                alt_drive: None,
+            }),
             format: String::from("json"),
         };
 
@@ -791,7 +854,11 @@ mod tests {
         run_collector(&command, out);
 
         let command = Commands::Acquire {
-            artifact: Some(Tasks { alt_file: None }),
+            artifact: Some(Tasks {
+                alt_file: None,
+                alt_drive: None,
+                all_drives: false,
+            }),
             format: String::from("json"),
         };
 
@@ -813,6 +880,7 @@ mod tests {
             artifact: Some(Shellbags {
                 resolve_guids: false,
                 alt_file: None,
+                alt_drive: None,
             }),
             format: String::from("json"),
         };
@@ -838,6 +906,9 @@ mod tests {
             artifact: Some(Bits {
                 carve: false,
                 alt_file: None,
+                max_carved: None,
+                carve_threads: None,
+                carve_chunk_size: None,
             }),
             format: String::from("json"),
         };
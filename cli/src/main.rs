@@ -9,7 +9,7 @@ mod collector;
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
-    /// Full path to TOML collector
+    /// Full path to TOML collector. Use `-` to read from stdin
     #[clap(short, long, value_parser)]
     toml: Option<String>,
 
@@ -35,7 +35,24 @@ fn parse_args(args: &Args) {
     println!("[artemis] Starting artemis collection!");
 
     if let Some(toml) = &args.toml {
-        if !toml.is_empty() {
+        if toml == "-" {
+            let toml_results = core::core::read_config_from_reader(&mut std::io::stdin().lock());
+            let mut collection = match toml_results {
+                Ok(results) => results,
+                Err(err) => {
+                    println!("[artemis] Failed to read TOML collector from stdin: {err:?}");
+                    return;
+                }
+            };
+            let collection_results = core::core::artemis_collection(&mut collection);
+            match collection_results {
+                Ok(_) => info!("[artemis] Collection success"),
+                Err(err) => {
+                    println!("[artemis] Failed to collect artifacts: {err:?}");
+                    return;
+                }
+            }
+        } else if !toml.is_empty() {
             let collection_results = core::core::parse_toml_file(toml);
             match collection_results {
                 Ok(_) => info!("[artemis] Collection success"),
@@ -86,11 +103,7 @@ fn parse_args(args: &Args) {
             output: String::from("local"),
             format: String::from("json"),
             compress: false,
-            filter_name: None,
-            filter_script: None,
-            url: None,
-            api_key: None,
-            logging: None,
+            ..Default::default()
         };
         run_collector(command, out)
     } else {
@@ -207,6 +220,7 @@ mod tests {
                     metadata: false,
                     start_path: String::from("/"),
                     depth: 1,
+                    follow_links: false,
                     regex_filter: None,
                 }),
                 format: String::from("json"),
@@ -226,7 +240,7 @@ mod tests {
             javascript: None,
             command: Some(Commands::Acquire {
                 artifact: Some(Shortcuts {
-                    path: String::from("C:\\"),
+                    path: Some(String::from("C:\\")),
                 }),
                 format: String::from("json"),
             }),
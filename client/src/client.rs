@@ -1,4 +1,7 @@
-use crate::{enrollment::enroll::enroll_client, filesystem::config::read_config};
+use crate::{
+    enrollment::enroll::enroll_client, filesystem::config::read_config,
+    uploads::upload::upload_pending,
+};
 use log::error;
 
 #[tokio::main]
@@ -20,6 +23,14 @@ pub async fn start(path: &str) {
         );
         return;
     }
+
+    let upload_status = upload_pending(&config).await;
+    if upload_status.is_err() {
+        error!(
+            "[client] Could not upload stored collections: {:?}",
+            upload_status.unwrap_err()
+        );
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
 use super::error::FileSystemError;
 use log::error;
-use tokio::fs::create_dir_all;
+use tokio::fs::{create_dir_all, read_dir};
 
 /// Create a directory and all its parents
 pub(crate) async fn create_dirs(path: &str) -> Result<(), FileSystemError> {
@@ -16,12 +16,46 @@ pub(crate) async fn create_dirs(path: &str) -> Result<(), FileSystemError> {
     Ok(())
 }
 
+/// List the full paths of the entries in a directory
+pub(crate) async fn list_directory(path: &str) -> Result<Vec<String>, FileSystemError> {
+    let dir_result = read_dir(path).await;
+    let mut dir = match dir_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[client] Failed to read directory {path}: {err:?}");
+            return Err(FileSystemError::ReadDirectory);
+        }
+    };
+
+    let mut entries = Vec::new();
+    loop {
+        match dir.next_entry().await {
+            Ok(Some(entry)) => entries.push(entry.path().display().to_string()),
+            Ok(None) => break,
+            Err(err) => {
+                error!("[client] Failed to read entry in {path}: {err:?}");
+                return Err(FileSystemError::ReadDirectory);
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::filesystem::directory::create_dirs;
+    use crate::filesystem::directory::{create_dirs, list_directory};
 
     #[tokio::test]
     async fn test_create_dirs() {
         create_dirs(&"./tmp/atest").await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_list_directory() {
+        create_dirs("./tmp/alist/sub").await.unwrap();
+        let results = list_directory("./tmp/alist").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].ends_with("sub"));
+    }
 }
@@ -8,6 +8,8 @@ pub enum FileSystemError {
     CreateDirectory,
     CrateFile,
     WriteFile,
+    ReadDirectory,
+    RemoveFile,
 }
 
 impl fmt::Display for FileSystemError {
@@ -19,6 +21,8 @@ impl fmt::Display for FileSystemError {
             FileSystemError::CreateDirectory => write!(f, "Could not create directory"),
             FileSystemError::CrateFile => write!(f, "Could not create file"),
             FileSystemError::WriteFile => write!(f, "Could not write file"),
+            FileSystemError::ReadDirectory => write!(f, "Could not read directory"),
+            FileSystemError::RemoveFile => write!(f, "Could not remove file"),
         }
     }
 }
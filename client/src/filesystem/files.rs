@@ -2,7 +2,7 @@ use super::error::FileSystemError;
 use log::{error, info};
 use std::path::Path;
 use tokio::{
-    fs::{read, File},
+    fs::{read, remove_file as remove, File},
     io::AsyncWriteExt,
 };
 
@@ -55,9 +55,23 @@ pub(crate) async fn write_file(data: &[u8], path: &str) -> Result<(), FileSystem
     Ok(())
 }
 
+/// Remove a file
+pub(crate) async fn remove_file(path: &str) -> Result<(), FileSystemError> {
+    let status = remove(path).await;
+    if status.is_err() {
+        error!(
+            "[client] Failed to remove file {path}: {:?}",
+            status.unwrap_err()
+        );
+        return Err(FileSystemError::RemoveFile);
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::read_file;
+    use super::{read_file, remove_file};
     use crate::filesystem::files::write_file;
     use crate::filesystem::{directory::create_dirs, files::is_file};
     use std::path::PathBuf;
@@ -89,4 +103,12 @@ mod tests {
         let test = b"hello world!";
         write_file(test, "./tmp/test").await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_remove_file() {
+        create_dirs("./tmp").await.unwrap();
+        write_file(b"remove me", "./tmp/test_remove").await.unwrap();
+        remove_file("./tmp/test_remove").await.unwrap();
+        assert!(!is_file("./tmp/test_remove"));
+    }
 }
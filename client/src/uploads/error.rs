@@ -0,0 +1,22 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum UploadError {
+    Client,
+    Request,
+    BadResponse,
+    ContentHash,
+    Storage,
+}
+
+impl fmt::Display for UploadError {
+    fn fmt<'a>(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UploadError::Client => write!(f, "Failed to create upload client"),
+            UploadError::Request => write!(f, "Failed to send upload request"),
+            UploadError::BadResponse => write!(f, "Bad response for upload"),
+            UploadError::ContentHash => write!(f, "Server received upload with a different hash"),
+            UploadError::Storage => write!(f, "Failed to read client storage"),
+        }
+    }
+}
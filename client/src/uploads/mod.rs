@@ -0,0 +1,2 @@
+mod error;
+pub(crate) mod upload;
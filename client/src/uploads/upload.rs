@@ -0,0 +1,267 @@
+use super::error::UploadError;
+use crate::filesystem::{
+    directory::list_directory,
+    files::{is_file, read_file, remove_file},
+};
+use common::server::{config::ArtemisConfig, uploads::CONTENT_HASH_HEADER};
+use log::{error, info};
+use reqwest::{
+    header::USER_AGENT,
+    multipart::{Form, Part},
+    Client, ClientBuilder, StatusCode,
+};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/**
+ * Upload collection files waiting in the client storage directory
+ * Files are stored at `<storage>/artemis/storage/<collection_id>/<filename>` and removed once the server accepts them
+ * Failed uploads are kept and retried on the next run. The content hash lets the server skip files it already received
+ */
+pub(crate) async fn upload_pending(config: &ArtemisConfig) -> Result<(), UploadError> {
+    let storage = format!("{}/artemis/storage", config.endpoint_server.storage);
+    let collections = match list_directory(&storage).await {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[client] Could not read client storage {storage}: {err:?}");
+            return Err(UploadError::Storage);
+        }
+    };
+
+    // One client is shared by all uploads in this run so the connection pool is reused
+    let client = build_client(config.endpoint_server.verify_ssl)?;
+    let mut status = Ok(());
+    for collection in collections {
+        let collection_id = match Path::new(&collection)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse::<u64>().ok())
+        {
+            Some(result) => result,
+            // Only collection directories are uploaded
+            None => continue,
+        };
+
+        let files = list_directory(&collection).await.unwrap_or_default();
+        for path in files {
+            if !is_file(&path) {
+                continue;
+            }
+            let filename = Path::new(&path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default()
+                .to_string();
+            let data = match read_file(&path).await {
+                Ok(result) => result,
+                Err(err) => {
+                    error!("[client] Could not read collection file {path}: {err:?}");
+                    status = Err(UploadError::Storage);
+                    continue;
+                }
+            };
+
+            if let Err(err) =
+                upload_collection(&client, config, &collection_id, &filename, &data).await
+            {
+                error!("[client] Could not upload {path}. Will retry on next run: {err:?}");
+                status = Err(err);
+                continue;
+            }
+            info!("[client] Uploaded {path}");
+            let _ = remove_file(&path).await;
+        }
+    }
+    status
+}
+
+/**
+ * Upload a collection file to the server
+ * The SHA256 hash of the file is sent in the `x-artemis-content-hash` header. The server skips files it already received so retries are safe
+ * The server rejects the upload with `422` if the file was corrupted in transit
+ */
+pub(crate) async fn upload_collection(
+    client: &Client,
+    config: &ArtemisConfig,
+    collection_id: &u64,
+    filename: &str,
+    data: &[u8],
+) -> Result<(), UploadError> {
+    let builder = client.post(format!(
+        "{}:{}/endpoint/v{}/upload",
+        config.endpoint_server.address, config.endpoint_server.port, config.endpoint_server.version
+    ));
+
+    let part_result = Part::bytes(data.to_vec())
+        .file_name(filename.to_string())
+        .mime_str("application/octet-stream");
+    let part = match part_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[client] Could not create upload part for {filename}: {err:?}");
+            return Err(UploadError::Request);
+        }
+    };
+    let form = Form::new()
+        .text("endpoint-id", config.endpoint_id.clone())
+        .text("collection-id", collection_id.to_string())
+        .part("collection", part);
+
+    let res_result = builder
+        .header(
+            USER_AGENT,
+            format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+        )
+        .header(CONTENT_HASH_HEADER, content_hash(data))
+        .multipart(form)
+        .send()
+        .await;
+    let response = match res_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[client] Could not send upload request: {err:?}");
+            return Err(UploadError::Request);
+        }
+    };
+
+    if response.status() == StatusCode::UNPROCESSABLE_ENTITY {
+        error!("[client] Server rejected upload {filename}. Content hash did not match");
+        return Err(UploadError::ContentHash);
+    }
+    if response.status() != StatusCode::OK {
+        error!(
+            "[client] Got non-200 response for upload: {}",
+            response.status()
+        );
+        return Err(UploadError::BadResponse);
+    }
+
+    Ok(())
+}
+
+/// Create a new upload client
+fn build_client(verify_ssl: bool) -> Result<Client, UploadError> {
+    let client_result = ClientBuilder::new()
+        .danger_accept_invalid_certs(!verify_ssl)
+        .build();
+    match client_result {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("[client] Could not create upload client: {err:?}");
+            Err(UploadError::Client)
+        }
+    }
+}
+
+/// Get the hex encoded SHA256 hash of the collection file
+pub(crate) fn content_hash(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_client, content_hash, upload_collection, upload_pending};
+    use crate::filesystem::{config::read_config, files::is_file};
+    use crate::uploads::error::UploadError;
+    use common::server::uploads::CONTENT_HASH_HEADER;
+    use httpmock::{Method::POST, MockServer};
+    use std::{
+        fs::{create_dir_all, write},
+        path::PathBuf,
+    };
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(
+            content_hash(b"test"),
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_collection() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/client.toml");
+
+        let mut config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        let server = MockServer::start();
+        config.endpoint_server.port = server.port();
+
+        let mock_me = server.mock(|when, then| {
+            when.method(POST)
+                .path("/endpoint/v1/upload")
+                .header(CONTENT_HASH_HEADER, content_hash(b"data"))
+                .header_exists("content-type")
+                .body_contains("name=\"collection-id\"\r\n\r\n1\r\n")
+                .body_contains("filename=\"test\\\"quoted.jsonl.gz\"");
+            then.status(200);
+        });
+        let client = build_client(config.endpoint_server.verify_ssl).unwrap();
+        upload_collection(&client, &config, &1, "test\"quoted.jsonl.gz", b"data")
+            .await
+            .unwrap();
+        mock_me.assert();
+    }
+
+    #[tokio::test]
+    async fn test_upload_pending() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/client.toml");
+
+        let mut config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+        config.endpoint_server.storage = String::from("./tmp/upload_pending");
+
+        let server = MockServer::start();
+        config.endpoint_server.port = server.port();
+
+        let collection = "./tmp/upload_pending/artemis/storage/10";
+        create_dir_all(collection).unwrap();
+        let path = format!("{collection}/test.jsonl");
+        write(&path, b"data").unwrap();
+
+        let mock_me = server.mock(|when, then| {
+            when.method(POST)
+                .path("/endpoint/v1/upload")
+                .header(CONTENT_HASH_HEADER, content_hash(b"data"));
+            then.status(200);
+        });
+        upload_pending(&config).await.unwrap();
+        mock_me.assert();
+        assert!(!is_file(&path));
+    }
+
+    #[test]
+    fn test_build_client() {
+        assert!(build_client(true).is_ok());
+        assert!(build_client(false).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_upload_collection_hash_mismatch() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/client.toml");
+
+        let mut config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        let server = MockServer::start();
+        config.endpoint_server.port = server.port();
+
+        let mock_me = server.mock(|when, then| {
+            when.method(POST).path("/endpoint/v1/upload");
+            then.status(422);
+        });
+        let client = build_client(config.endpoint_server.verify_ssl).unwrap();
+        let result = upload_collection(&client, &config, &1, "test.jsonl.gz", b"data").await;
+        assert!(matches!(result, Err(UploadError::ContentHash)));
+        mock_me.assert();
+    }
+}
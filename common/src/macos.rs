@@ -273,6 +273,16 @@ pub struct FsEvents {
 pub struct LaunchdPlist {
     pub launchd_data: Dictionary,
     pub plist_path: String,
+    /**Unique name of the daemon or agent */
+    pub label: String,
+    /**Path to the executable. Empty if only `ProgramArguments` is set */
+    pub program: String,
+    /**Executable and arguments to run */
+    pub program_arguments: Vec<String>,
+    /**Run when the daemon or agent is loaded */
+    pub run_at_load: bool,
+    /**Keep running or restart on conditions. A `KeepAlive` dictionary of conditions is true */
+    pub keep_alive: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -23,4 +23,12 @@ pub struct EndpointServer {
     pub storage: String,
     pub verify_ssl: bool,
     pub version: u8,
+    /**Accept IPv4 clients on an IPv6 `address` as IPv4-mapped addresses. Ex: `address = "::"`. Default is false */
+    pub dual_stack: Option<bool>,
+    /**Max endpoints sent a command at the same time when broadcasting. Default is 32 */
+    pub broadcast_concurrency: Option<usize>,
+    /**Seconds to wait when sending a broadcast command to a single endpoint. Default is 5 */
+    pub broadcast_timeout_secs: Option<u64>,
+    /**Path the Prometheus metrics are served at. Default is `/metrics` */
+    pub metrics_path: Option<String>,
 }
@@ -55,6 +55,39 @@ pub enum JobType {
     Unknown,
 }
 
+/**Commands sent from the server to a connected endpoint */
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub enum EndpointCommand {
+    /**Run the provided TOML collection */
+    RunCollection { collection: String },
+}
+
+/**Endpoints a broadcast command was sent to */
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct BroadcastResult {
+    pub succeeded: Vec<String>,
+    /**Endpoints that disconnected before the command was sent */
+    pub failed: Vec<String>,
+    /**Endpoints that did not accept the command before the timeout */
+    pub timed_out: Vec<String>,
+}
+
+/**Progress events emitted while a collection is running */
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub enum CollectionEvent {
+    ArtifactStarted { name: String },
+    RecordsEmitted { name: String, count: usize },
+    ArtifactFinished { name: String },
+    ArtifactFailed { name: String, error: String },
+}
+
+/**Collection event sent from an endpoint to the server */
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct CollectionEventMessage {
+    pub endpoint_id: String,
+    pub collection_event: CollectionEvent,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct JobMetadata {
     pub endpoint_id: String,
@@ -2,4 +2,5 @@ pub mod config;
 pub mod enrollment;
 pub mod heartbeat;
 pub mod jobs;
+pub mod uploads;
 pub mod webui;
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/**
+ * Header containing the hex encoded SHA256 hash of an uploaded collection file
+ * The server rejects the upload with `422` if the received file does not match. Files already received are skipped
+ */
+pub const CONTENT_HASH_HEADER: &str = "x-artemis-content-hash";
+
+/**Entry in the `manifest.json` file stored in each collection directory */
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ManifestEntry {
+    /**SHA256 hash of the received file */
+    pub sha256: String,
+    pub size: u64,
+    /**Hash matched the `x-artemis-content-hash` header sent by the endpoint */
+    pub verified: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReparseRequest {
+    /**Artifact to parse from the raw files stored in the collection. Ex: `prefetch` */
+    pub artifact: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReparseResult {
+    pub artifact: String,
+    /**Version of artemis that parsed the raw files */
+    pub version: String,
+    /**Output files relative to the collection directory */
+    pub files: Vec<String>,
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
 pub enum EndpointOS {
@@ -43,3 +44,53 @@ pub struct EndpointList {
     pub ip: String,
     pub artemis_version: String,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct TomlValidation {
+    /**Artifact names found in the collection TOML */
+    pub artifacts: Vec<String>,
+    /**Validation errors. Empty if the TOML is valid */
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ServerEvent {
+    /**When the server recorded the event */
+    pub timestamp: u64,
+    pub level: LogLevel,
+    /**Endpoint ID or `server` */
+    pub source: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ArtifactRequest {
+    pub endpoint_id: String,
+    pub collection_id: String,
+    pub filename: String,
+    pub offset: usize,
+    pub count: usize,
+    /**Field to filter on. Empty searches the whole record */
+    pub field: String,
+    /**Case insensitive value the field must contain. Empty returns all records */
+    pub filter: String,
+    /**Column to sort by. Empty keeps the file order */
+    pub sort: String,
+    pub ascending: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ArtifactRecords {
+    /**Number of records matching the filter */
+    pub total: usize,
+    /**Fields found in the returned records */
+    pub columns: Vec<String>,
+    pub records: Vec<Value>,
+}
@@ -1,4 +1,4 @@
-use serde::{Deserialize, Serialize};
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
 use serde_json::Value;
 use std::collections::{BTreeMap, HashMap};
 
@@ -94,6 +94,10 @@ pub struct WindowsBits {
     pub bits: Vec<BitsInfo>,
     pub carved_jobs: Vec<JobInfo>,
     pub carved_files: Vec<FileInfo>,
+    /**Some ESE tables could not be parsed. BITS entries may be missing file info */
+    pub partial: bool,
+    /**Local users were available to resolve account SIDs. Only well-known SIDs are resolved if false */
+    pub users_resolved: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -112,6 +116,12 @@ pub struct BitsInfo {
     pub job_description: String,
     pub job_command: String,
     pub job_arguments: String,
+    /**Program BITS runs when the job completes or errors (`SetNotifyCmdLine`) */
+    pub notify_command: String,
+    /**Arguments for the notification program */
+    pub notify_args: String,
+    /**Custom HTTP headers sent with the transfer */
+    pub custom_headers: Vec<String>,
     pub error_count: u32,
     pub job_type: JobType,
     pub job_state: JobState,
@@ -127,9 +137,15 @@ pub struct BitsInfo {
     pub carved: bool,
     pub transient_error_count: u32,
     pub acls: Vec<AccessControlEntry>,
+    /**Base64 encoded raw ACL data */
+    pub acls_raw: String,
     pub timeout: u32,
     pub retry_delay: u32,
     pub additional_sids: Vec<String>,
+    pub source_format: BitsFormat,
+    /**Raw bytes of the ESE `Jobs` row blob. Only included in output if `include_raw` is enabled */
+    #[serde(skip)]
+    pub raw: Vec<u8>,
 }
 
 #[derive(Debug, Serialize)]
@@ -159,6 +175,12 @@ pub struct JobInfo {
     pub job_description: String,
     pub job_command: String,
     pub job_arguments: String,
+    /**Program BITS runs when the job completes or errors (`SetNotifyCmdLine`) */
+    pub notify_command: String,
+    /**Arguments for the notification program */
+    pub notify_args: String,
+    /**Custom HTTP headers sent with the transfer */
+    pub custom_headers: Vec<String>,
     pub error_count: u32,
     pub transient_error_count: u32,
     pub job_type: JobType,
@@ -167,6 +189,8 @@ pub struct JobInfo {
     pub flags: JobFlags,
     pub http_method: String,
     pub acls: Vec<AccessControlEntry>,
+    /**Base64 encoded raw ACL data */
+    pub acls_raw: String,
     pub additional_sids: Vec<String>,
     pub timeout: u32,
     pub retry_delay: u32,
@@ -174,6 +198,13 @@ pub struct JobInfo {
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize)]
+pub enum BitsFormat {
+    Ese,
+    Legacy,
+}
+
+/**BITS job state. Serialized as `{code, name}` */
+#[derive(Debug, PartialEq, Clone)]
 pub enum JobState {
     Queued,
     Connecting,
@@ -184,27 +215,112 @@ pub enum JobState {
     Transferred,
     Acknowledged,
     Cancelled,
-    Unknown,
-}
-
-#[derive(Debug, PartialEq, Clone, Serialize)]
+    Unknown(u32),
+}
+
+impl JobState {
+    /// Raw `BG_JOB_STATE` value
+    pub fn code(&self) -> u32 {
+        match self {
+            JobState::Queued => 0,
+            JobState::Connecting => 1,
+            JobState::Transferring => 2,
+            JobState::Suspended => 3,
+            JobState::Error => 4,
+            JobState::TransientError => 5,
+            JobState::Transferred => 6,
+            JobState::Acknowledged => 7,
+            JobState::Cancelled => 8,
+            JobState::Unknown(code) => *code,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            JobState::Unknown(code) => format!("Unknown({code})"),
+            _ => format!("{self:?}"),
+        }
+    }
+}
+
+impl Serialize for JobState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_code(serializer, self.code(), &self.name())
+    }
+}
+
+/**BITS job priority. Serialized as `{code, name}` */
+#[derive(Debug, PartialEq, Clone)]
 pub enum JobPriority {
     Foreground,
     High,
     Normal,
     Low,
-    Unknown,
+    Unknown(u32),
 }
 
-#[derive(Debug, PartialEq, Clone, Serialize)]
+impl JobPriority {
+    /// Raw `BG_JOB_PRIORITY` value
+    pub fn code(&self) -> u32 {
+        match self {
+            JobPriority::Foreground => 0,
+            JobPriority::High => 1,
+            JobPriority::Normal => 2,
+            JobPriority::Low => 3,
+            JobPriority::Unknown(code) => *code,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            JobPriority::Unknown(code) => format!("Unknown({code})"),
+            _ => format!("{self:?}"),
+        }
+    }
+}
+
+impl Serialize for JobPriority {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_code(serializer, self.code(), &self.name())
+    }
+}
+
+/**BITS job type. Serialized as `{code, name}` */
+#[derive(Debug, PartialEq, Clone)]
 pub enum JobType {
     Download,
     Upload,
     UploadReply,
-    Unknown,
+    Unknown(u32),
 }
 
-#[derive(Debug, PartialEq, Clone, Serialize)]
+impl JobType {
+    /// Raw `BG_JOB_TYPE` value
+    pub fn code(&self) -> u32 {
+        match self {
+            JobType::Download => 0,
+            JobType::Upload => 1,
+            JobType::UploadReply => 2,
+            JobType::Unknown(code) => *code,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            JobType::Unknown(code) => format!("Unknown({code})"),
+            _ => format!("{self:?}"),
+        }
+    }
+}
+
+impl Serialize for JobType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_code(serializer, self.code(), &self.name())
+    }
+}
+
+/**BITS job notification flags. Serialized as `{code, name}` */
+#[derive(Debug, PartialEq, Clone)]
 pub enum JobFlags {
     Transferred,
     Error,
@@ -215,7 +331,46 @@ pub enum JobFlags {
     TransferredBackgroundErrorDisable,
     Modification,
     FileTransferred,
-    Unknown,
+    Unknown(u32),
+}
+
+impl JobFlags {
+    /// Raw `BG_NOTIFY` flags value
+    pub fn code(&self) -> u32 {
+        match self {
+            JobFlags::Transferred => 1,
+            JobFlags::Error => 2,
+            JobFlags::TransferredBackgroundError => 3,
+            JobFlags::Disable => 4,
+            JobFlags::TransferredBackgroundDisable => 5,
+            JobFlags::ErrorBackgroundDisable => 6,
+            JobFlags::TransferredBackgroundErrorDisable => 7,
+            JobFlags::Modification => 8,
+            JobFlags::FileTransferred => 16,
+            JobFlags::Unknown(code) => *code,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            JobFlags::Unknown(code) => format!("Unknown({code})"),
+            _ => format!("{self:?}"),
+        }
+    }
+}
+
+impl Serialize for JobFlags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_code(serializer, self.code(), &self.name())
+    }
+}
+
+/// Serialize a BITS value as the raw code and its name
+fn serialize_code<S: Serializer>(serializer: S, code: u32, name: &str) -> Result<S::Ok, S::Error> {
+    let mut value = serializer.serialize_struct("BitsCode", 2)?;
+    value.serialize_field("code", &code)?;
+    value.serialize_field("name", name)?;
+    value.end()
 }
 
 #[derive(Debug, PartialEq, Serialize, Clone)]
@@ -368,6 +523,11 @@ pub enum ColumnType {
 pub struct EventLogRecord {
     pub event_record_id: u64,
     pub timestamp: i64,
+    /**Provider name from the `System` section */
+    pub provider: String,
+    pub event_id: u64,
+    pub level: u64,
+    pub channel: String,
     pub data: Value,
 }
 
@@ -718,6 +878,8 @@ pub struct Prefetch {
     pub path: String,
     pub filename: String,
     pub hash: String,
+    /// Format version from the file header. 23 (Win7), 26 (Win8), or 30 (Win10+)
+    pub version: u32,
     pub last_run_time: i64,
     pub all_run_times: Vec<i64>,
     pub run_count: u32,
@@ -729,6 +891,56 @@ pub struct Prefetch {
     pub accessed_directories_count: u32,
     pub accessed_files: Vec<String>,
     pub accessed_directories: Vec<String>,
+    pub file_metrics: Vec<FileMetric>,
+    /// Prefetch contained malformed or manipulated directory entries
+    pub suspicious: bool,
+    pub anomalies: Vec<String>,
+    /// Executable has high entropy and may be packed. Only checked if enabled
+    pub high_entropy: bool,
+    /// Raw bytes of each volume information entry. Only included in output if `include_raw` is enabled
+    #[serde(skip)]
+    pub raw_volumes: Vec<Vec<u8>>,
+    /// Original FILETIME of `last_run_time`. Only included in output if `keep_raw_filetime` is enabled
+    #[serde(skip)]
+    pub last_run_filetime: u64,
+    /// Original FILETIMEs of `all_run_times`. Only included in output if `keep_raw_filetime` is enabled
+    #[serde(skip)]
+    pub all_run_filetimes: Vec<u64>,
+    /// Original FILETIMEs of `volume_creation`. Only included in output if `keep_raw_filetime` is enabled
+    #[serde(skip)]
+    pub volume_creation_filetime: Vec<u64>,
+}
+
+/// State of the `Prefetch` directory when collected
+#[derive(Debug, PartialEq, Serialize)]
+pub enum PrefetchState {
+    /// Directory contains prefetch files
+    Parsed,
+    /// Directory exists but contains no prefetch files. Prefetch may be disabled or the files were deleted
+    Empty,
+    /// Directory does not exist
+    Missing,
+}
+
+/// Context for a `Prefetch` collection. Useful to explain why no prefetch entries were found
+#[derive(Debug, Serialize)]
+pub struct PrefetchStatus {
+    pub path: String,
+    pub state: PrefetchState,
+    /// Number of prefetch files parsed
+    pub files: usize,
+    /// `EnablePrefetcher` value from the SYSTEM Registry file. 0 is disabled. Only read when collecting from the systemdrive
+    pub enable_prefetcher: Option<u32>,
+    /// Prefetch directory is empty or the `EnablePrefetcher` value is disabled
+    pub disabled: bool,
+}
+
+/// File accessed by the executable and its NTFS file reference
+#[derive(Debug, Serialize)]
+pub struct FileMetric {
+    pub filename: String,
+    pub mft_entry: u64,
+    pub mft_sequence: u16,
 }
 
 #[derive(Debug, Serialize)]
@@ -785,6 +997,8 @@ pub struct ServicesData {
     pub required_privileges: Vec<String>,
     pub error_control: ServiceError,
     pub reg_path: String,
+    /**Image path or service DLL is in a user writable or temporary directory */
+    pub user_writable_path: bool,
 }
 
 #[derive(Debug, PartialEq, Serialize)]
@@ -857,6 +1071,25 @@ pub struct ShimData {
     pub indexes: Vec<TagData>,
     pub db_data: DatabaseData,
     pub sdb_path: String,
+    /**Shims, patches, flags, and layers applied to executables in the database */
+    pub shim_entries: Vec<ShimEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShimEntry {
+    /**Name of the executable the fix is applied to */
+    pub exe_name: String,
+    pub app_name: String,
+    pub vendor: String,
+    pub exe_id: String,
+    /**Files that must exist for the fix to be applied */
+    pub matching_files: Vec<String>,
+    /**SHIM, PATCH, FLAG, or LAYER */
+    pub fix_type: String,
+    pub fix_name: String,
+    pub command_line: String,
+    /**DLL that implements the shim. Found in the database LIBRARY */
+    pub dll_file: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -1039,6 +1272,8 @@ pub struct TaskXml {
     pub data: Option<String>,
     pub principals: Option<Vec<Principals>>,
     pub actions: Actions,
+    /**Triggers normalized into a common schedule representation */
+    pub schedules: Vec<TaskSchedule>,
     pub path: String,
 }
 
@@ -1302,6 +1537,8 @@ pub struct TaskJob {
     pub user_data: String,
     pub start_error: u32,
     pub triggers: Vec<VarTriggers>,
+    /**Triggers normalized into a common schedule representation */
+    pub schedules: Vec<TaskSchedule>,
     pub path: String,
 }
 
@@ -1379,11 +1616,78 @@ pub enum TriggerTypes {
     EventAtLogon,
 }
 
+/// Common representation of XML and Job `Task` triggers
+#[derive(Debug, PartialEq, Serialize)]
+pub struct TaskSchedule {
+    pub schedule_type: ScheduleType,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    /**Interval between runs as an ISO8601 duration */
+    pub interval: Option<String>,
+    pub delay: Option<String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub enum ScheduleType {
+    Once,
+    Daily,
+    Weekly,
+    Monthly,
+    MonthlyDayOfWeek,
+    Boot,
+    Registration,
+    Idle,
+    Event,
+    Logon,
+    Session,
+    Wnf,
+    Unknown,
+}
+
+/// Program execution evidence normalized from `Prefetch`, `Amcache`, `UserAssist`, `BITS`, or `SRUM`
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ExecutionEvent {
+    pub path: String,
+    pub timestamp: i64,
+    pub source_artifact: ExecutionSource,
+    /// Only tracked by `Prefetch` and `UserAssist`
+    pub run_count: Option<u32>,
+    /// Only tracked by `BITS` and `SRUM`
+    pub sid: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum ExecutionSource {
+    Prefetch,
+    Amcache,
+    UserAssist,
+    Bits,
+    Srum,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AutorunEntry {
+    /// Curated autostart location. Ex: `HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Run`
+    pub location: String,
+    pub value_name: String,
+    pub command: String,
+    pub data_type: String,
+    /// Last write time of the Registry key containing the value
+    pub last_modified: i64,
+    pub reg_path: String,
+    pub reg_file: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct UserAssistEntry {
     pub path: String,
     pub last_execution: i64,
     pub count: u32,
+    /// Number of times the program had focus. Not tracked by the Windows XP layout
+    pub focus_count: u32,
+    /// Total focus time in milliseconds. Not tracked by the Windows XP layout
+    pub focus_time: u32,
     pub reg_path: String,
     pub rot_path: String,
     pub folder_path: String,
@@ -1,11 +1,20 @@
 /**
  * Inspired by https://deno.com/blog/roll-your-own-javascript-runtime-pt3
  */
+#[cfg(feature = "scripting")]
 use deno_core::{include_js_files, snapshot::CreateSnapshotOptions, Extension};
+#[cfg(feature = "scripting")]
 use std::{env, fs::File, io::Write, path::PathBuf};
 
-/// Create a SnapShot at build time to help speed up our JavaScript Runtime
 fn main() {
+    // The JavaScript Runtime is only included with the `scripting` feature
+    #[cfg(feature = "scripting")]
+    create_snapshot();
+}
+
+/// Create a SnapShot at build time to help speed up our JavaScript Runtime
+#[cfg(feature = "scripting")]
+fn create_snapshot() {
     let extensions = Extension {
         esm_files: include_js_files!(artemis 
         "javascript/console.js",
@@ -12,6 +12,9 @@ class Encoding {
     extract_utf16_string = (data) => {
         return core.ops.js_extract_utf16_string(data);
     };
+    extract_string_auto = (data) => {
+        return core.ops.js_extract_string_auto(data);
+    };
     bytes_encode = (data) => {
         return core.ops.js_encode_bytes(data);
     };
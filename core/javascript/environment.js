@@ -4,7 +4,7 @@ class Environment {
         return core.ops.js_env();
     };
     environmentValue = (key) => {
-        return core.ops.js_env_value(key);
+        return core.ops.environment_value(key);
     };
 }
 export const environment = new Environment();
@@ -2,9 +2,10 @@ use crate::artifacts::os::macos::plist::{
     error::PlistError,
     property_list::{get_boolean, get_data, get_signed_int, get_string, parse_plist_file_dict},
 };
+use crate::utils::time::systemtime_to_unixepoch;
 use log::warn;
 use plist::{Dictionary, Value};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::SystemTime;
 
 #[derive(Debug)]
 pub(crate) struct DownloadsPlist {
@@ -112,15 +113,12 @@ impl DownloadsPlist {
         };
 
         let date_time: SystemTime = date_added.into();
-        let epoch_time_results = date_time.duration_since(UNIX_EPOCH);
-        let epoch_time = match epoch_time_results {
-            Ok(results) => results,
-            Err(err) => {
-                warn!("Failed to parse Timestamp in PLIST file: {err:?}");
-                return 0;
-            }
-        };
-        epoch_time.as_secs()
+        let epoch_time = systemtime_to_unixepoch(&date_time);
+        if epoch_time < 0 {
+            warn!("Timestamp in PLIST file is before 1970: {epoch_time}");
+            return 0;
+        }
+        epoch_time as u64
     }
 }
 
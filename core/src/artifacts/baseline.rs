@@ -0,0 +1,224 @@
+use crate::{
+    artifacts::error::CollectionError, filesystem::files::read_file, utils::stable_id::stable_id,
+};
+use log::error;
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+
+/**
+ * Get the fields used to compare an artifact record against the baseline
+ * Artifacts without a mapped key compare the whole record. Changed records are then reported as removed and added
+ */
+pub(crate) fn baseline_key(output_name: &str) -> &'static [&'static str] {
+    match output_name {
+        "prefetch" => &["path"],
+        "amcache" => &["path"],
+        "shimcache" => &["path", "key_path"],
+        "userassist" => &["path", "reg_path"],
+        "shortcuts" => &["source_path"],
+        "recyclebin" => &["recycle_path"],
+        "shellbags" => &["path", "reg_path"],
+        "services" => &["name"],
+        "users" => &["sid"],
+        "bits" => &["job_id", "file_id"],
+        "processes" => &["full_path", "arguments"],
+        "files" => &["full_path"],
+        _ => &[],
+    }
+}
+
+/**
+ * Compare artifact data against the baseline JSON file at `path`
+ * The baseline may be `json` output from a previous collection, an object keyed by artifact name, or an array of records
+ */
+pub(crate) fn compare_baseline(
+    serde_data: &Value,
+    output_name: &str,
+    path: &str,
+) -> Result<Value, CollectionError> {
+    let data_result = read_file(path);
+    let data = match data_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Could not read baseline {path}: {err:?}");
+            return Err(CollectionError::Baseline);
+        }
+    };
+
+    let baseline_result = serde_json::from_slice(&data);
+    let baseline: Value = match baseline_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Could not parse baseline {path}: {err:?}");
+            return Err(CollectionError::Baseline);
+        }
+    };
+
+    let artifact_baseline = match &baseline {
+        Value::Object(entries) if entries.contains_key("data") => &baseline["data"],
+        Value::Object(entries) => entries.get(output_name).unwrap_or(&Value::Null),
+        _ => &baseline,
+    };
+
+    Ok(diff_baseline(artifact_baseline, serde_data, output_name))
+}
+
+/**
+ * Diff current artifact records against baseline records using the artifact `baseline_key`
+ * Each returned record has a `change` category of `added`, `removed`, or `changed`. Unchanged records are dropped
+ */
+pub(crate) fn diff_baseline(baseline: &Value, current: &Value, output_name: &str) -> Value {
+    let fields = baseline_key(output_name);
+    let baseline_entries = artifact_records(baseline);
+
+    let mut baseline_ids: HashMap<String, VecDeque<usize>> = HashMap::new();
+    for (index, record) in baseline_entries.iter().enumerate() {
+        baseline_ids
+            .entry(stable_id(record, fields))
+            .or_default()
+            .push_back(index);
+    }
+
+    let mut matched = vec![false; baseline_entries.len()];
+    let mut changes = Vec::new();
+    for record in artifact_records(current) {
+        let id = stable_id(record, fields);
+        let previous = baseline_ids
+            .get_mut(&id)
+            .and_then(|indexes| indexes.pop_front());
+
+        match previous {
+            Some(index) => {
+                matched[index] = true;
+                if baseline_entries[index] != record {
+                    changes.push(json!({
+                        "change": "changed",
+                        "id": id,
+                        "record": record,
+                        "baseline": baseline_entries[index],
+                    }));
+                }
+            }
+            None => changes.push(json!({
+                "change": "added",
+                "id": id,
+                "record": record,
+            })),
+        }
+    }
+
+    for (index, record) in baseline_entries.iter().enumerate() {
+        if matched[index] {
+            continue;
+        }
+        changes.push(json!({
+            "change": "removed",
+            "id": stable_id(record, fields),
+            "record": record,
+        }));
+    }
+
+    Value::Array(changes)
+}
+
+/// Get the records of an artifact. Arrays nested one level in an object (ex: `bits` and `carved_jobs`) are also included
+fn artifact_records(serde_data: &Value) -> Vec<&Value> {
+    match serde_data {
+        Value::Array(records) => records.iter().collect(),
+        Value::Object(entries) => entries
+            .values()
+            .filter_map(Value::as_array)
+            .flatten()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{baseline_key, compare_baseline, diff_baseline};
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_baseline_key() {
+        assert_eq!(baseline_key("prefetch"), ["path"]);
+        assert!(baseline_key("unknown").is_empty());
+    }
+
+    #[test]
+    fn test_diff_baseline() {
+        let baseline = json!([
+            {"path": "C:\\Windows\\notepad.exe", "run_count": 1},
+            {"path": "C:\\Windows\\calc.exe", "run_count": 4},
+            {"path": "C:\\Windows\\cmd.exe", "run_count": 10},
+        ]);
+        let current = json!([
+            {"path": "C:\\Windows\\notepad.exe", "run_count": 2},
+            {"path": "C:\\Windows\\cmd.exe", "run_count": 10},
+            {"path": "C:\\Temp\\evil.exe", "run_count": 1},
+        ]);
+
+        let result = diff_baseline(&baseline, &current, "prefetch");
+        let changes = result.as_array().unwrap();
+        assert_eq!(changes.len(), 3);
+
+        assert_eq!(changes[0]["change"], "changed");
+        assert_eq!(changes[0]["record"]["run_count"], 2);
+        assert_eq!(changes[0]["baseline"]["run_count"], 1);
+
+        assert_eq!(changes[1]["change"], "added");
+        assert_eq!(changes[1]["record"]["path"], "C:\\Temp\\evil.exe");
+
+        assert_eq!(changes[2]["change"], "removed");
+        assert_eq!(changes[2]["record"]["path"], "C:\\Windows\\calc.exe");
+    }
+
+    #[test]
+    fn test_diff_baseline_nested() {
+        let baseline = json!({"bits": [{"job_id": "1", "file_id": "a", "state": "Queued"}], "carved_jobs": []});
+        let current = json!({"bits": [{"job_id": "1", "file_id": "a", "state": "Transferred"}], "carved_jobs": [{"job_id": "2", "file_id": "b"}]});
+
+        let result = diff_baseline(&baseline, &current, "bits");
+        let changes = result.as_array().unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0]["change"], "changed");
+        assert_eq!(changes[1]["change"], "added");
+    }
+
+    #[test]
+    fn test_diff_baseline_unmapped() {
+        let baseline = json!([{"name": "a", "value": 1}]);
+        let current = json!([{"name": "a", "value": 2}]);
+
+        let result = diff_baseline(&baseline, &current, "unknown");
+        let changes = result.as_array().unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0]["change"], "added");
+        assert_eq!(changes[1]["change"], "removed");
+    }
+
+    #[test]
+    fn test_compare_baseline() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/baseline/prefetch.json");
+
+        let current = json!([
+            {"path": "C:\\Windows\\notepad.exe", "run_count": 2},
+            {"path": "C:\\Windows\\cmd.exe", "run_count": 10},
+        ]);
+        let result =
+            compare_baseline(&current, "prefetch", &test_location.display().to_string()).unwrap();
+        let changes = result.as_array().unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0]["change"], "changed");
+        assert_eq!(changes[1]["change"], "removed");
+        assert_eq!(changes[1]["record"]["path"], "C:\\Windows\\calc.exe");
+    }
+
+    #[test]
+    fn test_compare_baseline_missing() {
+        let result = compare_baseline(&json!([]), "prefetch", "./does/not/exist.json");
+        assert!(result.is_err());
+    }
+}
@@ -4,6 +4,7 @@ use super::{
         safari_history,
     },
     error::CollectionError,
+    metadata_only::{collect_metadata, collect_oversize},
     os::{
         files::artifact::filelisting,
         linux::artifacts::{journals, logons, sudo_logs_linux},
@@ -14,658 +15,84 @@ use super::{
         processes::artifact::processes,
         systeminfo::artifact::systeminfo,
         unix::artifacts::{bash_history, cron_job, python_history, zsh_history},
-        windows::artifacts::{
-            amcache, bits, eventlogs, jumplists, prefetch, raw_filelist, recycle_bin, registry,
-            search, services, shellbags, shimcache, shimdb, shortcuts, srum, tasks, userassist,
-            users_windows, usnjrnl, wmi_persist,
+        windows::{
+            accounts::cache::clear_sid_cache,
+            artifacts::{
+                amcache, autoruns, bits, eventlogs, execution, jumplists, prefetch, raw_filelist,
+                recycle_bin, registry, search, services, shellbags, shimcache, shimdb, shortcuts,
+                srum, tasks, userassist, users_windows, usnjrnl, wmi_persist,
+            },
         },
     },
+    timeout::run_with_timeout,
 };
 use crate::{
-    runtime::deno::execute_script,
-    structs::toml::ArtemisToml,
-    utils::{logging::upload_logs, output::compress_final_output},
+    filesystem::{
+        access::take_access_denied,
+        selection::{mtime_limits, set_max_parse_bytes, set_mtime_limits, take_oversize},
+        timeout::set_read_timeout,
+    },
+    structs::{
+        artifacts::runtime::script::JSScript,
+        toml::{ArtemisToml, Artifacts, Output},
+    },
+    utils::{
+        events::emit_event,
+        logging::{access_denied_status, order_collection_status, upload_logs},
+        output::{
+            compress_final_output, flush_batch, label_output_name, set_output_fields,
+            set_output_label, set_output_transforms, verify_output_dir,
+        },
+        raw::{filetime_supported, keep_raw_filetime},
+    },
 };
+use common::server::jobs::CollectionEvent;
 use log::{error, info, warn};
+use std::{
+    cmp::Reverse,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
 
 /// Parse the TOML collector and get artifacts
 pub(crate) fn collect(collector: &mut ArtemisToml) -> Result<(), CollectionError> {
-    // Loop through all supported artifacts
-    for artifacts in &collector.artifacts {
-        let filter = artifacts.filter.unwrap_or(false);
-        match artifacts.artifact_name.as_str() {
-            "loginitems" => {
-                let options = match &artifacts.loginitems {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-                let results = loginitems(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected loginitems"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse loginitems, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "emond" => {
-                let options = match &artifacts.emond {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-                let results = emond(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected emond"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse emond, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "fseventsd" => {
-                let options = match &artifacts.fseventsd {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-                let results = fseventsd(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected fseventsd"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse fseventsd, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "launchd" => {
-                let options = match &artifacts.launchd {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-                let results = launchd(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected launchd"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse launchd, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "files" => {
-                let options = match &artifacts.files {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-
-                let results = filelisting(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected file listing"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse filelisting, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "users-macos" => {
-                let options = match &artifacts.users_macos {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-                let results = users_macos(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected users"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse users, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "groups-macos" => {
-                let options = match &artifacts.groups_macos {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-                let results = groups_macos(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected groups"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse groups, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "processes" => {
-                let options = match &artifacts.processes {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-
-                let results = processes(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected processes"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse processes, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "systeminfo" => {
-                let results = systeminfo(&mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected systeminfo"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse systeminfo, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "execpolicy" => {
-                let options = match &artifacts.execpolicy {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-                let results = execpolicy(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected execpolicy"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse execpolicy, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "unifiedlogs" => {
-                let options = match &artifacts.unifiedlogs {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-
-                let results = unifiedlogs(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected unified logs"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse unified logs, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "safari-history" => {
-                let results = safari_history(&mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Safari history"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse Safari history, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "safari-downloads" => {
-                let results = safari_downloads(&mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Safari downloads"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse Safari downloads, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "firefox-history" => {
-                let results = firefox_history(&mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Firefox history"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse Firefox history, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "firefox-downloads" => {
-                let results = firefox_downloads(&mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Firefox downloads"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse Firefox downloads, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "chromium-history" => {
-                let results = chromium_history(&mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Chromium history"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse Chromium history, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "chromium-downloads" => {
-                let results = chromium_downloads(&mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Chromium downloads"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse Chromium downloads, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "shell_history" => {
-                let results = bash_history(&mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected bash history"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse bash history, error: {err:?}");
-                        continue;
-                    }
-                }
-                let results = zsh_history(&mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected zsh history"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse zsh history, error: {err:?}");
-                        continue;
-                    }
-                }
-                let results = python_history(&mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected python history"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse python history, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "cron" => {
-                let results = cron_job(&mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected cron"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse cron data, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "sudologs-macos" => {
-                let options = match &artifacts.sudologs_macos {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-
-                let results = sudo_logs_macos(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected macOS sudo logs"),
-                    Err(err) => {
-                        error!(
-                            "[artemis-core] Failed to parse macOS sudo log data, error: {err:?}"
-                        );
-                        continue;
-                    }
-                }
-            }
-            "spotlight" => {
-                let options = match &artifacts.spotlight {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-                let results = spotlight(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected spotlight"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse spotlight, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "script" => {
-                let script_data = &artifacts.script;
-                let script = match script_data {
-                    Some(result) => result,
-                    _ => continue,
-                };
-                let results = execute_script(&mut collector.output, script);
-                match results {
-                    Ok(_) => info!("Executed JavaScript "),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to execute JavaScript error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            // Linux
-            "journal" => {
-                let options = match &artifacts.journals {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-
-                let results = journals(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected journals"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse journals, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "logon" => {
-                let options = match &artifacts.logons {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-
-                let results = logons(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected logons"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse logons, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "sudologs-linux" => {
-                let options = match &artifacts.sudologs_linux {
-                    Some(result_data) => result_data,
-                    _ => continue,
-                };
-
-                let results = sudo_logs_linux(&mut collector.output, &filter, options);
-                match results {
-                    Ok(_) => info!("Collected Linux sudo logs"),
-                    Err(err) => {
-                        error!(
-                            "[artemis-core] Failed to parse Linux sudo log data, error: {err:?}"
-                        );
-                        continue;
-                    }
-                }
-            }
-            // Windows
-            "prefetch" => {
-                let artifact = match &artifacts.prefetch {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = prefetch(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected prefetch"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse prefetch, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "eventlogs" => {
-                let artifact = match &artifacts.eventlogs {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = eventlogs(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Eventlogs"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse Eventlogs, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "rawfiles" => {
-                let artifact = match &artifacts.rawfiles {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = raw_filelist(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Raw Filelisting"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to get raw filelisting, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "shimdb" => {
-                let artifact = match &artifacts.shimdb {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = shimdb(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected shimdb"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse shimdb, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "registry" => {
-                let artifact = match &artifacts.registry {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = registry(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected registry"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse registry, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "userassist" => {
-                let artifact = match &artifacts.userassist {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = userassist(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected userassist"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse userassist, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "shimcache" => {
-                let artifact = match &artifacts.shimcache {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = shimcache(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected shimcache"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse shimcache, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "shellbags" => {
-                let artifact = match &artifacts.shellbags {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = shellbags(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected shellbags"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse shellbags, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "amcache" => {
-                let artifact = match &artifacts.amcache {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = amcache(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected amcache"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse amcache, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "shortcuts" => {
-                let artifact = match &artifacts.shortcuts {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = shortcuts(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected shortcuts"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse shortcut files, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "usnjrnl" => {
-                let artifact = match &artifacts.usnjrnl {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = usnjrnl(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected usnjrnl"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse usnjrnl, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "bits" => {
-                let artifact = match &artifacts.bits {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = bits(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected bits"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse bits, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "srum" => {
-                let artifact = match &artifacts.srum {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = srum(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected SRUM"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse srum, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "search" => {
-                let artifact = match &artifacts.search {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = search(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected search"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse search, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "users-windows" => {
-                let artifact = match &artifacts.users_windows {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = users_windows(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Users"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse users, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "tasks" => {
-                let artifact = match &artifacts.tasks {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = tasks(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Schedule Tasks"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse schedule tasks, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "services" => {
-                let artifact = match &artifacts.services {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = services(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Services"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse services, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "jumplists" => {
-                let artifact = match &artifacts.jumplists {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = jumplists(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Jumplists"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse jumplists, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "recyclebin" => {
-                let artifact = match &artifacts.recyclebin {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = recycle_bin(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected Recycle Bin"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse recycle bin, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            "wmipersist" => {
-                let artifact = match &artifacts.wmipersist {
-                    Some(result) => result,
-                    None => continue,
-                };
-                let results = wmi_persist(artifact, &mut collector.output, &filter);
-                match results {
-                    Ok(_) => info!("Collected WMI Persistence"),
-                    Err(err) => {
-                        error!("[artemis-core] Failed to parse WMI persistence, error: {err:?}");
-                        continue;
-                    }
-                }
-            }
-            _ => warn!(
-                "[artemis-core] Unsupported artifact: {}",
-                artifacts.artifact_name
-            ),
+    // Fail before parsing any artifacts if the output cannot be written
+    if let Err(err) = verify_output_dir(&collector.output) {
+        error!("[artemis-core] Cannot start collection: {err:?}");
+        return Err(CollectionError::OutputDirectory);
+    }
+
+    if collector.output.output == "sqlite" && collector.output.sqlite_replace.unwrap_or(false) {
+        replace_sqlite(&collector.output);
+    }
+
+    let batch_set =
+        collector.output.batch_bytes.is_some() || collector.output.batch_count.is_some();
+    if batch_set && collector.output.output != "local" && collector.output.format != "jsonl" {
+        warn!(
+            "[artemis-core] Batched uploads only support jsonl output. {} output will be uploaded without batching",
+            collector.output.format
+        );
+    }
+
+    priority_order(&mut collector.artifacts);
+    // Resolved SIDs are shared by the artifacts in this collection only
+    clear_sid_cache();
+
+    // Artifacts are collected one at a time unless multiple workers are configured
+    let workers = collector.workers.unwrap_or(1);
+    if workers > 1 {
+        collect_parallel(&collector.artifacts, &mut collector.output, workers);
+    } else {
+        for artifacts in &collector.artifacts {
+            collect_artifact(artifacts, &mut collector.output);
         }
     }
 
-    if collector.output.output != "local" {
+    // SQLite databases are written to the local system
+    if collector.output.output != "local" && collector.output.output != "sqlite" {
+        // Upload any remaining batched output before the logs
+        let _ = flush_batch(&collector.output);
         let output_dir = format!("{}/{}", collector.output.directory, collector.output.name);
 
         let _ = upload_logs(&output_dir, &collector.output);
@@ -676,11 +103,1060 @@ pub(crate) fn collect(collector: &mut ArtemisToml) -> Result<(), CollectionError
     Ok(())
 }
 
+/// Sort artifacts so the highest priority artifacts are collected first. Ties keep the TOML order
+fn priority_order(artifacts: &mut [Artifacts]) {
+    // Stable sort preserves the config order for artifacts with the same priority
+    artifacts.sort_by_key(|artifact| Reverse(artifact.priority.unwrap_or_default()));
+}
+
+/// Remove the SQLite database from a previous run
+fn replace_sqlite(output: &Output) {
+    if let Err(err) = crate::output::local::sqlite::sqlite_replace(output) {
+        error!("[artemis-core] Could not replace SQLite database: {err:?}");
+    }
+}
+
+/// Run a JavaScript artifact if artemis was built with the `scripting` feature
+#[cfg(feature = "scripting")]
+fn run_script(output: &mut Output, script: &JSScript) -> Result<(), CollectionError> {
+    if let Err(err) = crate::runtime::deno::execute_script(output, script) {
+        error!(
+            "[artemis-core] Could not run script {}: {err:?}",
+            script.name
+        );
+        return Err(CollectionError::Script);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "scripting"))]
+fn run_script(_output: &mut Output, script: &JSScript) -> Result<(), CollectionError> {
+    error!(
+        "[artemis-core] Script {} requires artemis to be built with the scripting feature",
+        script.name
+    );
+    Err(CollectionError::Scripting)
+}
+
+/**
+ * Collect artifacts using a pool of worker threads. Each worker writes its own output files
+ * JavaScript artifacts are run after the pool on the calling thread
+ */
+fn collect_parallel(artifacts: &[Artifacts], output: &mut Output, workers: usize) {
+    let (scripts, parsers): (Vec<&Artifacts>, Vec<&Artifacts>) = artifacts
+        .iter()
+        .partition(|artifact| artifact.artifact_name == "script");
+
+    let next = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..workers.min(parsers.len()) {
+            let mut worker_output = output.clone();
+            let next = &next;
+            let parsers = &parsers;
+            scope.spawn(move || {
+                while let Some(artifact) = parsers.get(next.fetch_add(1, Ordering::SeqCst)) {
+                    collect_artifact(artifact, &mut worker_output);
+                }
+            });
+        }
+    });
+
+    for script in scripts {
+        collect_artifact(script, output);
+    }
+
+    // Workers finish in any order. Keep the status log in the order the artifacts were queued (priority, then TOML order)
+    let names: Vec<String> = artifacts
+        .iter()
+        .map(|artifact| label_output_name(&artifact.artifact_name, artifact.label.as_deref()))
+        .collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    let _ = order_collection_status(output, &names);
+}
+
+/// Collect a single artifact based on the TOML options. Artifacts with a `timeout` are parsed on a worker thread
+fn collect_artifact(artifacts: &Artifacts, output: &mut Output) {
+    let seconds = match artifacts.timeout {
+        Some(result) => result,
+        None => return parse_artifact(artifacts, output),
+    };
+
+    let worker_artifacts = artifacts.clone();
+    let mut worker_output = output.clone();
+    let result = run_with_timeout(seconds, move || {
+        parse_artifact(&worker_artifacts, &mut worker_output);
+        worker_output
+    });
+    match result {
+        Some(result) => *output = result,
+        None => {
+            error!(
+                "[artemis-core] {} did not finish within {seconds} seconds",
+                artifacts.artifact_name
+            );
+            artifact_failed(
+                output,
+                &artifacts.artifact_name,
+                &format!("Did not finish within {seconds} seconds"),
+            );
+        }
+    }
+}
+
+/**
+ * Parse a single artifact and record any paths the parser could not access
+ * Access denied paths do not fail the artifact. The parser skips them and they are listed in the `status.log` file
+ * Source files outside the `source_mtime_min` and `source_mtime_max` limits are skipped while the parser runs
+ * Source files larger than `max_parse_bytes` are skipped and output as `<artifact>_oversize` metadata instead
+ * Raw NTFS and ESE reads longer than `read_timeout_secs` are aborted while the parser runs
+ * Output records only keep the artifact `fields` if set. The artifact `transforms` are then applied in order
+ */
+fn parse_artifact(artifacts: &Artifacts, output: &mut Output) {
+    // Clear any paths left over from a previous artifact on this thread
+    let _ = take_access_denied();
+    let _ = take_oversize();
+    set_mtime_limits(mtime_limits(output));
+    // Metadata only collections do not parse any files
+    if !artifacts.metadata_only.unwrap_or(false) {
+        set_max_parse_bytes(output.max_parse_bytes);
+    }
+    set_read_timeout(output.read_timeout_secs);
+    if keep_raw_filetime(output) && !filetime_supported(&artifacts.artifact_name) {
+        warn!(
+            "[artemis-core] keep_raw_filetime is only supported by prefetch. {} will not include FILETIME values",
+            artifacts.artifact_name
+        );
+    }
+    set_output_fields(artifacts.fields.clone());
+    set_output_label(artifacts.label.as_deref());
+    set_output_transforms(artifacts.transforms.clone());
+    run_parser(artifacts, output);
+    set_output_transforms(None);
+    set_output_label(None);
+    set_output_fields(None);
+    set_read_timeout(None);
+    set_max_parse_bytes(None);
+    set_mtime_limits(None);
+    access_status(&artifacts.artifact_name, output);
+    oversize_status(artifacts, output);
+}
+
+/// Record the paths the artifact could not access
+fn access_status(artifact_name: &str, output: &Output) {
+    for path in take_access_denied() {
+        warn!("[artemis-core] {artifact_name} could not access {path}");
+        let _ = access_denied_status(artifact_name, output, &path);
+    }
+}
+
+/// Output the metadata of the source files that were too large to parse
+fn oversize_status(artifacts: &Artifacts, output: &mut Output) {
+    let paths = take_oversize();
+    if paths.is_empty() {
+        return;
+    }
+
+    warn!(
+        "[artemis-core] {} skipped {} files larger than max_parse_bytes",
+        artifacts.artifact_name,
+        paths.len()
+    );
+    if let Err(err) = collect_oversize(artifacts, &paths, output) {
+        error!(
+            "[artemis-core] Failed to output {} oversize metadata: {err:?}",
+            artifacts.artifact_name
+        );
+    }
+}
+
+/// Run the parser for a single artifact and output the results
+fn run_parser(artifacts: &Artifacts, output: &mut Output) {
+    let filter = artifacts.filter.unwrap_or(false);
+    emit_event(
+        output,
+        CollectionEvent::ArtifactStarted {
+            name: artifacts.artifact_name.clone(),
+        },
+    );
+    if artifacts.metadata_only.unwrap_or(false) {
+        let results = collect_metadata(artifacts, output);
+        match results {
+            Ok(_) => info!("Collected {} metadata", artifacts.artifact_name),
+            Err(err) => {
+                error!(
+                    "[artemis-core] Failed to collect {} metadata, error: {err:?}",
+                    artifacts.artifact_name
+                );
+                artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                return;
+            }
+        }
+        emit_event(
+            output,
+            CollectionEvent::ArtifactFinished {
+                name: artifacts.artifact_name.clone(),
+            },
+        );
+        return;
+    }
+    match artifacts.artifact_name.as_str() {
+        "loginitems" => {
+            let options = match &artifacts.loginitems {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = loginitems(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected loginitems"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse loginitems, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "emond" => {
+            let options = match &artifacts.emond {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = emond(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected emond"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse emond, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "fseventsd" => {
+            let options = match &artifacts.fseventsd {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = fseventsd(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected fseventsd"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse fseventsd, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "launchd" => {
+            let options = match &artifacts.launchd {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = launchd(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected launchd"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse launchd, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "files" => {
+            let options = match &artifacts.files {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+
+            let results = filelisting(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected file listing"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse filelisting, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "users-macos" => {
+            let options = match &artifacts.users_macos {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = users_macos(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected users"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse users, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "groups-macos" => {
+            let options = match &artifacts.groups_macos {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = groups_macos(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected groups"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse groups, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "processes" => {
+            let options = match &artifacts.processes {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+
+            let results = processes(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected processes"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse processes, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "systeminfo" => {
+            let results = systeminfo(output, &filter);
+            match results {
+                Ok(_) => info!("Collected systeminfo"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse systeminfo, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "execpolicy" => {
+            let options = match &artifacts.execpolicy {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = execpolicy(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected execpolicy"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse execpolicy, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "unifiedlogs" => {
+            let options = match &artifacts.unifiedlogs {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+
+            let results = unifiedlogs(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected unified logs"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse unified logs, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "safari-history" => {
+            let results = safari_history(output, &filter);
+            match results {
+                Ok(_) => info!("Collected Safari history"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse Safari history, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "safari-downloads" => {
+            let results = safari_downloads(output, &filter);
+            match results {
+                Ok(_) => info!("Collected Safari downloads"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse Safari downloads, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "firefox-history" => {
+            let results = firefox_history(output, &filter);
+            match results {
+                Ok(_) => info!("Collected Firefox history"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse Firefox history, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "firefox-downloads" => {
+            let results = firefox_downloads(output, &filter);
+            match results {
+                Ok(_) => info!("Collected Firefox downloads"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse Firefox downloads, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "chromium-history" => {
+            let results = chromium_history(output, &filter);
+            match results {
+                Ok(_) => info!("Collected Chromium history"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse Chromium history, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "chromium-downloads" => {
+            let results = chromium_downloads(output, &filter);
+            match results {
+                Ok(_) => info!("Collected Chromium downloads"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse Chromium downloads, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "shell_history" => {
+            let results = bash_history(output, &filter);
+            match results {
+                Ok(_) => info!("Collected bash history"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse bash history, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+            let results = zsh_history(output, &filter);
+            match results {
+                Ok(_) => info!("Collected zsh history"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse zsh history, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+            let results = python_history(output, &filter);
+            match results {
+                Ok(_) => info!("Collected python history"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse python history, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "cron" => {
+            let results = cron_job(output, &filter);
+            match results {
+                Ok(_) => info!("Collected cron"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse cron data, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "sudologs-macos" => {
+            let options = match &artifacts.sudologs_macos {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+
+            let results = sudo_logs_macos(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected macOS sudo logs"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse macOS sudo log data, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "spotlight" => {
+            let options = match &artifacts.spotlight {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = spotlight(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected spotlight"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse spotlight, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "script" => {
+            let script_data = &artifacts.script;
+            let script = match script_data {
+                Some(result) => result,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = run_script(output, script);
+            match results {
+                Ok(_) => info!("Executed JavaScript "),
+                Err(err) => {
+                    error!("[artemis-core] Failed to execute JavaScript error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        // Linux
+        "journal" => {
+            let options = match &artifacts.journals {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+
+            let results = journals(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected journals"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse journals, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "logon" => {
+            let options = match &artifacts.logons {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+
+            let results = logons(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected logons"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse logons, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "sudologs-linux" => {
+            let options = match &artifacts.sudologs_linux {
+                Some(result_data) => result_data,
+                _ => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+
+            let results = sudo_logs_linux(output, &filter, options);
+            match results {
+                Ok(_) => info!("Collected Linux sudo logs"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse Linux sudo log data, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        // Windows
+        "prefetch" => {
+            let artifact = match &artifacts.prefetch {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = prefetch(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected prefetch"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse prefetch, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "eventlogs" => {
+            let artifact = match &artifacts.eventlogs {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = eventlogs(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected Eventlogs"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse Eventlogs, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "rawfiles" => {
+            let artifact = match &artifacts.rawfiles {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = raw_filelist(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected Raw Filelisting"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to get raw filelisting, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "shimdb" => {
+            let artifact = match &artifacts.shimdb {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = shimdb(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected shimdb"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse shimdb, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "registry" => {
+            let artifact = match &artifacts.registry {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = registry(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected registry"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse registry, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "userassist" => {
+            let artifact = match &artifacts.userassist {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = userassist(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected userassist"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse userassist, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "shimcache" => {
+            let artifact = match &artifacts.shimcache {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = shimcache(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected shimcache"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse shimcache, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "shellbags" => {
+            let artifact = match &artifacts.shellbags {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = shellbags(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected shellbags"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse shellbags, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "amcache" => {
+            let artifact = match &artifacts.amcache {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = amcache(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected amcache"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse amcache, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "shortcuts" => {
+            let artifact = match &artifacts.shortcuts {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = shortcuts(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected shortcuts"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse shortcut files, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "usnjrnl" => {
+            let artifact = match &artifacts.usnjrnl {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = usnjrnl(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected usnjrnl"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse usnjrnl, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "bits" => {
+            let artifact = match &artifacts.bits {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = bits(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected bits"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse bits, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "srum" => {
+            let artifact = match &artifacts.srum {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = srum(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected SRUM"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse srum, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "search" => {
+            let artifact = match &artifacts.search {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = search(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected search"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse search, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "users-windows" => {
+            let artifact = match &artifacts.users_windows {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = users_windows(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected Users"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse users, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "tasks" => {
+            let artifact = match &artifacts.tasks {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = tasks(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected Schedule Tasks"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse schedule tasks, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "services" => {
+            let artifact = match &artifacts.services {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = services(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected Services"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse services, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "jumplists" => {
+            let artifact = match &artifacts.jumplists {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = jumplists(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected Jumplists"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse jumplists, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "recyclebin" => {
+            let artifact = match &artifacts.recyclebin {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = recycle_bin(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected Recycle Bin"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse recycle bin, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "wmipersist" => {
+            let artifact = match &artifacts.wmipersist {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = wmi_persist(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected WMI Persistence"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse WMI persistence, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "execution" => {
+            let artifact = match &artifacts.execution {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = execution(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected execution events"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to get execution events, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        "autoruns" => {
+            let artifact = match &artifacts.autoruns {
+                Some(result) => result,
+                None => {
+                    artifact_failed(output, &artifacts.artifact_name, "Missing artifact options");
+                    return;
+                }
+            };
+            let results = autoruns(artifact, output, &filter);
+            match results {
+                Ok(_) => info!("Collected autoruns"),
+                Err(err) => {
+                    error!("[artemis-core] Failed to parse autoruns, error: {err:?}");
+                    artifact_failed(output, &artifacts.artifact_name, &err.to_string());
+                    return;
+                }
+            }
+        }
+        _ => {
+            warn!(
+                "[artemis-core] Unsupported artifact: {}",
+                artifacts.artifact_name
+            );
+            artifact_failed(output, &artifacts.artifact_name, "Unsupported artifact");
+            return;
+        }
+    }
+    emit_event(
+        output,
+        CollectionEvent::ArtifactFinished {
+            name: artifacts.artifact_name.clone(),
+        },
+    );
+}
+
+/// Notify the caller that an artifact could not be collected
+fn artifact_failed(output: &Output, name: &str, error: &str) {
+    emit_event(
+        output,
+        CollectionEvent::ArtifactFailed {
+            name: name.to_string(),
+            error: error.to_string(),
+        },
+    );
+}
+
 #[cfg(test)]
 mod tests {
-    use super::collect;
-    use crate::{filesystem::files::read_file, structs::toml::ArtemisToml};
-    use std::path::PathBuf;
+    use super::{access_status, collect, priority_order};
+    use crate::{
+        artifacts::error::CollectionError,
+        filesystem::{
+            access::{record_access_denied, take_access_denied},
+            files::read_file,
+        },
+        structs::toml::ArtemisToml,
+    };
+    use common::server::jobs::CollectionEvent;
+    use httpmock::{Method::PUT, MockServer};
+    use std::{
+        fs::{copy, create_dir_all, read_to_string, remove_file},
+        path::{Path, PathBuf},
+        sync::mpsc::sync_channel,
+    };
 
     #[test]
     fn test_collect() {
@@ -711,4 +1187,245 @@ mod tests {
         let mut collector = ArtemisToml::parse_artemis_toml(&buffer).unwrap();
         collect(&mut collector).unwrap();
     }
+
+    #[test]
+    fn test_collect_workers() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/linux/quick.toml");
+
+        let buffer = read_file(&test_location.display().to_string()).unwrap();
+        let mut collector = ArtemisToml::parse_artemis_toml(&buffer).unwrap();
+        collector.workers = Some(4);
+        collect(&mut collector).unwrap();
+    }
+
+    #[test]
+    fn test_collect_workers_batch() {
+        let server = MockServer::start();
+        let mock_me = server.mock(|when, then| {
+            when.method(PUT).path_contains("batch_");
+            then.status(201);
+        });
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/linux/quick.toml");
+
+        let buffer = read_file(&test_location.display().to_string()).unwrap();
+        let mut collector = ArtemisToml::parse_artemis_toml(&buffer).unwrap();
+        collector.workers = Some(4);
+        collector.output.name = String::from("workers_batch_test");
+        collector.output.format = String::from("jsonl");
+        collector.output.compress = false;
+        collector.output.output = String::from("azure");
+        collector.output.url = Some(format!(
+            "http://127.0.0.1:{}/mycontainername?sp=rcw&st=2023-06-14T03:00:40Z",
+            server.port()
+        ));
+        collector.output.api_key = Some(String::new());
+        collector.output.batch_count = Some(2);
+        collect(&mut collector).unwrap();
+
+        // Both artifact outputs are staged by separate workers and uploaded once as a single batch
+        mock_me.assert_hits(1);
+        assert!(!Path::new("./tmp/workers_batch_test/batch").exists());
+    }
+
+    #[test]
+    fn test_priority_order() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/linux/priority.toml");
+
+        let buffer = read_file(&test_location.display().to_string()).unwrap();
+        let mut collector = ArtemisToml::parse_artemis_toml(&buffer).unwrap();
+        priority_order(&mut collector.artifacts);
+
+        let names: Vec<&str> = collector
+            .artifacts
+            .iter()
+            .map(|artifact| artifact.artifact_name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["processes", "shell_history", "systeminfo", "unsupported"]
+        );
+    }
+
+    #[test]
+    fn test_collect_priority() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/linux/priority.toml");
+
+        let buffer = read_file(&test_location.display().to_string()).unwrap();
+        let mut collector = ArtemisToml::parse_artemis_toml(&buffer).unwrap();
+        let (sender, receiver) = sync_channel(1000);
+        collector.output.events = Some(sender);
+        collect(&mut collector).unwrap();
+
+        let started: Vec<String> = receiver
+            .try_iter()
+            .filter_map(|event| match event {
+                CollectionEvent::ArtifactStarted { name } => Some(name),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            started,
+            vec!["processes", "shell_history", "systeminfo", "unsupported"]
+        );
+    }
+
+    #[test]
+    fn test_access_status() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/linux/quick.toml");
+
+        let buffer = read_file(&test_location.display().to_string()).unwrap();
+        let mut collector = ArtemisToml::parse_artemis_toml(&buffer).unwrap();
+        collector.output.name = String::from("access_denied_test");
+        collector.output.directory = String::from("./tmp");
+        let status_log = "./tmp/access_denied_test/status.log";
+        let _ = remove_file(status_log);
+
+        // Simulate a parser that was denied access to a file
+        record_access_denied("/root/.bash_history");
+        access_status("shell_history", &collector.output);
+
+        let status = read_to_string(status_log).unwrap();
+        assert_eq!(status, "shell_history:access_denied:/root/.bash_history\n");
+        assert!(take_access_denied().is_empty());
+    }
+
+    #[test]
+    fn test_collect_output_not_writable() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/linux/quick.toml");
+
+        let buffer = read_file(&test_location.display().to_string()).unwrap();
+        let mut collector = ArtemisToml::parse_artemis_toml(&buffer).unwrap();
+        // Cannot create an output directory under a file
+        collector.output.directory = format!("{}/Cargo.toml", env!("CARGO_MANIFEST_DIR"));
+        let (sender, receiver) = sync_channel(1000);
+        collector.output.events = Some(sender);
+
+        let result = collect(&mut collector);
+        assert!(matches!(result, Err(CollectionError::OutputDirectory)));
+        // No artifacts were parsed
+        assert_eq!(receiver.try_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_collect_labels() {
+        let test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let sdb_path = |name: &str| {
+            let mut path = test_location.clone();
+            path.push(format!("tests/test_data/windows/shimdb/{name}"));
+            path.display().to_string()
+        };
+        let toml_data = format!(
+            r#"system = "windows"
+
+[output]
+name = "label_test"
+directory = "./tmp"
+format = "json"
+compress = false
+endpoint_id = "abcd"
+collection_id = 1
+output = "local"
+
+[[artifacts]]
+artifact_name = "shimdb"
+label = "custom"
+[artifacts.shimdb]
+alt_file = {:?}
+
+[[artifacts]]
+artifact_name = "shimdb"
+label = "atomic x86"
+[artifacts.shimdb]
+alt_file = {:?}
+"#,
+            sdb_path("ArtemisCustomShim.sdb"),
+            sdb_path("AtomicShimx86.sdb")
+        );
+        let mut collector = ArtemisToml::parse_artemis_toml(toml_data.as_bytes()).unwrap();
+        let status_log = "./tmp/label_test/status.log";
+        let _ = remove_file(status_log);
+        collect(&mut collector).unwrap();
+
+        let status = read_to_string(status_log).unwrap();
+        let files: Vec<(&str, &str)> = status
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .collect();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].0, "shimdb_custom");
+        assert_eq!(files[1].0, "shimdb_atomic_x86");
+        assert_ne!(files[0].1, files[1].1);
+        for (_, file) in files {
+            assert!(PathBuf::from(format!("./tmp/label_test/{file}")).exists());
+        }
+    }
+
+    #[test]
+    fn test_collect_max_parse_bytes() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win10");
+        let prefetch_dir = "./tmp/max_parse_bytes/prefetch";
+        create_dir_all(prefetch_dir).unwrap();
+        // 2259 bytes and 16693 bytes
+        for name in [
+            "AM_DELTA_PATCH_1.377.757.0.EX-0C03AAF4.pf",
+            "APPLICATIONFRAMEHOST.EXE-CCEEF759.pf",
+        ] {
+            copy(
+                format!("{}/{name}", test_location.display()),
+                format!("{prefetch_dir}/{name}"),
+            )
+            .unwrap();
+        }
+
+        let toml_data = format!(
+            r#"system = "windows"
+
+[output]
+name = "max_parse_bytes_test"
+directory = "./tmp"
+format = "json"
+compress = false
+endpoint_id = "abcd"
+collection_id = 1
+output = "local"
+max_parse_bytes = 10000
+
+[[artifacts]]
+artifact_name = "prefetch"
+[artifacts.prefetch]
+alt_dir = {prefetch_dir:?}
+"#
+        );
+        let mut collector = ArtemisToml::parse_artemis_toml(toml_data.as_bytes()).unwrap();
+        let status_log = "./tmp/max_parse_bytes_test/status.log";
+        let _ = remove_file(status_log);
+        collect(&mut collector).unwrap();
+
+        let status = read_to_string(status_log).unwrap();
+        let files: Vec<(&str, &str)> = status
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .collect();
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0].0, "prefetch");
+        assert_eq!(files[1].0, "prefetch_status");
+        assert_eq!(files[2].0, "prefetch_oversize");
+
+        let parsed = read_to_string(format!("./tmp/max_parse_bytes_test/{}", files[0].1)).unwrap();
+        assert!(parsed.contains("AM_DELTA_PATCH_1.377.757.0.EX"));
+        assert!(!parsed.contains("APPLICATIONFRAMEHOST.EXE"));
+
+        let oversize =
+            read_to_string(format!("./tmp/max_parse_bytes_test/{}", files[2].1)).unwrap();
+        assert!(oversize.contains("APPLICATIONFRAMEHOST.EXE-CCEEF759.pf"));
+        assert!(oversize.contains("\"skipped_oversize\":true"));
+        assert!(oversize.contains("\"size\":16693"));
+    }
 }
@@ -5,6 +5,12 @@ pub(crate) enum CollectionError {
     Output,
     FilterOutput,
     Format,
+    OutputDirectory,
+    Baseline,
+    Script,
+    Scripting,
+    MetadataOnly,
+    MetadataUnsupported,
 }
 
 impl std::error::Error for CollectionError {}
@@ -15,6 +21,40 @@ impl fmt::Display for CollectionError {
             CollectionError::Output => write!(f, "Failed to output data"),
             CollectionError::FilterOutput => write!(f, "Failed to filter macos data"),
             CollectionError::Format => write!(f, "Unknown formatter provided"),
+            CollectionError::OutputDirectory => write!(f, "Output directory is not writable"),
+            CollectionError::Baseline => write!(f, "Failed to compare against baseline"),
+            CollectionError::Script => write!(f, "Failed to execute JavaScript"),
+            CollectionError::Scripting => {
+                write!(f, "JavaScript requires the scripting feature")
+            }
+            CollectionError::MetadataOnly => write!(f, "Failed to collect source file metadata"),
+            CollectionError::MetadataUnsupported => {
+                write!(f, "Artifact does not support metadata only collection")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[derive(Debug)]
+pub(crate) enum SampleError {
+    ReadFile,
+    Unknown,
+    Parse,
+    Serialize,
+}
+
+#[cfg(test)]
+impl std::error::Error for SampleError {}
+
+#[cfg(test)]
+impl fmt::Display for SampleError {
+    fn fmt<'a>(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SampleError::ReadFile => write!(f, "Could not read sample file"),
+            SampleError::Unknown => write!(f, "Unknown sample artifact"),
+            SampleError::Parse => write!(f, "Could not parse sample artifact"),
+            SampleError::Serialize => write!(f, "Could not serialize sample artifact"),
         }
     }
 }
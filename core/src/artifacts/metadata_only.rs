@@ -0,0 +1,428 @@
+/**
+ * Collect filesystem metadata for the source files of path-based artifacts without parsing them
+ * Useful for quickly scoping a collection before parsing everything
+ * Source files skipped because of `max_parse_bytes` are also output as metadata only
+ */
+use super::{error::CollectionError, output::output_artifact};
+use crate::{
+    filesystem::{
+        files::{get_file_size, get_filename, hash_file, Hashes},
+        metadata::{get_timestamps, glob_paths},
+    },
+    structs::toml::{Artifacts, Output},
+    utils::{environment::get_systemdrive, time},
+};
+use log::{error, warn};
+use serde::Serialize;
+use std::path::MAIN_SEPARATOR;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct SourceMetadata {
+    pub(crate) full_path: String,
+    pub(crate) filename: String,
+    pub(crate) size: u64,
+    pub(crate) created: i64,
+    pub(crate) modified: i64,
+    pub(crate) accessed: i64,
+    pub(crate) changed: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct OversizeMetadata {
+    pub(crate) full_path: String,
+    pub(crate) filename: String,
+    pub(crate) size: u64,
+    pub(crate) sha256: String,
+    pub(crate) skipped_oversize: bool,
+}
+
+/// Output the metadata of the source files for an artifact as `<artifact>_metadata`
+pub(crate) fn collect_metadata(
+    artifacts: &Artifacts,
+    output: &mut Output,
+) -> Result<(), CollectionError> {
+    let start_time = time::time_now();
+
+    let patterns = source_paths(artifacts)?;
+    let entries = source_metadata(&patterns);
+
+    let serde_data_result = serde_json::to_value(entries);
+    let serde_data = match serde_data_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!(
+                "[artemis-core] Failed to serialize {} metadata: {err:?}",
+                artifacts.artifact_name
+            );
+            return Err(CollectionError::MetadataOnly);
+        }
+    };
+
+    let filter = artifacts.filter.unwrap_or(false);
+    output_artifact(
+        &serde_data,
+        &format!("{}_metadata", artifacts.artifact_name),
+        output,
+        &start_time,
+        &filter,
+    )
+}
+
+/// Output the source files that were too large to parse as `<artifact>_oversize`
+pub(crate) fn collect_oversize(
+    artifacts: &Artifacts,
+    paths: &[String],
+    output: &mut Output,
+) -> Result<(), CollectionError> {
+    let start_time = time::time_now();
+    let entries = oversize_metadata(paths);
+
+    let serde_data_result = serde_json::to_value(entries);
+    let serde_data = match serde_data_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!(
+                "[artemis-core] Failed to serialize {} oversize metadata: {err:?}",
+                artifacts.artifact_name
+            );
+            return Err(CollectionError::MetadataOnly);
+        }
+    };
+
+    let filter = artifacts.filter.unwrap_or(false);
+    output_artifact(
+        &serde_data,
+        &format!("{}_oversize", artifacts.artifact_name),
+        output,
+        &start_time,
+        &filter,
+    )
+}
+
+/// Get the size and SHA256 hash of source files that were not parsed. The files are hashed in chunks
+pub(crate) fn oversize_metadata(paths: &[String]) -> Vec<OversizeMetadata> {
+    let hashes = Hashes {
+        md5: false,
+        sha1: false,
+        sha256: true,
+    };
+
+    let mut entries = Vec::new();
+    for path in paths {
+        let (_, _, sha256) = hash_file(&hashes, path);
+        entries.push(OversizeMetadata {
+            full_path: path.clone(),
+            filename: get_filename(path),
+            size: get_file_size(path),
+            sha256,
+            skipped_oversize: true,
+        });
+    }
+    entries
+}
+
+/// Get the metadata for all files matching the glob patterns. The files are not read
+pub(crate) fn source_metadata(patterns: &[String]) -> Vec<SourceMetadata> {
+    let mut entries = Vec::new();
+    for pattern in patterns {
+        let paths_result = glob_paths(pattern);
+        let paths = match paths_result {
+            Ok(result) => result,
+            Err(err) => {
+                warn!("[artemis-core] Could not glob source files {pattern}: {err:?}");
+                continue;
+            }
+        };
+
+        for path in paths {
+            if !path.is_file {
+                continue;
+            }
+
+            let timestamps_result = get_timestamps(&path.full_path);
+            let timestamps = match timestamps_result {
+                Ok(result) => result,
+                Err(err) => {
+                    warn!(
+                        "[artemis-core] Could not get timestamps for {}: {err:?}",
+                        path.full_path
+                    );
+                    continue;
+                }
+            };
+
+            entries.push(SourceMetadata {
+                size: get_file_size(&path.full_path),
+                full_path: path.full_path,
+                filename: path.filename,
+                created: timestamps.created,
+                modified: timestamps.modified,
+                accessed: timestamps.accessed,
+                changed: timestamps.changed,
+            });
+        }
+    }
+    entries
+}
+
+/// Get the glob patterns for the source files of a path-based artifact. Alternative paths in the artifact options are used if set
+pub(crate) fn source_paths(artifacts: &Artifacts) -> Result<Vec<String>, CollectionError> {
+    let sep = MAIN_SEPARATOR;
+    let patterns = match artifacts.artifact_name.as_str() {
+        "prefetch" => match artifacts
+            .prefetch
+            .as_ref()
+            .and_then(|opt| opt.alt_dir.clone())
+        {
+            Some(dir) => vec![format!("{dir}{sep}*.pf")],
+            None => vec![format!("{}:\\Windows\\Prefetch\\*.pf", systemdrive()?)],
+        },
+        "eventlogs" => {
+            let options = artifacts.eventlogs.as_ref();
+            match options.and_then(|opt| opt.alt_file.clone()) {
+                Some(file) => vec![file],
+                None => {
+                    let drive = match options.and_then(|opt| opt.alt_drive) {
+                        Some(drive) => drive,
+                        None => systemdrive()?,
+                    };
+                    vec![format!("{drive}:\\Windows\\System32\\winevt\\Logs\\*.evtx")]
+                }
+            }
+        }
+        "amcache" => match artifacts
+            .amcache
+            .as_ref()
+            .and_then(|opt| opt.alt_file.clone())
+        {
+            Some(file) => vec![file],
+            None => vec![format!(
+                "{}:\\Windows\\appcompat\\Programs\\Amcache.hve",
+                systemdrive()?
+            )],
+        },
+        "shimdb" => match artifacts
+            .shimdb
+            .as_ref()
+            .and_then(|opt| opt.alt_file.clone())
+        {
+            Some(file) => vec![file],
+            None => vec![format!(
+                "{}:\\Windows\\apppatch\\sysmain.sdb",
+                systemdrive()?
+            )],
+        },
+        "srum" => match artifacts.srum.as_ref().and_then(|opt| opt.alt_file.clone()) {
+            Some(file) => vec![file],
+            None => vec![format!(
+                "{}:\\Windows\\System32\\sru\\SRUDB.dat",
+                systemdrive()?
+            )],
+        },
+        "bits" => match artifacts.bits.as_ref().and_then(|opt| opt.alt_file.clone()) {
+            Some(file) => vec![file],
+            None => vec![format!(
+                "{}:\\ProgramData\\Microsoft\\Network\\Downloader\\qmgr*",
+                systemdrive()?
+            )],
+        },
+        "search" => match artifacts
+            .search
+            .as_ref()
+            .and_then(|opt| opt.alt_file.clone())
+        {
+            Some(file) => vec![file],
+            None => vec![format!(
+                "{}:\\ProgramData\\Microsoft\\Search\\Data\\Applications\\Windows\\Windows.*",
+                systemdrive()?
+            )],
+        },
+        "jumplists" => match artifacts
+            .jumplists
+            .as_ref()
+            .and_then(|opt| opt.alt_file.clone())
+        {
+            Some(file) => vec![file],
+            None => vec![format!(
+                "{}:\\Users\\*\\AppData\\Roaming\\Microsoft\\Windows\\Recent\\*Destinations\\*",
+                systemdrive()?
+            )],
+        },
+        "recyclebin" => match artifacts
+            .recyclebin
+            .as_ref()
+            .and_then(|opt| opt.alt_file.clone())
+        {
+            Some(file) => vec![file],
+            None => vec![format!("{}:\\$RECYCLE.BIN\\*\\$I*", systemdrive()?)],
+        },
+        "shortcuts" => match &artifacts.shortcuts {
+            Some(options) => vec![format!("{}{sep}*.lnk", options.path)],
+            None => return Err(CollectionError::MetadataUnsupported),
+        },
+        "tasks" => match artifacts
+            .tasks
+            .as_ref()
+            .and_then(|opt| opt.alt_file.clone())
+        {
+            Some(file) => vec![file],
+            None => {
+                let drive = match artifacts.tasks.as_ref().and_then(|opt| opt.alt_drive) {
+                    Some(drive) => drive,
+                    None => systemdrive()?,
+                };
+                vec![
+                    format!("{drive}:\\Windows\\System32\\Tasks\\**\\*"),
+                    format!("{drive}:\\Windows\\Tasks\\*.job"),
+                ]
+            }
+        },
+        "journals" => match artifacts
+            .journals
+            .as_ref()
+            .and_then(|opt| opt.alt_path.clone())
+        {
+            Some(path) => vec![format!("{path}{sep}*.journal")],
+            None => vec![
+                String::from("/var/log/journal/*/*.journal"),
+                String::from("/run/systemd/journal/*/*.journal"),
+            ],
+        },
+        "logons" => match artifacts
+            .logons
+            .as_ref()
+            .and_then(|opt| opt.alt_file.clone())
+        {
+            Some(file) => vec![file],
+            None => vec![
+                String::from("/var/log/wtmp"),
+                String::from("/var/log/btmp"),
+                String::from("/var/run/utmp"),
+            ],
+        },
+        "fseventsd" => match artifacts
+            .fseventsd
+            .as_ref()
+            .and_then(|opt| opt.alt_file.clone())
+        {
+            Some(file) => vec![file],
+            None => vec![String::from("/System/Volumes/Data/.fseventsd/*")],
+        },
+        _ => return Err(CollectionError::MetadataUnsupported),
+    };
+
+    Ok(patterns)
+}
+
+/// Get the systemdrive letter for the default Windows paths
+fn systemdrive() -> Result<char, CollectionError> {
+    let drive_result = get_systemdrive();
+    match drive_result {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("[artemis-core] Could not get systemdrive for metadata collection: {err:?}");
+            Err(CollectionError::MetadataOnly)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect_metadata, oversize_metadata, source_metadata, source_paths};
+    use crate::{
+        artifacts::error::CollectionError,
+        structs::toml::{Artifacts, Output},
+    };
+    use std::{
+        fs::{create_dir_all, write},
+        path::PathBuf,
+    };
+
+    fn output_options(name: &str, format: &str, directory: &str, compress: bool) -> Output {
+        Output {
+            name: name.to_string(),
+            directory: directory.to_string(),
+            format: format.to_string(),
+            compress,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("local"),
+            ..Default::default()
+        }
+    }
+
+    fn bad_prefetch() -> Artifacts {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/bad data");
+
+        let artifact = format!(
+            "artifact_name = \"prefetch\"\nmetadata_only = true\n[prefetch]\nalt_dir = {:?}\n",
+            test_location.display().to_string()
+        );
+        toml::from_str(&artifact).unwrap()
+    }
+
+    #[test]
+    fn test_collect_metadata() {
+        let mut output = output_options("metadata_temp", "json", "./tmp", false);
+        // The prefetch files are malformed. Parsing them would fail
+        collect_metadata(&bad_prefetch(), &mut output).unwrap();
+    }
+
+    #[test]
+    fn test_source_metadata() {
+        let patterns = source_paths(&bad_prefetch()).unwrap();
+        let mut results = source_metadata(&patterns);
+        results.sort_by(|a, b| a.filename.cmp(&b.filename));
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].filename, "bad_compression.pf");
+        assert_eq!(results[0].size, 21206);
+        assert_eq!(results[1].filename, "malformed.pf");
+        assert_eq!(results[1].size, 1755);
+        assert!(results[1].modified > 0);
+    }
+
+    #[test]
+    fn test_source_paths() {
+        let artifact: Artifacts = toml::from_str(
+            "artifact_name = \"journals\"\nmetadata_only = true\n[journals]\nalt_path = \"/tmp/journal\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            source_paths(&artifact).unwrap(),
+            vec![format!(
+                "/tmp/journal{}*.journal",
+                std::path::MAIN_SEPARATOR
+            )]
+        );
+
+        let artifact: Artifacts =
+            toml::from_str("artifact_name = \"processes\"\nmetadata_only = true\n").unwrap();
+        assert!(matches!(
+            source_paths(&artifact),
+            Err(CollectionError::MetadataUnsupported)
+        ));
+    }
+
+    #[test]
+    fn test_oversize_metadata() {
+        let dir = "./tmp/oversize/metadata";
+        create_dir_all(dir).unwrap();
+        let path = format!("{dir}/large.bin");
+        write(&path, b"artemis").unwrap();
+
+        let results = oversize_metadata(&[path.clone()]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].full_path, path);
+        assert_eq!(results[0].filename, "large.bin");
+        assert_eq!(results[0].size, 7);
+        assert_eq!(
+            results[0].sha256,
+            "8c704e3a6730d5654e02588991b8d4e98e7281ae405615df9f99398019d3d7b9"
+        );
+        assert!(results[0].skipped_oversize);
+    }
+}
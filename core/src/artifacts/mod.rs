@@ -1,5 +1,12 @@
 pub(crate) mod applications;
+pub(crate) mod baseline;
 pub(crate) mod collection;
-mod error;
+pub(crate) mod error;
+pub(crate) mod metadata_only;
 pub(crate) mod os;
 pub(crate) mod output;
+#[cfg(test)]
+pub(crate) mod samples;
+pub(crate) mod since;
+pub(crate) mod timeout;
+pub(crate) mod volume;
@@ -1,6 +1,6 @@
 use super::{error::FileError, filelisting::get_filelist};
 use crate::{
-    filesystem::files::Hashes,
+    filesystem::{files::Hashes, walk::WalkOptions},
     structs::{artifacts::os::files::FileOptions, toml::Output},
 };
 use log::error;
@@ -16,9 +16,13 @@ pub(crate) fn filelisting(
         sha1: options.sha1.unwrap_or(false),
         sha256: options.sha256.unwrap_or(false),
     };
+    let walk = WalkOptions {
+        max_depth: options.depth.unwrap_or(1).into(),
+        follow_links: options.follow_links.unwrap_or(false),
+    };
     let artifact_result = get_filelist(
         &options.start_path,
-        options.depth.unwrap_or(1).into(),
+        &walk,
         options.metadata.unwrap_or(false),
         &hashes,
         options.regex_filter.as_ref().unwrap_or(&String::new()),
@@ -55,6 +59,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -66,6 +71,7 @@ mod tests {
         let file_config = FileOptions {
             start_path: String::from("/"),
             depth: Some(1),
+            follow_links: None,
             metadata: Some(false),
             md5: Some(false),
             sha1: Some(false),
@@ -84,6 +90,7 @@ mod tests {
         let file_config = FileOptions {
             start_path: String::from("C:\\"),
             depth: Some(1),
+            follow_links: None,
             metadata: Some(false),
             md5: Some(false),
             sha1: Some(false),
@@ -12,6 +12,7 @@ use crate::artifacts::os::systeminfo::info::get_platform;
 use crate::artifacts::output::output_artifact;
 use crate::filesystem::files::{file_extension, hash_file};
 use crate::filesystem::metadata::get_metadata;
+use crate::filesystem::walk::{walk_directory, WalkOptions};
 use crate::filesystem::{files::Hashes, metadata::get_timestamps};
 use crate::structs::toml::Output;
 use crate::utils::regex_options::{create_regex, regex_check};
@@ -21,7 +22,7 @@ use log::{error, info, warn};
 use regex::Regex;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Error as ioError};
-use walkdir::{DirEntry, WalkDir};
+use walkdir::DirEntry;
 
 #[cfg(target_os = "macos")]
 use common::macos::MachoInfo;
@@ -42,7 +43,7 @@ use std::os::unix::prelude::MetadataExt;
 /// Get file listing
 pub(crate) fn get_filelist(
     start_directory: &str,
-    depth: usize,
+    walk: &WalkOptions,
     metadata: bool,
     hashes: &Hashes,
     path_filter: &str,
@@ -51,8 +52,6 @@ pub(crate) fn get_filelist(
 ) -> Result<(), FileError> {
     let start_time = time_now();
 
-    let start_walk = WalkDir::new(start_directory).same_file_system(true);
-    let begin_walk = start_walk.max_depth(depth);
     let mut filelist_vec: Vec<FileInfo> = Vec::new();
 
     let path_filter = user_regex(path_filter)?;
@@ -67,18 +66,18 @@ pub(crate) fn get_filelist(
         }
     }
 
-    for entries in begin_walk
-        .into_iter()
-        .filter_entry(|f| !skip_firmlinks(f, &firmlink_paths))
-    {
-        let entry = match entries {
-            Ok(result) => result,
-            Err(err) => {
-                warn!("[files] Failed to get file info: {err:?}");
-                continue;
-            }
-        };
+    let walk_result = walk_directory(start_directory, walk, |f| {
+        !skip_firmlinks(f, &firmlink_paths)
+    });
+    let begin_walk = match walk_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[files] Failed to walk {start_directory}: {err:?}");
+            return Err(FileError::Filelisting);
+        }
+    };
 
+    for entry in begin_walk {
         // If Regex does not match then skip file info
         if !regex_check(&path_filter, &entry.path().display().to_string()) {
             continue;
@@ -320,6 +319,7 @@ mod tests {
     use crate::artifacts::os::files::filelisting::executable_metadata;
     use crate::artifacts::os::files::filelisting::file_metadata;
     use crate::artifacts::os::files::filelisting::get_filelist;
+    use crate::filesystem::walk::WalkOptions;
     use crate::{
         artifacts::os::files::filelisting::{user_regex, Hashes},
         structs::toml::Output,
@@ -343,6 +343,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -360,9 +361,13 @@ mod tests {
         let path_filter = r".*/Downloads";
         let mut output = output_options("files_temp", "local", "./tmp", false);
 
+        let walk = WalkOptions {
+            max_depth: depth,
+            follow_links: false,
+        };
         let results = get_filelist(
             &start_location,
-            depth,
+            &walk,
             metadata,
             &hashes,
             path_filter,
@@ -423,9 +428,13 @@ mod tests {
         let path_filter = "";
         let mut output = output_options("files_temp", "local", "./tmp", false);
 
+        let walk = WalkOptions {
+            max_depth: depth,
+            follow_links: false,
+        };
         get_filelist(
             &start_location,
-            depth,
+            &walk,
             metadata,
             &hashes,
             path_filter,
@@ -449,9 +458,13 @@ mod tests {
         let path_filter = "";
         let mut output = output_options("files_temp", "local", "./tmp", false);
 
+        let walk = WalkOptions {
+            max_depth: depth,
+            follow_links: false,
+        };
         get_filelist(
             start_location,
-            depth,
+            &walk,
             metadata,
             &hashes,
             path_filter,
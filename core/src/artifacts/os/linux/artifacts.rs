@@ -120,6 +120,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
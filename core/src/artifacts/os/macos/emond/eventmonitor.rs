@@ -11,10 +11,12 @@ use crate::{
         property_list::{get_boolean, get_string},
     },
     filesystem::files::list_files,
+    utils::time::{cocoatime_to_unixepoch, systemtime_to_unixepoch, unixepoch_to_iso8601},
 };
 use common::macos::{Actions, EmondData};
 use log::{error, warn};
 use plist::Value;
+use std::time::SystemTime;
 
 /// Parse all Emond rules files at provided path
 pub(crate) fn parse_emond_rules(path: &str) -> Result<Vec<EmondData>, PlistError> {
@@ -84,7 +86,7 @@ pub(crate) fn parse_emond_data(path: &str) -> Result<Vec<EmondData>, PlistError>
                         } else if key == "criterion" {
                             emond_data.criterion = get_dictionary_values(value);
                         } else if key == "startTime" {
-                            emond_data.start_time = get_string(&value)?;
+                            emond_data.start_time = parse_start_time(&value);
                         } else if key == "variables" {
                             emond_data.variables = get_dictionary_values(value);
                         } else if key == "name" {
@@ -212,18 +214,47 @@ fn check_clients() -> bool {
     true
 }
 
+/**
+ * Normalize the Rule `startTime` to an ISO8601 UTC string
+ * The value may be a plist date, a Cocoa timestamp, or a date string such as `2016-12-11 17:00:00 +0000`
+ * Strings that cannot be parsed are returned unchanged
+ */
+fn parse_start_time(value: &Value) -> String {
+    match value {
+        Value::Date(date) => {
+            let time: SystemTime = (*date).into();
+            unixepoch_to_iso8601(&systemtime_to_unixepoch(&time))
+        }
+        Value::Real(cocoatime) => unixepoch_to_iso8601(&cocoatime_to_unixepoch(cocoatime)),
+        Value::Integer(cocoatime) => match cocoatime.as_signed() {
+            Some(result) => unixepoch_to_iso8601(&cocoatime_to_unixepoch(&(result as f64))),
+            None => String::new(),
+        },
+        Value::String(start_time) => {
+            match chrono::DateTime::parse_from_str(start_time, "%Y-%m-%d %H:%M:%S %z") {
+                Ok(result) => unixepoch_to_iso8601(&result.timestamp()),
+                Err(_err) => start_time.clone(),
+            }
+        }
+        _ => {
+            warn!("[emond] Unexpected startTime value: {value:?}");
+            String::new()
+        }
+    }
+}
+
 #[cfg(test)]
 #[cfg(target_os = "macos")]
 mod tests {
     use super::parse_emond_rules;
     use crate::{
         artifacts::os::macos::emond::eventmonitor::{
-            check_clients, parse_actions, parse_emond_data, parse_event_types,
+            check_clients, parse_actions, parse_emond_data, parse_event_types, parse_start_time,
         },
         filesystem::directory::is_directory,
     };
     use plist::{Dictionary, Value};
-    use std::path::PathBuf;
+    use std::{path::PathBuf, time::UNIX_EPOCH};
 
     #[test]
     fn test_system_parse_emond_rules() {
@@ -354,4 +385,22 @@ mod tests {
         assert_eq!(results.command_actions[0].command, "nc -l");
         assert_eq!(results.command_actions[0].arguments.len(), 0);
     }
+
+    #[test]
+    fn test_parse_start_time() {
+        let test = Value::Date(plist::Date::from(UNIX_EPOCH));
+        assert_eq!(parse_start_time(&test), "1970-01-01T00:00:00Z");
+
+        let test = Value::Real(0.0);
+        assert_eq!(parse_start_time(&test), "2001-01-01T00:00:00Z");
+
+        let test = Value::Integer(86400i64.into());
+        assert_eq!(parse_start_time(&test), "2001-01-02T00:00:00Z");
+
+        let test = Value::String(String::from("2016-12-11 17:00:00 -0500"));
+        assert_eq!(parse_start_time(&test), "2016-12-11T22:00:00Z");
+
+        let test = Value::String(String::from("tomorrow"));
+        assert_eq!(parse_start_time(&test), "tomorrow");
+    }
 }
@@ -36,8 +36,25 @@ pub(crate) fn fsevents_data(data: &[u8]) -> nom::IResult<&[u8], Vec<FsEvents>> {
         }
 
         let header_size = 12;
-        let (stream_input, fsevent_data) =
-            take(fsevents_header.stream_size - header_size)(fsevents_data)?;
+        if fsevents_header.stream_size < header_size {
+            warn!(
+                "[fsevents] Got invalid stream size: {}",
+                fsevents_header.stream_size
+            );
+            break;
+        }
+        let stream_result: nom::IResult<&[u8], &[u8]> =
+            take(fsevents_header.stream_size - header_size)(fsevents_data);
+        let (stream_input, fsevent_data) = match stream_result {
+            Ok(result) => result,
+            Err(_err) => {
+                // Last page is truncated. Parse what remains
+                warn!(
+                    "[fsevents] Stream size larger than remaining data. Parsing remaining records"
+                );
+                (&[] as &[u8], fsevents_data)
+            }
+        };
 
         // Parse `FsEvent` stream data
         let (_result, mut fsevents) = get_fsevent(fsevent_data, fsevents_header.signature)?;
@@ -48,14 +65,27 @@ pub(crate) fn fsevents_data(data: &[u8]) -> nom::IResult<&[u8], Vec<FsEvents>> {
     Ok((input, total_fsevents))
 }
 
-/// Begin parsing `FsEvent` stream
+/**
+ * Begin parsing `FsEvent` stream
+ * A corrupt record skips the rest of the stream (page). Records before the corrupt record are kept
+ */
 fn get_fsevent(data: &[u8], sig: u32) -> nom::IResult<&[u8], Vec<FsEvents>> {
     let mut input_results = data;
     let mut fsevents_array: Vec<FsEvents> = Vec::new();
 
     // Parse `FsEvent` stream and get each `FsEvent` record
     while !input_results.is_empty() {
-        let (input_data, fsevent_results) = get_fsevent_data(input_results, &sig)?;
+        let fsevent_result = get_fsevent_data(input_results, &sig);
+        let (input_data, fsevent_results) = match fsevent_result {
+            Ok(result) => result,
+            Err(_err) => {
+                warn!(
+                    "[fsevents] Corrupt FsEvent record. Skipping remaining {} bytes in page",
+                    input_results.len()
+                );
+                return Ok((&[], fsevents_array));
+            }
+        };
         input_results = input_data;
         fsevents_array.push(fsevent_results);
     }
@@ -289,4 +319,51 @@ mod tests {
         assert_eq!(results.len(), 0);
         assert_eq!(data.len(), 1);
     }
+
+    /// Build a `FsEvent` page with the provided signature and record data
+    fn test_page(signature: &[u8; 4], records: &[u8]) -> Vec<u8> {
+        let mut page = signature.to_vec();
+        page.extend_from_slice(&[0, 0, 0, 0]);
+        page.extend_from_slice(&(records.len() as u32 + 12).to_le_bytes());
+        page.extend_from_slice(records);
+        page
+    }
+
+    #[test]
+    fn test_fsevents_data_corrupt_page() {
+        // DLS1 record: path, event id, flags
+        let mut first = b"a\0".to_vec();
+        first.extend_from_slice(&1u64.to_le_bytes());
+        first.extend_from_slice(&1u32.to_le_bytes());
+
+        // Record is missing its flags
+        let mut corrupt = b"b\0".to_vec();
+        corrupt.extend_from_slice(&2u64.to_le_bytes());
+
+        // DLS2 record: path, event id, flags, node
+        let mut last = b"c\0".to_vec();
+        last.extend_from_slice(&3u64.to_le_bytes());
+        last.extend_from_slice(&8u32.to_le_bytes());
+        last.extend_from_slice(&4u64.to_le_bytes());
+
+        let mut test = test_page(b"1SLD", &first);
+        test.append(&mut test_page(b"1SLD", &corrupt));
+        test.append(&mut test_page(b"2SLD", &last));
+
+        let (_, results) = fsevents_data(&test).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].path, "/a");
+        assert_eq!(results[0].flags, ["Created"]);
+        assert_eq!(results[1].path, "/c");
+        assert_eq!(results[1].event_id, 3);
+        assert_eq!(results[1].flags, ["Renamed"]);
+        assert_eq!(results[1].node, 4);
+    }
+
+    #[test]
+    fn test_fsevents_data_bad_stream_size() {
+        let test = [49, 83, 76, 68, 0, 0, 0, 0, 4, 0, 0, 0, 97, 0];
+        let (_, results) = fsevents_data(&test).unwrap();
+        assert!(results.is_empty());
+    }
 }
@@ -4,6 +4,7 @@ use std::fmt;
 pub(crate) enum LaunchdError {
     UserPath,
     Files,
+    Plist,
 }
 
 impl std::error::Error for LaunchdError {}
@@ -13,6 +14,7 @@ impl fmt::Display for LaunchdError {
         match self {
             LaunchdError::Files => write!(f, "Failed to get PLIST files"),
             LaunchdError::UserPath => write!(f, "Failed to get user paths"),
+            LaunchdError::Plist => write!(f, "Failed to parse launchd PLIST file"),
         }
     }
 }
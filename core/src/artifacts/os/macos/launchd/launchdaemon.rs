@@ -16,22 +16,12 @@ use crate::{
 };
 use common::macos::LaunchdPlist;
 use log::{error, warn};
+use plist::Value;
 
 /// Grab `LuanchDaemons` and `LaunchAgents`
 pub(crate) fn grab_launchd(options: &LaunchdOptions) -> Result<Vec<LaunchdPlist>, LaunchdError> {
     if let Some(alt_file) = &options.alt_file {
-        let results = parse_plist_file_dict(alt_file);
-        let launchd_data = match results {
-            Ok(launchd_data_dictionary) => LaunchdPlist {
-                launchd_data: launchd_data_dictionary,
-                plist_path: alt_file.to_string(),
-            },
-            Err(err) => {
-                warn!("[launchd] Failed to parse plist file {alt_file}: {err:?}");
-                return Err(LaunchdError::Files);
-            }
-        };
-
+        let launchd_data = parse_launchd_plist(alt_file)?;
         return Ok(vec![launchd_data]);
     }
 
@@ -56,25 +46,7 @@ pub(crate) fn grab_launchd_daemons() -> Result<Vec<LaunchdPlist>, LaunchdError>
         Err(err) => warn!("[launchd] Failed to get system launchd daemon plist files: {err:?}"),
     }
 
-    let mut launchd_plist_vec: Vec<LaunchdPlist> = Vec::new();
-    for data in plist_files {
-        if !data.ends_with("plist") {
-            continue;
-        }
-
-        let launchd_results = parse_plist_file_dict(&data);
-        match launchd_results {
-            Ok(launchd_data_dictionary) => {
-                let launchd_data = LaunchdPlist {
-                    launchd_data: launchd_data_dictionary,
-                    plist_path: data,
-                };
-                launchd_plist_vec.push(launchd_data);
-            }
-            Err(err) => warn!("[launchd] Failed to parse plist file {data}: {err:?}"),
-        }
-    }
-    Ok(launchd_plist_vec)
+    Ok(parse_launchd_files(plist_files))
 }
 
 /// Get and parse System and User launchd Agents
@@ -92,25 +64,73 @@ pub(crate) fn grab_launchd_agents() -> Result<Vec<LaunchdPlist>, LaunchdError> {
         Err(err) => warn!("[launchd] Failed to get system launchd agent plist files: {err:?}"),
     }
 
+    Ok(parse_launchd_files(plist_files))
+}
+
+/// Parse launchd plist files. Malformed plists are skipped so one bad file does not stop the rest
+fn parse_launchd_files(plist_files: Vec<String>) -> Vec<LaunchdPlist> {
     let mut launchd_plist_vec: Vec<LaunchdPlist> = Vec::new();
     for data in plist_files {
         if !data.ends_with("plist") {
             continue;
         }
 
-        let launchd_results = parse_plist_file_dict(&data);
-        match launchd_results {
-            Ok(launchd_data_dictionary) => {
-                let launchd_data = LaunchdPlist {
-                    launchd_data: launchd_data_dictionary,
-                    plist_path: data,
-                };
-                launchd_plist_vec.push(launchd_data);
-            }
-            Err(err) => warn!("[launchd] Failed to parse plist file {data}: {err:?}"),
+        match parse_launchd_plist(&data) {
+            Ok(launchd_data) => launchd_plist_vec.push(launchd_data),
+            Err(err) => warn!("[launchd] Skipping plist file {data}: {err:?}"),
         }
     }
-    Ok(launchd_plist_vec)
+    launchd_plist_vec
+}
+
+/// Parse a binary or XML launchd plist and extract the common persistence keys
+fn parse_launchd_plist(path: &str) -> Result<LaunchdPlist, LaunchdError> {
+    let launchd_data = match parse_plist_file_dict(path) {
+        Ok(result) => result,
+        Err(err) => {
+            warn!("[launchd] Failed to parse plist file {path}: {err:?}");
+            return Err(LaunchdError::Plist);
+        }
+    };
+
+    let label = launchd_data
+        .get("Label")
+        .and_then(Value::as_string)
+        .unwrap_or_default()
+        .to_string();
+    let program = launchd_data
+        .get("Program")
+        .and_then(Value::as_string)
+        .unwrap_or_default()
+        .to_string();
+    let program_arguments = match launchd_data.get("ProgramArguments") {
+        Some(Value::Array(args)) => args
+            .iter()
+            .filter_map(Value::as_string)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    };
+    let run_at_load = launchd_data
+        .get("RunAtLoad")
+        .and_then(Value::as_boolean)
+        .unwrap_or(false);
+    let keep_alive = match launchd_data.get("KeepAlive") {
+        Some(Value::Boolean(result)) => *result,
+        // Conditions when launchd should keep the job alive
+        Some(Value::Dictionary(_)) => true,
+        _ => false,
+    };
+
+    Ok(LaunchdPlist {
+        launchd_data,
+        plist_path: path.to_string(),
+        label,
+        program,
+        program_arguments,
+        run_at_load,
+        keep_alive,
+    })
 }
 
 /// Get User Launchd daemons
@@ -127,6 +147,10 @@ fn system_launchd_daemons() -> Result<Vec<String>, LaunchdError> {
     ];
     let mut daemons: Vec<String> = Vec::new();
     for paths in system_daemons {
+        // Not every macOS version has all system directories
+        if !is_directory(paths) {
+            continue;
+        }
         let mut results = launchd_data(paths)?;
         daemons.append(&mut results);
     }
@@ -141,6 +165,10 @@ fn system_launchd_agents() -> Result<Vec<String>, LaunchdError> {
     ];
     let mut agents: Vec<String> = Vec::new();
     for paths in system_agents {
+        // Not every macOS version has all system directories
+        if !is_directory(paths) {
+            continue;
+        }
         let mut results = launchd_data(paths)?;
         agents.append(&mut results);
     }
@@ -190,13 +218,15 @@ fn launchd_data(path: &str) -> Result<Vec<String>, LaunchdError> {
 #[cfg(target_os = "macos")]
 mod tests {
     use super::{
-        grab_launchd, grab_launchd_agents, grab_launchd_daemons, system_launchd_agents,
-        system_launchd_daemons, user_launchd_agents, user_launchd_daemons,
+        grab_launchd, grab_launchd_agents, grab_launchd_daemons, parse_launchd_files,
+        parse_launchd_plist, system_launchd_agents, system_launchd_daemons, user_launchd_agents,
+        user_launchd_daemons,
     };
     use crate::{
         artifacts::os::macos::launchd::launchdaemon::launchd_data,
         structs::artifacts::os::macos::LaunchdOptions,
     };
+    use std::path::PathBuf;
 
     #[test]
     fn test_grab_launchd() {
@@ -243,4 +273,48 @@ mod tests {
         let results = launchd_data("/System/Library/LaunchAgents/").unwrap();
         assert!(results.len() > 5);
     }
+
+    #[test]
+    fn test_parse_launchd_plist_xml() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/macos/launchd/com.example.updater.plist");
+
+        let result = parse_launchd_plist(&test_location.display().to_string()).unwrap();
+        assert_eq!(result.label, "com.example.updater");
+        assert_eq!(result.program, "");
+        assert_eq!(
+            result.program_arguments,
+            vec!["/Users/Shared/.updater/updater", "--silent"]
+        );
+        assert_eq!(result.run_at_load, true);
+        assert_eq!(result.keep_alive, true);
+    }
+
+    #[test]
+    fn test_parse_launchd_plist_binary() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/macos/launchd/com.example.daemon.plist");
+
+        let result = parse_launchd_plist(&test_location.display().to_string()).unwrap();
+        assert_eq!(result.label, "com.example.daemon");
+        assert_eq!(result.program, "/usr/local/bin/exampled");
+        assert!(result.program_arguments.is_empty());
+        assert_eq!(result.run_at_load, false);
+        assert_eq!(result.keep_alive, true);
+    }
+
+    #[test]
+    fn test_parse_launchd_files_malformed() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/macos/launchd");
+
+        let files = launchd_data(&test_location.display().to_string()).unwrap();
+        assert_eq!(files.len(), 4);
+
+        let mut results = parse_launchd_files(files);
+        results.sort_by(|a, b| a.label.cmp(&b.label));
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].label, "com.example.daemon");
+        assert_eq!(results[1].label, "com.example.updater");
+    }
 }
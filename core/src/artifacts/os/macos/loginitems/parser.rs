@@ -81,8 +81,10 @@ pub(crate) fn grab_loginitems(
         }
     }
 
-    let mut app_loginitems = loginitem_apps_system()?;
-    loginitems_data.append(&mut app_loginitems);
+    match loginitem_apps_system() {
+        Ok(mut app_loginitems) => loginitems_data.append(&mut app_loginitems),
+        Err(err) => error!("[loginitems] Could not parse bundled app loginitems: {err:?}"),
+    }
 
     Ok(loginitems_data)
 }
@@ -0,0 +1,226 @@
+/**
+ * Bounded cache of resolved SIDs shared by artifacts during a collection
+ * The local users are read from the SAM the first time a SID is not cached. SIDs that do not resolve are cached as misses
+ * The least recently used SID is evicted once the cache is full
+ */
+use super::{error::AccountError, parser::get_users};
+use log::warn;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Mutex, OnceLock},
+};
+
+/// Max number of SIDs to keep in the cache
+const SID_CACHE_SIZE: usize = 1024;
+
+static SID_CACHE: OnceLock<Mutex<SidCache>> = OnceLock::new();
+
+#[derive(Debug)]
+pub(crate) struct SidCache {
+    capacity: usize,
+    entries: HashMap<String, Option<String>>,
+    /**Least recently used SID is at the front */
+    order: VecDeque<String>,
+    /**Local users from the SAM. Loaded on the first cache miss */
+    users: Option<HashMap<String, String>>,
+    /**Local users could not be read from the SAM */
+    users_failed: bool,
+}
+
+impl SidCache {
+    pub(crate) fn new(capacity: usize) -> SidCache {
+        SidCache {
+            capacity,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            users: None,
+            users_failed: false,
+        }
+    }
+
+    /// Get a cached SID. Returns `None` if the SID has not been resolved yet
+    pub(crate) fn get(&mut self, sid: &str) -> Option<Option<String>> {
+        let value = self.entries.get(sid)?.clone();
+        self.touch(sid);
+        Some(value)
+    }
+
+    /// Cache a resolved SID, or a miss if `name` is `None`. Evicts the least recently used SID if full
+    pub(crate) fn insert(&mut self, sid: &str, name: Option<String>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.insert(sid.to_string(), name).is_some() {
+            self.touch(sid);
+            return;
+        }
+
+        self.order.push_back(sid.to_string());
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    /// Resolve a SID using the cache first, otherwise the provided lookup
+    pub(crate) fn resolve<F>(&mut self, sid: &str, lookup: F) -> Option<String>
+    where
+        F: FnOnce(&str) -> Option<String>,
+    {
+        if let Some(value) = self.get(sid) {
+            return value;
+        }
+
+        let name = lookup(sid);
+        self.insert(sid, name.clone());
+        name
+    }
+
+    /// Load the local users if not loaded yet. Returns false if the users could not be read
+    fn load_users<F>(&mut self, loader: F) -> bool
+    where
+        F: FnOnce() -> Result<HashMap<String, String>, AccountError>,
+    {
+        if self.users.is_none() {
+            let users = match loader() {
+                Ok(result) => result,
+                Err(err) => {
+                    warn!("[accounts] Could not get users to resolve SIDs: {err:?}");
+                    self.users_failed = true;
+                    HashMap::new()
+                }
+            };
+            self.users = Some(users);
+        }
+        !self.users_failed
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Move a SID to the most recently used position
+    fn touch(&mut self, sid: &str) {
+        if let Some(index) = self.order.iter().position(|entry| entry == sid) {
+            if let Some(entry) = self.order.remove(index) {
+                self.order.push_back(entry);
+            }
+        }
+    }
+}
+
+/// Resolve a SID to a local username using the shared cache
+pub(crate) fn resolve_sid(sid: &str) -> Option<String> {
+    let cache = SID_CACHE.get_or_init(|| Mutex::new(SidCache::new(SID_CACHE_SIZE)));
+    let mut cache = match cache.lock() {
+        Ok(result) => result,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    if let Some(value) = cache.get(sid) {
+        return value;
+    }
+
+    cache.load_users(get_users);
+    let users = cache.users.take().unwrap_or_default();
+    let name = cache.resolve(sid, |sid| users.get(sid).cloned());
+    cache.users = Some(users);
+    name
+}
+
+/// Check if the local users could be read from the SAM. If false only well-known SIDs can be resolved
+pub(crate) fn local_users_available() -> bool {
+    let cache = SID_CACHE.get_or_init(|| Mutex::new(SidCache::new(SID_CACHE_SIZE)));
+    let mut cache = match cache.lock() {
+        Ok(result) => result,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    cache.load_users(get_users)
+}
+
+/// Remove all cached SIDs and users. Called at the start of each collection
+pub(crate) fn clear_sid_cache() {
+    if let Some(cache) = SID_CACHE.get() {
+        let mut cache = match cache.lock() {
+            Ok(result) => result,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *cache = SidCache::new(SID_CACHE_SIZE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SidCache;
+    use crate::artifacts::os::windows::accounts::error::AccountError;
+    use std::{cell::Cell, collections::HashMap};
+
+    #[test]
+    fn test_resolve_cached() {
+        let mut cache = SidCache::new(8);
+        let lookups = Cell::new(0);
+        let lookup = |_: &str| {
+            lookups.set(lookups.get() + 1);
+            Some(String::from("bob"))
+        };
+
+        let sid = "S-1-5-21-1079689790-2336414676-942872339-1001";
+        assert_eq!(cache.resolve(sid, lookup), Some(String::from("bob")));
+        assert_eq!(cache.resolve(sid, lookup), Some(String::from("bob")));
+        assert_eq!(cache.resolve(sid, lookup), Some(String::from("bob")));
+        assert_eq!(lookups.get(), 1);
+    }
+
+    #[test]
+    fn test_resolve_cached_miss() {
+        let mut cache = SidCache::new(8);
+        let lookups = Cell::new(0);
+        let lookup = |_: &str| {
+            lookups.set(lookups.get() + 1);
+            None
+        };
+
+        assert_eq!(cache.resolve("S-1-5-21-1-2-3-1002", lookup), None);
+        assert_eq!(cache.resolve("S-1-5-21-1-2-3-1002", lookup), None);
+        assert_eq!(lookups.get(), 1);
+        assert_eq!(cache.get("S-1-5-21-1-2-3-1002"), Some(None));
+    }
+
+    #[test]
+    fn test_insert_evicts_least_recent() {
+        let mut cache = SidCache::new(2);
+        cache.insert("S-1-5-21-1-2-3-1001", Some(String::from("bob")));
+        cache.insert("S-1-5-21-1-2-3-1002", Some(String::from("alice")));
+        // Use the first SID so the second is evicted
+        assert!(cache.get("S-1-5-21-1-2-3-1001").is_some());
+        cache.insert("S-1-5-21-1-2-3-1003", None);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("S-1-5-21-1-2-3-1002").is_none());
+        assert!(cache.get("S-1-5-21-1-2-3-1001").is_some());
+        assert!(cache.get("S-1-5-21-1-2-3-1003").is_some());
+    }
+
+    #[test]
+    fn test_load_users_failed() {
+        let mut cache = SidCache::new(8);
+        assert!(!cache.load_users(|| Err(AccountError::GetUserInfo)));
+        // Failure is remembered so the SAM is not read again
+        assert!(!cache.load_users(|| Ok(HashMap::new())));
+        assert_eq!(cache.users, Some(HashMap::new()));
+    }
+
+    #[test]
+    fn test_load_users() {
+        let mut cache = SidCache::new(8);
+        let mut users = HashMap::new();
+        users.insert(String::from("S-1-5-21-1-2-3-1001"), String::from("bob"));
+        assert!(cache.load_users(|| Ok(users)));
+        assert_eq!(
+            cache.users.as_ref().unwrap().get("S-1-5-21-1-2-3-1001"),
+            Some(&String::from("bob"))
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub(crate) mod cache;
 mod error;
 pub(crate) mod parser;
 mod users;
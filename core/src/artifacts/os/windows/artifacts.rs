@@ -1,5 +1,6 @@
 use super::jumplists::parser::grab_jumplists;
 use super::ntfs::parser::ntfs_filelist;
+use super::prefetch::parser::grab_prefetch_status;
 use super::recyclebin::parser::grab_recycle_bin;
 use super::registry::parser::parse_registry;
 use super::search::parser::grab_search;
@@ -7,20 +8,24 @@ use super::services::parser::grab_services;
 use super::tasks::parser::grab_tasks;
 use super::wmi::parser::grab_wmi_persist;
 use super::{
-    accounts::parser::grab_users, amcache::parser::grab_amcache, bits::parser::grab_bits,
-    error::WinArtifactError, eventlogs::parser::grab_eventlogs, prefetch::parser::grab_prefetch,
+    accounts::parser::grab_users, amcache::parser::grab_amcache, autoruns::parser::grab_autoruns,
+    bits::parser::collect_bits, error::WinArtifactError, eventlogs::parser::grab_eventlogs,
+    execution::parser::grab_execution, prefetch::parser::collect_prefetch,
     shellbags::parser::grab_shellbags, shimcache::parser::grab_shimcache,
-    shimdb::parser::grab_shimdb, shortcuts::parser::grab_lnk_directory, srum::parser::grab_srum,
+    shimdb::parser::grab_shimdb, shortcuts::parser::grab_shortcuts, srum::parser::grab_srum,
     userassist::parser::grab_userassist, usnjrnl::parser::grab_usnjrnl,
 };
 use crate::artifacts::output::output_artifact;
+use crate::filesystem::drives::get_drives;
 use crate::structs::artifacts::os::windows::{
-    AmcacheOptions, BitsOptions, EventLogsOptions, JumplistsOptions, PrefetchOptions,
-    RawFilesOptions, RecycleBinOptions, RegistryOptions, SearchOptions, ServicesOptions,
-    ShellbagsOptions, ShimcacheOptions, ShimdbOptions, ShortcutOptions, SrumOptions, TasksOptions,
-    UserAssistOptions, UsnJrnlOptions, WindowsUserOptions, WmiPersistOptions,
+    AmcacheOptions, AutorunsOptions, BitsOptions, EventLogsOptions, ExecutionOptions,
+    JumplistsOptions, PrefetchOptions, RawFilesOptions, RecycleBinOptions, RegistryOptions,
+    SearchOptions, ServicesOptions, ShellbagsOptions, ShimcacheOptions, ShimdbOptions,
+    ShortcutOptions, SrumOptions, TasksOptions, UserAssistOptions, UsnJrnlOptions,
+    WindowsUserOptions, WmiPersistOptions,
 };
 use crate::structs::toml::Output;
+use crate::utils::raw::{attach_filetime, attach_raw, include_raw, keep_raw_filetime, RawBudget};
 use crate::utils::time;
 use log::error;
 use serde_json::Value;
@@ -33,17 +38,19 @@ pub(crate) fn prefetch(
 ) -> Result<(), WinArtifactError> {
     let start_time = time::time_now();
 
-    let pf_results = grab_prefetch(options);
+    let pf_results = collect_prefetch(options, include_raw(output));
     let pf_data = match pf_results {
         Ok(results) => results,
         Err(err) => {
             error!("[artemis-core] Artemis failed to parse Prefetch: {err:?}");
+            // Report if the Prefetch directory is missing even though parsing failed
+            prefetch_status(options, 0, output, &start_time, filter)?;
             return Err(WinArtifactError::Prefetch);
         }
     };
 
-    let serde_data_result = serde_json::to_value(pf_data);
-    let serde_data = match serde_data_result {
+    let serde_data_result = serde_json::to_value(&pf_data);
+    let mut serde_data = match serde_data_result {
         Ok(results) => results,
         Err(err) => {
             error!("[artemis-core] Failed to serialize prefetch: {err:?}");
@@ -51,8 +58,64 @@ pub(crate) fn prefetch(
         }
     };
 
+    if include_raw(output) {
+        let mut budget = RawBudget::new(output);
+        if let Some(entries) = serde_data.as_array_mut() {
+            for (entry, pf) in entries.iter_mut().zip(&pf_data) {
+                let volumes = pf
+                    .raw_volumes
+                    .iter()
+                    .filter_map(|volume| budget.encode(volume))
+                    .map(Value::String)
+                    .collect();
+                attach_raw(entry, Value::Array(volumes));
+            }
+        }
+    }
+
+    if keep_raw_filetime(output) {
+        if let Some(entries) = serde_data.as_array_mut() {
+            for (entry, pf) in entries.iter_mut().zip(&pf_data) {
+                attach_filetime(entry, "last_run_time", Value::from(pf.last_run_filetime));
+                attach_filetime(
+                    entry,
+                    "all_run_times",
+                    Value::from(pf.all_run_filetimes.clone()),
+                );
+                attach_filetime(
+                    entry,
+                    "volume_creation",
+                    Value::from(pf.volume_creation_filetime.clone()),
+                );
+            }
+        }
+    }
+
     let output_name = "prefetch";
-    output_data(&serde_data, output_name, output, &start_time, filter)
+    output_data(&serde_data, output_name, output, &start_time, filter)?;
+    prefetch_status(options, pf_data.len(), output, &start_time, filter)
+}
+
+/// Output the `Prefetch` directory state. An empty directory may mean Prefetch is disabled
+fn prefetch_status(
+    options: &PrefetchOptions,
+    parsed: usize,
+    output: &mut Output,
+    start_time: &u64,
+    filter: &bool,
+) -> Result<(), WinArtifactError> {
+    let status = grab_prefetch_status(options, parsed);
+    let serde_data_result = serde_json::to_value(vec![status]);
+    let serde_data = match serde_data_result {
+        Ok(results) => results,
+        Err(err) => {
+            error!("[artemis-core] Failed to serialize prefetch status: {err:?}");
+            return Err(WinArtifactError::Serialize);
+        }
+    };
+
+    let output_name = "prefetch_status";
+    output_data(&serde_data, output_name, output, start_time, filter)
 }
 
 /// Parse the Windows `EventLogs` artifact
@@ -264,7 +327,7 @@ pub(crate) fn shortcuts(
 ) -> Result<(), WinArtifactError> {
     let start_time = time::time_now();
 
-    let artifact_result = grab_lnk_directory(&options.path);
+    let artifact_result = grab_shortcuts(options);
     let entries = match artifact_result {
         Ok(result) => result,
         Err(err) => {
@@ -290,6 +353,30 @@ pub(crate) fn usnjrnl(
     options: &UsnJrnlOptions,
     output: &mut Output,
     filter: &bool,
+) -> Result<(), WinArtifactError> {
+    if !options.all_drives.unwrap_or(false) {
+        return usnjrnl_drive(options, output, filter, None);
+    }
+
+    for drive in get_drives(options.removable_drives.unwrap_or(false)) {
+        let drive_options = UsnJrnlOptions {
+            alt_drive: Some(drive),
+            alt_path: None,
+            all_drives: None,
+            removable_drives: None,
+        };
+        // Continue to the next drive if one drive fails
+        let _ = usnjrnl_drive(&drive_options, output, filter, Some(&drive));
+    }
+    Ok(())
+}
+
+/// Parse the Windows `UsnJrnl` artifact and tag each entry with the source drive if provided
+fn usnjrnl_drive(
+    options: &UsnJrnlOptions,
+    output: &mut Output,
+    filter: &bool,
+    drive: Option<&char>,
 ) -> Result<(), WinArtifactError> {
     let start_time = time::time_now();
 
@@ -303,13 +390,16 @@ pub(crate) fn usnjrnl(
     };
 
     let serde_data_result = serde_json::to_value(entries);
-    let serde_data = match serde_data_result {
+    let mut serde_data = match serde_data_result {
         Ok(results) => results,
         Err(err) => {
             error!("[artemis-core] Failed to serialize usnjrnl: {err:?}");
             return Err(WinArtifactError::Serialize);
         }
     };
+    if let Some(letter) = drive {
+        tag_drive(&mut serde_data, letter);
+    }
     let output_name = "usnjrnl";
     output_data(&serde_data, output_name, output, &start_time, filter)
 }
@@ -322,7 +412,7 @@ pub(crate) fn bits(
 ) -> Result<(), WinArtifactError> {
     let start_time = time::time_now();
 
-    let artifact_result = grab_bits(options);
+    let artifact_result = collect_bits(options, include_raw(output));
     let entries = match artifact_result {
         Ok(result) => result,
         Err(err) => {
@@ -331,14 +421,29 @@ pub(crate) fn bits(
         }
     };
 
-    let serde_data_result = serde_json::to_value(entries);
-    let serde_data = match serde_data_result {
+    let serde_data_result = serde_json::to_value(&entries);
+    let mut serde_data = match serde_data_result {
         Ok(results) => results,
         Err(err) => {
             error!("[artemis-core] Failed to serialize bits: {err:?}");
             return Err(WinArtifactError::Serialize);
         }
     };
+
+    if include_raw(output) {
+        let mut budget = RawBudget::new(output);
+        if let Some(bits) = serde_data.get_mut("bits").and_then(Value::as_array_mut) {
+            for (entry, info) in bits.iter_mut().zip(&entries.bits) {
+                // Only BITS parsed from the ESE database have raw row data
+                if info.raw.is_empty() {
+                    continue;
+                }
+                if let Some(raw) = budget.encode(&info.raw) {
+                    attach_raw(entry, Value::String(raw));
+                }
+            }
+        }
+    }
     let output_name = "bits";
     output_data(&serde_data, output_name, output, &start_time, filter)
 }
@@ -410,6 +515,30 @@ pub(crate) fn tasks(
     options: &TasksOptions,
     output: &mut Output,
     filter: &bool,
+) -> Result<(), WinArtifactError> {
+    if !options.all_drives.unwrap_or(false) || options.alt_file.is_some() {
+        return tasks_drive(options, output, filter, None);
+    }
+
+    for drive in get_drives(options.removable_drives.unwrap_or(false)) {
+        let drive_options = TasksOptions {
+            alt_file: None,
+            alt_drive: Some(drive),
+            all_drives: None,
+            removable_drives: None,
+        };
+        // Continue to the next drive if one drive fails
+        let _ = tasks_drive(&drive_options, output, filter, Some(&drive));
+    }
+    Ok(())
+}
+
+/// Parse the Windows `Schedule Tasks` artifact and tag each task with the source drive if provided
+fn tasks_drive(
+    options: &TasksOptions,
+    output: &mut Output,
+    filter: &bool,
+    drive: Option<&char>,
 ) -> Result<(), WinArtifactError> {
     let start_time = time::time_now();
 
@@ -423,13 +552,16 @@ pub(crate) fn tasks(
     };
 
     let serde_data_result = serde_json::to_value(task_data);
-    let serde_data = match serde_data_result {
+    let mut serde_data = match serde_data_result {
         Ok(results) => results,
         Err(err) => {
             error!("[artemis-core] Failed to serialize tasks: {err:?}");
             return Err(WinArtifactError::Serialize);
         }
     };
+    if let Some(letter) = drive {
+        tag_drive(&mut serde_data, letter);
+    }
 
     let output_name = "tasks";
     output_data(&serde_data, output_name, output, &start_time, filter)
@@ -555,7 +687,95 @@ pub(crate) fn wmi_persist(
     output_data(&serde_data, output_name, output, &start_time, filter)
 }
 
+/// Get a merged Windows program execution timeline
+pub(crate) fn execution(
+    options: &ExecutionOptions,
+    output: &mut Output,
+    filter: &bool,
+) -> Result<(), WinArtifactError> {
+    let start_time = time::time_now();
+
+    let artifact_result = grab_execution(options);
+    let entries = match artifact_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Artemis failed to get execution events: {err:?}");
+            return Err(WinArtifactError::Execution);
+        }
+    };
+
+    let serde_data_result = serde_json::to_value(entries);
+    let serde_data = match serde_data_result {
+        Ok(results) => results,
+        Err(err) => {
+            error!("[artemis-core] Failed to serialize execution events: {err:?}");
+            return Err(WinArtifactError::Serialize);
+        }
+    };
+    let output_name = "execution";
+    output_data(&serde_data, output_name, output, &start_time, filter)
+}
+
+/// Parse the Windows autostart Registry locations
+pub(crate) fn autoruns(
+    options: &AutorunsOptions,
+    output: &mut Output,
+    filter: &bool,
+) -> Result<(), WinArtifactError> {
+    let start_time = time::time_now();
+
+    let autoruns_result = grab_autoruns(options);
+    let autoruns_data = match autoruns_result {
+        Ok(results) => results,
+        Err(err) => {
+            error!("[artemis-core] Artemis failed to parse autoruns: {err:?}");
+            return Err(WinArtifactError::Autoruns);
+        }
+    };
+
+    let serde_data_result = serde_json::to_value(autoruns_data);
+    let serde_data = match serde_data_result {
+        Ok(results) => results,
+        Err(err) => {
+            error!("[artemis-core] Failed to serialize autoruns: {err:?}");
+            return Err(WinArtifactError::Serialize);
+        }
+    };
+
+    let output_name = "autoruns";
+    output_data(&serde_data, output_name, output, &start_time, filter)
+}
+
 /// Output Windows artifacts
+/**
+ * Add a `source_drive` field to each record collected from a drive
+ * Arrays nested one level in an object (ex: `tasks` and `jobs`) are also tagged
+ */
+fn tag_drive(serde_data: &mut Value, drive: &char) {
+    let tag = |records: &mut Vec<Value>| {
+        for record in records.iter_mut() {
+            if let Value::Object(entries) = record {
+                entries.insert(
+                    String::from("source_drive"),
+                    Value::String(drive.to_string()),
+                );
+            }
+        }
+    };
+
+    match serde_data {
+        Value::Array(records) => tag(records),
+        Value::Object(entries) => {
+            for value in entries.values_mut() {
+                if let Value::Array(records) = value {
+                    tag(records);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 pub(crate) fn output_data(
     serde_data: &Value,
     output_name: &str,
@@ -579,22 +799,23 @@ pub(crate) fn output_data(
 mod tests {
     use crate::{
         artifacts::os::windows::artifacts::{
-            amcache, bits, eventlogs, jumplists, output_data, prefetch, raw_filelist, recycle_bin,
-            registry, search, services, shellbags, shimcache, shimdb, shortcuts, srum, tasks,
-            userassist, users_windows, usnjrnl, wmi_persist,
+            amcache, autoruns, bits, eventlogs, execution, jumplists, output_data, prefetch,
+            raw_filelist, recycle_bin, registry, search, services, shellbags, shimcache, shimdb,
+            shortcuts, srum, tag_drive, tasks, userassist, users_windows, usnjrnl, wmi_persist,
         },
         structs::{
             artifacts::os::windows::{
-                AmcacheOptions, BitsOptions, EventLogsOptions, JumplistsOptions, PrefetchOptions,
-                RawFilesOptions, RecycleBinOptions, RegistryOptions, SearchOptions,
-                ServicesOptions, ShellbagsOptions, ShimcacheOptions, ShimdbOptions,
-                ShortcutOptions, SrumOptions, TasksOptions, UserAssistOptions, UsnJrnlOptions,
-                WindowsUserOptions, WmiPersistOptions,
+                AmcacheOptions, AutorunsOptions, BitsOptions, EventLogsOptions, ExecutionOptions,
+                JumplistsOptions, PrefetchOptions, RawFilesOptions, RecycleBinOptions,
+                RegistryOptions, SearchOptions, ServicesOptions, ShellbagsOptions,
+                ShimcacheOptions, ShimdbOptions, ShortcutOptions, SrumOptions, TasksOptions,
+                UserAssistOptions, UsnJrnlOptions, WindowsUserOptions, WmiPersistOptions,
             },
             toml::Output,
         },
         utils::time,
     };
+    use serde_json::json;
     use std::path::PathBuf;
 
     fn output_options(name: &str, format: &str, directory: &str, compress: bool) -> Output {
@@ -608,15 +829,18 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: String::from("local"),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
     #[test]
     fn test_eventlogs() {
-        let evt = EventLogsOptions { alt_file: None };
+        let evt = EventLogsOptions {
+            alt_file: None,
+            alt_drive: None,
+            channels: None,
+            event_ids: None,
+        };
         let mut output = output_options("eventlogs_temp", "json", "./tmp", true);
 
         let status = eventlogs(&evt, &mut output, &false).unwrap();
@@ -634,7 +858,11 @@ mod tests {
 
     #[test]
     fn test_prefetch() {
-        let pf = PrefetchOptions { alt_dir: None };
+        let pf = PrefetchOptions {
+            alt_dir: None,
+            entropy: None,
+            max_last_run_times: None,
+        };
         let mut output = output_options("prefetch_temp", "json", "./tmp", false);
 
         let status = prefetch(&pf, &mut output, &false).unwrap();
@@ -701,6 +929,7 @@ mod tests {
         let options = ShellbagsOptions {
             alt_file: None,
             resolve_guids: false,
+            alt_drive: None,
         };
         let mut output = output_options("bags_temp", "json", "./tmp", false);
 
@@ -723,6 +952,8 @@ mod tests {
         let options = UsnJrnlOptions {
             alt_drive: None,
             alt_path: None,
+            all_drives: None,
+            removable_drives: None,
         };
         let mut output = output_options("usn_temp", "json", "./tmp", false);
 
@@ -736,7 +967,7 @@ mod tests {
         test_location.push("tests/test_data/windows/lnk/win11");
 
         let options = ShortcutOptions {
-            path: test_location.display().to_string(),
+            path: Some(test_location.display().to_string()),
         };
         let mut output = output_options("shortcuts_temp", "json", "./tmp", false);
 
@@ -749,6 +980,9 @@ mod tests {
         let options = BitsOptions {
             alt_file: None,
             carve: false,
+            max_carved: None,
+            carve_threads: None,
+            carve_chunk_size: None,
         };
         let mut output = output_options("bits_temp", "json", "./tmp", false);
 
@@ -765,6 +999,17 @@ mod tests {
         assert_eq!(status, ());
     }
 
+    #[test]
+    fn test_execution() {
+        let options = ExecutionOptions {
+            sources: Vec::new(),
+        };
+        let mut output = output_options("execution_temp", "json", "./tmp", false);
+
+        let status = execution(&options, &mut output, &false).unwrap();
+        assert_eq!(status, ());
+    }
+
     #[test]
     #[ignore = "Takes a long time"]
     fn test_search() {
@@ -785,6 +1030,15 @@ mod tests {
         assert_eq!(status, ());
     }
 
+    #[test]
+    fn test_autoruns() {
+        let options = AutorunsOptions { alt_drive: None };
+        let mut output = output_options("autoruns_temp", "json", "./tmp", false);
+
+        let status = autoruns(&options, &mut output, &false).unwrap();
+        assert_eq!(status, ());
+    }
+
     #[test]
     fn test_users_windows() {
         let options = WindowsUserOptions { alt_file: None };
@@ -796,7 +1050,12 @@ mod tests {
 
     #[test]
     fn test_tasks() {
-        let options = TasksOptions { alt_file: None };
+        let options = TasksOptions {
+            alt_file: None,
+            alt_drive: None,
+            all_drives: None,
+            removable_drives: None,
+        };
         let mut output = output_options("tasks_temp", "json", "./tmp", false);
 
         let status = tasks(&options, &mut output, &false).unwrap();
@@ -805,7 +1064,10 @@ mod tests {
 
     #[test]
     fn test_services() {
-        let options = ServicesOptions { alt_file: None };
+        let options = ServicesOptions {
+            alt_file: None,
+            alt_drive: None,
+        };
         let mut output = output_options("services_temp", "json", "./tmp", false);
 
         let status = services(&options, &mut output, &false).unwrap();
@@ -840,4 +1102,30 @@ mod tests {
         let status = output_data(&data, name, &mut output, &start_time, &false).unwrap();
         assert_eq!(status, ());
     }
+
+    #[test]
+    fn test_tag_drive() {
+        let mut data = json!([{"name": "a"}, {"name": "b"}]);
+        tag_drive(&mut data, &'D');
+        assert_eq!(data[1]["source_drive"], "D");
+
+        let mut data = json!({"tasks": [{"name": "a"}], "jobs": [{"name": "b"}]});
+        tag_drive(&mut data, &'E');
+        assert_eq!(data["tasks"][0]["source_drive"], "E");
+        assert_eq!(data["jobs"][0]["source_drive"], "E");
+    }
+
+    #[test]
+    fn test_usnjrnl_all_drives() {
+        let options = UsnJrnlOptions {
+            alt_drive: None,
+            alt_path: None,
+            all_drives: Some(true),
+            removable_drives: None,
+        };
+        let mut output = output_options("usn_all_drives", "json", "./tmp", false);
+
+        let status = usnjrnl(&options, &mut output, &false).unwrap();
+        assert_eq!(status, ());
+    }
 }
@@ -0,0 +1,20 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum AutorunsError {
+    RegistryFiles,
+    AutorunsData,
+    DriveLetter,
+}
+
+impl std::error::Error for AutorunsError {}
+
+impl fmt::Display for AutorunsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AutorunsError::RegistryFiles => write!(f, "Could not get Registry files"),
+            AutorunsError::AutorunsData => write!(f, "Could not get autoruns data"),
+            AutorunsError::DriveLetter => write!(f, "Failed to get systemdrive letter"),
+        }
+    }
+}
@@ -0,0 +1,255 @@
+/**
+ * Curated list of Registry autostart locations checked by the autoruns parser
+ * To check a new location add an entry to `AUTORUN_LOCATIONS`
+ *
+ * References:
+ * `https://learn.microsoft.com/en-us/sysinternals/downloads/autoruns`
+ * `https://attack.mitre.org/techniques/T1547/001/`
+ */
+use crate::utils::regex_options::create_regex;
+use regex::Regex;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum AutorunHive {
+    System,
+    Software,
+    NtUser,
+}
+
+impl AutorunHive {
+    /// Prefix used when displaying a location in this hive
+    fn prefix(&self) -> &str {
+        match self {
+            AutorunHive::System => "HKLM\\SYSTEM",
+            AutorunHive::Software => "HKLM\\SOFTWARE",
+            AutorunHive::NtUser => "HKCU",
+        }
+    }
+}
+
+pub(crate) struct AutorunLocation {
+    pub(crate) hive: AutorunHive,
+    /**Key path below the hive root. A `*` matches any part of a single key name */
+    pub(crate) key: &'static str,
+    /**Value names to collect. Empty collects all values in the key */
+    pub(crate) values: &'static [&'static str],
+}
+
+pub(crate) const AUTORUN_LOCATIONS: &[AutorunLocation] = &[
+    AutorunLocation {
+        hive: AutorunHive::Software,
+        key: "Microsoft\\Windows\\CurrentVersion\\Run",
+        values: &[],
+    },
+    AutorunLocation {
+        hive: AutorunHive::Software,
+        key: "Microsoft\\Windows\\CurrentVersion\\RunOnce",
+        values: &[],
+    },
+    AutorunLocation {
+        hive: AutorunHive::Software,
+        key: "Microsoft\\Windows\\CurrentVersion\\Policies\\Explorer\\Run",
+        values: &[],
+    },
+    AutorunLocation {
+        hive: AutorunHive::Software,
+        key: "WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Run",
+        values: &[],
+    },
+    AutorunLocation {
+        hive: AutorunHive::Software,
+        key: "WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\RunOnce",
+        values: &[],
+    },
+    AutorunLocation {
+        hive: AutorunHive::Software,
+        key: "Microsoft\\Windows NT\\CurrentVersion\\Winlogon",
+        values: &["Shell", "Userinit", "Taskman"],
+    },
+    AutorunLocation {
+        hive: AutorunHive::Software,
+        key: "Microsoft\\Windows NT\\CurrentVersion\\Windows",
+        values: &["AppInit_DLLs"],
+    },
+    AutorunLocation {
+        hive: AutorunHive::Software,
+        key: "WOW6432Node\\Microsoft\\Windows NT\\CurrentVersion\\Windows",
+        values: &["AppInit_DLLs"],
+    },
+    AutorunLocation {
+        hive: AutorunHive::Software,
+        key: "Microsoft\\Windows NT\\CurrentVersion\\Image File Execution Options\\*",
+        values: &["Debugger"],
+    },
+    AutorunLocation {
+        hive: AutorunHive::Software,
+        key: "Microsoft\\Windows\\CurrentVersion\\Explorer\\ShellServiceObjectDelayLoad",
+        values: &[],
+    },
+    AutorunLocation {
+        hive: AutorunHive::Software,
+        key: "Microsoft\\Active Setup\\Installed Components\\*",
+        values: &["StubPath"],
+    },
+    AutorunLocation {
+        hive: AutorunHive::System,
+        key: "ControlSet*\\Control\\Session Manager",
+        values: &["BootExecute"],
+    },
+    AutorunLocation {
+        hive: AutorunHive::System,
+        key: "ControlSet*\\Control\\SafeBoot",
+        values: &["AlternateShell"],
+    },
+    AutorunLocation {
+        hive: AutorunHive::System,
+        key: "ControlSet*\\Control\\Lsa",
+        values: &[
+            "Authentication Packages",
+            "Notification Packages",
+            "Security Packages",
+        ],
+    },
+    AutorunLocation {
+        hive: AutorunHive::NtUser,
+        key: "Software\\Microsoft\\Windows\\CurrentVersion\\Run",
+        values: &[],
+    },
+    AutorunLocation {
+        hive: AutorunHive::NtUser,
+        key: "Software\\Microsoft\\Windows\\CurrentVersion\\RunOnce",
+        values: &[],
+    },
+    AutorunLocation {
+        hive: AutorunHive::NtUser,
+        key: "Software\\Microsoft\\Windows\\CurrentVersion\\Policies\\Explorer\\Run",
+        values: &[],
+    },
+    AutorunLocation {
+        hive: AutorunHive::NtUser,
+        key: "Software\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Run",
+        values: &[],
+    },
+    AutorunLocation {
+        hive: AutorunHive::NtUser,
+        key: "Software\\Microsoft\\Windows NT\\CurrentVersion\\Windows",
+        values: &["Load", "Run"],
+    },
+    AutorunLocation {
+        hive: AutorunHive::NtUser,
+        key: "Software\\Microsoft\\Windows NT\\CurrentVersion\\Winlogon",
+        values: &["Shell"],
+    },
+    AutorunLocation {
+        hive: AutorunHive::NtUser,
+        key: "Software\\Microsoft\\Command Processor",
+        values: &["AutoRun"],
+    },
+    AutorunLocation {
+        hive: AutorunHive::NtUser,
+        key: "Environment",
+        values: &["UserInitMprLogonScript"],
+    },
+];
+
+impl AutorunLocation {
+    /// Location as displayed in the output. Ex: `HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Run`
+    pub(crate) fn display(&self) -> String {
+        format!("{}\\{}", self.hive.prefix(), self.key)
+    }
+
+    /// Regex pattern matching the lowercase full Registry key path, including the hive root key
+    fn pattern(&self) -> String {
+        let key = self
+            .key
+            .to_lowercase()
+            .split('*')
+            .map(regex::escape)
+            .collect::<Vec<String>>()
+            .join(r"[^\\]*");
+        format!(r"^[^\\]+\\{key}$")
+    }
+
+    /// Check if the lowercase Registry key path is this location
+    pub(crate) fn matches(&self, path: &str) -> bool {
+        match create_regex(&self.pattern()) {
+            Ok(result) => result.is_match(path),
+            Err(_err) => false,
+        }
+    }
+
+    /// Check if the value name should be collected
+    pub(crate) fn wants_value(&self, name: &str) -> bool {
+        self.values.is_empty()
+            || self
+                .values
+                .iter()
+                .any(|value| value.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Get all the autostart locations in a hive
+pub(crate) fn hive_locations(hive: &AutorunHive) -> Vec<&'static AutorunLocation> {
+    AUTORUN_LOCATIONS
+        .iter()
+        .filter(|location| &location.hive == hive)
+        .collect()
+}
+
+/// Create a single regex matching any autostart location in the hive
+pub(crate) fn hive_regex(hive: &AutorunHive) -> Regex {
+    let patterns: Vec<String> = hive_locations(hive)
+        .iter()
+        .map(|location| format!("(?:{})", location.pattern()))
+        .collect();
+    create_regex(&patterns.join("|")).unwrap() // always valid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hive_locations, hive_regex, AutorunHive, AUTORUN_LOCATIONS};
+
+    #[test]
+    fn test_hive_regex() {
+        let regex = hive_regex(&AutorunHive::NtUser);
+        assert!(regex.is_match("root\\software\\microsoft\\windows\\currentversion\\run"));
+        assert!(regex.is_match("cmi-createhive{d43b12b8-09b5-40db-b4f6-f6dfeb78daec}\\environment"));
+        assert!(
+            !regex.is_match("root\\software\\microsoft\\windows\\currentversion\\runonce\\setup")
+        );
+        assert!(!regex.is_match("root\\microsoft\\windows\\currentversion\\run"));
+
+        let regex = hive_regex(&AutorunHive::System);
+        assert!(regex.is_match("root\\controlset001\\control\\session manager"));
+        assert!(!regex.is_match("root\\controlset001\\control\\session manager\\environment"));
+    }
+
+    #[test]
+    fn test_location_matches() {
+        let location = &AUTORUN_LOCATIONS[8];
+        assert!(location.matches(
+            "root\\microsoft\\windows nt\\currentversion\\image file execution options\\sethc.exe"
+        ));
+        assert!(!location
+            .matches("root\\microsoft\\windows nt\\currentversion\\image file execution options"));
+        assert_eq!(
+            location.display(),
+            "HKLM\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Image File Execution Options\\*"
+        );
+    }
+
+    #[test]
+    fn test_wants_value() {
+        assert!(AUTORUN_LOCATIONS[0].wants_value("OneDrive"));
+        assert!(AUTORUN_LOCATIONS[5].wants_value("userinit"));
+        assert!(!AUTORUN_LOCATIONS[5].wants_value("DefaultUserName"));
+    }
+
+    #[test]
+    fn test_hive_locations() {
+        let total = hive_locations(&AutorunHive::System).len()
+            + hive_locations(&AutorunHive::Software).len()
+            + hive_locations(&AutorunHive::NtUser).len();
+        assert_eq!(total, AUTORUN_LOCATIONS.len());
+    }
+}
@@ -0,0 +1,3 @@
+mod error;
+mod locations;
+pub(crate) mod parser;
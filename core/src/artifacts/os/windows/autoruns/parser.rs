@@ -0,0 +1,281 @@
+/**
+ * Windows autostart (autoruns) Registry locations are a common form of persistence.
+ * Programs listed in keys such as `Run` and `RunOnce` are executed when the system boots or a user logs on.
+ * A curated list of locations in the SYSTEM, SOFTWARE, and NTUSER.DAT Registry files is checked
+ *
+ * References:
+ * `https://learn.microsoft.com/en-us/sysinternals/downloads/autoruns`
+ * `https://attack.mitre.org/techniques/T1547/001/`
+ *
+ * Other Parsers:
+ * `https://learn.microsoft.com/en-us/sysinternals/downloads/autoruns`
+ * `https://github.com/Velocidex/velociraptor`
+ */
+use super::{
+    error::AutorunsError,
+    locations::{hive_locations, hive_regex, AutorunHive},
+};
+use crate::{
+    artifacts::os::windows::registry::helper::{get_registry_keys, get_registry_keys_by_ref},
+    filesystem::ntfs::{raw_files::get_user_registry_files, setup::setup_ntfs_parser},
+    structs::artifacts::os::windows::AutorunsOptions,
+    utils::environment::get_systemdrive,
+};
+use common::windows::{AutorunEntry, RegistryEntry};
+use log::error;
+
+/// Parse autostart Registry locations based on `AutorunsOptions`
+pub(crate) fn grab_autoruns(options: &AutorunsOptions) -> Result<Vec<AutorunEntry>, AutorunsError> {
+    if let Some(drive) = &options.alt_drive {
+        return alt_drive(drive);
+    }
+
+    let drive_result = get_systemdrive();
+    let drive = match drive_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[autoruns] Could not determine systemdrive: {err:?}");
+            return Err(AutorunsError::DriveLetter);
+        }
+    };
+    alt_drive(&drive)
+}
+
+/// Get autostart entries from the SYSTEM, SOFTWARE, and all NTUSER.DAT Registry files on a drive
+pub(crate) fn alt_drive(drive: &char) -> Result<Vec<AutorunEntry>, AutorunsError> {
+    let mut entries = Vec::new();
+    let system_hives = [
+        (
+            AutorunHive::System,
+            format!("{drive}:\\Windows\\System32\\config\\SYSTEM"),
+        ),
+        (
+            AutorunHive::Software,
+            format!("{drive}:\\Windows\\System32\\config\\SOFTWARE"),
+        ),
+    ];
+    for (hive, path) in system_hives {
+        match hive_file_autoruns(&path, &hive) {
+            Ok(mut result) => entries.append(&mut result),
+            Err(err) => {
+                error!("[autoruns] Could not get autoruns from {path}: {err:?}");
+                continue;
+            }
+        }
+    }
+
+    entries.append(&mut user_autoruns(drive)?);
+    Ok(entries)
+}
+
+/// Get autostart entries from a Registry file at provided path
+fn hive_file_autoruns(path: &str, hive: &AutorunHive) -> Result<Vec<AutorunEntry>, AutorunsError> {
+    let start_path = "";
+    let reg_results = get_registry_keys(start_path, &hive_regex(hive), path);
+    let regs = match reg_results {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[autoruns] Could not parse {path}: {err:?}");
+            return Err(AutorunsError::AutorunsData);
+        }
+    };
+
+    Ok(autorun_entries(&regs, hive, path))
+}
+
+/// Get autostart entries from all NTUSER.DAT files on a drive
+fn user_autoruns(drive: &char) -> Result<Vec<AutorunEntry>, AutorunsError> {
+    let user_reg_results = get_user_registry_files(drive);
+    let user_hives = match user_reg_results {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[autoruns] Could not get user hives: {err:?}");
+            return Err(AutorunsError::RegistryFiles);
+        }
+    };
+
+    let parser_result = setup_ntfs_parser(drive);
+    let mut ntfs_parser = match parser_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[autoruns] Could not create ntfs parser: {err:?}");
+            return Err(AutorunsError::AutorunsData);
+        }
+    };
+
+    let regex = hive_regex(&AutorunHive::NtUser);
+    let start_path = "";
+
+    let mut entries = Vec::new();
+    for hive in user_hives {
+        if hive.filename != "NTUSER.DAT" {
+            continue;
+        }
+        let reg_results =
+            get_registry_keys_by_ref(start_path, &regex, &hive.reg_reference, &mut ntfs_parser);
+        match reg_results {
+            Ok(result) => entries.append(&mut autorun_entries(
+                &result,
+                &AutorunHive::NtUser,
+                &hive.full_path,
+            )),
+            Err(err) => {
+                error!("[autoruns] Could not parse {}: {err:?}", hive.full_path);
+                continue;
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Create an entry for each wanted value in the Registry keys that are autostart locations
+fn autorun_entries(
+    regs: &[RegistryEntry],
+    hive: &AutorunHive,
+    reg_file: &str,
+) -> Vec<AutorunEntry> {
+    let locations = hive_locations(hive);
+
+    let mut entries = Vec::new();
+    for reg in regs {
+        let path = reg.path.to_lowercase();
+        let location = match locations.iter().find(|location| location.matches(&path)) {
+            Some(result) => result,
+            None => continue,
+        };
+
+        for value in &reg.values {
+            if !location.wants_value(&value.value) {
+                continue;
+            }
+            entries.push(AutorunEntry {
+                location: location.display(),
+                value_name: value.value.clone(),
+                command: value.data.clone(),
+                data_type: value.data_type.clone(),
+                last_modified: reg.last_modified,
+                reg_path: reg.path.clone(),
+                reg_file: reg_file.to_string(),
+            });
+        }
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::autorun_entries;
+    use crate::artifacts::os::windows::autoruns::locations::AutorunHive;
+    use common::windows::{KeyValue, RegistryEntry};
+
+    fn reg_entry(path: &str, values: &[(&str, &str)]) -> RegistryEntry {
+        let (key, name) = path.rsplit_once('\\').unwrap();
+        RegistryEntry {
+            path: path.to_string(),
+            key: key.to_string(),
+            name: name.to_string(),
+            values: values
+                .iter()
+                .map(|(value, data)| KeyValue {
+                    value: value.to_string(),
+                    data: data.to_string(),
+                    data_type: String::from("REG_SZ"),
+                })
+                .collect(),
+            last_modified: 1700000000,
+            depth: path.matches('\\').count(),
+            security_offset: 0,
+        }
+    }
+
+    #[test]
+    fn test_autorun_entries() {
+        let regs = vec![
+            reg_entry(
+                "ROOT\\Microsoft\\Windows NT\\CurrentVersion\\Winlogon",
+                &[
+                    ("Userinit", "C:\\Windows\\system32\\userinit.exe,"),
+                    ("DefaultUserName", "bob"),
+                ],
+            ),
+            reg_entry(
+                "ROOT\\Microsoft\\Windows\\CurrentVersion\\Run",
+                &[(
+                    "SecurityHealth",
+                    "%windir%\\system32\\SecurityHealthSystray.exe",
+                )],
+            ),
+            reg_entry(
+                "ROOT\\Microsoft\\Windows\\CurrentVersion\\Explorer",
+                &[("GlobalAssocChangedCounter", "3")],
+            ),
+        ];
+
+        let results = autorun_entries(&regs, &AutorunHive::Software, "SOFTWARE");
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[0].location,
+            "HKLM\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Winlogon"
+        );
+        assert_eq!(results[0].value_name, "Userinit");
+        assert_eq!(results[0].command, "C:\\Windows\\system32\\userinit.exe,");
+        assert_eq!(results[0].last_modified, 1700000000);
+        assert_eq!(results[1].value_name, "SecurityHealth");
+        assert_eq!(
+            results[1].reg_path,
+            "ROOT\\Microsoft\\Windows\\CurrentVersion\\Run"
+        );
+        assert_eq!(results[1].reg_file, "SOFTWARE");
+    }
+
+    #[test]
+    fn test_autorun_entries_wrong_hive() {
+        let regs = vec![reg_entry(
+            "ROOT\\Microsoft\\Windows\\CurrentVersion\\Run",
+            &[(
+                "SecurityHealth",
+                "%windir%\\system32\\SecurityHealthSystray.exe",
+            )],
+        )];
+
+        let results = autorun_entries(&regs, &AutorunHive::NtUser, "NTUSER.DAT");
+        assert!(results.is_empty());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_os = "windows")]
+mod windows_tests {
+    use super::{alt_drive, grab_autoruns, hive_file_autoruns};
+    use crate::{
+        artifacts::os::windows::autoruns::locations::AutorunHive,
+        structs::artifacts::os::windows::AutorunsOptions,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_grab_autoruns() {
+        let options = AutorunsOptions { alt_drive: None };
+        let results = grab_autoruns(&options).unwrap();
+        assert!(results.len() > 3);
+    }
+
+    #[test]
+    fn test_alt_drive() {
+        let results = alt_drive(&'C').unwrap();
+        assert!(results.len() > 3);
+    }
+
+    #[test]
+    fn test_hive_file_autoruns() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests\\test_data\\windows\\registry\\win10\\NTUSER.DAT");
+
+        let results =
+            hive_file_autoruns(&test_location.display().to_string(), &AutorunHive::NtUser).unwrap();
+        assert!(!results.is_empty());
+        assert!(results.iter().any(
+            |entry| entry.location == "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Run"
+        ));
+    }
+}
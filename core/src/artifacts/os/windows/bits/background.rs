@@ -6,30 +6,114 @@ use super::{
 };
 use crate::{
     artifacts::os::windows::ese::{
-        helper::{get_all_pages, get_catalog_info, get_page_data},
+        catalog::Catalog,
+        helper::{
+            get_all_pages, get_all_pages_reader, get_catalog_info, get_catalog_reader,
+            get_page_data, get_page_data_reader,
+        },
         tables::table_info,
     },
-    filesystem::{files::is_file, ntfs::raw_files::raw_read_file},
+    filesystem::provider::{FileProvider, ReadSeek},
+    utils::encoding::base64_decode_standard,
+};
+use common::windows::{BitsFormat, BitsInfo, FileInfo, TableDump, WindowsBits};
+use log::{error, warn};
+use std::{
+    collections::{HashMap, HashSet},
+    io::BufReader,
 };
-use common::windows::{BitsInfo, TableDump, WindowsBits};
-use log::error;
+
+/**
+ * Parse BITS data on the provided systemdrive without needing to know the format ahead of time  
+ * Probes for the ESE `qmgr.db` (Win10+) first and falls back to the legacy `qmgr0.dat` and `qmgr1.dat` files
+ */
+pub(crate) fn parse_bits(
+    systemdrive: &char,
+    carve: bool,
+    provider: &dyn FileProvider,
+    include_raw: bool,
+) -> Result<WindowsBits, BitsError> {
+    let ese_path = format!("{systemdrive}:\\ProgramData\\Microsoft\\Network\\Downloader\\qmgr.db");
+    if is_provider_file(&ese_path, provider) {
+        return parse_ese_bits(&ese_path, carve, provider, include_raw);
+    }
+    parse_legacy_bits(systemdrive, carve, provider)
+}
+
+/// Check if the `FileProvider` has a file at the provided path
+fn is_provider_file(path: &str, provider: &dyn FileProvider) -> bool {
+    match provider.metadata(path) {
+        Ok(result) => result.is_file,
+        Err(_err) => false,
+    }
+}
 
 /**
  * Parse modern version (Win10+) of BITS which is an ESE database by dumping the `Jobs` and `Files` tables and parsing their contents  
+ * The ESE tables and carved data are read through the `FileProvider`
  */
-pub(crate) fn parse_ese_bits(bits_path: &str, carve: bool) -> Result<WindowsBits, BitsError> {
-    // Dump the Jobs and Files tables from the BITS database
-    let files = get_bits_ese(bits_path, "Files")?;
-    let jobs_info = get_bits_ese(bits_path, "Jobs")?;
+pub(crate) fn parse_ese_bits(
+    bits_path: &str,
+    carve: bool,
+    provider: &dyn FileProvider,
+    include_raw: bool,
+) -> Result<WindowsBits, BitsError> {
+    // Dump the Jobs and Files tables from the BITS database. Jobs can still be returned if the Files table is corrupt
+    let mut ese_tables = get_bits_ese_tables(bits_path, &["Jobs", "Files"], provider)?;
+    let jobs_info = match ese_tables.tables.remove("Jobs") {
+        Some(result) => result,
+        None => {
+            error!("[bits] Could not dump Jobs table at {bits_path}");
+            return Err(BitsError::ParseEse);
+        }
+    };
 
     let jobs = get_jobs(&jobs_info)?;
+    // Raw row bytes are only needed when they are attached to the output
+    let job_blobs = if include_raw {
+        get_job_blobs(&jobs_info)
+    } else {
+        HashMap::new()
+    };
+
+    let files_info = match ese_tables.tables.get("Files") {
+        Some(files) => match get_files(files) {
+            Ok(result) => Some(result),
+            Err(err) => {
+                warn!("[bits] Could not parse Files table at {bits_path}: {err:?}");
+                None
+            }
+        },
+        None => {
+            if let Some(err) = ese_tables.errors.get("Files") {
+                warn!("[bits] Could not dump Files table at {bits_path}: {err:?}");
+            }
+            None
+        }
+    };
+    let partial = files_info.is_none();
+    if partial {
+        warn!("[bits] Files table at {bits_path} is unrecoverable. Returning job info only");
+    }
 
-    let files_info = get_files(&files)?;
+    // Job only entries are returned with empty file info
+    let empty_file = [FileInfo {
+        file_id: String::new(),
+        filename: String::new(),
+        full_path: String::new(),
+        tmp_fullpath: String::new(),
+        drive: String::new(),
+        volume: String::new(),
+        url: String::new(),
+        download_bytes_size: 0,
+        transfer_bytes_size: 0,
+        files_transferred: 0,
+    }];
     let mut bits_info: Vec<BitsInfo> = Vec::new();
 
     for job in &jobs {
-        for file in &files_info {
-            if job.file_id == file.file_id {
+        for file in files_info.as_deref().unwrap_or(&empty_file[..]) {
+            if partial || job.file_id == file.file_id {
                 let bit_info = BitsInfo {
                     job_id: job.job_id.clone(),
                     file_id: job.file_id.clone(),
@@ -45,6 +129,9 @@ pub(crate) fn parse_ese_bits(bits_path: &str, carve: bool) -> Result<WindowsBits
                     job_description: job.job_description.clone(),
                     job_command: job.job_command.clone(),
                     job_arguments: job.job_arguments.clone(),
+                    notify_command: job.notify_command.clone(),
+                    notify_args: job.notify_args.clone(),
+                    custom_headers: job.custom_headers.clone(),
                     error_count: job.error_count,
                     job_type: job.job_type.clone(),
                     job_state: job.job_state.clone(),
@@ -61,8 +148,11 @@ pub(crate) fn parse_ese_bits(bits_path: &str, carve: bool) -> Result<WindowsBits
                     retry_delay: job.retry_delay,
                     transient_error_count: job.transient_error_count,
                     acls: job.acls.clone(),
+                    acls_raw: job.acls_raw.clone(),
                     additional_sids: job.additional_sids.clone(),
                     carved: false,
+                    source_format: BitsFormat::Ese,
+                    raw: job_blobs.get(&job.job_id).cloned().unwrap_or_default(),
                 };
                 bits_info.push(bit_info);
             }
@@ -73,11 +163,13 @@ pub(crate) fn parse_ese_bits(bits_path: &str, carve: bool) -> Result<WindowsBits
         bits: bits_info,
         carved_jobs: Vec::new(),
         carved_files: Vec::new(),
+        partial,
+        users_resolved: false,
     };
     // If we are carving and since this is ESE bits we currently do not combine job and file info
     if carve {
         let is_legacy = false;
-        let read_result = raw_read_file(bits_path);
+        let read_result = provider.read(bits_path);
         if read_result.is_ok() {
             let (_carved_bits, mut carved_jobs, mut carved_files) =
                 parse_carve(&read_result.unwrap_or_default(), is_legacy);
@@ -93,6 +185,30 @@ pub(crate) fn parse_ese_bits(bits_path: &str, carve: bool) -> Result<WindowsBits
     Ok(windows_bits)
 }
 
+/// Get the raw `Blob` column bytes of each row in the `Jobs` table by job ID
+fn get_job_blobs(column_rows: &[Vec<TableDump>]) -> HashMap<String, Vec<u8>> {
+    let mut blobs = HashMap::new();
+    for rows in column_rows {
+        let mut job_id = String::new();
+        let mut blob = Vec::new();
+        for column in rows {
+            if column.column_name == "Id" {
+                job_id.clone_from(&column.column_data);
+            } else if column.column_name == "Blob" {
+                blob = base64_decode_standard(&column.column_data).unwrap_or_default();
+            }
+        }
+        blobs.insert(job_id, blob);
+    }
+    blobs
+}
+
+/// Tables dumped from an ESE database and the tables that could not be dumped
+pub(crate) struct EseTables {
+    pub(crate) tables: HashMap<String, Vec<Vec<TableDump>>>,
+    pub(crate) errors: HashMap<String, BitsError>,
+}
+
 /// Extract BITs info from ESE database
 pub(crate) fn get_bits_ese(path: &str, table: &str) -> Result<Vec<Vec<TableDump>>, BitsError> {
     let catalog_result = get_catalog_info(path);
@@ -104,8 +220,78 @@ pub(crate) fn get_bits_ese(path: &str, table: &str) -> Result<Vec<Vec<TableDump>
         }
     };
 
-    let mut info = table_info(&catalog, table);
-    let pages_result = get_all_pages(path, &(info.table_page as u32));
+    get_table_rows(path, &catalog, table, &mut None)
+}
+
+/**
+ * Extract multiple tables from the BITS ESE database  
+ * A table that fails to dump is recorded in `errors` instead of failing the whole database. Only a bad catalog returns an error  
+ * The live system reads the database by path (raw NTFS on Windows). Other providers are read with `FileProvider::open`
+ */
+pub(crate) fn get_bits_ese_tables(
+    path: &str,
+    tables: &[&str],
+    provider: &dyn FileProvider,
+) -> Result<EseTables, BitsError> {
+    let mut reader = if provider.is_live() {
+        None
+    } else {
+        match provider.open(path) {
+            Ok(result) => Some(BufReader::new(result)),
+            Err(err) => {
+                error!("[bits] Failed to open {path}: {err:?}");
+                return Err(BitsError::ReadFile);
+            }
+        }
+    };
+
+    let catalog_result = match reader.as_mut() {
+        Some(fs) => get_catalog_reader(fs),
+        None => get_catalog_info(path),
+    };
+    let catalog = match catalog_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[bits] Failed to parse {path} catalog: {err:?}");
+            return Err(BitsError::ParseEse);
+        }
+    };
+
+    let mut ese_tables = EseTables {
+        tables: HashMap::new(),
+        errors: HashMap::new(),
+    };
+    for table in tables {
+        match get_table_rows(path, &catalog, table, &mut reader) {
+            Ok(result) => {
+                ese_tables.tables.insert(table.to_string(), result);
+            }
+            Err(err) => {
+                ese_tables.errors.insert(table.to_string(), err);
+            }
+        }
+    }
+
+    Ok(ese_tables)
+}
+
+/// Dump the rows of a table using the ESE catalog. Reads from `reader` if provided, otherwise from the path
+fn get_table_rows(
+    path: &str,
+    catalog: &[Catalog],
+    table: &str,
+    reader: &mut Option<BufReader<Box<dyn ReadSeek>>>,
+) -> Result<Vec<Vec<TableDump>>, BitsError> {
+    let mut info = table_info(catalog, table);
+    if info.table_name.is_empty() {
+        error!("[bits] Could not find {table} table in {path} catalog");
+        return Err(BitsError::ParseEse);
+    }
+
+    let pages_result = match reader.as_mut() {
+        Some(fs) => get_all_pages_reader(fs, &(info.table_page as u32)),
+        None => get_all_pages(path, &(info.table_page as u32)),
+    };
     let pages = match pages_result {
         Ok(result) => result,
         Err(err) => {
@@ -114,7 +300,10 @@ pub(crate) fn get_bits_ese(path: &str, table: &str) -> Result<Vec<Vec<TableDump>
         }
     };
 
-    let rows_results = get_page_data(path, &pages, &mut info, table);
+    let rows_results = match reader.as_mut() {
+        Some(fs) => get_page_data_reader(fs, &pages, &mut info, table),
+        None => get_page_data(path, &pages, &mut info, table),
+    };
     let table_rows = match rows_results {
         Ok(result) => result,
         Err(err) => {
@@ -129,7 +318,11 @@ pub(crate) fn get_bits_ese(path: &str, table: &str) -> Result<Vec<Vec<TableDump>
 /**
  * Parse older version (pre-Win10) of BITS which is a custom binary format
  */
-pub(crate) fn parse_legacy_bits(systemdrive: &char, carve: bool) -> Result<WindowsBits, BitsError> {
+pub(crate) fn parse_legacy_bits(
+    systemdrive: &char,
+    carve: bool,
+    provider: &dyn FileProvider,
+) -> Result<WindowsBits, BitsError> {
     let mut bits_path =
         format!("{systemdrive}:\\ProgramData\\Microsoft\\Network\\Downloader\\qmgr0.dat");
 
@@ -137,33 +330,56 @@ pub(crate) fn parse_legacy_bits(systemdrive: &char, carve: bool) -> Result<Windo
         bits: Vec::new(),
         carved_jobs: Vec::new(),
         carved_files: Vec::new(),
+        partial: false,
+        users_resolved: false,
     };
-    if is_file(&bits_path) {
-        let mut results = legacy_bits(&bits_path, carve)?;
+    if is_provider_file(&bits_path, provider) {
+        let mut results = legacy_bits(&bits_path, carve, provider)?;
         windows_bits.bits.append(&mut results.bits);
         windows_bits.carved_files.append(&mut results.carved_files);
         windows_bits.carved_jobs.append(&mut results.carved_jobs);
     }
     // Legacy BITS has two (2) files
     bits_path = format!("{systemdrive}:\\ProgramData\\Microsoft\\Network\\Downloader\\qmgr1.dat");
-    if is_file(&bits_path) {
-        let mut results = legacy_bits(&bits_path, carve)?;
+    if is_provider_file(&bits_path, provider) {
+        let mut results = legacy_bits(&bits_path, carve, provider)?;
         windows_bits.bits.append(&mut results.bits);
         windows_bits.carved_files.append(&mut results.carved_files);
         windows_bits.carved_jobs.append(&mut results.carved_jobs);
     }
 
+    // qmgr0.dat and qmgr1.dat usually contain copies of the same jobs
+    merge_legacy_bits(&mut windows_bits.bits);
     Ok(windows_bits)
 }
 
+/// Remove duplicate entries found in both legacy BITS files
+fn merge_legacy_bits(bits: &mut Vec<BitsInfo>) {
+    let mut seen = HashSet::new();
+    bits.retain(|entry| {
+        seen.insert((
+            entry.job_id.clone(),
+            entry.full_path.clone(),
+            entry.modified,
+            entry.carved,
+        ))
+    });
+}
+
 /// Parse the older BITS file
-pub(crate) fn legacy_bits(path: &str, carve: bool) -> Result<WindowsBits, BitsError> {
+pub(crate) fn legacy_bits(
+    path: &str,
+    carve: bool,
+    provider: &dyn FileProvider,
+) -> Result<WindowsBits, BitsError> {
     let mut windows_bits = WindowsBits {
         bits: Vec::new(),
         carved_jobs: Vec::new(),
         carved_files: Vec::new(),
+        partial: false,
+        users_resolved: false,
     };
-    let read_results = raw_read_file(path);
+    let read_results = provider.read(path);
     let bits_data = match read_results {
         Ok(results) => results,
         Err(err) => {
@@ -175,7 +391,7 @@ pub(crate) fn legacy_bits(path: &str, carve: bool) -> Result<WindowsBits, BitsEr
     windows_bits.bits.append(&mut bits);
 
     if carve {
-        let is_legacy = false;
+        let is_legacy = true;
         let (mut carved_bits, mut carved_jobs, mut carved_files) =
             parse_carve(&bits_data, is_legacy);
         windows_bits.carved_jobs.append(&mut carved_jobs);
@@ -209,18 +425,49 @@ mod tests {
     use super::parse_ese_bits;
     use crate::{
         artifacts::os::windows::bits::background::{
-            get_bits_ese, legacy_bits, parse_carve, parse_legacy_bits,
+            get_bits_ese, get_bits_ese_tables, legacy_bits, merge_legacy_bits, parse_bits,
+            parse_carve, parse_legacy_bits,
         },
-        filesystem::files::read_file,
+        filesystem::{files::read_file, provider::LiveFileProvider},
     };
+    use common::windows::BitsFormat;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_parse_bits() {
+        let results = parse_bits(&'C', false, &LiveFileProvider { raw_ntfs: true }, false).unwrap();
+        for entry in results.bits {
+            assert!(
+                entry.source_format == BitsFormat::Ese || entry.source_format == BitsFormat::Legacy
+            );
+        }
+    }
+
     #[test]
     fn test_parse_ese_bits() {
         let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_location.push("tests\\test_data\\windows\\ese\\win10\\qmgr.db");
-        let results = parse_ese_bits(test_location.to_str().unwrap(), false).unwrap();
+        let results = parse_ese_bits(
+            test_location.to_str().unwrap(),
+            false,
+            &LiveFileProvider { raw_ntfs: true },
+            false,
+        )
+        .unwrap();
         assert_eq!(results.bits.len(), 1);
+        assert_eq!(results.bits[0].source_format, BitsFormat::Ese);
+        // Raw row bytes are only read when requested
+        assert!(results.bits[0].raw.is_empty());
+        assert!(!results.partial);
+
+        let results = parse_ese_bits(
+            test_location.to_str().unwrap(),
+            false,
+            &LiveFileProvider { raw_ntfs: true },
+            true,
+        )
+        .unwrap();
+        assert!(!results.bits[0].raw.is_empty());
     }
 
     #[test]
@@ -231,9 +478,42 @@ mod tests {
         assert_eq!(results.len(), 1);
     }
 
+    #[test]
+    fn test_parse_ese_bits_partial() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests\\test_data\\windows\\ese\\win10\\qmgr_missing_files.db");
+        let results = parse_ese_bits(
+            test_location.to_str().unwrap(),
+            false,
+            &LiveFileProvider { raw_ntfs: true },
+            false,
+        )
+        .unwrap();
+        assert!(results.partial);
+        assert_eq!(results.bits.len(), 1);
+        assert!(!results.bits[0].job_id.is_empty());
+        assert!(results.bits[0].full_path.is_empty());
+        assert!(results.bits[0].url.is_empty());
+    }
+
+    #[test]
+    fn test_get_bits_ese_tables() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests\\test_data\\windows\\ese\\win10\\qmgr_missing_files.db");
+        let results = get_bits_ese_tables(
+            test_location.to_str().unwrap(),
+            &["Jobs", "Files"],
+            &LiveFileProvider { raw_ntfs: true },
+        )
+        .unwrap();
+        assert_eq!(results.tables.get("Jobs").unwrap().len(), 1);
+        assert!(results.tables.get("Files").is_none());
+        assert!(results.errors.contains_key("Files"));
+    }
+
     #[test]
     fn test_parse_legacy_bits() {
-        let results = parse_legacy_bits(&'C', false).unwrap();
+        let results = parse_legacy_bits(&'C', false, &LiveFileProvider { raw_ntfs: true }).unwrap();
         assert_eq!(results.bits.is_empty(), true);
     }
 
@@ -241,8 +521,14 @@ mod tests {
     fn test_legacy_bits() {
         let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_location.push("tests\\test_data\\windows\\bits\\win81\\qmgr0.dat");
-        let results = legacy_bits(&test_location.to_str().unwrap(), false).unwrap();
+        let results = legacy_bits(
+            &test_location.to_str().unwrap(),
+            false,
+            &LiveFileProvider { raw_ntfs: true },
+        )
+        .unwrap();
         assert_eq!(results.bits.len(), 1);
+        assert_eq!(results.bits[0].source_format, BitsFormat::Legacy);
     }
 
     #[test]
@@ -254,4 +540,70 @@ mod tests {
         assert_eq!(jobs.len(), 86);
         assert_eq!(files.len(), 41);
     }
+
+    #[test]
+    fn test_parse_ese_bits_carve() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests\\test_data\\windows\\ese\\win10\\qmgr.db");
+        let results = parse_ese_bits(
+            test_location.to_str().unwrap(),
+            true,
+            &LiveFileProvider { raw_ntfs: true },
+            false,
+        )
+        .unwrap();
+        assert!(!results.bits.is_empty());
+        assert!(results
+            .bits
+            .iter()
+            .all(|entry| entry.source_format == BitsFormat::Ese));
+        assert!(!results.carved_jobs.is_empty());
+    }
+
+    #[test]
+    fn test_legacy_bits_carve() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests\\test_data\\windows\\bits\\win81\\qmgr0.dat");
+        let results = legacy_bits(
+            &test_location.to_str().unwrap(),
+            true,
+            &LiveFileProvider { raw_ntfs: true },
+        )
+        .unwrap();
+        // Legacy carving combines the job and file info
+        assert_eq!(results.bits.len(), 21);
+        assert_eq!(results.bits.iter().filter(|entry| entry.carved).count(), 20);
+        assert!(results.carved_jobs.is_empty());
+        assert!(results.carved_files.is_empty());
+
+        // ESE carving keeps the jobs separate and cannot find the legacy file info
+        let data = read_file(test_location.to_str().unwrap()).unwrap();
+        let (bits, jobs, files) = parse_carve(&data, false);
+        assert!(bits.is_empty());
+        assert_eq!(jobs.len(), 20);
+        assert!(files.is_empty());
+    }
+
+    #[test]
+    fn test_merge_legacy_bits() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests\\test_data\\windows\\bits\\win81\\qmgr0.dat");
+        let mut results = legacy_bits(
+            &test_location.to_str().unwrap(),
+            false,
+            &LiveFileProvider { raw_ntfs: true },
+        )
+        .unwrap();
+        let mut copy = legacy_bits(
+            &test_location.to_str().unwrap(),
+            false,
+            &LiveFileProvider { raw_ntfs: true },
+        )
+        .unwrap();
+        results.bits.append(&mut copy.bits);
+        assert_eq!(results.bits.len(), 2);
+
+        merge_legacy_bits(&mut results.bits);
+        assert_eq!(results.bits.len(), 1);
+    }
 }
@@ -1,6 +1,20 @@
-use crate::utils::nom_helper::{nom_unsigned_four_bytes, nom_unsigned_sixteen_bytes, Endian};
-use common::windows::{BitsInfo, FileInfo, JobFlags, JobInfo, JobPriority, JobState, JobType};
+use crate::{
+    structs::artifacts::os::windows::BitsOptions,
+    utils::nom_helper::{nom_unsigned_four_bytes, nom_unsigned_sixteen_bytes, Endian},
+};
+use common::windows::{
+    BitsFormat, BitsInfo, FileInfo, JobFlags, JobInfo, JobPriority, JobState, JobType, WindowsBits,
+};
+use log::warn;
 use nom::bytes::complete::take_until;
+use std::{
+    cell::Cell,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
 
 use super::{
     files::get_legacy_files,
@@ -9,63 +23,137 @@ use super::{
 
 pub(crate) type WinBits = (Vec<BitsInfo>, Vec<JobInfo>, Vec<FileInfo>);
 
+/// Known BITS job delimiters
+const JOB_DELIMITERS: [[u8; 16]; 10] = [
+    [
+        147, 54, 32, 53, 160, 12, 16, 74, 132, 243, 177, 126, 123, 73, 156, 215,
+    ],
+    [
+        16, 19, 112, 200, 54, 83, 179, 65, 131, 229, 129, 85, 127, 54, 27, 135,
+    ],
+    [
+        140, 147, 234, 100, 3, 15, 104, 64, 180, 111, 249, 127, 229, 29, 77, 205,
+    ],
+    [
+        179, 70, 237, 61, 59, 16, 249, 68, 188, 47, 232, 55, 139, 211, 25, 134,
+    ],
+    [
+        161, 86, 9, 225, 67, 175, 201, 66, 146, 230, 111, 152, 86, 235, 167, 246,
+    ],
+    [
+        159, 149, 212, 76, 100, 112, 242, 75, 132, 215, 71, 106, 126, 98, 105, 159,
+    ],
+    [
+        241, 25, 38, 169, 50, 3, 191, 76, 148, 39, 137, 136, 24, 149, 136, 49,
+    ],
+    [
+        193, 51, 188, 221, 251, 90, 175, 77, 184, 161, 34, 104, 179, 157, 1, 173,
+    ],
+    [
+        208, 87, 86, 143, 44, 1, 62, 78, 173, 44, 244, 165, 215, 101, 111, 175,
+    ],
+    [
+        80, 103, 65, 148, 87, 3, 29, 70, 164, 204, 93, 217, 153, 7, 6, 228,
+    ],
+];
+
+/// Delimiter for ESE BITS file data
+const FILE_DELIMITER: [u8; 16] = [
+    228, 207, 158, 81, 70, 217, 151, 67, 183, 62, 38, 133, 19, 5, 26, 178,
+];
+
+/// Chunks are aligned to this size if the data is not an ESE database
+const DEFAULT_PAGE_SIZE: usize = 4096;
+
+/// Options for scanning data for carving delimiters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CarveScan {
+    /// Number of threads used to scan for delimiters
+    pub(crate) threads: usize,
+    /// Size in bytes of each scanned chunk. Rounded up to a page boundary
+    pub(crate) chunk_size: Option<usize>,
+}
+
+thread_local! {
+    /// Scan options for the BITS parser running on the current thread
+    static CARVE_SCAN: Cell<CarveScan> = const {
+        Cell::new(CarveScan {
+            threads: 1,
+            chunk_size: None,
+        })
+    };
+}
+
+/// Get the scan options from the `BITS` options
+pub(crate) fn carve_scan(options: &BitsOptions) -> CarveScan {
+    CarveScan {
+        threads: options.carve_threads.unwrap_or(1).max(1),
+        chunk_size: options.carve_chunk_size,
+    }
+}
+
+/// Run `func` with the provided carving scan options on the current thread
+pub(crate) fn with_carve_scan<T, F>(scan: CarveScan, func: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let previous = CARVE_SCAN.with(|current| current.replace(scan));
+    let result = func();
+    CARVE_SCAN.with(|current| current.set(previous));
+    result
+}
+
 /// Attempt to carve out BITS data by looking for known job and file delimiters
 pub(crate) fn carve_bits(data: &[u8], is_legacy: bool) -> nom::IResult<&[u8], WinBits> {
-    let job_delimiters = vec![
-        [
-            147, 54, 32, 53, 160, 12, 16, 74, 132, 243, 177, 126, 123, 73, 156, 215,
-        ],
-        [
-            16, 19, 112, 200, 54, 83, 179, 65, 131, 229, 129, 85, 127, 54, 27, 135,
-        ],
-        [
-            140, 147, 234, 100, 3, 15, 104, 64, 180, 111, 249, 127, 229, 29, 77, 205,
-        ],
-        [
-            179, 70, 237, 61, 59, 16, 249, 68, 188, 47, 232, 55, 139, 211, 25, 134,
-        ],
-        [
-            161, 86, 9, 225, 67, 175, 201, 66, 146, 230, 111, 152, 86, 235, 167, 246,
-        ],
-        [
-            159, 149, 212, 76, 100, 112, 242, 75, 132, 215, 71, 106, 126, 98, 105, 159,
-        ],
-        [
-            241, 25, 38, 169, 50, 3, 191, 76, 148, 39, 137, 136, 24, 149, 136, 49,
-        ],
-        [
-            193, 51, 188, 221, 251, 90, 175, 77, 184, 161, 34, 104, 179, 157, 1, 173,
-        ],
-        [
-            208, 87, 86, 143, 44, 1, 62, 78, 173, 44, 244, 165, 215, 101, 111, 175,
-        ],
-        [
-            80, 103, 65, 148, 87, 3, 29, 70, 164, 204, 93, 217, 153, 7, 6, 228,
-        ],
-    ];
+    let scan = CARVE_SCAN.with(Cell::get);
+    carve_bits_scan(data, is_legacy, &scan)
+}
+
+/**
+ * Carve BITS data using the provided scan options  
+ * Finding the delimiters is the slow part of carving large databases. The data is split into page aligned chunks that are scanned in parallel  
+ * The records are then parsed in order from the full data, so records straddling a chunk boundary are not missed
+ */
+fn carve_bits_scan<'a>(
+    data: &'a [u8],
+    is_legacy: bool,
+    scan: &CarveScan,
+) -> nom::IResult<&'a [u8], WinBits> {
+    let mut delimiters: Vec<&[u8]> = JOB_DELIMITERS.iter().map(|job| job.as_slice()).collect();
+    if !is_legacy {
+        delimiters.push(FILE_DELIMITER.as_slice());
+    }
+    let hits = delimiter_hits(data, &delimiters, scan, carve_page_size(data, is_legacy));
 
-    let mut job_data = data;
+    // Offset of the remaining data
+    let offset = |remaining: &[u8]| data.len() - remaining.len();
+    let mut job_offset = 0;
     let mut bits = Vec::new();
     let mut jobs = Vec::new();
     let mut files = Vec::new();
     let carve = true;
+    let source_format = if is_legacy {
+        BitsFormat::Legacy
+    } else {
+        BitsFormat::Ese
+    };
 
     // Start by scanning for known job delimiters
-    for job in job_delimiters {
-        while !job_data.is_empty() {
-            let scan_results = scan_delimiter(job_data, &job);
+    for job_hits in hits.iter().take(JOB_DELIMITERS.len()) {
+        while job_offset < data.len() {
             // If no hits move on to next delimiter
-            let hit_data = match scan_results {
-                Ok((input, _)) => input,
-                Err(_err) => break,
+            let hit = match next_hit(job_hits, job_offset) {
+                Some(result) => result,
+                None => break,
             };
+            let hit_data = &data[hit..];
 
             let (input, _) = nom_unsigned_sixteen_bytes(hit_data, Endian::Le)?;
             let (_, job_type_value) = nom_unsigned_four_bytes(input, Endian::Le)?;
             let job_type = get_type(&job_type_value);
 
-            if job_type == JobType::Unknown {
-                job_data = input;
+            if matches!(job_type, JobType::Unknown(_)) {
+                job_offset = offset(input);
                 continue;
             }
 
@@ -81,13 +169,17 @@ pub(crate) fn carve_bits(data: &[u8], is_legacy: bool) -> nom::IResult<&[u8], Wi
                 job_description: String::new(),
                 job_command: String::new(),
                 job_arguments: String::new(),
+                notify_command: String::new(),
+                notify_args: String::new(),
+                custom_headers: Vec::new(),
                 error_count: 0,
-                job_type: JobType::Unknown,
-                job_state: JobState::Unknown,
-                priority: JobPriority::Unknown,
-                flags: JobFlags::Unknown,
+                job_type: JobType::Unknown(0),
+                job_state: JobState::Unknown(0),
+                priority: JobPriority::Unknown(0),
+                flags: JobFlags::Unknown(0),
                 http_method: String::new(),
                 acls: Vec::new(),
+                acls_raw: String::new(),
                 additional_sids: Vec::new(),
                 transient_error_count: 0,
                 retry_delay: 0,
@@ -100,15 +192,20 @@ pub(crate) fn carve_bits(data: &[u8], is_legacy: bool) -> nom::IResult<&[u8], Wi
                 let (remaining_input, file) = get_legacy_files(input, is_legacy, carve)?;
                 let (remaining_input, _) = job_details(remaining_input, &mut job, is_legacy)?;
 
-                job_data = remaining_input;
+                job_offset = offset(remaining_input);
                 let carved = true;
-                bits.push(combine_file_and_job(&job, &file, carved));
+                bits.push(combine_file_and_job(
+                    &job,
+                    &file,
+                    carved,
+                    source_format.clone(),
+                ));
                 continue;
             }
             let remaining_input_result = job_details(input, &mut job, is_legacy);
             match remaining_input_result {
-                Ok((result, _)) => job_data = result,
-                Err(_) => job_data = &[],
+                Ok((result, _)) => job_offset = offset(result),
+                Err(_) => job_offset = data.len(),
             }
             jobs.push(job);
         }
@@ -121,27 +218,30 @@ pub(crate) fn carve_bits(data: &[u8], is_legacy: bool) -> nom::IResult<&[u8], Wi
     // For ESE BITS since the file data is in a separate table it may be located at a completely different offset than the job data table
     // (In legacy BITs the file data is part of the BITS job entry)
     // So we scan for file data using a separate loop
-    let mut file_data = data;
-    while !file_data.is_empty() {
-        let scan_results = get_legacy_files(file_data, is_legacy, carve);
+    let file_hits = &hits[JOB_DELIMITERS.len()];
+    let mut file_offset = 0;
+    while file_offset < data.len() {
+        let scan_results = match next_hit(file_hits, file_offset) {
+            Some(hit) => get_legacy_files(&data[hit..], is_legacy, carve),
+            None => Err(nom::Err::Error(nom::error::Error::new(
+                &data[file_offset..],
+                nom::error::ErrorKind::TakeUntil,
+            ))),
+        };
         let (hit_data, file) = match scan_results {
             Ok(results) => results,
             Err(_err) => {
                 // Before we break because of parsing error, check one more time for the file delimiter
                 // If we find another delimiter, keep trying to parse the data
-                let (check_data, _) = nom_unsigned_sixteen_bytes(file_data, Endian::Le)?;
-                let file_delimiter = [
-                    228, 207, 158, 81, 70, 217, 151, 67, 183, 62, 38, 133, 19, 5, 26, 178,
-                ];
-                let scan_results = scan_delimiter(check_data, &file_delimiter);
-                match scan_results {
-                    Ok((input, _)) => file_data = input,
-                    Err(_err) => break,
+                let _ = nom_unsigned_sixteen_bytes(&data[file_offset..], Endian::Le)?;
+                match next_hit(file_hits, file_offset + FILE_DELIMITER.len()) {
+                    Some(hit) => file_offset = hit,
+                    None => break,
                 };
                 continue;
             }
         };
-        file_data = hit_data;
+        file_offset = offset(hit_data);
         if file.full_path.is_empty() {
             continue;
         }
@@ -150,8 +250,123 @@ pub(crate) fn carve_bits(data: &[u8], is_legacy: bool) -> nom::IResult<&[u8], Wi
     Ok((data, (bits, jobs, files)))
 }
 
-/// The legacy BITS format has both job and file info in same structure, we combine them both here into one structure
-pub(crate) fn combine_file_and_job(job: &JobInfo, file: &FileInfo, carved: bool) -> BitsInfo {
+/// Get the first delimiter hit at or after `offset`
+fn next_hit(hits: &[usize], offset: usize) -> Option<usize> {
+    let index = hits.partition_point(|hit| *hit < offset);
+    hits.get(index).copied()
+}
+
+/// Get the page size of an ESE database. Other data uses the default page size
+fn carve_page_size(data: &[u8], is_legacy: bool) -> usize {
+    if is_legacy {
+        return DEFAULT_PAGE_SIZE;
+    }
+
+    let page_offset = 236;
+    let page_result = data
+        .get(page_offset..)
+        .map(|input| nom_unsigned_four_bytes(input, Endian::Le));
+    match page_result {
+        Some(Ok((_, size))) if size.is_power_of_two() && (4096..=32768).contains(&size) => {
+            size as usize
+        }
+        _ => DEFAULT_PAGE_SIZE,
+    }
+}
+
+/**
+ * Find the offsets of every delimiter in the data. Returns the sorted offsets for each delimiter  
+ * Chunks overlap by the delimiter size so delimiters straddling a chunk boundary are found. Hits are kept by the chunk they start in
+ */
+fn delimiter_hits(
+    data: &[u8],
+    delimiters: &[&[u8]],
+    scan: &CarveScan,
+    page_size: usize,
+) -> Vec<Vec<usize>> {
+    let chunk_size = match scan.chunk_size {
+        Some(size) => size,
+        None => data.len().div_ceil(scan.threads.max(1)),
+    };
+    let chunk_size = chunk_size.max(1).div_ceil(page_size) * page_size;
+    let chunks: Vec<(usize, usize)> = (0..data.len())
+        .step_by(chunk_size)
+        .map(|start| (start, (start + chunk_size).min(data.len())))
+        .collect();
+
+    let mut chunk_hits: Vec<Vec<Vec<usize>>> = if scan.threads <= 1 || chunks.len() <= 1 {
+        chunks
+            .iter()
+            .map(|(start, end)| chunk_delimiter_hits(data, delimiters, *start, *end))
+            .collect()
+    } else {
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(Vec::new());
+        thread::scope(|scope| {
+            for _ in 0..scan.threads.min(chunks.len()) {
+                scope.spawn(|| {
+                    while let Some((start, end)) = chunks.get(next.fetch_add(1, Ordering::SeqCst)) {
+                        let hits = chunk_delimiter_hits(data, delimiters, *start, *end);
+                        if let Ok(mut results) = results.lock() {
+                            results.push((*start, hits));
+                        }
+                    }
+                });
+            }
+        });
+        let mut results = results.into_inner().unwrap_or_default();
+        results.sort_by_key(|(start, _)| *start);
+        results.into_iter().map(|(_, hits)| hits).collect()
+    };
+
+    // Chunks are in order, so appending keeps the offsets sorted
+    let mut hits = vec![Vec::new(); delimiters.len()];
+    for chunk in &mut chunk_hits {
+        for (delimiter_hits, chunk_delimiter_hits) in hits.iter_mut().zip(chunk.iter_mut()) {
+            delimiter_hits.append(chunk_delimiter_hits);
+        }
+    }
+    hits
+}
+
+/// Find the offsets of every delimiter that starts between `start` and `end`
+fn chunk_delimiter_hits(
+    data: &[u8],
+    delimiters: &[&[u8]],
+    start: usize,
+    end: usize,
+) -> Vec<Vec<usize>> {
+    let mut hits = Vec::new();
+    for delimiter in delimiters {
+        let overlap = delimiter.len().saturating_sub(1);
+        let chunk = &data[start..(end + overlap).min(data.len())];
+
+        let mut offsets = Vec::new();
+        let mut chunk_offset = 0;
+        while chunk_offset < chunk.len() {
+            let hit_data = match scan_delimiter(&chunk[chunk_offset..], delimiter) {
+                Ok((input, _)) => input,
+                Err(_err) => break,
+            };
+            let hit = chunk.len() - hit_data.len();
+            if start + hit >= end {
+                break;
+            }
+            offsets.push(start + hit);
+            chunk_offset = hit + 1;
+        }
+        hits.push(offsets);
+    }
+    hits
+}
+
+/// Combine job and file info into one structure. The legacy BITS format has both in the same structure
+pub(crate) fn combine_file_and_job(
+    job: &JobInfo,
+    file: &FileInfo,
+    carved: bool,
+    source_format: BitsFormat,
+) -> BitsInfo {
     BitsInfo {
         job_id: job.job_id.clone(),
         file_id: job.file_id.clone(),
@@ -166,6 +381,9 @@ pub(crate) fn combine_file_and_job(job: &JobInfo, file: &FileInfo, carved: bool)
         job_description: job.job_description.clone(),
         job_command: job.job_command.clone(),
         job_arguments: job.job_arguments.clone(),
+        notify_command: job.notify_command.clone(),
+        notify_args: job.notify_args.clone(),
+        custom_headers: job.custom_headers.clone(),
         error_count: job.error_count,
         job_type: job.job_type.clone(),
         job_state: job.job_state.clone(),
@@ -182,9 +400,12 @@ pub(crate) fn combine_file_and_job(job: &JobInfo, file: &FileInfo, carved: bool)
         expiration: job.expiration,
         transient_error_count: job.transient_error_count,
         acls: job.acls.clone(),
+        acls_raw: job.acls_raw.clone(),
         timeout: job.timeout,
         retry_delay: job.retry_delay,
         additional_sids: job.additional_sids.clone(),
+        source_format,
+        raw: Vec::new(),
     }
 }
 
@@ -193,11 +414,122 @@ pub(crate) fn scan_delimiter<'a>(data: &'a [u8], delimiter: &[u8]) -> nom::IResu
     Ok((input, ()))
 }
 
+/**
+ * Limit the number of carved entries to `max_carved`. Jobs, files, and legacy carved entries are each limited separately  
+ * Entries with the most recovered fields are kept. Parsed (non-carved) entries are never removed
+ */
+pub(crate) fn limit_carved(bits: &mut WindowsBits, max_carved: usize) {
+    limit_entries(&mut bits.carved_jobs, max_carved, job_confidence, "jobs");
+    limit_entries(&mut bits.carved_files, max_carved, file_confidence, "files");
+
+    let (mut carved, parsed): (Vec<BitsInfo>, Vec<BitsInfo>) =
+        bits.bits.drain(..).partition(|entry| entry.carved);
+    limit_entries(&mut carved, max_carved, bits_confidence, "entries");
+    bits.bits = parsed;
+    bits.bits.append(&mut carved);
+}
+
+/// Sort entries by confidence (highest first) and truncate to `max_carved`
+fn limit_entries<T>(
+    entries: &mut Vec<T>,
+    max_carved: usize,
+    confidence: fn(&T) -> usize,
+    name: &str,
+) {
+    if entries.len() <= max_carved {
+        return;
+    }
+    warn!(
+        "[bits] Carved {} {name}, only keeping {max_carved} with the most recovered data",
+        entries.len()
+    );
+    // Stable sort so entries with equal confidence keep their carved order
+    entries.sort_by_key(|entry| std::cmp::Reverse(confidence(entry)));
+    entries.truncate(max_carved);
+}
+
+/// Count the recovered fields of a value
+fn count_present(strings: &[&str], numbers: &[u64]) -> usize {
+    strings.iter().filter(|value| !value.is_empty()).count()
+        + numbers.iter().filter(|value| **value != 0).count()
+}
+
+/// Simple confidence score for a carved job based on how many fields were recovered
+fn job_confidence(job: &JobInfo) -> usize {
+    count_present(
+        &[
+            &job.job_id,
+            &job.file_id,
+            &job.owner_sid,
+            &job.job_name,
+            &job.job_description,
+            &job.job_command,
+            &job.target_path,
+            &job.http_method,
+        ],
+        &[
+            job.created as u64,
+            job.modified as u64,
+            job.completed as u64,
+            job.expiration as u64,
+        ],
+    )
+}
+
+/// Simple confidence score for a carved file based on how many fields were recovered
+fn file_confidence(file: &FileInfo) -> usize {
+    count_present(
+        &[
+            &file.file_id,
+            &file.filename,
+            &file.full_path,
+            &file.tmp_fullpath,
+            &file.volume,
+            &file.url,
+        ],
+        &[file.download_bytes_size, file.transfer_bytes_size],
+    )
+}
+
+/// Simple confidence score for a carved legacy entry based on how many fields were recovered
+fn bits_confidence(bits: &BitsInfo) -> usize {
+    count_present(
+        &[
+            &bits.job_id,
+            &bits.file_id,
+            &bits.owner_sid,
+            &bits.job_name,
+            &bits.job_description,
+            &bits.job_command,
+            &bits.target_path,
+            &bits.http_method,
+            &bits.filename,
+            &bits.full_path,
+            &bits.tmp_file,
+            &bits.volume,
+            &bits.url,
+        ],
+        &[
+            bits.created as u64,
+            bits.modified as u64,
+            bits.completed as u64,
+            bits.expiration as u64,
+            bits.bytes_downloaded,
+            bits.bytes_transferred,
+        ],
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{carve_bits, combine_file_and_job, scan_delimiter};
+    use super::{
+        carve_bits, carve_bits_scan, combine_file_and_job, delimiter_hits, file_confidence,
+        job_confidence, limit_carved, next_hit, scan_delimiter, CarveScan, FILE_DELIMITER,
+    };
     use crate::filesystem::files::read_file;
-    use common::windows::{FileInfo, JobFlags, JobInfo, JobPriority, JobState, JobType};
+    use common::windows::{
+        BitsFormat, FileInfo, JobFlags, JobInfo, JobPriority, JobState, JobType, WindowsBits,
+    };
     use std::path::PathBuf;
 
     #[test]
@@ -239,10 +571,107 @@ mod tests {
     }
 
     #[test]
-    fn test_combine_file_and_job() {
-        let job = JobInfo {
-            job_id: String::new(),
-            file_id: String::new(),
+    fn test_carve_bits_parallel() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/ese/win10/qmgr.db");
+        let data = read_file(test_location.to_str().unwrap()).unwrap();
+
+        let serial = CarveScan {
+            threads: 1,
+            chunk_size: None,
+        };
+        let (_, (_, serial_jobs, serial_files)) = carve_bits_scan(&data, false, &serial).unwrap();
+
+        for chunk_size in [None, Some(4096), Some(100000)] {
+            let parallel = CarveScan {
+                threads: 4,
+                chunk_size,
+            };
+            let (_, (_, jobs, files)) = carve_bits_scan(&data, false, &parallel).unwrap();
+            assert_eq!(jobs.len(), serial_jobs.len());
+            assert_eq!(files.len(), serial_files.len());
+            assert_eq!(jobs[18].job_id, serial_jobs[18].job_id);
+            assert_eq!(files[8].url, serial_files[8].url);
+        }
+        assert_eq!(serial_jobs.len(), 86);
+        assert_eq!(serial_files.len(), 41);
+
+        test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/bits/win81/qmgr0.dat");
+        let data = read_file(test_location.to_str().unwrap()).unwrap();
+
+        let parallel = CarveScan {
+            threads: 3,
+            chunk_size: Some(1),
+        };
+        let (_, (serial_bits, _, _)) = carve_bits_scan(&data, true, &serial).unwrap();
+        let (_, (bits, _, _)) = carve_bits_scan(&data, true, &parallel).unwrap();
+        assert_eq!(bits.len(), serial_bits.len());
+        assert_eq!(bits.len(), 20);
+    }
+
+    #[test]
+    fn test_delimiter_hits() {
+        let mut data = vec![0; 4096 * 3];
+        // Straddles the first chunk boundary
+        data[4090..4106].copy_from_slice(&FILE_DELIMITER);
+        data[8192..8208].copy_from_slice(&FILE_DELIMITER);
+
+        let scan = CarveScan {
+            threads: 2,
+            chunk_size: Some(4096),
+        };
+        let hits = delimiter_hits(&data, &[FILE_DELIMITER.as_slice()], &scan, 4096);
+        assert_eq!(hits, vec![vec![4090, 8192]]);
+    }
+
+    #[test]
+    fn test_next_hit() {
+        let hits = [10, 20, 30];
+        assert_eq!(next_hit(&hits, 0), Some(10));
+        assert_eq!(next_hit(&hits, 20), Some(20));
+        assert_eq!(next_hit(&hits, 21), Some(30));
+        assert_eq!(next_hit(&hits, 31), None);
+    }
+
+    #[test]
+    fn test_limit_carved() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/ese/win10/qmgr.db");
+        let data = read_file(test_location.to_str().unwrap()).unwrap();
+
+        let (_, (bits, carved_jobs, carved_files)) = carve_bits(&data, false).unwrap();
+        let mut job_scores: Vec<usize> = carved_jobs.iter().map(job_confidence).collect();
+        job_scores.sort_unstable_by(|a, b| b.cmp(a));
+        let mut file_scores: Vec<usize> = carved_files.iter().map(file_confidence).collect();
+        file_scores.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut results = WindowsBits {
+            bits,
+            carved_jobs,
+            carved_files,
+            partial: false,
+            users_resolved: false,
+        };
+        limit_carved(&mut results, 10);
+        assert_eq!(results.carved_jobs.len(), 10);
+        assert_eq!(results.carved_files.len(), 10);
+
+        // Highest confidence entries are kept
+        let kept: Vec<usize> = results.carved_jobs.iter().map(job_confidence).collect();
+        assert_eq!(kept, job_scores[..10]);
+        let kept: Vec<usize> = results.carved_files.iter().map(file_confidence).collect();
+        assert_eq!(kept, file_scores[..10]);
+
+        // Nothing to remove
+        limit_carved(&mut results, 100);
+        assert_eq!(results.carved_jobs.len(), 10);
+    }
+
+    fn test_job(job_id: &str, file_id: &str) -> JobInfo {
+        JobInfo {
+            job_id: job_id.to_string(),
+            file_id: file_id.to_string(),
             owner_sid: String::new(),
             created: 0,
             modified: 0,
@@ -252,24 +681,30 @@ mod tests {
             job_description: String::new(),
             job_command: String::new(),
             job_arguments: String::new(),
+            notify_command: String::new(),
+            notify_args: String::new(),
+            custom_headers: Vec::new(),
             error_count: 0,
-            job_type: JobType::Unknown,
-            job_state: JobState::Unknown,
-            priority: JobPriority::Unknown,
-            flags: JobFlags::Unknown,
+            job_type: JobType::Unknown(0),
+            job_state: JobState::Unknown(0),
+            priority: JobPriority::Unknown(0),
+            flags: JobFlags::Unknown(0),
             http_method: String::new(),
             acls: Vec::new(),
+            acls_raw: String::new(),
             additional_sids: Vec::new(),
             transient_error_count: 0,
             retry_delay: 0,
             timeout: 0,
             target_path: String::new(),
-        };
+        }
+    }
 
-        let file = FileInfo {
-            file_id: String::new(),
+    fn test_file(file_id: &str, full_path: &str) -> FileInfo {
+        FileInfo {
+            file_id: file_id.to_string(),
             filename: String::new(),
-            full_path: String::new(),
+            full_path: full_path.to_string(),
             tmp_fullpath: String::new(),
             drive: String::new(),
             volume: String::new(),
@@ -277,10 +712,28 @@ mod tests {
             download_bytes_size: 0,
             transfer_bytes_size: 0,
             files_transferred: 0,
-        };
+        }
+    }
+
+    #[test]
+    fn test_combine_file_and_job() {
+        let job = test_job("", "");
+        let file = test_file("", "");
 
-        let bit_info = combine_file_and_job(&job, &file, true);
+        let bit_info = combine_file_and_job(&job, &file, true, BitsFormat::Legacy);
         assert_eq!(bit_info.carved, true);
+        assert_eq!(bit_info.source_format, BitsFormat::Legacy);
+    }
+
+    #[test]
+    fn test_carve_bits_source_format() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/bits/win81/qmgr0.dat");
+        let data = read_file(test_location.to_str().unwrap()).unwrap();
+        let (_, (results, _, _)) = carve_bits(&data, true).unwrap();
+        assert!(results
+            .iter()
+            .all(|entry| entry.source_format == BitsFormat::Legacy));
     }
 
     #[test]
@@ -1,7 +1,7 @@
 use std::fmt;
 
 #[derive(Debug, PartialEq, Eq)]
-pub(crate) enum BitsError {
+pub enum BitsError {
     ReadFile,
     Systemdrive,
     ParseEse,
@@ -5,7 +5,7 @@ use crate::{
         securitydescriptor::{acl::parse_acl, sid::grab_sid},
     },
     utils::{
-        encoding::base64_decode_standard,
+        encoding::{base64_decode_standard, base64_encode_standard},
         nom_helper::{
             nom_unsigned_eight_bytes, nom_unsigned_four_bytes, nom_unsigned_one_byte,
             nom_unsigned_sixteen_bytes, Endian,
@@ -16,7 +16,7 @@ use crate::{
     },
 };
 use common::windows::{
-    AccessItem, BitsInfo, JobFlags, JobInfo, JobPriority, JobState, JobType, TableDump,
+    AccessItem, BitsFormat, BitsInfo, JobFlags, JobInfo, JobPriority, JobState, JobType, TableDump,
 };
 use log::error;
 use nom::bytes::complete::{take, take_until};
@@ -38,13 +38,17 @@ pub(crate) fn get_jobs(column_rows: &[Vec<TableDump>]) -> Result<Vec<JobInfo>, B
             job_description: String::new(),
             job_command: String::new(),
             job_arguments: String::new(),
+            notify_command: String::new(),
+            notify_args: String::new(),
+            custom_headers: Vec::new(),
             error_count: 0,
-            job_type: JobType::Unknown,
-            job_state: JobState::Unknown,
-            priority: JobPriority::Unknown,
-            flags: JobFlags::Unknown,
+            job_type: JobType::Unknown(0),
+            job_state: JobState::Unknown(0),
+            priority: JobPriority::Unknown(0),
+            flags: JobFlags::Unknown(0),
             http_method: String::new(),
             acls: Vec::new(),
+            acls_raw: String::new(),
             additional_sids: Vec::new(),
             transient_error_count: 0,
             retry_delay: 0,
@@ -96,14 +100,32 @@ pub(crate) fn get_legacy_jobs(data: &[u8]) -> Result<Vec<BitsInfo>, BitsError> {
     Ok(jobs)
 }
 
+/// Versions of the legacy BITS format. The size of the file header depends on the version
+#[derive(Debug, PartialEq)]
+pub(crate) enum LegacyVersion {
+    /// Windows 7 and earlier
+    Win7,
+    /// Windows 8 and later
+    Win8,
+}
+
+/// Detect the version of a legacy BITS file from the first byte of the header
+pub(crate) fn legacy_version(data: &[u8]) -> nom::IResult<&[u8], LegacyVersion> {
+    let (input, sig) = nom_unsigned_one_byte(data, Endian::Le)?;
+    let win8 = 40;
+    if sig == win8 {
+        return Ok((input, LegacyVersion::Win8));
+    }
+    Ok((input, LegacyVersion::Win7))
+}
+
 /// Parse older BITS format
 fn parse_legacy_job(data: &[u8]) -> nom::IResult<&[u8], Vec<BitsInfo>> {
-    let (_, sig) = nom_unsigned_one_byte(data, Endian::Le)?;
-    let win10 = 40;
-    let win10_size = 24;
-    let win7_size = 16;
-
-    let change_size: u8 = if sig == win10 { win10_size } else { win7_size };
+    let (_, version) = legacy_version(data)?;
+    let change_size: u8 = match version {
+        LegacyVersion::Win8 => 24,
+        LegacyVersion::Win7 => 16,
+    };
 
     let (input, _change_data) = take(change_size)(data)?;
 
@@ -127,13 +149,17 @@ fn parse_legacy_job(data: &[u8]) -> nom::IResult<&[u8], Vec<BitsInfo>> {
             job_description: String::new(),
             job_command: String::new(),
             job_arguments: String::new(),
+            notify_command: String::new(),
+            notify_args: String::new(),
+            custom_headers: Vec::new(),
             error_count: 0,
-            job_type: JobType::Unknown,
-            job_state: JobState::Unknown,
-            priority: JobPriority::Unknown,
-            flags: JobFlags::Unknown,
+            job_type: JobType::Unknown(0),
+            job_state: JobState::Unknown(0),
+            priority: JobPriority::Unknown(0),
+            flags: JobFlags::Unknown(0),
             http_method: String::new(),
             acls: Vec::new(),
+            acls_raw: String::new(),
             additional_sids: Vec::new(),
             transient_error_count: 0,
             retry_delay: 0,
@@ -148,7 +174,7 @@ fn parse_legacy_job(data: &[u8]) -> nom::IResult<&[u8], Vec<BitsInfo>> {
         let (remaining_input, _) = job_details(remaining_input, &mut job, is_legacy)?;
         let carved = false;
 
-        jobs.push(combine_file_and_job(&job, &file, carved));
+        jobs.push(combine_file_and_job(&job, &file, carved, BitsFormat::Legacy));
         job_count += 1;
         if job_count == number_jobs {
             break;
@@ -260,17 +286,24 @@ pub(crate) fn parse_job<'a>(
 
     let padding_size: u16 = 982;
     let (input, _) = take(padding_size)(input)?;
+    let acl_start = input;
     let (input, acls) = parse_acl(input, &AccessItem::NonFolder)?;
+    // Keep the raw ACL data too
+    let acls_raw = base64_encode_standard(&acl_start[..acl_start.len() - input.len()]);
     job_info.job_type = get_type(&job_type);
     job_info.priority = get_priority(&job_priority);
     job_info.job_state = get_state(&job_state);
     job_info.job_name = job_name;
     job_info.job_description = description;
+    // BITS only stores the notification command line for a job
+    job_info.notify_command.clone_from(&cmd);
+    job_info.notify_args.clone_from(&args);
     job_info.job_command = cmd;
     job_info.job_arguments = args;
     job_info.owner_sid = sid;
     job_info.flags = get_flag(&job_flag);
     job_info.acls = acls;
+    job_info.acls_raw = acls_raw;
 
     // Only grab additional SIDs if we are not carving
     if !carve {
@@ -383,19 +416,40 @@ pub(crate) fn job_details<'a>(
     let (input, method_data) = take(method_size * wide_char_adjust)(input)?;
     job_info.http_method = extract_utf16_string(method_data);
 
-    // Rest of data is unknown maybe custom http headers?
+    // Custom HTTP headers are stored as a single CRLF separated string
+    let (input, headers_size) = nom_unsigned_four_bytes(input, Endian::Le)?;
+    // When carving return early if size is larger than remaining input
+    if headers_size as usize > input.len()
+        || (headers_size as usize).saturating_mul(wide_char_adjust as usize) > input.len()
+    {
+        return Ok((input, ()));
+    }
+    let (input, headers_data) = take(headers_size * wide_char_adjust)(input)?;
+    job_info.custom_headers = split_headers(&extract_utf16_string(headers_data));
+
+    // Rest of data is unknown
     // Last 16 bytes is the footer (same value as header)
 
     Ok((input, ()))
 }
 
+/// Split the custom HTTP headers string into separate headers
+fn split_headers(headers: &str) -> Vec<String> {
+    headers
+        .split("\r\n")
+        .map(str::trim)
+        .filter(|header| !header.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 /// Determine the job type
 pub(crate) fn get_type(job_type: &u32) -> JobType {
     match job_type {
         0 => JobType::Download,
         1 => JobType::Upload,
         2 => JobType::UploadReply,
-        _ => JobType::Unknown,
+        _ => JobType::Unknown(*job_type),
     }
 }
 
@@ -406,7 +460,7 @@ pub(crate) fn get_priority(job_priority: &u32) -> JobPriority {
         1 => JobPriority::High,
         2 => JobPriority::Normal,
         3 => JobPriority::Low,
-        _ => JobPriority::Unknown,
+        _ => JobPriority::Unknown(*job_priority),
     }
 }
 
@@ -422,7 +476,7 @@ pub(crate) fn get_state(job_state: &u32) -> JobState {
         6 => JobState::Transferred,
         7 => JobState::Acknowledged,
         8 => JobState::Cancelled,
-        _ => JobState::Unknown,
+        _ => JobState::Unknown(*job_state),
     }
 }
 
@@ -438,22 +492,41 @@ pub(crate) fn get_flag(job_flag: &u32) -> JobFlags {
         7 => JobFlags::TransferredBackgroundErrorDisable,
         8 => JobFlags::Modification,
         16 => JobFlags::FileTransferred,
-        _ => JobFlags::Unknown,
+        _ => JobFlags::Unknown(*job_flag),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        artifacts::os::windows::bits::jobs::{
-            get_flag, get_legacy_jobs, get_priority, get_state, get_type, job_details, parse_job,
-            parse_legacy_job,
+        artifacts::os::windows::{
+            bits::jobs::{
+                get_flag, get_legacy_jobs, get_priority, get_state, get_type, job_details,
+                legacy_version, parse_job, parse_legacy_job, split_headers, LegacyVersion,
+            },
+            securitydescriptor::acl::parse_acl,
         },
         filesystem::files::read_file,
+        utils::encoding::base64_decode_standard,
     };
-    use common::windows::{JobFlags, JobInfo, JobPriority, JobState, JobType};
+    use common::windows::{AccessItem, JobFlags, JobInfo, JobPriority, JobState, JobType};
+    use serde_json::json;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_legacy_version() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/bits/win81/qmgr0.dat");
+        let data = read_file(test_location.to_str().unwrap()).unwrap();
+
+        let (_, version) = legacy_version(&data).unwrap();
+        assert_eq!(version, LegacyVersion::Win8);
+
+        let (_, version) = legacy_version(&[32, 0, 0, 0]).unwrap();
+        assert_eq!(version, LegacyVersion::Win7);
+        assert!(legacy_version(&[]).is_err());
+    }
+
     #[test]
     fn test_get_flag() {
         let test = 1;
@@ -482,6 +555,44 @@ mod tests {
         assert_eq!(results, JobType::Download);
     }
 
+    #[test]
+    fn test_get_unknown() {
+        assert_eq!(get_type(&9), JobType::Unknown(9));
+        assert_eq!(get_priority(&20), JobPriority::Unknown(20));
+        assert_eq!(get_state(&12), JobState::Unknown(12));
+        assert_eq!(get_flag(&32), JobFlags::Unknown(32));
+    }
+
+    #[test]
+    fn test_serialize_codes() {
+        let state = serde_json::to_value(get_state(&6)).unwrap();
+        assert_eq!(state, json!({"code": 6, "name": "Transferred"}));
+
+        let flags = serde_json::to_value(get_flag(&16)).unwrap();
+        assert_eq!(flags, json!({"code": 16, "name": "FileTransferred"}));
+
+        let priority = serde_json::to_value(get_priority(&2)).unwrap();
+        assert_eq!(priority, json!({"code": 2, "name": "Normal"}));
+
+        let job_type = serde_json::to_value(get_type(&2)).unwrap();
+        assert_eq!(job_type, json!({"code": 2, "name": "UploadReply"}));
+    }
+
+    #[test]
+    fn test_serialize_unknown_codes() {
+        let state = serde_json::to_value(get_state(&12)).unwrap();
+        assert_eq!(state, json!({"code": 12, "name": "Unknown(12)"}));
+
+        let job_type = serde_json::to_value(get_type(&3)).unwrap();
+        assert_eq!(job_type, json!({"code": 3, "name": "Unknown(3)"}));
+
+        let priority = serde_json::to_value(get_priority(&4)).unwrap();
+        assert_eq!(priority["name"], "Unknown(4)");
+
+        let flags = serde_json::to_value(get_flag(&0)).unwrap();
+        assert_eq!(flags, json!({"code": 0, "name": "Unknown(0)"}));
+    }
+
     #[test]
     fn test_parse_job() {
         let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -499,13 +610,17 @@ mod tests {
             job_description: String::new(),
             job_command: String::new(),
             job_arguments: String::new(),
+            notify_command: String::new(),
+            notify_args: String::new(),
+            custom_headers: Vec::new(),
             error_count: 0,
-            job_type: JobType::Unknown,
-            job_state: JobState::Unknown,
-            priority: JobPriority::Unknown,
-            flags: JobFlags::Unknown,
+            job_type: JobType::Unknown(0),
+            job_state: JobState::Unknown(0),
+            priority: JobPriority::Unknown(0),
+            flags: JobFlags::Unknown(0),
             http_method: String::new(),
             acls: Vec::new(),
+            acls_raw: String::new(),
             additional_sids: Vec::new(),
             transient_error_count: 0,
             retry_delay: 0,
@@ -520,6 +635,12 @@ mod tests {
         );
         assert_eq!(job.job_name, "Chrome Component Updater");
 
+        // The raw ACL data should parse to the same entries
+        assert!(!job.acls.is_empty());
+        let raw = base64_decode_standard(&job.acls_raw).unwrap();
+        let (_, acls) = parse_acl(&raw, &AccessItem::NonFolder).unwrap();
+        assert_eq!(acls, job.acls);
+
         assert_eq!(
             job.job_description,
             "lmelglejhemejginpboagddgdfbepgmp_372_all_ZZ_djv5ss66g7sivnpz6ljtwr2zji.crx3"
@@ -543,13 +664,17 @@ mod tests {
             job_description: String::new(),
             job_command: String::new(),
             job_arguments: String::new(),
+            notify_command: String::new(),
+            notify_args: String::new(),
+            custom_headers: Vec::new(),
             error_count: 0,
-            job_type: JobType::Unknown,
-            job_state: JobState::Unknown,
-            priority: JobPriority::Unknown,
-            flags: JobFlags::Unknown,
+            job_type: JobType::Unknown(0),
+            job_state: JobState::Unknown(0),
+            priority: JobPriority::Unknown(0),
+            flags: JobFlags::Unknown(0),
             http_method: String::new(),
             acls: Vec::new(),
+            acls_raw: String::new(),
             additional_sids: Vec::new(),
             transient_error_count: 0,
             retry_delay: 0,
@@ -582,6 +707,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_job_notify() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/bits/win10/job_notify.raw");
+        let data = read_file(test_location.to_str().unwrap()).unwrap();
+        let mut job = JobInfo {
+            job_id: String::new(),
+            file_id: String::new(),
+            owner_sid: String::new(),
+            created: 0,
+            modified: 0,
+            expiration: 0,
+            completed: 0,
+            job_name: String::new(),
+            job_description: String::new(),
+            job_command: String::new(),
+            job_arguments: String::new(),
+            notify_command: String::new(),
+            notify_args: String::new(),
+            custom_headers: Vec::new(),
+            error_count: 0,
+            job_type: JobType::Unknown(0),
+            job_state: JobState::Unknown(0),
+            priority: JobPriority::Unknown(0),
+            flags: JobFlags::Unknown(0),
+            http_method: String::new(),
+            acls: Vec::new(),
+            acls_raw: String::new(),
+            additional_sids: Vec::new(),
+            transient_error_count: 0,
+            retry_delay: 0,
+            timeout: 0,
+            target_path: String::new(),
+        };
+
+        let (input, _) = parse_job(&data, &mut job, false).unwrap();
+        assert_eq!(job.notify_command, "C:\\Windows\\System32\\cmd.exe");
+        assert_eq!(job.notify_args, "/c C:\\Users\\bob\\Downloads\\evil.exe");
+        assert_eq!(job.job_command, job.notify_command);
+
+        let _ = job_details(&input, &mut job, false).unwrap();
+        assert_eq!(job.http_method, "GET");
+        assert_eq!(
+            job.custom_headers,
+            vec!["X-Test: artemis", "Authorization: Basic dGVzdA=="]
+        );
+    }
+
+    #[test]
+    fn test_split_headers() {
+        let results = split_headers("X-Test: artemis\r\n\r\nAccept: */*\r\n");
+        assert_eq!(results, vec!["X-Test: artemis", "Accept: */*"]);
+        assert!(split_headers("").is_empty());
+    }
+
     #[test]
     #[cfg(target_os = "windows")]
     fn test_get_jobs() {
@@ -1,6 +1,7 @@
 mod background;
 mod carve;
-mod error;
+pub(crate) mod error;
 mod files;
 mod jobs;
 pub(crate) mod parser;
+mod sids;
@@ -14,11 +14,17 @@
  * `https://github.com/ANSSI-FR/bits_parser` (only pre-win10 BITS files)
  */
 use super::{
-    background::{legacy_bits, parse_ese_bits, parse_legacy_bits},
+    background::{legacy_bits, parse_bits, parse_ese_bits},
+    carve::{carve_scan, limit_carved, with_carve_scan},
     error::BitsError,
+    sids::{resolve_accounts, resolve_local_accounts},
 };
 use crate::{
-    filesystem::files::{file_extension, is_file},
+    artifacts::os::windows::accounts::cache::{local_users_available, resolve_sid},
+    filesystem::{
+        files::file_extension,
+        provider::{FileProvider, LiveFileProvider},
+    },
     structs::artifacts::os::windows::BitsOptions,
     utils::environment::get_systemdrive,
 };
@@ -29,37 +35,80 @@ use log::error;
  * Grab the `BITS` data from the default path(s) or an alternative path  
  * The associated `BITS` file(s) is locked if the `BITS` service is running so we read the raw file to bypass the lock
  */
-pub(crate) fn grab_bits(options: &BitsOptions) -> Result<WindowsBits, BitsError> {
-    let path = if let Some(alt) = &options.alt_file {
-        alt.to_string()
-    } else {
-        let systemdrive_result = get_systemdrive();
-        let systemdrive = match systemdrive_result {
-            Ok(result) => result,
-            Err(err) => {
-                error!("[bits] Could not get systemdrive: {err:?}");
-                return Err(BitsError::Systemdrive);
-            }
-        };
-        let bits_path =
-            format!("{systemdrive}:\\ProgramData\\Microsoft\\Network\\Downloader\\qmgr.db");
-        // If qmbgr.db is not found this may be an older system that uses the older BITS format
-        if !is_file(&bits_path) {
-            return parse_legacy_bits(&systemdrive, options.carve);
+pub fn grab_bits(options: &BitsOptions) -> Result<WindowsBits, BitsError> {
+    collect_bits(options, false)
+}
+
+/// Grab the `BITS` data based on the options. Raw `Jobs` row bytes are only kept if `include_raw` is set
+pub(crate) fn collect_bits(
+    options: &BitsOptions,
+    include_raw: bool,
+) -> Result<WindowsBits, BitsError> {
+    with_carve_scan(carve_scan(options), || bits_options(options, include_raw))
+}
+
+/// Grab the `BITS` data based on the options. Carving uses the scan options set by `grab_bits`
+fn bits_options(options: &BitsOptions, include_raw: bool) -> Result<WindowsBits, BitsError> {
+    if let Some(alt) = &options.alt_file {
+        let provider = LiveFileProvider { raw_ntfs: true };
+        let mut bits = provider_bits_path(alt, options.carve, &provider, include_raw)?;
+        if let Some(max_carved) = options.max_carved {
+            limit_carved(&mut bits, max_carved);
+        }
+        return Ok(bits);
+    }
+
+    let systemdrive_result = get_systemdrive();
+    let systemdrive = match systemdrive_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[bits] Could not get systemdrive: {err:?}");
+            return Err(BitsError::Systemdrive);
         }
-        bits_path
     };
-    grab_bits_path(&path, options.carve)
+    // Let the dispatcher determine if the system uses the ESE or older BITS format
+    let provider = LiveFileProvider { raw_ntfs: true };
+    let mut bits = parse_bits(&systemdrive, options.carve, &provider, include_raw)?;
+
+    // Resolve ACL SIDs using the local users. Consumers are told if the users could not be read
+    resolve_local_accounts(&mut bits, local_users_available(), &resolve_sid);
+
+    if let Some(max_carved) = options.max_carved {
+        limit_carved(&mut bits, max_carved);
+    }
+    Ok(bits)
 }
 
 /**
  * Grab the BITS data from file path
  */
-pub(crate) fn grab_bits_path(path: &str, carve: bool) -> Result<WindowsBits, BitsError> {
-    if file_extension(path) == "db" {
-        return parse_ese_bits(path, carve);
-    }
-    legacy_bits(path, carve)
+pub fn grab_bits_path(path: &str, carve: bool) -> Result<WindowsBits, BitsError> {
+    provider_bits_path(path, carve, &LiveFileProvider { raw_ntfs: true }, false)
+}
+
+/**
+ * Grab the BITS data from an ESE database at a custom path. Used for offline or mounted databases
+ */
+pub fn grab_ese_bits(path: &str, carve: bool) -> Result<WindowsBits, BitsError> {
+    parse_ese_bits(path, carve, &LiveFileProvider { raw_ntfs: true }, false)
+}
+
+/// Grab the BITS data from file path using the provided `FileProvider`
+pub(crate) fn provider_bits_path(
+    path: &str,
+    carve: bool,
+    provider: &dyn FileProvider,
+    include_raw: bool,
+) -> Result<WindowsBits, BitsError> {
+    let mut bits = if file_extension(path) == "db" {
+        parse_ese_bits(path, carve, provider, include_raw)?
+    } else {
+        legacy_bits(path, carve, provider)?
+    };
+
+    // Users on the current system may not match the provided file. Only resolve well-known SIDs
+    resolve_accounts(&mut bits, &|_: &str| None);
+    Ok(bits)
 }
 
 #[cfg(test)]
@@ -76,6 +125,9 @@ mod tests {
         let options = BitsOptions {
             alt_file: None,
             carve: true,
+            max_carved: None,
+            carve_threads: None,
+            carve_chunk_size: None,
         };
         let _ = grab_bits(&options).unwrap();
     }
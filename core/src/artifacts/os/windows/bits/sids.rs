@@ -0,0 +1,165 @@
+use crate::artifacts::os::windows::securitydescriptor::sid::well_known_sid;
+use common::windows::{AccessControlEntry, WindowsBits};
+use log::warn;
+
+/**
+ * Resolve the `BITS` ACL SIDs using the local users  
+ * If the local users are unavailable only well-known SIDs are resolved and `users_resolved` is false
+ */
+pub(crate) fn resolve_local_accounts<F>(bits: &mut WindowsBits, users_available: bool, lookup: &F)
+where
+    F: Fn(&str) -> Option<String>,
+{
+    bits.users_resolved = users_available;
+    if !users_available {
+        warn!("[bits] Could not get local users. Only well-known SIDs will be resolved to account names");
+        resolve_accounts(bits, &|_: &str| None);
+        return;
+    }
+    resolve_accounts(bits, lookup);
+}
+
+/// Resolve the account names for the SIDs in the `BITS` ACLs using well-known SIDs and the provided lookup
+pub(crate) fn resolve_accounts<F>(bits: &mut WindowsBits, lookup: &F)
+where
+    F: Fn(&str) -> Option<String>,
+{
+    for info in &mut bits.bits {
+        resolve_acls(&mut info.acls, lookup);
+    }
+    for job in &mut bits.carved_jobs {
+        resolve_acls(&mut job.acls, lookup);
+    }
+}
+
+/// Set the account name for each ACL entry if the SID can be resolved
+fn resolve_acls<F>(acls: &mut [AccessControlEntry], lookup: &F)
+where
+    F: Fn(&str) -> Option<String>,
+{
+    for ace in acls {
+        if !ace.account.is_empty() {
+            continue;
+        }
+        if let Some(name) = well_known_sid(&ace.sid) {
+            ace.account = name.to_string();
+        } else if let Some(name) = lookup(&ace.sid) {
+            ace.account = name;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_acls, resolve_local_accounts};
+    use common::windows::{
+        AccessControlEntry, AceTypes, BitsFormat, BitsInfo, JobFlags, JobPriority, JobState,
+        JobType, ObjectFlag, WindowsBits,
+    };
+    use std::collections::HashMap;
+
+    fn ace(sid: &str) -> AccessControlEntry {
+        AccessControlEntry {
+            ace_type: AceTypes::AccessAllowedAceType,
+            flags: Vec::new(),
+            access_rights: Vec::new(),
+            sid: sid.to_string(),
+            account: String::new(),
+            object_flags: ObjectFlag::None,
+            object_type_guid: String::new(),
+            inherited_object_type_guid: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_acls() {
+        let mut acls = vec![
+            ace("S-1-5-18"),
+            ace("S-1-5-21-1079689790-2336414676-942872339-1001"),
+            ace("S-1-5-21-1-2-3-1002"),
+        ];
+        let mut users = HashMap::new();
+        users.insert(
+            String::from("S-1-5-21-1079689790-2336414676-942872339-1001"),
+            String::from("bob"),
+        );
+
+        resolve_acls(&mut acls, &|sid: &str| users.get(sid).cloned());
+        assert_eq!(acls[0].account, "SYSTEM");
+        assert_eq!(acls[1].account, "bob");
+        assert_eq!(acls[2].account, "");
+    }
+
+    fn bits(acls: Vec<AccessControlEntry>) -> WindowsBits {
+        let info = BitsInfo {
+            job_id: String::new(),
+            file_id: String::new(),
+            owner_sid: String::new(),
+            created: 0,
+            modified: 0,
+            completed: 0,
+            expiration: 0,
+            files_total: 0,
+            bytes_downloaded: 0,
+            bytes_transferred: 0,
+            job_name: String::new(),
+            job_description: String::new(),
+            job_command: String::new(),
+            job_arguments: String::new(),
+            notify_command: String::new(),
+            notify_args: String::new(),
+            custom_headers: Vec::new(),
+            error_count: 0,
+            job_type: JobType::Download,
+            job_state: JobState::Queued,
+            priority: JobPriority::Normal,
+            flags: JobFlags::Transferred,
+            http_method: String::new(),
+            full_path: String::new(),
+            filename: String::new(),
+            target_path: String::new(),
+            tmp_file: String::new(),
+            volume: String::new(),
+            url: String::new(),
+            timeout: 0,
+            retry_delay: 0,
+            transient_error_count: 0,
+            acls,
+            acls_raw: Vec::new(),
+            additional_sids: Vec::new(),
+            carved: false,
+            source_format: BitsFormat::Ese,
+            raw: Vec::new(),
+        };
+        WindowsBits {
+            bits: vec![info],
+            carved_jobs: Vec::new(),
+            carved_files: Vec::new(),
+            partial: false,
+            users_resolved: false,
+        }
+    }
+
+    #[test]
+    fn test_resolve_local_accounts_users_failed() {
+        let mut results = bits(vec![
+            ace("S-1-5-18"),
+            ace("S-1-5-21-1079689790-2336414676-942872339-1001"),
+        ]);
+        // Simulate the local users failing to load. The lookup should not be used
+        resolve_local_accounts(&mut results, false, &|_: &str| Some(String::from("bob")));
+
+        assert!(!results.users_resolved);
+        assert_eq!(results.bits[0].acls[0].account, "SYSTEM");
+        assert_eq!(results.bits[0].acls[1].account, "");
+    }
+
+    #[test]
+    fn test_resolve_local_accounts() {
+        let mut results = bits(vec![ace("S-1-5-21-1079689790-2336414676-942872339-1001")]);
+        resolve_local_accounts(&mut results, true, &|_: &str| Some(String::from("bob")));
+
+        assert!(results.users_resolved);
+        assert_eq!(results.bits[0].acls[0].account, "bob");
+    }
+}
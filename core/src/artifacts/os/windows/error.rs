@@ -24,6 +24,8 @@ pub(crate) enum WinArtifactError {
     Jumplists,
     RecycleBin,
     WmiPersist,
+    Execution,
+    Autoruns,
 }
 
 impl std::error::Error for WinArtifactError {}
@@ -53,6 +55,8 @@ impl fmt::Display for WinArtifactError {
             WinArtifactError::Jumplists => write!(f, "Failed to parse Jumplists"),
             WinArtifactError::RecycleBin => write!(f, "Failed to parse Recycle Bin"),
             WinArtifactError::WmiPersist => write!(f, "Failed to parse WMI persist"),
+            WinArtifactError::Execution => write!(f, "Failed to get execution events"),
+            WinArtifactError::Autoruns => write!(f, "Failed to parse autoruns"),
         }
     }
 }
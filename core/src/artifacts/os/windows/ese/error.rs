@@ -5,6 +5,7 @@ pub(crate) enum EseError {
     ReadFile,
     Catalog,
     ParseEse,
+    ReadTimeout,
 }
 
 impl std::error::Error for EseError {}
@@ -15,6 +16,7 @@ impl fmt::Display for EseError {
             EseError::ReadFile => write!(f, "Failed to read ESE db"),
             EseError::Catalog => write!(f, "Failed to parse Catalog"),
             EseError::ParseEse => write!(f, "Failed to parse ESE"),
+            EseError::ReadTimeout => write!(f, "Timed out reading ESE db"),
         }
     }
 }
@@ -37,9 +37,12 @@ use crate::{
     filesystem::{
         files::file_reader,
         ntfs::{
-            raw_files::raw_reader, reader::read_bytes, sector_reader::SectorReader,
-            setup::setup_ntfs_parser,
+            raw_files::raw_reader,
+            reader::read_bytes,
+            sector_reader::SectorReader,
+            setup::{setup_ntfs_parser, NtfsParser},
         },
+        timeout::with_read_timeout,
     },
     utils::nom_helper::nom_data,
 };
@@ -56,13 +59,11 @@ pub(crate) fn get_catalog_info(path: &str) -> Result<Vec<Catalog>, EseError> {
     let catalog = if plat != "Windows" {
         let reader = setup_ese_reader(path)?;
         let mut buf_reader = BufReader::new(reader);
-
-        let page_size = ese_page_size(None, &mut buf_reader)?;
-        Catalog::grab_catalog(None, &mut buf_reader, page_size)?
+        get_catalog_reader(&mut buf_reader)?
     } else {
         // On Windows use a NTFS reader
-        let mut ntfs_parser = setup_ntfs_parser(&path.chars().next().unwrap_or('C')).unwrap();
-        let ntfs_file = setup_ese_reader_windows(&ntfs_parser.ntfs, &mut ntfs_parser.fs, path)?;
+        let (mut ntfs_parser, record) = setup_ese_reader_windows(path)?;
+        let ntfs_file = ese_ntfs_file(&ntfs_parser.ntfs, &mut ntfs_parser.fs, record)?;
 
         let page_size = ese_page_size(Some(&ntfs_file), &mut ntfs_parser.fs)?;
         Catalog::grab_catalog(Some(&ntfs_file), &mut ntfs_parser.fs, page_size)?
@@ -78,12 +79,10 @@ pub(crate) fn get_all_pages(path: &str, first_page: &u32) -> Result<Vec<u32>, Es
     let pages = if plat != "Windows" {
         let reader = setup_ese_reader(path)?;
         let mut buf_reader = BufReader::new(reader);
-
-        let page_size = ese_page_size(None, &mut buf_reader)?;
-        get_pages(first_page, None, &mut buf_reader, &page_size)?
+        get_all_pages_reader(&mut buf_reader, first_page)?
     } else {
-        let mut ntfs_parser = setup_ntfs_parser(&path.chars().next().unwrap_or('C')).unwrap();
-        let ntfs_file = setup_ese_reader_windows(&ntfs_parser.ntfs, &mut ntfs_parser.fs, path)?;
+        let (mut ntfs_parser, record) = setup_ese_reader_windows(path)?;
+        let ntfs_file = ese_ntfs_file(&ntfs_parser.ntfs, &mut ntfs_parser.fs, record)?;
         let page_size = ese_page_size(Some(&ntfs_file), &mut ntfs_parser.fs)?;
         get_pages(
             first_page,
@@ -107,27 +106,16 @@ pub(crate) fn get_page_data(
     let mut total_rows = HashMap::new();
     total_rows.insert(name.to_string(), Vec::new());
 
-    let page_size;
-    let last_page = 0;
     let mut rows = if plat != "Windows" {
         let reader = setup_ese_reader(path)?;
         let mut buf_reader = BufReader::new(reader);
-
-        page_size = ese_page_size(None, &mut buf_reader)?;
-        let mut rows = Vec::new();
-        for page in pages {
-            if page == &last_page {
-                continue;
-            }
-            let mut page_rows = page_data(page, None, &mut buf_reader, &page_size, info)?;
-            rows.append(&mut page_rows);
-        }
-        row_data(&mut rows, None, &mut buf_reader, &page_size, info, name)?
+        get_page_data_reader(&mut buf_reader, pages, info, name)?
     } else {
-        let mut ntfs_parser = setup_ntfs_parser(&path.chars().next().unwrap_or('C')).unwrap();
-        let ntfs_file = setup_ese_reader_windows(&ntfs_parser.ntfs, &mut ntfs_parser.fs, path)?;
+        let (mut ntfs_parser, record) = setup_ese_reader_windows(path)?;
+        let ntfs_file = ese_ntfs_file(&ntfs_parser.ntfs, &mut ntfs_parser.fs, record)?;
 
-        page_size = ese_page_size(Some(&ntfs_file), &mut ntfs_parser.fs)?;
+        let page_size = ese_page_size(Some(&ntfs_file), &mut ntfs_parser.fs)?;
+        let last_page = 0;
         let mut rows = Vec::new();
 
         for page in pages {
@@ -191,8 +179,8 @@ pub(crate) fn get_filtered_page_data(
         row_data(&mut rows, None, &mut buf_reader, &page_size, info, name)?
     } else {
         // On Windows use a NTFS reader
-        let mut ntfs_parser = setup_ntfs_parser(&path.chars().next().unwrap_or('C')).unwrap();
-        let ntfs_file = setup_ese_reader_windows(&ntfs_parser.ntfs, &mut ntfs_parser.fs, path)?;
+        let (mut ntfs_parser, record) = setup_ese_reader_windows(path)?;
+        let ntfs_file = ese_ntfs_file(&ntfs_parser.ntfs, &mut ntfs_parser.fs, record)?;
 
         page_size = ese_page_size(Some(&ntfs_file), &mut ntfs_parser.fs)?;
         let mut rows = Vec::new();
@@ -245,6 +233,46 @@ pub(crate) fn get_filtered_page_data(
     Ok(total_rows)
 }
 
+/**
+ * Get `Catalog` data from an ESE database opened with a reader  
+ * Used when the database is not read from the live filesystem. Ex: through a `FileProvider`
+ */
+pub(crate) fn get_catalog_reader<T: std::io::Seek + std::io::Read>(
+    fs: &mut BufReader<T>,
+) -> Result<Vec<Catalog>, EseError> {
+    let page_size = ese_page_size(None, fs)?;
+    Catalog::grab_catalog(None, fs, page_size)
+}
+
+/// Get all pages from ESE table opened with a reader. First page can be found from the `Catalog`
+pub(crate) fn get_all_pages_reader<T: std::io::Seek + std::io::Read>(
+    fs: &mut BufReader<T>,
+    first_page: &u32,
+) -> Result<Vec<u32>, EseError> {
+    let page_size = ese_page_size(None, fs)?;
+    get_pages(first_page, None, fs, &page_size)
+}
+
+/// Get all page data (rows) from table opened with a reader based on array of pages
+pub(crate) fn get_page_data_reader<T: std::io::Seek + std::io::Read>(
+    fs: &mut BufReader<T>,
+    pages: &[u32],
+    info: &mut TableInfo,
+    name: &str,
+) -> Result<HashMap<String, Vec<Vec<TableDump>>>, EseError> {
+    let page_size = ese_page_size(None, fs)?;
+    let last_page = 0;
+    let mut rows = Vec::new();
+    for page in pages {
+        if page == &last_page {
+            continue;
+        }
+        let mut page_rows = page_data(page, None, fs, &page_size, info)?;
+        rows.append(&mut page_rows);
+    }
+    row_data(&mut rows, None, fs, &page_size, info, name)
+}
+
 /// Get specified columns from table
 pub(crate) fn dump_table_columns(
     path: &str,
@@ -270,8 +298,8 @@ pub(crate) fn dump_table_columns(
         }
         row_data(&mut rows, None, &mut buf_reader, &page_size, info, name)?
     } else {
-        let mut ntfs_parser = setup_ntfs_parser(&path.chars().next().unwrap_or('C')).unwrap();
-        let ntfs_file = setup_ese_reader_windows(&ntfs_parser.ntfs, &mut ntfs_parser.fs, path)?;
+        let (mut ntfs_parser, record) = setup_ese_reader_windows(path)?;
+        let ntfs_file = ese_ntfs_file(&ntfs_parser.ntfs, &mut ntfs_parser.fs, record)?;
 
         page_size = ese_page_size(Some(&ntfs_file), &mut ntfs_parser.fs)?;
         let mut rows = Vec::new();
@@ -317,17 +345,45 @@ pub(crate) fn dump_table_columns(
     Ok(total_rows)
 }
 
-/// Setup Windows ESE reader using NTFS parser
-fn setup_ese_reader_windows<'a>(
-    ntfs_file: &'a Ntfs,
+/**
+ * Setup Windows ESE reader using NTFS parser. Returns the parser and the file record number of the ESE file  
+ * Acquiring the ESE file is aborted with `EseError::ReadTimeout` if it takes longer than the read timeout
+ */
+fn setup_ese_reader_windows(path: &str) -> Result<(NtfsParser, u64), EseError> {
+    let owned_path = path.to_string();
+    with_read_timeout(path, EseError::ReadTimeout, move || {
+        let drive = owned_path.chars().next().unwrap_or('C');
+        let mut ntfs_parser = match setup_ntfs_parser(&drive) {
+            Ok(result) => result,
+            Err(err) => {
+                error!("[ese] Could not setup NTFS parser: {err:?}");
+                return Err(EseError::ReadFile);
+            }
+        };
+
+        let reader_result = raw_reader(&owned_path, &ntfs_parser.ntfs, &mut ntfs_parser.fs);
+        let record = match reader_result {
+            Ok(result) => result.file_record_number(),
+            Err(err) => {
+                error!("[ese] Could not setup reader: {err:?}");
+                return Err(EseError::ReadFile);
+            }
+        };
+        Ok((ntfs_parser, record))
+    })
+}
+
+/// Get the NTFS file for the ESE file record number
+fn ese_ntfs_file<'a>(
+    ntfs: &'a Ntfs,
     fs: &mut BufReader<SectorReader<File>>,
-    path: &str,
+    record: u64,
 ) -> Result<NtfsFile<'a>, EseError> {
-    let reader_result = raw_reader(path, ntfs_file, fs);
-    let ntfs_file = match reader_result {
+    let ntfs_file_result = ntfs.file(fs, record);
+    let ntfs_file = match ntfs_file_result {
         Ok(result) => result,
         Err(err) => {
-            error!("[ese] Could not setup reader: {err:?}");
+            error!("[ese] Could not get NTFS file: {err:?}");
             return Err(EseError::ReadFile);
         }
     };
@@ -700,7 +756,7 @@ mod tests {
     };
     use crate::artifacts::os::windows::ese::{
         catalog::CatalogType,
-        tables::{get_column_flags, get_column_type, ColumnInfo, TableInfo},
+        tables::{get_column_flags, get_column_type, table_info, ColumnInfo, TableInfo},
     };
     use common::windows::ColumnType;
     use std::{collections::HashMap, path::PathBuf};
@@ -972,4 +1028,33 @@ mod tests {
         assert_eq!(job[0][1].column_type, ColumnType::LongBinary);
         assert_eq!(job[0][1].column_data.len(), 2740);
     }
+
+    #[test]
+    fn test_get_page_data_stable_column_order() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests\\test_data\\windows\\ese\\win10\\qmgr.db");
+        let path = test_location.to_str().unwrap();
+
+        let catalog = get_catalog_info(path).unwrap();
+        let mut outputs = Vec::new();
+        for _ in 0..3 {
+            let mut info = table_info(&catalog, "Files");
+            let pages = get_all_pages(path, &(info.table_page as u32)).unwrap();
+            let results = get_page_data(path, &pages, &mut info, "Files").unwrap();
+            outputs.push(serde_json::to_vec(&results).unwrap());
+        }
+
+        assert_eq!(outputs[0], outputs[1]);
+        assert_eq!(outputs[1], outputs[2]);
+
+        let info = table_info(&catalog, "Files");
+        let ids: Vec<i32> = info
+            .column_info
+            .iter()
+            .map(|column| column.column_id)
+            .collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+    }
 }
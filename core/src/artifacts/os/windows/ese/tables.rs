@@ -110,6 +110,8 @@ pub(crate) fn table_info(catalog: &[Catalog], name: &str) -> TableInfo {
             info.long_value_page = entry.column_or_father_data_page;
         }
     }
+    // Keep the column definition order so each row serializes its fields in the same order every parse
+    info.column_info.sort_by_key(|column| column.column_id);
     info
 }
 
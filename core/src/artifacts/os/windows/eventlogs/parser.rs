@@ -17,8 +17,41 @@ use crate::{
     utils::{environment::get_systemdrive, time::time_now},
 };
 use common::windows::EventLogRecord;
-use evtx::EvtxParser;
+use evtx::{EvtxParser, SerializedEvtxRecord};
 use log::error;
+use serde_json::Value;
+use std::fs::File;
+
+/// Channel and EventID filters applied to each `EventLog` record. Empty filters allow all records
+#[derive(Debug, Default)]
+pub(crate) struct EventFilter {
+    pub(crate) channels: Vec<String>,
+    pub(crate) event_ids: Vec<u64>,
+}
+
+impl EventFilter {
+    /// Create the filter from the `EventLogsOptions`
+    pub(crate) fn from_options(options: &EventLogsOptions) -> EventFilter {
+        EventFilter {
+            channels: options.channels.clone().unwrap_or_default(),
+            event_ids: options.event_ids.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Check if the record matches the filter. Channels are case insensitive
+    pub(crate) fn matches(&self, record: &EventLogRecord) -> bool {
+        if !self.channels.is_empty()
+            && !self
+                .channels
+                .iter()
+                .any(|channel| channel.eq_ignore_ascii_case(&record.channel))
+        {
+            return false;
+        }
+
+        self.event_ids.is_empty() || self.event_ids.contains(&record.event_id)
+    }
+}
 
 /// Parse `EventLogs` based on `EventLogsOptions`
 pub(crate) fn grab_eventlogs(
@@ -26,34 +59,33 @@ pub(crate) fn grab_eventlogs(
     output: &mut Output,
     filter: &bool,
 ) -> Result<(), EventLogsError> {
+    let event_filter = EventFilter::from_options(options);
     if let Some(file) = &options.alt_file {
-        return alt_eventlogs(file, output, filter);
+        return alt_eventlogs(file, output, filter, &event_filter);
+    }
+
+    if let Some(drive) = options.alt_drive {
+        return drive_eventlogs(&drive, output, filter, &event_filter);
     }
 
-    default_eventlogs(output, filter)
+    default_eventlogs(output, filter, &event_filter)
 }
 
 /// Parse the `EventLog` evtx file at provided path
-pub(crate) fn parse_eventlogs(path: &str) -> Result<Vec<EventLogRecord>, EventLogsError> {
-    let evt_parser_results = EvtxParser::from_path(path);
-    let mut evt_parser = match evt_parser_results {
-        Ok(result) => result,
-        Err(err) => {
-            error!("[eventlogs] Failed to parse event log {path}, error: {err:?}");
-            return Err(EventLogsError::Parser);
-        }
-    };
+pub(crate) fn parse_eventlogs(
+    path: &str,
+    event_filter: &EventFilter,
+) -> Result<Vec<EventLogRecord>, EventLogsError> {
+    let mut evt_parser = evtx_parser(path)?;
 
     let mut eventlog_records: Vec<EventLogRecord> = Vec::new();
     for record in evt_parser.records_json_value() {
         match record {
             Ok(data) => {
-                let event_record = EventLogRecord {
-                    event_record_id: data.event_record_id,
-                    timestamp: data.timestamp.timestamp_nanos_opt().unwrap_or_default(),
-                    data: data.data,
-                };
-                eventlog_records.push(event_record);
+                let event_record = event_record(data);
+                if event_filter.matches(&event_record) {
+                    eventlog_records.push(event_record);
+                }
             }
             Err(err) => {
                 error!("[eventlogs] Issue parsing record from {path}, error: {err:?}");
@@ -65,26 +97,50 @@ pub(crate) fn parse_eventlogs(path: &str) -> Result<Vec<EventLogRecord>, EventLo
 }
 
 /// Read and parse `EventLog` files at default Windows path. Typically C:\Windows\System32\winevt
-fn default_eventlogs(output: &mut Output, filter: &bool) -> Result<(), EventLogsError> {
+fn default_eventlogs(
+    output: &mut Output,
+    filter: &bool,
+    event_filter: &EventFilter,
+) -> Result<(), EventLogsError> {
     let drive_result = get_systemdrive();
     let drive = match drive_result {
         Ok(result) => result,
         Err(err) => {
-            error!("[prefetch] Could not determine systemdrive: {err:?}");
+            error!("[eventlogs] Could not determine systemdrive: {err:?}");
             return Err(EventLogsError::DefaultDrive);
         }
     };
+    drive_eventlogs(&drive, output, filter, event_filter)
+}
+
+/// Read and parse `EventLog` files at the default path on the provided drive
+fn drive_eventlogs(
+    drive: &char,
+    output: &mut Output,
+    filter: &bool,
+    event_filter: &EventFilter,
+) -> Result<(), EventLogsError> {
     let path = format!("{drive}:\\Windows\\System32\\winevt\\Logs");
-    read_directory(&path, output, filter)
+    read_directory(&path, output, filter, event_filter)
 }
 
 /// Read and parse `EventLog` files with alternative path
-fn alt_eventlogs(path: &str, output: &mut Output, filter: &bool) -> Result<(), EventLogsError> {
-    read_eventlogs(path, output, filter)
+fn alt_eventlogs(
+    path: &str,
+    output: &mut Output,
+    filter: &bool,
+    event_filter: &EventFilter,
+) -> Result<(), EventLogsError> {
+    read_eventlogs(path, output, filter, event_filter)
 }
 
 /// Read all files at provided path
-fn read_directory(path: &str, output: &mut Output, filter: &bool) -> Result<(), EventLogsError> {
+fn read_directory(
+    path: &str,
+    output: &mut Output,
+    filter: &bool,
+    event_filter: &EventFilter,
+) -> Result<(), EventLogsError> {
     let dir_results = list_files(path);
     let read_dir = match dir_results {
         Ok(result) => result,
@@ -100,7 +156,7 @@ fn read_directory(path: &str, output: &mut Output, filter: &bool) -> Result<(),
             continue;
         }
 
-        let eventlogs_results = read_eventlogs(&evtx_file, output, filter);
+        let eventlogs_results = read_eventlogs(&evtx_file, output, filter, event_filter);
         match eventlogs_results {
             Ok(_) => continue,
             Err(err) => {
@@ -113,37 +169,57 @@ fn read_directory(path: &str, output: &mut Output, filter: &bool) -> Result<(),
     Ok(())
 }
 
-/// Read and parse the `EventLog` file
-fn read_eventlogs(path: &str, output: &mut Output, filter: &bool) -> Result<(), EventLogsError> {
-    let start_time = time_now();
-
-    let evt_parser_results = EvtxParser::from_path(path);
-    let mut evt_parser = match evt_parser_results {
-        Ok(result) => result,
-        Err(err) => {
-            error!("[eventlogs] Failed to parse event log {path}, error: {err:?}");
-            return Err(EventLogsError::Parser);
-        }
-    };
+/**
+ * Read and parse the `EventLog` file  
+ * Records are output in batches so large `EventLogs` are not kept in memory
+ */
+fn read_eventlogs(
+    path: &str,
+    output: &mut Output,
+    filter: &bool,
+    event_filter: &EventFilter,
+) -> Result<(), EventLogsError> {
+    let mut start_time = time_now();
+    let mut evt_parser = evtx_parser(path)?;
 
+    let max_records = 10000;
     let mut eventlog_records: Vec<EventLogRecord> = Vec::new();
     for record in evt_parser.records_json_value() {
         match record {
             Ok(data) => {
-                let event_record = EventLogRecord {
-                    event_record_id: data.event_record_id,
-                    timestamp: data.timestamp.timestamp_nanos_opt().unwrap_or_default(),
-                    data: data.data,
-                };
-                eventlog_records.push(event_record);
+                let event_record = event_record(data);
+                if event_filter.matches(&event_record) {
+                    eventlog_records.push(event_record);
+                }
             }
             Err(err) => {
                 error!("[eventlogs] Issue parsing record from {path}, error: {err:?}");
                 continue;
             }
         }
+
+        // To keep memory usage small we only keep 10,000 records in the vec at a time
+        if eventlog_records.len() >= max_records {
+            output_records(&eventlog_records, output, &start_time, filter)?;
+            eventlog_records = Vec::new();
+            start_time = time_now();
+        }
+    }
+
+    if !eventlog_records.is_empty() {
+        output_records(&eventlog_records, output, &start_time, filter)?;
     }
-    let serde_data_result = serde_json::to_value(&eventlog_records);
+    Ok(())
+}
+
+/// Output a batch of `EventLog` records
+fn output_records(
+    eventlog_records: &[EventLogRecord],
+    output: &mut Output,
+    start_time: &u64,
+    filter: &bool,
+) -> Result<(), EventLogsError> {
+    let serde_data_result = serde_json::to_value(eventlog_records);
     let serde_data = match serde_data_result {
         Ok(results) => results,
         Err(err) => {
@@ -152,7 +228,7 @@ fn read_eventlogs(path: &str, output: &mut Output, filter: &bool) -> Result<(),
         }
     };
 
-    let result = output_data(&serde_data, "eventlogs", output, &start_time, filter);
+    let result = output_data(&serde_data, "eventlogs", output, start_time, filter);
     match result {
         Ok(_result) => {}
         Err(err) => {
@@ -162,11 +238,53 @@ fn read_eventlogs(path: &str, output: &mut Output, filter: &bool) -> Result<(),
     Ok(())
 }
 
+/// Open the `EventLog` evtx file at provided path
+fn evtx_parser(path: &str) -> Result<EvtxParser<File>, EventLogsError> {
+    let evt_parser_results = EvtxParser::from_path(path);
+    match evt_parser_results {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("[eventlogs] Failed to parse event log {path}, error: {err:?}");
+            Err(EventLogsError::Parser)
+        }
+    }
+}
+
+/// Create an `EventLogRecord` and pull the common fields from the `System` section
+fn event_record(record: SerializedEvtxRecord<Value>) -> EventLogRecord {
+    let system = &record.data["Event"]["System"];
+    EventLogRecord {
+        event_record_id: record.event_record_id,
+        timestamp: record.timestamp.timestamp_nanos_opt().unwrap_or_default(),
+        provider: system["Provider"]["#attributes"]["Name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        event_id: event_id(&system["EventID"]),
+        level: system["Level"].as_u64().unwrap_or_default(),
+        channel: system["Channel"].as_str().unwrap_or_default().to_string(),
+        data: record.data,
+    }
+}
+
+/// Get the EventID. It is an object if the EventID has `Qualifiers`
+fn event_id(value: &Value) -> u64 {
+    match value.as_u64() {
+        Some(result) => result,
+        None => value["#text"].as_u64().unwrap_or_default(),
+    }
+}
+
 #[cfg(test)]
 #[cfg(target_os = "windows")]
 mod tests {
-    use super::{alt_eventlogs, default_eventlogs, grab_eventlogs, read_directory, read_eventlogs};
+    use super::{
+        alt_eventlogs, default_eventlogs, drive_eventlogs, event_id, grab_eventlogs,
+        parse_eventlogs, read_directory, read_eventlogs, EventFilter,
+    };
     use crate::{structs::artifacts::os::windows::EventLogsOptions, structs::toml::Output};
+    use common::windows::EventLogRecord;
+    use serde_json::{json, Value};
     use std::{fs::read_dir, path::PathBuf};
 
     fn output_options(name: &str, output: &str, directory: &str, compress: bool) -> Output {
@@ -180,15 +298,18 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
     #[test]
     fn test_grab_eventlogs() {
-        let options = EventLogsOptions { alt_file: None };
+        let options = EventLogsOptions {
+            alt_file: None,
+            alt_drive: None,
+            channels: None,
+            event_ids: None,
+        };
         let mut output = output_options("eventlog_temp", "local", "./tmp", true);
 
         let results = grab_eventlogs(&options, &mut output, &false).unwrap();
@@ -199,7 +320,7 @@ mod tests {
     fn test_default_eventlogs() {
         let mut output = output_options("eventlog_temp", "local", "./tmp", true);
 
-        let results = default_eventlogs(&mut output, &false).unwrap();
+        let results = default_eventlogs(&mut output, &false, &EventFilter::default()).unwrap();
         assert_eq!(results, ())
     }
 
@@ -209,7 +330,7 @@ mod tests {
         let path = "madeup";
         let mut output = output_options("eventlog_temp", "local", "./tmp", true);
 
-        let results = alt_eventlogs(&path, &mut output, &false).unwrap();
+        let results = alt_eventlogs(&path, &mut output, &false, &EventFilter::default()).unwrap();
         assert_eq!(results, ())
     }
 
@@ -219,8 +340,13 @@ mod tests {
         test_location.push("tests/test_data/windows/eventlogs");
         let mut output = output_options("eventlog_temp", "local", "./tmp", false);
 
-        let results =
-            read_directory(&test_location.display().to_string(), &mut output, &false).unwrap();
+        let results = read_directory(
+            &test_location.display().to_string(),
+            &mut output,
+            &false,
+            &EventFilter::default(),
+        )
+        .unwrap();
         assert_eq!(results, ())
     }
 
@@ -236,9 +362,91 @@ mod tests {
                 &file_path.unwrap().path().display().to_string(),
                 &mut output,
                 &false,
+                &EventFilter::default(),
             )
             .unwrap();
             assert_eq!(results, ())
         }
     }
+
+    #[test]
+    fn test_drive_eventlogs() {
+        let mut output = output_options("eventlog_temp", "local", "./tmp", true);
+
+        let results = drive_eventlogs(&'C', &mut output, &false, &EventFilter::default()).unwrap();
+        assert_eq!(results, ())
+    }
+
+    #[test]
+    fn test_parse_eventlogs_filter() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/eventlogs/System.evtx");
+        let path = test_location.display().to_string();
+
+        let all = parse_eventlogs(&path, &EventFilter::default()).unwrap();
+        assert!(!all.is_empty());
+        assert!(all.iter().all(|record| record.channel == "System"));
+        assert!(all.iter().all(|record| !record.provider.is_empty()));
+
+        let event_filter = EventFilter {
+            channels: vec![String::from("system")],
+            event_ids: vec![all[0].event_id],
+        };
+        let results = parse_eventlogs(&path, &event_filter).unwrap();
+        assert!(!results.is_empty());
+        assert!(results.len() <= all.len());
+        assert!(results
+            .iter()
+            .all(|record| record.event_id == all[0].event_id));
+
+        let event_filter = EventFilter {
+            channels: vec![String::from("Security")],
+            event_ids: Vec::new(),
+        };
+        let results = parse_eventlogs(&path, &event_filter).unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_event_filter() {
+        let record = EventLogRecord {
+            event_record_id: 1,
+            timestamp: 0,
+            provider: String::from("Microsoft-Windows-Security-Auditing"),
+            event_id: 4624,
+            level: 0,
+            channel: String::from("Security"),
+            data: Value::Null,
+        };
+
+        assert!(EventFilter::default().matches(&record));
+
+        let event_filter = EventFilter {
+            channels: vec![String::from("security")],
+            event_ids: vec![4624, 4625],
+        };
+        assert!(event_filter.matches(&record));
+
+        let event_filter = EventFilter {
+            channels: Vec::new(),
+            event_ids: vec![4625],
+        };
+        assert!(!event_filter.matches(&record));
+
+        let event_filter = EventFilter {
+            channels: vec![String::from("System")],
+            event_ids: Vec::new(),
+        };
+        assert!(!event_filter.matches(&record));
+    }
+
+    #[test]
+    fn test_event_id() {
+        assert_eq!(event_id(&json!(7045)), 7045);
+        assert_eq!(
+            event_id(&json!({"#attributes": {"Qualifiers": 16384}, "#text": 7036})),
+            7036
+        );
+        assert_eq!(event_id(&Value::Null), 0);
+    }
 }
@@ -0,0 +1,20 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ExecutionError {
+    UnknownSource,
+    Parser,
+    NoSources,
+}
+
+impl std::error::Error for ExecutionError {}
+
+impl fmt::Display for ExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutionError::UnknownSource => write!(f, "Unknown execution source"),
+            ExecutionError::Parser => write!(f, "Failed to parse execution source"),
+            ExecutionError::NoSources => write!(f, "Could not parse any execution sources"),
+        }
+    }
+}
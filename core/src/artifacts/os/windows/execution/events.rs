@@ -0,0 +1,230 @@
+use common::windows::{
+    Amcache, ExecutionEvent, ExecutionSource, Prefetch, UserAssistEntry, WindowsBits,
+};
+use serde_json::Value;
+
+/// Each `Prefetch` run time is an execution of the program
+pub(crate) fn prefetch_events(entries: &[Prefetch]) -> Vec<ExecutionEvent> {
+    let mut events = Vec::new();
+    for pf in entries {
+        let path = prefetch_executable(pf);
+        for run_time in &pf.all_run_times {
+            // Unused run time slots are zero
+            if *run_time == 0 {
+                continue;
+            }
+            events.push(ExecutionEvent {
+                path: path.clone(),
+                timestamp: *run_time,
+                source_artifact: ExecutionSource::Prefetch,
+                run_count: Some(pf.run_count),
+                sid: None,
+            });
+        }
+    }
+    events
+}
+
+/// `Prefetch` only stores the executable name. The full path is usually one of the accessed files
+fn prefetch_executable(pf: &Prefetch) -> String {
+    let name = format!("\\{}", pf.filename.to_lowercase());
+    pf.accessed_files
+        .iter()
+        .find(|file| file.to_lowercase().ends_with(&name))
+        .unwrap_or(&pf.filename)
+        .to_string()
+}
+
+/// `Amcache` entries use the Registry key last modified time
+pub(crate) fn amcache_events(entries: &[Amcache]) -> Vec<ExecutionEvent> {
+    entries
+        .iter()
+        .filter(|entry| !entry.path.is_empty() && entry.last_modified != 0)
+        .map(|entry| ExecutionEvent {
+            path: entry.path.clone(),
+            timestamp: entry.last_modified,
+            source_artifact: ExecutionSource::Amcache,
+            run_count: None,
+            sid: None,
+        })
+        .collect()
+}
+
+pub(crate) fn userassist_events(entries: &[UserAssistEntry]) -> Vec<ExecutionEvent> {
+    entries
+        .iter()
+        .filter(|entry| entry.last_execution != 0)
+        .map(|entry| ExecutionEvent {
+            path: entry.path.clone(),
+            timestamp: entry.last_execution,
+            source_artifact: ExecutionSource::UserAssist,
+            run_count: Some(entry.count),
+            sid: None,
+        })
+        .collect()
+}
+
+/// `BITS` jobs can run a command when the job completes or errors
+pub(crate) fn bits_events(bits: &WindowsBits) -> Vec<ExecutionEvent> {
+    let mut events = Vec::new();
+    for job in &bits.bits {
+        let commands = [&job.job_command, &job.notify_command];
+        bits_commands(
+            &mut events,
+            &commands,
+            job.completed,
+            job.modified,
+            &job.owner_sid,
+        );
+    }
+    for job in &bits.carved_jobs {
+        let commands = [&job.job_command, &job.notify_command];
+        bits_commands(
+            &mut events,
+            &commands,
+            job.completed,
+            job.modified,
+            &job.owner_sid,
+        );
+    }
+    events
+}
+
+/// Use the completed time if the job finished, otherwise the last modified time
+fn bits_commands(
+    events: &mut Vec<ExecutionEvent>,
+    commands: &[&String],
+    completed: i64,
+    modified: i64,
+    sid: &str,
+) {
+    let timestamp = if completed != 0 { completed } else { modified };
+    for command in commands {
+        if command.is_empty() {
+            continue;
+        }
+        events.push(ExecutionEvent {
+            path: command.to_string(),
+            timestamp,
+            source_artifact: ExecutionSource::Bits,
+            run_count: None,
+            sid: optional_sid(sid),
+        });
+    }
+}
+
+/// Parse the serialized `SRUM` Application Resource Usage table
+pub(crate) fn srum_events(table: &Value) -> Vec<ExecutionEvent> {
+    let rows = match table.as_array() {
+        Some(result) => result,
+        None => return Vec::new(),
+    };
+
+    let mut events = Vec::new();
+    for row in rows {
+        let path = row["app_id"].as_str().unwrap_or_default();
+        let timestamp = row["timestamp"].as_i64().unwrap_or_default();
+        if path.is_empty() || timestamp == 0 {
+            continue;
+        }
+
+        let sid = row["user_id"].as_str().unwrap_or_default();
+        events.push(ExecutionEvent {
+            path: path.to_string(),
+            timestamp,
+            source_artifact: ExecutionSource::Srum,
+            run_count: None,
+            sid: optional_sid(sid),
+        });
+    }
+    events
+}
+
+fn optional_sid(sid: &str) -> Option<String> {
+    if sid.is_empty() {
+        return None;
+    }
+    Some(sid.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bits_events, prefetch_events, srum_events, userassist_events};
+    use crate::artifacts::os::windows::{
+        bits::parser::grab_bits_path, prefetch::parser::custom_prefetch_path,
+    };
+    use common::windows::{ExecutionSource, UserAssistEntry};
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_prefetch_events() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win81");
+
+        let entries = custom_prefetch_path(&test_location.display().to_string()).unwrap();
+        let events = prefetch_events(&entries);
+        assert!(events.len() >= entries.len());
+        for event in events {
+            assert_eq!(event.source_artifact, ExecutionSource::Prefetch);
+            assert!(event.timestamp > 0);
+            assert!(event.run_count.is_some());
+        }
+    }
+
+    #[test]
+    fn test_userassist_events() {
+        let entries = vec![
+            UserAssistEntry {
+                path: String::from("C:\\Windows\\notepad.exe"),
+                last_execution: 1700000000,
+                count: 4,
+                focus_count: 0,
+                focus_time: 0,
+                reg_path: String::new(),
+                rot_path: String::new(),
+                folder_path: String::new(),
+            },
+            UserAssistEntry {
+                path: String::from("UEME_CTLSESSION"),
+                last_execution: 0,
+                count: 0,
+                focus_count: 0,
+                focus_time: 0,
+                reg_path: String::new(),
+                rot_path: String::new(),
+                folder_path: String::new(),
+            },
+        ];
+        let events = userassist_events(&entries);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].run_count, Some(4));
+        assert_eq!(events[0].source_artifact, ExecutionSource::UserAssist);
+    }
+
+    #[test]
+    fn test_bits_events() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/bits/win81/qmgr0.dat");
+
+        let bits = grab_bits_path(&test_location.display().to_string(), false).unwrap();
+        for event in bits_events(&bits) {
+            assert_eq!(event.source_artifact, ExecutionSource::Bits);
+            assert!(!event.path.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_srum_events() {
+        let table = json!([
+            {"app_id": "\\Device\\HarddiskVolume3\\Windows\\System32\\cmd.exe", "timestamp": 1700000000, "user_id": "S-1-5-18"},
+            {"app_id": "", "timestamp": 1700000000, "user_id": ""},
+        ]);
+        let events = srum_events(&table);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].sid.as_deref(), Some("S-1-5-18"));
+        assert_eq!(events[0].source_artifact, ExecutionSource::Srum);
+
+        assert!(srum_events(&json!({})).is_empty());
+    }
+}
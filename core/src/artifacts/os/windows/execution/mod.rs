@@ -0,0 +1,3 @@
+mod error;
+pub(crate) mod events;
+pub(crate) mod parser;
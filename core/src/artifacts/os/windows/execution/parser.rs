@@ -0,0 +1,260 @@
+/**
+ * Several Windows artifacts contain evidence of program execution: `Prefetch`, `Amcache`, `UserAssist`, `BITS` commands, and `SRUM`
+ * This combines them into a single timeline of `ExecutionEvent`s tagged with the originating artifact
+ *
+ * Events for the same program at about the same time (rounded to the minute) are merged into one event.
+ * The first source in the order above is kept. Missing run counts and SIDs are filled from the merged events
+ */
+use super::{
+    error::ExecutionError,
+    events::{amcache_events, bits_events, prefetch_events, srum_events, userassist_events},
+};
+use crate::{
+    artifacts::os::windows::{
+        amcache::parser::grab_amcache, bits::parser::grab_bits, prefetch::parser::grab_prefetch,
+        srum::parser::grab_srum_table, userassist::parser::grab_userassist,
+    },
+    structs::artifacts::os::windows::{
+        AmcacheOptions, BitsOptions, ExecutionOptions, PrefetchOptions, SrumOptions,
+        UserAssistOptions,
+    },
+};
+use common::windows::{ExecutionEvent, ExecutionSource};
+use log::{error, warn};
+use std::collections::HashMap;
+
+/// `SRUM` Application Resource Usage table
+const SRUM_APPLICATION: &str = "{D10CA2FE-6FCF-4F6D-848E-B2E99266FA89}";
+/// Timestamps are rounded to this many seconds when merging events
+const ROUND_SECONDS: i64 = 60;
+
+/// Get a merged execution timeline from the requested artifacts
+pub(crate) fn grab_execution(
+    options: &ExecutionOptions,
+) -> Result<Vec<ExecutionEvent>, ExecutionError> {
+    let sources = execution_sources(&options.sources)?;
+
+    let mut events = Vec::new();
+    let mut parsed = false;
+    for source in sources {
+        // Not every system has every artifact. Ex: SRUM is not enabled by default on Windows Server
+        if let Ok(mut result) = source_events(&source) {
+            events.append(&mut result);
+            parsed = true;
+        }
+    }
+
+    if !parsed {
+        return Err(ExecutionError::NoSources);
+    }
+    Ok(merge_execution(events))
+}
+
+/// Parse the execution source names. All sources are used if none are provided
+fn execution_sources(names: &[String]) -> Result<Vec<ExecutionSource>, ExecutionError> {
+    if names.is_empty() {
+        return Ok(vec![
+            ExecutionSource::Prefetch,
+            ExecutionSource::Amcache,
+            ExecutionSource::UserAssist,
+            ExecutionSource::Bits,
+            ExecutionSource::Srum,
+        ]);
+    }
+
+    let mut sources = Vec::new();
+    for name in names {
+        let source = match name.to_lowercase().as_str() {
+            "prefetch" => ExecutionSource::Prefetch,
+            "amcache" => ExecutionSource::Amcache,
+            "userassist" => ExecutionSource::UserAssist,
+            "bits" => ExecutionSource::Bits,
+            "srum" => ExecutionSource::Srum,
+            _ => {
+                error!("[execution] Unknown execution source: {name}");
+                return Err(ExecutionError::UnknownSource);
+            }
+        };
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+    // Keep the merge priority independent of the order provided
+    sources.sort_by_key(|source| *source as u8);
+    Ok(sources)
+}
+
+/// Run the parser for a single source using its default options
+fn source_events(source: &ExecutionSource) -> Result<Vec<ExecutionEvent>, ExecutionError> {
+    let events = match source {
+        ExecutionSource::Prefetch => {
+            let options = PrefetchOptions {
+                alt_dir: None,
+                entropy: None,
+                max_last_run_times: None,
+            };
+            match grab_prefetch(&options) {
+                Ok(result) => prefetch_events(&result),
+                Err(err) => {
+                    warn!("[execution] Could not parse Prefetch: {err:?}");
+                    return Err(ExecutionError::Parser);
+                }
+            }
+        }
+        ExecutionSource::Amcache => {
+            let options = AmcacheOptions { alt_file: None };
+            match grab_amcache(&options) {
+                Ok(result) => amcache_events(&result),
+                Err(err) => {
+                    warn!("[execution] Could not parse Amcache: {err:?}");
+                    return Err(ExecutionError::Parser);
+                }
+            }
+        }
+        ExecutionSource::UserAssist => {
+            let options = UserAssistOptions {
+                resolve_descriptions: None,
+                alt_file: None,
+            };
+            match grab_userassist(&options) {
+                Ok(result) => userassist_events(&result),
+                Err(err) => {
+                    warn!("[execution] Could not parse UserAssist: {err:?}");
+                    return Err(ExecutionError::Parser);
+                }
+            }
+        }
+        ExecutionSource::Bits => {
+            let options = BitsOptions {
+                alt_file: None,
+                carve: false,
+                max_carved: None,
+                carve_threads: None,
+                carve_chunk_size: None,
+            };
+            match grab_bits(&options) {
+                Ok(result) => bits_events(&result),
+                Err(err) => {
+                    warn!("[execution] Could not parse BITS: {err:?}");
+                    return Err(ExecutionError::Parser);
+                }
+            }
+        }
+        ExecutionSource::Srum => {
+            let options = SrumOptions { alt_file: None };
+            match grab_srum_table(&options, SRUM_APPLICATION) {
+                Ok(result) => srum_events(&result),
+                Err(err) => {
+                    warn!("[execution] Could not parse SRUM: {err:?}");
+                    return Err(ExecutionError::Parser);
+                }
+            }
+        }
+    };
+    Ok(events)
+}
+
+/**
+ * De-duplicate events on the path (case insensitive) and timestamp rounded to the minute
+ * Events should be ordered by source priority. Returns the events sorted by timestamp
+ */
+fn merge_execution(events: Vec<ExecutionEvent>) -> Vec<ExecutionEvent> {
+    let mut merged: Vec<ExecutionEvent> = Vec::new();
+    let mut seen: HashMap<(String, i64), usize> = HashMap::new();
+
+    for event in events {
+        let key = (event.path.to_lowercase(), round_timestamp(event.timestamp));
+        if let Some(index) = seen.get(&key) {
+            let existing = &mut merged[*index];
+            if existing.run_count.is_none() {
+                existing.run_count = event.run_count;
+            }
+            if existing.sid.is_none() {
+                existing.sid = event.sid;
+            }
+            continue;
+        }
+        seen.insert(key, merged.len());
+        merged.push(event);
+    }
+
+    merged.sort_by(|first, second| {
+        first
+            .timestamp
+            .cmp(&second.timestamp)
+            .then_with(|| first.path.cmp(&second.path))
+    });
+    merged
+}
+
+/// Round a timestamp to the nearest minute
+fn round_timestamp(timestamp: i64) -> i64 {
+    (timestamp + ROUND_SECONDS / 2).div_euclid(ROUND_SECONDS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{execution_sources, merge_execution, round_timestamp};
+    use common::windows::{ExecutionEvent, ExecutionSource};
+
+    fn event(path: &str, timestamp: i64, source: ExecutionSource) -> ExecutionEvent {
+        ExecutionEvent {
+            path: path.to_string(),
+            timestamp,
+            source_artifact: source,
+            run_count: None,
+            sid: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_execution() {
+        let mut prefetch = event(
+            "C:\\Windows\\cmd.exe",
+            1700000010,
+            ExecutionSource::Prefetch,
+        );
+        prefetch.run_count = Some(3);
+        let mut srum = event("c:\\windows\\CMD.EXE", 1700000020, ExecutionSource::Srum);
+        srum.sid = Some(String::from("S-1-5-18"));
+
+        let events = vec![
+            prefetch,
+            event(
+                "C:\\Windows\\notepad.exe",
+                1600000000,
+                ExecutionSource::Amcache,
+            ),
+            srum,
+            event("C:\\Windows\\cmd.exe", 1700003600, ExecutionSource::Srum),
+        ];
+
+        let merged = merge_execution(events);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].path, "C:\\Windows\\notepad.exe");
+        assert_eq!(merged[1].source_artifact, ExecutionSource::Prefetch);
+        assert_eq!(merged[1].run_count, Some(3));
+        assert_eq!(merged[1].sid.as_deref(), Some("S-1-5-18"));
+        assert_eq!(merged[2].timestamp, 1700003600);
+    }
+
+    #[test]
+    fn test_round_timestamp() {
+        assert_eq!(round_timestamp(1700000010), round_timestamp(1700000020));
+        assert_ne!(round_timestamp(1700000010), round_timestamp(1700000100));
+        assert_eq!(round_timestamp(0), 0);
+    }
+
+    #[test]
+    fn test_execution_sources() {
+        assert_eq!(execution_sources(&[]).unwrap().len(), 5);
+
+        let sources = execution_sources(&[String::from("SRUM"), String::from("prefetch")]).unwrap();
+        assert_eq!(
+            sources,
+            vec![ExecutionSource::Prefetch, ExecutionSource::Srum]
+        );
+
+        assert!(execution_sources(&[String::from("shimcache")]).is_err());
+    }
+}
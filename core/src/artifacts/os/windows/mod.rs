@@ -1,10 +1,12 @@
 pub(crate) mod accounts;
 pub(crate) mod amcache;
 pub(crate) mod artifacts;
+pub(crate) mod autoruns;
 pub(crate) mod bits;
 pub(crate) mod error;
 pub(crate) mod ese;
 pub(crate) mod eventlogs;
+pub(crate) mod execution;
 pub(crate) mod jumplists;
 mod ntfs;
 mod ole;
@@ -3,6 +3,7 @@ use std::fmt;
 #[derive(Debug)]
 pub enum PrefetchError {
     Header,
+    Signature,
     Decompress,
     Version,
     FileMetrics,
@@ -19,6 +20,7 @@ impl fmt::Display for PrefetchError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             PrefetchError::Header => write!(f, "Failed to read prefetch header"),
+            PrefetchError::Signature => write!(f, "Not a prefetch file"),
             PrefetchError::Decompress => write!(f, "Failed to decompress prefetch data"),
             PrefetchError::Version => write!(f, "Failed to parse version data"),
             PrefetchError::FileMetrics => write!(f, "Failed to parse file metrics data"),
@@ -1,4 +1,5 @@
 use crate::utils::nom_helper::{nom_unsigned_four_bytes, nom_unsigned_two_bytes, Endian};
+use byteorder::{LittleEndian, ReadBytesExt};
 use nom::bytes::complete::take;
 
 pub(crate) struct FileMetricsVersion23 {
@@ -8,32 +9,49 @@ pub(crate) struct FileMetricsVersion23 {
     pub(crate) filename_offset: u32,
     pub(crate) filename_size: u32,
     _unknown4: u32,
-    _mft_index: Vec<u8>, // 6 bytes
-    _sequence_number: u16,
+    /// NTFS file reference. Zero if the entry does not contain one
+    pub(crate) mft_entry: u64,
+    pub(crate) mft_sequence: u16,
 }
 
 impl FileMetricsVersion23 {
     /// Get File metrics related to accessed files.
-    /// Version 23 supports Win7+
+    /// Version 23 supports Win7+. Version 17 (WinXP) entries are smaller and do not contain a file reference
     pub(crate) fn parse_file_metrics<'a>(
         data: &'a [u8],
         metrics_offset: u32,
         number_entries: &'a u32,
+        version: u32,
     ) -> nom::IResult<&'a [u8], Vec<FileMetricsVersion23>> {
         let mut metrics_vec: Vec<FileMetricsVersion23> = Vec::new();
         let mut count = 0;
         let (mut metrics_data, _) = take(metrics_offset)(data)?;
+        let version17 = 17;
         while &count < number_entries {
             let (input, unknown) = nom_unsigned_four_bytes(metrics_data, Endian::Le)?;
             let (input, unknown2) = nom_unsigned_four_bytes(input, Endian::Le)?;
-            let (input, unknown3) = nom_unsigned_four_bytes(input, Endian::Le)?;
+            // Version 17 entries are 20 bytes. Version 23, 26, and 30 entries are 32 bytes
+            let (input, unknown3) = if version == version17 {
+                (input, 0)
+            } else {
+                nom_unsigned_four_bytes(input, Endian::Le)?
+            };
             let (input, filename_offset) = nom_unsigned_four_bytes(input, Endian::Le)?;
 
             let (input, filename_size) = nom_unsigned_four_bytes(input, Endian::Le)?;
-            let (input, unknown4) = nom_unsigned_four_bytes(input, Endian::Le)?;
-            let mft_size: usize = 6;
-            let (input, mft_index_data) = take(mft_size)(input)?;
-            let (input, sequence_number) = nom_unsigned_two_bytes(input, Endian::Le)?;
+            let (mut input, unknown4) = nom_unsigned_four_bytes(input, Endian::Le)?;
+
+            let mut mft_entry = 0;
+            let mut mft_sequence = 0;
+            if version != version17 {
+                let mft_size: usize = 6;
+                let (remaining, mut mft_index_data) = take(mft_size)(input)?;
+                let (remaining, sequence_number) = nom_unsigned_two_bytes(remaining, Endian::Le)?;
+
+                mft_entry = mft_index_data.read_u48::<LittleEndian>().unwrap_or(0);
+                mft_sequence = sequence_number;
+                input = remaining;
+            }
             let utf16_adjust = 2;
 
             let metrics = FileMetricsVersion23 {
@@ -43,8 +61,8 @@ impl FileMetricsVersion23 {
                 filename_offset,
                 filename_size: filename_size * utf16_adjust,
                 _unknown4: unknown4,
-                _mft_index: mft_index_data.to_vec(),
-                _sequence_number: sequence_number,
+                mft_entry,
+                mft_sequence,
             };
             count += 1;
             metrics_vec.push(metrics);
@@ -141,7 +159,7 @@ mod tests {
         ];
         let number_entries = 64;
         let (_, results) =
-            FileMetricsVersion23::parse_file_metrics(&test_data, 0, &number_entries).unwrap();
+            FileMetricsVersion23::parse_file_metrics(&test_data, 0, &number_entries, 30).unwrap();
 
         assert_eq!(results.len(), 64);
         assert_eq!(results[0].filename_offset, 0);
@@ -153,4 +171,23 @@ mod tests {
         assert_eq!(results[63].filename_offset, 0x27a8);
         assert_eq!(results[63].filename_size, 0xbe);
     }
+
+    #[test]
+    fn test_parse_file_metrics_version17() {
+        let test_data = vec![
+            1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 61, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0,
+            124, 0, 0, 0, 62, 0, 0, 0, 2, 0, 0, 0,
+        ];
+        let number_entries = 2;
+        let (input, results) =
+            FileMetricsVersion23::parse_file_metrics(&test_data, 0, &number_entries, 17).unwrap();
+
+        assert!(input.is_empty());
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].filename_offset, 0);
+        assert_eq!(results[0].filename_size, 0x7a);
+        assert_eq!(results[0].mft_entry, 0);
+        assert_eq!(results[1].filename_offset, 0x7c);
+        assert_eq!(results[1].filename_size, 0x7c);
+    }
 }
@@ -1,5 +1,6 @@
 use super::filemetrics::FileMetricsVersion23;
-use crate::utils::strings::extract_utf16_string;
+use crate::utils::strings::{extract_utf16_string_limit, MAX_UTF16_CHARS};
+use log::warn;
 use nom::bytes::complete::take;
 
 /// Get all the accessed files
@@ -16,7 +17,11 @@ pub(crate) fn get_accessed_files<'a>(
         let (filename_start, _) = take(metric.filename_offset)(input)?;
         let (_, filename) = take(metric.filename_size)(filename_start)?;
 
-        filenames.push(extract_utf16_string(filename));
+        let (filename, truncated) = extract_utf16_string_limit(filename, MAX_UTF16_CHARS);
+        if truncated {
+            warn!("[prefetch] Accessed filename longer than {MAX_UTF16_CHARS} characters. Truncated to: {filename}");
+        }
+        filenames.push(filename);
     }
     Ok((input, filenames))
 }
@@ -61,6 +66,7 @@ mod tests {
             &decom_data,
             result.file_array_offset,
             &result.number_files,
+            30,
         )
         .unwrap();
 
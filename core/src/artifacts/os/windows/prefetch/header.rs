@@ -2,7 +2,8 @@ use crate::utils::{
     nom_helper::{nom_unsigned_four_bytes, Endian},
     strings::extract_utf16_string,
 };
-use nom::bytes::complete::take;
+use log::error;
+use nom::{bytes::complete::take, error::ErrorKind};
 use serde::Serialize;
 
 pub(crate) struct CompressedHeader {
@@ -36,35 +37,48 @@ impl CompressedHeader {
 
 #[derive(Debug, Serialize)]
 pub(crate) struct Header {
+    /**Format version. Passed to the version specific parsers */
     pub(crate) version: u32,
     signature: u32,
     unknown: u32,
+    /**Size of the uncompressed prefetch data */
     pub(crate) size: u32,
+    /**Executable name. Max 29 characters */
     pub(crate) filename: String,
     pub(crate) pf_hash: String,
-    unknown_flags: u32,
+    flags: u32,
 }
 
 impl Header {
+    /// Parse the uncompressed prefetch header. Fails if the data does not have the `SCCA` signature
     pub(crate) fn parse_header(data: &[u8]) -> nom::IResult<&[u8], Header> {
         let (input, version) = nom_unsigned_four_bytes(data, Endian::Le)?;
         let (input, signature) = nom_unsigned_four_bytes(input, Endian::Le)?;
-        let (input, _unknown_data) = nom_unsigned_four_bytes(input, Endian::Le)?;
+        let sig = 0x41434353; // SCCA
+
+        if signature != sig {
+            error!("[prefetch] Not a prefetch file, got signature: {signature}");
+            return Err(nom::Err::Failure(nom::error::Error::new(
+                input,
+                ErrorKind::Fail,
+            )));
+        }
+        let (input, unknown) = nom_unsigned_four_bytes(input, Endian::Le)?;
         let (input, size) = nom_unsigned_four_bytes(input, Endian::Le)?;
 
         let filename_size: usize = 60;
         let (input, filename_data) = take(filename_size)(input)?;
         let (input, pf_hash) = nom_unsigned_four_bytes(input, Endian::Le)?;
-        let (input, _unknown_flags_data) = nom_unsigned_four_bytes(input, Endian::Le)?;
+        let (input, flags) = nom_unsigned_four_bytes(input, Endian::Le)?;
 
         let header = Header {
             version,
             signature,
-            unknown: 0,
+            unknown,
             size,
             filename: extract_utf16_string(filename_data),
             pf_hash: format!("{pf_hash:X?}"),
-            unknown_flags: 0,
+            flags,
         };
 
         Ok((input, header))
@@ -124,10 +138,10 @@ mod tests {
         let (_, result) = Header::parse_header(&result).unwrap();
         assert_eq!(result.version, 30);
         assert_eq!(result.signature, 0x41434353); // SCCA
-        assert_eq!(result.unknown, 0);
+        assert_eq!(result.unknown, 17);
         assert_eq!(result.filename, "7Z.EXE");
         assert_eq!(result.pf_hash, "886612C8");
-        assert_eq!(result.unknown_flags, 0);
+        assert_eq!(result.flags, 0);
     }
 
     #[test]
@@ -153,9 +167,36 @@ mod tests {
         let (_, result) = Header::parse_header(&result).unwrap();
         assert_eq!(result.version, 30);
         assert_eq!(result.signature, 0x41434353); // SCCA
-        assert_eq!(result.unknown, 0);
+        assert_eq!(result.unknown, 17);
         assert_eq!(result.filename, "7Z.EXE");
         assert_eq!(result.pf_hash, "886612C8");
-        assert_eq!(result.unknown_flags, 0);
+        assert_eq!(result.flags, 0);
+    }
+
+    #[test]
+    fn test_parse_header_uncompressed() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win7/DLLHOST.EXE-766398D2.pf");
+
+        let buffer = fs::read(test_location).unwrap();
+        let (_, result) = Header::parse_header(&buffer).unwrap();
+        assert_eq!(result.version, 23);
+        assert_eq!(result.signature, 0x41434353); // SCCA
+        assert_eq!(result.unknown, 17);
+        assert_eq!(result.size, 17440);
+        assert_eq!(result.size as usize, buffer.len());
+        assert_eq!(result.filename, "DLLHOST.EXE");
+        assert_eq!(result.pf_hash, "766398D2");
+        assert_eq!(result.flags, 0);
+    }
+
+    #[test]
+    fn test_parse_header_bad_signature() {
+        let mut test_data = vec![0; 84];
+        test_data[0] = 30;
+        test_data[4..8].copy_from_slice(b"MZ\x90\x00");
+
+        let result = Header::parse_header(&test_data);
+        assert!(matches!(result, Err(nom::Err::Failure(_))));
     }
 }
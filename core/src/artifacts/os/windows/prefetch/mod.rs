@@ -1,8 +1,9 @@
-mod error;
+pub(crate) mod error;
 mod filemetrics;
 mod filenames;
 mod header;
 pub(crate) mod parser;
 pub(crate) mod pf;
+mod registry;
 mod versions;
 mod volume;
@@ -11,41 +11,168 @@
  */
 use crate::{
     artifacts::os::windows::prefetch::error::PrefetchError,
-    filesystem::files::{file_extension, list_files, read_file},
+    filesystem::{
+        files::file_extension,
+        provider::{FileProvider, LiveFileProvider},
+    },
     structs::artifacts::os::windows::PrefetchOptions,
-    utils::environment::get_systemdrive,
+    utils::{entropy::is_high_entropy, environment::get_systemdrive},
 };
-use common::windows::Prefetch;
-use log::error;
+use common::windows::{Prefetch, PrefetchState, PrefetchStatus};
+use log::{error, warn};
 
-use super::pf::parse_prefetch;
+use super::{pf::parse_prefetch, registry::enable_prefetcher};
+
+/// Windows 8+ prefetch files store up to eight last run times
+const MAX_LAST_RUN_TIMES: usize = 8;
 
 /// Parse `Prefetch` based on `PrefetchOptions`
-pub(crate) fn grab_prefetch(options: &PrefetchOptions) -> Result<Vec<Prefetch>, PrefetchError> {
+pub fn grab_prefetch(options: &PrefetchOptions) -> Result<Vec<Prefetch>, PrefetchError> {
+    collect_prefetch(options, false)
+}
+
+/// Parse `Prefetch` based on `PrefetchOptions`. Raw volume bytes are only kept if `include_raw` is set
+pub(crate) fn collect_prefetch(
+    options: &PrefetchOptions,
+    include_raw: bool,
+) -> Result<Vec<Prefetch>, PrefetchError> {
+    let provider = LiveFileProvider { raw_ntfs: false };
+    let mut prefetch_data = if let Some(path) = &options.alt_dir {
+        provider_prefetch_path(path, &provider, include_raw)?
+    } else {
+        let path = format!("{}:\\Windows\\Prefetch", systemdrive()?);
+        read_directory(&path, &provider, include_raw)?
+    };
+
+    if options.entropy.unwrap_or(false) {
+        match entropy_root(options.alt_dir.as_deref()) {
+            Some(root) => check_entropy(&mut prefetch_data, &root),
+            None => warn!("[prefetch] Could not determine the volume of the alternative Prefetch directory. Skipping entropy"),
+        }
+    }
+    trim_run_times(
+        &mut prefetch_data,
+        options.max_last_run_times.unwrap_or(MAX_LAST_RUN_TIMES),
+    );
+    Ok(prefetch_data)
+}
+
+/// Only keep the `max` most recent last run times. The most recent run time is always kept
+fn trim_run_times(prefetch_data: &mut [Prefetch], max: usize) {
+    let max = max.max(1);
+    for prefetch in prefetch_data.iter_mut() {
+        if prefetch.all_run_times.len() <= max {
+            continue;
+        }
+        prefetch
+            .all_run_times
+            .sort_unstable_by(|first, second| second.cmp(first));
+        prefetch.all_run_times.truncate(max);
+        prefetch
+            .all_run_filetimes
+            .sort_unstable_by(|first, second| second.cmp(first));
+        prefetch.all_run_filetimes.truncate(max);
+    }
+}
+
+/**
+ * Get the state of the `Prefetch` directory used by `PrefetchOptions` and the number of parsed prefetch files  
+ * The `EnablePrefetcher` Registry value is only read when collecting from the systemdrive
+ */
+pub(crate) fn grab_prefetch_status(options: &PrefetchOptions, parsed: usize) -> PrefetchStatus {
+    let provider = LiveFileProvider { raw_ntfs: false };
     if let Some(path) = &options.alt_dir {
-        return custom_prefetch_path(path);
+        return prefetch_status(path, parsed, None, &provider);
+    }
+    match systemdrive() {
+        Ok(drive) => {
+            let path = format!("{drive}:\\Windows\\Prefetch");
+            prefetch_status(&path, parsed, enable_prefetcher(&drive), &provider)
+        }
+        Err(_err) => prefetch_status("", parsed, None, &provider),
     }
+}
+
+/// Determine if the `Prefetch` directory is missing, empty, or contains prefetch files
+fn prefetch_status(
+    path: &str,
+    parsed: usize,
+    enable_prefetcher: Option<u32>,
+    provider: &dyn FileProvider,
+) -> PrefetchStatus {
+    let is_dir = match provider.metadata(path) {
+        Ok(result) => result.is_directory,
+        Err(_err) => false,
+    };
 
+    let state = if !is_dir {
+        PrefetchState::Missing
+    } else {
+        let has_prefetch = match provider.list_dir(path) {
+            Ok(result) => result.iter().any(|file| file_extension(file) == "pf"),
+            Err(_err) => false,
+        };
+        if has_prefetch {
+            PrefetchState::Parsed
+        } else {
+            PrefetchState::Empty
+        }
+    };
+
+    let disabled = state == PrefetchState::Empty || enable_prefetcher == Some(0);
+    PrefetchStatus {
+        path: path.to_string(),
+        state,
+        files: parsed,
+        enable_prefetcher,
+        disabled,
+    }
+}
+
+/// Get the systemdrive letter
+fn systemdrive() -> Result<char, PrefetchError> {
     let drive_result = get_systemdrive();
-    let drive = match drive_result {
-        Ok(result) => result,
+    match drive_result {
+        Ok(result) => Ok(result),
         Err(err) => {
             error!("[prefetch] Could not determine systemdrive: {err:?}");
-            return Err(PrefetchError::DriveLetter);
+            Err(PrefetchError::DriveLetter)
         }
-    };
-    let path = format!("{drive}:\\Windows\\Prefetch");
-    read_directory(&path)
+    }
+}
+
+/**
+ * Read and parse prefetch files at a custom directory path  
+ * Can be any directory including a mounted Volume Shadow Copy (`\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopy1\Windows\Prefetch`)
+ */
+pub fn custom_prefetch_path(path: &str) -> Result<Vec<Prefetch>, PrefetchError> {
+    provider_prefetch_path(path, &LiveFileProvider { raw_ntfs: false }, false)
 }
 
-/// Read and parse prefetch files at a custom path
-pub(crate) fn custom_prefetch_path(path: &str) -> Result<Vec<Prefetch>, PrefetchError> {
-    read_directory(path)
+/// Read and parse prefetch files at a directory path using the provided `FileProvider`
+pub(crate) fn provider_prefetch_path(
+    path: &str,
+    provider: &dyn FileProvider,
+    include_raw: bool,
+) -> Result<Vec<Prefetch>, PrefetchError> {
+    let is_dir = match provider.metadata(path) {
+        Ok(result) => result.is_directory,
+        Err(_err) => false,
+    };
+    if !is_dir {
+        error!("[prefetch] Provided prefetch path {path} is not a directory");
+        return Err(PrefetchError::ReadDirectory);
+    }
+    read_directory(path, provider, include_raw)
 }
 
 /// Read all files at provided path
-fn read_directory(path: &str) -> Result<Vec<Prefetch>, PrefetchError> {
-    let dir_results = list_files(path);
+fn read_directory(
+    path: &str,
+    provider: &dyn FileProvider,
+    include_raw: bool,
+) -> Result<Vec<Prefetch>, PrefetchError> {
+    let dir_results = provider.list_dir(path);
     let read_dir = match dir_results {
         Ok(result) => result,
         Err(err) => {
@@ -61,7 +188,7 @@ fn read_directory(path: &str) -> Result<Vec<Prefetch>, PrefetchError> {
             continue;
         }
 
-        let prefetch_results = read_prefetch(&pf_file);
+        let prefetch_results = read_prefetch(&pf_file, provider, include_raw);
         match prefetch_results {
             Ok(result) => prefetch_data.push(result),
             Err(err) => {
@@ -75,8 +202,12 @@ fn read_directory(path: &str) -> Result<Vec<Prefetch>, PrefetchError> {
 }
 
 /// Read and parse the prefetch file
-fn read_prefetch(path: &str) -> Result<Prefetch, PrefetchError> {
-    let buffer_results = read_file(path);
+fn read_prefetch(
+    path: &str,
+    provider: &dyn FileProvider,
+    include_raw: bool,
+) -> Result<Prefetch, PrefetchError> {
+    let buffer_results = provider.read(path);
     let buffer = match buffer_results {
         Ok(result) => result,
         Err(err) => {
@@ -85,25 +216,111 @@ fn read_prefetch(path: &str) -> Result<Prefetch, PrefetchError> {
         }
     };
 
-    parse_prefetch(&buffer, path)
+    parse_prefetch(&buffer, path, include_raw)
+}
+
+/**
+ * Get the root of the volume that executables are read from for entropy  
+ * An alternative directory must end in `Windows\Prefetch`. Ex: `E:\mount\Windows\Prefetch` uses `E:\mount`
+ */
+fn entropy_root(alt_dir: Option<&str>) -> Option<String> {
+    let path = match alt_dir {
+        Some(result) => result.trim_end_matches(['\\', '/']),
+        None => return systemdrive().ok().map(|drive| format!("{drive}:")),
+    };
+
+    let suffix = "\\windows\\prefetch";
+    let normalized = path.replace('/', "\\").to_ascii_lowercase();
+    if !normalized.ends_with(suffix) {
+        return None;
+    }
+    Some(path[..path.len() - suffix.len()].to_string())
+}
+
+/// Flag prefetch entries whose executable still exists and has high entropy
+fn check_entropy(prefetch_data: &mut [Prefetch], root: &str) {
+    for prefetch in prefetch_data.iter_mut() {
+        if let Some(path) = executable_path(prefetch, root) {
+            prefetch.high_entropy = is_high_entropy(&path);
+        }
+    }
+}
+
+/**
+ * Get the path to the executable referenced by the prefetch file
+ * Prefetch paths start with a volume (`\VOLUME{...}` or `\DEVICE\HARDDISKVOLUME2`). The volume is assumed to be the provided root
+ */
+fn executable_path(prefetch: &Prefetch, root: &str) -> Option<String> {
+    // Prefetch filenames are truncated to 29 characters
+    let filename = prefetch.filename.to_uppercase();
+    let file = prefetch.accessed_files.iter().find(|entry| {
+        entry
+            .rsplit('\\')
+            .next()
+            .is_some_and(|name| name.to_uppercase().starts_with(&filename))
+    })?;
+
+    let mut components = file.trim_start_matches('\\').splitn(2, '\\');
+    let volume = components.next()?;
+    let mut path = components.next()?;
+    if volume.to_uppercase() == "DEVICE" {
+        path = path.split_once('\\')?.1;
+    }
+    Some(format!("{root}\\{path}"))
 }
 
 #[cfg(test)]
 #[cfg(target_os = "windows")]
 mod tests {
-    use super::{custom_prefetch_path, grab_prefetch};
+    use super::{
+        custom_prefetch_path, entropy_root, executable_path, grab_prefetch, prefetch_status,
+    };
     use crate::{
-        artifacts::os::windows::prefetch::parser::{read_directory, read_prefetch},
+        artifacts::os::windows::prefetch::parser::{
+            grab_prefetch_status, read_directory, read_prefetch,
+        },
+        filesystem::provider::LiveFileProvider,
         structs::artifacts::os::windows::PrefetchOptions,
     };
-    use std::path::PathBuf;
+    use common::windows::PrefetchState;
+    use std::{
+        fs::{create_dir_all, remove_dir_all},
+        path::PathBuf,
+    };
 
     #[test]
     fn test_grab_prefetch() {
-        let options = PrefetchOptions { alt_dir: None };
+        let options = PrefetchOptions {
+            alt_dir: None,
+            entropy: None,
+            max_last_run_times: None,
+        };
         let _ = grab_prefetch(&options).unwrap();
     }
 
+    #[test]
+    fn test_grab_prefetch_directory_path() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win10");
+
+        let options = PrefetchOptions {
+            alt_dir: Some(test_location.display().to_string()),
+            entropy: None,
+            max_last_run_times: None,
+        };
+        let results = grab_prefetch(&options).unwrap();
+        assert_eq!(results.len(), 272);
+    }
+
+    #[test]
+    #[should_panic(expected = "ReadDirectory")]
+    fn test_custom_prefetch_path_missing() {
+        let _ = custom_prefetch_path(
+            "\\\\?\\GLOBALROOT\\Device\\HarddiskVolumeShadowCopy9999\\Windows\\Prefetch",
+        )
+        .unwrap();
+    }
+
     #[test]
     fn test_custom_prefetch_path() {
         let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -128,7 +345,12 @@ mod tests {
         let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_location.push("tests/test_data/windows/prefetch/win10");
 
-        let results = read_directory(&test_location.display().to_string()).unwrap();
+        let results = read_directory(
+            &test_location.display().to_string(),
+            &LiveFileProvider { raw_ntfs: false },
+            false,
+        )
+        .unwrap();
         assert_eq!(results.len(), 272);
 
         assert_eq!(
@@ -221,7 +443,12 @@ mod tests {
         let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_location.push("tests/test_data/windows/prefetch/win81/CMD.EXE-AC113AA8.pf");
 
-        let results = read_prefetch(&test_location.display().to_string()).unwrap();
+        let results = read_prefetch(
+            &test_location.display().to_string(),
+            &LiveFileProvider { raw_ntfs: false },
+            false,
+        )
+        .unwrap();
 
         assert_eq!(results.path.contains("CMD.EXE-AC113AA8.pf"), true);
         assert_eq!(results.filename, "CMD.EXE");
@@ -274,7 +501,12 @@ mod tests {
         let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_location.push("tests/test_data/windows/prefetch/bad data/malformed.pf");
 
-        let results = read_prefetch(&test_location.display().to_string()).unwrap();
+        let results = read_prefetch(
+            &test_location.display().to_string(),
+            &LiveFileProvider { raw_ntfs: false },
+            false,
+        )
+        .unwrap();
 
         assert_eq!(results.path.contains("CMD.EXE-AC113AA8.pf"), true);
         assert_eq!(results.filename, "CMD.EXE");
@@ -320,4 +552,158 @@ mod tests {
             "\\DEVICE\\HARDDISKVOLUME2\\WINDOWS\\SYSWOW64"
         );
     }
+
+    #[test]
+    fn test_executable_path() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win81/CMD.EXE-AC113AA8.pf");
+
+        let mut result = read_prefetch(
+            &test_location.display().to_string(),
+            &LiveFileProvider { raw_ntfs: false },
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            executable_path(&result, "C:").unwrap(),
+            "C:\\WINDOWS\\SYSWOW64\\CMD.EXE"
+        );
+
+        result.accessed_files = vec![String::from(
+            "\\VOLUME{01d0f9a19c586134-d49d126f}\\USERS\\BOB\\CMD.EXE",
+        )];
+        assert_eq!(
+            executable_path(&result, "D:").unwrap(),
+            "D:\\USERS\\BOB\\CMD.EXE"
+        );
+
+        result.accessed_files = Vec::new();
+        assert!(executable_path(&result, "C:").is_none());
+    }
+
+    #[test]
+    fn test_entropy_root() {
+        assert_eq!(
+            entropy_root(Some("E:\\mount\\Windows\\Prefetch\\")).unwrap(),
+            "E:\\mount"
+        );
+        assert_eq!(
+            entropy_root(Some(
+                "\\\\?\\GLOBALROOT\\Device\\HarddiskVolumeShadowCopy1\\Windows\\Prefetch"
+            ))
+            .unwrap(),
+            "\\\\?\\GLOBALROOT\\Device\\HarddiskVolumeShadowCopy1"
+        );
+        assert!(entropy_root(Some("E:\\prefetch_export")).is_none());
+        assert!(entropy_root(None).unwrap().ends_with(':'));
+    }
+
+    #[test]
+    fn test_grab_prefetch_max_last_run_times() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win10");
+
+        let mut options = PrefetchOptions {
+            alt_dir: Some(test_location.display().to_string()),
+            entropy: None,
+            max_last_run_times: None,
+        };
+        let full = grab_prefetch(&options).unwrap();
+        assert!(full.iter().any(|entry| entry.all_run_times.len() == 8));
+
+        options.max_last_run_times = Some(3);
+        let trimmed = grab_prefetch(&options).unwrap();
+        assert_eq!(full.len(), trimmed.len());
+        for (full_entry, trimmed_entry) in full.iter().zip(trimmed.iter()) {
+            let mut most_recent = full_entry.all_run_times.clone();
+            most_recent.sort_unstable_by(|first, second| second.cmp(first));
+            most_recent.truncate(3);
+            assert_eq!(trimmed_entry.all_run_times, most_recent);
+            assert_eq!(trimmed_entry.last_run_time, full_entry.last_run_time);
+        }
+
+        // The most recent run time is always kept
+        options.max_last_run_times = Some(0);
+        let results = grab_prefetch(&options).unwrap();
+        for (full_entry, entry) in full.iter().zip(results.iter()) {
+            let most_recent: Vec<i64> = full_entry
+                .all_run_times
+                .iter()
+                .max()
+                .into_iter()
+                .copied()
+                .collect();
+            assert_eq!(entry.all_run_times, most_recent);
+        }
+    }
+
+    #[test]
+    fn test_grab_prefetch_entropy() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win81");
+
+        let options = PrefetchOptions {
+            alt_dir: Some(test_location.display().to_string()),
+            entropy: Some(true),
+            max_last_run_times: None,
+        };
+        let results = grab_prefetch(&options).unwrap();
+        assert!(!results.is_empty());
+    }
+
+    #[test]
+    fn test_prefetch_status_empty() {
+        let path = "./tmp/prefetch_status_empty";
+        let _ = remove_dir_all(path);
+        create_dir_all(path).unwrap();
+
+        let status = prefetch_status(path, 0, None, &LiveFileProvider { raw_ntfs: false });
+        assert_eq!(status.state, PrefetchState::Empty);
+        assert_eq!(status.files, 0);
+        assert!(status.disabled);
+    }
+
+    #[test]
+    fn test_prefetch_status_missing() {
+        let status = prefetch_status(
+            "./tmp/prefetch_status_missing",
+            0,
+            None,
+            &LiveFileProvider { raw_ntfs: false },
+        );
+        assert_eq!(status.state, PrefetchState::Missing);
+        assert!(!status.disabled);
+    }
+
+    #[test]
+    fn test_prefetch_status_registry_disabled() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win10");
+
+        let status = prefetch_status(
+            &test_location.display().to_string(),
+            272,
+            Some(0),
+            &LiveFileProvider { raw_ntfs: false },
+        );
+        assert_eq!(status.state, PrefetchState::Parsed);
+        assert!(status.disabled);
+    }
+
+    #[test]
+    fn test_grab_prefetch_status() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win10");
+
+        let options = PrefetchOptions {
+            alt_dir: Some(test_location.display().to_string()),
+            entropy: None,
+            max_last_run_times: None,
+        };
+        let status = grab_prefetch_status(&options, 272);
+        assert_eq!(status.state, PrefetchState::Parsed);
+        assert_eq!(status.files, 272);
+        assert_eq!(status.enable_prefetcher, None);
+        assert!(!status.disabled);
+    }
 }
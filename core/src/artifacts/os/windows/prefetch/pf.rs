@@ -7,11 +7,15 @@ use super::{
     volume::Volume,
 };
 use crate::utils::compression::decompress::{decompress_xpress, XpressType};
-use common::windows::Prefetch;
+use common::windows::{FileMetric, Prefetch};
 use log::error;
 
-/// Parse Prefetch files and return parsed data or error
-pub(crate) fn parse_prefetch(data: &[u8], path: &str) -> Result<Prefetch, PrefetchError> {
+/// Parse Prefetch files and return parsed data or error. Raw volume bytes are only kept if `include_raw` is set
+pub(crate) fn parse_prefetch(
+    data: &[u8],
+    path: &str,
+    include_raw: bool,
+) -> Result<Prefetch, PrefetchError> {
     let is_compressed_results = CompressedHeader::is_compressed(data);
     let is_compressed = match is_compressed_results {
         Ok((_, result)) => result,
@@ -33,19 +37,27 @@ pub(crate) fn parse_prefetch(data: &[u8], path: &str) -> Result<Prefetch, Prefet
         }
     } else {
         // Data is not compressed
-        return get_prefetch_data(data, path);
+        return get_prefetch_data(data, path, include_raw);
     };
 
     let pf_data = decompress_pf(&mut pf_data.to_vec(), &header.uncompressed_size)?;
-    get_prefetch_data(&pf_data, path)
+    get_prefetch_data(&pf_data, path, include_raw)
 }
 
 /// Get each part of the prefetch file format
-fn get_prefetch_data(data: &[u8], path: &str) -> Result<Prefetch, PrefetchError> {
+fn get_prefetch_data(
+    data: &[u8],
+    path: &str,
+    include_raw: bool,
+) -> Result<Prefetch, PrefetchError> {
     let results = Header::parse_header(data);
 
     let (pf_data, header) = match results {
         Ok((data, result)) => (data, result),
+        Err(nom::Err::Failure(_)) => {
+            error!("[prefetch] {path} does not have a prefetch signature");
+            return Err(PrefetchError::Signature);
+        }
         Err(err) => {
             error!("[prefetch] Failed to parse header: {err:?}");
             return Err(PrefetchError::Header);
@@ -61,11 +73,11 @@ fn get_prefetch_data(data: &[u8], path: &str) -> Result<Prefetch, PrefetchError>
         }
     };
 
-    // Version 23 supports Win7+
     let results = FileMetricsVersion23::parse_file_metrics(
         data,
         version.file_array_offset,
         &version.number_files,
+        header.version,
     );
     let metrics = match results {
         Ok((_, result)) => result,
@@ -89,6 +101,7 @@ fn get_prefetch_data(data: &[u8], path: &str) -> Result<Prefetch, PrefetchError>
         version.volume_info_offset,
         &version.number_volumes,
         header.version,
+        include_raw,
     );
     let volumes = match results {
         Ok((_, result)) => result,
@@ -98,21 +111,40 @@ fn get_prefetch_data(data: &[u8], path: &str) -> Result<Prefetch, PrefetchError>
         }
     };
 
+    let file_metrics = metrics
+        .iter()
+        .zip(filenames.iter())
+        .map(|(metric, filename)| FileMetric {
+            filename: filename.clone(),
+            mft_entry: metric.mft_entry,
+            mft_sequence: metric.mft_sequence,
+        })
+        .collect();
+
     let mut prefetch = Prefetch {
         path: path.to_string(),
         filename: header.filename,
         hash: header.pf_hash,
+        version: header.version,
         last_run_time: version.run_times.first().unwrap_or(&0).to_owned(),
         all_run_times: version.run_times,
+        last_run_filetime: version.run_filetimes.first().unwrap_or(&0).to_owned(),
+        all_run_filetimes: version.run_filetimes,
         run_count: version.run_count,
         size: header.size,
         volume_serial: Vec::new(),
         volume_creation: Vec::new(),
+        volume_creation_filetime: Vec::new(),
         volume_path: Vec::new(),
         accessed_files_count: version.number_files,
         accessed_directories_count: 0,
         accessed_files: filenames,
         accessed_directories: Vec::new(),
+        file_metrics,
+        suspicious: false,
+        anomalies: Vec::new(),
+        high_entropy: false,
+        raw_volumes: Vec::new(),
     };
 
     // Loop through multiple volumes if needed
@@ -121,13 +153,21 @@ fn get_prefetch_data(data: &[u8], path: &str) -> Result<Prefetch, PrefetchError>
             .volume_serial
             .push(format!("{:X?}", volume.volume_serial));
         prefetch.volume_creation.push(volume.volume_creation);
+        prefetch
+            .volume_creation_filetime
+            .push(volume.volume_creation_filetime);
         prefetch.volume_path.push(volume.volume_path);
 
         prefetch.accessed_directories_count += volume.number_directory_strings;
         prefetch
             .accessed_directories
             .append(&mut volume.directories);
+        prefetch.anomalies.append(&mut volume.anomalies);
+        if include_raw {
+            prefetch.raw_volumes.push(volume.raw);
+        }
     }
+    prefetch.suspicious = !prefetch.anomalies.is_empty();
 
     Ok(prefetch)
 }
@@ -179,7 +219,10 @@ mod tests {
             pf::{decompress_pf, get_prefetch_data, parse_prefetch},
         },
         filesystem::files::read_file,
-        utils::compression::decompress::{decompress_xpress, XpressType},
+        utils::{
+            compression::decompress::{decompress_xpress, XpressType},
+            time::filetime_to_unixepoch,
+        },
     };
     use std::path::PathBuf;
 
@@ -189,11 +232,12 @@ mod tests {
         test_location.push("tests/test_data/windows/prefetch/win10/_IU14D2N.TMP-136252D4.pf");
 
         let buffer = read_file(&test_location.to_str().unwrap()).unwrap();
-        let results = parse_prefetch(&buffer, test_location.to_str().unwrap()).unwrap();
+        let results = parse_prefetch(&buffer, test_location.to_str().unwrap(), false).unwrap();
 
         assert_eq!(results.path.contains("_IU14D2N.TMP-136252D4.pf"), true);
         assert_eq!(results.filename, "_IU14D2N.TMP");
         assert_eq!(results.hash, "136252D4");
+        assert_eq!(results.version, 30);
         assert_eq!(results.last_run_time, 1655507964);
         assert_eq!(
             results.all_run_times,
@@ -239,13 +283,23 @@ mod tests {
         );
     }
 
+    #[test]
+    #[should_panic(expected = "Signature")]
+    fn test_parse_prefetch_not_prefetch() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/registry/win10/NTUSER.DAT");
+
+        let buffer = read_file(&test_location.to_str().unwrap()).unwrap();
+        let _ = parse_prefetch(&buffer, test_location.to_str().unwrap(), false).unwrap();
+    }
+
     #[test]
     fn test_get_prefetch_data() {
         let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_location.push("tests/test_data/windows/prefetch/win81/CMD.EXE-AC113AA8.pf");
 
         let buffer = read_file(&test_location.to_str().unwrap()).unwrap();
-        let results = get_prefetch_data(&buffer, test_location.to_str().unwrap()).unwrap();
+        let results = get_prefetch_data(&buffer, test_location.to_str().unwrap(), false).unwrap();
 
         assert_eq!(results.path.contains("CMD.EXE-AC113AA8.pf"), true);
         assert_eq!(results.filename, "CMD.EXE");
@@ -278,6 +332,17 @@ mod tests {
         );
         assert_eq!(results.accessed_files[27], "\\DEVICE\\HARDDISKVOLUME2\\USERS\\BOB\\APPDATA\\LOCAL\\TEMP\\TMP832F744F467240578F4610EC7E1C7547.EXEC.CMD");
 
+        assert_eq!(results.file_metrics.len(), 28);
+        assert_eq!(
+            results.file_metrics[0].filename,
+            "\\DEVICE\\HARDDISKVOLUME2\\WINDOWS\\SYSTEM32\\NTDLL.DLL"
+        );
+        assert_eq!(results.file_metrics[0].mft_entry, 292116);
+        assert_eq!(results.file_metrics[0].mft_sequence, 2);
+        assert_eq!(results.file_metrics[3].mft_entry, 292457);
+        assert_eq!(results.file_metrics[3].mft_sequence, 1);
+        assert_eq!(results.file_metrics[27].mft_entry, 0);
+
         assert_eq!(
             results.accessed_directories[2],
             "\\DEVICE\\HARDDISKVOLUME2\\WINDOWS\\GLOBALIZATION\\SORTING"
@@ -292,6 +357,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_prefetch_data_filetimes() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win81/CMD.EXE-AC113AA8.pf");
+
+        let buffer = read_file(&test_location.to_str().unwrap()).unwrap();
+        let results = get_prefetch_data(&buffer, test_location.to_str().unwrap(), false).unwrap();
+
+        assert_eq!(
+            filetime_to_unixepoch(&results.last_run_filetime),
+            results.last_run_time
+        );
+        assert_eq!(results.all_run_filetimes.len(), results.all_run_times.len());
+        for (filetime, run_time) in results.all_run_filetimes.iter().zip(&results.all_run_times) {
+            assert_eq!(filetime_to_unixepoch(filetime), *run_time);
+        }
+        assert_eq!(results.volume_creation_filetime.len(), 1);
+        assert_eq!(
+            filetime_to_unixepoch(&results.volume_creation_filetime[0]),
+            results.volume_creation[0]
+        );
+    }
+
+    #[test]
+    fn test_get_prefetch_data_include_raw() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win81/CMD.EXE-AC113AA8.pf");
+
+        let buffer = read_file(&test_location.to_str().unwrap()).unwrap();
+        let results = get_prefetch_data(&buffer, test_location.to_str().unwrap(), false).unwrap();
+        assert!(results.raw_volumes.is_empty());
+
+        let results = get_prefetch_data(&buffer, test_location.to_str().unwrap(), true).unwrap();
+        assert_eq!(results.raw_volumes.len(), 1);
+        assert!(!results.raw_volumes[0].is_empty());
+    }
+
+    #[test]
+    fn test_get_prefetch_data_version31() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/versions/version31.pf");
+
+        let buffer = read_file(&test_location.to_str().unwrap()).unwrap();
+        let results = get_prefetch_data(&buffer, test_location.to_str().unwrap(), false).unwrap();
+
+        assert_eq!(results.filename, "7Z.EXE");
+        assert_eq!(results.version, 31);
+        assert_eq!(results.volume_serial, vec!["4290933E"]);
+        assert_eq!(
+            results.volume_path,
+            vec!["\\VOLUME{01d6828290579d13-4290933e}"]
+        );
+        assert_eq!(results.accessed_directories_count, 15);
+        assert!(results.anomalies.is_empty());
+    }
+
     #[test]
     fn test_decompress_win11() {
         let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -0,0 +1,83 @@
+use crate::{
+    artifacts::os::windows::registry::helper::get_registry_keys, utils::regex_options::create_regex,
+};
+use common::windows::RegistryEntry;
+use log::warn;
+
+/**
+ * Get the `EnablePrefetcher` value from the SYSTEM Registry file  
+ * 0 = disabled, 1 = application launch, 2 = boot launch, 3 = application and boot launch
+ */
+pub(crate) fn enable_prefetcher(drive: &char) -> Option<u32> {
+    let path = format!("{drive}:\\Windows\\System32\\config\\SYSTEM");
+    let start_path = "";
+    let regex = create_regex(
+        r".*\\controlset([0-9]+)\\control\\session manager\\memory management\\prefetchparameters$",
+    )
+    .unwrap(); // always valid
+
+    let entries_result = get_registry_keys(start_path, &regex, &path);
+    let entries = match entries_result {
+        Ok(result) => result,
+        Err(err) => {
+            warn!("[prefetch] Could not read EnablePrefetcher from {path}: {err:?}");
+            return None;
+        }
+    };
+
+    prefetcher_value(&entries)
+}
+
+/// Get the `EnablePrefetcher` value from the `PrefetchParameters` key(s)
+fn prefetcher_value(entries: &[RegistryEntry]) -> Option<u32> {
+    for entry in entries {
+        for value in &entry.values {
+            if value.value.to_lowercase() != "enableprefetcher" {
+                continue;
+            }
+            if let Ok(result) = value.data.parse::<u32>() {
+                return Some(result);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prefetcher_value;
+    use common::windows::{KeyValue, RegistryEntry};
+
+    #[test]
+    fn test_prefetcher_value() {
+        let entries = vec![RegistryEntry {
+            path: String::from("ROOT\\ControlSet001\\Control\\Session Manager\\Memory Management\\PrefetchParameters"),
+            key: String::from("ROOT\\ControlSet001\\Control\\Session Manager\\Memory Management"),
+            name: String::from("PrefetchParameters"),
+            values: vec![
+                KeyValue {
+                    value: String::from("EnableBootTrace"),
+                    data: String::from("0"),
+                    data_type: String::from("REG_DWORD"),
+                },
+                KeyValue {
+                    value: String::from("EnablePrefetcher"),
+                    data: String::from("3"),
+                    data_type: String::from("REG_DWORD"),
+                },
+            ],
+            last_modified: 0,
+            depth: 5,
+            security_offset: 0,
+        }];
+        assert_eq!(prefetcher_value(&entries), Some(3));
+        assert_eq!(prefetcher_value(&[]), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_enable_prefetcher() {
+        use super::enable_prefetcher;
+        let _ = enable_prefetcher(&'C');
+    }
+}
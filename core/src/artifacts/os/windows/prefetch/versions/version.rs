@@ -1,5 +1,14 @@
 use super::{version23::Version23, version26::Version26, version30::Version30};
-use log::error;
+use log::{error, warn};
+
+/// Newer versions up to this value are assumed to keep the version 30 layout
+const MAX_CLOSE_VERSION: u32 = 39;
+
+/// Check if an unrecognized Prefetch version is close enough to version 30 to try parsing it
+pub(crate) fn is_close_version(version: u32) -> bool {
+    let version31 = 31;
+    version > version31 && version <= MAX_CLOSE_VERSION
+}
 
 pub(crate) struct VersionInfo {
     pub(crate) file_array_offset: u32,
@@ -12,6 +21,7 @@ pub(crate) struct VersionInfo {
     pub(crate) number_volumes: u32,
     pub(crate) volume_info_size: u32,
     pub(crate) run_times: Vec<i64>,
+    pub(crate) run_filetimes: Vec<u64>,
     pub(crate) run_count: u32,
 }
 
@@ -21,6 +31,7 @@ impl VersionInfo {
         let version23 = 23; // Win7
         let version26 = 26; // Win8
         let version30 = 30; // Win10+
+        let version31 = 31; // Win11 24H2+
 
         let mut version_info = VersionInfo {
             file_array_offset: 0,
@@ -33,15 +44,19 @@ impl VersionInfo {
             number_volumes: 0,
             volume_info_size: 0,
             run_times: Vec::new(),
+            run_filetimes: Vec::new(),
             run_count: 0,
         };
 
         let (pf_data, result) = if version == version26 {
             Version26::parse_file_info_ver26(data)?
-        } else if version == version30 {
+        } else if version == version30 || version == version31 {
             Version30::parse_file_info_ver30(data)?
         } else if version == version23 {
             Version23::parse_file_info_ver23(data)?
+        } else if is_close_version(version) {
+            warn!("[prefetch] Unrecognized Prefetch version: {version}. Attempting to parse using version 30 layout");
+            Version30::parse_file_info_ver30(data)?
         } else {
             error!("[prefetch] Unsupported Prefetch version: {}", version);
             return Err(nom::Err::Incomplete(nom::Needed::Unknown));
@@ -58,6 +73,7 @@ impl VersionInfo {
         version_info.number_volumes = result.number_volumes;
         version_info.volume_info_size = result.volume_info_size;
         version_info.run_times = result.run_times;
+        version_info.run_filetimes = result.run_filetimes;
         version_info.run_count = result.run_count;
 
         Ok((pf_data, version_info))
@@ -66,7 +82,9 @@ impl VersionInfo {
 
 #[cfg(test)]
 mod tests {
-    use crate::artifacts::os::windows::prefetch::versions::version::VersionInfo;
+    use crate::artifacts::os::windows::prefetch::versions::version::{
+        is_close_version, VersionInfo,
+    };
     use std::{fs, path::PathBuf};
 
     #[test]
@@ -90,4 +108,31 @@ mod tests {
 
         assert_eq!(result.run_times, vec![1665886665]);
     }
+
+    #[test]
+    fn test_get_version_info_version31() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/versions/version30.raw");
+
+        let buffer = fs::read(test_location).unwrap();
+
+        let (_, result) = VersionInfo::get_version_info(&buffer, 31).unwrap();
+        assert_eq!(result.file_array_offset, 296);
+        assert_eq!(result.number_volumes, 1);
+        assert_eq!(result.run_times, vec![1665886665]);
+
+        let (_, result) = VersionInfo::get_version_info(&buffer, 33).unwrap();
+        assert_eq!(result.number_files, 64);
+
+        let result = VersionInfo::get_version_info(&buffer, 50);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_is_close_version() {
+        assert!(!is_close_version(30));
+        assert!(!is_close_version(31));
+        assert!(is_close_version(32));
+        assert!(!is_close_version(40));
+    }
 }
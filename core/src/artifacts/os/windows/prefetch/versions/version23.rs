@@ -24,8 +24,10 @@ impl Version23 {
         let (input, unknown) = nom_unsigned_eight_bytes(input, Endian::Le)?;
 
         let mut run_times: Vec<i64> = Vec::new();
+        let mut run_filetimes: Vec<u64> = Vec::new();
         let (input, runtime) = nom_unsigned_eight_bytes(input, Endian::Le)?;
         run_times.push(filetime_to_unixepoch(&runtime));
+        run_filetimes.push(runtime);
 
         let (input, unknown2_data) = take(size_of::<u128>())(input)?;
 
@@ -46,6 +48,7 @@ impl Version23 {
             volume_info_size,
             _unknown: unknown,
             run_times,
+            run_filetimes,
             _unknown2: unknown2_data.to_vec(),
             run_count,
             _unknown3: unknown3,
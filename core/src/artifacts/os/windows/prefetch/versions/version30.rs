@@ -20,6 +20,7 @@ pub(crate) struct Version30 {
     pub(crate) volume_info_size: u32,
     pub(crate) _unknown: u64,
     pub(crate) run_times: Vec<i64>,
+    pub(crate) run_filetimes: Vec<u64>,
     pub(crate) _unknown2: Vec<u8>, // may be 16 or 8 bytes. Depending on size of prefetch data (224 vs 216)
     pub(crate) run_count: u32,
     pub(crate) _unknown3: u32,
@@ -43,6 +44,7 @@ impl Version30 {
         let (mut input, unknown) = nom_unsigned_eight_bytes(input, Endian::Le)?;
 
         let mut run_times: Vec<i64> = Vec::new();
+        let mut run_filetimes: Vec<u64> = Vec::new();
 
         let max_runtime_count = 8;
         let mut count = 0;
@@ -52,6 +54,7 @@ impl Version30 {
             let no_runs = 0;
             if runtime != no_runs {
                 run_times.push(filetime_to_unixepoch(&runtime));
+                run_filetimes.push(runtime);
             }
             count += 1;
             input = runs_data;
@@ -93,6 +96,7 @@ impl Version30 {
             volume_info_size,
             _unknown: unknown,
             run_times,
+            run_filetimes,
             _unknown2: unknown2,
             run_count,
             _unknown3: unknown3,
@@ -1,13 +1,17 @@
+use super::versions::version::is_close_version;
 use crate::utils::{
     nom_helper::{
         nom_unsigned_eight_bytes, nom_unsigned_four_bytes, nom_unsigned_two_bytes, Endian,
     },
-    strings::extract_utf16_string,
+    strings::{extract_utf16_string, extract_utf16_string_limit, MAX_UTF16_CHARS},
     time::filetime_to_unixepoch,
 };
-use log::error;
-use nom::bytes::complete::take;
-use std::mem::size_of;
+use log::{error, warn};
+use nom::{bytes::complete::take, error::ErrorKind};
+use std::{collections::HashSet, mem::size_of};
+
+/// Max directory entries parsed for a single volume. Real prefetch files reference far fewer directories
+const MAX_DIRECTORY_STRINGS: usize = 16384;
 
 // There are three (3) Volume versions, however all of them have the same first 36 bytes
 // Rest of bytes are unknown
@@ -17,28 +21,73 @@ pub(crate) struct Volume {
     _volume_number_chars: u32,
     pub(crate) volume_path: String,
     pub(crate) volume_creation: i64,
+    pub(crate) volume_creation_filetime: u64,
     pub(crate) volume_serial: u32,
     _file_ref_offset: u32,
     _file_ref_data_size: u32,
     _directory_strings_offset: u32,
     pub(crate) number_directory_strings: u32,
     pub(crate) directories: Vec<String>,
+    /// Malformed or manipulated directory entries found while parsing
+    pub(crate) anomalies: Vec<String>,
+    /// Raw bytes of the volume information entry
+    pub(crate) raw: Vec<u8>,
 }
 
 impl Volume {
-    /// Parse all Volume information entries
+    /// Parse all Volume information entries. Raw entry bytes are only kept if `include_raw` is set
     pub(crate) fn parse_volume<'a>(
         data: &'a [u8],
         volume_offset: u32,
         number_volumes: &'a u32,
         version: u32,
+        include_raw: bool,
     ) -> nom::IResult<&'a [u8], Vec<Volume>> {
         let mut volume_vec: Vec<Volume> = Vec::new();
         let mut count = 0;
         let (mut volume_data, _) = take(volume_offset)(data)?;
         let volume_start = volume_data;
 
-        while &count < number_volumes {
+        // Every volume entry is at least 36 bytes
+        let min_volume_size = 36;
+
+        // Real prefetch files only reference a handful of volumes
+        let max_volumes = 32;
+        let implausible = *number_volumes > max_volumes;
+        let volume_limit = if implausible {
+            warn!("[prefetch] Number of volumes {number_volumes} is implausibly large. Only parsing up to {max_volumes} volumes");
+            max_volumes
+        } else {
+            *number_volumes
+        };
+
+        let version31 = 31;
+        let newer_entry_size = if version == version31 || is_close_version(version) {
+            Volume::newer_entry_size(volume_start, volume_limit, min_volume_size)
+        } else {
+            0
+        };
+
+        if !implausible
+            && !Volume::in_bounds(
+                volume_start,
+                0,
+                (*number_volumes as usize).saturating_mul(min_volume_size),
+            )
+        {
+            error!("[prefetch] Number of volumes {number_volumes} exceeds volume data size");
+            return Err(nom::Err::Failure(nom::error::Error::new(
+                volume_start,
+                ErrorKind::TooLarge,
+            )));
+        }
+
+        while count < volume_limit {
+            // A corrupt count may point past the data. Return the volumes parsed so far
+            if !Volume::in_bounds(volume_data, 0, min_volume_size) {
+                warn!("[prefetch] Volume data ended after {count} volumes");
+                break;
+            }
             let (input, volume_path_offset) = nom_unsigned_four_bytes(volume_data, Endian::Le)?;
             let (input, volume_number_chars) = nom_unsigned_four_bytes(input, Endian::Le)?;
             let (input, volume_creation) = nom_unsigned_eight_bytes(input, Endian::Le)?;
@@ -49,30 +98,41 @@ impl Volume {
             let (input, directory_strings_offset) = nom_unsigned_four_bytes(input, Endian::Le)?;
             let (input, number_directory_strings) = nom_unsigned_four_bytes(input, Endian::Le)?;
 
-            let (volume_path_start, _) = take(volume_path_offset)(volume_start)?;
             let utf16_adjust = 2;
-            let (_, volume_path_data) =
-                take(volume_number_chars * utf16_adjust)(volume_path_start)?;
+            let volume_path_size = (volume_number_chars as usize).saturating_mul(utf16_adjust);
+            if !Volume::in_bounds(volume_start, volume_path_offset as usize, volume_path_size) {
+                error!("[prefetch] Volume path offset {volume_path_offset} and size {volume_path_size} exceed volume data size");
+                return Err(nom::Err::Failure(nom::error::Error::new(
+                    volume_start,
+                    ErrorKind::Eof,
+                )));
+            }
 
-            let (_, directories) = Volume::get_directories(
+            let (volume_path_start, _) = take(volume_path_offset)(volume_start)?;
+            let (_, volume_path_data) = take(volume_path_size)(volume_path_start)?;
+
+            let (_, (directories, anomalies)) = Volume::get_directories(
                 volume_start,
                 directory_strings_offset,
                 number_directory_strings,
             )?;
 
-            let volume = Volume {
+            let mut volume = Volume {
                 _volume_path_offset: volume_path_offset,
                 _volume_number_chars: volume_number_chars,
                 volume_path: extract_utf16_string(volume_path_data),
                 volume_creation: filetime_to_unixepoch(&volume_creation),
+                volume_creation_filetime: volume_creation,
                 volume_serial,
                 _file_ref_offset: file_ref_offset,
                 _file_ref_data_size: file_ref_data_size,
                 _directory_strings_offset: directory_strings_offset,
                 number_directory_strings,
                 directories,
+                anomalies,
+                raw: Vec::new(),
             };
-            volume_vec.push(volume);
+            let entry_start = volume_data;
             count += 1;
 
             let version30 = 30;
@@ -86,40 +146,151 @@ impl Volume {
                 let unknown_size: usize = 68;
                 let (input, _) = take(unknown_size)(input)?;
                 input
+            } else if newer_entry_size != 0 {
+                let unknown_size = newer_entry_size - min_volume_size;
+                if !Volume::in_bounds(input, 0, unknown_size) {
+                    warn!(
+                        "[prefetch] Volume entry size {newer_entry_size} exceeds volume data size"
+                    );
+                    volume_vec.push(volume);
+                    break;
+                }
+                let (input, _) = take(unknown_size)(input)?;
+                input
             } else {
                 error!("[prefetch] Unsupported prefetch volume info version: {version}");
+                volume_vec.push(volume);
                 break;
             };
+            if include_raw {
+                volume.raw = entry_start[..entry_start.len() - volume_data.len()].to_vec();
+            }
+            volume_vec.push(volume);
         }
 
         Ok((volume_data, volume_vec))
     }
 
-    /// Get all the accessed directories
-    fn get_directories(data: &[u8], offset: u32, entries: u32) -> nom::IResult<&[u8], Vec<String>> {
+    /**
+     * Get all the accessed directories and any anomalies in the directory entries
+     * Duplicate and zero length entries are kept but recorded as anomalies
+     * An entry that runs past the data stops parsing and returns the directories parsed so far
+     * The directory count comes from the file, so it is limited by the available data and `MAX_DIRECTORY_STRINGS`
+     */
+    fn get_directories(
+        data: &[u8],
+        offset: u32,
+        entries: u32,
+    ) -> nom::IResult<&[u8], (Vec<String>, Vec<String>)> {
+        if !Volume::in_bounds(data, offset as usize, 0) {
+            error!("[prefetch] Directory strings offset {offset} exceeds volume data size");
+            return Err(nom::Err::Failure(nom::error::Error::new(
+                data,
+                ErrorKind::TooLarge,
+            )));
+        }
         let (mut directory_start, _) = take(offset)(data)?;
 
+        let mut anomalies: Vec<String> = Vec::new();
+        // Every directory entry is at least 4 bytes (size and end of string character)
+        let min_entry_size = 4;
+        let available = (directory_start.len() / min_entry_size).min(MAX_DIRECTORY_STRINGS);
+        let entry_limit = if entries as usize > available {
+            warn!("[prefetch] Directory count {entries} is implausibly large. Only parsing up to {available} entries");
+            anomalies.push(format!(
+                "Directory count {entries} exceeds the directory data. Only parsed up to {available} entries"
+            ));
+            available as u32
+        } else {
+            entries
+        };
+
         let mut count = 0;
         let mut directories: Vec<String> = Vec::new();
+        let mut seen: HashSet<String> = HashSet::new();
         let utf16_adjust = 2;
 
-        while count < entries {
+        while count < entry_limit {
+            if !Volume::in_bounds(directory_start, 0, size_of::<u16>()) {
+                warn!("[prefetch] Directory data ended after {count} entries");
+                anomalies.push(format!(
+                    "Directory data ended after {count} of {entries} entries"
+                ));
+                break;
+            }
             let (path_data, size) = nom_unsigned_two_bytes(directory_start, Endian::Le)?;
-            let (remaining_data, path) = take(size * utf16_adjust)(path_data)?;
+            let path_size = size as usize * utf16_adjust;
+            // Declared length plus the end of string character must fit in the remaining data
+            if !Volume::in_bounds(path_data, 0, path_size + size_of::<u16>()) {
+                warn!(
+                    "[prefetch] Directory entry {count} length {size} exceeds directory data size"
+                );
+                anomalies.push(format!(
+                    "Directory entry {count} length {size} runs past the directory data"
+                ));
+                break;
+            }
+            let (remaining_data, path) = take(path_size)(path_data)?;
 
             // Nom end of string character (UTF16)
             let (remaining_data, _) = take(size_of::<u16>())(remaining_data)?;
             directory_start = remaining_data;
-            directories.push(extract_utf16_string(path));
+
+            let (directory, truncated) = extract_utf16_string_limit(path, MAX_UTF16_CHARS);
+            if truncated {
+                anomalies.push(format!(
+                    "Directory entry {count} name was truncated to {MAX_UTF16_CHARS} characters"
+                ));
+            }
+            if size == 0 {
+                anomalies.push(format!("Directory entry {count} has a zero length name"));
+            } else if !seen.insert(directory.clone()) {
+                anomalies.push(format!("Duplicate directory entry: {directory}"));
+            }
+            directories.push(directory);
             count += 1;
         }
-        Ok((directory_start, directories))
+        Ok((directory_start, (directories, anomalies)))
+    }
+
+    /**
+     * Determine the volume entry size for newer Prefetch versions
+     * The first volume path is stored right after the volume entries, so its offset divided by the number of volumes is the entry size.
+     * Falls back to the version 30 entry size (96 bytes) if the offset does not look like a valid entry size
+     */
+    fn newer_entry_size(data: &[u8], number_volumes: u32, min_volume_size: usize) -> usize {
+        let version30_size = 96;
+        if number_volumes == 0 {
+            return version30_size;
+        }
+
+        let offset_result = nom_unsigned_four_bytes(data, Endian::Le);
+        let path_offset = match offset_result {
+            Ok((_, result)) => result as usize,
+            Err(_err) => return version30_size,
+        };
+
+        let volumes = number_volumes as usize;
+        if path_offset % volumes != 0 || path_offset / volumes < min_volume_size {
+            warn!("[prefetch] Could not determine volume entry size from volume path offset {path_offset}. Using version 30 entry size");
+            return version30_size;
+        }
+
+        path_offset / volumes
+    }
+
+    /// Verify an offset and size read from the file are within the provided data
+    fn in_bounds(data: &[u8], offset: usize, size: usize) -> bool {
+        match offset.checked_add(size) {
+            Some(end) => end <= data.len(),
+            None => false,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Volume;
+    use super::{Volume, MAX_DIRECTORY_STRINGS};
 
     #[test]
     fn test_parse_volume() {
@@ -229,7 +400,8 @@ mod tests {
 
         let volume_offset = 0;
         let volumes = 1;
-        let (_, results) = Volume::parse_volume(&test_data, volume_offset, &volumes, 30).unwrap();
+        let (_, results) =
+            Volume::parse_volume(&test_data, volume_offset, &volumes, 30, true).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(
             results[0].volume_path,
@@ -243,6 +415,13 @@ mod tests {
         assert_eq!(results[0]._file_ref_data_size, 696);
         assert_eq!(results[0]._directory_strings_offset, 864);
         assert_eq!(results[0].number_directory_strings, 15);
+        assert!(results[0].anomalies.is_empty());
+        // Version 30 volume entries are 96 bytes
+        assert_eq!(results[0].raw, test_data[..96]);
+
+        let (_, results) =
+            Volume::parse_volume(&test_data, volume_offset, &volumes, 30, false).unwrap();
+        assert!(results[0].raw.is_empty());
 
         assert_eq!(
             results[0].directories[0],
@@ -335,8 +514,10 @@ mod tests {
 
         let volume_offset = 0;
         let volumes = 15;
-        let (_, results) = Volume::get_directories(&test_data, volume_offset, volumes).unwrap();
+        let (_, (results, anomalies)) =
+            Volume::get_directories(&test_data, volume_offset, volumes).unwrap();
         assert_eq!(results.len(), 15);
+        assert!(anomalies.is_empty());
 
         assert_eq!(
             results[2],
@@ -352,4 +533,164 @@ mod tests {
             "\\VOLUME{01d6828290579d13-4290933e}\\WINDOWS\\SYSTEM32"
         );
     }
+
+    #[test]
+    fn test_parse_volume_bad_offsets() {
+        let mut test_data = vec![0; 36];
+        // Volume path offset
+        test_data[0..4].copy_from_slice(&u32::MAX.to_le_bytes());
+        // Volume path number of characters
+        test_data[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        let result = Volume::parse_volume(&test_data, 0, &1, 30, false);
+        assert!(result.is_err());
+
+        // Directory strings offset and count
+        let mut test_data = vec![0; 36];
+        test_data[28..32].copy_from_slice(&10u32.to_le_bytes());
+        test_data[32..36].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        let result = Volume::parse_volume(&test_data, 0, &1, 30, false);
+        assert!(result.is_err());
+
+        let result = Volume::parse_volume(&test_data, 0, &u32::MAX, 30, false);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_volume_fuzz() {
+        // Simple linear congruential generator so the test is repeatable
+        let mut seed: u32 = 0x1337;
+        let mut next = || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            seed
+        };
+
+        for size in 0..256 {
+            let mut test_data = Vec::with_capacity(size);
+            for _ in 0..size {
+                test_data.push(next() as u8);
+            }
+            // Mix in some large offsets and counts
+            if size >= 36 {
+                let field = (next() as usize % 9) * 4;
+                test_data[field..field + 4].copy_from_slice(&next().to_le_bytes());
+            }
+
+            let volumes = next() % 4;
+            let _ = Volume::parse_volume(&test_data, next() % 64, &volumes, 30, false);
+            let _ = Volume::get_directories(&test_data, next() % 64, next());
+        }
+    }
+
+    #[test]
+    fn test_parse_volume_absurd_count() {
+        // Three empty version 30 volume entries (36 bytes plus 60 unknown bytes)
+        let test_data = vec![0; 96 * 3];
+
+        let start = std::time::Instant::now();
+        let (_, results) = Volume::parse_volume(&test_data, 0, &u32::MAX, 30, false).unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(start.elapsed().as_secs() < 1);
+
+        // Count is capped even if there is enough data
+        let test_data = vec![0; 96 * 40];
+        let (_, results) = Volume::parse_volume(&test_data, 0, &0xFFFF, 30, false).unwrap();
+        assert_eq!(results.len(), 32);
+    }
+
+    #[test]
+    fn test_get_directories_anomalies() {
+        let mut test_data = Vec::new();
+        // Two copies of "\\A", a zero length entry, then an entry that claims 100 characters
+        for _ in 0..2 {
+            test_data.extend_from_slice(&[2, 0, 92, 0, 65, 0, 0, 0]);
+        }
+        test_data.extend_from_slice(&[0, 0, 0, 0]);
+        test_data.extend_from_slice(&[100, 0, 66, 0, 0, 0]);
+
+        let (_, (results, anomalies)) = Volume::get_directories(&test_data, 0, 4).unwrap();
+        assert_eq!(results, vec!["\\A", "\\A", ""]);
+        assert_eq!(
+            anomalies,
+            vec![
+                "Duplicate directory entry: \\A",
+                "Directory entry 2 has a zero length name",
+                "Directory entry 3 length 100 runs past the directory data"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_directories_inflated_count() {
+        let test_data = [2, 0, 92, 0, 65, 0, 0, 0];
+
+        let (_, (results, anomalies)) = Volume::get_directories(&test_data, 0, u32::MAX).unwrap();
+        assert_eq!(results, vec!["\\A"]);
+        assert_eq!(
+            anomalies,
+            vec![
+                "Directory count 4294967295 exceeds the directory data. Only parsed up to 2 entries",
+                "Directory data ended after 1 of 4294967295 entries"
+            ]
+        );
+
+        // Large buffer of zero length entries is capped at MAX_DIRECTORY_STRINGS
+        let test_data = vec![0; (MAX_DIRECTORY_STRINGS + 10) * 4];
+        let (_, (results, anomalies)) = Volume::get_directories(&test_data, 0, u32::MAX).unwrap();
+        assert_eq!(results.len(), MAX_DIRECTORY_STRINGS);
+        assert_eq!(
+            anomalies[0],
+            "Directory count 4294967295 exceeds the directory data. Only parsed up to 16384 entries"
+        );
+
+        assert!(Volume::get_directories(&test_data, u32::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn test_in_bounds() {
+        let test_data = [0; 10];
+        assert!(Volume::in_bounds(&test_data, 2, 8));
+        assert!(!Volume::in_bounds(&test_data, 2, 9));
+        assert!(!Volume::in_bounds(&test_data, usize::MAX, 1));
+    }
+
+    #[test]
+    fn test_parse_volume_version31() {
+        // Two volume entries using a larger 112 byte entry size. Volume paths start after the entries
+        let entry_size = 112;
+        let mut test_data = Vec::new();
+        for (path_offset, serial) in [(224u32, 1u32), (228, 2)] {
+            let mut entry = Vec::new();
+            entry.extend_from_slice(&path_offset.to_le_bytes());
+            entry.extend_from_slice(&1u32.to_le_bytes());
+            entry.extend_from_slice(&0u64.to_le_bytes());
+            entry.extend_from_slice(&serial.to_le_bytes());
+            entry.extend_from_slice(&[0; 8]);
+            entry.extend_from_slice(&232u32.to_le_bytes());
+            entry.extend_from_slice(&0u32.to_le_bytes());
+            entry.resize(entry_size, 0);
+            test_data.append(&mut entry);
+        }
+        test_data.extend_from_slice(&[67, 0, 0, 0, 68, 0, 0, 0]);
+
+        let (_, results) = Volume::parse_volume(&test_data, 0, &2, 31, true).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].volume_path, "C");
+        assert_eq!(results[0].volume_serial, 1);
+        assert_eq!(results[1].volume_path, "D");
+        assert_eq!(results[1].volume_serial, 2);
+        assert_eq!(results[1].raw.len(), entry_size);
+    }
+
+    #[test]
+    fn test_newer_entry_size() {
+        let test_data = [224, 0, 0, 0];
+        assert_eq!(Volume::newer_entry_size(&test_data, 2, 36), 112);
+        assert_eq!(Volume::newer_entry_size(&test_data, 0, 36), 96);
+
+        let test_data = [20, 0, 0, 0];
+        assert_eq!(Volume::newer_entry_size(&test_data, 1, 36), 96);
+        assert_eq!(Volume::newer_entry_size(&[], 1, 36), 96);
+    }
 }
@@ -109,9 +109,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -24,8 +24,37 @@ pub(crate) fn grab_sid(data: &[u8]) -> nom::IResult<&[u8], String> {
     Ok((input, windows_sid))
 }
 
+/// Lookup the account name for common well-known Windows SIDs
+pub(crate) fn well_known_sid(sid: &str) -> Option<&'static str> {
+    let name = match sid {
+        "S-1-0-0" => "NULL SID",
+        "S-1-1-0" => "Everyone",
+        "S-1-3-0" => "CREATOR OWNER",
+        "S-1-3-1" => "CREATOR GROUP",
+        "S-1-5-2" => "NETWORK",
+        "S-1-5-4" => "INTERACTIVE",
+        "S-1-5-6" => "SERVICE",
+        "S-1-5-7" => "ANONYMOUS LOGON",
+        "S-1-5-11" => "Authenticated Users",
+        "S-1-5-18" => "SYSTEM",
+        "S-1-5-19" => "LOCAL SERVICE",
+        "S-1-5-20" => "NETWORK SERVICE",
+        "S-1-5-32-544" => "Administrators",
+        "S-1-5-32-545" => "Users",
+        "S-1-5-32-546" => "Guests",
+        "S-1-5-32-547" => "Power Users",
+        "S-1-5-32-551" => "Backup Operators",
+        "S-1-5-32-555" => "Remote Desktop Users",
+        "S-1-5-80-0" => "All Services",
+        "S-1-15-2-1" => "ALL APPLICATION PACKAGES",
+        _ => return None,
+    };
+    Some(name)
+}
+
 #[cfg(test)]
 mod tests {
+    use super::well_known_sid;
     use crate::artifacts::os::windows::securitydescriptor::sid::grab_sid;
 
     #[test]
@@ -34,4 +63,11 @@ mod tests {
         let (_, results) = grab_sid(&test).unwrap();
         assert_eq!(results, "S-1-5-7");
     }
+
+    #[test]
+    fn test_well_known_sid() {
+        assert_eq!(well_known_sid("S-1-5-18").unwrap(), "SYSTEM");
+        assert_eq!(well_known_sid("S-1-5-32-544").unwrap(), "Administrators");
+        assert_eq!(well_known_sid("S-1-5-21-1-2-3-1001"), None);
+    }
 }
@@ -23,6 +23,9 @@ pub(crate) fn grab_services(options: &ServicesOptions) -> Result<Vec<ServicesDat
     if let Some(file) = &options.alt_file {
         return grab_service_file(file);
     }
+    if let Some(drive) = &options.alt_drive {
+        return alt_drive_services(drive);
+    }
     default_services()
 }
 
@@ -61,7 +64,21 @@ mod tests {
 
     #[test]
     fn test_grab_services() {
-        let options = ServicesOptions { alt_file: None };
+        let options = ServicesOptions {
+            alt_file: None,
+            alt_drive: None,
+        };
+
+        let result = grab_services(&options).unwrap();
+        assert!(result.len() > 10);
+    }
+
+    #[test]
+    fn test_grab_services_alt_drive() {
+        let options = ServicesOptions {
+            alt_file: None,
+            alt_drive: Some(get_systemdrive().unwrap()),
+        };
 
         let result = grab_services(&options).unwrap();
         assert!(result.len() > 10);
@@ -67,6 +67,7 @@ fn collect_service(service_data: &Vec<RegistryEntry>, service_name: &str) -> Ser
         required_privileges: Vec::new(),
         error_control: ServiceError::Unknown,
         reg_path: String::new(),
+        user_writable_path: false,
     };
 
     for info in service_data {
@@ -91,9 +92,40 @@ fn collect_service(service_data: &Vec<RegistryEntry>, service_name: &str) -> Ser
         }
     }
 
+    service.user_writable_path =
+        is_user_writable(&service.path) || is_user_writable(&service.service_dll);
     service
 }
 
+/// Check if a path is in a directory that standard users can write to, such as user profiles or temp directories
+fn is_user_writable(path: &str) -> bool {
+    if path.is_empty() {
+        return false;
+    }
+    let locations = [
+        "\\users\\",
+        "\\temp\\",
+        "\\tmp\\",
+        "\\programdata\\",
+        "\\$recycle.bin\\",
+        "\\perflogs\\",
+        "\\windows\\tasks\\",
+        "\\windows\\tracing\\",
+        "%temp%",
+        "%tmp%",
+        "%appdata%",
+        "%localappdata%",
+        "%userprofile%",
+        "%public%",
+        "%programdata%",
+    ];
+
+    let lower_path = path.to_lowercase().replace('/', "\\");
+    locations
+        .iter()
+        .any(|location| lower_path.contains(location))
+}
+
 /// Get metadata associated with Service
 fn metadata(value: &KeyValue, service: &mut ServicesData) {
     match value.value.as_str() {
@@ -124,7 +156,7 @@ mod tests {
     use crate::{
         artifacts::os::windows::services::{
             registry::get_services_data,
-            service::{collect_service, metadata, parse_services, ServicesData},
+            service::{collect_service, is_user_writable, metadata, parse_services, ServicesData},
         },
         utils::environment::get_systemdrive,
     };
@@ -197,6 +229,7 @@ mod tests {
             required_privileges: Vec::new(),
             error_control: ServiceError::Unknown,
             reg_path: String::new(),
+            user_writable_path: false,
         };
 
         metadata(&test, &mut service);
@@ -205,4 +238,21 @@ mod tests {
             "\\SystemRoot\\System32\\drivers\\1394ohci.sys"
         );
     }
+
+    #[test]
+    fn test_is_user_writable() {
+        assert!(is_user_writable(
+            "C:\\Users\\bob\\AppData\\Local\\Temp\\evil.exe"
+        ));
+        assert!(is_user_writable("\"%TEMP%\\svc.exe\" -k"));
+        assert!(is_user_writable("C:\\ProgramData\\update.dll"));
+        assert!(is_user_writable("c:/windows/temp/svc.exe"));
+        assert!(!is_user_writable(
+            "%SystemRoot%\\system32\\svchost.exe -k netsvcs -p"
+        ));
+        assert!(!is_user_writable(
+            "\\SystemRoot\\System32\\drivers\\1394ohci.sys"
+        ));
+        assert!(!is_user_writable(""));
+    }
 }
@@ -14,11 +14,12 @@ use crate::{
     filesystem::{files::get_filename, ntfs::raw_files::get_user_registry_files},
     structs::artifacts::os::windows::ShellbagsOptions,
     utils::{
+        encoding::base64_decode_standard,
         environment::{get_clsids, get_systemdrive},
         regex_options::create_regex,
     },
 };
-use common::windows::{RegistryEntry, ShellItem, ShellType};
+use common::windows::{KeyValue, RegistryEntry, ShellItem, ShellType};
 use log::error;
 use serde::Serialize;
 use serde_json::Value;
@@ -38,6 +39,8 @@ pub(crate) struct Shellbag {
     pub(crate) reg_file: String,
     pub(crate) reg_path: String,
     pub(crate) reg_file_path: String,
+    /**Position in the parent `MRUListEx`. 0 is the most recently used */
+    pub(crate) mru_position: Option<u32>,
     pub(crate) stores: Vec<HashMap<String, Value>>,
 }
 
@@ -46,6 +49,9 @@ pub(crate) fn grab_shellbags(options: &ShellbagsOptions) -> Result<Vec<Shellbag>
     if let Some(path) = &options.alt_file {
         return alt_shellbags(path, options.resolve_guids);
     }
+    if let Some(drive) = &options.alt_drive {
+        return parse_shellbags(drive, options.resolve_guids);
+    }
 
     let drive_result = get_systemdrive();
     let drive = match drive_result {
@@ -104,6 +110,7 @@ struct RegInfo {
     reg_file: String,
     reg_file_path: String,
     last_modified: i64,
+    mru_position: Option<u32>,
 }
 
 /**
@@ -193,6 +200,7 @@ fn extract_shellbags(
     shell_map: &mut HashMap<String, Shellbag>,
 ) {
     for entry in shellbags {
+        let mru_order = mru_positions(&entry.values);
         for value in &entry.values {
             // Shellbag Registry value names should always be a number
             // Skip non-number values
@@ -240,6 +248,7 @@ fn extract_shellbags(
                 reg_file: reg_filename.to_string(),
                 reg_file_path: reg_path.to_string(),
                 last_modified: entry.last_modified,
+                mru_position: mru_order.get(&value.value).copied(),
             };
 
             update_shellbags(&data, shell_map, clsids, &reg_info);
@@ -247,6 +256,35 @@ fn extract_shellbags(
     }
 }
 
+/**
+ * Get the position of each `BagMRU` value name in the `MRUListEx` value
+ * `MRUListEx` is a list of 4 byte value names ordered from most to least recently used. The list ends with 0xffffffff
+ */
+fn mru_positions(values: &[KeyValue]) -> HashMap<String, u32> {
+    let mut positions = HashMap::new();
+    let mru = match values.iter().find(|value| value.value == "MRUListEx") {
+        Some(result) => result,
+        None => return positions,
+    };
+    let data = match base64_decode_standard(&mru.data) {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[shellbags] Could not decode MRUListEx data: {err:?}");
+            return positions;
+        }
+    };
+
+    let end = 0xffffffff;
+    for (position, chunk) in data.chunks_exact(4).enumerate() {
+        let name = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        if name == end {
+            break;
+        }
+        positions.insert(name.to_string(), position as u32);
+    }
+    positions
+}
+
 /**
 * The goal of parsing `Shellbags` is to reconstruct the directories that a user has browsed to.
 * Each `ShellItem` is a single directory. Get the parent directory (if any) from our hashmap and append our current `ShellItem` to it
@@ -292,6 +330,7 @@ fn update_shellbags(
             reg_file: reg_info.reg_file.clone(),
             reg_file_path: reg_info.reg_file_path.clone(),
             reg_path: reg_info.reg_path.clone(),
+            mru_position: reg_info.mru_position,
             stores: shell.stores.clone(),
         };
 
@@ -330,6 +369,7 @@ fn update_shellbags(
         reg_file: reg_info.reg_file.clone(),
         reg_file_path: reg_info.reg_file_path.clone(),
         reg_path: reg_info.reg_path.clone(),
+        mru_position: reg_info.mru_position,
         stores: shell.stores.clone(),
     };
 
@@ -350,15 +390,15 @@ mod tests {
         artifacts::os::windows::{
             registry::helper::get_registry_keys,
             shellbags::parser::{
-                alt_shellbags, extract_shellbags, grab_shellbags, parse_shellbags, save_shellbags,
-                update_shellbags, RegInfo, Shellbag,
+                alt_shellbags, extract_shellbags, grab_shellbags, mru_positions, parse_shellbags,
+                save_shellbags, update_shellbags, RegInfo, Shellbag,
             },
         },
         filesystem::files::get_filename,
         structs::artifacts::os::windows::ShellbagsOptions,
         utils::regex_options::create_regex,
     };
-    use common::windows::{ShellItem, ShellType};
+    use common::windows::{KeyValue, ShellItem, ShellType};
     use std::{collections::HashMap, path::PathBuf};
 
     #[test]
@@ -366,6 +406,7 @@ mod tests {
         let options = ShellbagsOptions {
             resolve_guids: true,
             alt_file: None,
+            alt_drive: None,
         };
 
         let _results = grab_shellbags(&options).unwrap();
@@ -426,9 +467,14 @@ mod tests {
             reg_file: String::from("shellbags are complex"),
             reg_file_path: String::from("shellbags are complex"),
             last_modified: 0,
+            mru_position: Some(2),
         };
         update_shellbags(&item, &mut shell_map, &empty_clsids, &reg_info);
         assert_eq!(shell_map.len(), 1);
+        assert_eq!(
+            shell_map.get("shellbags are complex").unwrap().mru_position,
+            Some(2)
+        );
     }
 
     #[test]
@@ -446,6 +492,7 @@ mod tests {
             reg_file: String::from("shellbags are complex"),
             reg_path: String::from("shellbags are complex"),
             reg_file_path: String::from("shellbags are complex"),
+            mru_position: None,
             stores: Vec::new(),
         };
         let mut shell_map = HashMap::new();
@@ -455,4 +502,39 @@ mod tests {
         save_shellbags(&mut shellbag_vec, &shell_map);
         assert_eq!(shellbag_vec.len(), 1);
     }
+
+    #[test]
+    fn test_mru_positions() {
+        let values = vec![
+            KeyValue {
+                value: String::from("MRUListEx"),
+                // Value names 2, 0, 1 followed by the end marker
+                data: String::from("AgAAAAAAAAABAAAA/////w=="),
+                data_type: String::from("REG_BINARY"),
+            },
+            KeyValue {
+                value: String::from("NodeSlot"),
+                data: String::from("1"),
+                data_type: String::from("REG_DWORD"),
+            },
+        ];
+        let result = mru_positions(&values);
+        assert_eq!(result.len(), 3);
+        assert_eq!(result.get("2"), Some(&0));
+        assert_eq!(result.get("0"), Some(&1));
+        assert_eq!(result.get("1"), Some(&2));
+
+        assert!(mru_positions(&values[1..]).is_empty());
+    }
+
+    #[test]
+    fn test_grab_shellbags_alt_drive() {
+        let options = ShellbagsOptions {
+            resolve_guids: false,
+            alt_file: None,
+            alt_drive: Some('C'),
+        };
+
+        let _results = grab_shellbags(&options).unwrap();
+    }
 }
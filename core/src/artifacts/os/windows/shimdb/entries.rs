@@ -0,0 +1,237 @@
+use super::{
+    tag::{get_tag, Tags},
+    tags::{
+        binary::parse_binary, dword::parse_dword, qword::parse_qword, stringref::parse_stringref,
+        word::parse_word,
+    },
+};
+use crate::utils::nom_helper::{nom_unsigned_four_bytes, Endian};
+use common::windows::ShimEntry;
+use nom::bytes::complete::take;
+use std::collections::HashMap;
+
+const TAG_NAME: u16 = 0x6001;
+const TAG_VENDOR: u16 = 0x6005;
+const TAG_APP_NAME: u16 = 0x6006;
+const TAG_COMMAND_LINE: u16 = 0x6008;
+const TAG_DLLFILE: u16 = 0x600A;
+const TAG_EXE_ID: u16 = 0x9004;
+
+const TAG_LIBRARY: u16 = 0x7002;
+const TAG_SHIM: u16 = 0x7004;
+const TAG_EXE: u16 = 0x7007;
+const TAG_MATCHING_FILE: u16 = 0x7008;
+const TAG_SHIM_REF: u16 = 0x7009;
+const TAG_PATCH_REF: u16 = 0x700A;
+const TAG_LAYER: u16 = 0x700B;
+const TAG_FLAG_REF: u16 = 0x7015;
+
+/// A `LIST` tag with its values and any sublists. Unlike `parse_list` the hierarchy is preserved
+#[derive(Debug)]
+struct SdbList {
+    tag: u16,
+    values: HashMap<u16, String>,
+    lists: Vec<SdbList>,
+}
+
+impl SdbList {
+    fn value(&self, tag: u16) -> String {
+        self.values.get(&tag).cloned().unwrap_or_default()
+    }
+}
+
+/**
+ * Get the shims, patches, flags, and layers applied to each EXE in the DATABASE list
+ * The DATABASE list contains:
+ *   LIBRARY - Definitions of the shims, patches, and flags. Includes the DLL that implements a shim
+ *   LAYER - Named groups of shims. Can be applied to an EXE or to all applications
+ *   EXE - Executable to fix. References the shims, patches, flags, and layers by name
+ */
+pub(crate) fn parse_entries<'a>(
+    db_data: &'a [u8],
+    stringtable_data: &'a [u8],
+) -> nom::IResult<&'a [u8], Vec<ShimEntry>> {
+    let database_tag = 0x7001;
+    let (input, database) = parse_tree(db_data, database_tag, stringtable_data)?;
+
+    let mut dll_files: HashMap<String, String> = HashMap::new();
+    library_dlls(&database, &mut dll_files);
+
+    let mut exes: Vec<&SdbList> = Vec::new();
+    find_lists(&database, TAG_EXE, &mut exes);
+
+    let mut entries: Vec<ShimEntry> = Vec::new();
+    for exe in exes {
+        let matching_files: Vec<String> = exe
+            .lists
+            .iter()
+            .filter(|list| list.tag == TAG_MATCHING_FILE)
+            .map(|list| list.value(TAG_NAME))
+            .collect();
+
+        for fix in &exe.lists {
+            let fix_type = match fix.tag {
+                TAG_SHIM_REF => "SHIM",
+                TAG_PATCH_REF => "PATCH",
+                TAG_FLAG_REF => "FLAG",
+                TAG_LAYER => "LAYER",
+                _ => continue,
+            };
+            let fix_name = fix.value(TAG_NAME);
+            let dll_file = if fix.tag == TAG_SHIM_REF {
+                dll_files.get(&fix_name).cloned().unwrap_or_default()
+            } else {
+                String::new()
+            };
+
+            entries.push(ShimEntry {
+                exe_name: exe.value(TAG_NAME),
+                app_name: exe.value(TAG_APP_NAME),
+                vendor: exe.value(TAG_VENDOR),
+                exe_id: exe.value(TAG_EXE_ID),
+                matching_files: matching_files.clone(),
+                fix_type: fix_type.to_string(),
+                fix_name,
+                command_line: fix.value(TAG_COMMAND_LINE),
+                dll_file,
+            });
+        }
+    }
+
+    Ok((input, entries))
+}
+
+/// Track the DLL that implements each shim defined in a LIBRARY
+fn library_dlls(list: &SdbList, dll_files: &mut HashMap<String, String>) {
+    for sublist in &list.lists {
+        if sublist.tag == TAG_LIBRARY {
+            for shim in sublist.lists.iter().filter(|entry| entry.tag == TAG_SHIM) {
+                dll_files.insert(shim.value(TAG_NAME), shim.value(TAG_DLLFILE));
+            }
+            continue;
+        }
+        library_dlls(sublist, dll_files);
+    }
+}
+
+/// Recursively find all lists with the provided tag
+fn find_lists<'a>(list: &'a SdbList, tag: u16, found: &mut Vec<&'a SdbList>) {
+    for sublist in &list.lists {
+        if sublist.tag == tag {
+            found.push(sublist);
+            continue;
+        }
+        find_lists(sublist, tag, found);
+    }
+}
+
+/// Parse the tags in a `LIST`. Sublists are parsed recursively
+fn parse_tree<'a>(
+    data: &'a [u8],
+    list_tag: u16,
+    stringtable_data: &'a [u8],
+) -> nom::IResult<&'a [u8], SdbList> {
+    let mut list = SdbList {
+        tag: list_tag,
+        values: HashMap::new(),
+        lists: Vec::new(),
+    };
+
+    let mut input = data;
+    let min_tag_size = 2;
+    while input.len() > min_tag_size {
+        let (sdb_data, (tag, tag_value)) = get_tag(input)?;
+        let (tag_data, value) = match tag {
+            Tags::List => {
+                let (sdb_data, list_size) = nom_unsigned_four_bytes(sdb_data, Endian::Le)?;
+                let (sdb_data, list_data) = take(list_size)(sdb_data)?;
+                let (_, sublist) = parse_tree(list_data, tag_value, stringtable_data)?;
+                list.lists.push(sublist);
+                input = sdb_data;
+                continue;
+            }
+            Tags::Binary => parse_binary(sdb_data, &tag_value)?,
+            Tags::Stringref => parse_stringref(sdb_data, stringtable_data)?,
+            Tags::Qword => parse_qword(sdb_data)?,
+            Tags::Dword => parse_dword(sdb_data)?,
+            Tags::Null => (sdb_data, String::from("true")),
+            Tags::Word => parse_word(sdb_data)?,
+            // Strings are only found in the stringtable. Unknown tags cannot be skipped since we do not know their size
+            Tags::String | Tags::Unkonwn => break,
+        };
+        list.values.insert(tag_value, value);
+        input = tag_data;
+    }
+
+    Ok((input, list))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_lists, parse_entries, parse_tree, TAG_EXE, TAG_NAME};
+    use crate::{
+        artifacts::os::windows::shimdb::{
+            database::get_data, stringtable::get_stringtable_data, tag::get_tag,
+        },
+        filesystem::files::read_file,
+    };
+    use std::path::PathBuf;
+
+    /// Split the custom sdb file into the DATABASE and STRINGTABLE lists
+    fn custom_sdb() -> (Vec<u8>, Vec<u8>) {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/shimdb/ArtemisCustomShim.sdb");
+        let buffer = read_file(&test_location.display().to_string()).unwrap();
+
+        // Skip the header and INDEXES list
+        let indexes_start = 12;
+        let (input, _) = get_tag(&buffer[indexes_start..]).unwrap();
+        let indexes_size = u32::from_le_bytes(input[0..4].try_into().unwrap()) as usize;
+        let (input, _) = get_tag(&input[indexes_size + 4..]).unwrap();
+
+        let (input, db_data) = get_data(input).unwrap();
+        let (input, _) = get_tag(input).unwrap();
+        let (_, table_data) = get_stringtable_data(input).unwrap();
+        (db_data, table_data)
+    }
+
+    #[test]
+    fn test_parse_entries() {
+        let (db_data, table_data) = custom_sdb();
+        let (_, result) = parse_entries(&db_data, &table_data).unwrap();
+        assert_eq!(result.len(), 4);
+
+        assert_eq!(result[0].exe_name, "notepad.exe");
+        assert_eq!(result[0].app_name, "Notepad Helper");
+        assert_eq!(result[0].vendor, "Artemis");
+        assert_eq!(result[0].exe_id, "aabbccdd-eeff-0011-2233-445566778899");
+        assert_eq!(result[0].matching_files, vec!["*", "notepad.exe"]);
+        assert_eq!(result[0].fix_type, "SHIM");
+        assert_eq!(result[0].fix_name, "InjectDll");
+        assert_eq!(result[0].command_line, "C:\\Tools\\inject.dll");
+        assert_eq!(result[0].dll_file, "AcGenral.dll");
+
+        assert_eq!(result[1].fix_type, "PATCH");
+        assert_eq!(result[1].fix_name, "HotPatchEntry");
+        assert_eq!(result[1].dll_file, "");
+
+        assert_eq!(result[2].exe_name, "calc.exe");
+        assert_eq!(result[2].fix_type, "LAYER");
+        assert_eq!(result[2].fix_name, "ArtemisLayer");
+        assert_eq!(result[3].fix_type, "FLAG");
+        assert_eq!(result[3].fix_name, "DisableThemes");
+    }
+
+    #[test]
+    fn test_parse_tree() {
+        let (db_data, table_data) = custom_sdb();
+        let (_, result) = parse_tree(&db_data, 0x7001, &table_data).unwrap();
+        assert_eq!(result.value(TAG_NAME), "ArtemisCustomShim");
+        assert_eq!(result.lists.len(), 4);
+
+        let mut exes = Vec::new();
+        find_lists(&result, TAG_EXE, &mut exes);
+        assert_eq!(exes.len(), 2);
+        assert_eq!(exes[1].value(TAG_NAME), "calc.exe");
+    }
+}
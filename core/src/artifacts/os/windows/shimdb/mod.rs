@@ -1,5 +1,6 @@
 mod database;
-mod error;
+mod entries;
+pub(crate) mod error;
 mod header;
 mod indexes;
 pub(crate) mod parser;
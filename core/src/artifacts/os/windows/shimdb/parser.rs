@@ -20,7 +20,7 @@ use common::windows::ShimData;
 use log::error;
 
 /// Parse `Shimdb` based on `ShimdbOptions`
-pub(crate) fn grab_shimdb(options: &ShimdbOptions) -> Result<Vec<ShimData>, ShimdbError> {
+pub fn grab_shimdb(options: &ShimdbOptions) -> Result<Vec<ShimData>, ShimdbError> {
     if let Some(file) = &options.alt_file {
         let result = custom_shimdb_path(file)?;
         return Ok(vec![result]);
@@ -38,7 +38,7 @@ pub(crate) fn grab_shimdb(options: &ShimdbOptions) -> Result<Vec<ShimData>, Shim
 }
 
 /// SDB files can technically exist anywhere and do not have to end in `.sdb`. Parse any custom paths provided
-pub(crate) fn custom_shimdb_path(path: &str) -> Result<ShimData, ShimdbError> {
+pub fn custom_shimdb_path(path: &str) -> Result<ShimData, ShimdbError> {
     parse_sdb_file(path)
 }
 
@@ -145,6 +145,10 @@ mod tests {
 
         test_location.push("T1546.011CompatDatabase.sdb");
         tests.push(test_location.display().to_string());
+        test_location.pop();
+
+        test_location.push("ArtemisCustomShim.sdb");
+        tests.push(test_location.display().to_string());
 
         for path in tests {
             let result = custom_shimdb_path(&path).unwrap();
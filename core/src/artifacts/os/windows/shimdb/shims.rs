@@ -1,5 +1,6 @@
 use super::{
     database::{get_data, parse_db},
+    entries::parse_entries,
     header::SdbHeader,
     tag::{generate_tags, get_tag},
 };
@@ -27,6 +28,7 @@ pub(crate) fn parse_shimdb(data: &[u8]) -> nom::IResult<&[u8], ShimData> {
             list_data: Vec::new(),
         },
         sdb_path: String::new(),
+        shim_entries: Vec::new(),
     };
 
     let tag_values = generate_tags();
@@ -84,6 +86,14 @@ pub(crate) fn parse_shimdb(data: &[u8]) -> nom::IResult<&[u8], ShimData> {
         }
     }
 
+    let entries_result = parse_entries(&database_data, &stringtable_data);
+    match entries_result {
+        Ok((_, result)) => shim_data.shim_entries = result,
+        Err(err) => {
+            error!("[shimdb] Failed to parse shim entries: {err:?}");
+        }
+    }
+
     Ok((data, shim_data))
 }
 
@@ -189,6 +199,14 @@ mod tests {
                 .unwrap(),
             "C:\\Tools\\AtomicTest.dll"
         );
+        assert_eq!(result.shim_entries.len(), 1);
+        assert_eq!(result.shim_entries[0].exe_name, "AtomicTest.exe");
+        assert_eq!(result.shim_entries[0].fix_type, "SHIM");
+        assert_eq!(result.shim_entries[0].fix_name, "InjectDll");
+        assert_eq!(
+            result.shim_entries[0].command_line,
+            "C:\\Tools\\AtomicTest.dll"
+        );
     }
 
     #[test]
@@ -221,4 +239,30 @@ mod tests {
             "10.0.18362.1 (WinBuild.160101.0800)"
         );
     }
+
+    #[test]
+    fn test_parse_installed_shimdb() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/shimdb/ArtemisCustomShim.sdb");
+
+        let buffer = read_file(&test_location.display().to_string()).unwrap();
+        let (_, result) = parse_shimdb(&buffer).unwrap();
+        assert_eq!(result.indexes.len(), 1);
+        assert_eq!(result.db_data.name, "ArtemisCustomShim");
+        assert_eq!(result.db_data.sdb_version, "3.0");
+        assert_eq!(
+            result.db_data.database_id,
+            "1f2e3d4c-5b6a-4978-8695-a4b3c2d1e0f0"
+        );
+
+        assert_eq!(result.shim_entries.len(), 4);
+        assert_eq!(result.shim_entries[0].exe_name, "notepad.exe");
+        assert_eq!(result.shim_entries[0].fix_name, "InjectDll");
+        assert_eq!(result.shim_entries[0].dll_file, "AcGenral.dll");
+        assert_eq!(result.shim_entries[0].command_line, "C:\\Tools\\inject.dll");
+        assert_eq!(result.shim_entries[1].fix_type, "PATCH");
+        assert_eq!(result.shim_entries[2].exe_name, "calc.exe");
+        assert_eq!(result.shim_entries[2].fix_type, "LAYER");
+        assert_eq!(result.shim_entries[3].fix_type, "FLAG");
+    }
 }
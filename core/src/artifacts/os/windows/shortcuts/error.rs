@@ -7,6 +7,7 @@ pub enum LnkError {
     ReadFile,
     NotLnkData,
     ReadDirectory,
+    Systemdrive,
 }
 
 impl std::error::Error for LnkError {}
@@ -19,6 +20,7 @@ impl fmt::Display for LnkError {
             LnkError::ReadFile => write!(f, "Could not read lnk file"),
             LnkError::NotLnkData => write!(f, "Not shortcut data"),
             LnkError::ReadDirectory => write!(f, "Could not read directory"),
+            LnkError::Systemdrive => write!(f, "Could not determine systemdrive"),
         }
     }
 }
@@ -1,5 +1,6 @@
+use super::find_block;
 use crate::utils::nom_helper::{nom_unsigned_four_bytes, Endian};
-use nom::bytes::complete::{take, take_until};
+use nom::bytes::complete::take;
 use std::mem::size_of;
 
 /// Determine if extra Codepage data exists in `Shortcut` data
@@ -14,10 +15,7 @@ pub(crate) fn has_codepage(data: &[u8]) -> (bool, u32) {
 /// Parse `Shortcut` Codepage info
 fn parse_codepage(data: &[u8]) -> nom::IResult<&[u8], u32> {
     let sig = [4, 0, 0, 160];
-    let (_, sig_start) = take_until(sig.as_slice())(data)?;
-
-    let adjust_start = 4;
-    let (code_data, _) = take(sig_start.len() - adjust_start)(data)?;
+    let (code_data, _) = find_block(data, &sig)?;
     let (input, _size_data) = take(size_of::<u32>())(code_data)?;
     let (input, _sig_data) = take(size_of::<u32>())(input)?;
 
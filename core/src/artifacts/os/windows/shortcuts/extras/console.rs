@@ -1,10 +1,11 @@
+use super::find_block;
 use crate::utils::{
     encoding::base64_encode_standard,
     nom_helper::{nom_unsigned_four_bytes, nom_unsigned_two_bytes, Endian},
     strings::extract_utf16_string,
 };
 use common::windows::{ColorFlags, Console, CursorSize, FontFamily, FontWeight};
-use nom::bytes::complete::{take, take_until};
+use nom::bytes::complete::take;
 use std::mem::size_of;
 
 /// Determine if extra Console Properties data exists in `Shortcut` data
@@ -19,10 +20,7 @@ pub(crate) fn has_console(data: &[u8]) -> (bool, Vec<Console>) {
 /// Parse `Shortcut` Console info
 fn parse_console(data: &[u8]) -> nom::IResult<&[u8], Vec<Console>> {
     let sig = [2, 0, 0, 160];
-    let (_, sig_start) = take_until(sig.as_slice())(data)?;
-
-    let adjust_start = 4;
-    let (console_data, _) = take(sig_start.len() - adjust_start)(data)?;
+    let (console_data, _) = find_block(data, &sig)?;
     let (input, _size_data) = take(size_of::<u32>())(console_data)?;
     let (input, _sig_data) = take(size_of::<u32>())(input)?;
 
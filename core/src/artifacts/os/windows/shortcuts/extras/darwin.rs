@@ -1,5 +1,6 @@
+use super::find_block;
 use crate::utils::strings::{extract_utf16_string, extract_utf8_string};
-use nom::bytes::complete::{take, take_until};
+use nom::bytes::complete::take;
 use std::mem::size_of;
 
 /// Determine if extra Darwin folder data exists in `Shortcut` data
@@ -14,10 +15,7 @@ pub(crate) fn has_darwin(data: &[u8]) -> (bool, String) {
 /// Parse `Shortcut` Darwin info
 fn parse_darwin(data: &[u8]) -> nom::IResult<&[u8], String> {
     let sig = [6, 0, 0, 160];
-    let (_, sig_start) = take_until(sig.as_slice())(data)?;
-
-    let adjust_start = 4;
-    let (darwin_data, _) = take(sig_start.len() - adjust_start)(data)?;
+    let (darwin_data, _) = find_block(data, &sig)?;
     let (input, _size_data) = take(size_of::<u32>())(darwin_data)?;
     let (input, _sig_data) = take(size_of::<u32>())(input)?;
 
@@ -1,5 +1,6 @@
+use super::find_block;
 use crate::utils::strings::extract_utf16_string;
-use nom::bytes::complete::{take, take_until, take_while};
+use nom::bytes::complete::{take, take_while};
 use std::mem::size_of;
 
 /// Determine if extra Environment data exists in `Shortcut` data
@@ -14,10 +15,7 @@ pub(crate) fn has_environment(data: &[u8]) -> (bool, String) {
 /// Scan for Environment data and parse if exists
 fn parse_environment(data: &[u8]) -> nom::IResult<&[u8], String> {
     let sig = [1, 0, 0, 160];
-    let (_, sig_start) = take_until(sig.as_slice())(data)?;
-
-    let adjust_start = 4;
-    let (env_data, _) = take(sig_start.len() - adjust_start)(data)?;
+    let (env_data, _) = find_block(data, &sig)?;
     let (input, _size_data) = take(size_of::<u32>())(env_data)?;
     let (input, _sig_data) = take(size_of::<u32>())(input)?;
 
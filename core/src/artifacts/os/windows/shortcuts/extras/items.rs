@@ -1,6 +1,7 @@
+use super::find_block;
 use crate::artifacts::os::windows::shortcuts::shellitems::parse_lnk_shellitems;
 use common::windows::ShellItem;
-use nom::bytes::complete::{take, take_until};
+use nom::bytes::complete::take;
 use std::mem::size_of;
 
 /// Determine if extra `ShellItem` data exists in `Shortcut` data
@@ -15,10 +16,7 @@ pub(crate) fn has_item(data: &[u8]) -> (bool, Vec<ShellItem>) {
 /// Parse `Shortcut` `ShellItem` info
 fn parse_item(data: &[u8]) -> nom::IResult<&[u8], Vec<ShellItem>> {
     let sig = [12, 0, 0, 160];
-    let (_, sig_start) = take_until(sig.as_slice())(data)?;
-
-    let adjust_start = 4;
-    let (item_data, _) = take(sig_start.len() - adjust_start)(data)?;
+    let (item_data, _) = find_block(data, &sig)?;
     let (input, _size_data) = take(size_of::<u32>())(item_data)?;
     let (input, _sig_data) = take(size_of::<u32>())(input)?;
 
@@ -1,8 +1,9 @@
+use super::find_block;
 use crate::utils::{
     nom_helper::{nom_unsigned_four_bytes, Endian},
     uuid::format_guid_le_bytes,
 };
-use nom::bytes::complete::{take, take_until};
+use nom::bytes::complete::take;
 use std::mem::size_of;
 
 /// Determine if extra Known Folder data exists in `Shortcut` data
@@ -17,10 +18,7 @@ pub(crate) fn has_known(data: &[u8]) -> (bool, String) {
 /// Parse `Shortcut` Known Folder info
 fn parse_known(data: &[u8]) -> nom::IResult<&[u8], String> {
     let sig = [11, 0, 0, 160];
-    let (_, sig_start) = take_until(sig.as_slice())(data)?;
-
-    let adjust_start = 4;
-    let (known_data, _) = take(sig_start.len() - adjust_start)(data)?;
+    let (known_data, _) = find_block(data, &sig)?;
     let (input, _size_data) = take(size_of::<u32>())(known_data)?;
     let (input, _sig_data) = take(size_of::<u32>())(input)?;
 
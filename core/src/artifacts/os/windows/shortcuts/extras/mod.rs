@@ -8,3 +8,58 @@ pub(crate) mod property;
 pub(crate) mod shim;
 pub(crate) mod special;
 pub(crate) mod tracker;
+
+use nom::{
+    bytes::complete::{take, take_until},
+    error::{Error, ErrorKind},
+};
+
+/**
+ * Scan for an extra data block signature and return the data starting at the block size  
+ * The block size is the four (4) bytes before the signature. Returns an error if the block is truncated
+ */
+pub(crate) fn find_block<'a>(data: &'a [u8], sig: &[u8]) -> nom::IResult<&'a [u8], ()> {
+    let (_, sig_start) = take_until(sig)(data)?;
+
+    let adjust_start = 4;
+    if sig_start.len() < adjust_start {
+        return Err(nom::Err::Failure(Error::new(data, ErrorKind::Eof)));
+    }
+    let (block_data, _) = take(sig_start.len() - adjust_start)(data)?;
+    Ok((block_data, ()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        console::has_console, environment::has_environment, find_block, tracker::has_tracker,
+    };
+
+    #[test]
+    fn test_find_block() {
+        let test = [1, 2, 3, 4, 16, 0, 0, 0, 3, 0, 0, 160, 0, 0, 0, 0];
+        let (block, _) = find_block(&test, &[3, 0, 0, 160]).unwrap();
+        assert_eq!(block.len(), 12);
+        assert_eq!(block[0], 16);
+    }
+
+    #[test]
+    fn test_find_block_missing_size() {
+        // Signature at the start of the data has no room for the block size
+        let test = [3, 0, 0, 160, 0, 0, 0, 0];
+        assert!(find_block(&test, &[3, 0, 0, 160]).is_err());
+    }
+
+    #[test]
+    fn test_truncated_blocks() {
+        // Signatures followed by truncated block data should be skipped
+        let tracker = [96, 0, 0, 0, 3, 0, 0, 160, 88, 0, 0, 0, 0, 0, 0, 0, 100, 101];
+        assert!(!has_tracker(&tracker).0);
+
+        let env = [20, 3, 0, 0, 1, 0, 0, 160, 37, 0];
+        assert!(!has_environment(&env).0);
+
+        let console = [204, 0, 0, 0, 2, 0, 0, 160, 7, 0];
+        assert!(!has_console(&console).0);
+    }
+}
@@ -1,6 +1,7 @@
+use super::find_block;
 use crate::artifacts::os::windows::propertystore::parser::get_property_guid;
 use log::error;
-use nom::bytes::complete::{take, take_until};
+use nom::bytes::complete::take;
 use serde_json::Value;
 use std::{collections::HashMap, mem::size_of};
 
@@ -16,10 +17,7 @@ pub(crate) fn has_property(data: &[u8]) -> (bool, Vec<HashMap<String, Value>>) {
 /// Scan for Property Store data and parse if exists
 fn parse_property(data: &[u8]) -> nom::IResult<&[u8], Vec<HashMap<String, Value>>> {
     let sig = [9, 0, 0, 160];
-    let (_, sig_start) = take_until(sig.as_slice())(data)?;
-
-    let adjust_start = 4;
-    let (property_tracker, _) = take(sig_start.len() - adjust_start)(data)?;
+    let (property_tracker, _) = find_block(data, &sig)?;
     let (input, _size_data) = take(size_of::<u32>())(property_tracker)?;
     let (input, _sig_data) = take(size_of::<u32>())(input)?;
 
@@ -1,3 +1,4 @@
+use super::find_block;
 use crate::utils::strings::extract_utf16_string;
 use nom::bytes::complete::{take, take_until};
 use std::mem::size_of;
@@ -14,10 +15,7 @@ pub(crate) fn has_shim(data: &[u8]) -> (bool, String) {
 /// Parse `Shortcut` Shim info
 fn parse_shim(data: &[u8]) -> nom::IResult<&[u8], String> {
     let sig = [8, 0, 0, 160];
-    let (_, sig_start) = take_until(sig.as_slice())(data)?;
-
-    let adjust_start = 4;
-    let (shim_data, _) = take(sig_start.len() - adjust_start)(data)?;
+    let (shim_data, _) = find_block(data, &sig)?;
     let (input, _size_data) = take(size_of::<u32>())(shim_data)?;
     let (input, _sig_data) = take(size_of::<u32>())(input)?;
 
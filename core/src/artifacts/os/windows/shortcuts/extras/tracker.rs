@@ -1,9 +1,10 @@
+use super::find_block;
 use crate::utils::{
     nom_helper::{nom_unsigned_four_bytes, Endian},
     strings::extract_utf8_string,
     uuid::format_guid_le_bytes,
 };
-use nom::bytes::complete::{take, take_until};
+use nom::bytes::complete::take;
 use std::mem::size_of;
 
 #[derive(Debug)]
@@ -44,10 +45,7 @@ pub(crate) fn has_tracker(data: &[u8]) -> (bool, Tracker) {
 /// Scan for Tracker data and parse if exists
 fn parse_tracker(data: &[u8]) -> nom::IResult<&[u8], Tracker> {
     let sig = [3, 0, 0, 160];
-    let (_, sig_start) = take_until(sig.as_slice())(data)?;
-
-    let adjust_start = 4;
-    let (tracker_start, _) = take(sig_start.len() - adjust_start)(data)?;
+    let (tracker_start, _) = find_block(data, &sig)?;
     let (input, size) = nom_unsigned_four_bytes(tracker_start, Endian::Le)?;
     let (input, sig) = nom_unsigned_four_bytes(input, Endian::Le)?;
     let (input, tracker_size) = nom_unsigned_four_bytes(input, Endian::Le)?;
@@ -1,4 +1,4 @@
-mod error;
+pub(crate) mod error;
 mod extras;
 mod header;
 mod location;
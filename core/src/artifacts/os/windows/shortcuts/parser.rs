@@ -15,12 +15,65 @@
  * `https://github.com/Velocidex/velociraptor`
  */
 use super::{error::LnkError, header::LnkHeader, shortcut::get_shortcut_data};
-use crate::filesystem::files::{list_files, read_file};
+use crate::{
+    filesystem::{
+        files::{list_files, read_file},
+        metadata::glob_paths,
+    },
+    structs::artifacts::os::windows::ShortcutOptions,
+    utils::environment::get_systemdrive,
+};
 use common::windows::ShortcutInfo;
 use log::error;
 
+/// Grab `Shortcut` files from the Recent and Desktop directories for all users or an alternative directory
+pub fn grab_shortcuts(options: &ShortcutOptions) -> Result<Vec<ShortcutInfo>, LnkError> {
+    if let Some(path) = &options.path {
+        return grab_lnk_directory(path);
+    }
+    let systemdrive_result = get_systemdrive();
+    let drive = match systemdrive_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[shortcuts] Could not get systemdrive: {err:?}");
+            return Err(LnkError::Systemdrive);
+        }
+    };
+
+    let paths = [
+        format!("{drive}:\\Users\\*\\AppData\\Roaming\\Microsoft\\Windows\\Recent\\*.lnk"),
+        format!("{drive}:\\Users\\*\\Desktop\\*.lnk"),
+    ];
+
+    let mut shortcut_info = Vec::new();
+    for path in paths {
+        let glob_results = glob_paths(&path);
+        let glob_paths = match glob_results {
+            Ok(result) => result,
+            Err(err) => {
+                error!("[shortcuts] Could not glob shortcut paths {path}: {err:?}");
+                continue;
+            }
+        };
+
+        for entry in glob_paths {
+            if !entry.is_file {
+                continue;
+            }
+            match grab_lnk_file(&entry.full_path) {
+                Ok(info) => shortcut_info.push(info),
+                Err(_err) => {
+                    error!("[shortcuts] Failed to parse file: {}", entry.full_path);
+                    continue;
+                }
+            }
+        }
+    }
+    Ok(shortcut_info)
+}
+
 /// `Shortcut` files can be location anywhere. Provide a directory and parse any `lnk` (`Shortcut`) files
-pub(crate) fn grab_lnk_directory(path: &str) -> Result<Vec<ShortcutInfo>, LnkError> {
+pub fn grab_lnk_directory(path: &str) -> Result<Vec<ShortcutInfo>, LnkError> {
     let files_results = list_files(path);
     let files = match files_results {
         Ok(results) => results,
@@ -45,7 +98,7 @@ pub(crate) fn grab_lnk_directory(path: &str) -> Result<Vec<ShortcutInfo>, LnkErr
 }
 
 /// Parse a single `shortcut` file
-pub(crate) fn grab_lnk_file(path: &str) -> Result<ShortcutInfo, LnkError> {
+pub fn grab_lnk_file(path: &str) -> Result<ShortcutInfo, LnkError> {
     let result = read_file(path);
     let lnk_data = match result {
         Ok(data) => data,
@@ -60,7 +113,7 @@ pub(crate) fn grab_lnk_file(path: &str) -> Result<ShortcutInfo, LnkError> {
 }
 
 /// Parse the raw bytes of `shortcut` data
-pub(crate) fn parse_lnk_data(data: &[u8]) -> Result<ShortcutInfo, LnkError> {
+pub fn parse_lnk_data(data: &[u8]) -> Result<ShortcutInfo, LnkError> {
     let result = LnkHeader::check_header(data);
     let is_header = match result {
         Ok((_, result)) => result,
@@ -88,10 +141,11 @@ pub(crate) fn parse_lnk_data(data: &[u8]) -> Result<ShortcutInfo, LnkError> {
 #[cfg(test)]
 #[cfg(target_os = "windows")]
 mod tests {
-    use super::{grab_lnk_directory, grab_lnk_file};
+    use super::{grab_lnk_directory, grab_lnk_file, grab_shortcuts};
     use crate::artifacts::os::windows::shortcuts::parser::parse_lnk_data;
     use crate::filesystem::directory::{get_user_paths, is_directory};
     use crate::filesystem::files::list_files;
+    use crate::structs::artifacts::os::windows::ShortcutOptions;
     use common::windows::ShellType::{Delegate, Directory, RootFolder};
     use common::windows::{AttributeFlags, DataFlags, DriveType, LocationFlag, ShellItem};
     use std::path::PathBuf;
@@ -115,6 +169,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_grab_shortcuts() {
+        let options = ShortcutOptions { path: None };
+        let results = grab_shortcuts(&options).unwrap();
+        for entry in results {
+            assert!(entry.source_path.ends_with(".lnk"));
+        }
+    }
+
+    #[test]
+    fn test_grab_shortcuts_path() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/lnk/win11");
+        let options = ShortcutOptions {
+            path: Some(test_location.display().to_string()),
+        };
+        let results = grab_shortcuts(&options).unwrap();
+        assert_eq!(results.len(), 5);
+    }
+
     #[test]
     fn test_parse_lnk_data() {
         let test = [
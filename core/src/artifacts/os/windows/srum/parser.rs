@@ -33,23 +33,33 @@ pub(crate) fn grab_srum(
     output: &mut Output,
     filter: &bool,
 ) -> Result<(), SrumError> {
-    let path = if let Some(alt) = &options.alt_file {
-        alt.to_string()
-    } else {
-        let systemdrive_result = get_systemdrive();
-        let systemdrive = match systemdrive_result {
-            Ok(result) => result,
-            Err(err) => {
-                error!("[srum] Could not get systemdrive: {err:?}");
-                return Err(SrumError::Systemdrive);
-            }
-        };
-        format!("{systemdrive}:\\Windows\\System32\\sru\\SRUDB.dat")
-    };
-
+    let path = srum_file(options)?;
     parse_srum(&path, output, filter)
 }
 
+/// Grab a single `SRUM` table from the default or an alternative path
+pub(crate) fn grab_srum_table(options: &SrumOptions, table: &str) -> Result<Value, SrumError> {
+    let path = srum_file(options)?;
+    get_srum(&path, table)
+}
+
+/// Get the `SRUM` database path. Uses the alternative file if provided
+fn srum_file(options: &SrumOptions) -> Result<String, SrumError> {
+    if let Some(alt) = &options.alt_file {
+        return Ok(alt.to_string());
+    }
+
+    let systemdrive_result = get_systemdrive();
+    let systemdrive = match systemdrive_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[srum] Could not get systemdrive: {err:?}");
+            return Err(SrumError::Systemdrive);
+        }
+    };
+    Ok(format!("{systemdrive}:\\Windows\\System32\\sru\\SRUDB.dat"))
+}
+
 /**
  * Grab the `SRUM` data from the provided path  
  * We then dump a single provided table associated with `SRUM` along with the `SruDbIdMapTable` index
@@ -62,8 +72,9 @@ pub(crate) fn grab_srum_path(path: &str, table: &str) -> Result<Value, SrumError
 #[cfg(target_os = "windows")]
 mod tests {
     use crate::{
-        artifacts::os::windows::srum::parser::grab_srum_path,
-        structs::artifacts::os::windows::SrumOptions, structs::toml::Output,
+        artifacts::os::windows::srum::parser::{grab_srum_path, grab_srum_table},
+        structs::artifacts::os::windows::SrumOptions,
+        structs::toml::Output,
     };
 
     use super::grab_srum;
@@ -78,9 +89,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -92,6 +101,14 @@ mod tests {
         assert_eq!(results.is_null(), false)
     }
 
+    #[test]
+    fn test_grab_srum_table() {
+        let options = SrumOptions { alt_file: None };
+
+        let results = grab_srum_table(&options, "{D10CA2FE-6FCF-4F6D-848E-B2E99266FA89}").unwrap();
+        assert!(results.is_array())
+    }
+
     #[test]
     fn test_grab_srum() {
         let options = SrumOptions { alt_file: None };
@@ -1,5 +1,6 @@
 use super::{
     error::TaskError,
+    schedule::job_schedules,
     sections::{fixed::parse_fixed, variable::parse_variable},
 };
 use crate::filesystem::files::read_file;
@@ -60,6 +61,7 @@ fn read_job(path: &str) -> Result<TaskJob, TaskError> {
         comments: variable_value.comment,
         user_data: variable_value.user_data,
         start_error: variable_value.start_error,
+        schedules: job_schedules(&variable_value.triggers),
         triggers: variable_value.triggers,
         path: path.to_string(),
     };
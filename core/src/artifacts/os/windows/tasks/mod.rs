@@ -1,6 +1,7 @@
 mod error;
 mod job;
 pub(crate) mod parser;
+mod schedule;
 mod schemas;
 mod sections;
 mod xml;
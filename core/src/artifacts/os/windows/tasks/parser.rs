@@ -22,7 +22,7 @@ use crate::{
 use common::windows::{TaskData, TaskJob, TaskXml};
 use log::{error, warn};
 
-/// Grab Schedule Tasks based on `TaskOptions`
+/// Grab Schedule Tasks based on `TaskOptions`. Can parse a single file, an alternative drive, or the systemdrive
 pub(crate) fn grab_tasks(options: &TasksOptions) -> Result<TaskData, TaskError> {
     if let Some(file) = &options.alt_file {
         if file.ends_with(".job") {
@@ -43,6 +43,10 @@ pub(crate) fn grab_tasks(options: &TasksOptions) -> Result<TaskData, TaskError>
         return Ok(task);
     }
 
+    if let Some(alt) = options.alt_drive {
+        return drive_tasks(&alt);
+    }
+
     let drive_result = get_systemdrive();
     let drive = match drive_result {
         Ok(result) => result,
@@ -140,7 +144,25 @@ mod tests {
 
     #[test]
     fn test_grab_tasks() {
-        let options = TasksOptions { alt_file: None };
+        let options = TasksOptions {
+            alt_file: None,
+            alt_drive: None,
+            all_drives: None,
+            removable_drives: None,
+        };
+
+        let result = grab_tasks(&options).unwrap();
+        assert!(result.tasks.len() > 10);
+    }
+
+    #[test]
+    fn test_grab_tasks_alt_drive() {
+        let options = TasksOptions {
+            alt_file: None,
+            alt_drive: Some('C'),
+            all_drives: None,
+            removable_drives: None,
+        };
 
         let result = grab_tasks(&options).unwrap();
         assert!(result.tasks.len() > 10);
@@ -0,0 +1,256 @@
+use common::windows::{
+    BaseTriggers, CalendarTrigger, ScheduleType, TaskSchedule, TriggerFlags, TriggerTypes,
+    Triggers, VarTriggers,
+};
+
+/// Normalize XML `Task` triggers into a common schedule format
+pub(crate) fn xml_schedules(triggers: &Triggers) -> Vec<TaskSchedule> {
+    let mut schedules = Vec::new();
+
+    for boot in &triggers.boot {
+        schedules.push(base_schedule(
+            ScheduleType::Boot,
+            &boot.common,
+            boot.delay.clone(),
+        ));
+    }
+    for registration in &triggers.registration {
+        schedules.push(base_schedule(
+            ScheduleType::Registration,
+            &registration.common,
+            registration.delay.clone(),
+        ));
+    }
+    for idle in &triggers.idle {
+        schedules.push(base_schedule(ScheduleType::Idle, &idle.common, None));
+    }
+    for time in &triggers.time {
+        schedules.push(base_schedule(
+            ScheduleType::Once,
+            &time.common,
+            time.random_delay.clone(),
+        ));
+    }
+    for event in &triggers.event {
+        schedules.push(base_schedule(
+            ScheduleType::Event,
+            &event.common,
+            event.delay.clone(),
+        ));
+    }
+    for logon in &triggers.logon {
+        schedules.push(base_schedule(
+            ScheduleType::Logon,
+            &logon.common,
+            logon.delay.clone(),
+        ));
+    }
+    for session in &triggers.session {
+        schedules.push(base_schedule(
+            ScheduleType::Session,
+            &session.common,
+            session.delay.clone(),
+        ));
+    }
+    for wnf in &triggers.wnf {
+        schedules.push(base_schedule(
+            ScheduleType::Wnf,
+            &wnf.common,
+            wnf.delay.clone(),
+        ));
+    }
+    for calendar in &triggers.calendar {
+        schedules.push(calendar_schedule(calendar));
+    }
+
+    schedules
+}
+
+/// Normalize the older Job `Task` triggers into a common schedule format
+pub(crate) fn job_schedules(triggers: &[VarTriggers]) -> Vec<TaskSchedule> {
+    let mut schedules = Vec::new();
+    for trigger in triggers {
+        // The trigger type is an enum value. The last type in the list is the actual value
+        let schedule_type = match trigger.types.last() {
+            Some(TriggerTypes::Once) => ScheduleType::Once,
+            Some(TriggerTypes::Daily) => ScheduleType::Daily,
+            Some(TriggerTypes::Weekly) => ScheduleType::Weekly,
+            Some(TriggerTypes::MonthlyDate) => ScheduleType::Monthly,
+            Some(TriggerTypes::MonthlyDow) => ScheduleType::MonthlyDayOfWeek,
+            Some(TriggerTypes::EventOnIdle) => ScheduleType::Idle,
+            Some(TriggerTypes::EventAtSystemstart) => ScheduleType::Boot,
+            Some(TriggerTypes::EventAtLogon) => ScheduleType::Logon,
+            None => ScheduleType::Unknown,
+        };
+
+        let end = if trigger.flags.contains(&TriggerFlags::HasEndDate) {
+            Some(trigger.end_date.clone())
+        } else {
+            None
+        };
+
+        let interval = if trigger.interval_mins > 0 {
+            Some(format!("PT{}M", trigger.interval_mins))
+        } else {
+            None
+        };
+
+        let schedule = TaskSchedule {
+            schedule_type,
+            start: Some(format!("{} {}", trigger.start_date, trigger.start_time)),
+            end,
+            interval,
+            delay: None,
+            enabled: !trigger.flags.contains(&TriggerFlags::Disabled),
+        };
+        schedules.push(schedule);
+    }
+
+    schedules
+}
+
+/// Determine the schedule type and interval for a Calendar trigger
+fn calendar_schedule(calendar: &CalendarTrigger) -> TaskSchedule {
+    let mut schedule = base_schedule(
+        ScheduleType::Unknown,
+        &calendar.common,
+        calendar.random_delay.clone(),
+    );
+
+    if let Some(day) = &calendar.schedule_by_day {
+        schedule.schedule_type = ScheduleType::Daily;
+        schedule.interval = Some(format!("P{}D", day.days_interval.unwrap_or(1)));
+    } else if let Some(week) = &calendar.schedule_by_week {
+        schedule.schedule_type = ScheduleType::Weekly;
+        schedule.interval = Some(format!("P{}W", week.weeks_interval.unwrap_or(1)));
+    } else if calendar.schedule_by_month.is_some() {
+        schedule.schedule_type = ScheduleType::Monthly;
+    } else if calendar.schedule_by_month_day_of_week.is_some() {
+        schedule.schedule_type = ScheduleType::MonthlyDayOfWeek;
+    }
+
+    schedule
+}
+
+/// Build a schedule from the values shared by all XML triggers
+fn base_schedule(
+    schedule_type: ScheduleType,
+    common: &Option<BaseTriggers>,
+    delay: Option<String>,
+) -> TaskSchedule {
+    let mut schedule = TaskSchedule {
+        schedule_type,
+        start: None,
+        end: None,
+        interval: None,
+        delay,
+        enabled: true,
+    };
+
+    if let Some(base) = common {
+        schedule.start = base.start_boundary.clone();
+        schedule.end = base.end_boundary.clone();
+        schedule.enabled = base.enabled.unwrap_or(true);
+        schedule.interval = base
+            .repetition
+            .as_ref()
+            .map(|repeat| repeat.interval.clone());
+    }
+
+    schedule
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base_schedule, calendar_schedule, job_schedules, xml_schedules};
+    use common::windows::{
+        BaseTriggers, BootTrigger, ByDay, CalendarTrigger, Repetition, ScheduleType, TriggerFlags,
+        TriggerTypes, Triggers, VarTriggers,
+    };
+
+    fn base() -> Option<BaseTriggers> {
+        Some(BaseTriggers {
+            id: None,
+            start_boundary: Some(String::from("2023-08-01T10:00:00")),
+            end_boundary: None,
+            enabled: Some(false),
+            execution_time_limit: None,
+            repetition: Some(Repetition {
+                interval: String::from("PT1H"),
+                duration: None,
+                stop_at_duration_end: None,
+            }),
+        })
+    }
+
+    #[test]
+    fn test_xml_schedules() {
+        let triggers = Triggers {
+            boot: vec![BootTrigger {
+                common: None,
+                delay: Some(String::from("PT5M")),
+            }],
+            registration: Vec::new(),
+            idle: Vec::new(),
+            time: Vec::new(),
+            event: Vec::new(),
+            logon: Vec::new(),
+            session: Vec::new(),
+            calendar: Vec::new(),
+            wnf: Vec::new(),
+        };
+
+        let result = xml_schedules(&triggers);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].schedule_type, ScheduleType::Boot);
+        assert_eq!(result[0].delay, Some(String::from("PT5M")));
+        assert!(result[0].enabled);
+    }
+
+    #[test]
+    fn test_job_schedules() {
+        let triggers = vec![VarTriggers {
+            start_date: String::from("2023-8-1"),
+            end_date: String::from("2023-9-1"),
+            start_time: String::from("10:0"),
+            duration: 0,
+            interval_mins: 15,
+            flags: vec![TriggerFlags::HasEndDate],
+            types: vec![TriggerTypes::Once, TriggerTypes::Daily],
+        }];
+
+        let result = job_schedules(&triggers);
+        assert_eq!(result[0].schedule_type, ScheduleType::Daily);
+        assert_eq!(result[0].start, Some(String::from("2023-8-1 10:0")));
+        assert_eq!(result[0].end, Some(String::from("2023-9-1")));
+        assert_eq!(result[0].interval, Some(String::from("PT15M")));
+        assert!(result[0].enabled);
+    }
+
+    #[test]
+    fn test_calendar_schedule() {
+        let calendar = CalendarTrigger {
+            common: base(),
+            random_delay: None,
+            schedule_by_day: Some(ByDay {
+                days_interval: Some(2),
+            }),
+            schedule_by_week: None,
+            schedule_by_month: None,
+            schedule_by_month_day_of_week: None,
+        };
+
+        let result = calendar_schedule(&calendar);
+        assert_eq!(result.schedule_type, ScheduleType::Daily);
+        assert_eq!(result.interval, Some(String::from("P2D")));
+        assert!(!result.enabled);
+    }
+
+    #[test]
+    fn test_base_schedule() {
+        let result = base_schedule(ScheduleType::Logon, &base(), None);
+        assert_eq!(result.start, Some(String::from("2023-08-01T10:00:00")));
+        assert_eq!(result.interval, Some(String::from("PT1H")));
+        assert!(!result.enabled);
+    }
+}
@@ -1,5 +1,6 @@
 use super::{
     error::TaskError,
+    schedule::xml_schedules,
     schemas::{actions::parse_actions, registration::parse_registration, triggers::parse_trigger},
 };
 use crate::utils::encoding::read_xml;
@@ -43,6 +44,7 @@ fn process_xml(xml: &str, path: &str) -> Result<TaskXml, TaskError> {
             send_email: Vec::new(),
             show_message: Vec::new(),
         },
+        schedules: Vec::new(),
         path: path.to_string(),
     };
 
@@ -89,6 +91,10 @@ fn process_xml(xml: &str, path: &str) -> Result<TaskXml, TaskError> {
         }
     }
 
+    if let Some(triggers) = &task_xml.triggers {
+        task_xml.schedules = xml_schedules(triggers);
+    }
+
     Ok(task_xml)
 }
 
@@ -82,27 +82,48 @@ fn get_entries(
     }
 }
 
-/// Parse out the `UserAssist` data: Execution count and last execution time
+/**
+ * Parse out the `UserAssist` data: Execution count, focus count, focus time, and last execution time
+ * Windows 7 and higher use a 72 byte entry. Windows XP uses a 16 byte entry without focus info
+ */
 fn get_userassist_data(data: &[u8]) -> nom::IResult<&[u8], UserAssistEntry> {
     let mut userassist = UserAssistEntry {
         path: String::new(),
         last_execution: 0,
         count: 0,
+        focus_count: 0,
+        focus_time: 0,
         reg_path: String::new(),
         rot_path: String::new(),
         folder_path: String::new(),
     };
     let entry_size = 72;
+    let xp_entry_size = 16;
+    if data.len() == xp_entry_size {
+        let (input, _session) = nom_unsigned_four_bytes(data, Endian::Le)?;
+        let (input, count) = nom_unsigned_four_bytes(input, Endian::Le)?;
+        let (input, last_execution) = nom_unsigned_eight_bytes(input, Endian::Le)?;
+
+        // Windows XP starts the execution count at 5
+        let xp_count_start = 5;
+        userassist.count = count.saturating_sub(xp_count_start);
+        userassist.last_execution = filetime_to_unixepoch(&last_execution);
+        return Ok((input, userassist));
+    }
     if data.len() != entry_size {
         return Ok((data, userassist));
     }
     let (input, _unknown) = nom_unsigned_four_bytes(data, Endian::Le)?;
     let (input, count) = nom_unsigned_four_bytes(input, Endian::Le)?;
-    let unknown_data_size: u8 = 52;
+    let (input, focus_count) = nom_unsigned_four_bytes(input, Endian::Le)?;
+    let (input, focus_time) = nom_unsigned_four_bytes(input, Endian::Le)?;
+    let unknown_data_size: u8 = 44;
     let (input, _unknown2) = take(unknown_data_size)(input)?;
     let (input, last_execution) = nom_unsigned_eight_bytes(input, Endian::Le)?;
 
     userassist.count = count;
+    userassist.focus_count = focus_count;
+    userassist.focus_time = focus_time;
     userassist.last_execution = filetime_to_unixepoch(&last_execution);
 
     Ok((input, userassist))
@@ -173,6 +194,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_userassist_data_win7() {
+        let mut test_data = vec![0; 72];
+        test_data[4..8].copy_from_slice(&14u32.to_le_bytes());
+        test_data[8..12].copy_from_slice(&20u32.to_le_bytes());
+        test_data[12..16].copy_from_slice(&95000u32.to_le_bytes());
+        test_data[60..68].copy_from_slice(&132223123460000000u64.to_le_bytes());
+
+        let (_, result) = get_userassist_data(&test_data).unwrap();
+        assert_eq!(result.count, 14);
+        assert_eq!(result.focus_count, 20);
+        assert_eq!(result.focus_time, 95000);
+        assert_eq!(result.last_execution, 1577838746);
+    }
+
+    #[test]
+    fn test_get_userassist_data_xp() {
+        let mut test_data = vec![0; 16];
+        test_data[0..4].copy_from_slice(&1u32.to_le_bytes());
+        test_data[4..8].copy_from_slice(&8u32.to_le_bytes());
+        test_data[8..16].copy_from_slice(&132223123460000000u64.to_le_bytes());
+
+        let (_, result) = get_userassist_data(&test_data).unwrap();
+        assert_eq!(result.count, 3);
+        assert_eq!(result.focus_count, 0);
+        assert_eq!(result.focus_time, 0);
+        assert_eq!(result.last_execution, 1577838746);
+    }
+
     #[test]
     fn test_rot_decode() {
         let test_input = "Ehfg vf cerggl pbby nppbeqvat gb Sreevf";
@@ -11,7 +11,10 @@ use crate::{
 use byteorder::{LittleEndian, ReadBytesExt};
 use common::windows::{AttributeFlags, Reason, Source};
 use log::{error, warn};
-use nom::bytes::complete::{take, take_until, take_while};
+use nom::{
+    bytes::complete::{take, take_while},
+    error::ErrorKind,
+};
 use ntfs::{structured_values::NtfsFileNamespace, Ntfs, NtfsError};
 use std::{collections::HashMap, fs::File, io::BufReader};
 
@@ -65,42 +68,16 @@ impl UsnJrnlFormat {
                 break;
             }
 
-            let (input, _major_version) = nom_unsigned_two_bytes(input, Endian::Le)?;
-            let (input, _minor_version) = nom_unsigned_two_bytes(input, Endian::Le)?;
-
-            let entry_size: u8 = 6;
-            let (input, mut entry_data) = take(entry_size)(input)?;
-            let (input, mft_seq) = nom_unsigned_two_bytes(input, Endian::Le)?;
-
-            let (input, mut parent_entry_data) = take(entry_size)(input)?;
-            let (input, parent_mft_seq) = nom_unsigned_two_bytes(input, Endian::Le)?;
-
-            let (input, update_sequence_number) = nom_unsigned_eight_bytes(input, Endian::Le)?;
-            let (input, usn_time) = nom_unsigned_eight_bytes(input, Endian::Le)?;
-            let (input, reason) = nom_unsigned_four_bytes(input, Endian::Le)?;
-            let (input, source) = nom_unsigned_four_bytes(input, Endian::Le)?;
-            let (input, security_descriptor_id) = nom_unsigned_four_bytes(input, Endian::Le)?;
-            let (input, flags) = nom_unsigned_four_bytes(input, Endian::Le)?;
-            let (input, name_size) = nom_unsigned_two_bytes(input, Endian::Le)?;
-            let (input, name_offset) = nom_unsigned_two_bytes(input, Endian::Le)?;
-
-            let offset_position = 60;
-            if name_offset != offset_position {
-                remaining_input = input;
-                continue;
-            }
-
-            // The name always follows the name offset. So we actually do not need it
-            let (input, name_data) = take(name_size)(input)?;
-            let name = extract_utf16_string(name_data);
-
-            let update_time = filetime_to_unixepoch(&usn_time);
-            let update_reason = UsnJrnlFormat::reason_flags(&reason);
-            let update_source_flags = UsnJrnlFormat::source_flag(&source);
+            let (input, entry) = UsnJrnlFormat::parse_record(input)?;
+            remaining_input = input;
+            let mut entry = match entry {
+                Some(result) => result,
+                None => continue,
+            };
 
-            let file_attributes = file_attribute_flags(&flags);
             let mut parents: Vec<String> = Vec::new();
-            let parent_entry = parent_entry_data.read_u48::<LittleEndian>().unwrap_or(5);
+            let parent_entry = entry.parent_mft_entry;
+            let parent_mft_seq = entry.parent_mft_sequence;
 
             // Check our cache and if found make sure the sequence numbers match
             if let Some(cache) = cache_ids.clone().get(&parent_entry) {
@@ -139,28 +116,8 @@ impl UsnJrnlFormat {
                 };
             }
 
-            let entry = UsnJrnlFormat {
-                _major_version,
-                _minor_version,
-                update_time,
-                update_reason,
-                update_source_flags,
-                security_descriptor_id,
-                file_attributes,
-                _name_size: name_size,
-                _name_offset: name_offset,
-                name,
-                mft_entry: entry_data.read_u48::<LittleEndian>().unwrap_or(0),
-                mft_sequence: mft_seq,
-                parent_mft_entry: parent_entry,
-                parent_mft_sequence: parent_mft_seq,
-                update_sequence_number,
-                full_path: parents.join("\\"),
-            };
-
+            entry.full_path = parents.join("\\");
             entries.push(entry);
-
-            remaining_input = input;
         }
 
         Ok((remaining_input, entries))
@@ -182,67 +139,80 @@ impl UsnJrnlFormat {
                 break;
             }
 
-            let (input, _major_version) = nom_unsigned_two_bytes(input, Endian::Le)?;
-            let (input, _minor_version) = nom_unsigned_two_bytes(input, Endian::Le)?;
-
-            let entry_size: u8 = 6;
-            let (input, mut entry_data) = take(entry_size)(input)?;
-            let (input, mft_seq) = nom_unsigned_two_bytes(input, Endian::Le)?;
-
-            let (input, mut parent_entry_data) = take(entry_size)(input)?;
-            let (input, parent_mft_seq) = nom_unsigned_two_bytes(input, Endian::Le)?;
-
-            let (input, update_sequence_number) = nom_unsigned_eight_bytes(input, Endian::Le)?;
-            let (input, usn_time) = nom_unsigned_eight_bytes(input, Endian::Le)?;
-            let (input, reason) = nom_unsigned_four_bytes(input, Endian::Le)?;
-            let (input, source) = nom_unsigned_four_bytes(input, Endian::Le)?;
-            let (input, security_descriptor_id) = nom_unsigned_four_bytes(input, Endian::Le)?;
-            let (input, flags) = nom_unsigned_four_bytes(input, Endian::Le)?;
-            let (input, name_size) = nom_unsigned_two_bytes(input, Endian::Le)?;
-            let (input, name_offset) = nom_unsigned_two_bytes(input, Endian::Le)?;
-
-            let offset_position = 60;
-            if name_offset != offset_position {
-                remaining_input = input;
-                continue;
+            let (input, entry) = UsnJrnlFormat::parse_record(input)?;
+            remaining_input = input;
+            if let Some(result) = entry {
+                entries.push(result);
             }
+        }
 
-            // The name always follows the name offset. So we actually do not need it
-            let (input, name_data) = take(name_size)(input)?;
-            let name = extract_utf16_string(name_data);
-
-            let update_time = filetime_to_unixepoch(&usn_time);
-            let update_reason = UsnJrnlFormat::reason_flags(&reason);
-            let update_source_flags = UsnJrnlFormat::source_flag(&source);
-
-            let file_attributes = file_attribute_flags(&flags);
-            let parent_entry = parent_entry_data.read_u48::<LittleEndian>().unwrap_or(5);
-
-            let entry = UsnJrnlFormat {
-                _major_version,
-                _minor_version,
-                update_time,
-                update_reason,
-                update_source_flags,
-                security_descriptor_id,
-                file_attributes,
-                _name_size: name_size,
-                _name_offset: name_offset,
-                name,
-                mft_entry: entry_data.read_u48::<LittleEndian>().unwrap_or(0),
-                mft_sequence: mft_seq,
-                parent_mft_entry: parent_entry,
-                parent_mft_sequence: parent_mft_seq,
-                update_sequence_number,
-                full_path: String::new(),
-            };
-
-            entries.push(entry);
+        Ok((remaining_input, entries))
+    }
 
-            remaining_input = input;
+    /**
+     * Parse a single `USN_RECORD_V2` or `USN_RECORD_V3` entry starting at the version details
+     * Returns `None` if the name offset does not match the record version
+     */
+    fn parse_record(data: &[u8]) -> nom::IResult<&[u8], Option<UsnJrnlFormat>> {
+        let (input, _major_version) = nom_unsigned_two_bytes(data, Endian::Le)?;
+        let (input, _minor_version) = nom_unsigned_two_bytes(input, Endian::Le)?;
+
+        // Version 3 uses 128 bit file references. NTFS file references only use the lower 64 bits
+        let version3 = 3;
+        let reference_size: u8 = if _major_version == version3 { 16 } else { 8 };
+        let (input, entry_data) = take(reference_size)(input)?;
+        let (input, parent_entry_data) = take(reference_size)(input)?;
+
+        let (input, update_sequence_number) = nom_unsigned_eight_bytes(input, Endian::Le)?;
+        let (input, usn_time) = nom_unsigned_eight_bytes(input, Endian::Le)?;
+        let (input, reason) = nom_unsigned_four_bytes(input, Endian::Le)?;
+        let (input, source) = nom_unsigned_four_bytes(input, Endian::Le)?;
+        let (input, security_descriptor_id) = nom_unsigned_four_bytes(input, Endian::Le)?;
+        let (input, flags) = nom_unsigned_four_bytes(input, Endian::Le)?;
+        let (input, name_size) = nom_unsigned_two_bytes(input, Endian::Le)?;
+        let (input, name_offset) = nom_unsigned_two_bytes(input, Endian::Le)?;
+
+        let offset_position = if _major_version == version3 { 76 } else { 60 };
+        if name_offset != offset_position {
+            return Ok((input, None));
         }
 
-        Ok((remaining_input, entries))
+        // The name always follows the name offset. So we actually do not need it
+        let (input, name_data) = take(name_size)(input)?;
+        let name = extract_utf16_string(name_data);
+
+        let (mft_entry, mft_sequence) = UsnJrnlFormat::file_reference(entry_data, 0);
+        let (parent_mft_entry, parent_mft_sequence) =
+            UsnJrnlFormat::file_reference(parent_entry_data, 5);
+
+        let entry = UsnJrnlFormat {
+            _major_version,
+            _minor_version,
+            update_time: filetime_to_unixepoch(&usn_time),
+            update_reason: UsnJrnlFormat::reason_flags(&reason),
+            update_source_flags: UsnJrnlFormat::source_flag(&source),
+            security_descriptor_id,
+            file_attributes: file_attribute_flags(&flags),
+            _name_size: name_size,
+            _name_offset: name_offset,
+            name,
+            mft_entry,
+            mft_sequence,
+            parent_mft_entry,
+            parent_mft_sequence,
+            update_sequence_number,
+            full_path: String::new(),
+        };
+
+        Ok((input, Some(entry)))
+    }
+
+    /// Get the MFT entry and sequence number from a file reference. Uses `default` if the entry cannot be read
+    fn file_reference(data: &[u8], default: u64) -> (u64, u16) {
+        let mut reference = data;
+        let entry = reference.read_u48::<LittleEndian>().unwrap_or(default);
+        let sequence = reference.read_u16::<LittleEndian>().unwrap_or_default();
+        (entry, sequence)
     }
 
     /// Nom any zero (0) padding at the end of an `UsnJrnl` entry
@@ -257,12 +227,20 @@ impl UsnJrnlFormat {
         /*
          * Since we nom'd all zeros there is a chance we nom'd part of the size data :`(
          * Ex: Size = 4096 (0x1000, nom'd 00)
-         * So now we jump to the version details: Major version 2, minor version: 0
-         * We only support version 2
+         * So now we jump to the version details: Major version 2 or 3, minor version: 0
          */
-        let version_details = [2, 0, 0, 0];
-        let (remaining_input, _) = take_until(version_details.as_slice())(remaining_input)?;
-        Ok((remaining_input, ()))
+        let version2 = [2, 0, 0, 0];
+        let version3 = [3, 0, 0, 0];
+        let version_position = remaining_input
+            .windows(version2.len())
+            .position(|window| window == version2 || window == version3);
+        match version_position {
+            Some(position) => Ok((&remaining_input[position..], ())),
+            None => Err(nom::Err::Error(nom::error::Error::new(
+                remaining_input,
+                ErrorKind::TakeUntil,
+            ))),
+        }
     }
 
     /// Recursively search for parents by looking up the parent MFT entry ID
@@ -619,6 +597,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_usnjrnl_no_parent_version3() {
+        let test_data = [
+            160, 0, 0, 0, 3, 0, 0, 0, 51, 88, 5, 0, 0, 0, 13, 0, 0, 0, 0, 0, 0, 0, 0, 0, 211, 87,
+            5, 0, 0, 0, 13, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 54, 96, 6, 0, 0, 0, 220, 174, 212, 97,
+            67, 52, 217, 1, 2, 0, 0, 128, 0, 0, 0, 0, 0, 0, 0, 0, 32, 0, 0, 0, 84, 0, 76, 0, 98, 0,
+            57, 0, 55, 0, 102, 0, 56, 0, 54, 0, 48, 0, 50, 0, 45, 0, 100, 0, 57, 0, 98, 0, 54, 0,
+            45, 0, 52, 0, 51, 0, 56, 0, 55, 0, 45, 0, 97, 0, 53, 0, 99, 0, 56, 0, 45, 0, 98, 0, 99,
+            0, 53, 0, 99, 0, 50, 0, 55, 0, 51, 0, 102, 0, 52, 0, 51, 0, 51, 0, 51, 0, 46, 0, 106,
+            0, 115, 0, 111, 0, 110, 0, 108, 0, 0, 0, 0, 0,
+        ];
+        let (_, results) = UsnJrnlFormat::parse_usnjrnl_no_parent(&test_data).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]._major_version, 3);
+        assert_eq!(results[0].mft_entry, 350259);
+        assert_eq!(results[0].mft_sequence, 13);
+        assert_eq!(results[0].parent_mft_entry, 350163);
+        assert_eq!(results[0].parent_mft_sequence, 13);
+        assert_eq!(results[0].update_time, 1675039199);
+        assert_eq!(results[0].update_reason, vec![Extend, Close]);
+        assert_eq!(results[0]._name_offset, 76);
+        assert_eq!(
+            results[0].name,
+            "b97f8602-d9b6-4387-a5c8-bc5c273f4333.jsonl"
+        );
+    }
+
     #[test]
     fn test_iterate_parents() {
         let test = 955759;
@@ -643,6 +648,10 @@ mod tests {
         let test = [0, 1, 2, 0, 0, 0];
         let (result, _) = UsnJrnlFormat::nom_padding(&test).unwrap();
         assert_eq![result[0], 2];
+
+        let test = [0, 0, 1, 3, 0, 0, 0];
+        let (result, _) = UsnJrnlFormat::nom_padding(&test).unwrap();
+        assert_eq![result[0], 3];
     }
 
     #[test]
@@ -57,6 +57,8 @@ mod tests {
         let params = UsnJrnlOptions {
             alt_drive: None,
             alt_path: None,
+            all_drives: None,
+            removable_drives: None,
         };
         let results = grab_usnjrnl(&params).unwrap();
         assert!(results.len() > 10);
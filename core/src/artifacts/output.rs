@@ -1,11 +1,33 @@
 use crate::{
-    artifacts::error::CollectionError,
-    output::formats::{json::json_format, jsonl::jsonl_format},
-    runtime::deno::filter_script,
+    artifacts::{
+        baseline::compare_baseline,
+        error::CollectionError,
+        since::{
+            filter_since, flag_time_anomalies, since_field, sort_by_time, TIME_ANOMALY_FIELD,
+            TIME_ANOMALY_FLOOR,
+        },
+        timeout::is_cancelled,
+        volume::split_by_volume,
+    },
+    filesystem::selection::without_mtime_limits,
+    output::formats::{error::FormatError, json::json_format, jsonl::jsonl_format},
     structs::toml::Output,
+    utils::{
+        events::{emit_event, record_count},
+        output::{
+            batch_enabled, iso_field, labeled_name, output_fields, output_transforms,
+            project_fields, timestamp_key, timestamp_transform, transform::apply_transforms,
+        },
+        time::time_now,
+    },
 };
-use log::error;
+use common::server::jobs::CollectionEvent;
+use log::{error, warn};
 use serde_json::Value;
+use std::sync::{Mutex, MutexGuard};
+
+/// Serializes local output writes. Workers collecting artifacts in parallel share the SQLite database and combined output file
+static OUTPUT_LOCK: Mutex<()> = Mutex::new(());
 
 /// Output forensic artifacts
 pub(crate) fn output_artifact(
@@ -14,46 +36,155 @@ pub(crate) fn output_artifact(
     output: &mut Output,
     start_time: &u64,
     filter: &bool,
+) -> Result<(), CollectionError> {
+    // The collection stopped waiting for this parser. Do not write partial output
+    if is_cancelled() {
+        warn!("[artemis-core] Parser for {output_name} timed out. Discarding output");
+        return Ok(());
+    }
+    let artifact_name = output_name;
+
+    let since_data;
+    let serde_data = match output.since_epoch {
+        Some(since) => {
+            since_data = filter_since(serde_data, output_name, &since);
+            &since_data
+        }
+        None => serde_data,
+    };
+    let sorted_data;
+    let serde_data = if output.sort_by_time.unwrap_or(false) {
+        if batch_enabled(output) {
+            warn!("[artemis-core] sort_by_time is not supported with batched uploads. Output for {output_name} will not be sorted");
+            serde_data
+        } else {
+            sorted_data = sort_by_time(serde_data, output_name);
+            &sorted_data
+        }
+    } else {
+        serde_data
+    };
+    let anomaly_data;
+    let serde_data = if output.time_anomalies.unwrap_or(false) {
+        let floor = output.time_anomaly_floor.unwrap_or(TIME_ANOMALY_FLOOR);
+        let now = time_now() as i64;
+        anomaly_data = flag_time_anomalies(serde_data, output_name, &floor, &now);
+        &anomaly_data
+    } else {
+        serde_data
+    };
+    let timestamp_data;
+    let serde_data = if output.timestamp_field.is_some()
+        || output.timestamp_format.is_some()
+        || output.timestamp_iso.unwrap_or(false)
+    {
+        timestamp_data = timestamp_transform(serde_data, output_name, output);
+        &timestamp_data
+    } else {
+        serde_data
+    };
+    let projected_data;
+    let serde_data = match output_fields() {
+        Some(mut fields) => {
+            // Keep the copied timestamp so the records can still be indexed by time
+            if let Some(key) =
+                since_field(output_name).and_then(|field| timestamp_key(field, output))
+            {
+                if !fields.contains(&key) {
+                    fields.push(key);
+                }
+            }
+            // Keep the ISO8601 copy of a kept primary timestamp
+            if output.timestamp_iso.unwrap_or(false) {
+                if let Some(field) = since_field(output_name) {
+                    if fields.iter().any(|value| value == field) {
+                        fields.push(iso_field(field));
+                    }
+                }
+            }
+            if output.time_anomalies.unwrap_or(false)
+                && !fields.iter().any(|field| field == TIME_ANOMALY_FIELD)
+            {
+                fields.push(TIME_ANOMALY_FIELD.to_string());
+            }
+            projected_data = project_fields(serde_data, output_name, &fields);
+            &projected_data
+        }
+        None => serde_data,
+    };
+    let transformed_data;
+    let serde_data = match output_transforms() {
+        Some(transforms) => {
+            transformed_data = apply_transforms(serde_data, &transforms);
+            &transformed_data
+        }
+        None => serde_data,
+    };
+    let diff_data;
+    let diff_name;
+    let (serde_data, output_name) = match &output.baseline {
+        Some(path) => {
+            diff_data = compare_baseline(serde_data, output_name, path)?;
+            diff_name = format!("{output_name}_diff");
+            (&diff_data, diff_name.as_str())
+        }
+        None => (serde_data, output_name),
+    };
+    // Artifact specific options above use the artifact name. The label only changes where the output is written
+    let labeled = labeled_name(output_name);
+    let output_name = labeled.as_str();
+
+    if output.split_by_volume.unwrap_or(false) {
+        if let Some(groups) = split_by_volume(serde_data, artifact_name) {
+            for (volume, volume_data) in groups {
+                let volume_name = format!("{output_name}_{volume}");
+                write_output(&volume_data, &volume_name, output, start_time, filter)?;
+            }
+            return Ok(());
+        }
+    }
+    write_output(serde_data, output_name, output, start_time, filter)
+}
+
+/// Write the artifact data to the configured format or pass it to the filter script
+fn write_output(
+    serde_data: &Value,
+    output_name: &str,
+    output: &mut Output,
+    start_time: &u64,
+    filter: &bool,
 ) -> Result<(), CollectionError> {
     if *filter {
         if let Some(script) = &output.filter_script.clone() {
             let args = vec![serde_data.to_string(), output_name.to_string()];
-            if let Some(name) = &output.filter_name.clone() {
-                let filter_result = filter_script(output, &args, name, script);
-                return match filter_result {
-                    Ok(_) => Ok(()),
-                    Err(err) => {
-                        error!(
-                            "[artemis-core] Could not apply filter script to windows data: {err:?}"
-                        );
-                        Err(CollectionError::FilterOutput)
-                    }
-                };
-            }
-            let filter_result = filter_script(output, &args, "UnknownFilterName", script);
-            return match filter_result {
-                Ok(_) => Ok(()),
-                Err(err) => {
-                    error!(
-                        "[artemis-core] Could not apply unknown filter script to windows data: {err:?}"
-                    );
-                    Err(CollectionError::FilterOutput)
-                }
-            };
+            let name = output
+                .filter_name
+                .clone()
+                .unwrap_or_else(|| String::from("UnknownFilterName"));
+            return run_filter(output, &args, &name, script);
         }
     }
 
-    let output_status = if output.format.to_lowercase() == "json" {
-        json_format(serde_data, output_name, output, start_time)
-    } else if output.format.to_lowercase() == "jsonl" {
-        jsonl_format(serde_data, output_name, output, start_time)
-    } else {
-        error!(
-            "[artemis-core] Unknown formatter provided: {}",
-            output.format
-        );
-        return Err(CollectionError::Format);
-    };
+    // Filter scripts write their output through this function. Only lock once the data is written directly
+    let _guard = local_output_lock(output);
+    // Staged output files are written by artemis. They are not artifact sources
+    let output_status = without_mtime_limits(|| {
+        if output.output == "sqlite" {
+            Ok(sqlite_output(serde_data, output_name, output))
+        } else if output.format.to_lowercase() == "json" {
+            Ok(json_format(serde_data, output_name, output, start_time))
+        } else if output.format.to_lowercase() == "jsonl" {
+            Ok(jsonl_format(serde_data, output_name, output, start_time))
+        } else if output.format.to_lowercase() == "parquet" {
+            Ok(parquet_output(serde_data, output_name, output, start_time))
+        } else {
+            error!(
+                "[artemis-core] Unknown formatter provided: {}",
+                output.format
+            );
+            Err(CollectionError::Format)
+        }
+    })?;
     match output_status {
         Ok(_) => {}
         Err(err) => {
@@ -61,12 +192,111 @@ pub(crate) fn output_artifact(
             return Err(CollectionError::Output);
         }
     }
+    emit_event(
+        output,
+        CollectionEvent::RecordsEmitted {
+            name: output_name.to_string(),
+            count: record_count(serde_data),
+        },
+    );
     Ok(())
 }
 
+/**
+ * Lock the output destination if it is on the local system. A worker that panicked while writing does not stop the remaining output
+ * Remote uploads use unique names and batched uploads have their own lock, so workers do not wait on each other's network requests
+ */
+fn local_output_lock(output: &Output) -> Option<MutexGuard<'static, ()>> {
+    if output.output != "local" && output.output != "sqlite" {
+        return None;
+    }
+    match OUTPUT_LOCK.lock() {
+        Ok(result) => Some(result),
+        Err(err) => {
+            warn!("[artemis-core] Output lock poisoned: {err:?}");
+            Some(err.into_inner())
+        }
+    }
+}
+
+/// Apply the filter script if artemis was built with the `scripting` feature
+#[cfg(feature = "scripting")]
+fn run_filter(
+    output: &mut Output,
+    args: &[String],
+    filter_name: &str,
+    script: &str,
+) -> Result<(), CollectionError> {
+    let filter_result = crate::runtime::deno::filter_script(output, args, filter_name, script);
+    match filter_result {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            error!("[artemis-core] Could not apply filter script {filter_name} to data: {err:?}");
+            Err(CollectionError::FilterOutput)
+        }
+    }
+}
+
+#[cfg(not(feature = "scripting"))]
+fn run_filter(
+    _output: &mut Output,
+    _args: &[String],
+    filter_name: &str,
+    _script: &str,
+) -> Result<(), CollectionError> {
+    error!("[artemis-core] Filter script {filter_name} requires artemis to be built with the scripting feature");
+    Err(CollectionError::FilterOutput)
+}
+
+/// Output to `parquet` if artemis was built with the `parquet` feature
+#[cfg(feature = "parquet")]
+fn parquet_output(
+    serde_data: &Value,
+    output_name: &str,
+    output: &mut Output,
+    start_time: &u64,
+) -> Result<(), FormatError> {
+    crate::output::formats::parquet::parquet_format(serde_data, output_name, output, start_time)
+}
+
+#[cfg(not(feature = "parquet"))]
+fn parquet_output(
+    _serde_data: &Value,
+    _output_name: &str,
+    _output: &mut Output,
+    _start_time: &u64,
+) -> Result<(), FormatError> {
+    error!("[artemis-core] Parquet output requires artemis to be built with the parquet feature");
+    Err(FormatError::Output)
+}
+
+/// Output to a local `SQLite` database
+fn sqlite_output(
+    serde_data: &Value,
+    output_name: &str,
+    output: &Output,
+) -> Result<(), FormatError> {
+    let sqlite_result =
+        crate::output::local::sqlite::sqlite_output(serde_data, output, output_name);
+    match sqlite_result {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            error!("[artemis-core] Failed to output {output_name} to SQLite: {err:?}");
+            Err(FormatError::Output)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{artifacts::output::output_artifact, structs::toml::Output, utils::time};
+    use crate::{
+        artifacts::output::{local_output_lock, output_artifact},
+        filesystem::files::{list_files, read_file},
+        structs::toml::Output,
+        utils::{output::set_output_fields, time},
+    };
+    use serde_json::json;
+    use std::{fs::remove_dir_all, path::PathBuf};
 
     fn output_options(name: &str, format: &str, directory: &str, compress: bool) -> Output {
         Output {
@@ -79,9 +309,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: String::from("local"),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -95,4 +323,243 @@ mod tests {
         let status = output_artifact(&data, name, &mut output, &start_time, &false).unwrap();
         assert_eq!(status, ());
     }
+
+    /// Output prefetch data with a renamed timestamp and return the contents of the output files
+    fn timestamp_output(format: &str) -> Vec<u8> {
+        let name = format!("timestamp_{format}");
+        let mut output = output_options(&name, format, "./tmp", false);
+        output.timestamp_field = Some(String::from("@timestamp"));
+        output.timestamp_format = Some(String::from("iso8601"));
+        let _ = remove_dir_all(format!("./tmp/{name}"));
+
+        let data = json!([{"path": "C:\\test.exe", "last_run_time": 1620610795}]);
+        let start_time = time::time_now();
+        output_artifact(&data, "prefetch", &mut output, &start_time, &false).unwrap();
+
+        let mut contents = Vec::new();
+        for file in list_files(&format!("./tmp/{name}")).unwrap() {
+            contents.append(&mut read_file(&file).unwrap());
+        }
+        contents
+    }
+
+    #[test]
+    fn test_output_artifact_timestamp_json() {
+        let contents = String::from_utf8(timestamp_output("json")).unwrap();
+        assert!(contents.contains("\"@timestamp\":\"2021-05-10T01:39:55Z\""));
+        assert!(contents.contains("\"last_run_time\":1620610795"));
+    }
+
+    #[test]
+    fn test_output_artifact_timestamp_jsonl() {
+        let contents = String::from_utf8(timestamp_output("jsonl")).unwrap();
+        assert!(contents.contains("\"@timestamp\":\"2021-05-10T01:39:55Z\""));
+    }
+
+    #[test]
+    fn test_output_artifact_baseline() {
+        let name = "baseline_output";
+        let mut output = output_options(name, "json", "./tmp", false);
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/baseline/prefetch.json");
+        output.baseline = Some(test_location.display().to_string());
+        let _ = remove_dir_all(format!("./tmp/{name}"));
+
+        let data = json!([
+            {"path": "C:\\Windows\\notepad.exe", "run_count": 1},
+            {"path": "C:\\Windows\\calc.exe", "run_count": 4},
+            {"path": "C:\\Windows\\cmd.exe", "run_count": 11},
+        ]);
+        let start_time = time::time_now();
+        output_artifact(&data, "prefetch", &mut output, &start_time, &false).unwrap();
+
+        let mut contents = Vec::new();
+        for file in list_files(&format!("./tmp/{name}")).unwrap() {
+            contents.append(&mut read_file(&file).unwrap());
+        }
+        let contents = String::from_utf8(contents).unwrap();
+        assert!(contents.contains("\"artifact_name\":\"prefetch_diff\""));
+        assert!(contents.contains("\"change\":\"changed\""));
+        assert!(!contents.contains("notepad.exe"));
+    }
+
+    #[test]
+    #[cfg(feature = "parquet")]
+    fn test_output_artifact_timestamp_parquet() {
+        let contents = timestamp_output("parquet");
+        let column = b"@timestamp";
+        assert!(contents
+            .windows(column.len())
+            .any(|window| window == column));
+    }
+
+    #[test]
+    fn test_output_artifact_fields() {
+        let name = "fields_output";
+        let mut output = output_options(name, "jsonl", "./tmp", false);
+        let _ = remove_dir_all(format!("./tmp/{name}"));
+
+        let data = json!([
+            {"path": "C:\\first.exe", "last_run_time": 100, "run_count": 2, "volume_serial": "abcd"},
+            {"path": "C:\\second.exe", "last_run_time": 200, "run_count": 4, "volume_serial": "abcd"},
+        ]);
+        let start_time = time::time_now();
+        set_output_fields(Some(vec![
+            String::from("path"),
+            String::from("last_run_time"),
+            String::from("missing"),
+        ]));
+        let status = output_artifact(&data, "prefetch", &mut output, &start_time, &false);
+        set_output_fields(None);
+        status.unwrap();
+
+        let mut contents = Vec::new();
+        for file in list_files(&format!("./tmp/{name}")).unwrap() {
+            contents.append(&mut read_file(&file).unwrap());
+        }
+        let contents = String::from_utf8(contents).unwrap();
+        assert!(contents.contains("first.exe"));
+        assert!(contents.contains("\"last_run_time\":200"));
+        assert!(!contents.contains("run_count"));
+        assert!(!contents.contains("volume_serial"));
+        assert!(!contents.contains("missing"));
+    }
+
+    #[test]
+    fn test_output_artifact_sort_by_time() {
+        let name = "sort_output";
+        let mut output = output_options(name, "jsonl", "./tmp", false);
+        output.sort_by_time = Some(true);
+        let _ = remove_dir_all(format!("./tmp/{name}"));
+
+        let data = json!([
+            {"path": "C:\\third.exe", "last_run_time": 300},
+            {"path": "C:\\first.exe", "last_run_time": 100},
+            {"path": "C:\\second.exe", "last_run_time": 200},
+        ]);
+        let start_time = time::time_now();
+        output_artifact(&data, "prefetch", &mut output, &start_time, &false).unwrap();
+
+        let mut contents = Vec::new();
+        for file in list_files(&format!("./tmp/{name}")).unwrap() {
+            contents.append(&mut read_file(&file).unwrap());
+        }
+        let contents = String::from_utf8(contents).unwrap();
+        let first = contents.find("first.exe").unwrap();
+        let second = contents.find("second.exe").unwrap();
+        let third = contents.find("third.exe").unwrap();
+        assert!(first < second && second < third);
+    }
+
+    #[test]
+    fn test_output_artifact_time_anomalies() {
+        let name = "anomaly_output";
+        let mut output = output_options(name, "jsonl", "./tmp", false);
+        output.time_anomalies = Some(true);
+        let _ = remove_dir_all(format!("./tmp/{name}"));
+
+        let data = json!([
+            {"path": "C:\\future.exe", "last_run_time": 4102444800i64, "run_count": 1},
+            {"path": "C:\\old.exe", "last_run_time": 315532800, "run_count": 1},
+            {"path": "C:\\normal.exe", "last_run_time": 1600000000, "run_count": 1},
+        ]);
+        let start_time = time::time_now();
+        set_output_fields(Some(vec![String::from("path")]));
+        let status = output_artifact(&data, "prefetch", &mut output, &start_time, &false);
+        set_output_fields(None);
+        status.unwrap();
+
+        let mut contents = Vec::new();
+        for file in list_files(&format!("./tmp/{name}")).unwrap() {
+            contents.append(&mut read_file(&file).unwrap());
+        }
+        let contents = String::from_utf8(contents).unwrap();
+        assert!(contents.contains("\"time_anomaly\":\"future\""));
+        assert!(contents.contains("\"time_anomaly\":\"before_floor\""));
+        assert_eq!(contents.matches("time_anomaly").count(), 2);
+        assert!(!contents.contains("run_count"));
+    }
+
+    #[test]
+    fn test_output_artifact_timestamp_iso() {
+        let name = "timestamp_iso_output";
+        let mut output = output_options(name, "jsonl", "./tmp", false);
+        output.timestamp_iso = Some(true);
+        let _ = remove_dir_all(format!("./tmp/{name}"));
+
+        let data = json!([{"path": "C:\\first.exe", "last_run_time": 1620610795, "run_count": 2}]);
+        let start_time = time::time_now();
+        set_output_fields(Some(vec![
+            String::from("path"),
+            String::from("last_run_time"),
+        ]));
+        let status = output_artifact(&data, "prefetch", &mut output, &start_time, &false);
+        set_output_fields(None);
+        status.unwrap();
+
+        let mut contents = Vec::new();
+        for file in list_files(&format!("./tmp/{name}")).unwrap() {
+            contents.append(&mut read_file(&file).unwrap());
+        }
+        let contents = String::from_utf8(contents).unwrap();
+        assert!(contents.contains("\"last_run_time\":1620610795"));
+        assert!(contents.contains("\"last_run_time_iso\":\"2021-05-10T01:39:55Z\""));
+        assert!(!contents.contains("run_count"));
+    }
+
+    #[test]
+    fn test_output_artifact_split_by_volume() {
+        let name = "split_volume_output";
+        let mut output = output_options(name, "json", "./tmp", false);
+        output.split_by_volume = Some(true);
+        let _ = remove_dir_all(format!("./tmp/{name}"));
+
+        let data = json!([
+            {"filename": "first.pf", "volume_serial": ["D49D126F"]},
+            {"filename": "second.pf", "volume_serial": ["8A1B2C3D"]},
+            {"filename": "third.pf", "volume_serial": ["D49D126F"]},
+        ]);
+        let start_time = time::time_now();
+        output_artifact(&data, "prefetch", &mut output, &start_time, &false).unwrap();
+
+        let mut volumes = Vec::new();
+        for file in list_files(&format!("./tmp/{name}")).unwrap() {
+            if !file.ends_with(".json") {
+                continue;
+            }
+            let value: serde_json::Value =
+                serde_json::from_slice(&read_file(&file).unwrap()).unwrap();
+            let filenames: Vec<String> = value["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|entry| entry["filename"].as_str().unwrap().to_string())
+                .collect();
+            volumes.push((
+                value["metadata"]["artifact_name"]
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+                filenames,
+            ));
+        }
+        volumes.sort();
+
+        assert_eq!(volumes.len(), 2);
+        assert_eq!(volumes[0].0, "prefetch_8A1B2C3D");
+        assert_eq!(volumes[0].1, vec!["second.pf"]);
+        assert_eq!(volumes[1].0, "prefetch_D49D126F");
+        assert_eq!(volumes[1].1, vec!["first.pf", "third.pf"]);
+    }
+
+    #[test]
+    fn test_local_output_lock() {
+        let mut output = output_options("lock_test", "jsonl", "./tmp", false);
+        let guard = local_output_lock(&output);
+        assert!(guard.is_some());
+        drop(guard);
+
+        output.output = String::from("gcp");
+        assert!(local_output_lock(&output).is_none());
+    }
 }
@@ -0,0 +1,269 @@
+/**
+ * Test support for parsing a directory of sample artifacts, such as a regression corpus
+ * Each file is matched against a list of `SampleParser`s by signature or file name. The first parser that matches is used
+ *
+ * New artifacts can be supported by adding a `SampleParser` to `sample_parsers`
+ */
+use super::error::SampleError;
+use crate::{
+    artifacts::os::windows::{
+        bits::parser::provider_bits_path, prefetch::pf::parse_prefetch,
+        shimdb::parser::custom_shimdb_path,
+    },
+    filesystem::{
+        files::{file_extension, read_file},
+        provider::LiveFileProvider,
+        walk::{walk_directory, WalkOptions},
+    },
+};
+use log::error;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Detect and parse a single artifact type
+pub(crate) struct SampleParser {
+    /// Name of the artifact
+    pub(crate) name: &'static str,
+    /// Check if the file at `path` with contents `data` is this artifact
+    pub(crate) detect: fn(path: &str, data: &[u8]) -> bool,
+    /// Parse the file at `path` with contents `data`
+    pub(crate) parse: fn(path: &str, data: &[u8]) -> Result<Value, SampleError>,
+}
+
+/// Sample artifacts that can be detected
+pub(crate) fn sample_parsers() -> Vec<SampleParser> {
+    vec![
+        SampleParser {
+            name: "prefetch",
+            detect: is_prefetch,
+            parse: sample_prefetch,
+        },
+        SampleParser {
+            name: "bits",
+            detect: is_bits,
+            parse: sample_bits,
+        },
+        SampleParser {
+            name: "shimdb",
+            detect: is_shimdb,
+            parse: sample_shimdb,
+        },
+    ]
+}
+
+/// Parse all sample artifacts in a directory and its subdirectories. Results are sorted by path
+pub(crate) fn parse_sample_dir(dir: &str) -> Vec<(String, Result<Value, SampleError>)> {
+    parse_sample_dir_with(dir, &sample_parsers())
+}
+
+/// Parse all sample artifacts in a directory using the provided parsers
+pub(crate) fn parse_sample_dir_with(
+    dir: &str,
+    parsers: &[SampleParser],
+) -> Vec<(String, Result<Value, SampleError>)> {
+    let options = WalkOptions {
+        max_depth: 32,
+        follow_links: false,
+    };
+    let walk_result = walk_directory(dir, &options, |_| true);
+    let walk = match walk_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Could not walk sample directory {dir}: {err:?}");
+            return Vec::new();
+        }
+    };
+
+    let mut paths: Vec<String> = walk
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.path().display().to_string())
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let result = parse_sample(&path, parsers);
+            (path, result)
+        })
+        .collect()
+}
+
+/// Detect and parse a single sample file
+fn parse_sample(path: &str, parsers: &[SampleParser]) -> Result<Value, SampleError> {
+    let data_result = read_file(path);
+    let data = match data_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Could not read sample {path}: {err:?}");
+            return Err(SampleError::ReadFile);
+        }
+    };
+
+    for parser in parsers {
+        if (parser.detect)(path, &data) {
+            return (parser.parse)(path, &data);
+        }
+    }
+    Err(SampleError::Unknown)
+}
+
+/// Serialize parsed sample data
+fn sample_value<T: Serialize>(data: &T) -> Result<Value, SampleError> {
+    let value_result = serde_json::to_value(data);
+    match value_result {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("[artemis-core] Could not serialize sample: {err:?}");
+            Err(SampleError::Serialize)
+        }
+    }
+}
+
+/// Check if data starts with `signature` at `offset`
+fn has_signature(data: &[u8], offset: usize, signature: &[u8]) -> bool {
+    data.get(offset..offset + signature.len()) == Some(signature)
+}
+
+/// Prefetch files have a `SCCA` signature or are compressed with a `MAM` signature
+fn is_prefetch(_path: &str, data: &[u8]) -> bool {
+    has_signature(data, 4, b"SCCA") || has_signature(data, 0, b"MAM\x04")
+}
+
+fn sample_prefetch(path: &str, data: &[u8]) -> Result<Value, SampleError> {
+    let prefetch_result = parse_prefetch(data, path, false);
+    match prefetch_result {
+        Ok(result) => sample_value(&result),
+        Err(err) => {
+            error!("[artemis-core] Could not parse prefetch sample {path}: {err:?}");
+            Err(SampleError::Parse)
+        }
+    }
+}
+
+/// BITS data is either an ESE database (`qmgr.db`) or a legacy `qmgr0.dat` or `qmgr1.dat` file
+fn is_bits(path: &str, data: &[u8]) -> bool {
+    let ese_sig = [0xef, 0xcd, 0xab, 0x89];
+    if file_extension(path) == "db" && has_signature(data, 4, &ese_sig) {
+        return true;
+    }
+
+    let name = path
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    name.starts_with("qmgr") && name.ends_with(".dat")
+}
+
+fn sample_bits(path: &str, _data: &[u8]) -> Result<Value, SampleError> {
+    let bits_result = provider_bits_path(path, false, &LiveFileProvider { raw_ntfs: false }, false);
+    match bits_result {
+        Ok(result) => sample_value(&result),
+        Err(err) => {
+            error!("[artemis-core] Could not parse BITS sample {path}: {err:?}");
+            Err(SampleError::Parse)
+        }
+    }
+}
+
+/// Shim databases have a `sdbf` signature
+fn is_shimdb(_path: &str, data: &[u8]) -> bool {
+    has_signature(data, 8, b"sdbf")
+}
+
+fn sample_shimdb(path: &str, _data: &[u8]) -> Result<Value, SampleError> {
+    let shimdb_result = custom_shimdb_path(path);
+    match shimdb_result {
+        Ok(result) => sample_value(&result),
+        Err(err) => {
+            error!("[artemis-core] Could not parse shimdb sample {path}: {err:?}");
+            Err(SampleError::Parse)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        is_bits, is_prefetch, is_shimdb, parse_sample_dir, parse_sample_dir_with, SampleParser,
+    };
+    use crate::artifacts::error::SampleError;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_sample_dir() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win81");
+
+        let results = parse_sample_dir(&test_location.display().to_string());
+        assert!(results.len() > 100);
+        for (path, result) in results {
+            let value = result.unwrap();
+            assert!(path.ends_with(".pf"));
+            assert!(value["run_count"].is_number());
+        }
+    }
+
+    #[test]
+    fn test_parse_sample_dir_unknown() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/shimdb/win10");
+
+        let results = parse_sample_dir(&test_location.display().to_string());
+        assert_eq!(results.len(), 4);
+        assert!(results[0].0.ends_with("database.raw"));
+        assert!(matches!(results[0].1, Err(SampleError::Unknown)));
+
+        assert!(parse_sample_dir("/does/not/exist").is_empty());
+    }
+
+    #[test]
+    fn test_parse_sample_dir_with() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/shimdb/win10");
+
+        let parsers = [SampleParser {
+            name: "raw",
+            detect: |path, _data| path.ends_with(".raw"),
+            parse: |_path, data| Ok(json!({ "size": data.len() })),
+        }];
+        let results = parse_sample_dir_with(&test_location.display().to_string(), &parsers);
+        assert_eq!(results.len(), 4);
+        assert!(results[0].1.as_ref().unwrap()["size"].is_number());
+        assert!(matches!(results[3].1, Err(SampleError::Unknown)));
+        assert_eq!(parsers[0].name, "raw");
+    }
+
+    #[test]
+    fn test_detect_samples() {
+        assert!(is_prefetch("test.pf", b"\x1e\0\0\0SCCA"));
+        assert!(is_prefetch("test.pf", b"MAM\x04\0\0\0\0"));
+        assert!(!is_prefetch("test.pf", b"SCCA"));
+
+        assert!(is_bits("C:\\qmgr0.dat", &[]));
+        assert!(is_bits("./qmgr.db", &[0, 0, 0, 0, 0xef, 0xcd, 0xab, 0x89]));
+        assert!(!is_bits("./other.db", &[0, 0, 0, 0]));
+
+        assert!(is_shimdb("test", b"\x02\0\0\0\x01\0\0\0sdbf"));
+        assert!(!is_shimdb("test", b"sdbf"));
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_parse_sample_dir_shimdb() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/shimdb");
+
+        let results = parse_sample_dir(&test_location.display().to_string());
+        let shimdb: Vec<_> = results
+            .iter()
+            .filter(|(path, _)| path.ends_with(".sdb"))
+            .collect();
+        assert_eq!(shimdb.len(), 3);
+        for (_, result) in shimdb {
+            assert!(result.is_ok());
+        }
+    }
+}
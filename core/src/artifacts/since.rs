@@ -0,0 +1,280 @@
+use serde_json::Value;
+
+/// Field added to records with an implausible primary timestamp
+pub(crate) const TIME_ANOMALY_FIELD: &str = "time_anomaly";
+/// Default floor for plausible timestamps. 2000-01-01 00:00:00 UTC
+pub(crate) const TIME_ANOMALY_FLOOR: i64 = 946684800;
+
+/**
+ * Get the primary timestamp field used by the `since_epoch` filter for an artifact
+ * Returns `None` if the artifact has no comparable timestamp
+ */
+pub(crate) fn since_field(output_name: &str) -> Option<&'static str> {
+    let field = match output_name {
+        "prefetch" => "last_run_time",
+        "bits" => "created",
+        "amcache" => "last_modified",
+        "shimcache" => "last_modified",
+        "userassist" => "last_execution",
+        "shortcuts" => "created",
+        "usnjrnl" => "update_time",
+        "recyclebin" => "deleted",
+        "shellbags" => "reg_modified",
+        _ => return None,
+    };
+    Some(field)
+}
+
+/**
+ * Remove records whose primary timestamp is before `since` (inclusive boundary)
+ * Records without a comparable timestamp are kept. Artifacts without a mapped timestamp are returned unchanged
+ * Arrays nested one level in an object (ex: `bits` and `carved_jobs`) are also filtered
+ */
+pub(crate) fn filter_since(serde_data: &Value, output_name: &str, since: &i64) -> Value {
+    let field = match since_field(output_name) {
+        Some(result) => result,
+        None => return serde_data.clone(),
+    };
+
+    match serde_data {
+        Value::Array(records) => Value::Array(filter_records(records, field, since)),
+        Value::Object(entries) => {
+            let mut filtered = entries.clone();
+            for value in filtered.values_mut() {
+                if let Value::Array(records) = value {
+                    *records = filter_records(records, field, since);
+                }
+            }
+            Value::Object(filtered)
+        }
+        _ => serde_data.clone(),
+    }
+}
+
+/// Keep records at or after `since`. Records missing the field pass through
+fn filter_records(records: &[Value], field: &str, since: &i64) -> Vec<Value> {
+    records
+        .iter()
+        .filter(|record| match record.get(field).and_then(Value::as_i64) {
+            Some(timestamp) => timestamp >= *since,
+            None => true,
+        })
+        .cloned()
+        .collect()
+}
+
+/**
+ * Sort records by their primary timestamp (ascending). Records without a comparable timestamp are placed last
+ * The sort is stable. Artifacts without a mapped timestamp are returned unchanged
+ * Arrays nested one level in an object are sorted separately
+ */
+pub(crate) fn sort_by_time(serde_data: &Value, output_name: &str) -> Value {
+    let field = match since_field(output_name) {
+        Some(result) => result,
+        None => return serde_data.clone(),
+    };
+
+    let mut sorted = serde_data.clone();
+    match &mut sorted {
+        Value::Array(records) => sort_records(records, field),
+        Value::Object(entries) => {
+            for value in entries.values_mut() {
+                if let Value::Array(records) = value {
+                    sort_records(records, field);
+                }
+            }
+        }
+        _ => {}
+    }
+    sorted
+}
+
+/// Sort records by `field`. Records missing the field are kept at the end in their original order
+fn sort_records(records: &mut [Value], field: &str) {
+    records.sort_by_key(|record| {
+        let timestamp = record.get(field).and_then(Value::as_i64);
+        (timestamp.is_none(), timestamp)
+    });
+}
+
+/**
+ * Flag records whose primary timestamp is after `now` (`future`) or before `floor` (`before_floor`) under `time_anomaly`
+ * Timestamps of 0 are unset and are not flagged. Artifacts without a mapped timestamp are returned unchanged
+ * Arrays nested one level in an object are also flagged
+ */
+pub(crate) fn flag_time_anomalies(
+    serde_data: &Value,
+    output_name: &str,
+    floor: &i64,
+    now: &i64,
+) -> Value {
+    let field = match since_field(output_name) {
+        Some(result) => result,
+        None => return serde_data.clone(),
+    };
+
+    let mut flagged = serde_data.clone();
+    match &mut flagged {
+        Value::Array(records) => flag_records(records, field, floor, now),
+        Value::Object(entries) => {
+            for value in entries.values_mut() {
+                if let Value::Array(records) = value {
+                    flag_records(records, field, floor, now);
+                }
+            }
+        }
+        _ => {}
+    }
+    flagged
+}
+
+/// Add the `time_anomaly` marker to records with a timestamp outside of `floor` and `now`
+fn flag_records(records: &mut [Value], field: &str, floor: &i64, now: &i64) {
+    for record in records.iter_mut() {
+        let timestamp = match record.get(field).and_then(Value::as_i64) {
+            Some(result) if result != 0 => result,
+            _ => continue,
+        };
+        let anomaly = if timestamp > *now {
+            "future"
+        } else if timestamp < *floor {
+            "before_floor"
+        } else {
+            continue;
+        };
+        if let Value::Object(entries) = record {
+            entries.insert(
+                TIME_ANOMALY_FIELD.to_string(),
+                Value::String(anomaly.to_string()),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filter_since, flag_time_anomalies, since_field, sort_by_time, TIME_ANOMALY_FLOOR};
+    use serde_json::json;
+
+    #[test]
+    fn test_since_field() {
+        assert_eq!(since_field("prefetch").unwrap(), "last_run_time");
+        assert_eq!(since_field("bits").unwrap(), "created");
+        assert!(since_field("processes").is_none());
+    }
+
+    #[test]
+    fn test_filter_since_inclusive() {
+        let data = json!([
+            {"path": "a", "last_run_time": 99},
+            {"path": "b", "last_run_time": 100},
+            {"path": "c", "last_run_time": 101},
+        ]);
+        let result = filter_since(&data, "prefetch", &100);
+        let records = result.as_array().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["path"], "b");
+        assert_eq!(records[1]["path"], "c");
+    }
+
+    #[test]
+    fn test_filter_since_missing_field() {
+        let data = json!([{"path": "a"}, {"path": "b", "last_run_time": "bad"}]);
+        let result = filter_since(&data, "prefetch", &100);
+        assert_eq!(result.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_filter_since_nested() {
+        let data = json!({
+            "bits": [{"created": 10}, {"created": 20}],
+            "carved_jobs": [{"created": 5}],
+            "carved_files": [{"url": "test"}],
+        });
+        let result = filter_since(&data, "bits", &20);
+        assert_eq!(result["bits"].as_array().unwrap().len(), 1);
+        assert_eq!(result["carved_jobs"].as_array().unwrap().len(), 0);
+        assert_eq!(result["carved_files"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_filter_since_unmapped() {
+        let data = json!([{"start_time": 1}]);
+        let result = filter_since(&data, "processes", &100);
+        assert_eq!(result, data);
+    }
+
+    #[test]
+    fn test_sort_by_time() {
+        let data = json!([
+            {"path": "a", "last_run_time": 30},
+            {"path": "b"},
+            {"path": "c", "last_run_time": 10},
+            {"path": "d", "last_run_time": 20},
+        ]);
+        let result = sort_by_time(&data, "prefetch");
+        let paths: Vec<&str> = result
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|record| record["path"].as_str().unwrap())
+            .collect();
+        assert_eq!(paths, vec!["c", "d", "a", "b"]);
+    }
+
+    #[test]
+    fn test_sort_by_time_nested() {
+        let data = json!({
+            "bits": [{"created": 20}, {"created": 10}],
+            "carved_jobs": [{"created": 5}, {"created": 1}],
+        });
+        let result = sort_by_time(&data, "bits");
+        assert_eq!(result["bits"][0]["created"], 10);
+        assert_eq!(result["carved_jobs"][0]["created"], 1);
+        assert_eq!(
+            sort_by_time(&json!([{"start_time": 2}, {"start_time": 1}]), "processes")[0]
+                ["start_time"],
+            2
+        );
+    }
+
+    #[test]
+    fn test_flag_time_anomalies_future() {
+        let now = 1700000000;
+        let data = json!([
+            {"path": "a", "last_run_time": 1600000000},
+            {"path": "b", "last_run_time": 1700000001},
+            {"path": "c", "last_run_time": 4102444800i64},
+        ]);
+        let result = flag_time_anomalies(&data, "prefetch", &TIME_ANOMALY_FLOOR, &now);
+        assert!(result[0].get("time_anomaly").is_none());
+        assert_eq!(result[1]["time_anomaly"], "future");
+        assert_eq!(result[2]["time_anomaly"], "future");
+    }
+
+    #[test]
+    fn test_flag_time_anomalies_before_floor() {
+        let now = 1700000000;
+        let data = json!({
+            "bits": [{"created": 946684799}, {"created": 946684800}, {"created": 0}],
+            "carved_jobs": [{"created": 315532800}],
+            "carved_files": [{"url": "test"}],
+        });
+        let result = flag_time_anomalies(&data, "bits", &TIME_ANOMALY_FLOOR, &now);
+        assert_eq!(result["bits"][0]["time_anomaly"], "before_floor");
+        assert!(result["bits"][1].get("time_anomaly").is_none());
+        assert!(result["bits"][2].get("time_anomaly").is_none());
+        assert_eq!(result["carved_jobs"][0]["time_anomaly"], "before_floor");
+        assert!(result["carved_files"][0].get("time_anomaly").is_none());
+
+        let result = flag_time_anomalies(&data, "bits", &1000000000, &now);
+        assert_eq!(result["bits"][1]["time_anomaly"], "before_floor");
+    }
+
+    #[test]
+    fn test_flag_time_anomalies_unmapped() {
+        let data = json!([{"start_time": 4102444800i64}]);
+        let result = flag_time_anomalies(&data, "processes", &TIME_ANOMALY_FLOOR, &1700000000);
+        assert_eq!(result, data);
+    }
+}
@@ -0,0 +1,120 @@
+/**
+ * Run artifact parsers with a time limit so a single malformed artifact cannot stall a collection
+ * Rust threads cannot be killed. When the limit is reached the parser keeps running on its own thread until it finishes,
+ * but it is marked as cancelled and any output it tries to write afterwards is discarded
+ */
+use log::error;
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+use tokio::{runtime::Builder, sync::oneshot, time::timeout};
+
+thread_local! {
+    /// Set on threads running a parser with a time limit
+    static CANCELLED: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+/// Check if the parser running on the current thread reached its time limit
+pub(crate) fn is_cancelled() -> bool {
+    CANCELLED.with(|cancelled| match cancelled.borrow().as_ref() {
+        Some(flag) => flag.load(Ordering::SeqCst),
+        None => false,
+    })
+}
+
+/**
+ * Run a parser on a worker thread and wait at most `seconds` for it to finish
+ * Returns `None` if the parser did not finish in time
+ */
+pub(crate) fn run_with_timeout<T, F>(seconds: u64, parser: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let runtime_result = Builder::new_current_thread().enable_time().build();
+    let runtime = match runtime_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Could not create timeout runtime: {err:?}");
+            return None;
+        }
+    };
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let worker_cancelled = cancelled.clone();
+    let (sender, receiver) = oneshot::channel();
+    let worker_result = thread::Builder::new().spawn(move || {
+        CANCELLED.with(|cancelled| *cancelled.borrow_mut() = Some(worker_cancelled));
+        let _ = sender.send(parser());
+    });
+    if let Err(err) = worker_result {
+        error!("[artemis-core] Could not start parser thread: {err:?}");
+        return None;
+    }
+
+    let result = runtime.block_on(async { timeout(Duration::from_secs(seconds), receiver).await });
+    match result {
+        Ok(Ok(value)) => Some(value),
+        Ok(Err(_err)) => {
+            error!("[artemis-core] Parser thread exited without a result");
+            None
+        }
+        Err(_err) => {
+            cancelled.store(true, Ordering::SeqCst);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_cancelled, run_with_timeout};
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread::sleep,
+        time::{Duration, Instant},
+    };
+
+    #[test]
+    fn test_run_with_timeout() {
+        let result = run_with_timeout(5, || {
+            assert!(!is_cancelled());
+            10
+        });
+        assert_eq!(result.unwrap(), 10);
+    }
+
+    #[test]
+    fn test_run_with_timeout_slow_parser() {
+        let discarded = Arc::new(AtomicBool::new(false));
+        let parser_discarded = discarded.clone();
+
+        let start = Instant::now();
+        let result = run_with_timeout(1, move || {
+            sleep(Duration::from_secs(3));
+            // A real parser would skip writing output here
+            parser_discarded.store(is_cancelled(), Ordering::SeqCst);
+        });
+        assert!(result.is_none());
+        assert!(start.elapsed() < Duration::from_secs(3));
+
+        sleep(Duration::from_secs(3));
+        assert!(discarded.load(Ordering::SeqCst));
+        assert!(!is_cancelled());
+    }
+
+    #[test]
+    fn test_run_with_timeout_panic() {
+        let result: Option<u8> = run_with_timeout(5, || panic!("bad artifact"));
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,129 @@
+use serde_json::Value;
+
+/// Volume key for records without a source volume
+const UNKNOWN_VOLUME: &str = "unknown";
+
+/**
+ * Get the field used to key an artifact's records by their source volume
+ * Records collected from multiple drives are tagged with `source_drive`
+ */
+pub(crate) fn volume_field(output_name: &str) -> &'static str {
+    match output_name {
+        "prefetch" => "volume_serial",
+        _ => "source_drive",
+    }
+}
+
+/**
+ * Split records into groups keyed by their source volume. Groups are returned in the order each volume is first seen
+ * Returns `None` if the data is not an array of records or no record has a source volume
+ */
+pub(crate) fn split_by_volume(
+    serde_data: &Value,
+    output_name: &str,
+) -> Option<Vec<(String, Value)>> {
+    let records = serde_data.as_array()?;
+    let field = volume_field(output_name);
+
+    let mut groups: Vec<(String, Vec<Value>)> = Vec::new();
+    let mut found = false;
+    for record in records {
+        let volume = match record.get(field).and_then(record_volume) {
+            Some(result) => {
+                found = true;
+                result
+            }
+            None => UNKNOWN_VOLUME.to_string(),
+        };
+
+        match groups.iter_mut().find(|(key, _)| key == &volume) {
+            Some((_, entries)) => entries.push(record.clone()),
+            None => groups.push((volume, vec![record.clone()])),
+        }
+    }
+    if !found {
+        return None;
+    }
+
+    Some(
+        groups
+            .into_iter()
+            .map(|(volume, entries)| (volume, Value::Array(entries)))
+            .collect(),
+    )
+}
+
+/// Get the volume key from a field value. Prefetch files list the executable volume first
+fn record_volume(value: &Value) -> Option<String> {
+    let volume = match value {
+        Value::String(result) => result.as_str(),
+        Value::Array(values) => values.first()?.as_str()?,
+        _ => return None,
+    };
+    if volume.is_empty() {
+        return None;
+    }
+
+    // The volume is used in the output name. Only keep characters that are safe for filenames
+    let key = volume
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{record_volume, split_by_volume, volume_field};
+    use serde_json::json;
+
+    #[test]
+    fn test_volume_field() {
+        assert_eq!(volume_field("prefetch"), "volume_serial");
+        assert_eq!(volume_field("usnjrnl"), "source_drive");
+    }
+
+    #[test]
+    fn test_split_by_volume() {
+        let data = json!([
+            {"filename": "a.pf", "volume_serial": ["D49D126F"]},
+            {"filename": "b.pf", "volume_serial": ["8A1B2C3D", "D49D126F"]},
+            {"filename": "c.pf", "volume_serial": ["D49D126F"]},
+            {"filename": "d.pf", "volume_serial": []},
+        ]);
+        let groups = split_by_volume(&data, "prefetch").unwrap();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].0, "D49D126F");
+        assert_eq!(groups[0].1.as_array().unwrap().len(), 2);
+        assert_eq!(groups[0].1[1]["filename"], "c.pf");
+        assert_eq!(groups[1].0, "8A1B2C3D");
+        assert_eq!(groups[1].1[0]["filename"], "b.pf");
+        assert_eq!(groups[2].0, "unknown");
+        assert_eq!(groups[2].1[0]["filename"], "d.pf");
+    }
+
+    #[test]
+    fn test_split_by_volume_drive() {
+        let data = json!([
+            {"name": "a", "source_drive": "C"},
+            {"name": "b", "source_drive": "D"},
+        ]);
+        let groups = split_by_volume(&data, "usnjrnl").unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[1].0, "D");
+    }
+
+    #[test]
+    fn test_split_by_volume_none() {
+        let data = json!([{"name": "a"}, {"name": "b"}]);
+        assert!(split_by_volume(&data, "usnjrnl").is_none());
+        assert!(split_by_volume(&json!({"bits": []}), "bits").is_none());
+    }
+
+    #[test]
+    fn test_record_volume() {
+        assert_eq!(record_volume(&json!("\\\\?\\C:")).unwrap(), "____C_");
+        assert!(record_volume(&json!("")).is_none());
+        assert!(record_volume(&json!(10)).is_none());
+    }
+}
@@ -1,14 +1,18 @@
-use crate::artifacts::collection::collect;
+use crate::artifacts::{collection::collect, error::CollectionError};
 use crate::{
     error::TomlError,
     filesystem::files::{read_file, read_text_file},
-    runtime::deno::raw_script,
     structs::toml::ArtemisToml,
-    utils::logging::create_log_file,
+    utils::{
+        logging::create_log_file,
+        output::{ndjson, resolve_output_dir},
+    },
 };
+use common::server::jobs::CollectionEvent;
 use log::{error, info, LevelFilter};
 use serde_json::Value;
 use simplelog::{Config, SimpleLogger, WriteLogger};
+use std::{io::Read, sync::mpsc::SyncSender};
 
 /// Parse a TOML file at provided path
 pub fn parse_toml_file(path: &str) -> Result<(), TomlError> {
@@ -45,6 +49,26 @@ pub fn parse_toml_data(data: &[u8]) -> Result<(), TomlError> {
     Ok(())
 }
 
+/// Parse a TOML string and run the collection. Uses the same validation as a TOML file
+pub fn parse_toml_from_str(data: &str) -> Result<(), TomlError> {
+    parse_toml_data(data.as_bytes())
+}
+
+/// Read and validate a TOML collection from a reader. Ex: standard input
+pub fn read_config_from_reader<R: Read>(reader: &mut R) -> Result<ArtemisToml, TomlError> {
+    let mut buffer = Vec::new();
+    if let Err(err) = reader.read_to_end(&mut buffer) {
+        error!("[artemis-core] Could not read TOML data: {err:?}");
+        return Err(TomlError::FailedToReadFile);
+    }
+
+    let toml_results = ArtemisToml::parse_artemis_toml(&buffer);
+    match toml_results {
+        Ok(results) => Ok(results),
+        Err(_) => Err(TomlError::BadToml),
+    }
+}
+
 /// Execute a JavaScript file at provided path
 pub fn parse_js_file(path: &str) -> Result<Value, TomlError> {
     let _ = SimpleLogger::init(LevelFilter::Warn, Config::default());
@@ -56,7 +80,13 @@ pub fn parse_js_file(path: &str) -> Result<Value, TomlError> {
         }
     };
 
-    let script_result = raw_script(&script);
+    run_js(&script)
+}
+
+/// Execute JavaScript if artemis was built with the `scripting` feature
+#[cfg(feature = "scripting")]
+fn run_js(script: &str) -> Result<Value, TomlError> {
+    let script_result = crate::runtime::deno::raw_script(script);
     if script_result.is_err() {
         error!("[runtime] Failed to execute js file");
         return Err(TomlError::BadJs);
@@ -65,15 +95,27 @@ pub fn parse_js_file(path: &str) -> Result<Value, TomlError> {
     Ok(script_result.unwrap_or_default())
 }
 
+#[cfg(not(feature = "scripting"))]
+fn run_js(_script: &str) -> Result<Value, TomlError> {
+    error!("[runtime] JavaScript requires artemis to be built with the scripting feature");
+    Err(TomlError::BadJs)
+}
+
 /// Based on target system collect data based on TOML config
 pub fn artemis_collection(collection: &mut ArtemisToml) -> Result<(), TomlError> {
+    let output_dir = resolve_output_dir(&mut collection.output);
     if let Ok((log_file, level)) = create_log_file(&collection.output) {
         let _ = WriteLogger::init(level, Config::default(), log_file);
     }
+    info!("[artemis-core] Output directory: {output_dir}");
 
     let result = collect(collection);
     match result {
         Ok(_) => info!("[artemis-core] Core parsed TOML data"),
+        Err(CollectionError::OutputDirectory) => {
+            error!("[artemis-core] Core could not write to the output directory");
+            return Err(TomlError::OutputNotWritable);
+        }
         Err(err) => {
             error!("[artemis-core] Core failed to parse collection: {err:?}");
             return Err(TomlError::BadToml);
@@ -82,15 +124,36 @@ pub fn artemis_collection(collection: &mut ArtemisToml) -> Result<(), TomlError>
     Ok(())
 }
 
+/**
+ * Based on target system collect data based on TOML config and send progress events to the provided channel  
+ * Events are dropped if the channel is full so a slow receiver never stalls the collection
+ */
+pub fn artemis_collection_events(
+    collection: &mut ArtemisToml,
+    events: SyncSender<CollectionEvent>,
+) -> Result<(), TomlError> {
+    collection.output.events = Some(events);
+    artemis_collection(collection)
+}
+
+/// Read back NDJSON (jsonl) output as an iterator of records. Gzip (`.gz`) and zstd (`.zst`) files are decompressed while reading
+pub fn read_ndjson(path: &str) -> impl Iterator<Item = Result<Value, TomlError>> {
+    ndjson::read_ndjson(path).map(|record| record.map_err(|_| TomlError::BadOutput))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{parse_js_file, parse_toml_data, parse_toml_file};
+    use super::{
+        artemis_collection_events, parse_js_file, parse_toml_data, parse_toml_file,
+        parse_toml_from_str, read_config_from_reader, read_ndjson,
+    };
     use crate::{
         core::{artemis_collection, ArtemisToml},
         filesystem::files::read_file,
         structs::toml::Output,
     };
-    use std::path::PathBuf;
+    use common::server::jobs::CollectionEvent;
+    use std::{io::Cursor, path::PathBuf, sync::mpsc::sync_channel};
 
     #[test]
     #[cfg(target_os = "macos")]
@@ -150,6 +213,37 @@ mod tests {
     }
 
     #[test]
+    fn test_read_config_from_reader() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/linux/processes.toml");
+
+        let buffer = read_file(&test_location.display().to_string()).unwrap();
+        let from_file = ArtemisToml::parse_artemis_toml(&buffer).unwrap();
+
+        let text = String::from_utf8(buffer).unwrap();
+        let from_reader = read_config_from_reader(&mut Cursor::new(text.as_bytes())).unwrap();
+        assert_eq!(format!("{from_reader:?}"), format!("{from_file:?}"));
+        assert_eq!(from_reader.artifacts[0].artifact_name, "processes");
+    }
+
+    #[test]
+    fn test_read_config_from_reader_bad_toml() {
+        let result = read_config_from_reader(&mut Cursor::new("system = ".as_bytes()));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_parse_toml_from_str() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/linux/processes.toml");
+
+        let buffer = read_file(&test_location.display().to_string()).unwrap();
+        parse_toml_from_str(&String::from_utf8(buffer).unwrap()).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "scripting")]
     fn test_parse_js_file() {
         let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_location.push("tests/test_data/deno_scripts/vanilla.js");
@@ -178,9 +272,39 @@ mod tests {
                 filter_name: Some(String::new()),
                 filter_script: Some(String::new()),
                 logging: Some(String::new()),
+                ..Default::default()
             },
             artifacts: Vec::new(),
+            workers: None,
         };
         artemis_collection(&mut collection).unwrap();
     }
+
+    #[test]
+    fn test_artemis_collection_events() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/linux/quick.toml");
+
+        let buffer = read_file(&test_location.display().to_string()).unwrap();
+        let mut collection = ArtemisToml::parse_artemis_toml(&buffer).unwrap();
+
+        let (sender, receiver) = sync_channel(1000);
+        artemis_collection_events(&mut collection, sender).unwrap();
+
+        let events: Vec<CollectionEvent> = receiver.try_iter().collect();
+        assert!(!events.is_empty());
+        assert_eq!(
+            events[0],
+            CollectionEvent::ArtifactStarted {
+                name: collection.artifacts[0].artifact_name.clone()
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_ndjson() {
+        let results: Vec<_> = read_ndjson("./tmp/missing_output.jsonl.gz").collect();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
 }
@@ -6,6 +6,8 @@ pub enum TomlError {
     FailedToReadFile,
     BadToml,
     BadJs,
+    OutputNotWritable,
+    BadOutput,
 }
 
 impl std::error::Error for TomlError {}
@@ -17,6 +19,8 @@ impl fmt::Display for TomlError {
             TomlError::FailedToReadFile => write!(f, "Failed to read TOML data"),
             TomlError::BadToml => write!(f, "Failed to parse TOML data"),
             TomlError::BadJs => write!(f, "Failed run JavaScript code"),
+            TomlError::OutputNotWritable => write!(f, "Output directory is not writable"),
+            TomlError::BadOutput => write!(f, "Failed to read output data"),
         }
     }
 }
@@ -0,0 +1,88 @@
+/**
+ * Track paths that could not be read due to permission errors
+ * Parsers usually skip files they cannot read. The paths are recorded so the collection status can show exactly what was inaccessible
+ */
+use std::{
+    cell::RefCell,
+    io::{Error, ErrorKind},
+};
+
+thread_local! {
+    /// Paths denied to the parser running on the current thread
+    static DENIED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Check if an IO error was caused by missing permissions
+pub(crate) fn is_access_denied(err: &Error) -> bool {
+    err.kind() == ErrorKind::PermissionDenied
+}
+
+/// Record a path that could not be accessed
+pub(crate) fn record_access_denied(path: &str) {
+    DENIED.with(|denied| {
+        let mut denied = denied.borrow_mut();
+        if !denied.iter().any(|entry| entry == path) {
+            denied.push(path.to_string());
+        }
+    });
+}
+
+/// Get and clear the paths denied on the current thread
+pub(crate) fn take_access_denied() -> Vec<String> {
+    DENIED.with(|denied| denied.take())
+}
+
+/// Read a file by parsing the NTFS filesystem after normal access was denied. Only supported on Windows
+#[cfg(target_os = "windows")]
+pub(crate) fn raw_fallback(path: &str) -> Option<Vec<u8>> {
+    use super::ntfs::raw_files::raw_read_file;
+    use log::warn;
+
+    match raw_read_file(path) {
+        Ok(result) => Some(result),
+        Err(err) => {
+            warn!("[artemis-core] Raw read fallback failed for {path}: {err:?}");
+            None
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn raw_fallback(_path: &str) -> Option<Vec<u8>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_access_denied, raw_fallback, record_access_denied, take_access_denied};
+    use std::{
+        io::{Error, ErrorKind},
+        thread,
+    };
+
+    #[test]
+    fn test_is_access_denied() {
+        assert!(is_access_denied(&Error::from(ErrorKind::PermissionDenied)));
+        assert!(!is_access_denied(&Error::from(ErrorKind::NotFound)));
+    }
+
+    #[test]
+    fn test_record_access_denied() {
+        record_access_denied("/root/secret");
+        record_access_denied("/root/secret");
+        record_access_denied("/etc/shadow");
+
+        // Other threads track their own paths
+        let other = thread::spawn(take_access_denied).join().unwrap();
+        assert!(other.is_empty());
+
+        assert_eq!(take_access_denied(), vec!["/root/secret", "/etc/shadow"]);
+        assert!(take_access_denied().is_empty());
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_raw_fallback() {
+        assert!(raw_fallback("/etc/shadow").is_none());
+    }
+}
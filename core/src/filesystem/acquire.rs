@@ -244,7 +244,8 @@ mod tests {
             output: output.to_string(),
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
-            logging: Some(String::new())
+            logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
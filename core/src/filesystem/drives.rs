@@ -0,0 +1,68 @@
+use sysinfo::Disks;
+
+/**
+ * Get the drive letters of mounted NTFS volumes on Windows systems
+ * Removable drives are skipped unless `include_removable` is set. Network drives are never returned
+ * Returns an empty list on non-Windows systems
+ */
+pub(crate) fn get_drives(include_removable: bool) -> Vec<char> {
+    let disks = Disks::new_with_refreshed_list();
+
+    let mut drives = Vec::new();
+    for disk in &disks {
+        if disk.is_removable() && !include_removable {
+            continue;
+        }
+        if disk.file_system().to_str().unwrap_or_default() != "NTFS" {
+            continue;
+        }
+
+        let mount = disk.mount_point().display().to_string();
+        if let Some(drive) = drive_letter(&mount) {
+            if !drives.contains(&drive) {
+                drives.push(drive);
+            }
+        }
+    }
+    drives.sort_unstable();
+    drives
+}
+
+/// Get the drive letter from a mount point such as `C:\`
+fn drive_letter(mount: &str) -> Option<char> {
+    let mut chars = mount.chars();
+    let letter = chars.next()?;
+    if !letter.is_ascii_alphabetic() || chars.next()? != ':' {
+        return None;
+    }
+    Some(letter.to_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{drive_letter, get_drives};
+
+    #[test]
+    fn test_drive_letter() {
+        assert_eq!(drive_letter("C:\\").unwrap(), 'C');
+        assert_eq!(drive_letter("d:").unwrap(), 'D');
+        assert!(drive_letter("/").is_none());
+        assert!(drive_letter("/mnt/c").is_none());
+        assert!(drive_letter("").is_none());
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_get_drives() {
+        use crate::utils::environment::get_systemdrive;
+
+        let drives = get_drives(false);
+        assert!(drives.contains(&get_systemdrive().unwrap()));
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_get_drives_unix() {
+        assert!(get_drives(true).is_empty());
+    }
+}
@@ -25,6 +25,8 @@ pub(crate) enum FileSystemError {
     AcquireFile,
     UploadSetup,
     FinalUpload,
+    AccessDenied,
+    ReadTimeout,
 }
 
 impl std::error::Error for FileSystemError {}
@@ -57,6 +59,8 @@ impl fmt::Display for FileSystemError {
             FileSystemError::AcquireFile => write!(f, "Could not finish file acquisition"),
             FileSystemError::UploadSetup => write!(f, "Could not setup file upload"),
             FileSystemError::FinalUpload => write!(f, "Could not finish file upload"),
+            FileSystemError::AccessDenied => write!(f, "Access denied"),
+            FileSystemError::ReadTimeout => write!(f, "Read timed out"),
         }
     }
 }
@@ -1,4 +1,10 @@
-use super::{directory::is_directory, error::FileSystemError, metadata::get_metadata};
+use super::{
+    access::{is_access_denied, raw_fallback, record_access_denied},
+    directory::is_directory,
+    error::FileSystemError,
+    metadata::get_metadata,
+    selection::skip_source,
+};
 use log::{error, warn};
 use md5::{Digest, Md5};
 use serde::Deserialize;
@@ -8,7 +14,7 @@ use std::fs::read_to_string;
 use std::io::{BufRead, BufReader, Lines};
 use std::{
     fs::{read, read_dir, File},
-    io::{copy, Read},
+    io::{copy, Read, Seek, SeekFrom},
     path::Path,
 };
 
@@ -37,6 +43,10 @@ pub(crate) fn list_files_directories(path: &str) -> Result<Vec<String>, FileSyst
         Ok(result) => result,
         Err(err) => {
             error!("[artemis-core] Failed to get directory contents: {err:?}");
+            if is_access_denied(&err) {
+                record_access_denied(path);
+                return Err(FileSystemError::AccessDenied);
+            }
             return Err(FileSystemError::ReadDirectory);
         }
     };
@@ -52,6 +62,9 @@ pub(crate) fn list_files_directories(path: &str) -> Result<Vec<String>, FileSyst
         };
 
         let full_path = entry.path().display().to_string();
+        if entry.path().is_file() && skip_source(&full_path) {
+            continue;
+        }
         data.push(full_path);
     }
 
@@ -106,6 +119,10 @@ pub(crate) fn file_reader(path: &str) -> Result<File, FileSystemError> {
         Ok(result) => result,
         Err(err) => {
             error!("[artemis-core] Failed to open file {path}: {err:?}");
+            if is_access_denied(&err) {
+                record_access_denied(path);
+                return Err(FileSystemError::AccessDenied);
+            }
             return Err(FileSystemError::OpenFile);
         }
     };
@@ -122,6 +139,29 @@ pub(crate) fn read_file_custom(path: &str, size: &u64) -> Result<Vec<u8>, FileSy
     file_read(path)
 }
 
+/// Read up to `len` bytes starting at `offset`. Returns fewer bytes if the file ends before `offset + len`
+pub(crate) fn read_file_range(
+    path: &str,
+    offset: u64,
+    len: usize,
+) -> Result<Vec<u8>, FileSystemError> {
+    let mut reader = file_reader(path)?;
+    if let Err(err) = reader.seek(SeekFrom::Start(offset)) {
+        error!("[artemis-core] Failed to seek to offset {offset} in {path}: {err:?}");
+        return Err(FileSystemError::ReadFile);
+    }
+
+    let mut data = Vec::with_capacity(len);
+    let read_result = reader.take(len as u64).read_to_end(&mut data);
+    match read_result {
+        Ok(_) => Ok(data),
+        Err(err) => {
+            error!("[artemis-core] Failed to read range from file {path}: {err:?}");
+            Err(FileSystemError::ReadFile)
+        }
+    }
+}
+
 /// Read a file into memory
 fn file_read(path: &str) -> Result<Vec<u8>, FileSystemError> {
     // Verify provided path is a file
@@ -132,6 +172,15 @@ fn file_read(path: &str) -> Result<Vec<u8>, FileSystemError> {
     let read_result = read(path);
     match read_result {
         Ok(result) => Ok(result),
+        Err(err) if is_access_denied(&err) => {
+            // Locked down files can often still be read by parsing the NTFS filesystem
+            if let Some(result) = raw_fallback(path) {
+                return Ok(result);
+            }
+            error!("[artemis-core] Access denied reading file {path}: {err:?}");
+            record_access_denied(path);
+            Err(FileSystemError::AccessDenied)
+        }
         Err(err) => {
             error!("[artemis-core] Failed to read file {path}: {err:?}");
             Err(FileSystemError::ReadFile)
@@ -151,6 +200,10 @@ fn file_read_text(path: &str) -> Result<String, FileSystemError> {
         Ok(result) => Ok(result),
         Err(err) => {
             error!("[artemis-core] Failed to read text file {path}: {err:?}");
+            if is_access_denied(&err) {
+                record_access_denied(path);
+                return Err(FileSystemError::AccessDenied);
+            }
             Err(FileSystemError::ReadFile)
         }
     }
@@ -336,7 +389,7 @@ mod tests {
     use crate::filesystem::files::{
         file_extension, file_lines, file_read_text, file_reader, file_too_large_custom,
         get_file_size, get_filename, hash_file, hash_file_data, list_files, read_file,
-        read_file_custom, read_text_file, Hashes,
+        read_file_custom, read_file_range, read_text_file, Hashes,
     };
     use std::path::PathBuf;
 
@@ -527,6 +580,45 @@ mod tests {
         assert_eq!(result.len(), 23);
     }
 
+    #[test]
+    fn test_read_file_range() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/system/files/test.txt");
+        let path = test_location.display().to_string();
+
+        let full = read_file(&path).unwrap();
+        let result = read_file_range(&path, 5, 10).unwrap();
+        assert_eq!(result, full[5..15]);
+
+        let result = read_file_range(&path, 20, 10).unwrap();
+        assert_eq!(result, full[20..]);
+        assert!(read_file_range(&path, 100, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_read_file_access_denied() {
+        use crate::filesystem::{access::take_access_denied, error::FileSystemError};
+        use std::{
+            fs::{create_dir_all, set_permissions, write, Permissions},
+            os::unix::fs::PermissionsExt,
+        };
+
+        create_dir_all("./tmp").unwrap();
+        let path = "./tmp/access_denied.txt";
+        write(path, b"secret").unwrap();
+        set_permissions(path, Permissions::from_mode(0o000)).unwrap();
+
+        let result = read_file(path);
+        set_permissions(path, Permissions::from_mode(0o644)).unwrap();
+        // Permissions are not enforced for root
+        if result.is_ok() {
+            return;
+        }
+        assert!(matches!(result, Err(FileSystemError::AccessDenied)));
+        assert_eq!(take_access_denied(), vec![path]);
+    }
+
     #[test]
     fn test_hash_file_data() {
         let test = b"rust is nice";
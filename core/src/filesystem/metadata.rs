@@ -1,4 +1,4 @@
-use crate::filesystem::error::FileSystemError;
+use crate::filesystem::{error::FileSystemError, selection::skip_source};
 use log::error;
 use serde::Serialize;
 use std::fs::symlink_metadata;
@@ -78,6 +78,9 @@ pub(crate) fn glob_paths(glob_pattern: &str) -> Result<Vec<GlobInfo>, FileSystem
     };
 
     for entry in paths.flatten() {
+        if entry.is_file() && skip_source(entry.to_str().unwrap_or_default()) {
+            continue;
+        }
         let glob_info = GlobInfo {
             full_path: entry.to_str().unwrap_or_default().to_string(),
             filename: entry
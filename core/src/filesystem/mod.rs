@@ -1,6 +1,12 @@
+pub(crate) mod access;
 pub(crate) mod acquire;
 pub(crate) mod directory;
+pub(crate) mod drives;
 mod error;
 pub(crate) mod files;
 pub(crate) mod metadata;
 pub(crate) mod ntfs;
+pub(crate) mod provider;
+pub(crate) mod selection;
+pub(crate) mod timeout;
+pub(crate) mod walk;
@@ -2,13 +2,14 @@ use super::{
     attributes::{get_attribute_data, get_filename_attribute},
     compression::check_wofcompressed,
     sector_reader::SectorReader,
-    setup::setup_ntfs_parser,
+    setup::{setup_ntfs_device, setup_ntfs_parser, NtfsParser},
 };
 use crate::{
     artifacts::os::systeminfo::info::get_platform,
     filesystem::{
         error::FileSystemError,
-        files::{read_file_custom, Hashes},
+        files::{read_file_custom, read_file_range, Hashes},
+        timeout::with_read_timeout,
     },
     utils::{
         regex_options::{create_regex, regex_check},
@@ -25,7 +26,7 @@ use sha1::Sha1;
 use sha2::Sha256;
 use std::{
     fs::File,
-    io::{copy, BufReader},
+    io::{copy, BufReader, SeekFrom},
 };
 
 /// Read the whole attribute data. This can be used to read a whole file
@@ -59,12 +60,7 @@ pub(crate) fn raw_reader<'a>(
     ntfs: &'a Ntfs,
     fs: &mut BufReader<SectorReader<File>>,
 ) -> Result<NtfsFile<'a>, FileSystemError> {
-    let min_path_len = 4;
-    if path.len() < min_path_len || !path.contains(':') {
-        return Err(FileSystemError::NotFile);
-    }
-
-    let drive = &path.chars().next().unwrap(); // Only need Drive letter
+    let (volume, _device) = volume_root(path)?;
     let root_dir_result = ntfs.root_directory(fs);
     let root_dir = match root_dir_result {
         Ok(result) => result,
@@ -77,11 +73,11 @@ pub(crate) fn raw_reader<'a>(
     let mut ntfs_options = NtfsOptions {
         start_path: path.to_string(),
         start_path_depth: 0,
-        depth: path.split('\\').count(),
+        depth: path_depth(path, &volume),
         path_regex: create_regex("").unwrap(), // Valid Regex, should never fail
         file_regex: create_regex("").unwrap(), // Valid Regex, should never fail
         filelist: Vec::new(),
-        directory_tracker: vec![format!("{drive}:")],
+        directory_tracker: vec![volume],
     };
 
     // Search and iterate through the NTFS system for the file
@@ -181,13 +177,66 @@ pub(crate) fn raw_read_file(path: &str) -> Result<Vec<u8>, FileSystemError> {
         return read_file_custom(path, &max_size);
     }
 
-    let min_path_len = 4;
-    if path.len() < min_path_len || !path.contains(':') {
-        return Err(FileSystemError::NotFile);
+    let owned_path = path.to_string();
+    with_read_timeout(path, FileSystemError::ReadTimeout, move || {
+        let (mut ntfs_parser, file_ref) = match raw_acquire_file(&owned_path)? {
+            (parser, Some(result)) => (parser, result),
+            (_, None) => return Ok(Vec::new()),
+        };
+        raw_read_by_file_ref(&file_ref, &ntfs_parser.ntfs, &mut ntfs_parser.fs)
+    })
+}
+
+/**
+ * Read up to `len` bytes starting at `offset` from a single file by parsing the NTFS system  
+ * Only the requested range is read from disk. Useful for inspecting headers of large locked files
+ */
+pub(crate) fn raw_read_file_range(
+    path: &str,
+    offset: u64,
+    len: usize,
+) -> Result<Vec<u8>, FileSystemError> {
+    // Raw file access only works on Windows. For all other platforms redirect to normal file access
+    let platform = get_platform();
+    if platform != "Windows" {
+        return read_file_range(path, offset, len);
     }
 
-    let drive = &path.chars().next().unwrap(); // Only need Drive letter
-    let mut ntfs_parser = setup_ntfs_parser(drive)?;
+    let owned_path = path.to_string();
+    with_read_timeout(path, FileSystemError::ReadTimeout, move || {
+        let (mut ntfs_parser, file_ref) = match raw_acquire_file(&owned_path)? {
+            (parser, Some(result)) => (parser, result),
+            (_, None) => return Ok(Vec::new()),
+        };
+        raw_read_range_by_file_ref(
+            &file_ref,
+            &ntfs_parser.ntfs,
+            &mut ntfs_parser.fs,
+            offset,
+            len,
+        )
+    })
+}
+
+/**
+ * Setup the NTFS parser for the volume of a file and search for the file reference  
+ * Returns the parser so the file can be read afterwards. The file reference is `None` if the file was not found
+ */
+fn raw_acquire_file(
+    path: &str,
+) -> Result<(NtfsParser, Option<NtfsFileReference>), FileSystemError> {
+    let (volume, device) = volume_root(path)?;
+    let mut ntfs_parser = setup_ntfs_device(&device)?;
+    let file_ref = raw_file_reference(path, volume, &mut ntfs_parser)?;
+    Ok((ntfs_parser, file_ref))
+}
+
+/// Search the NTFS system for the file reference of a path
+fn raw_file_reference(
+    path: &str,
+    volume: String,
+    ntfs_parser: &mut NtfsParser,
+) -> Result<Option<NtfsFileReference>, FileSystemError> {
     let root_dir_result = ntfs_parser.ntfs.root_directory(&mut ntfs_parser.fs);
     let root_dir = match root_dir_result {
         Ok(result) => result,
@@ -200,11 +249,11 @@ pub(crate) fn raw_read_file(path: &str) -> Result<Vec<u8>, FileSystemError> {
     let mut ntfs_options = NtfsOptions {
         start_path: path.to_string(),
         start_path_depth: 0,
-        depth: path.split('\\').count(),
+        depth: path_depth(path, &volume),
         path_regex: create_regex("").unwrap(), // Valid Regex, should never fail
         file_regex: create_regex("").unwrap(), // Valid Regex, should never fail
         filelist: Vec::new(),
-        directory_tracker: vec![format!("{drive}:")],
+        directory_tracker: vec![volume],
     };
 
     // Search and iterate through the NTFS system for the file
@@ -215,17 +264,49 @@ pub(crate) fn raw_read_file(path: &str) -> Result<Vec<u8>, FileSystemError> {
         &mut ntfs_options,
     );
 
-    let mut file_data: Vec<u8> = Vec::new();
-    for filelist in ntfs_options.filelist {
-        if filelist.full_path != path {
-            continue;
+    Ok(ntfs_options
+        .filelist
+        .into_iter()
+        .find(|filelist| filelist.full_path == path)
+        .map(|filelist| filelist.file))
+}
+
+/**
+ * Determine the volume root of a path and the device that must be opened to read it raw  
+ * Supports drive letter paths (`C:\Windows`) and device paths such as Volume Shadow Copies  
+ * (`\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopy1\Windows`)
+ */
+pub(crate) fn volume_root(path: &str) -> Result<(String, String), FileSystemError> {
+    let globalroot = "\\\\?\\GLOBALROOT\\Device\\";
+    if let Some(prefix) = path.get(..globalroot.len()) {
+        if prefix.eq_ignore_ascii_case(globalroot) {
+            let device_name = path[globalroot.len()..]
+                .split('\\')
+                .next()
+                .unwrap_or_default();
+            if device_name.is_empty() {
+                return Err(FileSystemError::NotFile);
+            }
+            let device = format!("{prefix}{device_name}");
+            return Ok((device.clone(), device));
         }
+    }
 
-        file_data = raw_read_by_file_ref(&filelist.file, &ntfs_parser.ntfs, &mut ntfs_parser.fs)?;
-        break;
+    let min_path_len = 4;
+    if path.len() < min_path_len || !path.contains(':') {
+        return Err(FileSystemError::NotFile);
     }
 
-    Ok(file_data)
+    let drive = path.chars().next().unwrap_or_default(); // Only need Drive letter
+    Ok((format!("{drive}:"), format!("\\\\.\\{drive}:")))
+}
+
+/// Number of path components below the volume root. Used to limit how deep we iterate the NTFS
+fn path_depth(path: &str, volume: &str) -> usize {
+    path.get(volume.len()..)
+        .unwrap_or_default()
+        .split('\\')
+        .count()
 }
 
 /**
@@ -308,14 +389,110 @@ pub(crate) fn raw_read_by_file_ref(
     Ok(file_data)
 }
 
+/**
+ * Read a range of a file by file reference. Seeks within the $DATA attribute so only the requested range is read  
+ * `WofCompressed` data cannot be seeked. The whole file is decompressed and the range is returned from the uncompressed data
+ */
+pub(crate) fn raw_read_range_by_file_ref(
+    ntfs_ref: &NtfsFileReference,
+    ntfs: &Ntfs,
+    fs: &mut BufReader<SectorReader<File>>,
+    offset: u64,
+    len: usize,
+) -> Result<Vec<u8>, FileSystemError> {
+    let compress_check = check_wofcompressed(ntfs_ref, ntfs, fs);
+    match compress_check {
+        Ok((is_compressed, uncompressed_data, _compressed_size)) => {
+            if is_compressed {
+                let start = (offset as usize).min(uncompressed_data.len());
+                let end = start.saturating_add(len).min(uncompressed_data.len());
+                return Ok(uncompressed_data[start..end].to_vec());
+            }
+        }
+        Err(err) => {
+            error!("[artemis-core] Could not check for decompression error: {err:?}. Returning regular data.");
+        }
+    }
+
+    let ntfs_file_result = ntfs_ref.to_file(ntfs, fs);
+    let ntfs_file = match ntfs_file_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Failed to get NTFS file, error: {err:?}");
+            return Err(FileSystemError::NotFile);
+        }
+    };
+    let data_name = "";
+    let ntfs_data_option = ntfs_file.data(fs, data_name);
+    let ntfs_data_result = match ntfs_data_option {
+        Some(result) => result,
+        None => return Err(FileSystemError::FileData),
+    };
+
+    let ntfs_data = match ntfs_data_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Failed to get NTFS data error: {err:?}");
+            return Err(FileSystemError::FileData);
+        }
+    };
+
+    let ntfs_attribute_result = ntfs_data.to_attribute();
+    let ntfs_attribute = match ntfs_attribute_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Failed to get NTFS attribute error: {err:?}");
+            return Err(FileSystemError::NoAttribute);
+        }
+    };
+
+    let data_result = ntfs_attribute.value(fs);
+    let mut data_attr_value = match data_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Failed to get NTFS attribute data error: {err:?}");
+            return Err(FileSystemError::NoDataAttributeValue);
+        }
+    };
+
+    // Nothing to read past the end of the file
+    if offset >= data_attr_value.len() {
+        return Ok(Vec::new());
+    }
+
+    if let Err(err) = data_attr_value.seek(fs, SeekFrom::Start(offset)) {
+        error!("[artemis-core] Could not seek to offset {offset} error: {err:?}");
+        return Err(FileSystemError::ReadFile);
+    }
+
+    let mut file_data: Vec<u8> = vec![0u8; len];
+    let mut total = 0;
+    while total < len {
+        let bytes_result = data_attr_value.read(fs, &mut file_data[total..]);
+        let bytes = match bytes_result {
+            Ok(result) => result,
+            Err(err) => {
+                error!("[artemis-core] Could not read file range error: {err:?}");
+                return Err(FileSystemError::ReadFile);
+            }
+        };
+
+        let finished = 0;
+        if bytes == finished {
+            break;
+        }
+        total += bytes;
+    }
+
+    // Make sure the data does not have any extra zeros from the intialization
+    file_data.truncate(total);
+    Ok(file_data)
+}
+
 /// Read a provided NTFS attribute. Can be used to read non-resident Alternative Data Streams (ADS)
 pub(crate) fn read_attribute(path: &str, attribute: &str) -> Result<Vec<u8>, FileSystemError> {
-    let min_path_len = 4;
-    if path.len() < min_path_len || !path.contains(':') {
-        return Err(FileSystemError::NotFile);
-    }
-    let drive = &path.chars().next().unwrap(); // Only need Drive letter
-    let mut ntfs_parser = setup_ntfs_parser(drive)?;
+    let (volume, device) = volume_root(path)?;
+    let mut ntfs_parser = setup_ntfs_device(&device)?;
 
     let root_dir_result = ntfs_parser.ntfs.root_directory(&mut ntfs_parser.fs);
     let root_dir = match root_dir_result {
@@ -329,11 +506,11 @@ pub(crate) fn read_attribute(path: &str, attribute: &str) -> Result<Vec<u8>, Fil
     let mut ntfs_options = NtfsOptions {
         start_path: path.to_string(),
         start_path_depth: 0,
-        depth: path.split('\\').count(),
+        depth: path_depth(path, &volume),
         path_regex: create_regex("").unwrap(), // Valid Regex, should never fail
         file_regex: create_regex("").unwrap(), // Valid Regex, should never fail
         filelist: Vec::new(),
-        directory_tracker: vec![format!("{drive}:")],
+        directory_tracker: vec![volume],
     };
 
     // Search and iterate through the NTFS system for the file
@@ -547,14 +724,16 @@ pub(crate) fn iterate_ntfs(
 #[cfg(test)]
 #[cfg(target_os = "windows")]
 mod tests {
-    use super::{get_user_registry_files, iterate_ntfs, raw_reader, NtfsOptions};
+    use super::{
+        get_user_registry_files, iterate_ntfs, path_depth, raw_reader, volume_root, NtfsOptions,
+    };
     use crate::{
         filesystem::{
             files::Hashes,
             ntfs::{
                 raw_files::{
                     raw_hash_data, raw_read_by_file_ref, raw_read_data, raw_read_file,
-                    read_attribute,
+                    raw_read_file_range, read_attribute,
                 },
                 sector_reader::SectorReader,
                 setup::setup_ntfs_parser,
@@ -684,6 +863,53 @@ mod tests {
         assert_eq!(result.len(), 23);
     }
 
+    #[test]
+    fn test_raw_read_file_range() {
+        let path = "C:\\Windows\\explorer.exe";
+        let full = raw_read_file(path).unwrap();
+
+        let offset = full.len() / 2;
+        let result = raw_read_file_range(path, offset as u64, 4096).unwrap();
+        assert_eq!(result, full[offset..offset + 4096]);
+
+        let result = raw_read_file_range(path, full.len() as u64 - 10, 4096).unwrap();
+        assert_eq!(result, full[full.len() - 10..]);
+        assert!(raw_read_file_range(path, full.len() as u64, 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_volume_root() {
+        let (volume, device) = volume_root("C:\\Windows\\Prefetch").unwrap();
+        assert_eq!(volume, "C:");
+        assert_eq!(device, "\\\\.\\C:");
+
+        let (volume, device) =
+            volume_root("\\\\?\\GLOBALROOT\\Device\\HarddiskVolumeShadowCopy1\\Windows\\Prefetch")
+                .unwrap();
+        assert_eq!(
+            volume,
+            "\\\\?\\GLOBALROOT\\Device\\HarddiskVolumeShadowCopy1"
+        );
+        assert_eq!(volume, device);
+
+        assert!(volume_root("\\\\?\\GLOBALROOT\\Device\\").is_err());
+        assert!(volume_root("bad").is_err());
+    }
+
+    #[test]
+    fn test_path_depth() {
+        assert_eq!(path_depth("C:\\Windows\\Prefetch", "C:"), 3);
+        assert_eq!(
+            path_depth(
+                "\\\\?\\GLOBALROOT\\Device\\HarddiskVolumeShadowCopy1\\Windows\\Prefetch",
+                "\\\\?\\GLOBALROOT\\Device\\HarddiskVolumeShadowCopy1"
+            ),
+            3
+        );
+    }
+
     #[test]
     fn test_read_attribute() {
         let result = read_attribute("C:\\$Extend\\$UsnJrnl", "$J").unwrap();
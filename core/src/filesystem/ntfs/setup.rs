@@ -12,12 +12,19 @@ pub(crate) struct NtfsParser {
 /// Setup NTFS parser by opening drive letter and creating Sector Reader
 pub(crate) fn setup_ntfs_parser(drive_letter: &char) -> Result<NtfsParser, FileSystemError> {
     let drive_path = format!("\\\\.\\{drive_letter}:");
+    setup_ntfs_device(&drive_path)
+}
 
-    let fs_result = File::open(drive_path);
+/**
+ * Setup NTFS parser by opening a volume device path and creating Sector Reader  
+ * Ex: `\\.\C:` or a Volume Shadow Copy such as `\\?\GLOBALROOT\Device\HarddiskVolumeShadowCopy1`
+ */
+pub(crate) fn setup_ntfs_device(device_path: &str) -> Result<NtfsParser, FileSystemError> {
+    let fs_result = File::open(device_path);
     let fs = match fs_result {
         Ok(result) => result,
         Err(err) => {
-            error!("[artemis-core] Failed to open drive: {drive_letter}, error: {err:?}");
+            error!("[artemis-core] Failed to open device: {device_path}, error: {err:?}");
             return Err(FileSystemError::OpenFile);
         }
     };
@@ -0,0 +1,290 @@
+/**
+ * Abstraction over where artifact files are read from
+ * Parsers that accept a `FileProvider` can read from the live system or from an alternative source such as a forensic image
+ */
+use super::{
+    directory::is_directory,
+    error::FileSystemError,
+    files::{file_reader, get_file_size, is_file, list_files_directories, read_file},
+    ntfs::raw_files::raw_read_file,
+};
+use log::error;
+use std::io::{Read, Seek};
+
+/// Reader that supports seeking
+pub(crate) trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+/// Basic metadata for an entry returned by a `FileProvider`
+#[derive(Debug, PartialEq)]
+pub(crate) struct FileMetadata {
+    pub(crate) is_file: bool,
+    pub(crate) is_directory: bool,
+    pub(crate) size: u64,
+}
+
+/// Source of files for artifact parsers
+pub(crate) trait FileProvider {
+    /// Open a file for reading
+    fn open(&self, path: &str) -> Result<Box<dyn ReadSeek>, FileSystemError>;
+    /// Read an entire file that is less than 2GB in size
+    fn read(&self, path: &str) -> Result<Vec<u8>, FileSystemError> {
+        let meta = self.metadata(path)?;
+        let max_size = 2147483648;
+        if meta.size > max_size {
+            return Err(FileSystemError::LargeFile);
+        }
+
+        let mut reader = self.open(path)?;
+        let mut data = Vec::with_capacity(meta.size as usize);
+        if let Err(err) = reader.read_to_end(&mut data) {
+            error!("[artemis-core] Failed to read file {path}: {err:?}");
+            return Err(FileSystemError::ReadFile);
+        }
+        Ok(data)
+    }
+    /// List the full paths of all files and directories in a directory
+    fn list_dir(&self, path: &str) -> Result<Vec<String>, FileSystemError>;
+    /// Get metadata for a path
+    fn metadata(&self, path: &str) -> Result<FileMetadata, FileSystemError>;
+    /// Files are read from the live system. Parsers may then use their own readers (Ex: raw NTFS for locked ESE databases)
+    fn is_live(&self) -> bool {
+        false
+    }
+}
+
+/// Read files from the live system
+pub(crate) struct LiveFileProvider {
+    /// Read files by parsing the NTFS filesystem to bypass locked files. Only applies on Windows
+    pub(crate) raw_ntfs: bool,
+}
+
+impl FileProvider for LiveFileProvider {
+    fn open(&self, path: &str) -> Result<Box<dyn ReadSeek>, FileSystemError> {
+        let reader = file_reader(path)?;
+        Ok(Box::new(reader))
+    }
+
+    fn read(&self, path: &str) -> Result<Vec<u8>, FileSystemError> {
+        if self.raw_ntfs {
+            return raw_read_file(path);
+        }
+        read_file(path)
+    }
+
+    fn list_dir(&self, path: &str) -> Result<Vec<String>, FileSystemError> {
+        list_files_directories(path)
+    }
+
+    fn metadata(&self, path: &str) -> Result<FileMetadata, FileSystemError> {
+        let is_file = is_file(path);
+        let is_directory = is_directory(path);
+        if !is_file && !is_directory {
+            return Err(FileSystemError::NotFile);
+        }
+
+        let size = if is_file { get_file_size(path) } else { 0 };
+        Ok(FileMetadata {
+            is_file,
+            is_directory,
+            size,
+        })
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileMetadata, FileProvider, LiveFileProvider, ReadSeek};
+    use crate::{
+        artifacts::os::windows::{
+            bits::parser::provider_bits_path, prefetch::parser::provider_prefetch_path,
+        },
+        filesystem::{error::FileSystemError, files::read_file},
+    };
+    use std::{collections::HashMap, io::Cursor, io::Read, path::PathBuf};
+
+    /// In memory provider to test parsers without the live filesystem
+    struct MemoryProvider {
+        files: HashMap<String, Vec<u8>>,
+    }
+
+    impl FileProvider for MemoryProvider {
+        fn open(&self, path: &str) -> Result<Box<dyn ReadSeek>, FileSystemError> {
+            match self.files.get(path) {
+                Some(result) => Ok(Box::new(Cursor::new(result.clone()))),
+                None => Err(FileSystemError::NotFile),
+            }
+        }
+
+        fn list_dir(&self, path: &str) -> Result<Vec<String>, FileSystemError> {
+            let prefix = format!("{path}\\");
+            let entries: Vec<String> = self
+                .files
+                .keys()
+                .filter(|entry| entry.starts_with(&prefix))
+                .cloned()
+                .collect();
+            if entries.is_empty() {
+                return Err(FileSystemError::NotDirectory);
+            }
+            Ok(entries)
+        }
+
+        fn metadata(&self, path: &str) -> Result<FileMetadata, FileSystemError> {
+            if let Some(data) = self.files.get(path) {
+                return Ok(FileMetadata {
+                    is_file: true,
+                    is_directory: false,
+                    size: data.len() as u64,
+                });
+            }
+            self.list_dir(path)?;
+            Ok(FileMetadata {
+                is_file: false,
+                is_directory: true,
+                size: 0,
+            })
+        }
+    }
+
+    #[test]
+    fn test_live_provider() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win10");
+        let dir = test_location.display().to_string();
+
+        let provider = LiveFileProvider { raw_ntfs: false };
+        let entries = provider.list_dir(&dir).unwrap();
+        assert!(!entries.is_empty());
+
+        let meta = provider.metadata(&dir).unwrap();
+        assert!(meta.is_directory);
+
+        let meta = provider.metadata(&entries[0]).unwrap();
+        assert!(meta.is_file);
+
+        let data = provider.read(&entries[0]).unwrap();
+        assert_eq!(data.len() as u64, meta.size);
+
+        let mut reader = provider.open(&entries[0]).unwrap();
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, data);
+
+        assert!(provider.metadata("/does/not/exist").is_err());
+    }
+
+    #[test]
+    fn test_memory_provider() {
+        let mut files = HashMap::new();
+        files.insert(String::from("C:\\test\\file.txt"), vec![1, 2, 3]);
+        let provider = MemoryProvider { files };
+
+        assert_eq!(provider.list_dir("C:\\test").unwrap().len(), 1);
+        assert!(provider.metadata("C:\\test").unwrap().is_directory);
+        assert_eq!(provider.metadata("C:\\test\\file.txt").unwrap().size, 3);
+        assert_eq!(provider.read("C:\\test\\file.txt").unwrap(), vec![1, 2, 3]);
+        assert!(provider.read("C:\\missing").is_err());
+    }
+
+    #[test]
+    fn test_memory_provider_prefetch() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location
+            .push("tests/test_data/windows/prefetch/win10/SHELLEXPERIENCEHOST.EXE-C83BCA53.pf");
+        let data = read_file(&test_location.display().to_string()).unwrap();
+
+        let mut files = HashMap::new();
+        files.insert(
+            String::from("E:\\Windows\\Prefetch\\SHELLEXPERIENCEHOST.EXE-C83BCA53.pf"),
+            data,
+        );
+        files.insert(
+            String::from("E:\\Windows\\Prefetch\\Layout.ini"),
+            Vec::new(),
+        );
+        let provider = MemoryProvider { files };
+
+        let results = provider_prefetch_path("E:\\Windows\\Prefetch", &provider, false).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].filename, "SHELLEXPERIENCEHOST.EXE");
+        assert_eq!(results[0].hash, "C83BCA53");
+        assert_eq!(results[0].last_run_time, 1620610795);
+
+        assert!(provider_prefetch_path("E:\\Missing", &provider, false).is_err());
+    }
+
+    #[test]
+    fn test_live_provider_prefetch_mixed_versions() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/mixed");
+
+        let provider = LiveFileProvider { raw_ntfs: false };
+        let results =
+            provider_prefetch_path(&test_location.display().to_string(), &provider, false).unwrap();
+        assert_eq!(results.len(), 3);
+
+        for result in results {
+            match result.hash.as_str() {
+                "57D17DAF" => {
+                    assert_eq!(result.version, 23);
+                    assert_eq!(result.filename, "MSCORSVW.EXE");
+                }
+                "8C6DCBE0" => {
+                    assert_eq!(result.version, 26);
+                    assert_eq!(result.filename, "WINDOWS-KB890830-X64-V5.91.EX");
+                }
+                "61043047" => {
+                    assert_eq!(result.version, 30);
+                    assert_eq!(result.filename, "CHCP.COM");
+                }
+                _ => panic!("unexpected prefetch file {}", result.path),
+            }
+            assert!(result.run_count > 0);
+            assert!(!result.accessed_files.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_memory_provider_bits() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/bits/win81/qmgr0.dat");
+        let data = read_file(&test_location.display().to_string()).unwrap();
+
+        let path = "E:\\ProgramData\\Microsoft\\Network\\Downloader\\qmgr0.dat";
+        let mut files = HashMap::new();
+        files.insert(path.to_string(), data);
+        let provider = MemoryProvider { files };
+
+        let results = provider_bits_path(path, false, &provider, false).unwrap();
+        assert_eq!(results.bits.len(), 1);
+        assert_eq!(
+            results.bits[0].job_id,
+            "5422299c-cd21-4c51-bad5-9da178edc742"
+        );
+    }
+
+    #[test]
+    fn test_memory_provider_ese_bits() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/ese/win10/qmgr.db");
+        let data = read_file(&test_location.display().to_string()).unwrap();
+
+        // Path does not exist on disk. The ESE tables must be read through the provider
+        let path = "E:\\ProgramData\\Microsoft\\Network\\Downloader\\qmgr.db";
+        let mut files = HashMap::new();
+        files.insert(path.to_string(), data);
+        let provider = MemoryProvider { files };
+        assert!(!provider.is_live());
+
+        let results = provider_bits_path(path, false, &provider, false).unwrap();
+        assert_eq!(results.bits.len(), 1);
+        assert!(!results.bits[0].job_id.is_empty());
+        assert!(!results.partial);
+    }
+}
@@ -0,0 +1,251 @@
+/**
+ * Skip artifact source files based on how recently they were modified or their size
+ * Files that are still being written to can produce inconsistent reads. Very large files can slow down a fast sweep
+ * The limits only apply on the thread running an artifact parser
+ */
+use crate::structs::toml::Output;
+use log::{info, warn};
+use std::{
+    cell::{Cell, RefCell},
+    fs::metadata,
+    time::SystemTime,
+};
+
+thread_local! {
+    /// Modification time limits for the parser running on the current thread
+    static MTIME_LIMITS: Cell<Option<MtimeLimits>> = const { Cell::new(None) };
+    /// Max size in bytes of source files parsed on the current thread
+    static MAX_PARSE_BYTES: Cell<Option<u64>> = const { Cell::new(None) };
+    /// Source files skipped on the current thread because they were larger than `MAX_PARSE_BYTES`
+    static OVERSIZE: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Age limits in seconds based on the last modified time of a source file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct MtimeLimits {
+    /// Skip files modified less than this many seconds ago
+    pub(crate) min_age: Option<u64>,
+    /// Skip files modified more than this many seconds ago
+    pub(crate) max_age: Option<u64>,
+}
+
+/// Get the modification time limits from the `Output` options
+pub(crate) fn mtime_limits(output: &Output) -> Option<MtimeLimits> {
+    if output.source_mtime_min.is_none() && output.source_mtime_max.is_none() {
+        return None;
+    }
+
+    Some(MtimeLimits {
+        min_age: output.source_mtime_min,
+        max_age: output.source_mtime_max,
+    })
+}
+
+/// Set the modification time limits for the current thread. `None` disables them
+pub(crate) fn set_mtime_limits(limits: Option<MtimeLimits>) {
+    MTIME_LIMITS.with(|current| current.set(limits));
+}
+
+/// Set the max size of source files parsed on the current thread. `None` disables the limit
+pub(crate) fn set_max_parse_bytes(limit: Option<u64>) {
+    MAX_PARSE_BYTES.with(|current| current.set(limit));
+}
+
+/// Get and clear the source files skipped on the current thread because of their size
+pub(crate) fn take_oversize() -> Vec<String> {
+    OVERSIZE.with(|oversize| oversize.take())
+}
+
+/// Run `func` without the modification time and size limits. Used when reading files artemis wrote, such as staged output
+pub(crate) fn without_mtime_limits<T, F>(func: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let limits = MTIME_LIMITS.with(|current| current.take());
+    let max_bytes = MAX_PARSE_BYTES.with(|current| current.take());
+    let result = func();
+    set_mtime_limits(limits);
+    set_max_parse_bytes(max_bytes);
+    result
+}
+
+/// Check if a source file is larger than the size limit. Oversize files are recorded so their metadata can still be output
+fn oversize_source(path: &str) -> bool {
+    let max_bytes = match MAX_PARSE_BYTES.with(Cell::get) {
+        Some(result) => result,
+        None => return false,
+    };
+
+    let size = match metadata(path) {
+        Ok(result) => result.len(),
+        Err(err) => {
+            warn!("[artemis-core] Could not get size of {path}: {err:?}");
+            return false;
+        }
+    };
+    if size <= max_bytes {
+        return false;
+    }
+
+    info!("[artemis-core] Skipping {path}. Size {size} bytes is larger than max_parse_bytes ({max_bytes} bytes)");
+    OVERSIZE.with(|oversize| {
+        let mut oversize = oversize.borrow_mut();
+        if !oversize.iter().any(|entry| entry == path) {
+            oversize.push(path.to_string());
+        }
+    });
+    true
+}
+
+/// Check if a source file should be skipped because of its modification time or size
+pub(crate) fn skip_source(path: &str) -> bool {
+    if oversize_source(path) {
+        return true;
+    }
+
+    let limits = match MTIME_LIMITS.with(Cell::get) {
+        Some(result) => result,
+        None => return false,
+    };
+
+    let modified_result = metadata(path).and_then(|meta| meta.modified());
+    let modified = match modified_result {
+        Ok(result) => result,
+        Err(err) => {
+            warn!("[artemis-core] Could not get modified time for {path}: {err:?}");
+            return false;
+        }
+    };
+    // Files modified in the future have an age of zero
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default()
+        .as_secs();
+
+    if let Some(min_age) = limits.min_age {
+        if age < min_age {
+            info!("[artemis-core] Skipping {path}. Modified {age} seconds ago which is newer than source_mtime_min ({min_age} seconds)");
+            return true;
+        }
+    }
+    if let Some(max_age) = limits.max_age {
+        if age > max_age {
+            info!("[artemis-core] Skipping {path}. Modified {age} seconds ago which is older than source_mtime_max ({max_age} seconds)");
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        set_max_parse_bytes, set_mtime_limits, skip_source, take_oversize, without_mtime_limits,
+        MtimeLimits,
+    };
+    use crate::filesystem::{files::list_files, metadata::glob_paths};
+    use std::{
+        fs::{create_dir_all, write, File},
+        thread,
+        time::{Duration, SystemTime},
+    };
+
+    /// Create a file with a modification time `age` seconds in the past
+    fn file_with_age(path: &str, age: u64) {
+        let file = File::create(path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(age))
+            .unwrap();
+    }
+
+    fn test_dir(name: &str) -> String {
+        let dir = format!("./tmp/mtime/{name}");
+        create_dir_all(&dir).unwrap();
+        file_with_age(&format!("{dir}/old.txt"), 3600);
+        file_with_age(&format!("{dir}/new.txt"), 0);
+        dir
+    }
+
+    #[test]
+    fn test_skip_source() {
+        let dir = test_dir("skip");
+        let old = format!("{dir}/old.txt");
+        let new = format!("{dir}/new.txt");
+        assert!(!skip_source(&old));
+        assert!(!skip_source(&new));
+
+        set_mtime_limits(Some(MtimeLimits {
+            min_age: Some(60),
+            max_age: None,
+        }));
+        assert!(!skip_source(&old));
+        assert!(skip_source(&new));
+
+        set_mtime_limits(Some(MtimeLimits {
+            min_age: None,
+            max_age: Some(60),
+        }));
+        assert!(skip_source(&old));
+        assert!(!skip_source(&new));
+
+        set_mtime_limits(None);
+        assert!(!skip_source(&old));
+    }
+
+    #[test]
+    fn test_list_files_mtime() {
+        let dir = test_dir("list");
+        set_mtime_limits(Some(MtimeLimits {
+            min_age: Some(60),
+            max_age: None,
+        }));
+
+        let files = list_files(&dir).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("old.txt"));
+
+        let globs = glob_paths(&format!("{dir}/*.txt")).unwrap();
+        assert_eq!(globs.len(), 1);
+        assert_eq!(globs[0].filename, "old.txt");
+
+        let all = without_mtime_limits(|| list_files(&dir).unwrap());
+        assert_eq!(all.len(), 2);
+        assert_eq!(list_files(&dir).unwrap().len(), 1);
+
+        // Other threads are not affected
+        let other = thread::spawn(move || list_files(&dir).unwrap().len())
+            .join()
+            .unwrap();
+        assert_eq!(other, 2);
+        set_mtime_limits(None);
+    }
+
+    #[test]
+    fn test_skip_source_oversize() {
+        let dir = "./tmp/oversize/skip";
+        create_dir_all(dir).unwrap();
+        write(format!("{dir}/small.txt"), [0; 10]).unwrap();
+        write(format!("{dir}/large.txt"), [0; 100]).unwrap();
+
+        set_max_parse_bytes(Some(50));
+        let files = list_files(dir).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("small.txt"));
+
+        let globs = glob_paths(&format!("{dir}/*.txt")).unwrap();
+        assert_eq!(globs.len(), 1);
+        assert_eq!(globs[0].filename, "small.txt");
+
+        // Listed twice but only recorded once
+        let oversize = take_oversize();
+        assert_eq!(oversize.len(), 1);
+        assert!(oversize[0].ends_with("large.txt"));
+        assert!(take_oversize().is_empty());
+
+        assert_eq!(without_mtime_limits(|| list_files(dir).unwrap()).len(), 2);
+        assert!(take_oversize().is_empty());
+
+        set_max_parse_bytes(None);
+        assert_eq!(list_files(dir).unwrap().len(), 2);
+        assert!(take_oversize().is_empty());
+    }
+}
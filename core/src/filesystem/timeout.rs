@@ -0,0 +1,131 @@
+/**
+ * Abort raw file reads that take too long. On flaky disks or network volumes a read can hang indefinitely
+ * The read runs on a worker thread while the calling thread waits up to `read_timeout_secs`
+ * A timed out read is left to finish in the background and its result is discarded
+ * The limit only applies on the thread running an artifact parser
+ */
+use crate::artifacts::timeout::run_with_timeout;
+use log::error;
+use std::cell::Cell;
+
+thread_local! {
+    /// Max seconds a single raw read may take on the current thread
+    static READ_TIMEOUT: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Set the read timeout in seconds for the current thread. `None` or `0` disables the timeout
+pub(crate) fn set_read_timeout(seconds: Option<u64>) {
+    READ_TIMEOUT.with(|current| current.set(seconds.filter(|value| *value != 0)));
+}
+
+/**
+ * Read `path` on a worker thread and return `timeout_err` if the read does not finish within the read timeout
+ * If no timeout is set the read runs on the current thread
+ */
+pub(crate) fn with_read_timeout<T, E, F>(path: &str, timeout_err: E, read: F) -> Result<T, E>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+    F: FnOnce() -> Result<T, E> + Send + 'static,
+{
+    let seconds = match READ_TIMEOUT.with(Cell::get) {
+        Some(result) => result,
+        None => return read(),
+    };
+
+    match run_with_timeout(seconds, read) {
+        Some(result) => result,
+        None => {
+            error!("[artemis-core] Read of {path} did not finish within read_timeout_secs ({seconds} seconds)");
+            Err(timeout_err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{set_read_timeout, with_read_timeout, READ_TIMEOUT};
+    use std::{
+        cell::Cell,
+        io::{Error, Read},
+        thread::{self, sleep},
+        time::{Duration, Instant},
+    };
+
+    #[derive(Debug, PartialEq)]
+    enum TestError {
+        Timeout,
+        Read,
+    }
+
+    /// Reader that waits `delay` before returning each chunk. Mimics a hung disk
+    struct SlowReader {
+        delay: Duration,
+        remaining: usize,
+    }
+
+    impl Read for SlowReader {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+            sleep(self.delay);
+            let size = buf.len().min(self.remaining).min(4);
+            buf[..size].fill(1);
+            self.remaining -= size;
+            Ok(size)
+        }
+    }
+
+    fn slow_read(delay: Duration) -> Result<Vec<u8>, TestError> {
+        let mut reader = SlowReader {
+            delay,
+            remaining: 8,
+        };
+        let mut data = Vec::new();
+        match reader.read_to_end(&mut data) {
+            Ok(_) => Ok(data),
+            Err(_) => Err(TestError::Read),
+        }
+    }
+
+    #[test]
+    fn test_with_read_timeout_slow_reader() {
+        set_read_timeout(Some(1));
+
+        let start = Instant::now();
+        let result = with_read_timeout("slow.bin", TestError::Timeout, || {
+            slow_read(Duration::from_secs(2))
+        });
+        assert_eq!(result, Err(TestError::Timeout));
+        assert!(start.elapsed() < Duration::from_secs(4));
+        set_read_timeout(None);
+    }
+
+    #[test]
+    fn test_with_read_timeout() {
+        set_read_timeout(Some(5));
+        let result = with_read_timeout("fast.bin", TestError::Timeout, || {
+            slow_read(Duration::from_millis(1))
+        });
+        assert_eq!(result.unwrap(), vec![1; 8]);
+
+        let result: Result<Vec<u8>, TestError> =
+            with_read_timeout("bad.bin", TestError::Timeout, || Err(TestError::Read));
+        assert_eq!(result, Err(TestError::Read));
+        set_read_timeout(None);
+    }
+
+    #[test]
+    fn test_set_read_timeout() {
+        set_read_timeout(Some(3));
+        assert_eq!(READ_TIMEOUT.with(Cell::get), Some(3));
+
+        // Other threads track their own timeout
+        let other = thread::spawn(|| READ_TIMEOUT.with(Cell::get))
+            .join()
+            .unwrap();
+        assert!(other.is_none());
+
+        set_read_timeout(Some(0));
+        assert!(READ_TIMEOUT.with(Cell::get).is_none());
+        set_read_timeout(None);
+    }
+}
@@ -0,0 +1,170 @@
+/**
+ * Walk a directory tree with a depth limit and a guard against cycles
+ * Symbolic links and junctions are not followed by default. If links are followed, directories that were already walked are skipped
+ */
+use super::{directory::is_directory, error::FileSystemError};
+use log::warn;
+use std::{collections::HashSet, path::PathBuf};
+use walkdir::{DirEntry, FilterEntry, IntoIter, WalkDir};
+
+/// Options that control how far a directory walk may go
+pub(crate) struct WalkOptions {
+    /// Maximum depth to descend. The start directory is depth 0
+    pub(crate) max_depth: usize,
+    /// Follow symbolic links and junctions into other directories
+    pub(crate) follow_links: bool,
+}
+
+/// Iterator over the entries of a directory walk
+pub(crate) struct DirectoryWalk<P> {
+    walk: FilterEntry<IntoIter, P>,
+    visited: HashSet<PathBuf>,
+}
+
+/// Walk a directory. Entries where `filter` returns false are skipped and not descended into
+pub(crate) fn walk_directory<P>(
+    start_directory: &str,
+    options: &WalkOptions,
+    filter: P,
+) -> Result<DirectoryWalk<P>, FileSystemError>
+where
+    P: FnMut(&DirEntry) -> bool,
+{
+    if !is_directory(start_directory) {
+        return Err(FileSystemError::NotDirectory);
+    }
+
+    let walk = WalkDir::new(start_directory)
+        .same_file_system(true)
+        .max_depth(options.max_depth)
+        .follow_links(options.follow_links)
+        .into_iter()
+        .filter_entry(filter);
+
+    Ok(DirectoryWalk {
+        walk,
+        visited: HashSet::new(),
+    })
+}
+
+impl<P> Iterator for DirectoryWalk<P>
+where
+    P: FnMut(&DirEntry) -> bool,
+{
+    type Item = DirEntry;
+
+    fn next(&mut self) -> Option<DirEntry> {
+        loop {
+            let entry = match self.walk.next()? {
+                Ok(result) => result,
+                Err(err) => {
+                    if let Some(ancestor) = err.loop_ancestor() {
+                        warn!("[artemis-core] Directory loop detected back to {ancestor:?}");
+                    } else {
+                        warn!("[artemis-core] Failed to walk directory entry: {err:?}");
+                    }
+                    continue;
+                }
+            };
+
+            if entry.file_type().is_dir() {
+                // The canonical path identifies directories reached through different links
+                if let Ok(real_path) = entry.path().canonicalize() {
+                    if !self.visited.insert(real_path) {
+                        warn!(
+                            "[artemis-core] Skipping already walked directory {:?}",
+                            entry.path()
+                        );
+                        self.walk.skip_current_dir();
+                        continue;
+                    }
+                }
+            }
+            return Some(entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{walk_directory, WalkOptions};
+    use std::{fs::create_dir_all, path::PathBuf};
+
+    #[test]
+    fn test_walk_directory() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows");
+
+        let options = WalkOptions {
+            max_depth: 1,
+            follow_links: false,
+        };
+        let entries: Vec<_> =
+            walk_directory(&test_location.display().to_string(), &options, |_| true)
+                .unwrap()
+                .collect();
+        assert!(entries.len() > 1);
+        assert!(entries.iter().all(|entry| entry.depth() <= 1));
+    }
+
+    #[test]
+    fn test_walk_directory_filter() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data");
+
+        let options = WalkOptions {
+            max_depth: 2,
+            follow_links: false,
+        };
+        let entries: Vec<_> =
+            walk_directory(&test_location.display().to_string(), &options, |entry| {
+                entry.file_name() != "windows"
+            })
+            .unwrap()
+            .collect();
+        assert!(entries.len() > 1);
+        for entry in entries {
+            let path = entry.path().strip_prefix(&test_location).unwrap();
+            assert!(!path.starts_with("windows"));
+        }
+    }
+
+    #[test]
+    fn test_walk_directory_not_directory() {
+        let options = WalkOptions {
+            max_depth: 1,
+            follow_links: false,
+        };
+        assert!(walk_directory("/does/not/exist", &options, |_| true).is_err());
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_walk_directory_symlink_loop() {
+        use std::os::unix::fs::symlink;
+
+        let test_location = "./tmp/walk_loop/a/b";
+        create_dir_all(test_location).unwrap();
+        let _ = symlink("../..", format!("{test_location}/loop"));
+
+        // Links are not followed by default
+        let options = WalkOptions {
+            max_depth: 100,
+            follow_links: false,
+        };
+        let entries: Vec<_> = walk_directory("./tmp/walk_loop", &options, |_| true)
+            .unwrap()
+            .collect();
+        assert_eq!(entries.len(), 4);
+
+        // Following links must still terminate
+        let options = WalkOptions {
+            max_depth: 100,
+            follow_links: true,
+        };
+        let entries: Vec<_> = walk_directory("./tmp/walk_loop", &options, |_| true)
+            .unwrap()
+            .collect();
+        assert_eq!(entries.len(), 3);
+    }
+}
@@ -77,6 +77,8 @@ pub mod core;
 mod error;
 mod filesystem;
 mod output;
+pub mod parsers;
+#[cfg(feature = "scripting")]
 mod runtime;
 pub mod structs;
 mod utils;
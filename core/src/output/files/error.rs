@@ -14,6 +14,7 @@ pub(crate) enum AcquireError {
     GcpSetup,
     GcpToken,
     GcpSession,
+    HttpClient,
 }
 
 impl std::error::Error for AcquireError {}
@@ -33,6 +34,7 @@ impl fmt::Display for AcquireError {
             AcquireError::GcpSetup => write!(f, "Could not setup GCP upload"),
             AcquireError::GcpToken => write!(f, "Could not create GCP token"),
             AcquireError::GcpSession => write!(f, "Could not create GCP session"),
+            AcquireError::HttpClient => write!(f, "Could not create HTTP client"),
         }
     }
 }
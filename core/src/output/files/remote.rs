@@ -5,7 +5,11 @@ use crate::{
         files::file_reader,
         metadata::{get_metadata, get_timestamps},
     },
-    output::remote::gcp::{create_jwt_gcp, gcp_get_upload_status, gcp_session, setup_gcp_upload},
+    output::remote::{
+        client::upload_client,
+        gcp::{create_jwt_gcp, gcp_get_upload_status, gcp_session, setup_gcp_upload},
+        retry::retry_policy,
+    },
     structs::toml::Output,
 };
 use flate2::{write::GzEncoder, Compression};
@@ -51,6 +55,7 @@ pub(crate) trait AcquireActionRemote {
 }
 
 trait GoogleUpload {
+    fn http_client(&self) -> Result<Client, AcquireError>;
     fn gcp_start(&mut self) -> Result<(), AcquireError>;
     fn gcp_upload(
         &self,
@@ -102,6 +107,18 @@ impl AcquireActionRemote for AcquireFileApiRemote {
 }
 
 impl GoogleUpload for AcquireFileApiRemote {
+    /// Create the HTTP client used for uploads
+    fn http_client(&self) -> Result<Client, AcquireError> {
+        let client_result = upload_client(&self.output);
+        match client_result {
+            Ok(result) => Ok(result),
+            Err(err) => {
+                error!("[artemis-core] Could not create upload client: {err:?}");
+                Err(AcquireError::HttpClient)
+            }
+        }
+    }
+
     /// Start uploading data to GCP
     fn gcp_start(&mut self) -> Result<(), AcquireError> {
         let setup_result = setup_gcp_upload(&self.output, &self.filename);
@@ -124,7 +141,9 @@ impl GoogleUpload for AcquireFileApiRemote {
             }
         };
 
-        let session_result = gcp_session(&session_url, &token);
+        let client = self.http_client()?;
+        let session_result =
+            gcp_session(&client, &session_url, &token, &retry_policy(&self.output));
         let session = match session_result {
             Ok(result) => result,
             Err(err) => {
@@ -149,8 +168,8 @@ impl GoogleUpload for AcquireFileApiRemote {
         let max = 15;
 
         let mut max_attempts = 0;
+        let client = self.http_client()?;
         while max_attempts < max {
-            let client = Client::new();
             let mut builder = client.put(&self.session);
             builder = builder.header("Content-Length", bytes.len());
 
@@ -165,7 +184,12 @@ impl GoogleUpload for AcquireFileApiRemote {
                 builder = builder.header("x-goog-meta-filename", self.filename.clone());
                 builder = builder.header("x-goog-meta-md5", self.md5.clone());
 
-                builder = builder.header("x-goog-meta-hostname", get_info_metadata().hostname);
+                let hostname = self
+                    .output
+                    .hostname_override
+                    .clone()
+                    .unwrap_or_else(|| get_info_metadata().hostname);
+                builder = builder.header("x-goog-meta-hostname", hostname);
                 builder =
                     builder.header("x-goog-meta-endpoint-id", self.output.endpoint_id.clone());
                 builder = builder.header("x-goog-meta-collection-id", self.output.collection_id);
@@ -210,7 +234,7 @@ impl GoogleUpload for AcquireFileApiRemote {
             }
 
             // Check to make sure GCP received our upload
-            let status_result = gcp_get_upload_status(&self.session, "*");
+            let status_result = gcp_get_upload_status(&client, &self.session, "*");
             if status_result.is_err() {
                 error!(
                     "[artemis-core] Could not check status of upload: {:?}",
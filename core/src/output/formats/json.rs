@@ -1,10 +1,14 @@
 use super::error::FormatError;
 use crate::{
-    artifacts::os::systeminfo::info::get_info_metadata,
     structs::toml::Output,
     utils::{
-        compression::compress::compress_gzip_data, logging::collection_status,
-        output::final_output, time::time_now, uuid::generate_uuid,
+        compression::compress::compress_gzip_data,
+        logging::collection_status,
+        output::{
+            envelope::{artifact_envelope, envelope_value},
+            final_output,
+        },
+        uuid::generate_uuid,
     },
 };
 use log::{error, info};
@@ -17,23 +21,8 @@ pub(crate) fn json_format(
     output: &mut Output,
     start_time: &u64,
 ) -> Result<(), FormatError> {
-    // Get small amount of system metadata
-    let info = get_info_metadata();
-    let mut collection_output = json![{
-        "metadata": {
-            "endpoint_id": output.endpoint_id,
-            "uuid": generate_uuid(),
-            "id": output.collection_id,
-            "artifact_name": output_name,
-            "complete_time": time_now(),
-            "start_time": start_time,
-            "hostname": info.hostname,
-            "os_version": info.os_version,
-            "platform": info.platform,
-            "kernel_version": info.kernel_version,
-            "load_performance": info.performance
-        }
-    }];
+    let envelope = artifact_envelope(output, output_name, start_time);
+    let mut collection_output = json![{ "metadata": envelope_value(&envelope) }];
 
     collection_output["data"] = serde_data.clone();
 
@@ -46,7 +35,7 @@ pub(crate) fn raw_json(
     output_name: &str,
     output: &mut Output,
 ) -> Result<(), FormatError> {
-    let serde_collection_results = serde_json::to_string(serde_data);
+    let serde_collection_results = json_string(serde_data, output.pretty.unwrap_or(false));
     let serde_collection = match serde_collection_results {
         Ok(results) => results,
         Err(err) => {
@@ -83,11 +72,20 @@ pub(crate) fn raw_json(
     Ok(())
 }
 
+/// Serialize to a compact or pretty printed JSON string
+fn json_string(serde_data: &Value, pretty: bool) -> Result<String, serde_json::Error> {
+    if pretty {
+        return serde_json::to_string_pretty(serde_data);
+    }
+    serde_json::to_string(serde_data)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{output::formats::json::json_format, structs::toml::Output, utils::time::time_now};
 
-    use super::raw_json;
+    use super::{json_string, raw_json};
+    use serde_json::json;
 
     #[test]
     fn test_json_format() {
@@ -104,6 +102,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         };
         let start_time = time_now();
 
@@ -127,10 +126,24 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         };
 
         let name = "test";
         let data = serde_json::Value::String(String::from("test123"));
         raw_json(&data, name, &mut output).unwrap();
     }
+
+    #[test]
+    fn test_json_string() {
+        let data = json!({"name": "test", "values": [1, 2]});
+
+        let compact = json_string(&data, false).unwrap();
+        assert!(!compact.contains('\n'));
+        assert_eq!(compact, r#"{"name":"test","values":[1,2]}"#);
+
+        let pretty = json_string(&data, true).unwrap();
+        assert!(pretty.contains("\n  \"name\": \"test\""));
+        assert!(pretty.contains("\n    1,"));
+    }
 }
@@ -1,15 +1,22 @@
 use super::error::FormatError;
 use crate::{
-    artifacts::os::systeminfo::info::get_info_metadata,
     structs::toml::Output,
     utils::{
-        compression::compress::compress_gzip_data, logging::collection_status,
-        output::final_output, time::time_now, uuid::generate_uuid,
+        compression::compress::compress_gzip_data,
+        logging::collection_status,
+        output::{
+            envelope::{artifact_envelope, envelope_value},
+            final_output,
+        },
+        uuid::generate_uuid,
     },
 };
 use log::{error, info};
 use serde_json::{json, Value};
 
+/// Name of the file used when all artifacts are written to a single file
+const COMBINED_NAME: &str = "combined";
+
 /// Output to `jsonl` files
 pub(crate) fn jsonl_format(
     serde_data: &Value,
@@ -17,24 +24,16 @@ pub(crate) fn jsonl_format(
     output: &mut Output,
     start_time: &u64,
 ) -> Result<(), FormatError> {
-    // Get small amount of system metadata
-    let info = get_info_metadata();
-    let mut collection_output = json![{
-        "metadata": {
-            "endpoint_id": output.endpoint_id,
-            "id": output.collection_id,
-            "artifact_name": output_name,
-            "complete_time": time_now(),
-            "start_time": start_time,
-            "hostname": info.hostname,
-            "os_version": info.os_version,
-            "platform": info.platform,
-            "kernel_version": info.kernel_version,
-            "load_performance": info.performance
-        }
-    }];
+    let envelope = artifact_envelope(output, output_name, start_time);
+    let mut collection_output = json![{ "metadata": envelope_value(&envelope) }];
 
-    let uuid = generate_uuid();
+    // All artifacts are appended to the same file. Tag each line so the records can be separated again
+    let uuid = if output.combined_output.unwrap_or(false) {
+        collection_output["artifact"] = Value::String(output_name.to_string());
+        COMBINED_NAME.to_string()
+    } else {
+        generate_uuid()
+    };
     // If our data is an array loop through each element and output as a separate line
     if serde_data.is_array() {
         let empty_vec = Vec::new();
@@ -192,7 +191,8 @@ mod tests {
         structs::toml::Output,
         utils::{time::time_now, uuid::generate_uuid},
     };
-    use serde_json::json;
+    use serde_json::{json, Value};
+    use std::fs::{read_to_string, remove_file};
 
     #[test]
     fn test_jsonl_format() {
@@ -209,6 +209,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         };
         let start_time = time_now();
 
@@ -232,6 +233,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         };
 
         let name = "test";
@@ -264,6 +266,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         };
 
         let uuid = generate_uuid();
@@ -299,6 +302,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         };
 
         let uuid = generate_uuid();
@@ -322,4 +326,44 @@ mod tests {
         let line = create_line(Some(&mut collection_output), &mut data).unwrap();
         assert!(!line.is_empty());
     }
+
+    #[test]
+    fn test_jsonl_format_combined() {
+        let mut output = Output {
+            name: String::from("combined_test"),
+            directory: String::from("./tmp"),
+            format: String::from("jsonl"),
+            compress: false,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("local"),
+            filter_name: Some(String::new()),
+            filter_script: Some(String::new()),
+            logging: Some(String::new()),
+            combined_output: Some(true),
+            ..Default::default()
+        };
+        let combined_path = "./tmp/combined_test/combined.jsonl";
+        let _ = remove_file(combined_path);
+        let start_time = time_now();
+
+        let data = json!([{"pid": 1}, {"pid": 2}]);
+        jsonl_format(&data, "processes", &mut output, &start_time).unwrap();
+        let data = json!({"hostname": "test"});
+        jsonl_format(&data, "systeminfo", &mut output, &start_time).unwrap();
+
+        let combined = read_to_string(combined_path).unwrap();
+        let lines: Vec<Value> = combined
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["artifact"], "processes");
+        assert_eq!(lines[1]["artifact"], "processes");
+        assert_eq!(lines[1]["data"]["pid"], 2);
+        assert_eq!(lines[2]["artifact"], "systeminfo");
+        assert_eq!(lines[2]["data"]["hostname"], "test");
+    }
 }
@@ -1,3 +1,5 @@
-mod error;
+pub(crate) mod error;
 pub(crate) mod json;
 pub(crate) mod jsonl;
+#[cfg(feature = "parquet")]
+pub(crate) mod parquet;
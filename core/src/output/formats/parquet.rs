@@ -0,0 +1,297 @@
+use super::error::FormatError;
+use crate::{
+    structs::toml::Output,
+    utils::{
+        compression::compress::compress_gzip_data,
+        logging::collection_status,
+        output::{
+            envelope::{artifact_envelope, write_envelope},
+            final_output,
+        },
+        uuid::generate_uuid,
+    },
+};
+use arrow_array::{ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema};
+use log::{error, info};
+use parquet::arrow::ArrowWriter;
+use serde_json::{Map, Value};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Output to `parquet` files. Each artifact is written as one columnar file
+pub(crate) fn parquet_format(
+    serde_data: &Value,
+    output_name: &str,
+    output: &mut Output,
+    start_time: &u64,
+) -> Result<(), FormatError> {
+    let uuid = match serde_data {
+        Value::Array(entries) => write_rows(entries, output_name, output)?,
+        // Non-array data is written as a single row
+        Value::Object(_) => write_rows(std::slice::from_ref(serde_data), output_name, output)?,
+        _ => write_rows(&[], output_name, output)?,
+    };
+
+    // Parquet rows have no metadata. Describe the file in a sidecar instead
+    let mut envelope = artifact_envelope(output, output_name, start_time);
+    envelope.uuid = uuid.clone();
+    if write_envelope(&envelope, output, &uuid).is_err() {
+        error!("[artemis-core] Failed to output {output_name} parquet metadata");
+        return Err(FormatError::Output);
+    }
+    Ok(())
+}
+
+/// Convert the rows to a `RecordBatch` and write as a `parquet` file. Returns the UUID of the file
+fn write_rows(
+    rows: &[Value],
+    output_name: &str,
+    output: &mut Output,
+) -> Result<String, FormatError> {
+    let batch = create_batch(rows)?;
+    let data = write_parquet(&batch)?;
+
+    let output_data = if output.compress {
+        let compressed_results = compress_gzip_data(&data);
+        match compressed_results {
+            Ok(result) => result,
+            Err(err) => {
+                error!("[artemis-core] Failed to compress parquet data: {err:?}");
+                return Err(FormatError::Output);
+            }
+        }
+    } else {
+        data
+    };
+
+    let uuid = generate_uuid();
+    let output_result = final_output(&output_data, output, &uuid);
+    match output_result {
+        Ok(_) => info!("[artemis-core] {output_name} parquet output success"),
+        Err(err) => {
+            error!("[artemis-core] Failed to output {output_name} parquet: {err:?}");
+            return Err(FormatError::Output);
+        }
+    }
+
+    let _ = collection_status(output_name, output, &uuid);
+    Ok(uuid)
+}
+
+/**
+ * Create an Arrow `RecordBatch` from JSON rows. The schema is derived from the top level keys of all rows
+ * Nested objects and arrays are stored as JSON string columns
+ */
+fn create_batch(rows: &[Value]) -> Result<RecordBatch, FormatError> {
+    let empty_map = Map::new();
+    let mut columns: BTreeMap<&str, DataType> = BTreeMap::new();
+    for row in rows {
+        for (key, value) in row.as_object().unwrap_or(&empty_map) {
+            let column_type = value_type(value);
+            let entry = columns.entry(key).or_insert(DataType::Null);
+            *entry = merge_type(entry, &column_type);
+        }
+    }
+
+    let mut fields = Vec::new();
+    let mut arrays: Vec<ArrayRef> = Vec::new();
+    for (key, column_type) in columns {
+        // Columns that only contain nulls are stored as strings
+        let column_type = if column_type == DataType::Null {
+            DataType::Utf8
+        } else {
+            column_type
+        };
+        let values = rows.iter().map(|row| row.get(key).unwrap_or(&Value::Null));
+        let array: ArrayRef = match column_type {
+            DataType::Boolean => Arc::new(BooleanArray::from(
+                values.map(Value::as_bool).collect::<Vec<_>>(),
+            )),
+            DataType::Int64 => Arc::new(Int64Array::from(
+                values.map(Value::as_i64).collect::<Vec<_>>(),
+            )),
+            DataType::Float64 => Arc::new(Float64Array::from(
+                values.map(Value::as_f64).collect::<Vec<_>>(),
+            )),
+            _ => Arc::new(StringArray::from(
+                values.map(string_value).collect::<Vec<_>>(),
+            )),
+        };
+        fields.push(Field::new(key, column_type, true));
+        arrays.push(array);
+    }
+
+    let schema = Arc::new(Schema::new(fields));
+    let batch_result = if arrays.is_empty() {
+        Ok(RecordBatch::new_empty(schema))
+    } else {
+        RecordBatch::try_new(schema, arrays)
+    };
+    match batch_result {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("[artemis-core] Failed to create parquet record batch: {err:?}");
+            Err(FormatError::Serialize)
+        }
+    }
+}
+
+/// Write the `RecordBatch` to `parquet` bytes
+fn write_parquet(batch: &RecordBatch) -> Result<Vec<u8>, FormatError> {
+    let writer_result = ArrowWriter::try_new(Vec::new(), batch.schema(), None);
+    let mut writer = match writer_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Failed to create parquet writer: {err:?}");
+            return Err(FormatError::Serialize);
+        }
+    };
+
+    if let Err(err) = writer.write(batch) {
+        error!("[artemis-core] Failed to write parquet data: {err:?}");
+        return Err(FormatError::Serialize);
+    }
+    match writer.into_inner() {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("[artemis-core] Failed to finish parquet data: {err:?}");
+            Err(FormatError::Serialize)
+        }
+    }
+}
+
+/// Determine the Arrow type for a JSON value
+fn value_type(value: &Value) -> DataType {
+    match value {
+        Value::Null => DataType::Null,
+        Value::Bool(_) => DataType::Boolean,
+        Value::Number(number) if number.is_i64() => DataType::Int64,
+        Value::Number(_) => DataType::Float64,
+        _ => DataType::Utf8,
+    }
+}
+
+/// Combine column types. Mixed types fall back to strings
+fn merge_type(current: &DataType, next: &DataType) -> DataType {
+    match (current, next) {
+        (DataType::Null, _) => next.clone(),
+        (_, DataType::Null) => current.clone(),
+        (DataType::Int64, DataType::Float64) | (DataType::Float64, DataType::Int64) => {
+            DataType::Float64
+        }
+        _ if current == next => current.clone(),
+        _ => DataType::Utf8,
+    }
+}
+
+/// Get the string value for a column. Nested values are serialized as JSON
+fn string_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(result) => Some(result.clone()),
+        _ => Some(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create_batch, merge_type, parquet_format, write_parquet};
+    use crate::structs::toml::Output;
+    use arrow_array::{Array, Int64Array, StringArray};
+    use arrow_schema::DataType;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use serde_json::json;
+    use std::fs::File;
+
+    fn output_options(name: &str, compress: bool) -> Output {
+        Output {
+            name: name.to_string(),
+            directory: String::from("./tmp"),
+            format: String::from("parquet"),
+            compress,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("local"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parquet_format() {
+        let mut output = output_options("parquet_test", false);
+        let data = json!([
+            {"path": "C:\\test.exe", "last_run_time": 10, "volumes": [{"serial": 1}]},
+            {"path": "C:\\other.exe", "last_run_time": 20, "volumes": []},
+        ]);
+        parquet_format(&data, "prefetch", &mut output, &0).unwrap();
+    }
+
+    #[test]
+    fn test_parquet_format_empty() {
+        let mut output = output_options("parquet_test", true);
+        parquet_format(&json!([]), "prefetch", &mut output, &0).unwrap();
+    }
+
+    #[test]
+    fn test_create_batch() {
+        let data = json!([
+            {"path": "a", "count": 1, "info": {"test": true}},
+            {"path": "b", "count": null},
+        ]);
+        let batch = create_batch(data.as_array().unwrap()).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.num_columns(), 3);
+
+        let count = batch.column_by_name("count").unwrap();
+        let count = count.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(count.value(0), 1);
+        assert!(count.is_null(1));
+
+        let info = batch.column_by_name("info").unwrap();
+        let info = info.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(info.value(0), "{\"test\":true}");
+    }
+
+    #[test]
+    fn test_write_parquet_roundtrip() {
+        let data = json!([{"path": "a"}, {"path": "b"}]);
+        let batch = create_batch(data.as_array().unwrap()).unwrap();
+        let bytes = write_parquet(&batch).unwrap();
+
+        std::fs::create_dir_all("./tmp").unwrap();
+        let path = "./tmp/parquet_roundtrip.parquet";
+        std::fs::write(path, bytes).unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let rows: usize = reader.map(|batch| batch.unwrap().num_rows()).sum();
+        assert_eq!(rows, 2);
+    }
+
+    #[test]
+    fn test_write_parquet_empty_schema() {
+        let batch = create_batch(&[]).unwrap();
+        let bytes = write_parquet(&batch).unwrap();
+        assert_eq!(&bytes[0..4], b"PAR1");
+    }
+
+    #[test]
+    fn test_merge_type() {
+        assert_eq!(
+            merge_type(&DataType::Int64, &DataType::Float64),
+            DataType::Float64
+        );
+        assert_eq!(
+            merge_type(&DataType::Null, &DataType::Boolean),
+            DataType::Boolean
+        );
+        assert_eq!(
+            merge_type(&DataType::Boolean, &DataType::Int64),
+            DataType::Utf8
+        );
+    }
+}
@@ -5,6 +5,7 @@ pub(crate) enum LocalError {
     CreateDirectory,
     CreateFile,
     WriteJson,
+    Sqlite,
 }
 
 impl std::error::Error for LocalError {}
@@ -15,6 +16,7 @@ impl fmt::Display for LocalError {
             LocalError::CreateDirectory => write!(f, "Failed to create output directory"),
             LocalError::CreateFile => write!(f, "Failed to create output file"),
             LocalError::WriteJson => write!(f, "Failed write json data"),
+            LocalError::Sqlite => write!(f, "Failed to output to SQLite database"),
         }
     }
 }
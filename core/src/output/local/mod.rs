@@ -1,2 +1,3 @@
 mod error;
 pub(crate) mod output;
+pub(crate) mod sqlite;
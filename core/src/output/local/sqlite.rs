@@ -0,0 +1,404 @@
+/**
+ * Output artifacts to a local SQLite database. Each artifact is written to its own table in `{directory}/{name}/{name}.db`
+ * Columns are created from the top level keys of the artifact data. Nested objects and arrays are stored as JSON text
+ */
+use super::error::LocalError;
+use crate::structs::toml::Output;
+use log::{error, warn};
+use rusqlite::{params_from_iter, types, Connection};
+use serde_json::{Map, Value};
+use std::{
+    collections::HashSet,
+    fs::{create_dir_all, remove_file},
+    path::Path,
+    time::Duration,
+};
+
+/// Insert the artifact data into a table named after the artifact
+pub(crate) fn sqlite_output(
+    serde_data: &Value,
+    output: &Output,
+    output_name: &str,
+) -> Result<(), LocalError> {
+    let rows = match serde_data {
+        Value::Array(entries) => entries.as_slice(),
+        // Non-array data is written as a single row
+        Value::Object(_) => std::slice::from_ref(serde_data),
+        _ => &[],
+    };
+
+    let output_path = format!("{}/{}", output.directory, output.name);
+    let result = create_dir_all(&output_path);
+    match result {
+        Ok(_) => {}
+        Err(err) => {
+            error!("[artemis-core] Failed to create output directory for {output_path}. Error: {err:?}");
+            return Err(LocalError::CreateDirectory);
+        }
+    }
+
+    let columns = get_columns(rows);
+    // Tables are created on the first write that contains data
+    if columns.is_empty() {
+        return Ok(());
+    }
+
+    let mut connection = open_database(&sqlite_path(output))?;
+    create_table(&connection, output_name, &columns)?;
+    insert_rows(&mut connection, output_name, &columns, rows)
+}
+
+/// Remove the existing database so the collection replaces any previous results
+pub(crate) fn sqlite_replace(output: &Output) -> Result<(), LocalError> {
+    let path = sqlite_path(output);
+    if !Path::new(&path).exists() {
+        return Ok(());
+    }
+
+    let result = remove_file(&path);
+    match result {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            error!("[artemis-core] Failed to remove existing SQLite database {path}: {err:?}");
+            Err(LocalError::Sqlite)
+        }
+    }
+}
+
+/// Path to the SQLite database for the collection
+fn sqlite_path(output: &Output) -> String {
+    format!("{}/{}/{}.db", output.directory, output.name, output.name)
+}
+
+/// Open or create the SQLite database
+fn open_database(path: &str) -> Result<Connection, LocalError> {
+    let connection_result = Connection::open(path);
+    let connection = match connection_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Failed to open SQLite database {path}: {err:?}");
+            return Err(LocalError::Sqlite);
+        }
+    };
+
+    // Multiple workers may write to the database at the same time
+    if let Err(err) = connection.busy_timeout(Duration::from_secs(60)) {
+        warn!("[artemis-core] Could not set SQLite busy timeout: {err:?}");
+    }
+    Ok(connection)
+}
+
+/**
+ * Get all top level keys of the rows in the order they are first seen
+ * SQLite column names are case insensitive. Keys that only differ by case share the first seen column
+ */
+fn get_columns(rows: &[Value]) -> Vec<String> {
+    let empty_map = Map::new();
+    let mut seen = HashSet::new();
+    let mut columns = Vec::new();
+    for row in rows {
+        for key in row.as_object().unwrap_or(&empty_map).keys() {
+            if seen.insert(key.to_ascii_lowercase()) {
+                columns.push(key.clone());
+            }
+        }
+    }
+    columns
+}
+
+/**
+ * Create the table if it does not exist. Tables created by a previous run are reused
+ * and any new columns are added so that data can be appended
+ */
+fn create_table(
+    connection: &Connection,
+    table: &str,
+    columns: &[String],
+) -> Result<(), LocalError> {
+    let definition = columns
+        .iter()
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let create = format!(
+        "CREATE TABLE IF NOT EXISTS {} ({definition})",
+        quote_identifier(table)
+    );
+    if let Err(err) = connection.execute(&create, []) {
+        error!("[artemis-core] Failed to create SQLite table {table}: {err:?}");
+        return Err(LocalError::Sqlite);
+    }
+
+    let existing = table_columns(connection, table)?;
+    for column in columns {
+        if existing
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(column))
+        {
+            continue;
+        }
+        let alter = format!(
+            "ALTER TABLE {} ADD COLUMN {}",
+            quote_identifier(table),
+            quote_identifier(column)
+        );
+        if let Err(err) = connection.execute(&alter, []) {
+            error!("[artemis-core] Failed to add column {column} to SQLite table {table}: {err:?}");
+            return Err(LocalError::Sqlite);
+        }
+    }
+    Ok(())
+}
+
+/// Get the column names of an existing table
+fn table_columns(connection: &Connection, table: &str) -> Result<HashSet<String>, LocalError> {
+    let query = format!("PRAGMA table_info({})", quote_identifier(table));
+    let statement_result = connection.prepare(&query);
+    let mut statement = match statement_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Failed to query SQLite table {table} columns: {err:?}");
+            return Err(LocalError::Sqlite);
+        }
+    };
+
+    let names_result = statement.query_map([], |row| row.get::<_, String>(1));
+    match names_result {
+        Ok(result) => Ok(result.filter_map(Result::ok).collect()),
+        Err(err) => {
+            error!("[artemis-core] Failed to get SQLite table {table} columns: {err:?}");
+            Err(LocalError::Sqlite)
+        }
+    }
+}
+
+/// Insert all rows in a single transaction
+fn insert_rows(
+    connection: &mut Connection,
+    table: &str,
+    columns: &[String],
+    rows: &[Value],
+) -> Result<(), LocalError> {
+    let transaction_result = connection.transaction();
+    let transaction = match transaction_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Failed to start SQLite transaction: {err:?}");
+            return Err(LocalError::Sqlite);
+        }
+    };
+
+    let insert = format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        quote_identifier(table),
+        columns
+            .iter()
+            .map(|column| quote_identifier(column))
+            .collect::<Vec<String>>()
+            .join(", "),
+        vec!["?"; columns.len()].join(", ")
+    );
+
+    {
+        let statement_result = transaction.prepare(&insert);
+        let mut statement = match statement_result {
+            Ok(result) => result,
+            Err(err) => {
+                error!("[artemis-core] Failed to prepare SQLite insert for {table}: {err:?}");
+                return Err(LocalError::Sqlite);
+            }
+        };
+
+        for row in rows {
+            let values = columns
+                .iter()
+                .map(|column| sqlite_value(row_value(row, column)));
+            if let Err(err) = statement.execute(params_from_iter(values)) {
+                error!("[artemis-core] Failed to insert row into SQLite table {table}: {err:?}");
+                return Err(LocalError::Sqlite);
+            }
+        }
+    }
+
+    if let Err(err) = transaction.commit() {
+        error!("[artemis-core] Failed to commit SQLite transaction: {err:?}");
+        return Err(LocalError::Sqlite);
+    }
+    Ok(())
+}
+
+/// Get the row value for a column. Falls back to a key that only differs by case
+fn row_value<'a>(row: &'a Value, column: &str) -> &'a Value {
+    if let Some(value) = row.get(column) {
+        return value;
+    }
+    row.as_object()
+        .and_then(|object| {
+            object
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(column))
+                .map(|(_, value)| value)
+        })
+        .unwrap_or(&Value::Null)
+}
+
+/// Convert a JSON value to a SQLite value. Nested values are serialized as JSON text
+fn sqlite_value(value: &Value) -> types::Value {
+    match value {
+        Value::Null => types::Value::Null,
+        Value::Bool(result) => types::Value::Integer(i64::from(*result)),
+        Value::Number(number) => {
+            if let Some(result) = number.as_i64() {
+                types::Value::Integer(result)
+            } else if let Some(result) = number.as_f64() {
+                types::Value::Real(result)
+            } else {
+                types::Value::Text(number.to_string())
+            }
+        }
+        Value::String(result) => types::Value::Text(result.clone()),
+        _ => types::Value::Text(value.to_string()),
+    }
+}
+
+/// Quote a table or column name
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        get_columns, quote_identifier, row_value, sqlite_output, sqlite_path, sqlite_replace,
+    };
+    use crate::structs::toml::Output;
+    use rusqlite::Connection;
+    use serde_json::json;
+
+    fn output_options(name: &str) -> Output {
+        Output {
+            name: name.to_string(),
+            directory: String::from("./tmp"),
+            format: String::from("json"),
+            compress: false,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("sqlite"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sqlite_output() {
+        let output = output_options("sqlite_test");
+        sqlite_replace(&output).unwrap();
+
+        let data = json!([
+            {"path": "C:\\test.exe", "run_count": 10, "volumes": [{"serial": 1}], "suspicious": false},
+            {"path": "C:\\other.exe", "run_count": 20, "volumes": []},
+        ]);
+        sqlite_output(&data, &output, "prefetch").unwrap();
+        // Appending data with a new column
+        let data = json!([{"path": "C:\\new.exe", "size": 1.5}]);
+        sqlite_output(&data, &output, "prefetch").unwrap();
+
+        let connection = Connection::open(sqlite_path(&output)).unwrap();
+        let count: i64 = connection
+            .query_row("SELECT COUNT(*) FROM prefetch", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 3);
+
+        let volumes: String = connection
+            .query_row(
+                "SELECT volumes FROM prefetch WHERE run_count = 10",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(volumes, "[{\"serial\":1}]");
+
+        let size: f64 = connection
+            .query_row(
+                "SELECT size FROM prefetch WHERE path = 'C:\\new.exe'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(size, 1.5);
+    }
+
+    #[test]
+    fn test_sqlite_replace() {
+        let output = output_options("sqlite_replace_test");
+        sqlite_output(&json!({"name": "test"}), &output, "users").unwrap();
+        sqlite_output(&json!([]), &output, "groups").unwrap();
+
+        sqlite_replace(&output).unwrap();
+        sqlite_output(&json!({"name": "test"}), &output, "users").unwrap();
+
+        let connection = Connection::open(sqlite_path(&output)).unwrap();
+        let count: i64 = connection
+            .query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_sqlite_output_column_case() {
+        let output = output_options("sqlite_case_test");
+        sqlite_replace(&output).unwrap();
+
+        let data = json!([{"Path": "C:\\test.exe"}, {"path": "C:\\other.exe", "Size": 1}]);
+        sqlite_output(&data, &output, "files").unwrap();
+        // Existing columns are matched regardless of case
+        let data = json!([{"PATH": "C:\\new.exe", "size": 2}]);
+        sqlite_output(&data, &output, "files").unwrap();
+
+        let connection = Connection::open(sqlite_path(&output)).unwrap();
+        let count: i64 = connection
+            .query_row(
+                "SELECT COUNT(*) FROM files WHERE path IS NOT NULL",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 3);
+
+        let size: i64 = connection
+            .query_row(
+                "SELECT size FROM files WHERE path = 'C:\\new.exe'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(size, 2);
+    }
+
+    #[test]
+    fn test_row_value() {
+        let data = json!({"Path": "exact", "NAME": "test"});
+        assert_eq!(row_value(&data, "Path"), "exact");
+        assert_eq!(row_value(&data, "name"), "test");
+        assert!(row_value(&data, "missing").is_null());
+    }
+
+    #[test]
+    fn test_get_columns() {
+        let data = json!([{"b": 1, "a": 2}, {"c": 3, "a": 4}]);
+        let columns = get_columns(data.as_array().unwrap());
+        assert_eq!(columns.len(), 3);
+        assert!(columns.contains(&String::from("c")));
+
+        let data = json!([{"Name": 1}, {"name": 2, "NAME": 3}]);
+        let columns = get_columns(data.as_array().unwrap());
+        assert_eq!(columns, vec![String::from("Name")]);
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("prefetch"), "\"prefetch\"");
+        assert_eq!(quote_identifier("bad\"name"), "\"bad\"\"name\"");
+    }
+}
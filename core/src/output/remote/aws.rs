@@ -1,15 +1,19 @@
-use super::error::RemoteError;
+use super::{
+    client::upload_client,
+    error::RemoteError,
+    retry::{backoff_delay, retry_policy, send_with_retry, RetryPolicy},
+};
 use crate::structs::toml::Output;
 use crate::utils::encoding::base64_decode_standard;
 use log::{error, info, warn};
 use nom::bytes::complete::take;
 use nom::error::ErrorKind;
 use reqwest::header::ETAG;
-use reqwest::{blocking::Client, StatusCode, Url};
+use reqwest::{blocking::Client, Url};
 use rusty_s3::actions::{CompleteMultipartUpload, CreateMultipartUpload, S3Action, UploadPart};
 use rusty_s3::{Bucket, Credentials, UrlStyle};
 use serde::Deserialize;
-use std::time::Duration;
+use std::{thread::sleep, time::Duration};
 
 /// Upload data to AWS S3 Bucket using a signed URL signature
 pub(crate) fn aws_upload(data: &[u8], output: &Output, filename: &str) -> Result<(), RemoteError> {
@@ -46,8 +50,12 @@ pub(crate) fn aws_upload(data: &[u8], output: &Output, filename: &str) -> Result
 
     let header_value = "application/json-seq";
     let aws_info = aws_creds(api_key)?;
+    let client = upload_client(output)?;
+    let policy = retry_policy(output);
 
     aws_start_upload(
+        &client,
+        &policy,
         aws_info,
         aws_endpoint_url,
         data,
@@ -58,6 +66,8 @@ pub(crate) fn aws_upload(data: &[u8], output: &Output, filename: &str) -> Result
 
 /// Start the AWS data upload
 fn aws_start_upload(
+    client: &Client,
+    policy: &RetryPolicy,
     aws_info: AwsInfo,
     aws_endpoint_url: Url,
     output_data: &[u8],
@@ -90,7 +100,7 @@ fn aws_start_upload(
         url.set_host(Some("127.0.0.1")).unwrap();
     }
 
-    let response = aws_create_multipart(url.as_str())?;
+    let response = aws_create_multipart(client, url.as_str(), policy)?;
 
     let multipart_res = CreateMultipartUpload::parse_response(&response);
     let multiplart = match multipart_res {
@@ -101,12 +111,18 @@ fn aws_start_upload(
         }
     };
 
+    let session = AwsSession {
+        client,
+        bucket: &bucket,
+        creds: &creds,
+        policy,
+    };
+
     let first_upload = 1;
     let etag_res = aws_multipart_upload(
         output_data,
         multiplart.upload_id(),
-        &bucket,
-        &creds,
+        &session,
         aws_filename,
         first_upload,
         header_value,
@@ -120,8 +136,7 @@ fn aws_start_upload(
 
     let etags: Vec<&str> = etag.iter().map(|tag| tag as &str).collect();
     aws_complete_multipart(
-        &bucket,
-        &creds,
+        &session,
         aws_filename,
         multiplart.upload_id(),
         etags,
@@ -130,55 +145,36 @@ fn aws_start_upload(
 }
 
 /// Create the AWS multipart upload session
-fn aws_create_multipart(url: &str) -> Result<String, RemoteError> {
-    let max_attempts = 15;
-    let mut attempts = 0;
-    let client = Client::new();
-
-    while attempts < max_attempts {
-        let session_result = client.post(url).send();
-        let session = match session_result {
-            Ok(result) => result,
-            Err(err) => {
-                error!("[artemis-core] Could not create session for multipart upload: {err:?}");
-                return Err(RemoteError::RemoteUpload);
-            }
-        };
-
-        if session.status() != StatusCode::OK && attempts < max_attempts {
-            warn!("[artemis-core] Non-200 response create on attempt {attempts} out of {max_attempts}. Response: {session:?}");
-            attempts += 1;
-            continue;
+fn aws_create_multipart(
+    client: &Client,
+    url: &str,
+    policy: &RetryPolicy,
+) -> Result<String, RemoteError> {
+    let session = send_with_retry(policy, "AWS S3 create multipart", || {
+        client.post(url).send()
+    })?;
+
+    let res_result = session.text();
+    match res_result {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("[artemis-core] Could not read response for multipart upload start: {err:?}");
+            Err(RemoteError::BadResponse)
         }
-
-        let res_result = session.text();
-        let response = match res_result {
-            Ok(result) => result,
-            Err(err) => {
-                error!(
-                    "[artemis-core] Could not read response for multipart upload start: {err:?}"
-                );
-                return Err(RemoteError::BadResponse);
-            }
-        };
-        return Ok(response);
     }
-
-    Err(RemoteError::RemoteUpload)
 }
 
 /// Complete and close the multipart upload session
 fn aws_complete_multipart(
-    bucket: &Bucket,
-    creds: &Credentials,
+    session: &AwsSession<'_>,
     aws_filename: &str,
     upload_id: &str,
     etags: Vec<&str>,
     bytes: &usize,
 ) -> Result<(), RemoteError> {
     let action = CompleteMultipartUpload::new(
-        bucket,
-        Some(creds),
+        session.bucket,
+        Some(session.creds),
         aws_filename,
         upload_id,
         etags.into_iter(),
@@ -191,37 +187,20 @@ fn aws_complete_multipart(
     if url.as_str().starts_with("http://blah.replacemeduh.com") {
         url.set_host(Some("127.0.0.1")).unwrap();
     }
-    let max_attempts = 15;
-    let mut attempts = 0;
-    let client = Client::new();
-
-    while attempts < max_attempts {
-        let complete_builder = client.post(url.as_str());
-        let complete_result = complete_builder.body(action.clone().body()).send();
-        let complete = match complete_result {
-            Ok(result) => result,
-            Err(err) => {
-                error!("[artemis-core] Could not complete multipart upload: {err:?}");
-                return Err(RemoteError::RemoteUpload);
-            }
-        };
-        let status = complete.status();
-
-        if status != StatusCode::OK {
-            if attempts < max_attempts {
-                warn!("[artemis-core] Non-200 response on complete attempt {attempts} out of {max_attempts}. Response: {complete:?}");
-                attempts += 1;
-                continue;
-            }
-            error!(
-                "[artemis-core] Non-200 response when trying to complete upload: {:?}",
-                complete.text()
-            );
-            return Err(RemoteError::RemoteUpload);
-        }
+    let max_attempts = session.policy.max_attempts;
+    let mut attempts = 1;
+
+    loop {
+        let complete = send_with_retry(session.policy, "AWS S3 complete multipart", || {
+            let complete_builder = session.client.post(url.as_str());
+            complete_builder.body(action.clone().body()).send()
+        })?;
+
+        // AWS may return a 200 response that contains an error
         let body = complete.text().unwrap_or_default();
-        if status == StatusCode::OK && body.contains("Internal Error") && attempts < max_attempts {
+        if body.contains("Internal Error") && attempts < max_attempts {
             warn!("[artemis-core] 200 response on attempt {attempts} out of {max_attempts} but the response contained an error. Response body: {body:?}");
+            sleep(backoff_delay(session.policy, attempts));
             attempts += 1;
             continue;
         }
@@ -237,8 +216,7 @@ fn aws_complete_multipart(
 fn aws_multipart_upload<'a>(
     output_data: &'a [u8],
     upload_id: &str,
-    bucket: &Bucket,
-    creds: &Credentials,
+    session: &AwsSession<'_>,
     aws_filename: &str,
     id: u16,
     header_value: &str,
@@ -248,148 +226,91 @@ fn aws_multipart_upload<'a>(
     // Valid for one (1) hour
     let duration = Duration::from_secs(3600);
 
-    let part_upload = UploadPart::new(bucket, Some(creds), aws_filename, id, upload_id);
+    let part_upload = UploadPart::new(
+        session.bucket,
+        Some(session.creds),
+        aws_filename,
+        id,
+        upload_id,
+    );
 
-    let client = Client::new();
-    let max_attempts = 15;
-    let mut attempts = 0;
+    let mut signed_url = part_upload.sign(duration);
 
-    while attempts < max_attempts {
-        let mut signed_url = part_upload.sign(duration);
+    // This is used for our test to ensure we hit the mock server
+    if signed_url
+        .as_str()
+        .starts_with("http://blah.replacemeduh.com")
+    {
+        signed_url.set_host(Some("127.0.0.1")).unwrap();
+    }
 
-        // This is used for our test to ensure we hit the mock server
-        if signed_url
-            .as_str()
-            .starts_with("http://blah.replacemeduh.com")
-        {
-            signed_url.set_host(Some("127.0.0.1")).unwrap();
-        }
+    let (remaining_chunk, chunk) = if output_data.len() <= gb_limit {
+        (&[] as &[u8], output_data)
+    } else {
+        // Grab the first chunk
+        take(gb_limit)(output_data)?
+    };
 
-        let mut builder = client.put(signed_url);
+    let res_result = send_with_retry(session.policy, "AWS S3 bucket", || {
+        let mut builder = session.client.put(signed_url.clone());
         builder = builder.header("Content-Type", header_value);
-
-        let mut etags: Vec<String> = Vec::new();
-        if output_data.len() <= gb_limit {
-            builder = builder.header("Content-Length", output_data.len());
-
-            let res_result = builder.body(output_data.to_vec()).send();
-            let response = match res_result {
-                Ok(result) => result,
-                Err(err) => {
-                    error!("[artemis-core] Could not upload data for multipart upload: {err:?}");
-                    return Err(nom::Err::Failure(nom::error::Error::new(
-                        &[],
-                        ErrorKind::Fail,
-                    )));
-                }
-            };
-
-            if response.status() != StatusCode::OK {
-                if attempts < max_attempts {
-                    warn!("[artemis-core] Non-200 response on upload attempt {attempts} out of {max_attempts}. Response: {response:?}");
-                    attempts += 1;
-                    continue;
-                }
-
-                error!(
-                    "[artemis-core] Non-200 response from AWS S3 bucket: {:?}",
-                    response.text()
-                );
-                return Err(nom::Err::Failure(nom::error::Error::new(
-                    &[],
-                    ErrorKind::Fail,
-                )));
-            }
-
-            if let Some(etag_header) = response.headers().get(ETAG) {
-                let etag = etag_header.to_str().unwrap_or_default();
-                if etag.is_empty() {
-                    error!("[artemis-core] Got empty ETAG");
-                    return Err(nom::Err::Failure(nom::error::Error::new(
-                        &[],
-                        ErrorKind::Fail,
-                    )));
-                }
-                etags.push(etag.to_string());
-                return Ok((&[], etags));
-            }
-            error!("[artemis-core] Missing ETAG header in response");
-            return Err(nom::Err::Failure(nom::error::Error::new(
-                &[],
-                ErrorKind::Fail,
-            )));
-        }
-
-        // Grab the first chunk
-        let (remaining_chunk, chunk) = take(gb_limit)(output_data)?;
         builder = builder.header("Content-Length", chunk.len());
-
-        let res_result = builder.body(chunk.to_vec()).send();
-        let response = match res_result {
-            Ok(result) => result,
-            Err(err) => {
-                error!("[artemis-core] Could not upload data for multipart upload: {err:?}");
-                return Err(nom::Err::Failure(nom::error::Error::new(
-                    &[],
-                    ErrorKind::Fail,
-                )));
-            }
-        };
-
-        if response.status() != StatusCode::OK {
-            if attempts < max_attempts {
-                warn!("[artemis-core] Non-200 response on upload attempt {attempts} out of {max_attempts}. Response: {response:?}");
-                attempts += 1;
-                continue;
-            }
-
-            error!(
-                "[artemis-core] Non-200 response from AWS S3 bucket: {:?}",
-                response.text()
-            );
+        builder.body(chunk.to_vec()).send()
+    });
+    let response = match res_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Could not upload data for multipart upload: {err:?}");
             return Err(nom::Err::Failure(nom::error::Error::new(
                 &[],
                 ErrorKind::Fail,
             )));
         }
+    };
 
-        if let Some(etag_header) = response.headers().get(ETAG) {
-            let etag = etag_header.to_str().unwrap_or_default();
-            if etag.is_empty() {
-                error!("[artemis-core] Got empty ETAG");
-                return Err(nom::Err::Failure(nom::error::Error::new(
-                    &[],
-                    ErrorKind::Fail,
-                )));
-            }
-            etags.push(etag.to_string());
-        } else {
-            error!("[artemis-core] Missing ETAG header in response");
+    let mut etags: Vec<String> = Vec::new();
+    if let Some(etag_header) = response.headers().get(ETAG) {
+        let etag = etag_header.to_str().unwrap_or_default();
+        if etag.is_empty() {
+            error!("[artemis-core] Got empty ETAG");
             return Err(nom::Err::Failure(nom::error::Error::new(
                 &[],
                 ErrorKind::Fail,
             )));
         }
+        etags.push(etag.to_string());
+    } else {
+        error!("[artemis-core] Missing ETAG header in response");
+        return Err(nom::Err::Failure(nom::error::Error::new(
+            &[],
+            ErrorKind::Fail,
+        )));
+    }
 
-        let next_id = id + 1;
-
-        let (_, mut other_etags) = aws_multipart_upload(
-            remaining_chunk,
-            upload_id,
-            bucket,
-            creds,
-            aws_filename,
-            next_id,
-            header_value,
-        )?;
-
-        etags.append(&mut other_etags);
+    if remaining_chunk.is_empty() {
         return Ok((&[], etags));
     }
-    Err(nom::Err::Failure(nom::error::Error::new(
-        &[],
-        ErrorKind::Fail,
-    )))
+
+    let next_id = id + 1;
+    let (_, mut other_etags) = aws_multipart_upload(
+        remaining_chunk,
+        upload_id,
+        session,
+        aws_filename,
+        next_id,
+        header_value,
+    )?;
+
+    etags.append(&mut other_etags);
+    Ok((&[], etags))
+}
+
+/// HTTP client and bucket info shared across a multipart upload
+struct AwsSession<'a> {
+    client: &'a Client,
+    bucket: &'a Bucket,
+    creds: &'a Credentials,
+    policy: &'a RetryPolicy,
 }
 
 #[derive(Deserialize)]
@@ -426,16 +347,23 @@ fn aws_creds(keys: &str) -> Result<AwsInfo, RemoteError> {
 mod tests {
     use super::{
         aws_complete_multipart, aws_create_multipart, aws_creds, aws_multipart_upload,
-        aws_start_upload, aws_upload,
+        aws_start_upload, aws_upload, AwsSession,
     };
-    use crate::structs::toml::Output;
+    use crate::{output::remote::retry::RetryPolicy, structs::toml::Output};
     use httpmock::{
         Method::{POST, PUT},
         MockServer,
     };
-    use reqwest::Url;
+    use reqwest::{blocking::Client, Url};
     use rusty_s3::{Bucket, Credentials, UrlStyle};
 
+    fn test_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: 1,
+        }
+    }
+
     fn output_options(
         name: &str,
         output: &str,
@@ -456,7 +384,8 @@ mod tests {
             output: output.to_string(),
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
-logging: Some(String::new())
+            logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -545,7 +474,16 @@ logging: Some(String::new())
             when.method(PUT);
             then.status(200).header("ETAG", "whatever");
         });
-        aws_start_upload(aws_info, url, test.as_bytes(), name, header_value).unwrap();
+        aws_start_upload(
+            &Client::new(),
+            &test_policy(),
+            aws_info,
+            url,
+            test.as_bytes(),
+            name,
+            header_value,
+        )
+        .unwrap();
         mock_me.assert_hits(2);
         mock_me_put.assert();
     }
@@ -560,11 +498,27 @@ logging: Some(String::new())
             then.status(200).body("hi");
         });
 
-        let result = aws_create_multipart(&url).unwrap();
+        let result = aws_create_multipart(&Client::new(), &url, &test_policy()).unwrap();
         assert_eq!(result, "hi");
         mock_me.assert();
     }
 
+    #[test]
+    #[should_panic(expected = "NonRetryable")]
+    fn test_aws_create_multipart_forbidden() {
+        let server = MockServer::start();
+        let port = server.port();
+        let url = format!("http://127.0.0.1:{port}");
+        let mock_me = server.mock(|when, then| {
+            when.method(POST);
+            then.status(403).body("AccessDenied");
+        });
+
+        let result = aws_create_multipart(&Client::new(), &url, &test_policy());
+        mock_me.assert_hits(1);
+        result.unwrap();
+    }
+
     #[test]
     fn test_aws_multipart_upload() {
         let server = MockServer::start();
@@ -584,11 +538,17 @@ logging: Some(String::new())
         });
 
         let bucket = Bucket::new(url, UrlStyle::VirtualHost, "blah", "us-east-1").unwrap();
+        let client = Client::new();
+        let session = AwsSession {
+            client: &client,
+            bucket: &bucket,
+            creds: &creds,
+            policy: &test_policy(),
+        };
         let (_, etag_res) = aws_multipart_upload(
             test.as_bytes(),
             "an id",
-            &bucket,
-            &creds,
+            &session,
             name,
             first_upload,
             "plain/text",
@@ -617,7 +577,14 @@ logging: Some(String::new())
         let bytes = 10;
 
         let bucket = Bucket::new(url, UrlStyle::VirtualHost, "blah", "us-east-1").unwrap();
-        aws_complete_multipart(&bucket, &creds, name, "myid", Vec::new(), &bytes).unwrap();
+        let client = Client::new();
+        let session = AwsSession {
+            client: &client,
+            bucket: &bucket,
+            creds: &creds,
+            policy: &test_policy(),
+        };
+        aws_complete_multipart(&session, name, "myid", Vec::new(), &bytes).unwrap();
         mock_me.assert();
     }
 
@@ -1,7 +1,10 @@
-use super::error::RemoteError;
+use super::{
+    client::upload_client,
+    error::RemoteError,
+    retry::{retry_policy, send_with_retry},
+};
 use crate::structs::toml::Output;
-use log::{error, info, warn};
-use reqwest::{blocking::Client, StatusCode};
+use log::{error, info};
 
 /// Upload data to Azure Blob Storage using a shared access signature (SAS) URI
 pub(crate) fn azure_upload(
@@ -32,43 +35,18 @@ pub(crate) fn azure_upload(
         return Err(RemoteError::RemoteUrl);
     }
 
-    let client = Client::new();
-    let max_attempts = 15;
-    let mut attempts = 0;
+    let client = upload_client(output)?;
+    let policy = retry_policy(output);
+    let azure_full_url = format!("{}/{azure_filename}?{}", azure_uris[0], azure_uris[1]);
 
-    while attempts < max_attempts {
-        let azure_full_url = format!("{}/{azure_filename}?{}", azure_uris[0], azure_uris[1]);
-
-        let mut builder = client.put(azure_full_url);
+    send_with_retry(&policy, "Azure blob storage", || {
+        let mut builder = client.put(&azure_full_url);
         builder = builder.header("Content-Type", header_value);
         builder = builder.header("Content-Length", data.len());
         builder = builder.header("x-ms-version", "2019-12-12");
         builder = builder.header("x-ms-blob-type", "Blockblob");
-        builder = builder.body(data.to_vec());
-
-        let res_result = builder.send();
-        let res = match res_result {
-            Ok(result) => result,
-            Err(err) => {
-                error!("[artemis-core] Failed to upload data to Azure blob storage: {err:?}");
-                return Err(RemoteError::RemoteUpload);
-            }
-        };
-
-        if res.status() != StatusCode::OK && res.status() != StatusCode::CREATED {
-            if attempts < max_attempts {
-                warn!("[artemis-core] Non-200 response on attempt {attempts} out of {max_attempts}. Response: {res:?}");
-                attempts += 1;
-                continue;
-            }
-            error!(
-                "[artemis-core] Non-200 response from Azure blob storage: {:?}",
-                res.text()
-            );
-            return Err(RemoteError::RemoteUpload);
-        }
-        break;
-    }
+        builder.body(data.to_vec()).send()
+    })?;
 
     info!(
         "[artemis-core] Uploaded {} bytes to Azure blob storage",
@@ -104,6 +82,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -161,4 +140,44 @@ mod tests {
         azure_upload(test.as_bytes(), &output, name).unwrap();
         mock_me.assert();
     }
+
+    #[test]
+    fn test_azure_upload_retry() {
+        let server = MockServer::start();
+        let port = server.port();
+        let mut output = output_options("azure_upload_test", "azure", "tmp", false, &format!("http://127.0.0.1:{port}/mycontainername?sp=rcw&st=2023-06-14T03:00:40Z&se=2023-06-14T11:00:40Z&skoid=asdfasdfas-asdfasdfsadf-asdfsfd-sadf"));
+        output.upload_attempts = Some(3);
+        output.upload_backoff_ms = Some(1);
+
+        let test = "A rust program";
+        let name = "output";
+        let mock_me = server.mock(|when, then| {
+            when.method(PUT);
+            then.status(503);
+        });
+        let result = azure_upload(test.as_bytes(), &output, name);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Received non-200 response from server"
+        );
+        mock_me.assert_hits(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "NonRetryable")]
+    fn test_azure_upload_forbidden() {
+        let server = MockServer::start();
+        let port = server.port();
+        let output = output_options("azure_upload_test", "azure", "tmp", false, &format!("http://127.0.0.1:{port}/mycontainername?sp=rcw&st=2023-06-14T03:00:40Z&se=2023-06-14T11:00:40Z&skoid=asdfasdfas-asdfasdfsadf-asdfsfd-sadf"));
+
+        let test = "A rust program";
+        let name = "output";
+        let mock_me = server.mock(|when, then| {
+            when.method(PUT);
+            then.status(403);
+        });
+        let result = azure_upload(test.as_bytes(), &output, name);
+        mock_me.assert_hits(1);
+        result.unwrap();
+    }
 }
@@ -0,0 +1,220 @@
+use super::error::RemoteError;
+use crate::structs::toml::Output;
+use log::error;
+use reqwest::{
+    blocking::{Client, ClientBuilder},
+    Proxy,
+};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+/// Options used to build an upload client. Uploads with the same options share a client
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ClientOptions {
+    proxy_url: Option<String>,
+    user_agent: Option<String>,
+    tls_insecure: bool,
+    connect_timeout: Option<u64>,
+    timeout: Option<u64>,
+}
+
+/// Upload clients shared by all artifact uploads. Reusing a client keeps its connections alive between uploads
+static UPLOAD_CLIENTS: OnceLock<Mutex<HashMap<ClientOptions, Client>>> = OnceLock::new();
+
+/**
+ * Get the HTTP client used for remote uploads based on the `Output` options  
+ * The client is only created once and reused for every upload with the same options
+ */
+pub(crate) fn upload_client(output: &Output) -> Result<Client, RemoteError> {
+    let options = ClientOptions {
+        proxy_url: output.proxy_url.clone(),
+        user_agent: output.user_agent.clone(),
+        tls_insecure: output.tls_insecure.unwrap_or(false),
+        connect_timeout: output.upload_connect_timeout_secs,
+        timeout: output.upload_timeout_secs,
+    };
+
+    let clients = UPLOAD_CLIENTS.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut cache = match clients.lock() {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Could not lock upload client cache: {err:?}");
+            return build_client(&options);
+        }
+    };
+    if let Some(client) = cache.get(&options) {
+        return Ok(client.clone());
+    }
+
+    let client = build_client(&options)?;
+    cache.insert(options, client.clone());
+    Ok(client)
+}
+
+/// Create a new HTTP client with connection pooling and TCP keep-alive
+fn build_client(options: &ClientOptions) -> Result<Client, RemoteError> {
+    let keep_alive = 60;
+    let mut builder = ClientBuilder::new().tcp_keepalive(Duration::from_secs(keep_alive));
+
+    // If no proxy is provided reqwest will check the HTTPS_PROXY and HTTP_PROXY env vars
+    if let Some(proxy_url) = &options.proxy_url {
+        let proxy_result = Proxy::all(proxy_url);
+        let proxy = match proxy_result {
+            Ok(result) => result,
+            Err(err) => {
+                error!("[artemis-core] Invalid proxy URL {proxy_url}: {err:?}");
+                return Err(RemoteError::HttpClient);
+            }
+        };
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(agent) = &options.user_agent {
+        builder = builder.user_agent(agent);
+    }
+
+    if options.tls_insecure {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    if let Some(seconds) = options.connect_timeout {
+        builder = builder.connect_timeout(Duration::from_secs(seconds));
+    }
+
+    if let Some(seconds) = options.timeout {
+        builder = builder.timeout(Duration::from_secs(seconds));
+    }
+
+    let client_result = builder.build();
+    match client_result {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("[artemis-core] Could not create upload client: {err:?}");
+            Err(RemoteError::HttpClient)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{upload_client, UPLOAD_CLIENTS};
+    use crate::structs::toml::Output;
+    use httpmock::{Method::GET, MockServer};
+    use std::time::Duration;
+
+    fn output_options() -> Output {
+        Output {
+            name: String::from("test_output"),
+            directory: String::from("dir"),
+            format: String::from("jsonl"),
+            compress: false,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("gcp"),
+            user_agent: Some(String::from("artemis-test-agent")),
+            tls_insecure: Some(true),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_upload_client_user_agent() {
+        let server = MockServer::start();
+        let port = server.port();
+        let mock_me = server.mock(|when, then| {
+            when.method(GET).header("user-agent", "artemis-test-agent");
+            then.status(200);
+        });
+
+        let client = upload_client(&output_options()).unwrap();
+        let res = client
+            .get(format!("http://127.0.0.1:{port}"))
+            .send()
+            .unwrap();
+
+        mock_me.assert();
+        assert_eq!(res.status(), 200);
+    }
+
+    #[test]
+    fn test_upload_client_proxy() {
+        let server = MockServer::start();
+        let port = server.port();
+        let mock_me = server.mock(|when, then| {
+            when.method(GET).path("/upload");
+            then.status(200);
+        });
+
+        let mut output = output_options();
+        output.proxy_url = Some(format!("http://127.0.0.1:{port}"));
+
+        // Requests should be sent to the proxy instead of the target host
+        let client = upload_client(&output).unwrap();
+        let res = client.get("http://artemis.invalid/upload").send().unwrap();
+
+        mock_me.assert();
+        assert_eq!(res.status(), 200);
+    }
+
+    #[test]
+    fn test_upload_client_bad_proxy() {
+        let mut output = output_options();
+        output.proxy_url = Some(String::from("not a url"));
+
+        assert!(upload_client(&output).is_err());
+    }
+
+    #[test]
+    fn test_upload_client_reused() {
+        let server = MockServer::start();
+        let port = server.port();
+        let mock_me = server.mock(|when, then| {
+            when.method(GET).header("user-agent", "artemis-reuse-agent");
+            then.status(200);
+        });
+
+        let mut output = output_options();
+        output.user_agent = Some(String::from("artemis-reuse-agent"));
+
+        for _ in 0..3 {
+            let client = upload_client(&output).unwrap();
+            let res = client
+                .get(format!("http://127.0.0.1:{port}"))
+                .send()
+                .unwrap();
+            assert_eq!(res.status(), 200);
+        }
+        mock_me.assert_hits(3);
+
+        // Only one client should be created for the same options
+        let cache = UPLOAD_CLIENTS.get().unwrap().lock().unwrap();
+        let clients = cache
+            .keys()
+            .filter(|key| key.user_agent == output.user_agent)
+            .count();
+        assert_eq!(clients, 1);
+    }
+
+    #[test]
+    fn test_upload_client_timeout() {
+        let server = MockServer::start();
+        let port = server.port();
+        server.mock(|when, then| {
+            when.method(GET);
+            then.status(200).delay(Duration::from_secs(3));
+        });
+
+        let mut output = output_options();
+        output.upload_timeout_secs = Some(1);
+        output.upload_connect_timeout_secs = Some(1);
+
+        let client = upload_client(&output).unwrap();
+        let res = client.get(format!("http://127.0.0.1:{port}")).send();
+        assert!(res.unwrap_err().is_timeout());
+    }
+}
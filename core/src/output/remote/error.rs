@@ -7,6 +7,8 @@ pub(crate) enum RemoteError {
     BadResponse,
     RemoteApiKey,
     MaxAttempts,
+    NonRetryable,
+    HttpClient,
 }
 
 impl std::error::Error for RemoteError {}
@@ -19,6 +21,8 @@ impl fmt::Display for RemoteError {
             RemoteError::RemoteApiKey => write!(f, "Missing API key from TOML"),
             RemoteError::BadResponse => write!(f, "Received non-200 response from server"),
             RemoteError::MaxAttempts => write!(f, "Max attempts (15) reached for trying uploads"),
+            RemoteError::HttpClient => write!(f, "Failed to create HTTP client"),
+            RemoteError::NonRetryable => write!(f, "Received non-retryable response from server"),
         }
     }
 }
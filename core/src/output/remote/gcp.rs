@@ -1,4 +1,8 @@
-use super::error::RemoteError;
+use super::{
+    client::upload_client,
+    error::RemoteError,
+    retry::{backoff_delay, retry_policy, retryable_status, send_with_retry, RetryPolicy},
+};
 use crate::{
     structs::toml::Output,
     utils::{encoding::base64_decode_standard, time::time_now},
@@ -8,6 +12,7 @@ use log::{error, info, warn};
 use reqwest::{blocking::Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::Error;
+use std::thread::sleep;
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -24,10 +29,11 @@ pub(crate) fn gcp_upload(data: &[u8], output: &Output, filename: &str) -> Result
 
     // Create the signed JWT token
     let token = create_jwt_gcp(&setup.api_key)?;
+    let client = upload_client(output)?;
+    let policy = retry_policy(output);
     // Create the upload session
-    let session_uri = gcp_session(session, &token)?;
+    let session_uri = gcp_session(&client, session, &token, &policy)?;
 
-    let client = Client::new();
     let mut builder = client.put(&session_uri);
 
     let header_value = "application/json-seq";
@@ -39,17 +45,25 @@ pub(crate) fn gcp_upload(data: &[u8], output: &Output, filename: &str) -> Result
         Ok(result) => result,
         Err(err) => {
             error!("[artemis-core] Failed to upload data to GCP storage: {err:?}");
-            let attempt = 0;
-            return gcp_resume_upload(&session_uri, data, attempt);
+            let attempt = 1;
+            return gcp_resume_upload(&client, &session_uri, data, &policy, attempt);
         }
     };
     if res.status() != StatusCode::OK && res.status() != StatusCode::CREATED {
+        let status = res.status();
+        if !retryable_status(status) {
+            error!(
+                "[artemis-core] Non-retryable response {status} from GCP storage: {:?}",
+                res.text()
+            );
+            return Err(RemoteError::NonRetryable);
+        }
         error!(
             "[artemis-core] Non-200 response from GCP storage: {:?}",
             res.text()
         );
-        let attempt = 0;
-        return gcp_resume_upload(&session_uri, data, attempt);
+        let attempt = 1;
+        return gcp_resume_upload(&client, &session_uri, data, &policy, attempt);
     }
 
     match res.bytes() {
@@ -114,25 +128,19 @@ pub(crate) fn setup_gcp_upload(output: &Output, filename: &str) -> Result<GcpSet
 }
 
 /// Create a resumable upload session
-pub(crate) fn gcp_session(url: &str, token: &str) -> Result<String, RemoteError> {
-    let client = Client::new();
-    let mut builder = client.post(url).bearer_auth(token);
-    builder = builder.header("Content-Length", 0);
-    let res_result = builder.send();
-    let res = match res_result {
-        Ok(result) => result,
-        Err(err) => {
-            error!("[artemis-core] Failed to establish Google Cloud Session: {err:?}");
-            return Err(RemoteError::RemoteUpload);
-        }
-    };
-    if res.status() != StatusCode::OK {
-        error!(
-            "[artemis-core] Non-200 response from Google Cloud Session: {:?}",
-            res.text()
-        );
-        return Err(RemoteError::BadResponse);
-    }
+pub(crate) fn gcp_session(
+    client: &Client,
+    url: &str,
+    token: &str,
+    policy: &RetryPolicy,
+) -> Result<String, RemoteError> {
+    let res = send_with_retry(policy, "Google Cloud Session", || {
+        client
+            .post(url)
+            .bearer_auth(token)
+            .header("Content-Length", 0)
+            .send()
+    })?;
     if let Some(location) = res.headers().get("Location") {
         let session_res = location.to_str();
         let session = match session_res {
@@ -149,20 +157,21 @@ pub(crate) fn gcp_session(url: &str, token: &str) -> Result<String, RemoteError>
     Err(RemoteError::BadResponse)
 }
 
-/// Attempt to resume a GCP upload. Will attempt to resume an upload 15 times
+/// Attempt to resume a GCP upload. Will attempt to resume an upload until the policy max attempts is reached
 fn gcp_resume_upload(
+    client: &Client,
     session_uri: &str,
     output_data: &[u8],
-    max_attempts: u8,
+    policy: &RetryPolicy,
+    attempt: u32,
 ) -> Result<(), RemoteError> {
-    let max = 15;
-
-    if max_attempts > max {
+    if attempt > policy.max_attempts {
         error!("[artemis-core] Max attempts reached for uploading to Google Cloud");
         return Err(RemoteError::MaxAttempts);
     }
-    let client = Client::new();
-    let status = gcp_get_upload_status(session_uri, &format!("{}", output_data.len()))?;
+    sleep(backoff_delay(policy, attempt));
+
+    let status = gcp_get_upload_status(client, session_uri, &format!("{}", output_data.len()))?;
     let complete = -1;
     if status == complete {
         return Ok(());
@@ -189,28 +198,36 @@ fn gcp_resume_upload(
     let res = match res_result {
         Ok(result) => result,
         Err(err) => {
-            error!("[artemis-core] Could not upload to GCP storage: {err:?}. Attempting again");
-            let try_again: u8 = 1;
-            let attempt = try_again + max_attempts;
-            return gcp_resume_upload(session_uri, output_data, attempt);
+            warn!("[artemis-core] Could not upload to GCP storage: {err:?}. Attempting again");
+            return gcp_resume_upload(client, session_uri, output_data, policy, attempt + 1);
         }
     };
     if res.status() != StatusCode::OK && res.status() != StatusCode::CREATED {
-        error!(
+        let status = res.status();
+        // 308 means the upload is incomplete and can be resumed
+        if status != StatusCode::PERMANENT_REDIRECT && !retryable_status(status) {
+            error!(
+                "[artemis-core] Non-retryable response {status} from GCP storage: {:?}",
+                res.text()
+            );
+            return Err(RemoteError::NonRetryable);
+        }
+        warn!(
             "[artemis-core] Non-200 response from GCP storage: {:?}. Attempting again",
             res.text()
         );
-        let try_again: u8 = 1;
-        let attempt = try_again + max_attempts;
-        return gcp_resume_upload(session_uri, output_data, attempt);
+        return gcp_resume_upload(client, session_uri, output_data, policy, attempt + 1);
     }
 
     Ok(())
 }
 
 /// Check the GCP upload status. A value of -1 means we are done
-pub(crate) fn gcp_get_upload_status(url: &str, upload_size: &str) -> Result<isize, RemoteError> {
-    let client = Client::new();
+pub(crate) fn gcp_get_upload_status(
+    client: &Client,
+    url: &str,
+    upload_size: &str,
+) -> Result<isize, RemoteError> {
     let mut builder = client.put(url);
     builder = builder.header("Content-Length", 0);
     builder = builder.header("Content-Range", format!("bytes */{upload_size}"));
@@ -342,15 +359,26 @@ pub(crate) fn create_jwt_gcp(key: &str) -> Result<String, RemoteError> {
 mod tests {
     use super::{create_jwt_gcp, gcp_get_upload_status, gcp_resume_upload, setup_gcp_upload};
     use crate::{
-        output::remote::gcp::{gcp_session, gcp_upload},
+        output::remote::{
+            gcp::{gcp_session, gcp_upload},
+            retry::RetryPolicy,
+        },
         structs::toml::Output,
     };
     use httpmock::{
         Method::{POST, PUT},
         MockServer,
     };
+    use reqwest::blocking::Client;
     use serde_json::json;
 
+    fn test_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: 1,
+        }
+    }
+
     fn output_options(
         name: &str,
         output: &str,
@@ -370,7 +398,8 @@ mod tests {
             output: output.to_string(),
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
-logging: Some(String::new())
+            logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -430,7 +459,13 @@ logging: Some(String::new())
                 .json_body(json!({ "timeCreated": "whatever", "name":"mockme" }));
         });
 
-        let session = gcp_session(&format!("http://127.0.0.1:{port}"), &result).unwrap();
+        let session = gcp_session(
+            &Client::new(),
+            &format!("http://127.0.0.1:{port}"),
+            &result,
+            &test_policy(),
+        )
+        .unwrap();
         mock_me.assert();
 
         assert_eq!(session, format!("http://127.0.0.1:{port}"));
@@ -458,7 +493,14 @@ logging: Some(String::new())
 
         let data = [0, 1, 2, 3, 4];
 
-        gcp_resume_upload(&format!("http://127.0.0.1:{port}"), &data, 0).unwrap();
+        gcp_resume_upload(
+            &Client::new(),
+            &format!("http://127.0.0.1:{port}"),
+            &data,
+            &test_policy(),
+            1,
+        )
+        .unwrap();
         mock_me.assert();
         mock_me_resume.assert();
     }
@@ -476,7 +518,14 @@ logging: Some(String::new())
         });
         let data = [0, 1, 2, 3, 4];
 
-        gcp_resume_upload(&format!("http://127.0.0.1:{port}"), &data, 0).unwrap();
+        gcp_resume_upload(
+            &Client::new(),
+            &format!("http://127.0.0.1:{port}"),
+            &data,
+            &test_policy(),
+            1,
+        )
+        .unwrap();
         mock_me.assert();
     }
 
@@ -491,7 +540,8 @@ logging: Some(String::new())
                 .json_body(json!({ "timeCreated": "whatever", "name":"mockme" }));
         });
 
-        let size = gcp_get_upload_status(&format!("http://127.0.0.1:{port}"), "10").unwrap();
+        let size = gcp_get_upload_status(&Client::new(), &format!("http://127.0.0.1:{port}"), "10")
+            .unwrap();
         mock_me.assert();
 
         assert_eq!(size, 5);
@@ -507,7 +557,8 @@ logging: Some(String::new())
                 .json_body(json!({ "timeCreated": "whatever", "name":"mockme" }));
         });
 
-        let size = gcp_get_upload_status(&format!("http://127.0.0.1:{port}"), "10").unwrap();
+        let size = gcp_get_upload_status(&Client::new(), &format!("http://127.0.0.1:{port}"), "10")
+            .unwrap();
         mock_me.assert();
 
         assert_eq!(size, -1);
@@ -564,7 +615,10 @@ logging: Some(String::new())
             output: String::from("local"),
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
-logging: Some(String::new())
+            logging: Some(String::new()),
+            upload_attempts: Some(2),
+            upload_backoff_ms: Some(1),
+            ..Default::default()
         };
 
         let test = "A rust program";
@@ -576,7 +630,9 @@ logging: Some(String::new())
     #[should_panic(expected = "BadResponse")]
     fn test_upload_gcp_non_ok() {
         let server = MockServer::start();
-        let output = output_options("gcp_upload_test", "gcp", "tmp", false, server.port());
+        let mut output = output_options("gcp_upload_test", "gcp", "tmp", false, server.port());
+        output.upload_attempts = Some(2);
+        output.upload_backoff_ms = Some(1);
 
         let mock_me = server.mock(|when, then| {
             when.method(POST);
@@ -589,4 +645,21 @@ logging: Some(String::new())
         gcp_upload(test.as_bytes(), &output, name).unwrap();
         mock_me.assert();
     }
+
+    #[test]
+    #[should_panic(expected = "NonRetryable")]
+    fn test_upload_gcp_forbidden() {
+        let server = MockServer::start();
+        let output = output_options("gcp_upload_test", "gcp", "tmp", false, server.port());
+
+        let mock_me = server.mock(|when, then| {
+            when.method(POST);
+            then.status(403);
+        });
+        let test = "A rust program";
+        let name = "output";
+        let result = gcp_upload(test.as_bytes(), &output, name);
+        mock_me.assert_hits(1);
+        result.unwrap();
+    }
 }
@@ -1,4 +1,6 @@
 pub(crate) mod aws;
 pub(crate) mod azure;
+pub(crate) mod client;
 mod error;
 pub(crate) mod gcp;
+pub(crate) mod retry;
@@ -0,0 +1,274 @@
+use super::error::RemoteError;
+use crate::structs::toml::Output;
+use log::{error, warn};
+use reqwest::{blocking::Response, Error, StatusCode};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    thread::sleep,
+    time::Duration,
+};
+
+/// Max attempts and backoff used for remote upload requests
+pub(crate) struct RetryPolicy {
+    pub(crate) max_attempts: u32,
+    /**Base delay in milliseconds. Doubled after each failed attempt */
+    pub(crate) base_delay: u64,
+}
+
+/// Build the retry policy from the `Output` options
+pub(crate) fn retry_policy(output: &Output) -> RetryPolicy {
+    let default_attempts = 15;
+    let default_delay = 500;
+
+    RetryPolicy {
+        max_attempts: output.upload_attempts.unwrap_or(default_attempts).max(1),
+        base_delay: output.upload_backoff_ms.unwrap_or(default_delay),
+    }
+}
+
+/**
+ * Send a request until it succeeds or the policy max attempts is reached.
+ * Server errors (5xx), timeouts, and connection failures are retried using exponential backoff with full jitter.
+ * Any other non-2xx response is not retried and fails immediately
+ */
+pub(crate) fn send_with_retry<F>(
+    policy: &RetryPolicy,
+    target: &str,
+    mut send: F,
+) -> Result<Response, RemoteError>
+where
+    F: FnMut() -> Result<Response, Error>,
+{
+    let mut attempt = 1;
+    loop {
+        match send() {
+            Ok(response) => {
+                let status = response.status();
+                if status.is_success() {
+                    return Ok(response);
+                }
+                if !retryable_status(status) {
+                    error!(
+                        "[artemis-core] Non-retryable response {status} from {target}: {:?}",
+                        response.text()
+                    );
+                    return Err(RemoteError::NonRetryable);
+                }
+                if attempt >= policy.max_attempts {
+                    error!(
+                        "[artemis-core] Response {status} from {target} after {attempt} attempts: {:?}",
+                        response.text()
+                    );
+                    return Err(RemoteError::BadResponse);
+                }
+                warn!(
+                    "[artemis-core] Response {status} from {target} on attempt {attempt} out of {}",
+                    policy.max_attempts
+                );
+            }
+            Err(err) => {
+                if !retryable_error(&err) {
+                    error!("[artemis-core] Failed to send request to {target}: {err:?}");
+                    return Err(RemoteError::RemoteUpload);
+                }
+                if attempt >= policy.max_attempts {
+                    error!("[artemis-core] Failed to send request to {target} after {attempt} attempts: {err:?}");
+                    return Err(RemoteError::RemoteUpload);
+                }
+                warn!(
+                    "[artemis-core] Request to {target} failed on attempt {attempt} out of {}: {err:?}",
+                    policy.max_attempts
+                );
+            }
+        }
+
+        sleep(backoff_delay(policy, attempt));
+        attempt += 1;
+    }
+}
+
+/// Server errors, request timeouts, and rate limiting may succeed on another attempt
+pub(crate) fn retryable_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Timeouts and connection errors (refused, reset) may succeed on another attempt
+fn retryable_error(err: &Error) -> bool {
+    err.is_timeout() || err.is_connect() || err.is_request() || err.is_body()
+}
+
+/// Get a random delay between zero and the exponential backoff for the attempt. Capped at one (1) minute
+pub(crate) fn backoff_delay(policy: &RetryPolicy, attempt: u32) -> Duration {
+    let max_delay = 60000;
+    let backoff = policy
+        .base_delay
+        .saturating_mul(2_u64.saturating_pow(attempt.saturating_sub(1)))
+        .min(max_delay);
+    if backoff == 0 {
+        return Duration::from_millis(0);
+    }
+
+    // RandomState is seeded randomly for each instance
+    let jitter = RandomState::new().build_hasher().finish() % (backoff + 1);
+    Duration::from_millis(jitter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{backoff_delay, retry_policy, retryable_status, send_with_retry, RetryPolicy};
+    use crate::structs::toml::Output;
+    use httpmock::{Method::PUT, MockServer};
+    use reqwest::{blocking::Client, StatusCode};
+    use std::{net::TcpListener, time::Duration};
+
+    fn test_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: 1,
+        }
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let output = Output {
+            name: String::from("test_output"),
+            directory: String::from("dir"),
+            format: String::from("jsonl"),
+            compress: false,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("gcp"),
+            filter_name: Some(String::new()),
+            filter_script: Some(String::new()),
+            logging: Some(String::new()),
+            upload_backoff_ms: Some(100),
+            ..Default::default()
+        };
+
+        let policy = retry_policy(&output);
+        assert_eq!(policy.max_attempts, 15);
+        assert_eq!(policy.base_delay, 100);
+    }
+
+    #[test]
+    fn test_send_with_retry() {
+        let server = MockServer::start();
+        let mut unavailable = server.mock(|when, then| {
+            when.method(PUT);
+            then.status(503);
+        });
+        let mock_me = server.mock(|when, then| {
+            when.method(PUT);
+            then.status(200).body("uploaded");
+        });
+
+        let client = Client::new();
+        let url = server.url("/upload");
+        let mut attempts = 0;
+        let response = send_with_retry(&test_policy(), "mock server", || {
+            attempts += 1;
+            // Service is available again on the third attempt
+            if attempts == 3 {
+                unavailable.assert_hits(2);
+                unavailable.delete();
+            }
+            client.put(&url).body("data").send()
+        })
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().unwrap(), "uploaded");
+        assert_eq!(attempts, 3);
+        mock_me.assert_hits(1);
+    }
+
+    #[test]
+    fn test_send_with_retry_non_retryable() {
+        let server = MockServer::start();
+        let mock_me = server.mock(|when, then| {
+            when.method(PUT);
+            then.status(403);
+        });
+
+        let client = Client::new();
+        let url = server.url("/upload");
+        let result = send_with_retry(&test_policy(), "mock server", || client.put(&url).send());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Received non-retryable response from server"
+        );
+        mock_me.assert_hits(1);
+    }
+
+    #[test]
+    fn test_send_with_retry_max_attempts() {
+        let server = MockServer::start();
+        let mock_me = server.mock(|when, then| {
+            when.method(PUT);
+            then.status(500);
+        });
+
+        let client = Client::new();
+        let url = server.url("/upload");
+        let result = send_with_retry(&test_policy(), "mock server", || client.put(&url).send());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Received non-200 response from server"
+        );
+        mock_me.assert_hits(3);
+    }
+
+    #[test]
+    fn test_send_with_retry_connection_failure() {
+        // Nothing is listening on the port so the connection is refused
+        let closed_port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let client = Client::new();
+        let url = format!("http://127.0.0.1:{closed_port}");
+        let mut attempts = 0;
+        let result = send_with_retry(&test_policy(), "closed port", || {
+            attempts += 1;
+            client.put(&url).send()
+        });
+        assert_eq!(result.unwrap_err().to_string(), "Failed to upload data");
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!retryable_status(StatusCode::FORBIDDEN));
+        assert!(!retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        let policy = RetryPolicy {
+            max_attempts: 15,
+            base_delay: 500,
+        };
+        for attempt in 1..20 {
+            let delay = backoff_delay(&policy, attempt);
+            assert!(delay <= Duration::from_millis(60000));
+        }
+        assert!(backoff_delay(&policy, 1) <= Duration::from_millis(500));
+        assert!(backoff_delay(&policy, 3) <= Duration::from_millis(2000));
+
+        let policy = RetryPolicy {
+            max_attempts: 1,
+            base_delay: 0,
+        };
+        assert_eq!(backoff_delay(&policy, 5), Duration::from_millis(0));
+    }
+}
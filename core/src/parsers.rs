@@ -0,0 +1,24 @@
+/**
+ * Call artifact parsers directly without a TOML collection or the JavaScript runtime  
+ * Each parser returns typed results from the `common` crate. Serializing or outputting the results is left to the caller
+ */
+pub mod windows {
+    pub use crate::artifacts::os::windows::{
+        bits::{
+            error::BitsError,
+            parser::{grab_bits, grab_bits_path, grab_ese_bits},
+        },
+        prefetch::{
+            error::PrefetchError,
+            parser::{custom_prefetch_path, grab_prefetch},
+        },
+        shimdb::{
+            error::ShimdbError,
+            parser::{custom_shimdb_path, grab_shimdb},
+        },
+        shortcuts::{
+            error::LnkError,
+            parser::{grab_lnk_directory, grab_lnk_file, grab_shortcuts, parse_lnk_data},
+        },
+    };
+}
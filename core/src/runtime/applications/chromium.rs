@@ -1,4 +1,4 @@
-use crate::artifacts::applications::chromium;
+use crate::{artifacts::applications::chromium, utils::output::serialize_output};
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -6,7 +6,7 @@ use deno_core::{error::AnyError, op2};
 /// Get `Chromium` history for all users
 pub(crate) fn get_chromium_users_history() -> Result<String, AnyError> {
     let history = chromium::history::get_chromium_history()?;
-    let results = serde_json::to_string(&history)?;
+    let results = serialize_output(&history)?;
     Ok(results)
 }
 
@@ -15,7 +15,7 @@ pub(crate) fn get_chromium_users_history() -> Result<String, AnyError> {
 /// Get `Chromium` history from provided path
 pub(crate) fn get_chromium_history(#[string] path: String) -> Result<String, AnyError> {
     let history = chromium::history::history_query(&path)?;
-    let results = serde_json::to_string(&history)?;
+    let results = serialize_output(&history)?;
     Ok(results)
 }
 
@@ -24,7 +24,7 @@ pub(crate) fn get_chromium_history(#[string] path: String) -> Result<String, Any
 /// Get `Chromium` downloads for all users
 pub(crate) fn get_chromium_users_downloads() -> Result<String, AnyError> {
     let downloads = chromium::downloads::get_chromium_downloads()?;
-    let results = serde_json::to_string(&downloads)?;
+    let results = serialize_output(&downloads)?;
     Ok(results)
 }
 
@@ -33,7 +33,7 @@ pub(crate) fn get_chromium_users_downloads() -> Result<String, AnyError> {
 /// Get `Chromium` downloads from provided path
 pub(crate) fn get_chromium_downloads(#[string] path: String) -> Result<String, AnyError> {
     let downloads = chromium::downloads::downloads_query(&path)?;
-    let results = serde_json::to_string(&downloads)?;
+    let results = serialize_output(&downloads)?;
     Ok(results)
 }
 
@@ -58,6 +58,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
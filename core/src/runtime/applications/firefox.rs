@@ -1,4 +1,4 @@
-use crate::artifacts::applications::firefox;
+use crate::{artifacts::applications::firefox, utils::output::serialize_output};
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -6,7 +6,7 @@ use deno_core::{error::AnyError, op2};
 /// Get `Firefox` history for all users
 pub(crate) fn get_firefox_users_history() -> Result<String, AnyError> {
     let history = firefox::history::get_firefox_history()?;
-    let results = serde_json::to_string(&history)?;
+    let results = serialize_output(&history)?;
     Ok(results)
 }
 
@@ -15,7 +15,7 @@ pub(crate) fn get_firefox_users_history() -> Result<String, AnyError> {
 /// Get `Firefox` history from provided path
 pub(crate) fn get_firefox_history(#[string] path: String) -> Result<String, AnyError> {
     let history = firefox::history::history_query(&path)?;
-    let results = serde_json::to_string(&history)?;
+    let results = serialize_output(&history)?;
     Ok(results)
 }
 
@@ -25,7 +25,7 @@ pub(crate) fn get_firefox_history(#[string] path: String) -> Result<String, AnyE
 pub(crate) fn get_firefox_users_downloads() -> Result<String, AnyError> {
     let downloads = firefox::downloads::get_firefox_downloads()?;
 
-    let results = serde_json::to_string(&downloads)?;
+    let results = serialize_output(&downloads)?;
     Ok(results)
 }
 
@@ -34,7 +34,7 @@ pub(crate) fn get_firefox_users_downloads() -> Result<String, AnyError> {
 /// Get `Firefox` downloads from provided path
 pub(crate) fn get_firefox_downloads(#[string] path: String) -> Result<String, AnyError> {
     let downloads = firefox::downloads::downloads_query(&path)?;
-    let results = serde_json::to_string(&downloads)?;
+    let results = serialize_output(&downloads)?;
     Ok(results)
 }
 
@@ -59,6 +59,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
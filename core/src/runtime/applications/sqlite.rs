@@ -1,4 +1,6 @@
-use crate::utils::{encoding::base64_encode_standard, strings::extract_utf16_string};
+use crate::utils::{
+    encoding::base64_encode_standard, output::serialize_output, strings::extract_utf16_string,
+};
 use deno_core::{error::AnyError, op2};
 use log::error;
 use rusqlite::{
@@ -99,7 +101,7 @@ pub(crate) fn query_sqlite(
         data.push(json_data);
     }
 
-    let results = serde_json::to_string(&data)?;
+    let results = serialize_output(&data)?;
     Ok(results)
 }
 
@@ -124,6 +126,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -4,6 +4,7 @@
  */
 use super::{
     error::RuntimeError,
+    permissions::OpPolicy,
     run::{run_async_script, run_script},
 };
 use crate::{
@@ -33,10 +34,11 @@ pub(crate) fn filter_script(
 /// Execute raw JavaScript code
 pub(crate) fn raw_script(script: &str) -> Result<Value, RuntimeError> {
     let args = [];
+    let policy = OpPolicy::default();
     let result = if script.contains("async function ") || script.contains(" await ") {
-        run_async_script(script, &args)
+        run_async_script(script, &args, &policy)
     } else {
-        run_script(script, &args)
+        run_script(script, &args, &policy)
     };
 
     let status = match result {
@@ -67,7 +69,7 @@ fn decode_script(
         Ok(result) => result,
         Err(err) => {
             error!("[runtime] Could not base64 provided javascript script: {err:?}",);
-            return Err(RuntimeError::Decode);
+            return Err(RuntimeError::InvalidScript);
         }
     };
 
@@ -76,14 +78,15 @@ fn decode_script(
         Ok(result) => result,
         Err(err) => {
             error!("[runtime] Could not read javascript script as string: {err:?}");
-            return Err(RuntimeError::Decode);
+            return Err(RuntimeError::InvalidScript);
         }
     };
 
+    let policy = OpPolicy::from_output(output);
     let result = if script.contains("async function") || script.contains(" await ") {
-        run_async_script(script, args)
+        run_async_script(script, args, &policy)
     } else {
-        run_script(script, args)
+        run_script(script, args, &policy)
     };
     let script_value = match result {
         Ok(result) => result,
@@ -125,7 +128,7 @@ pub(crate) fn output_data(
 mod tests {
     use super::{decode_script, execute_script, filter_script, raw_script};
     use crate::{
-        runtime::deno::output_data,
+        runtime::{deno::output_data, error::RuntimeError},
         structs::{artifacts::runtime::script::JSScript, toml::Output},
         utils::time,
     };
@@ -144,6 +147,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -172,6 +176,29 @@ mod tests {
         execute_script(&mut output, &script).unwrap();
     }
 
+    #[test]
+    fn test_execute_script_invalid_base64() {
+        let mut output = output_options("runtime_test", "local", "./tmp", false);
+        let script = JSScript {
+            name: String::from("bad base64"),
+            script: String::from("console.log(\"not base64\");"),
+        };
+        let result = execute_script(&mut output, &script);
+        assert!(matches!(result, Err(RuntimeError::InvalidScript)));
+    }
+
+    #[test]
+    fn test_execute_script_invalid_utf8() {
+        let mut output = output_options("runtime_test", "local", "./tmp", false);
+        // Base64 of the bytes 0xff 0xfe 0xfd
+        let script = JSScript {
+            name: String::from("bad utf8"),
+            script: String::from("//79"),
+        };
+        let result = execute_script(&mut output, &script);
+        assert!(matches!(result, Err(RuntimeError::InvalidScript)));
+    }
+
     #[test]
     #[cfg(target_os = "macos")]
     fn test_advanced_decode_script() {
@@ -1,7 +1,10 @@
 use crate::runtime::encoding::{
     base64::{js_base64_decode, js_base64_encode},
     bytes::js_encode_bytes,
-    strings::{js_bytes_to_hex_string, js_extract_utf16_string, js_extract_utf8_string},
+    strings::{
+        js_bytes_to_hex_string, js_extract_string_auto, js_extract_utf16_string,
+        js_extract_utf8_string,
+    },
     uuid::{js_format_guid_be_bytes, js_format_guid_le_bytes, js_generate_uuid},
     xml::js_read_xml,
 };
@@ -13,6 +16,7 @@ pub(crate) fn enocoding_functions() -> Vec<deno_core::OpDecl> {
         js_base64_encode(),
         js_extract_utf8_string(),
         js_extract_utf16_string(),
+        js_extract_string_auto(),
         js_encode_bytes(),
         js_read_xml(),
         js_bytes_to_hex_string(),
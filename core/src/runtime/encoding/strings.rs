@@ -1,4 +1,4 @@
-use crate::utils::strings::{extract_utf16_string, extract_utf8_string};
+use crate::utils::strings::{extract_string_auto, extract_utf16_string, extract_utf8_string};
 use deno_core::{op2, JsBuffer};
 
 #[op2]
@@ -15,6 +15,13 @@ pub(crate) fn js_extract_utf16_string(#[buffer] data: JsBuffer) -> String {
     extract_utf16_string(&data)
 }
 
+#[op2]
+#[string]
+/// Attempt to extract a string from raw bytes based on the byte-order-mark. Defaults to UTF16LE
+pub(crate) fn js_extract_string_auto(#[buffer] data: JsBuffer) -> String {
+    extract_string_auto(&data)
+}
+
 #[op2]
 #[string]
 /// Attempt to represent bytes as a Hex string
@@ -44,9 +51,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -72,6 +77,17 @@ mod tests {
         execute_script(&mut output, &script).unwrap();
     }
 
+    #[test]
+    fn test_js_extract_string_auto() {
+        let test = "ZnVuY3Rpb24gZXh0cmFjdFN0cmluZ0F1dG8oZGF0YSkgewogIGNvbnN0IHJlc3VsdCA9IGVuY29kaW5nLmV4dHJhY3Rfc3RyaW5nX2F1dG8oZGF0YSk7CiAgcmV0dXJuIHJlc3VsdDsKfQoKLy8gbWFpbi50cwpmdW5jdGlvbiBtYWluKCkgewogIGNvbnN0IHZhbHVlID0gVWludDhBcnJheS5mcm9tKFsyNTUsIDI1NCwgNzksIDAsIDgzLCAwLCA4MSwgMCwgODUsIDAsIDY5LCAwLCA4MiwgMCwgODksIDAsIDY4LCAwLCAwLCAwXSk7CiAgY29uc3QgcmVzdWx0ID0gZXh0cmFjdFN0cmluZ0F1dG8odmFsdWUpOwogIGNvbnNvbGUubG9nKHJlc3VsdCk7CiAgcmV0dXJuIHJlc3VsdDsKfQptYWluKCk7Cg==";
+        let mut output = output_options("runtime_test", "local", "./tmp", false);
+        let script = JSScript {
+            name: String::from("strings_test"),
+            script: test.to_string(),
+        };
+        execute_script(&mut output, &script).unwrap();
+    }
+
     #[test]
     fn test_js_bytes_to_hex_string() {
         let test = "Ly8gaHR0cHM6Ly9yYXcuZ2l0aHVidXNlcmNvbnRlbnQuY29tL3B1ZmZ5Y2lkL2FydGVtaXMtYXBpL21hc3Rlci9zcmMvZW5jb2Rpbmcvc3RyaW5ncy50cwpmdW5jdGlvbiBieXRlc1RvSGV4U3RyaW5nKGRhdGEpIHsKICBjb25zdCByZXN1bHQgPSBlbmNvZGluZy5ieXRlc190b19oZXhfc3RyaW5nKGRhdGEpOwogIHJldHVybiByZXN1bHQ7Cn0KCi8vIG1haW4udHMKZnVuY3Rpb24gbWFpbigpIHsKICBjb25zdCB2YWx1ZSA9IFVpbnQ4QXJyYXkuZnJvbShbNzksIDgzLCA4MSwgODUsIDY5LCA4MiwgODksIDY4LCA0NiwgNjksIDg4LCA2OSwgMF0pOwogIGNvbnN0IHJlc3VsdCA9IGJ5dGVzVG9IZXhTdHJpbmcodmFsdWUpOwogIGNvbnNvbGUubG9nKHJlc3VsdCk7CiAgcmV0dXJuIHJlc3VsdDsKfQptYWluKCk7";
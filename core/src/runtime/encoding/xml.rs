@@ -1,4 +1,4 @@
-use crate::utils::encoding::read_xml;
+use crate::utils::{encoding::read_xml, output::serialize_output};
 use deno_core::{error::AnyError, op2};
 use xml2json_rs::JsonBuilder;
 
@@ -13,7 +13,7 @@ pub(crate) fn js_read_xml(#[string] path: String) -> Result<String, AnyError> {
     let xml_builder = JsonBuilder::default();
     let xml_json = xml_builder.build_from_xml(&xml)?;
 
-    let json = serde_json::to_string(&xml_json)?;
+    let json = serialize_output(&xml_json)?;
     Ok(json)
 }
 
@@ -35,9 +35,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -1,5 +1,5 @@
-use crate::utils::environment::{get_env, get_env_value};
-use deno_core::op2;
+use crate::utils::environment::{get_env, get_env_value, get_environment_value};
+use deno_core::{error::AnyError, op2};
 use std::collections::HashMap;
 
 #[op2]
@@ -16,6 +16,17 @@ pub(crate) fn js_env_value(#[string] var: String) -> String {
     get_env_value(&var)
 }
 
+#[op2]
+#[string]
+/**
+ * Get a specific environment variable. Returns an empty string if the variable is not set  
+ * On Windows the system environment in the Registry is checked if the artemis process does not have the variable
+ */
+pub(crate) fn environment_value(#[string] key: String) -> Result<String, AnyError> {
+    let value = get_environment_value(&key)?;
+    Ok(value)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::runtime::deno::execute_script;
@@ -36,6 +47,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -73,4 +85,15 @@ mod tests {
         };
         execute_script(&mut output, &script).unwrap();
     }
+
+    #[test]
+    fn test_environment_value_unset() {
+        let test = "Ly8gaHR0cHM6Ly9yYXcuZ2l0aHVidXNlcmNvbnRlbnQuY29tL3B1ZmZ5Y2lkL2FydGVtaXMtYXBpL21hc3Rlci9zcmMvZW52aXJvbm1lbnQvZW52LnRzCmZ1bmN0aW9uIGdldEVudlZhbHVlKGtleSkgewogIGNvbnN0IGRhdGEgPSBlbnYuZW52aXJvbm1lbnRWYWx1ZShrZXkpOwogIHJldHVybiBkYXRhOwp9CgovLyBtYWluLnRzCmZ1bmN0aW9uIG1haW4oKSB7CiAgY29uc3QgdmFsdWUgPSBnZXRFbnZWYWx1ZSgiQVJURU1JU19VTlNFVF9FTlZfVkFMVUUiKTsKICBpZiAodmFsdWUgIT09ICIiKSB7CiAgICB0aHJvdyBuZXcgRXJyb3IoYEV4cGVjdGVkIGVtcHR5IHZhbHVlLCBnb3QgJHt2YWx1ZX1gKTsKICB9CiAgcmV0dXJuIHZhbHVlOwp9Cm1haW4oKTsK";
+        let mut output = output_options("runtime_test", "local", "./tmp", false);
+        let script = JSScript {
+            name: String::from("env_unset"),
+            script: test.to_string(),
+        };
+        execute_script(&mut output, &script).unwrap();
+    }
 }
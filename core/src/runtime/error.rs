@@ -2,10 +2,11 @@ use std::fmt;
 
 #[derive(Debug)]
 pub(crate) enum RuntimeError {
-    Decode,
+    InvalidScript,
     ExecuteScript,
     ScriptResult,
     Output,
+    OpNotPermitted,
 }
 
 impl std::error::Error for RuntimeError {}
@@ -13,10 +14,13 @@ impl std::error::Error for RuntimeError {}
 impl fmt::Display for RuntimeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            RuntimeError::Decode => write!(f, "Could not base64 javascript script"),
+            RuntimeError::InvalidScript => {
+                write!(f, "Script is not valid base64 encoded UTF8 javascript")
+            }
             RuntimeError::ExecuteScript => write!(f, "Could not run script"),
             RuntimeError::ScriptResult => write!(f, "Could not get script result"),
             RuntimeError::Output => write!(f, "Could not output data"),
+            RuntimeError::OpNotPermitted => write!(f, "Op not permitted"),
         }
     }
 }
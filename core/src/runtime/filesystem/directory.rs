@@ -5,6 +5,7 @@ use crate::{
         metadata::{get_metadata, get_timestamps},
     },
     runtime::error::RuntimeError,
+    utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -85,7 +86,7 @@ pub(crate) async fn js_read_dir(#[string] path: String) -> Result<String, AnyErr
         files.push(info);
     }
 
-    let data = serde_json::to_string(&files)?;
+    let data = serialize_output(&files)?;
     Ok(data)
 }
 
@@ -109,6 +110,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -3,6 +3,7 @@ use crate::filesystem::{
     files::{file_extension, get_filename, hash_file, read_file, read_text_file, Hashes},
     metadata::{get_metadata, get_timestamps, glob_paths},
 };
+use crate::utils::output::serialize_output;
 use deno_core::{error::AnyError, op2};
 use serde::Serialize;
 use std::path::Path;
@@ -46,7 +47,7 @@ pub(crate) fn js_stat(#[string] path: String) -> Result<String, AnyError> {
         info.gid = meta.gid();
     }
 
-    let data = serde_json::to_string(&info)?;
+    let data = serialize_output(&info)?;
 
     Ok(data)
 }
@@ -56,7 +57,7 @@ pub(crate) fn js_stat(#[string] path: String) -> Result<String, AnyError> {
 /// Return glob info based on provided glob string
 pub(crate) fn js_glob(#[string] glob: String) -> Result<String, AnyError> {
     let globs = glob_paths(&glob)?;
-    let data = serde_json::to_string(&globs)?;
+    let data = serialize_output(&globs)?;
 
     Ok(data)
 }
@@ -122,6 +123,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
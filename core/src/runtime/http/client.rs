@@ -1,4 +1,4 @@
-use crate::utils::strings::extract_utf8_string;
+use crate::utils::{output::serialize_output, strings::extract_utf8_string};
 use deno_core::{error::AnyError, op2, JsBuffer};
 use log::error;
 use nom::AsBytes;
@@ -114,7 +114,7 @@ pub(crate) async fn js_request(
         body: res_result.bytes().await?.to_vec(),
     };
 
-    let results = serde_json::to_string(&res)?;
+    let results = serialize_output(&res)?;
     Ok(results)
 }
 
@@ -142,9 +142,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
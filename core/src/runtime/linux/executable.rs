@@ -1,4 +1,6 @@
-use crate::artifacts::os::linux::executable::parser::parse_elf_file;
+use crate::{
+    artifacts::os::linux::executable::parser::parse_elf_file, utils::output::serialize_output,
+};
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -6,7 +8,7 @@ use deno_core::{error::AnyError, op2};
 /// Expose parsing elf file  to `Deno`
 pub(crate) fn get_elf(#[string] path: String) -> Result<String, AnyError> {
     let elf_data = parse_elf_file(&path)?;
-    let results = serde_json::to_string(&elf_data)?;
+    let results = serialize_output(&elf_data)?;
     Ok(results)
 }
 
@@ -28,9 +30,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -1,4 +1,6 @@
-use crate::artifacts::os::linux::logons::parser::grab_logon_file;
+use crate::{
+    artifacts::os::linux::logons::parser::grab_logon_file, utils::output::serialize_output,
+};
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -8,7 +10,7 @@ pub(crate) fn get_logon(#[string] path: String) -> Result<String, AnyError> {
     let mut logons = Vec::new();
     grab_logon_file(&path, &mut logons);
 
-    let results = serde_json::to_string(&logons)?;
+    let results = serialize_output(&logons)?;
     Ok(results)
 }
 
@@ -30,9 +32,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
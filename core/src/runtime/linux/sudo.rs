@@ -1,6 +1,6 @@
 use crate::{
     artifacts::os::linux::sudo::logs::grab_sudo_logs, runtime::error::RuntimeError,
-    structs::artifacts::os::linux::LinuxSudoOptions,
+    structs::artifacts::os::linux::LinuxSudoOptions, utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -23,7 +23,7 @@ pub(crate) fn get_sudologs_linux(#[string] path: String) -> Result<String, AnyEr
             return Err(RuntimeError::ExecuteScript.into());
         }
     };
-    let results = serde_json::to_string(&sudo)?;
+    let results = serialize_output(&sudo)?;
     Ok(results)
 }
 
@@ -48,6 +48,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
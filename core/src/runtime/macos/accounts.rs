@@ -1,6 +1,7 @@
 use crate::{
     artifacts::os::macos::accounts::{groups::grab_groups, users::grab_users},
     structs::artifacts::os::macos::{MacosGroupsOptions, MacosUsersOptions},
+    utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 
@@ -15,7 +16,7 @@ pub(crate) fn get_users_macos(#[string] path: String) -> Result<String, AnyError
     let users = grab_users(&MacosUsersOptions {
         alt_path: Some(user_path),
     });
-    let results = serde_json::to_string(&users)?;
+    let results = serialize_output(&users)?;
     Ok(results)
 }
 
@@ -30,7 +31,7 @@ pub(crate) fn get_groups_macos(#[string] path: String) -> Result<String, AnyErro
     let groups = grab_groups(&MacosGroupsOptions {
         alt_path: Some(group_path),
     });
-    let results = serde_json::to_string(&groups)?;
+    let results = serialize_output(&groups)?;
     Ok(results)
 }
 
@@ -55,6 +56,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -1,5 +1,6 @@
 use crate::{
     artifacts::os::macos::emond::parser::grab_emond, structs::artifacts::os::macos::EmondOptions,
+    utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 
@@ -15,7 +16,7 @@ pub(crate) fn get_emond(#[string] path: String) -> Result<String, AnyError> {
         }
     };
     let emond = grab_emond(&options)?;
-    let results = serde_json::to_string(&emond)?;
+    let results = serialize_output(&emond)?;
     Ok(results)
 }
 
@@ -40,6 +41,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
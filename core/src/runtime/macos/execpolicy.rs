@@ -1,6 +1,6 @@
 use crate::{
     artifacts::os::macos::execpolicy::policy::grab_execpolicy,
-    structs::artifacts::os::macos::ExecPolicyOptions,
+    structs::artifacts::os::macos::ExecPolicyOptions, utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 
@@ -11,7 +11,7 @@ pub(crate) fn get_execpolicy(#[string] path: String) -> Result<String, AnyError>
     let policy = grab_execpolicy(&ExecPolicyOptions {
         alt_file: Some(path),
     })?;
-    let results = serde_json::to_string(&policy)?;
+    let results = serialize_output(&policy)?;
     Ok(results)
 }
 
@@ -36,6 +36,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -1,4 +1,6 @@
-use crate::artifacts::os::macos::fsevents::parser::grab_fsventsd_file;
+use crate::{
+    artifacts::os::macos::fsevents::parser::grab_fsventsd_file, utils::output::serialize_output,
+};
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -6,7 +8,7 @@ use deno_core::{error::AnyError, op2};
 /// Expose parsing FsEvents to `Deno`
 pub(crate) fn get_fsevents(#[string] path: String) -> Result<String, AnyError> {
     let fsevents = grab_fsventsd_file(&path)?;
-    let results = serde_json::to_string(&fsevents)?;
+    let results = serialize_output(&fsevents)?;
     Ok(results)
 }
 
@@ -31,6 +33,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -1,6 +1,7 @@
 use crate::artifacts::os::macos::launchd::launchdaemon::{
     grab_launchd_agents, grab_launchd_daemons,
 };
+use crate::utils::output::serialize_output;
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -8,7 +9,7 @@ use deno_core::{error::AnyError, op2};
 /// Expose parsing launchd daemons to `Deno`
 pub(crate) fn get_launchd_daemons() -> Result<String, AnyError> {
     let launchd = grab_launchd_daemons()?;
-    let results = serde_json::to_string(&launchd)?;
+    let results = serialize_output(&launchd)?;
     Ok(results)
 }
 
@@ -17,7 +18,7 @@ pub(crate) fn get_launchd_daemons() -> Result<String, AnyError> {
 /// Expose parsing launchd agents to `Deno`
 pub(crate) fn get_launchd_agents() -> Result<String, AnyError> {
     let launchd = grab_launchd_agents()?;
-    let results = serde_json::to_string(&launchd)?;
+    let results = serialize_output(&launchd)?;
     Ok(results)
 }
 
@@ -44,6 +45,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -1,6 +1,6 @@
 use crate::{
     artifacts::os::macos::loginitems::parser::grab_loginitems,
-    structs::artifacts::os::macos::LoginitemsOptions,
+    structs::artifacts::os::macos::LoginitemsOptions, utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 
@@ -16,7 +16,7 @@ pub(crate) fn get_loginitems(#[string] path: String) -> Result<String, AnyError>
         }
     };
     let loginitems = grab_loginitems(&options)?;
-    let results = serde_json::to_string(&loginitems)?;
+    let results = serialize_output(&loginitems)?;
     Ok(results)
 }
 
@@ -41,6 +41,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -1,4 +1,4 @@
-use crate::artifacts::os::macos::macho::parser::parse_macho;
+use crate::{artifacts::os::macos::macho::parser::parse_macho, utils::output::serialize_output};
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -6,7 +6,7 @@ use deno_core::{error::AnyError, op2};
 /// Expose parsing macho file  to `Deno`
 pub(crate) fn get_macho(#[string] path: String) -> Result<String, AnyError> {
     let macho = parse_macho(&path)?;
-    let results = serde_json::to_string(&macho)?;
+    let results = serialize_output(&macho)?;
     Ok(results)
 }
 
@@ -31,6 +31,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -1,4 +1,7 @@
-use crate::artifacts::os::macos::plist::property_list::{parse_plist_data, parse_plist_file};
+use crate::{
+    artifacts::os::macos::plist::property_list::{parse_plist_data, parse_plist_file},
+    utils::output::serialize_output,
+};
 use deno_core::{error::AnyError, op2, JsBuffer};
 use log::error;
 
@@ -7,7 +10,7 @@ use log::error;
 /// Expose parsing plist file  to `Deno`
 pub(crate) fn get_plist(#[string] path: String) -> Result<String, AnyError> {
     let plist = parse_plist_file(&path)?;
-    let results = serde_json::to_string(&plist)?;
+    let results = serialize_output(&plist)?;
     Ok(results)
 }
 
@@ -24,7 +27,7 @@ pub(crate) fn get_plist_data(#[buffer] data: JsBuffer) -> Result<String, AnyErro
             return Err(err.into());
         }
     };
-    let results = serde_json::to_string(&plist)?;
+    let results = serialize_output(&plist)?;
     Ok(results)
 }
 
@@ -51,6 +54,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -1,4 +1,4 @@
-use crate::artifacts::applications::safari;
+use crate::{artifacts::applications::safari, utils::output::serialize_output};
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -6,7 +6,7 @@ use deno_core::{error::AnyError, op2};
 /// Get `Safari` history for all users
 pub(crate) fn get_safari_users_history() -> Result<String, AnyError> {
     let history = safari::history::get_safari_history()?;
-    let results = serde_json::to_string(&history)?;
+    let results = serialize_output(&history)?;
     Ok(results)
 }
 
@@ -15,7 +15,7 @@ pub(crate) fn get_safari_users_history() -> Result<String, AnyError> {
 /// Get `Safari` history from provided path
 pub(crate) fn get_safari_history(#[string] path: String) -> Result<String, AnyError> {
     let history = safari::history::history_query(&path)?;
-    let results = serde_json::to_string(&history)?;
+    let results = serialize_output(&history)?;
     Ok(results)
 }
 
@@ -24,7 +24,7 @@ pub(crate) fn get_safari_history(#[string] path: String) -> Result<String, AnyEr
 /// Get `Safari` downloads for all users
 pub(crate) fn get_safari_users_downloads() -> Result<String, AnyError> {
     let downloads = safari::downloads::get_safari_downloads()?;
-    let results = serde_json::to_string(&downloads)?;
+    let results = serialize_output(&downloads)?;
     Ok(results)
 }
 
@@ -33,7 +33,7 @@ pub(crate) fn get_safari_users_downloads() -> Result<String, AnyError> {
 /// Get `Safari` downloads from provided path
 pub(crate) fn get_safari_downloads(#[string] path: String) -> Result<String, AnyError> {
     let downloads = safari::downloads::downloads_query(&path)?;
-    let results = serde_json::to_string(&downloads)?;
+    let results = serialize_output(&downloads)?;
     Ok(results)
 }
 
@@ -58,6 +58,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
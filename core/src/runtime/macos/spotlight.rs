@@ -1,6 +1,7 @@
 use crate::artifacts::os::macos::spotlight::light::{
     parse_spotlight_reader, setup_spotlight_reader, StoreMeta,
 };
+use crate::utils::output::serialize_output;
 use deno_core::{error::AnyError, op2};
 use log::error;
 
@@ -28,7 +29,7 @@ pub(crate) fn get_spotlight(
         &(offset as u32),
     )?;
 
-    let results = serde_json::to_string(&entries)?;
+    let results = serialize_output(&entries)?;
     Ok(results)
 }
 
@@ -38,7 +39,7 @@ pub(crate) fn get_spotlight(
 pub(crate) fn setup_spotlight_parser(#[string] glob_path: String) -> Result<String, AnyError> {
     let meta = setup_spotlight_reader(&glob_path)?;
 
-    let results = serde_json::to_string(&meta)?;
+    let results = serialize_output(&meta)?;
     Ok(results)
 }
 
@@ -65,6 +66,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -1,4 +1,7 @@
-use crate::{artifacts::os::macos::sudo::logs::grab_sudo_logs, runtime::error::RuntimeError};
+use crate::{
+    utils::output::serialize_output,
+    {artifacts::os::macos::sudo::logs::grab_sudo_logs, runtime::error::RuntimeError},
+};
 use deno_core::{error::AnyError, op2};
 use log::error;
 use macos_unifiedlogs::parser::{
@@ -34,7 +37,7 @@ pub(crate) fn get_sudologs_macos(#[string] logarchive_path: String) -> Result<St
             return Err(RuntimeError::ExecuteScript.into());
         }
     };
-    let results = serde_json::to_string(&sudo)?;
+    let results = serialize_output(&sudo)?;
     Ok(results)
 }
 
@@ -59,6 +62,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
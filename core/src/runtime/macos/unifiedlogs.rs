@@ -1,4 +1,4 @@
-use crate::runtime::error::RuntimeError;
+use crate::{runtime::error::RuntimeError, utils::output::serialize_output};
 use deno_core::{error::AnyError, op2};
 use log::error;
 use macos_unifiedlogs::{
@@ -35,7 +35,7 @@ pub(crate) fn get_unified_log(
 
     let logs = parse_trace_file(&uuid, &shared, &timesync, &path)?;
 
-    let results = serde_json::to_string(&logs)?;
+    let results = serialize_output(&logs)?;
     Ok(results)
 }
 
@@ -87,6 +87,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
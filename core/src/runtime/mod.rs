@@ -9,6 +9,7 @@ mod http;
 mod linux;
 mod macos;
 mod nom;
+mod permissions;
 mod run;
 mod system;
 mod time;
@@ -1,3 +1,4 @@
+use crate::utils::output::serialize_output;
 use deno_core::{anyhow::anyhow, error::AnyError, op2, JsBuffer, ToJsBuffer};
 use nom::bytes::complete::{take, take_until, take_while};
 use serde::Serialize;
@@ -24,7 +25,7 @@ pub(crate) fn js_nom_take_string(
         remaining: remaining.to_string(),
         nommed,
     };
-    let results = serde_json::to_string(&nom_string)?;
+    let results = serialize_output(&nom_string)?;
 
     Ok(results)
 }
@@ -83,7 +84,7 @@ pub(crate) fn js_nom_take_until_string(
         remaining: remaining.to_string(),
         nommed,
     };
-    let results = serde_json::to_string(&nom_string)?;
+    let results = serialize_output(&nom_string)?;
 
     Ok(results)
 }
@@ -136,7 +137,7 @@ pub(crate) fn js_nom_take_while_string(
         remaining: remaining.to_string(),
         nommed,
     };
-    let results = serde_json::to_string(&nom_string)?;
+    let results = serialize_output(&nom_string)?;
 
     Ok(results)
 }
@@ -198,9 +199,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -0,0 +1,108 @@
+/**
+ * Restrict which `Artemis` ops a script may call
+ * The allowlist and denylist come from the collection `Output` options. Blocked ops are replaced in Rust when the runtime is created
+ * and throw an "Op not permitted" error back to the script instead of executing
+ */
+use super::error::RuntimeError;
+use crate::structs::toml::Output;
+use deno_core::{error::AnyError, op2, Extension, OpDecl, OpState};
+use log::error;
+use std::collections::HashSet;
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct OpPolicy {
+    pub(crate) allow: Option<Vec<String>>,
+    pub(crate) deny: Option<Vec<String>>,
+}
+
+impl OpPolicy {
+    /// Get the op allowlist and denylist from the `Output` options
+    pub(crate) fn from_output(output: &Output) -> OpPolicy {
+        OpPolicy {
+            allow: output.script_allow_ops.clone(),
+            deny: output.script_deny_ops.clone(),
+        }
+    }
+
+    /// Check if an op may be called. The denylist takes precedence over the allowlist
+    pub(crate) fn is_permitted(&self, op: &str) -> bool {
+        if let Some(deny) = &self.deny {
+            if deny.iter().any(|entry| entry == op) {
+                return false;
+            }
+        }
+
+        match &self.allow {
+            Some(allow) => allow.iter().any(|entry| entry == op),
+            None => true,
+        }
+    }
+}
+
+/**
+ * Create the extension that enforces the policy. The policy is stored in the `OpState`
+ * Blocked `Artemis` ops are swapped for `op_not_permitted` when the runtime registers its ops so the op body can never run
+ */
+pub(crate) fn policy_extension(ops: &[&'static str], policy: &OpPolicy) -> Extension {
+    let blocked: HashSet<&'static str> = ops
+        .iter()
+        .filter(|op| !policy.is_permitted(op))
+        .copied()
+        .collect();
+    let state_policy = policy.clone();
+
+    Extension {
+        name: "artemis_policy",
+        ops: vec![op_not_permitted()].into(),
+        op_state_fn: Some(Box::new(move |state: &mut OpState| {
+            state.put(state_policy);
+        })),
+        middleware_fn: Some(Box::new(move |op: OpDecl| {
+            if blocked.contains(op.name) {
+                return op.with_implementation_from(&op_not_permitted());
+            }
+            op
+        })),
+        ..Default::default()
+    }
+}
+
+#[op2(fast)]
+/// Called in place of any op blocked by the `OpPolicy`
+fn op_not_permitted(state: &mut OpState) -> Result<(), AnyError> {
+    let policy = state.borrow::<OpPolicy>();
+    error!("[runtime] Script called an op blocked by the op policy: {policy:?}");
+    Err(RuntimeError::OpNotPermitted.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{policy_extension, OpPolicy};
+
+    #[test]
+    fn test_is_permitted() {
+        let policy = OpPolicy::default();
+        assert!(policy.is_permitted("get_processes"));
+
+        let policy = OpPolicy {
+            allow: Some(vec![String::from("js_hostname"), String::from("js_uptime")]),
+            deny: Some(vec![String::from("js_uptime")]),
+        };
+        assert!(policy.is_permitted("js_hostname"));
+        assert!(!policy.is_permitted("js_uptime"));
+        assert!(!policy.is_permitted("get_processes"));
+    }
+
+    #[test]
+    fn test_policy_extension() {
+        let policy = OpPolicy {
+            allow: None,
+            deny: Some(vec![String::from("js_hostname")]),
+        };
+        let results = policy_extension(&["js_hostname", "js_uptime"], &policy);
+        assert_eq!(results.name, "artemis_policy");
+        assert_eq!(results.ops.len(), 1);
+        assert!(results.op_state_fn.is_some());
+        assert!(results.middleware_fn.is_some());
+    }
+}
@@ -1,5 +1,6 @@
 use super::linux::extensions::setup_linux_extensions;
 use super::macos::extensions::setup_macos_extensions;
+use super::permissions::{policy_extension, OpPolicy};
 use super::windows::extensions::setup_windows_extensions;
 use crate::runtime::error::RuntimeError;
 use deno_core::error::{custom_error, AnyError, JsError};
@@ -13,8 +14,12 @@ use std::rc::Rc;
 static RUNTIME_SNAPSHOT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/RUNJS_SNAPSHOT.bin"));
 
 /// Execute the decoded Javascript and return a `serde_json` Value
-pub(crate) fn run_script(script: &str, args: &[String]) -> Result<Value, AnyError> {
-    let mut runtime = create_worker_options()?;
+pub(crate) fn run_script(
+    script: &str,
+    args: &[String],
+    policy: &OpPolicy,
+) -> Result<Value, AnyError> {
+    let mut runtime = create_worker_options(policy)?;
 
     // Scripts executed via `execute_script` are run in a global context.
     let scripts_args = format!("const STATIC_ARGS = {args:?}");
@@ -58,8 +63,12 @@ pub(crate) fn run_script(script: &str, args: &[String]) -> Result<Value, AnyErro
 
 #[tokio::main(flavor = "current_thread")]
 /// Execute the decoded async Javascript and return the data asynchronously
-pub(crate) async fn run_async_script(script: &str, args: &[String]) -> Result<Value, AnyError> {
-    let mut runtime = create_worker_options()?;
+pub(crate) async fn run_async_script(
+    script: &str,
+    args: &[String],
+    policy: &OpPolicy,
+) -> Result<Value, AnyError> {
+    let mut runtime = create_worker_options(policy)?;
 
     // Scripts executed via `execute_script` are run in a global context.
     let scripts_args = format!("const STATIC_ARGS = {args:?}");
@@ -134,8 +143,8 @@ fn get_error_class_name(e: &AnyError) -> &'static str {
         .unwrap_or("[runtime] script execution class error")
 }
 
-/// Create the Deno runtime worker options. Pass optional args
-fn create_worker_options() -> Result<JsRuntime, AnyError> {
+/// Create the Deno runtime worker options. Ops blocked by the policy throw an error when called
+fn create_worker_options(policy: &OpPolicy) -> Result<JsRuntime, AnyError> {
     // This may be required for Linux? Not 100% sure. It runs fine without it. Ref: https://github.com/denoland/deno/pull/20495. May depend on V8 version (rusty_v8)
     //JsRuntime::init_platform(None);
 
@@ -152,6 +161,11 @@ fn create_worker_options() -> Result<JsRuntime, AnyError> {
     extensions = setup_macos_extensions();
     extensions.append(&mut setup_linux_extensions());
     extensions.append(&mut setup_windows_extensions());
+    let ops: Vec<&'static str> = extensions
+        .iter()
+        .flat_map(|extension| extension.ops.iter().map(|op| op.name))
+        .collect();
+    extensions.push(policy_extension(&ops, policy));
 
     let runtime = JsRuntime::new(RuntimeOptions {
         source_map_getter: None,
@@ -182,29 +196,59 @@ fn create_worker_options() -> Result<JsRuntime, AnyError> {
 #[cfg(test)]
 mod tests {
     use super::{create_worker_options, get_error_class_name, run_script};
-    use crate::runtime::{error::RuntimeError, run::run_async_script};
+    use crate::runtime::{error::RuntimeError, permissions::OpPolicy, run::run_async_script};
 
     #[test]
     fn test_create_worker_options() {
-        let results = create_worker_options().unwrap();
+        let results = create_worker_options(&OpPolicy::default()).unwrap();
         assert!(results.op_names().len() > 2);
     }
 
     #[test]
     fn test_run_script() {
-        let results = run_script("console.log('hello rust!')", &[]).unwrap();
+        let results = run_script("console.log('hello rust!')", &[], &OpPolicy::default()).unwrap();
         assert!(results.is_null());
     }
 
     #[test]
     fn test_run_async_script() {
-        let results = run_async_script("console.error('hello async rust!')", &[]).unwrap();
+        let results = run_async_script(
+            "console.error('hello async rust!')",
+            &[],
+            &OpPolicy::default(),
+        )
+        .unwrap();
         assert!(results.is_null());
     }
 
+    #[test]
+    fn test_run_script_op_not_permitted() {
+        let policy = OpPolicy {
+            allow: None,
+            deny: Some(vec![String::from("js_hostname")]),
+        };
+        let script = "try { Deno.core.ops.js_hostname(); } catch (err) { err.message; }";
+        let results = run_script(script, &[], &policy).unwrap();
+        assert_eq!(results.as_str().unwrap(), "Op not permitted");
+    }
+
+    #[test]
+    fn test_run_script_op_permitted() {
+        let policy = OpPolicy {
+            allow: Some(vec![String::from("js_hostname")]),
+            deny: None,
+        };
+        let results = run_script("Deno.core.ops.js_hostname();", &[], &policy).unwrap();
+        assert!(!results.as_str().unwrap().is_empty());
+
+        let script = "try { Deno.core.ops.js_uptime(); } catch (err) { err.message; }";
+        let results = run_script(script, &[], &policy).unwrap();
+        assert_eq!(results.as_str().unwrap(), "Op not permitted");
+    }
+
     #[test]
     fn test_get_error_class_name() {
-        let err = RuntimeError::Decode;
+        let err = RuntimeError::InvalidScript;
         let results = get_error_class_name(&err.into());
         assert_eq!(results, "Error");
     }
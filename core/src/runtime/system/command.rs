@@ -1,4 +1,4 @@
-use crate::utils::strings::extract_utf8_string;
+use crate::utils::{output::serialize_output, strings::extract_utf8_string};
 use deno_core::{error::AnyError, op2};
 use log::warn;
 use serde::Serialize;
@@ -35,7 +35,7 @@ pub(crate) fn js_command(
         stderr: extract_utf8_string(&out.stderr),
     };
 
-    let results = serde_json::to_string(&comm_result)?;
+    let results = serialize_output(&comm_result)?;
     Ok(results)
 }
 
@@ -57,9 +57,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -1,4 +1,4 @@
-use crate::artifacts::os::systeminfo::info::get_cpu;
+use crate::{artifacts::os::systeminfo::info::get_cpu, utils::output::serialize_output};
 use deno_core::{error::AnyError, op2};
 use sysinfo::System;
 
@@ -8,7 +8,7 @@ use sysinfo::System;
 pub(crate) fn js_cpu_info() -> Result<String, AnyError> {
     let mut info = System::new();
     let cpu = get_cpu(&mut info);
-    let results = serde_json::to_string(&cpu)?;
+    let results = serialize_output(&cpu)?;
     Ok(results)
 }
 
@@ -30,9 +30,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -1,4 +1,4 @@
-use crate::artifacts::os::systeminfo::info::get_disks;
+use crate::{artifacts::os::systeminfo::info::get_disks, utils::output::serialize_output};
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -6,7 +6,7 @@ use deno_core::{error::AnyError, op2};
 /// Return disk info about the system
 pub(crate) fn js_disk_info() -> Result<String, AnyError> {
     let disks = get_disks();
-    let results = serde_json::to_string(&disks)?;
+    let results = serialize_output(&disks)?;
     Ok(results)
 }
 
@@ -28,9 +28,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -1,4 +1,4 @@
-use crate::artifacts::os::systeminfo::info::get_memory;
+use crate::{artifacts::os::systeminfo::info::get_memory, utils::output::serialize_output};
 use deno_core::{error::AnyError, op2};
 use sysinfo::System;
 
@@ -8,7 +8,7 @@ use sysinfo::System;
 pub(crate) fn js_memory_info() -> Result<String, AnyError> {
     let mut info = System::new();
     let mem = get_memory(&mut info);
-    let results = serde_json::to_string(&mem)?;
+    let results = serialize_output(&mem)?;
     Ok(results)
 }
 
@@ -30,9 +30,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
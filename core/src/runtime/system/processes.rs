@@ -1,4 +1,7 @@
-use crate::{artifacts::os::processes::process::proc_list, filesystem::files::Hashes};
+use crate::{
+    utils::output::serialize_output,
+    {artifacts::os::processes::process::proc_list, filesystem::files::Hashes},
+};
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -11,7 +14,7 @@ pub(crate) fn get_processes(#[string] hashes: String, metadata: bool) -> Result<
         sha256: false,
     });
     let proc = proc_list(&hashes, metadata)?;
-    let results = serde_json::to_string(&proc)?;
+    let results = serialize_output(&proc)?;
     Ok(results)
 }
 
@@ -33,9 +36,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -1,4 +1,4 @@
-use crate::artifacts::os::systeminfo::info::get_info;
+use crate::{artifacts::os::systeminfo::info::get_info, utils::output::serialize_output};
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -6,7 +6,7 @@ use deno_core::{error::AnyError, op2};
 /// Expose pulling systeminfo to `Deno`
 pub(crate) fn get_systeminfo() -> Result<String, AnyError> {
     let info = get_info();
-    let results = serde_json::to_string(&info)?;
+    let results = serialize_output(&info)?;
     Ok(results)
 }
 
@@ -63,9 +63,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -1,4 +1,4 @@
-use crate::artifacts::os::unix::cron::crontab::parse_cron;
+use crate::{artifacts::os::unix::cron::crontab::parse_cron, utils::output::serialize_output};
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -6,7 +6,7 @@ use deno_core::{error::AnyError, op2};
 /// Get `Cron` data
 pub(crate) fn get_cron() -> Result<String, AnyError> {
     let cron = parse_cron()?;
-    let results = serde_json::to_string(&cron)?;
+    let results = serialize_output(&cron)?;
     Ok(results)
 }
 
@@ -32,6 +32,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
 
@@ -1,6 +1,7 @@
 use crate::artifacts::os::unix::shell_history::{
     bash::get_user_bash_history, python::get_user_python_history, zsh::get_user_zsh_history,
 };
+use crate::utils::output::serialize_output;
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -8,7 +9,7 @@ use deno_core::{error::AnyError, op2};
 /// Get `Bash history` for all users
 pub(crate) fn get_bash_history() -> Result<String, AnyError> {
     let history = get_user_bash_history()?;
-    let results = serde_json::to_string(&history)?;
+    let results = serialize_output(&history)?;
     Ok(results)
 }
 
@@ -17,7 +18,7 @@ pub(crate) fn get_bash_history() -> Result<String, AnyError> {
 /// Get `Zsh history` for all users
 pub(crate) fn get_zsh_history() -> Result<String, AnyError> {
     let history = get_user_zsh_history()?;
-    let results = serde_json::to_string(&history)?;
+    let results = serialize_output(&history)?;
     Ok(results)
 }
 
@@ -26,7 +27,7 @@ pub(crate) fn get_zsh_history() -> Result<String, AnyError> {
 /// Get `Python history` for all users
 pub(crate) fn get_python_history() -> Result<String, AnyError> {
     let history = get_user_python_history()?;
-    let results = serde_json::to_string(&history)?;
+    let results = serialize_output(&history)?;
     Ok(results)
 }
 
@@ -54,6 +55,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         }
     }
     #[test]
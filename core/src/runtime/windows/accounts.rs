@@ -1,6 +1,6 @@
 use crate::{
     artifacts::os::windows::accounts::parser::grab_users, runtime::error::RuntimeError,
-    structs::artifacts::os::windows::WindowsUserOptions,
+    structs::artifacts::os::windows::WindowsUserOptions, utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -12,7 +12,7 @@ pub(crate) fn get_users_windows() -> Result<String, AnyError> {
     let options = WindowsUserOptions { alt_file: None };
 
     let users = grab_users(&options)?;
-    let results = serde_json::to_string(&users)?;
+    let results = serialize_output(&users)?;
     Ok(results)
 }
 
@@ -30,7 +30,7 @@ pub(crate) fn get_alt_users_windows(#[string] file: String) -> Result<String, An
     };
 
     let users = grab_users(&options)?;
-    let results = serde_json::to_string(&users)?;
+    let results = serialize_output(&users)?;
     Ok(results)
 }
 
@@ -52,9 +52,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -1,6 +1,6 @@
 use crate::{
     artifacts::os::windows::amcache::parser::grab_amcache, runtime::error::RuntimeError,
-    structs::artifacts::os::windows::AmcacheOptions,
+    structs::artifacts::os::windows::AmcacheOptions, utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -12,7 +12,7 @@ pub(crate) fn get_amcache() -> Result<String, AnyError> {
     let options = AmcacheOptions { alt_file: None };
     let amcache = grab_amcache(&options)?;
 
-    let results = serde_json::to_string(&amcache)?;
+    let results = serialize_output(&amcache)?;
     Ok(results)
 }
 
@@ -30,7 +30,7 @@ pub(crate) fn get_alt_amcache(#[string] file: String) -> Result<String, AnyError
     };
 
     let amcache = grab_amcache(&options)?;
-    let results = serde_json::to_string(&amcache)?;
+    let results = serialize_output(&amcache)?;
     Ok(results)
 }
 
@@ -52,9 +52,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -0,0 +1,81 @@
+use crate::{
+    artifacts::os::windows::autoruns::parser::grab_autoruns, runtime::error::RuntimeError,
+    structs::artifacts::os::windows::AutorunsOptions, utils::output::serialize_output,
+};
+use deno_core::{error::AnyError, op2};
+use log::error;
+
+#[op2]
+#[string]
+/// Expose parsing autostart Registry locations at default systemdrive to Deno
+pub(crate) fn get_autoruns() -> Result<String, AnyError> {
+    let options = AutorunsOptions { alt_drive: None };
+    let autoruns = grab_autoruns(&options)?;
+
+    let results = serialize_output(&autoruns)?;
+    Ok(results)
+}
+
+#[op2]
+#[string]
+/// Expose parsing autostart Registry locations at alternative drive to Deno
+pub(crate) fn get_alt_autoruns(#[string] drive: String) -> Result<String, AnyError> {
+    if drive.is_empty() {
+        error!("[runtime] Failed to parse alt autoruns drive. Need drive letter");
+        return Err(RuntimeError::ExecuteScript.into());
+    }
+    // Get the first char from string (the drive letter)
+    let drive_char = &drive.chars().next().unwrap();
+    let options = AutorunsOptions {
+        alt_drive: Some(drive_char.to_owned()),
+    };
+    let autoruns = grab_autoruns(&options)?;
+
+    let results = serialize_output(&autoruns)?;
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        runtime::deno::execute_script, structs::artifacts::runtime::script::JSScript,
+        structs::toml::Output,
+    };
+
+    fn output_options(name: &str, output: &str, directory: &str, compress: bool) -> Output {
+        Output {
+            name: name.to_string(),
+            directory: directory.to_string(),
+            format: String::from("json"),
+            compress,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: output.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_get_autoruns() {
+        let test = "Ly8gaHR0cHM6Ly9yYXcuZ2l0aHVidXNlcmNvbnRlbnQuY29tL3B1ZmZ5Y2lkL2FydGVtaXMtYXBpL21hc3Rlci9zcmMvd2luZG93cy9hdXRvcnVucy50cwpmdW5jdGlvbiBnZXRBdXRvcnVucygpIHsKICBjb25zdCBkYXRhID0gRGVuby5jb3JlLm9wcy5nZXRfYXV0b3J1bnMoKTsKICBjb25zdCBhdXRvcnVucyA9IEpTT04ucGFyc2UoZGF0YSk7CiAgcmV0dXJuIGF1dG9ydW5zOwp9CgovLyBtYWluLnRzCmZ1bmN0aW9uIG1haW4oKSB7CiAgY29uc3QgZGF0YSA9IGdldEF1dG9ydW5zKCk7CiAgcmV0dXJuIGRhdGE7Cn0KbWFpbigpOwo=";
+        let mut output = output_options("runtime_test", "local", "./tmp", false);
+        let script = JSScript {
+            name: String::from("autoruns_default"),
+            script: test.to_string(),
+        };
+        execute_script(&mut output, &script).unwrap();
+    }
+
+    #[test]
+    fn test_get_alt_autoruns() {
+        let test = "ZnVuY3Rpb24gZ2V0QWx0QXV0b3J1bnMoZHJpdmUpIHsKICBjb25zdCBkYXRhID0gRGVuby5jb3JlLm9wcy5nZXRfYWx0X2F1dG9ydW5zKGRyaXZlKTsKICBjb25zdCBhdXRvcnVucyA9IEpTT04ucGFyc2UoZGF0YSk7CiAgcmV0dXJuIGF1dG9ydW5zOwp9CgovLyBtYWluLnRzCmZ1bmN0aW9uIG1haW4oKSB7CiAgY29uc3QgZGF0YSA9IGdldEFsdEF1dG9ydW5zKCJDIik7CiAgcmV0dXJuIGRhdGE7Cn0KbWFpbigpOwo=";
+        let mut output = output_options("runtime_test", "local", "./tmp", false);
+        let script = JSScript {
+            name: String::from("autoruns_alt"),
+            script: test.to_string(),
+        };
+        execute_script(&mut output, &script).unwrap();
+    }
+}
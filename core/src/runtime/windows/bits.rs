@@ -1,10 +1,12 @@
 use crate::{
-    artifacts::os::windows::bits::parser::{grab_bits, grab_bits_path},
+    artifacts::os::windows::bits::parser::{grab_bits, grab_bits_path, grab_ese_bits},
     runtime::error::RuntimeError,
     structs::artifacts::os::windows::BitsOptions,
+    utils::output::serialize_output,
 };
+use common::windows::WindowsBits;
 use deno_core::{error::AnyError, op2};
-use log::error;
+use log::{error, warn};
 
 #[op2]
 #[string]
@@ -13,10 +15,13 @@ pub(crate) fn get_bits(carve: bool) -> Result<String, AnyError> {
     let options = BitsOptions {
         alt_file: None,
         carve,
+        max_carved: None,
+        carve_threads: None,
+        carve_chunk_size: None,
     };
     let bits = grab_bits(&options)?;
 
-    let results = serde_json::to_string(&bits)?;
+    let results = serialize_output(&bits)?;
     Ok(results)
 }
 
@@ -31,7 +36,30 @@ pub(crate) fn get_bits_path(#[string] path: String, carve: bool) -> Result<Strin
 
     let bits = grab_bits_path(&path, carve)?;
 
-    let results = serde_json::to_string(&bits)?;
+    let results = serialize_output(&bits)?;
+    Ok(results)
+}
+
+#[op2]
+#[string]
+/// Expose parsing a BITS ESE database at a custom path to `Deno`. Returns empty results if the file is not a valid ESE database
+pub(crate) fn get_custom_bits(#[string] path: String, carve: bool) -> Result<String, AnyError> {
+    let bits_result = grab_ese_bits(&path, carve);
+    let bits = match bits_result {
+        Ok(result) => result,
+        Err(err) => {
+            warn!("[runtime] Could not parse BITS database at {path}: {err:?}");
+            WindowsBits {
+                bits: Vec::new(),
+                carved_jobs: Vec::new(),
+                carved_files: Vec::new(),
+                partial: false,
+                users_resolved: false,
+            }
+        }
+    };
+
+    let results = serialize_output(&bits)?;
     Ok(results)
 }
 
@@ -53,9 +81,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -80,4 +106,15 @@ mod tests {
         };
         execute_script(&mut output, &script).unwrap();
     }
+
+    #[test]
+    fn test_get_custom_bits_invalid() {
+        let test = "Ly8gZGVuby1mbXQtaWdub3JlLWZpbGUKLy8gZGVuby1saW50LWlnbm9yZS1maWxlCi8vIFRoaXMgY29kZSB3YXMgYnVuZGxlZCB1c2luZyBgZGVubyBidW5kbGVgIGFuZCBpdCdzIG5vdCByZWNvbW1lbmRlZCB0byBlZGl0IGl0IG1hbnVhbGx5CgpmdW5jdGlvbiBnZXRfY3VzdG9tX2JpdHMocGF0aCwgY2FydmUpIHsKICAgIGNvbnN0IGRhdGEgPSBEZW5vLmNvcmUub3BzLmdldF9jdXN0b21fYml0cyhwYXRoLCBjYXJ2ZSk7CiAgICBjb25zdCBiaXRzID0gSlNPTi5wYXJzZShkYXRhKTsKICAgIHJldHVybiBiaXRzOwp9CmZ1bmN0aW9uIGdldEN1c3RvbUJpdHMocGF0aCwgY2FydmUpIHsKICAgIHJldHVybiBnZXRfY3VzdG9tX2JpdHMocGF0aCwgY2FydmUpOwp9CmZ1bmN0aW9uIG1haW4oKSB7CiAgICBjb25zdCBwYXRoID0gIkM6XFxXaW5kb3dzXFxTeXN0ZW0zMlxcbm90ZXBhZC5leGUiOwogICAgY29uc3QgZW50cmllcyA9IGdldEN1c3RvbUJpdHMocGF0aCwgZmFsc2UpOwogICAgaWYgKGVudHJpZXMuYml0cy5sZW5ndGggIT09IDApIHsKICAgICAgICB0aHJvdyAiZXhwZWN0ZWQgZW1wdHkgQklUUyByZXN1bHRzIjsKICAgIH0KICAgIHJldHVybiBlbnRyaWVzOwp9Cm1haW4oKTsKCg==";
+        let mut output = output_options("runtime_test", "local", "./tmp", false);
+        let script = JSScript {
+            name: String::from("custom_bits"),
+            script: test.to_string(),
+        };
+        execute_script(&mut output, &script).unwrap();
+    }
 }
@@ -6,6 +6,7 @@ use crate::artifacts::os::windows::ese::{
     },
     tables::TableInfo,
 };
+use crate::utils::output::serialize_output;
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -13,7 +14,7 @@ use deno_core::{error::AnyError, op2};
 pub(crate) fn get_catalog(#[string] path: String) -> Result<String, AnyError> {
     let ese = get_catalog_info(&path)?;
 
-    let results = serde_json::to_string(&ese)?;
+    let results = serialize_output(&ese)?;
     Ok(results)
 }
 
@@ -36,7 +37,7 @@ pub(crate) fn page_data(
     let mut info: TableInfo = serde_json::from_str(&info_string)?;
     let ese = get_page_data(&path, &pages, &mut info, &name)?;
 
-    let results = serde_json::to_string(&ese)?;
+    let results = serialize_output(&ese)?;
     Ok(results)
 }
 
@@ -60,7 +61,7 @@ pub(crate) fn filter_page_data(
         &mut column_values,
     )?;
 
-    let results = serde_json::to_string(&ese)?;
+    let results = serialize_output(&ese)?;
     Ok(results)
 }
 
@@ -76,7 +77,7 @@ pub(crate) fn get_table_columns(
     let mut info: TableInfo = serde_json::from_str(&info_string)?;
     let ese = dump_table_columns(&path, &pages, &mut info, &name, &column_names)?;
 
-    let results = serde_json::to_string(&ese)?;
+    let results = serialize_output(&ese)?;
     Ok(results)
 }
 
@@ -98,9 +99,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
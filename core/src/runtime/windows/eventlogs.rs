@@ -1,5 +1,7 @@
 use crate::{
-    artifacts::os::windows::eventlogs::parser::parse_eventlogs, runtime::error::RuntimeError,
+    artifacts::os::windows::eventlogs::parser::{parse_eventlogs, EventFilter},
+    runtime::error::RuntimeError,
+    utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -12,9 +14,31 @@ pub(crate) fn get_eventlogs(#[string] path: String) -> Result<String, AnyError>
         error!("[runtime] Empty path to eventlog file");
         return Err(RuntimeError::ExecuteScript.into());
     }
-    let logs = parse_eventlogs(&path)?;
+    let logs = parse_eventlogs(&path, &EventFilter::default())?;
 
-    let results = serde_json::to_string(&logs)?;
+    let results = serialize_output(&logs)?;
+    Ok(results)
+}
+
+#[op2]
+#[string]
+/// Expose parsing a single eventlog file (evtx) to `Deno`. Only records matching the channels and EventIDs are returned
+pub(crate) fn get_eventlogs_filter(
+    #[string] path: String,
+    #[serde] channels: Vec<String>,
+    #[serde] event_ids: Vec<u64>,
+) -> Result<String, AnyError> {
+    if path.is_empty() {
+        error!("[runtime] Empty path to eventlog file");
+        return Err(RuntimeError::ExecuteScript.into());
+    }
+    let event_filter = EventFilter {
+        channels,
+        event_ids,
+    };
+    let logs = parse_eventlogs(&path, &event_filter)?;
+
+    let results = serialize_output(&logs)?;
     Ok(results)
 }
 
@@ -36,9 +60,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -52,4 +74,15 @@ mod tests {
         };
         execute_script(&mut output, &script).unwrap();
     }
+
+    #[test]
+    fn test_get_eventlogs_filter() {
+        let test = "Ly8gZGVuby1mbXQtaWdub3JlLWZpbGUKLy8gZGVuby1saW50LWlnbm9yZS1maWxlCi8vIFRoaXMgY29kZSB3YXMgYnVuZGxlZCB1c2luZyBgZGVubyBidW5kbGVgIGFuZCBpdCdzIG5vdCByZWNvbW1lbmRlZCB0byBlZGl0IGl0IG1hbnVhbGx5CgpmdW5jdGlvbiBnZXRfZXZlbnRsb2dzX2ZpbHRlcihwYXRoLCBjaGFubmVscywgZXZlbnRfaWRzKSB7CiAgICBjb25zdCBkYXRhID0gRGVuby5jb3JlLm9wcy5nZXRfZXZlbnRsb2dzX2ZpbHRlcihwYXRoLCBjaGFubmVscywgZXZlbnRfaWRzKTsKICAgIGNvbnN0IGxvZ19hcnJheSA9IEpTT04ucGFyc2UoZGF0YSk7CiAgICByZXR1cm4gbG9nX2FycmF5Owp9CmZ1bmN0aW9uIG1haW4oKSB7CiAgICBjb25zdCBwYXRoID0gIkM6XFxXaW5kb3dzXFxTeXN0ZW0zMlxcd2luZXZ0XFxMb2dzXFxTeXN0ZW0uZXZ0eCI7CiAgICBjb25zdCByZWNvcmRzID0gZ2V0X2V2ZW50bG9nc19maWx0ZXIocGF0aCwgWwogICAgICAgICJTeXN0ZW0iCiAgICBdLCBbCiAgICAgICAgNzA0NQogICAgXSk7CiAgICBmb3IgKGNvbnN0IHJlY29yZCBvZiByZWNvcmRzKXsKICAgICAgICBpZiAocmVjb3JkLmV2ZW50X2lkICE9IDcwNDUgfHwgcmVjb3JkLmNoYW5uZWwgIT0gIlN5c3RlbSIpIHsKICAgICAgICAgICAgdGhyb3cgInVuZXhwZWN0ZWQgcmVjb3JkIjsKICAgICAgICB9CiAgICB9CiAgICByZXR1cm4gcmVjb3JkczsKfQptYWluKCk7Cgo=";
+        let mut output = output_options("runtime_test", "local", "./tmp", true);
+        let script = JSScript {
+            name: String::from("service_installs_filter"),
+            script: test.to_string(),
+        };
+        execute_script(&mut output, &script).unwrap();
+    }
 }
@@ -1,24 +1,25 @@
 use super::{
     accounts::{get_alt_users_windows, get_users_windows},
     amcache::{get_alt_amcache, get_amcache},
-    bits::{get_bits, get_bits_path},
+    autoruns::{get_alt_autoruns, get_autoruns},
+    bits::{get_bits, get_bits_path, get_custom_bits},
     ese::{filter_page_data, get_catalog, get_pages, get_table_columns, page_data},
-    eventlogs::get_eventlogs,
+    eventlogs::{get_eventlogs, get_eventlogs_filter},
     jumplists::{get_jumplist_file, get_jumplists},
-    ntfs::{read_ads_data, read_raw_file},
+    ntfs::{hash_file, read_ads_data, read_raw_file},
     pe::get_pe,
     prefetch::{get_prefetch, get_prefetch_path},
     recyclebin::{get_recycle_bin, get_recycle_bin_file},
     registry::{get_registry, get_sk_info},
     search::get_search,
-    services::{get_service_file, get_services},
-    shellbags::{get_alt_shellbags, get_shellbags},
+    services::{get_alt_services, get_service_file, get_services},
+    shellbags::{get_alt_drive_shellbags, get_alt_shellbags, get_shellbags},
     shellitems::js_get_shellitem,
     shimcache::{get_alt_shimcache, get_shimcache},
     shimdb::{get_custom_shimdb, get_shimdb},
-    shortcuts::get_lnk_file,
+    shortcuts::{get_lnk_directory, get_lnk_file, get_shortcuts},
     srum::get_srum,
-    tasks::{get_task_file, get_tasks},
+    tasks::{get_alt_tasks, get_task_file, get_tasks},
     userassist::{get_alt_userassist, get_userassist},
     usnjrnl::{get_alt_usnjrnl, get_usnjrnl},
     wmi::get_wmipersist,
@@ -42,13 +43,18 @@ fn grab_functions() -> Vec<deno_core::OpDecl> {
         get_shimcache(),
         get_registry(),
         get_eventlogs(),
+        get_eventlogs_filter(),
         get_lnk_file(),
+        get_lnk_directory(),
+        get_shortcuts(),
         get_usnjrnl(),
         get_alt_usnjrnl(),
         get_shellbags(),
         get_alt_shellbags(),
+        get_alt_drive_shellbags(),
         read_raw_file(),
         read_ads_data(),
+        hash_file(),
         get_pe(),
         get_prefetch(),
         get_prefetch_path(),
@@ -60,13 +66,16 @@ fn grab_functions() -> Vec<deno_core::OpDecl> {
         get_custom_shimdb(),
         get_bits(),
         get_bits_path(),
+        get_custom_bits(),
         get_srum(),
         get_users_windows(),
         get_alt_users_windows(),
         get_search(),
         get_tasks(),
+        get_alt_tasks(),
         get_task_file(),
         get_services(),
+        get_alt_services(),
         get_service_file(),
         get_jumplists(),
         get_jumplist_file(),
@@ -80,6 +89,8 @@ fn grab_functions() -> Vec<deno_core::OpDecl> {
         get_table_columns(),
         js_get_shellitem(),
         get_wmipersist(),
+        get_autoruns(),
+        get_alt_autoruns(),
     ];
 
     exts
@@ -2,6 +2,7 @@ use crate::{
     artifacts::os::windows::jumplists::parser::{grab_jumplist_file, grab_jumplists},
     runtime::error::RuntimeError,
     structs::artifacts::os::windows::JumplistsOptions,
+    utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -13,7 +14,7 @@ pub(crate) fn get_jumplists() -> Result<String, AnyError> {
     let options = JumplistsOptions { alt_file: None };
     let jumplist = grab_jumplists(&options)?;
 
-    let results = serde_json::to_string(&jumplist)?;
+    let results = serialize_output(&jumplist)?;
     Ok(results)
 }
 
@@ -35,7 +36,7 @@ pub(crate) fn get_jumplist_file(#[string] path: String) -> Result<String, AnyErr
         }
     };
 
-    let results = serde_json::to_string(&jumplist)?;
+    let results = serialize_output(&jumplist)?;
     Ok(results)
 }
 
@@ -57,9 +58,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
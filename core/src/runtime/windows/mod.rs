@@ -1,5 +1,6 @@
 mod accounts;
 mod amcache;
+mod autoruns;
 mod bits;
 mod ese;
 mod eventlogs;
@@ -1,5 +1,8 @@
-use crate::filesystem::ntfs::raw_files::{raw_read_file, read_attribute};
-use deno_core::{error::AnyError, op2};
+use crate::filesystem::{
+    files::{hash_file_data, Hashes},
+    ntfs::raw_files::{raw_read_file, read_attribute},
+};
+use deno_core::{anyhow::anyhow, error::AnyError, op2};
 
 #[op2]
 #[buffer]
@@ -20,6 +23,38 @@ pub(crate) fn read_ads_data(
     Ok(data)
 }
 
+#[op2]
+#[string]
+/// Expose hashing a raw file to `Deno`. Supports md5, sha1, or sha256 and returns the hex digest
+pub(crate) fn hash_file(
+    #[string] path: String,
+    #[string] algo: String,
+) -> Result<String, AnyError> {
+    let hashes = match algo.to_lowercase().as_str() {
+        "md5" => Hashes {
+            md5: true,
+            sha1: false,
+            sha256: false,
+        },
+        "sha1" => Hashes {
+            md5: false,
+            sha1: true,
+            sha256: false,
+        },
+        "sha256" => Hashes {
+            md5: false,
+            sha1: false,
+            sha256: true,
+        },
+        _ => return Err(anyhow!("Unsupported hash algorithm: {algo}")),
+    };
+
+    let data = raw_read_file(&path)?;
+    let (md5, sha1, sha256) = hash_file_data(&hashes, &data);
+    let digest = [md5, sha1, sha256].concat();
+    Ok(digest)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -38,9 +73,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -65,4 +98,15 @@ mod tests {
         };
         execute_script(&mut output, &script).unwrap();
     }
+
+    #[test]
+    fn test_hash_file() {
+        let test = "Ly8gaHR0cHM6Ly9yYXcuZ2l0aHVidXNlcmNvbnRlbnQuY29tL3B1ZmZ5Y2lkL2FydGVtaXMtYXBpL21hc3Rlci9zcmMvd2luZG93cy9udGZzLnRzCmZ1bmN0aW9uIGhhc2hGaWxlKHBhdGgsIGFsZ28pIHsKICBjb25zdCBkYXRhID0gRGVuby5jb3JlLm9wcy5oYXNoX2ZpbGUocGF0aCwgYWxnbyk7CiAgcmV0dXJuIGRhdGE7Cn0KCi8vIGh0dHBzOi8vcmF3LmdpdGh1YnVzZXJjb250ZW50LmNvbS9wdWZmeWNpZC9hcnRlbWlzLWFwaS9tYXN0ZXIvc3JjL2Vudmlyb25tZW50L2Vudi50cwpmdW5jdGlvbiBnZXRFbnZWYWx1ZShrZXkpIHsKICBjb25zdCBkYXRhID0gZW52LmVudmlyb25tZW50VmFsdWUoa2V5KTsKICByZXR1cm4gZGF0YTsKfQoKLy8gbWFpbi50cwpmdW5jdGlvbiBtYWluKCkgewogIGNvbnN0IGRyaXZlID0gZ2V0RW52VmFsdWUoIlN5c3RlbURyaXZlIik7CiAgaWYgKGRyaXZlID09PSAiIikgewogICAgcmV0dXJuIFtdOwogIH0KICBjb25zdCBwYXRoID0gYCR7ZHJpdmV9XFxXaW5kb3dzXFxleHBsb3Jlci5leGVgOwogIGNvbnN0IG1kNSA9IGhhc2hGaWxlKHBhdGgsICJtZDUiKTsKICBjb25zdCBzaGExID0gaGFzaEZpbGUocGF0aCwgInNoYTEiKTsKICBjb25zdCBzaGEyNTYgPSBoYXNoRmlsZShwYXRoLCAiU0hBMjU2Iik7CiAgaWYgKG1kNS5sZW5ndGggIT09IDMyIHx8IHNoYTEubGVuZ3RoICE9PSA0MCB8fCBzaGEyNTYubGVuZ3RoICE9PSA2NCkgewogICAgdGhyb3cgbmV3IEVycm9yKCJVbmV4cGVjdGVkIGhhc2ggbGVuZ3RoIik7CiAgfQoKICBsZXQgdW5zdXBwb3J0ZWQgPSBmYWxzZTsKICB0cnkgewogICAgaGFzaEZpbGUocGF0aCwgImNyYzMyIik7CiAgfSBjYXRjaCAoX2Vycm9yKSB7CiAgICB1bnN1cHBvcnRlZCA9IHRydWU7CiAgfQogIGlmICghdW5zdXBwb3J0ZWQpIHsKICAgIHRocm93IG5ldyBFcnJvcigiVW5zdXBwb3J0ZWQgaGFzaCBhbGdvcml0aG0gd2FzIGFjY2VwdGVkIik7CiAgfQogIHJldHVybiBbeyBwYXRoLCBtZDUsIHNoYTEsIHNoYTI1NiB9XTsKfQptYWluKCk7Cg==";
+        let mut output = output_options("runtime_test", "local", "./tmp", false);
+        let script = JSScript {
+            name: String::from("hash_explorer"),
+            script: test.to_string(),
+        };
+        execute_script(&mut output, &script).unwrap();
+    }
 }
@@ -1,4 +1,4 @@
-use crate::artifacts::os::windows::pe::parser::parse_pe_file;
+use crate::{artifacts::os::windows::pe::parser::parse_pe_file, utils::output::serialize_output};
 use deno_core::{error::AnyError, op2};
 
 #[op2]
@@ -6,7 +6,7 @@ use deno_core::{error::AnyError, op2};
 /// Expose parsing pe file  to `Deno`
 pub(crate) fn get_pe(#[string] path: String) -> Result<String, AnyError> {
     let pe = parse_pe_file(&path)?;
-    let results = serde_json::to_string(&pe)?;
+    let results = serialize_output(&pe)?;
     Ok(results)
 }
 
@@ -28,9 +28,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -2,6 +2,7 @@ use crate::{
     artifacts::os::windows::prefetch::parser::{custom_prefetch_path, grab_prefetch},
     runtime::error::RuntimeError,
     structs::artifacts::os::windows::PrefetchOptions,
+    utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -10,10 +11,14 @@ use log::error;
 #[string]
 /// Get Prefetch files at using default drive (typically C)
 pub(crate) fn get_prefetch() -> Result<String, AnyError> {
-    let options = PrefetchOptions { alt_dir: None };
+    let options = PrefetchOptions {
+        alt_dir: None,
+        entropy: None,
+        max_last_run_times: None,
+    };
     let pf = grab_prefetch(&options)?;
 
-    let results = serde_json::to_string(&pf)?;
+    let results = serialize_output(&pf)?;
     Ok(results)
 }
 
@@ -28,7 +33,7 @@ pub(crate) fn get_prefetch_path(#[string] path: String) -> Result<String, AnyErr
 
     let pf = custom_prefetch_path(&path)?;
 
-    let results = serde_json::to_string(&pf)?;
+    let results = serialize_output(&pf)?;
     Ok(results)
 }
 
@@ -50,9 +55,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
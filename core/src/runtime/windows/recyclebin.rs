@@ -2,6 +2,7 @@ use crate::{
     artifacts::os::windows::recyclebin::parser::{grab_recycle_bin, grab_recycle_bin_path},
     runtime::error::RuntimeError,
     structs::artifacts::os::windows::RecycleBinOptions,
+    utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -13,7 +14,7 @@ pub(crate) fn get_recycle_bin() -> Result<String, AnyError> {
     let options = RecycleBinOptions { alt_file: None };
     let bin = grab_recycle_bin(&options)?;
 
-    let results = serde_json::to_string(&bin)?;
+    let results = serialize_output(&bin)?;
     Ok(results)
 }
 
@@ -35,7 +36,7 @@ pub(crate) fn get_recycle_bin_file(#[string] path: String) -> Result<String, Any
         }
     };
 
-    let results = serde_json::to_string(&bin)?;
+    let results = serialize_output(&bin)?;
 
     Ok(results)
 }
@@ -58,9 +59,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
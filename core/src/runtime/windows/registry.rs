@@ -1,6 +1,6 @@
 use crate::{
     artifacts::os::windows::registry::helper::{get_registry_keys, lookup_sk_info},
-    utils::regex_options::create_regex,
+    utils::{output::serialize_output, regex_options::create_regex},
 };
 use deno_core::{error::AnyError, op2};
 
@@ -12,7 +12,7 @@ pub(crate) fn get_registry(#[string] path: String) -> Result<String, AnyError> {
     let start_root = "";
     let reg = get_registry_keys(start_root, &all, &path)?;
 
-    let results = serde_json::to_string(&reg)?;
+    let results = serialize_output(&reg)?;
     Ok(results)
 }
 
@@ -22,7 +22,7 @@ pub(crate) fn get_registry(#[string] path: String) -> Result<String, AnyError> {
 pub(crate) fn get_sk_info(#[string] path: String, offset: i32) -> Result<String, AnyError> {
     let sk = lookup_sk_info(&path, offset)?;
 
-    let results = serde_json::to_string(&sk)?;
+    let results = serialize_output(&sk)?;
     Ok(results)
 }
 
@@ -44,9 +44,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
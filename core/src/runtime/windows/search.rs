@@ -1,5 +1,6 @@
 use crate::{
     artifacts::os::windows::search::parser::grab_search_path, runtime::error::RuntimeError,
+    utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -14,7 +15,7 @@ pub(crate) fn get_search(#[string] path: String, page_limit: u32) -> Result<Stri
     }
     let search = grab_search_path(&path, &page_limit)?;
 
-    let results = serde_json::to_string(&search)?;
+    let results = serialize_output(&search)?;
     Ok(results)
 }
 
@@ -36,9 +37,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -2,6 +2,7 @@ use crate::{
     artifacts::os::windows::services::parser::{grab_service_file, grab_services},
     runtime::error::RuntimeError,
     structs::artifacts::os::windows::ServicesOptions,
+    utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -10,10 +11,33 @@ use log::error;
 #[string]
 /// Expose parsing Services at default systemdrive to Deno
 pub(crate) fn get_services() -> Result<String, AnyError> {
-    let options = ServicesOptions { alt_file: None };
+    let options = ServicesOptions {
+        alt_file: None,
+        alt_drive: None,
+    };
     let service = grab_services(&options)?;
 
-    let results = serde_json::to_string(&service)?;
+    let results = serialize_output(&service)?;
+    Ok(results)
+}
+
+#[op2]
+#[string]
+/// Expose parsing Services at alternative drive to Deno
+pub(crate) fn get_alt_services(#[string] drive: String) -> Result<String, AnyError> {
+    if drive.is_empty() {
+        error!("[runtime] Failed to parse alt services drive. Need drive letter");
+        return Err(RuntimeError::ExecuteScript.into());
+    }
+    // Get the first char from string (the drive letter)
+    let drive_char = &drive.chars().next().unwrap();
+    let options = ServicesOptions {
+        alt_file: None,
+        alt_drive: Some(drive_char.to_owned()),
+    };
+    let service = grab_services(&options)?;
+
+    let results = serialize_output(&service)?;
     Ok(results)
 }
 
@@ -27,7 +51,7 @@ pub(crate) fn get_service_file(#[string] path: String) -> Result<String, AnyErro
     }
 
     let service = grab_service_file(&path)?;
-    let results = serde_json::to_string(&service)?;
+    let results = serialize_output(&service)?;
 
     Ok(results)
 }
@@ -50,9 +74,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -67,6 +89,17 @@ mod tests {
         execute_script(&mut output, &script).unwrap();
     }
 
+    #[test]
+    fn test_get_alt_services() {
+        let test = "ZnVuY3Rpb24gZ2V0QWx0U2VydmljZXMoZHJpdmUpIHsKICBjb25zdCBkYXRhID0gRGVuby5jb3JlLm9wcy5nZXRfYWx0X3NlcnZpY2VzKGRyaXZlKTsKICBjb25zdCBzZXJ2aWNlcyA9IEpTT04ucGFyc2UoZGF0YSk7CiAgcmV0dXJuIHNlcnZpY2VzOwp9CgovLyBtYWluLnRzCmZ1bmN0aW9uIG1haW4oKSB7CiAgY29uc3QgZGF0YSA9IGdldEFsdFNlcnZpY2VzKCJDIik7CiAgcmV0dXJuIGRhdGE7Cn0KbWFpbigpOwo=";
+        let mut output = output_options("runtime_test", "local", "./tmp", false);
+        let script = JSScript {
+            name: String::from("service_alt"),
+            script: test.to_string(),
+        };
+        execute_script(&mut output, &script).unwrap();
+    }
+
     #[test]
     fn test_get_service_file() {
         let test = "Ly8gaHR0cHM6Ly9yYXcuZ2l0aHVidXNlcmNvbnRlbnQuY29tL3B1ZmZ5Y2lkL2FydGVtaXMtYXBpL21hc3Rlci9zcmMvd2luZG93cy9zZXJ2aWNlcy50cwpmdW5jdGlvbiBnZXRTZXJ2aWNlRmlsZShwYXRoKSB7CiAgY29uc3QgZGF0YSA9IERlbm8uY29yZS5vcHMuZ2V0X3NlcnZpY2VfZmlsZShwYXRoKTsKICBjb25zdCBzZXJ2aWNlcyA9IEpTT04ucGFyc2UoZGF0YSk7CiAgcmV0dXJuIHNlcnZpY2VzOwp9CgovLyBtYWluLnRzCmZ1bmN0aW9uIG1haW4oKSB7CiAgY29uc3QgZGF0YSA9IGdldFNlcnZpY2VGaWxlKCJDOlxcV2luZG93c1xcU3lzdGVtMzJcXGNvbmZpZ1xcU1lTVEVNIik7CiAgcmV0dXJuIGRhdGE7Cn0KbWFpbigpOwo=";
@@ -1,6 +1,6 @@
 use crate::{
     artifacts::os::windows::shellbags::parser::grab_shellbags, runtime::error::RuntimeError,
-    structs::artifacts::os::windows::ShellbagsOptions,
+    structs::artifacts::os::windows::ShellbagsOptions, utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -12,10 +12,11 @@ pub(crate) fn get_shellbags(resolve: bool) -> Result<String, AnyError> {
     let options = ShellbagsOptions {
         alt_file: None,
         resolve_guids: resolve,
+        alt_drive: None,
     };
     let bags = grab_shellbags(&options)?;
 
-    let results = serde_json::to_string(&bags)?;
+    let results = serialize_output(&bags)?;
     Ok(results)
 }
 
@@ -31,11 +32,37 @@ pub(crate) fn get_alt_shellbags(resolve: bool, #[string] file: String) -> Result
     let options = ShellbagsOptions {
         alt_file: Some(file),
         resolve_guids: resolve,
+        alt_drive: None,
     };
 
     let bags = grab_shellbags(&options)?;
 
-    let results = serde_json::to_string(&bags)?;
+    let results = serialize_output(&bags)?;
+    Ok(results)
+}
+
+#[op2]
+#[string]
+/// Expose parsing shellbags located on alternative drive to `Deno`
+pub(crate) fn get_alt_drive_shellbags(
+    resolve: bool,
+    #[string] drive: String,
+) -> Result<String, AnyError> {
+    if drive.is_empty() {
+        error!("[runtime] Failed to parse alt shellbags drive. Need drive letter");
+        return Err(RuntimeError::ExecuteScript.into());
+    }
+    // Get the first char from string (the drive letter)
+    let drive_char = &drive.chars().next().unwrap();
+    let options = ShellbagsOptions {
+        alt_file: None,
+        resolve_guids: resolve,
+        alt_drive: Some(drive_char.to_owned()),
+    };
+
+    let bags = grab_shellbags(&options)?;
+
+    let results = serialize_output(&bags)?;
     Ok(results)
 }
 
@@ -57,9 +84,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -84,4 +109,15 @@ mod tests {
         };
         execute_script(&mut output, &script).unwrap();
     }
+
+    #[test]
+    fn test_get_alt_drive_shellbags() {
+        let test = "Ly8gaHR0cHM6Ly9yYXcuZ2l0aHVidXNlcmNvbnRlbnQuY29tL3B1ZmZ5Y2lkL2FydGVtaXMtYXBpL21hc3Rlci9zcmMvd2luZG93cy9zaGVsbGJhZ3MudHMKZnVuY3Rpb24gZ2V0QWx0RHJpdmVTaGVsbGJhZ3MocmVzb2x2ZV9ndWlkcywgZHJpdmUpIHsKICBjb25zdCBkYXRhID0gRGVuby5jb3JlLm9wcy5nZXRfYWx0X2RyaXZlX3NoZWxsYmFncyhyZXNvbHZlX2d1aWRzLCBkcml2ZSk7CiAgY29uc3QgcmVzdWx0ID0gSlNPTi5wYXJzZShkYXRhKTsKICByZXR1cm4gcmVzdWx0Owp9CgovLyBtYWluLnRzCmZ1bmN0aW9uIG1haW4oKSB7CiAgY29uc3QgYmFncyA9IGdldEFsdERyaXZlU2hlbGxiYWdzKHRydWUsICJDIik7CiAgcmV0dXJuIGJhZ3M7Cn0KbWFpbigpOwo=";
+        let mut output = output_options("runtime_test", "local", "./tmp", false);
+        let script = JSScript {
+            name: String::from("shellbags_alt_drive"),
+            script: test.to_string(),
+        };
+        execute_script(&mut output, &script).unwrap();
+    }
 }
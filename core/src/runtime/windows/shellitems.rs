@@ -1,4 +1,6 @@
-use crate::artifacts::os::windows::shellitems::items::get_shellitem;
+use crate::{
+    artifacts::os::windows::shellitems::items::get_shellitem, utils::output::serialize_output,
+};
 use deno_core::{anyhow::anyhow, error::AnyError, op2, JsBuffer, ToJsBuffer};
 use serde::Serialize;
 
@@ -18,7 +20,7 @@ pub(crate) fn js_get_shellitem(#[buffer] data: JsBuffer) -> Result<JsShellitem,
         Err(_) => return Err(anyhow!("Failed to get shellitem")),
     };
 
-    let item = serde_json::to_string(&item_data)?;
+    let item = serialize_output(&item_data)?;
     let value = JsShellitem {
         item,
         remaining: remaining.to_vec().into(),
@@ -44,9 +46,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
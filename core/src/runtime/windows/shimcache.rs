@@ -1,6 +1,6 @@
 use crate::{
     artifacts::os::windows::shimcache::parser::grab_shimcache, runtime::error::RuntimeError,
-    structs::artifacts::os::windows::ShimcacheOptions,
+    structs::artifacts::os::windows::ShimcacheOptions, utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -12,7 +12,7 @@ pub(crate) fn get_shimcache() -> Result<String, AnyError> {
     let options = ShimcacheOptions { alt_file: None };
     let shim = grab_shimcache(&options)?;
 
-    let results = serde_json::to_string(&shim)?;
+    let results = serialize_output(&shim)?;
     Ok(results)
 }
 
@@ -30,7 +30,7 @@ pub(crate) fn get_alt_shimcache(#[string] file: String) -> Result<String, AnyErr
     };
 
     let shim = grab_shimcache(&options)?;
-    let results = serde_json::to_string(&shim)?;
+    let results = serialize_output(&shim)?;
 
     Ok(results)
 }
@@ -53,9 +53,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -1,6 +1,7 @@
 use crate::{
     artifacts::os::windows::shimdb::parser::{custom_shimdb_path, grab_shimdb},
     structs::artifacts::os::windows::ShimdbOptions,
+    utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 
@@ -11,7 +12,7 @@ pub(crate) fn get_shimdb() -> Result<String, AnyError> {
     let options = ShimdbOptions { alt_file: None };
     let shimdb = grab_shimdb(&options)?;
 
-    let results = serde_json::to_string(&shimdb)?;
+    let results = serialize_output(&shimdb)?;
     Ok(results)
 }
 
@@ -21,7 +22,7 @@ pub(crate) fn get_shimdb() -> Result<String, AnyError> {
 pub(crate) fn get_custom_shimdb(#[string] paths: String) -> Result<String, AnyError> {
     let shimdb = custom_shimdb_path(&paths)?;
 
-    let results = serde_json::to_string(&shimdb)?;
+    let results = serialize_output(&shimdb)?;
     Ok(results)
 }
 
@@ -43,9 +44,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
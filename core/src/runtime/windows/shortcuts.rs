@@ -1,12 +1,38 @@
-use crate::artifacts::os::windows::shortcuts::parser::grab_lnk_file;
+use crate::{
+    artifacts::os::windows::shortcuts::parser::{grab_lnk_file, grab_shortcuts},
+    structs::artifacts::os::windows::ShortcutOptions,
+    utils::output::serialize_output,
+};
 use deno_core::{error::AnyError, op2};
 
+#[op2]
+#[string]
+/// Expose parsing `Shortcut` files in the Recent and Desktop directories for all users to `Deno`
+pub(crate) fn get_shortcuts() -> Result<String, AnyError> {
+    let options = ShortcutOptions { path: None };
+    let lnk = grab_shortcuts(&options)?;
+
+    let results = serialize_output(&lnk)?;
+    Ok(results)
+}
+
+#[op2]
+#[string]
+/// Expose parsing `Shortcut` files in a directory to `Deno`
+pub(crate) fn get_lnk_directory(#[string] path: String) -> Result<String, AnyError> {
+    let options = ShortcutOptions { path: Some(path) };
+    let lnk = grab_shortcuts(&options)?;
+
+    let results = serialize_output(&lnk)?;
+    Ok(results)
+}
+
 #[op2]
 #[string]
 pub(crate) fn get_lnk_file(#[string] path: String) -> Result<String, AnyError> {
     let lnk = grab_lnk_file(&path)?;
 
-    let results = serde_json::to_string(&lnk)?;
+    let results = serialize_output(&lnk)?;
     Ok(results)
 }
 
@@ -28,9 +54,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -44,4 +68,15 @@ mod tests {
         };
         execute_script(&mut output, &script).unwrap();
     }
+
+    #[test]
+    fn test_get_shortcuts() {
+        let test = "Ly8gaHR0cHM6Ly9yYXcuZ2l0aHVidXNlcmNvbnRlbnQuY29tL3B1ZmZ5Y2lkL2FydGVtaXMtYXBpL21hc3Rlci9zcmMvd2luZG93cy9zaG9ydGN1dHMudHMKZnVuY3Rpb24gZ2V0U2hvcnRjdXRzKCkgewogIGNvbnN0IGRhdGEgPSBEZW5vLmNvcmUub3BzLmdldF9zaG9ydGN1dHMoKTsKICBjb25zdCByZXN1bHRzID0gSlNPTi5wYXJzZShkYXRhKTsKICByZXR1cm4gcmVzdWx0czsKfQoKLy8gbWFpbi50cwpmdW5jdGlvbiBtYWluKCkgewogIGNvbnN0IGxuayA9IGdldFNob3J0Y3V0cygpOwogIHJldHVybiBsbms7Cn0KbWFpbigpOwo=";
+        let mut output = output_options("runtime_test", "local", "./tmp", false);
+        let script = JSScript {
+            name: String::from("shortcuts_default"),
+            script: test.to_string(),
+        };
+        execute_script(&mut output, &script).unwrap();
+    }
 }
@@ -1,4 +1,7 @@
-use crate::{artifacts::os::windows::srum::parser::grab_srum_path, runtime::error::RuntimeError};
+use crate::{
+    utils::output::serialize_output,
+    {artifacts::os::windows::srum::parser::grab_srum_path, runtime::error::RuntimeError},
+};
 use deno_core::{error::AnyError, op2};
 use log::error;
 
@@ -18,7 +21,7 @@ pub(crate) fn get_srum(
     }
     let srum = grab_srum_path(&path, &table)?;
 
-    let results = serde_json::to_string(&srum)?;
+    let results = serialize_output(&srum)?;
     Ok(results)
 }
 
@@ -40,9 +43,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -2,6 +2,7 @@ use crate::{
     artifacts::os::windows::tasks::parser::{grab_task_job, grab_task_xml, grab_tasks},
     runtime::error::RuntimeError,
     structs::artifacts::os::windows::TasksOptions,
+    utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -10,10 +11,37 @@ use log::error;
 #[string]
 /// Expose parsing Schedule Tasks at default systemdrive to Deno
 pub(crate) fn get_tasks() -> Result<String, AnyError> {
-    let options = TasksOptions { alt_file: None };
+    let options = TasksOptions {
+        alt_file: None,
+        alt_drive: None,
+        all_drives: None,
+        removable_drives: None,
+    };
     let task = grab_tasks(&options)?;
 
-    let results = serde_json::to_string(&task)?;
+    let results = serialize_output(&task)?;
+    Ok(results)
+}
+
+#[op2]
+#[string]
+/// Expose parsing Schedule Tasks at alternative drive to Deno
+pub(crate) fn get_alt_tasks(#[string] drive: String) -> Result<String, AnyError> {
+    if drive.is_empty() {
+        error!("[runtime] Failed to parse alt tasks drive. Need drive letter");
+        return Err(RuntimeError::ExecuteScript.into());
+    }
+    // Get the first char from string (the drive letter)
+    let drive_char = &drive.chars().next().unwrap();
+    let options = TasksOptions {
+        alt_file: None,
+        alt_drive: Some(drive_char.to_owned()),
+        all_drives: None,
+        removable_drives: None,
+    };
+    let task = grab_tasks(&options)?;
+
+    let results = serialize_output(&task)?;
     Ok(results)
 }
 
@@ -36,7 +64,7 @@ pub(crate) fn get_task_file(#[string] path: String) -> Result<String, AnyError>
             }
         };
 
-        serde_json::to_string(&task)?
+        serialize_output(&task)?
     } else {
         let task_result = grab_task_xml(&path);
         let task = match task_result {
@@ -47,7 +75,7 @@ pub(crate) fn get_task_file(#[string] path: String) -> Result<String, AnyError>
             }
         };
 
-        serde_json::to_string(&task)?
+        serialize_output(&task)?
     };
 
     Ok(results)
@@ -71,9 +99,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -88,6 +114,17 @@ mod tests {
         execute_script(&mut output, &script).unwrap();
     }
 
+    #[test]
+    fn test_get_alt_tasks() {
+        let test = "ZnVuY3Rpb24gZ2V0QWx0VGFza3MoZHJpdmUpIHsKICBjb25zdCBkYXRhID0gRGVuby5jb3JlLm9wcy5nZXRfYWx0X3Rhc2tzKGRyaXZlKTsKICBjb25zdCB0YXNrcyA9IEpTT04ucGFyc2UoZGF0YSk7CiAgcmV0dXJuIHRhc2tzOwp9CgovLyBtYWluLnRzCmZ1bmN0aW9uIG1haW4oKSB7CiAgY29uc3QgdGFza3MgPSBnZXRBbHRUYXNrcygiQyIpOwogIGlmICh0YXNrcyBpbnN0YW5jZW9mIEVycm9yKSB7CiAgICBjb25zb2xlLmVycm9yKGBHb3QgdGFzayBwYXJzaW5nIGVycm9yISAke3Rhc2tzfWApOwogIH0KICByZXR1cm4gdGFza3M7Cn0KbWFpbigpOwo=";
+        let mut output = output_options("runtime_test", "local", "./tmp", false);
+        let script = JSScript {
+            name: String::from("task_alt"),
+            script: test.to_string(),
+        };
+        execute_script(&mut output, &script).unwrap();
+    }
+
     #[test]
     fn test_get_task_file() {
         let test = "Ly8gaHR0cHM6Ly9yYXcuZ2l0aHVidXNlcmNvbnRlbnQuY29tL3B1ZmZ5Y2lkL2FydGVtaXMtYXBpL21hc3Rlci9zcmMvd2luZG93cy90YXNrcy50cwpmdW5jdGlvbiBnZXRUYXNrRmlsZShwYXRoKSB7CiAgY29uc3QgZGF0YSA9IERlbm8uY29yZS5vcHMuZ2V0X3Rhc2tfZmlsZShwYXRoKTsKICBjb25zdCB0YXNrcyA9IEpTT04ucGFyc2UoZGF0YSk7CiAgcmV0dXJuIHRhc2tzOwp9CgovLyBodHRwczovL3Jhdy5naXRodWJ1c2VyY29udGVudC5jb20vcHVmZnljaWQvYXJ0ZW1pcy1hcGkvbWFpbi9zcmMvZmlsZXN5c3RlbS9maWxlcy50cwpmdW5jdGlvbiBnbG9iKHBhdHRlcm4pIHsKICBjb25zdCBkYXRhID0gZnMuZ2xvYihwYXR0ZXJuKTsKICBjb25zdCByZXN1bHQgPSBKU09OLnBhcnNlKGRhdGEpOwogIHJldHVybiByZXN1bHQ7Cn0KCi8vIG1haW4udHMKZnVuY3Rpb24gbWFpbigpIHsKICBjb25zdCB4bWxfZmlsZXMgPSBnbG9iKCJDOlxcV2luZG93c1xcU3lzdGVtMzJcXFRhc2tzXFwqIik7CiAgaWYgKHhtbF9maWxlcyBpbnN0YW5jZW9mIEVycm9yKSB7CiAgICBjb25zb2xlLmVycm9yKGBHb3QgZ2xvYmJpbmcgZXJyb3IhICR7eG1sX2ZpbGVzfWApOwogICAgcmV0dXJuIHhtbF9maWxlczsKICB9CiAgZm9yIChjb25zdCBlbnRyeSBvZiB4bWxfZmlsZXMpIHsKICAgIGlmICghZW50cnkuaXNfZmlsZSkgewogICAgICBjb250aW51ZTsKICAgIH0KICAgIHJldHVybiBnZXRUYXNrRmlsZShlbnRyeS5mdWxsX3BhdGgpOwogIH0KfQptYWluKCk7Cg==";
@@ -1,6 +1,6 @@
 use crate::{
     artifacts::os::windows::userassist::parser::grab_userassist, runtime::error::RuntimeError,
-    structs::artifacts::os::windows::UserAssistOptions,
+    structs::artifacts::os::windows::UserAssistOptions, utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -16,7 +16,7 @@ pub(crate) fn get_userassist(resolve: bool) -> Result<String, AnyError> {
 
     let assist = grab_userassist(&options)?;
 
-    let results = serde_json::to_string(&assist)?;
+    let results = serialize_output(&assist)?;
     Ok(results)
 }
 
@@ -38,7 +38,7 @@ pub(crate) fn get_alt_userassist(
     };
 
     let assist = grab_userassist(&options)?;
-    let results = serde_json::to_string(&assist)?;
+    let results = serialize_output(&assist)?;
 
     Ok(results)
 }
@@ -61,9 +61,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -1,6 +1,6 @@
 use crate::{
     artifacts::os::windows::usnjrnl::parser::grab_usnjrnl, runtime::error::RuntimeError,
-    structs::artifacts::os::windows::UsnJrnlOptions,
+    structs::artifacts::os::windows::UsnJrnlOptions, utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -12,10 +12,12 @@ pub(crate) fn get_usnjrnl() -> Result<String, AnyError> {
     let options = UsnJrnlOptions {
         alt_drive: None,
         alt_path: None,
+        all_drives: None,
+        removable_drives: None,
     };
     let jrnl = grab_usnjrnl(&options)?;
 
-    let results = serde_json::to_string(&jrnl)?;
+    let results = serialize_output(&jrnl)?;
     Ok(results)
 }
 
@@ -32,11 +34,13 @@ pub(crate) fn get_alt_usnjrnl(#[string] drive: String) -> Result<String, AnyErro
     let options = UsnJrnlOptions {
         alt_drive: Some(drive_char.to_owned()),
         alt_path: None,
+        all_drives: None,
+        removable_drives: None,
     };
 
     let jrnl = grab_usnjrnl(&options)?;
 
-    let results = serde_json::to_string(&jrnl)?;
+    let results = serialize_output(&jrnl)?;
     Ok(results)
 }
 
@@ -58,9 +62,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
@@ -1,6 +1,6 @@
 use crate::{
     artifacts::os::windows::wmi::parser::grab_wmi_persist, runtime::error::RuntimeError,
-    structs::artifacts::os::windows::WmiPersistOptions,
+    structs::artifacts::os::windows::WmiPersistOptions, utils::output::serialize_output,
 };
 use deno_core::{error::AnyError, op2};
 use log::error;
@@ -12,7 +12,7 @@ pub(crate) fn get_wmipersist() -> Result<String, AnyError> {
     let options = WmiPersistOptions { alt_dir: None };
 
     let assist = grab_wmi_persist(&options)?;
-    let results = serde_json::to_string(&assist)?;
+    let results = serialize_output(&assist)?;
 
     Ok(results)
 }
@@ -32,7 +32,7 @@ pub(crate) fn get_alt_wmipersist(#[string] path: String) -> Result<String, AnyEr
 
     let assist = grab_wmi_persist(&options)?;
 
-    let results = serde_json::to_string(&assist)?;
+    let results = serialize_output(&assist)?;
     Ok(results)
 }
 
@@ -54,9 +54,7 @@ mod tests {
             endpoint_id: String::from("abcd"),
             collection_id: 0,
             output: output.to_string(),
-            filter_name: None,
-            filter_script: None,
-            logging: None,
+            ..Default::default()
         }
     }
 
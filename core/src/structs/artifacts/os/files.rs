@@ -1,9 +1,11 @@
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct FileOptions {
     pub start_path: String,
     pub depth: Option<u8>,
+    /**Follow symbolic links and junctions. Directories that were already walked are skipped. Default is false */
+    pub follow_links: Option<bool>,
     pub metadata: Option<bool>,
     pub md5: Option<bool>,
     pub sha1: Option<bool>,
@@ -1,16 +1,26 @@
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct PrefetchOptions {
     pub alt_dir: Option<String>,
+    /**Flag prefetch entries whose executable has high entropy (possibly packed). Requires reading each executable. An `alt_dir` must end in `Windows\Prefetch` so the volume can be found */
+    pub entropy: Option<bool>,
+    /**Max number of last run times output for each prefetch file. The most recent is always kept. Default is 8 */
+    pub max_last_run_times: Option<usize>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct EventLogsOptions {
     pub alt_file: Option<String>,
+    /**Parse the `EventLogs` at the default path on this drive instead of the systemdrive */
+    pub alt_drive: Option<char>,
+    /**Only output records from these channels. Ex: `Security` */
+    pub channels: Option<Vec<String>>,
+    /**Only output records with these EventIDs */
+    pub event_ids: Option<Vec<u64>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct RawFilesOptions {
     pub drive_letter: char,
     pub start_path: String,
@@ -25,12 +35,12 @@ pub struct RawFilesOptions {
     pub filename_regex: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct ShimdbOptions {
     pub alt_file: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct RegistryOptions {
     pub user_hives: bool,
     pub system_hives: bool,
@@ -38,82 +48,110 @@ pub struct RegistryOptions {
     pub alt_file: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct UserAssistOptions {
     pub resolve_descriptions: Option<bool>,
     pub alt_file: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct AutorunsOptions {
+    pub alt_drive: Option<char>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct ShimcacheOptions {
     pub alt_file: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct ShellbagsOptions {
     pub resolve_guids: bool,
     pub alt_file: Option<String>,
+    pub alt_drive: Option<char>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct AmcacheOptions {
     pub alt_file: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct ShortcutOptions {
-    /**Path to directory containing `Shortcut (lnk)` files */
-    pub path: String,
+    /**Path to directory containing `Shortcut (lnk)` files. Defaults to the Recent and Desktop directories for all users */
+    pub path: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct UsnJrnlOptions {
     pub alt_drive: Option<char>,
     pub alt_path: Option<String>,
+    /**Collect from every mounted NTFS drive. Output records are tagged with the source drive */
+    pub all_drives: Option<bool>,
+    /**Include removable drives when `all_drives` is set */
+    pub removable_drives: Option<bool>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct BitsOptions {
     pub alt_file: Option<String>,
     pub carve: bool,
+    /// Maximum number of carved jobs, files, and entries to return
+    pub max_carved: Option<usize>,
+    /// Number of threads used to scan the BITS data when carving. Default is 1
+    pub carve_threads: Option<usize>,
+    /// Size in bytes of each chunk scanned when carving. Rounded up to a page boundary. Default splits the data evenly between the threads
+    pub carve_chunk_size: Option<usize>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct SrumOptions {
     pub alt_file: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct WindowsUserOptions {
     pub alt_file: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct SearchOptions {
     pub alt_file: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct TasksOptions {
     pub alt_file: Option<String>,
+    pub alt_drive: Option<char>,
+    /**Collect from every mounted NTFS drive. Output records are tagged with the source drive */
+    pub all_drives: Option<bool>,
+    /**Include removable drives when `all_drives` is set */
+    pub removable_drives: Option<bool>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct ServicesOptions {
     pub alt_file: Option<String>,
+    pub alt_drive: Option<char>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct JumplistsOptions {
     pub alt_file: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct RecycleBinOptions {
     pub alt_file: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct WmiPersistOptions {
     pub alt_dir: Option<String>,
 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ExecutionOptions {
+    /**Artifacts to check for execution. Can be combination of: prefetch, amcache, userassist, bits, or srum. Empty to check all */
+    pub sources: Vec<String>,
+}
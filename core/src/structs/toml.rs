@@ -4,25 +4,30 @@ use super::artifacts::os::macos::{
     MacosGroupsOptions, MacosSudoOptions, MacosUsersOptions, SpotlightOptions, UnifiedLogsOptions,
 };
 use crate::structs::artifacts::os::windows::{
-    AmcacheOptions, BitsOptions, EventLogsOptions, JumplistsOptions, PrefetchOptions,
-    RawFilesOptions, RecycleBinOptions, RegistryOptions, SearchOptions, ServicesOptions,
-    ShellbagsOptions, ShimcacheOptions, ShimdbOptions, ShortcutOptions, SrumOptions, TasksOptions,
-    UserAssistOptions, UsnJrnlOptions, WindowsUserOptions, WmiPersistOptions,
+    AmcacheOptions, AutorunsOptions, BitsOptions, EventLogsOptions, ExecutionOptions,
+    JumplistsOptions, PrefetchOptions, RawFilesOptions, RecycleBinOptions, RegistryOptions,
+    SearchOptions, ServicesOptions, ShellbagsOptions, ShimcacheOptions, ShimdbOptions,
+    ShortcutOptions, SrumOptions, TasksOptions, UserAssistOptions, UsnJrnlOptions,
+    WindowsUserOptions, WmiPersistOptions,
 };
 use crate::structs::artifacts::{
     os::{files::FileOptions, processes::ProcessOptions},
     runtime::script::JSScript,
 };
+use common::server::jobs::CollectionEvent;
 use serde::Deserialize;
+use std::sync::mpsc::SyncSender;
 
 #[derive(Debug, Deserialize)]
 pub struct ArtemisToml {
     pub system: String,
     pub output: Output,
     pub artifacts: Vec<Artifacts>,
+    /**Number of artifacts to collect at the same time. Default is 1. Parsing runs in parallel while output writes and batched uploads are serialized */
+    pub workers: Option<usize>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct Output {
     pub name: String,
     pub endpoint_id: String,
@@ -35,15 +40,94 @@ pub struct Output {
     pub filter_script: Option<String>,
     pub url: Option<String>,
     pub api_key: Option<String>,
+    /**Optional proxy to use for remote uploads. Otherwise `HTTPS_PROXY` and `HTTP_PROXY` are used if set */
+    pub proxy_url: Option<String>,
+    /**Optional User-Agent to use for remote uploads */
+    pub user_agent: Option<String>,
+    /**Skip TLS certificate validation for remote uploads. Only use for internal CAs */
+    pub tls_insecure: Option<bool>,
+    /**Only output records whose primary timestamp is at or after this UnixEpoch (seconds) */
+    pub since_epoch: Option<i64>,
+    /**Batch remote uploads until this many bytes are buffered. Remaining data is uploaded at the end of the collection. Only `jsonl` output is batched */
+    pub batch_bytes: Option<u64>,
+    /**Batch remote uploads until this many artifact outputs are buffered */
+    pub batch_count: Option<u64>,
+    /**Replace an existing SQLite database instead of appending to it. Only used when output is `sqlite` */
+    pub sqlite_replace: Option<bool>,
+    /**Copy the primary timestamp of each artifact record to this field. Ex: `@timestamp` */
+    pub timestamp_field: Option<String>,
+    /**Format of the copied timestamp: `epoch_seconds`, `epoch_millis`, or `iso8601`. Default is `epoch_seconds`. Without `timestamp_field` the copy is written to `<field>_formatted` */
+    pub timestamp_format: Option<String>,
+    /**Remove the original timestamp field after copying it to `timestamp_field`. Without `timestamp_field` the original field is overwritten with the formatted timestamp */
+    pub timestamp_replace: Option<bool>,
+    /**Also output the primary timestamp as ISO8601 in `<field>_iso` next to the epoch `<field>` */
+    pub timestamp_iso: Option<bool>,
+    /**Attach the base64 encoded raw bytes of each parsed structure to its record under `_raw`. Only supported by some parsers */
+    pub include_raw: Option<bool>,
+    /**Write the records from all artifacts to a single `combined` JSONL file. Each line is tagged with its `artifact` name. Only used when format is `jsonl` and output is `local` */
+    pub combined_output: Option<bool>,
+    /**Max attempts for a remote upload request. Server errors, timeouts, and connection failures are retried. Default is 15 */
+    pub upload_attempts: Option<u32>,
+    /**Base delay in milliseconds for exponential backoff between remote upload attempts. Default is 500 */
+    pub upload_backoff_ms: Option<u64>,
+    /**Path to a baseline JSON file from a previous collection. Only records added, removed, or changed since the baseline are output as `<artifact>_diff` */
+    pub baseline: Option<String>,
+    /**Base directory that a relative `directory` is resolved against. Otherwise `ARTEMIS_OUTPUT_BASE` is used if set */
+    pub output_base: Option<String>,
+    /**Skip source files modified less than this many seconds ago. Avoids reading files that are still being written to */
+    pub source_mtime_min: Option<u64>,
+    /**Skip source files modified more than this many seconds ago */
+    pub source_mtime_max: Option<u64>,
+    /**Do not parse source files larger than this many bytes. They are output as `<artifact>_oversize` metadata entries instead */
+    pub max_parse_bytes: Option<u64>,
+    /**Split each artifact's records into `<artifact>_<volume>` outputs keyed by the source volume (ex: prefetch `volume_serial` or `source_drive`) */
+    pub split_by_volume: Option<bool>,
+    /**Emit the original FILETIME value alongside FILETIME derived timestamps as `<field>_filetime` (ex: prefetch `volume_creation_filetime`). Only supported by `prefetch`. Other artifacts log a warning and ignore it */
+    pub keep_raw_filetime: Option<bool>,
+    /**Abort raw NTFS and ESE file reads that take longer than this many seconds. The read fails with a timeout error so the collection can move on */
+    pub read_timeout_secs: Option<u64>,
+    /**Sort each artifact's records by their primary timestamp (ascending) before writing. Each artifact output is held in memory while sorting. Disabled when remote uploads are batched */
+    pub sort_by_time: Option<bool>,
+    /**Hostname to use in output metadata instead of the live hostname. Useful when parsing images offline */
+    pub hostname_override: Option<String>,
+    /**Only allow scripts to call these runtime ops. All ops are allowed if not set */
+    pub script_allow_ops: Option<Vec<String>>,
+    /**Block scripts from calling these runtime ops. Takes precedence over `script_allow_ops` */
+    pub script_deny_ops: Option<Vec<String>>,
+    /**Pretty print `json` output. Does not apply to `jsonl` output */
+    pub pretty: Option<bool>,
+    /**Seconds to wait when connecting to the remote upload service. Default has no limit */
+    pub upload_connect_timeout_secs: Option<u64>,
+    /**Seconds to wait for a remote upload request to finish. Default is 30 */
+    pub upload_timeout_secs: Option<u64>,
+    /**Add a `time_anomaly` field to records whose primary timestamp is in the future or before `time_anomaly_floor` */
+    pub time_anomalies: Option<bool>,
+    /**UnixEpoch (seconds) that earlier primary timestamps are flagged as implausible. Default is 946684800 (2000-01-01) */
+    pub time_anomaly_floor: Option<i64>,
     pub logging: Option<String>,
+    /**Optional channel to send collection progress events to. Not part of the TOML file */
+    #[serde(skip)]
+    pub events: Option<SyncSender<CollectionEvent>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct Artifacts {
     /**Based on artifact parse one of the artifact types */
     pub artifact_name: String,
     /**Specify whether to filter the parsed data */
     pub filter: Option<bool>,
+    /**Stop waiting for the artifact after this many seconds. Any output from the parser after this point is discarded */
+    pub timeout: Option<u64>,
+    /**Artifacts with a higher priority are collected first. Artifacts with the same priority keep the TOML order. Default is 0 */
+    pub priority: Option<i64>,
+    /**Only output the filesystem metadata of the artifact source files instead of parsing them. Only supported by path-based artifacts */
+    pub metadata_only: Option<bool>,
+    /**Only keep these fields in the output records. Unknown field names are logged and ignored */
+    pub fields: Option<Vec<String>>,
+    /**Appended to the output name of the artifact. Keeps the output of the same artifact collected multiple times separate */
+    pub label: Option<String>,
+    /**Transforms applied in order to each output record. Ex: rename, drop, add_constant, hash_field */
+    pub transforms: Option<Vec<RecordTransform>>,
     pub processes: Option<ProcessOptions>,
     pub files: Option<FileOptions>,
     pub unifiedlogs: Option<UnifiedLogsOptions>,
@@ -80,4 +164,23 @@ pub struct Artifacts {
     pub jumplists: Option<JumplistsOptions>,
     pub recyclebin: Option<RecycleBinOptions>,
     pub wmipersist: Option<WmiPersistOptions>,
+    pub execution: Option<ExecutionOptions>,
+    pub autoruns: Option<AutorunsOptions>,
+}
+
+/// Built-in transform applied to the output records of an artifact. Selected by the `type` key
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum RecordTransform {
+    /**Rename the field `from` to `to` */
+    Rename { from: String, to: String },
+    /**Remove the fields from each record */
+    Drop { fields: Vec<String> },
+    /**Add a field with the same value to each record. Replaces an existing field */
+    AddConstant {
+        field: String,
+        value: serde_json::Value,
+    },
+    /**Replace the field value with its SHA256 hash. Optional `salt` is hashed before the value */
+    HashField { field: String, salt: Option<String> },
 }
@@ -1,13 +1,52 @@
-use super::error::ArtemisError;
-use crate::structs::toml::ArtemisToml;
+use super::{
+    compression::decompress::decompress_gzip_data, environment::expand_env, error::ArtemisError,
+    output::transform::validate_transforms,
+};
+use crate::structs::toml::{ArtemisToml, Output};
 use log::error;
 use std::str::from_utf8;
+use toml::Value;
+
+/// Artifact options that contain paths. Environment variables in these options are expanded
+const PATH_OPTIONS: [&str; 6] = [
+    "alt_dir",
+    "alt_file",
+    "alt_path",
+    "logarchive_path",
+    "path",
+    "start_path",
+];
 
 impl ArtemisToml {
-    // Parse the Artemis TOML collector file
+    // Parse the Artemis TOML collector file. Gzip compressed TOML files are decompressed first
     pub(crate) fn parse_artemis_toml(toml_data: &[u8]) -> Result<ArtemisToml, ArtemisError> {
+        let gzip_magic = [0x1f, 0x8b];
+        let decompressed;
+        let toml_data = if toml_data.starts_with(&gzip_magic) {
+            decompressed = match decompress_gzip_data(toml_data) {
+                Ok(result) => result,
+                Err(err) => {
+                    error!("[artemis-core] Artemis failed to decompress TOML data. Error: {err:?}");
+                    return Err(ArtemisError::BadToml);
+                }
+            };
+            &decompressed
+        } else {
+            toml_data
+        };
+
         let toml_results = toml::from_str(from_utf8(toml_data).unwrap_or_default());
-        let mut artemis_collector: ArtemisToml = match toml_results {
+        let mut toml_value: Value = match toml_results {
+            Ok(results) => results,
+            Err(err) => {
+                error!("[artemis-core] Artemis failed to parse TOML data. Error: {err:?}");
+                return Err(ArtemisError::BadToml);
+            }
+        };
+        expand_artifact_paths(&mut toml_value)?;
+
+        let collector_results = toml_value.try_into();
+        let mut artemis_collector: ArtemisToml = match collector_results {
             Ok(results) => results,
             Err(err) => {
                 error!("[artemis-core] Artemis failed to parse TOML data. Error: {err:?}");
@@ -15,12 +54,71 @@ impl ArtemisToml {
             }
         };
 
+        for artifact in &artemis_collector.artifacts {
+            if let Some(transforms) = &artifact.transforms {
+                if let Err(err) = validate_transforms(transforms) {
+                    error!(
+                        "[artemis-core] Artemis failed to parse {} transforms. Error: {err:?}",
+                        artifact.artifact_name
+                    );
+                    return Err(ArtemisError::BadToml);
+                }
+            }
+        }
+
         // Format is always lowercase
         artemis_collector.output.format = artemis_collector.output.format.to_lowercase();
+        validate_output(&artemis_collector.output)?;
         Ok(artemis_collector)
     }
 }
 
+/// Reject output options that cannot work together
+fn validate_output(output: &Output) -> Result<(), ArtemisError> {
+    // Remote uploads use the same combined file name for every artifact. Each upload would overwrite the previous one
+    if output.combined_output.unwrap_or(false) && output.output != "local" {
+        error!(
+            "[artemis-core] combined_output is only supported for local output. Got {}",
+            output.output
+        );
+        return Err(ArtemisError::BadToml);
+    }
+    Ok(())
+}
+
+/// Expand environment variables in the path options of every artifact
+fn expand_artifact_paths(toml_value: &mut Value) -> Result<(), ArtemisError> {
+    let artifacts = match toml_value
+        .get_mut("artifacts")
+        .and_then(Value::as_array_mut)
+    {
+        Some(result) => result,
+        None => return Ok(()),
+    };
+
+    for artifact in artifacts {
+        let tables = match artifact.as_table_mut() {
+            Some(result) => result,
+            None => continue,
+        };
+        for (_, options) in tables.iter_mut() {
+            let options = match options.as_table_mut() {
+                Some(result) => result,
+                None => continue,
+            };
+            for (key, value) in options.iter_mut() {
+                if !PATH_OPTIONS.contains(&key.as_str()) {
+                    continue;
+                }
+                if let Value::String(path) = value {
+                    *path = expand_env(path)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{filesystem::files::read_file, utils::artemis_toml::ArtemisToml};
@@ -46,6 +144,29 @@ mod tests {
         assert_eq!(result.artifacts[0].processes.as_ref().unwrap().md5, true);
     }
 
+    #[test]
+    fn test_parse_artemis_toml_gzip() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows.toml");
+        let plain = read_file(&test_location.display().to_string()).unwrap();
+
+        test_location.set_extension("toml.gz");
+        let gzip = read_file(&test_location.display().to_string()).unwrap();
+
+        let plain_result = ArtemisToml::parse_artemis_toml(&plain).unwrap();
+        let gzip_result = ArtemisToml::parse_artemis_toml(&gzip).unwrap();
+        assert_eq!(format!("{plain_result:?}"), format!("{gzip_result:?}"));
+        assert_eq!(gzip_result.system, "windows");
+        assert!(!gzip_result.artifacts.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "BadToml")]
+    fn test_parse_artemis_bad_gzip_toml() {
+        let data = [0x1f, 0x8b, 0, 0, 0, 0];
+        ArtemisToml::parse_artemis_toml(&data).unwrap();
+    }
+
     #[test]
     #[should_panic(expected = "BadToml")]
     fn test_parse_artemis_bad_toml() {
@@ -63,4 +184,127 @@ mod tests {
 
         assert_eq!(result.artifacts[0].artifact_name, "processes");
     }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_parse_artemis_toml_env_paths() {
+        use crate::utils::environment::get_env_value;
+
+        let toml = r#"
+system = "linux"
+
+[output]
+name = "env_paths"
+directory = "./tmp"
+format = "json"
+compress = false
+endpoint_id = "abdc"
+collection_id = 1
+output = "local"
+
+[[artifacts]]
+artifact_name = "files"
+[artifacts.files]
+start_path = "${PATH}/$PATH"
+"#;
+        let result = ArtemisToml::parse_artemis_toml(toml.as_bytes()).unwrap();
+        let path = get_env_value("PATH");
+        assert_eq!(
+            result.artifacts[0].files.as_ref().unwrap().start_path,
+            format!("{path}/{path}")
+        );
+
+        let toml = toml.replace("$PATH", "$ARTEMIS_UNDEFINED_PATH");
+        assert!(ArtemisToml::parse_artemis_toml(toml.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_parse_artemis_toml_transforms() {
+        use crate::structs::toml::RecordTransform;
+        use serde_json::json;
+
+        let toml = r#"
+system = "linux"
+
+[output]
+name = "transforms"
+directory = "./tmp"
+format = "json"
+compress = false
+endpoint_id = "abdc"
+collection_id = 1
+output = "local"
+
+[[artifacts]]
+artifact_name = "processes"
+transforms = [
+    { type = "rename", from = "name", to = "process_name" },
+    { type = "drop", fields = ["arguments", "environment"] },
+    { type = "add_constant", field = "case", value = "IR-1234" },
+    { type = "hash_field", field = "path" },
+]
+[artifacts.processes]
+md5 = false
+sha1 = false
+sha256 = false
+metadata = false
+"#;
+        let result = ArtemisToml::parse_artemis_toml(toml.as_bytes()).unwrap();
+        let transforms = result.artifacts[0].transforms.as_ref().unwrap();
+        assert_eq!(transforms.len(), 4);
+        assert_eq!(
+            transforms[2],
+            RecordTransform::AddConstant {
+                field: String::from("case"),
+                value: json!("IR-1234"),
+            }
+        );
+        assert_eq!(
+            transforms[3],
+            RecordTransform::HashField {
+                field: String::from("path"),
+                salt: None,
+            }
+        );
+
+        let unknown = toml.replace("\"drop\"", "\"upper\"");
+        assert!(ArtemisToml::parse_artemis_toml(unknown.as_bytes()).is_err());
+
+        let missing = toml.replace(", to = \"process_name\"", "");
+        assert!(ArtemisToml::parse_artemis_toml(missing.as_bytes()).is_err());
+
+        let extra = toml.replace("field = \"path\"", "field = \"path\", algorithm = \"md5\"");
+        assert!(ArtemisToml::parse_artemis_toml(extra.as_bytes()).is_err());
+
+        let empty = toml.replace("[\"arguments\", \"environment\"]", "[]");
+        assert!(ArtemisToml::parse_artemis_toml(empty.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_parse_artemis_toml_combined_output() {
+        let toml = r#"
+system = "linux"
+
+[output]
+name = "combined"
+directory = "./tmp"
+format = "jsonl"
+compress = false
+endpoint_id = "abdc"
+collection_id = 1
+output = "local"
+combined_output = true
+
+[[artifacts]]
+artifact_name = "systeminfo"
+"#;
+        let result = ArtemisToml::parse_artemis_toml(toml.as_bytes()).unwrap();
+        assert!(result.output.combined_output.unwrap());
+
+        // Every artifact would overwrite the same remote combined file
+        for remote in ["gcp", "azure", "aws"] {
+            let toml = toml.replace("output = \"local\"", &format!("output = \"{remote}\""));
+            assert!(ArtemisToml::parse_artemis_toml(toml.as_bytes()).is_err());
+        }
+    }
 }
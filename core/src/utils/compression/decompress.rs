@@ -23,13 +23,18 @@ pub(crate) fn decompress_gzip(path: &str) -> Result<Vec<u8>, CompressionError> {
             return Err(CompressionError::GzipReadFile);
         }
     };
-    let mut data = MultiGzDecoder::new(&buffer[..]);
+    decompress_gzip_data(&buffer)
+}
+
+/// Decompress gzip compressed data
+pub(crate) fn decompress_gzip_data(data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+    let mut decoder = MultiGzDecoder::new(data);
 
     let mut decompress_data = Vec::new();
-    let result = data.read_to_end(&mut decompress_data);
+    let result = decoder.read_to_end(&mut decompress_data);
     if result.is_err() {
         error!(
-            "[compression] Could not decompress file {path}: {:?}",
+            "[compression] Could not decompress gzip data: {:?}",
             result.unwrap_err()
         );
         return Err(CompressionError::GzipDecompress);
@@ -194,6 +199,18 @@ mod tests {
         assert_eq!(files.len(), 78970);
     }
 
+    #[test]
+    fn test_decompress_gzip_data() {
+        use crate::utils::compression::{
+            compress::compress_gzip_data, decompress::decompress_gzip_data,
+        };
+
+        let data = compress_gzip_data(b"A rust program").unwrap();
+        let result = decompress_gzip_data(&data).unwrap();
+        assert_eq!(result, b"A rust program");
+        assert!(decompress_gzip_data(b"A rust program").is_err());
+    }
+
     #[test]
     fn test_decompress_zstd() {
         let test_data = [
@@ -0,0 +1,120 @@
+/**
+ * Shannon entropy helpers used to flag packed or compressed executables
+ * Entropy ranges from 0 (uniform data) to 8 (random data). Packed sections are typically above 7
+ */
+use crate::filesystem::files::read_file_custom;
+use log::error;
+use pelite::PeFile;
+
+/// Entropy at or above this value is considered high
+pub(crate) const HIGH_ENTROPY: f64 = 7.2;
+/// Files larger than 256MB are not read for entropy
+const MAX_ENTROPY_SIZE: u64 = 268435456;
+
+/// Calculate the Shannon entropy (bits per byte) of the provided data
+pub(crate) fn shannon_entropy(data: &[u8]) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+
+    let mut counts = [0u64; 256];
+    for byte in data {
+        counts[*byte as usize] += 1;
+    }
+
+    let size = data.len() as f64;
+    counts
+        .iter()
+        .filter(|count| **count != 0)
+        .map(|count| {
+            let probability = *count as f64 / size;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
+/**
+ * Calculate the highest entropy of a file. If the file is a `PE` file the highest section entropy is returned
+ * Otherwise the entropy of the whole file is returned. Files larger than `MAX_ENTROPY_SIZE` are skipped
+ */
+pub(crate) fn file_entropy(path: &str) -> Option<f64> {
+    let data_result = read_file_custom(path, &MAX_ENTROPY_SIZE);
+    let data = match data_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Could not read {path} for entropy: {err:?}");
+            return None;
+        }
+    };
+
+    if let Some(entropy) = pe_entropy(&data) {
+        return Some(entropy);
+    }
+    Some(shannon_entropy(&data))
+}
+
+/// Check if a file has high entropy
+pub(crate) fn is_high_entropy(path: &str) -> bool {
+    match file_entropy(path) {
+        Some(entropy) => entropy >= HIGH_ENTROPY,
+        None => false,
+    }
+}
+
+/// Get the highest section entropy of `PE` data. Returns `None` if the data is not a `PE` file or has no sections
+fn pe_entropy(data: &[u8]) -> Option<f64> {
+    let pe = PeFile::from_bytes(data).ok()?;
+
+    let mut highest: Option<f64> = None;
+    for section in pe.section_headers().iter() {
+        let section_data = match pe.get_section_bytes(section) {
+            Ok(result) => result,
+            Err(_err) => continue,
+        };
+        let entropy = shannon_entropy(section_data);
+        if highest.unwrap_or(0.0) <= entropy {
+            highest = Some(entropy);
+        }
+    }
+    highest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{file_entropy, is_high_entropy, pe_entropy, shannon_entropy};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_shannon_entropy() {
+        assert_eq!(shannon_entropy(&[]), 0.0);
+        assert_eq!(shannon_entropy(&[65; 100]), 0.0);
+        assert_eq!(shannon_entropy(&[0, 1, 0, 1]), 1.0);
+
+        let data: Vec<u8> = (0..=255).collect();
+        assert_eq!(shannon_entropy(&data), 8.0);
+    }
+
+    #[test]
+    fn test_file_entropy() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/windows/prefetch/win10/_IU14D2N.TMP-136252D4.pf");
+
+        // Prefetch files on Windows 10 are compressed
+        let entropy = file_entropy(&test_location.display().to_string()).unwrap();
+        assert!(entropy > 7.0);
+
+        assert!(file_entropy("/does/not/exist").is_none());
+        assert!(!is_high_entropy("/does/not/exist"));
+        assert!(pe_entropy(&[0, 1, 2, 3]).is_none());
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_pe_entropy() {
+        use crate::filesystem::files::read_file_custom;
+
+        let data = read_file("C:\\Windows\\explorer.exe").unwrap();
+        let entropy = pe_entropy(&data).unwrap();
+        assert!(entropy > 0.0 && entropy < 8.0);
+    }
+}
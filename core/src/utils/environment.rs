@@ -83,6 +83,55 @@ pub(crate) fn get_env_value(value: &str) -> String {
     String::new()
 }
 
+/**
+ * Get an environment variable value. Returns an empty string if the variable is not set  
+ * On Windows variable names are case insensitive. If the artemis process does not have the variable the system environment in the `SYSTEM` Registry file is checked
+ */
+pub(crate) fn get_environment_value(key: &str) -> Result<String, ArtemisError> {
+    let envs = get_env();
+    if !cfg!(target_os = "windows") {
+        return Ok(envs.get(key).cloned().unwrap_or_default());
+    }
+
+    if let Some((_, value)) = envs.iter().find(|(name, _)| name.eq_ignore_ascii_case(key)) {
+        return Ok(value.clone());
+    }
+
+    let system_envs = get_system_env()?;
+    let value = system_envs
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value.clone())
+        .unwrap_or_default();
+
+    // Values may be REG_EXPAND_SZ. Keep the raw value if it references undefined variables
+    Ok(expand_windows_env(&value, &envs).unwrap_or(value))
+}
+
+/// Get the system environment variables from the `SYSTEM` Registry file. Only the first `ControlSet` is used
+fn get_system_env() -> Result<HashMap<String, String>, ArtemisError> {
+    let systemdrive = get_systemdrive()?;
+    let path = format!("{systemdrive}:\\Windows\\System32\\config\\SYSTEM");
+    let reg_start = "";
+    let path_regex = create_regex(r".*\\controlset\d+\\control\\session manager\\environment$")?;
+    let reg_results = get_registry_keys(reg_start, &path_regex, &path);
+    let reg_values = match reg_results {
+        Ok(results) => results,
+        Err(err) => {
+            error!("[artemis-core] Could not get system environment variables: {err:?}");
+            return Err(ArtemisError::Env);
+        }
+    };
+
+    let mut environment = HashMap::new();
+    if let Some(entry) = reg_values.into_iter().next() {
+        for value in entry.values {
+            environment.insert(value.value, value.data);
+        }
+    }
+    Ok(environment)
+}
+
 /// Get all environment variables associated with artemis process
 pub(crate) fn get_env() -> HashMap<String, String> {
     let envs = vars_os();
@@ -96,9 +145,174 @@ pub(crate) fn get_env() -> HashMap<String, String> {
     environment
 }
 
+/**
+ * Expand environment variables in a path. Windows paths use `%VAR%` and other platforms use `$VAR` or `${VAR}`
+ * Returns an error if a variable is not defined
+ */
+pub(crate) fn expand_env(path: &str) -> Result<String, ArtemisError> {
+    let envs = get_env();
+    if cfg!(target_os = "windows") {
+        expand_windows_env(path, &envs)
+    } else {
+        expand_unix_env(path, &envs)
+    }
+}
+
+/// Expand `%VAR%` tokens. Variable names are case insensitive
+fn expand_windows_env(path: &str, envs: &HashMap<String, String>) -> Result<String, ArtemisError> {
+    let mut expanded = String::new();
+    let mut remaining = path;
+    while let Some(start) = remaining.find('%') {
+        expanded.push_str(&remaining[..start]);
+        let after = &remaining[start + 1..];
+        let end = match after.find('%') {
+            Some(result) => result,
+            None => {
+                expanded.push('%');
+                remaining = after;
+                break;
+            }
+        };
+
+        let name = &after[..end];
+        // Not a variable. Keep the percent sign and continue after it
+        if name.is_empty() || !name.chars().all(is_windows_env_char) {
+            expanded.push('%');
+            remaining = after;
+            continue;
+        }
+
+        let value = envs
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value);
+        match value {
+            Some(result) => expanded.push_str(result),
+            None => {
+                error!("[artemis-core] Environment variable {name} in {path} is not defined");
+                return Err(ArtemisError::UndefinedEnv);
+            }
+        }
+        remaining = &after[end + 1..];
+    }
+    expanded.push_str(remaining);
+    Ok(expanded)
+}
+
+/// Characters allowed in a Windows environment variable name, such as `ProgramFiles(x86)`
+fn is_windows_env_char(value: char) -> bool {
+    value.is_ascii_alphanumeric() || matches!(value, '_' | '(' | ')' | '-')
+}
+
+/// Expand `$VAR` and `${VAR}` tokens
+fn expand_unix_env(path: &str, envs: &HashMap<String, String>) -> Result<String, ArtemisError> {
+    let mut expanded = String::new();
+    let mut remaining = path;
+    while let Some(start) = remaining.find('$') {
+        expanded.push_str(&remaining[..start]);
+        let after = &remaining[start + 1..];
+
+        let (name, rest) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => ("", after),
+            }
+        } else {
+            let end = after
+                .find(|value: char| !(value.is_ascii_alphanumeric() || value == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+
+        // Not a variable. Keep the dollar sign and continue after it
+        if name.is_empty() || name.starts_with(|value: char| value.is_ascii_digit()) {
+            expanded.push('$');
+            remaining = after;
+            continue;
+        }
+
+        match envs.get(name) {
+            Some(result) => expanded.push_str(result),
+            None => {
+                error!("[artemis-core] Environment variable {name} in {path} is not defined");
+                return Err(ArtemisError::UndefinedEnv);
+            }
+        }
+        remaining = rest;
+    }
+    expanded.push_str(remaining);
+    Ok(expanded)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::get_env_value;
+    use super::{
+        expand_env, expand_unix_env, expand_windows_env, get_env_value, get_environment_value,
+    };
+    use std::collections::HashMap;
+
+    fn test_envs() -> HashMap<String, String> {
+        let mut envs = HashMap::new();
+        envs.insert(String::from("SystemRoot"), String::from("C:\\Windows"));
+        envs.insert(
+            String::from("ProgramFiles(x86)"),
+            String::from("C:\\Program Files (x86)"),
+        );
+        envs.insert(String::from("HOME"), String::from("/home/bob"));
+        envs.insert(String::from("XDG_DATA"), String::from("/data"));
+        envs
+    }
+
+    #[test]
+    fn test_expand_windows_env() {
+        let envs = test_envs();
+        assert_eq!(
+            expand_windows_env("%SystemRoot%\\Prefetch", &envs).unwrap(),
+            "C:\\Windows\\Prefetch"
+        );
+        assert_eq!(
+            expand_windows_env("%SYSTEMROOT%\\%ProgramFiles(x86)%", &envs).unwrap(),
+            "C:\\Windows\\C:\\Program Files (x86)"
+        );
+        assert_eq!(
+            expand_windows_env("C:\\$Recycle.Bin\\100% done %", &envs).unwrap(),
+            "C:\\$Recycle.Bin\\100% done %"
+        );
+        assert_eq!(expand_windows_env("50%", &envs).unwrap(), "50%");
+    }
+
+    #[test]
+    fn test_expand_unix_env() {
+        let envs = test_envs();
+        assert_eq!(
+            expand_unix_env("$HOME/.zsh_history", &envs).unwrap(),
+            "/home/bob/.zsh_history"
+        );
+        assert_eq!(
+            expand_unix_env("${XDG_DATA}/logs/${HOME}", &envs).unwrap(),
+            "/data/logs//home/bob"
+        );
+        assert_eq!(
+            expand_unix_env("/tmp/$/cost$5/${", &envs).unwrap(),
+            "/tmp/$/cost$5/${"
+        );
+    }
+
+    #[test]
+    fn test_expand_env_undefined() {
+        let envs = test_envs();
+        assert!(expand_windows_env("%ARTEMIS_UNDEFINED%\\test", &envs).is_err());
+        assert!(expand_unix_env("$ARTEMIS_UNDEFINED/test", &envs).is_err());
+        assert!(expand_unix_env("${ARTEMIS_UNDEFINED}/test", &envs).is_err());
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_expand_env() {
+        let result = expand_env("$PATH").unwrap();
+        assert_eq!(result, get_env_value("PATH"));
+        assert_eq!(expand_env("/var/log").unwrap(), "/var/log");
+    }
 
     #[test]
     #[cfg(target_os = "windows")]
@@ -146,4 +360,25 @@ mod tests {
         let result = get_env_value("PATH");
         assert!(!result.is_empty())
     }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_get_environment_value() {
+        let result = get_environment_value("PATH").unwrap();
+        assert_eq!(result, get_env_value("PATH"));
+        assert!(!result.is_empty());
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_get_environment_value() {
+        let result = get_environment_value("systemdrive").unwrap();
+        assert_eq!(result, "C:");
+    }
+
+    #[test]
+    fn test_get_environment_value_unset() {
+        let result = get_environment_value("ARTEMIS_UNSET_ENV_VALUE").unwrap();
+        assert_eq!(result, "");
+    }
 }
@@ -12,6 +12,7 @@ pub enum ArtemisError {
     Cleanup,
     ReadXml,
     UtfType,
+    UndefinedEnv,
 }
 
 impl std::error::Error for ArtemisError {}
@@ -29,6 +30,7 @@ impl fmt::Display for ArtemisError {
             ArtemisError::Cleanup => write!(f, "Failed to delete artemis output files"),
             ArtemisError::ReadXml => write!(f, "Failed to read XML"),
             ArtemisError::UtfType => write!(f, "Failed to determine UTF XML type"),
+            ArtemisError::UndefinedEnv => write!(f, "Undefined environment variable in path"),
         }
     }
 }
@@ -0,0 +1,90 @@
+use crate::structs::toml::Output;
+use common::server::jobs::CollectionEvent;
+
+/// Send a progress event if the caller provided a channel. Never blocks the collection
+pub(crate) fn emit_event(output: &Output, event: CollectionEvent) {
+    if let Some(sender) = &output.events {
+        // If the channel is full or the receiver is gone we drop the event instead of stalling
+        let _ = sender.try_send(event);
+    }
+}
+
+/// Count the number of records in the serialized artifact data
+pub(crate) fn record_count(serde_data: &serde_json::Value) -> usize {
+    match serde_data {
+        serde_json::Value::Array(values) => values.len(),
+        serde_json::Value::Null => 0,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{emit_event, record_count};
+    use crate::structs::toml::Output;
+    use common::server::jobs::CollectionEvent;
+    use serde_json::json;
+    use std::sync::mpsc::sync_channel;
+
+    fn output_options() -> Output {
+        Output {
+            name: String::from("events_test"),
+            directory: String::from("./tmp"),
+            format: String::from("json"),
+            compress: false,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("local"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_emit_event() {
+        let (sender, receiver) = sync_channel(1);
+        let mut output = output_options();
+        output.events = Some(sender);
+
+        emit_event(
+            &output,
+            CollectionEvent::ArtifactStarted {
+                name: String::from("prefetch"),
+            },
+        );
+        // Channel is full, this event should be dropped without blocking
+        emit_event(
+            &output,
+            CollectionEvent::ArtifactFinished {
+                name: String::from("prefetch"),
+            },
+        );
+
+        assert_eq!(
+            receiver.try_recv().unwrap(),
+            CollectionEvent::ArtifactStarted {
+                name: String::from("prefetch")
+            }
+        );
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_emit_event_no_channel() {
+        let output = output_options();
+        emit_event(
+            &output,
+            CollectionEvent::ArtifactStarted {
+                name: String::from("prefetch"),
+            },
+        );
+    }
+
+    #[test]
+    fn test_record_count() {
+        assert_eq!(record_count(&json!([1, 2, 3])), 3);
+        assert_eq!(record_count(&json!({"key": "value"})), 1);
+        assert_eq!(record_count(&serde_json::Value::Null), 0);
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
 };
 use log::{error, warn, LevelFilter};
 use std::{
-    fs::{create_dir_all, remove_dir, remove_file, File, OpenOptions},
+    fs::{create_dir_all, remove_dir, remove_file, write, File, OpenOptions},
     io::Write,
 };
 
@@ -50,6 +50,30 @@ pub(crate) fn collection_status(
     output: &Output,
     output_name: &str,
 ) -> Result<(), ArtemisError> {
+    /*
+     * This is a simple log file that maps artifact names to the uuid filename
+     * Ex: amcache:c639679b-40ec-4aca-9ed1-dc740c38731c.json
+     * The JSON file also contains the artifact name, but this provides a single file to quickly check where each artifact was saved to
+     */
+    let status_message = format!("{artifact_name}:{output_name}.{}\n", output.format);
+    write_status(output, &status_message)
+}
+
+/**
+ * Record a path the artifact could not access in the `status.log` file
+ * Ex: amcache:access_denied:C:\Windows\appcompat\Programs\Amcache.hve
+ */
+pub(crate) fn access_denied_status(
+    artifact_name: &str,
+    output: &Output,
+    path: &str,
+) -> Result<(), ArtemisError> {
+    let status_message = format!("{artifact_name}:access_denied:{path}\n");
+    write_status(output, &status_message)
+}
+
+/// Append a line to the `status.log` file
+fn write_status(output: &Output, status_message: &str) -> Result<(), ArtemisError> {
     let path = format!("{}/{}", output.directory, output.name);
     let result = create_dir_all(&path);
     match result {
@@ -74,12 +98,6 @@ pub(crate) fn collection_status(
         }
     };
 
-    /*
-     * This is a simple log file that maps artifact names to the uuid filename
-     * Ex: amcache:c639679b-40ec-4aca-9ed1-dc740c38731c.json
-     * The JSON file also contains the artifact name, but this provides a single file to quickly check where each artifact was saved to
-     */
-    let status_message = format!("{artifact_name}:{output_name}.{}\n", output.format);
     let write_result = status.write_all(status_message.as_bytes());
     match write_result {
         Ok(_) => {}
@@ -90,6 +108,43 @@ pub(crate) fn collection_status(
     Ok(())
 }
 
+/// Sort the `status.log` file so artifacts are listed in the provided order. Unknown artifacts are listed last
+pub(crate) fn order_collection_status(
+    output: &Output,
+    artifact_names: &[&str],
+) -> Result<(), ArtemisError> {
+    let status_log = format!("{}/{}/status.log", output.directory, output.name);
+    let status_result = read_file(&status_log);
+    let status_data = match status_result {
+        Ok(result) => result,
+        Err(err) => {
+            warn!("[artemis-core] Could not read status.log at {status_log}: {err:?}");
+            return Ok(());
+        }
+    };
+
+    let status = String::from_utf8_lossy(&status_data);
+    let mut lines: Vec<&str> = status.lines().collect();
+    lines.sort_by_key(|line| {
+        let name = line.split(':').next().unwrap_or_default();
+        artifact_names
+            .iter()
+            .position(|artifact| *artifact == name)
+            .unwrap_or(artifact_names.len())
+    });
+
+    let mut ordered = lines.join("\n");
+    ordered.push('\n');
+    let write_result = write(&status_log, ordered);
+    match write_result {
+        Ok(_) => Ok(()),
+        Err(err) => {
+            error!("[artemis-core] Failed to update status.log at {status_log}. Error: {err:?}");
+            Err(ArtemisError::LogFile)
+        }
+    }
+}
+
 /// Upload artemis logs
 pub(crate) fn upload_logs(output_dir: &str, output: &Output) -> Result<(), ArtemisError> {
     let files_res = list_files(output_dir);
@@ -114,7 +169,11 @@ pub(crate) fn upload_logs(output_dir: &str, output: &Output) -> Result<(), Artem
                 continue;
             }
         };
-        final_output(&log_data, output, &get_filename(&log))?;
+        let output_result = final_output(&log_data, output, &get_filename(&log));
+        if let Err(err) = output_result {
+            error!("[artemis-core] Could not upload log file {log}: {err:?}");
+            return Err(ArtemisError::Remote);
+        }
         let _ = remove_file(&log);
     }
 
@@ -133,7 +192,7 @@ pub(crate) fn upload_logs(output_dir: &str, output: &Output) -> Result<(), Artem
 
 #[cfg(test)]
 mod tests {
-    use super::{collection_status, create_log_file, upload_logs};
+    use super::{collection_status, create_log_file, order_collection_status, upload_logs};
     use crate::structs::toml::Output;
     use httpmock::{
         Method::{POST, PUT},
@@ -159,6 +218,7 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         };
 
         let (result, level) = create_log_file(&test).unwrap();
@@ -182,11 +242,42 @@ mod tests {
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
             logging: Some(String::new()),
+            ..Default::default()
         };
 
         collection_status("test", &test, "c639679b-40ec-4aca-9ed1-dc740c38731c").unwrap();
     }
 
+    #[test]
+    fn test_order_collection_status() {
+        let test = Output {
+            name: String::from("status_order"),
+            directory: String::from("tmp"),
+            format: String::from("json"),
+            compress: false,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("local"),
+            ..Default::default()
+        };
+        let _ = std::fs::remove_file("tmp/status_order/status.log");
+
+        // Simulate workers finishing in a different order than the TOML file
+        collection_status("script_output", &test, "c").unwrap();
+        collection_status("bits", &test, "b").unwrap();
+        collection_status("prefetch", &test, "a1").unwrap();
+        collection_status("prefetch", &test, "a2").unwrap();
+
+        order_collection_status(&test, &["prefetch", "bits"]).unwrap();
+        let status = std::fs::read_to_string("tmp/status_order/status.log").unwrap();
+        assert_eq!(
+            status,
+            "prefetch:a1.json\nprefetch:a2.json\nbits:b.json\nscript_output:c.json\n"
+        );
+    }
+
     #[test]
     fn test_upload_logs() {
         let server = MockServer::start();
@@ -213,7 +304,8 @@ mod tests {
             output: String::from("gcp"),
             filter_name: Some(String::new()),
             filter_script: Some(String::new()),
-            logging: Some(String::new())
+            logging: Some(String::new()),
+            ..Default::default()
         };
 
         let mock_me = server.mock(|when, then| {
@@ -1,12 +1,16 @@
 pub(crate) mod artemis_toml;
 pub(crate) mod compression;
 pub(crate) mod encoding;
+pub(crate) mod entropy;
 pub(crate) mod environment;
 mod error;
+pub(crate) mod events;
 pub(crate) mod logging;
 pub(crate) mod nom_helper;
 pub(crate) mod output;
+pub(crate) mod raw;
 pub(crate) mod regex_options;
+pub(crate) mod stable_id;
 pub(crate) mod strings;
 pub(crate) mod time;
 pub(crate) mod uuid;
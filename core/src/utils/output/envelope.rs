@@ -0,0 +1,147 @@
+use super::{error::OutputError, output::final_output};
+use crate::{
+    artifacts::os::systeminfo::info::get_info_metadata,
+    structs::toml::Output,
+    utils::{time::time_now, uuid::generate_uuid},
+};
+use common::system::LoadPerformance;
+use log::error;
+use serde::Serialize;
+use serde_json::Value;
+
+/**
+ * Provenance for every artifact output file
+ * `json` and `jsonl` output include the envelope as the `metadata` of each record. Other formats write it to a `<uuid>.meta.json` sidecar file
+ */
+#[derive(Debug, Serialize)]
+pub(crate) struct ArtifactEnvelope {
+    pub(crate) artemis_version: String,
+    pub(crate) endpoint_id: String,
+    /**Collection ID */
+    pub(crate) id: u64,
+    pub(crate) uuid: String,
+    pub(crate) artifact_name: String,
+    pub(crate) complete_time: u64,
+    pub(crate) start_time: u64,
+    pub(crate) hostname: String,
+    pub(crate) os_version: String,
+    pub(crate) platform: String,
+    pub(crate) kernel_version: String,
+    pub(crate) load_performance: LoadPerformance,
+}
+
+/// Create the envelope for an artifact using the `Output` options and system info. `hostname_override` replaces the live hostname
+pub(crate) fn artifact_envelope(
+    output: &Output,
+    output_name: &str,
+    start_time: &u64,
+) -> ArtifactEnvelope {
+    let info = get_info_metadata();
+    ArtifactEnvelope {
+        artemis_version: env!("CARGO_PKG_VERSION").to_string(),
+        endpoint_id: output.endpoint_id.clone(),
+        id: output.collection_id,
+        uuid: generate_uuid(),
+        artifact_name: output_name.to_string(),
+        complete_time: time_now(),
+        start_time: *start_time,
+        hostname: output.hostname_override.clone().unwrap_or(info.hostname),
+        os_version: info.os_version,
+        platform: info.platform,
+        kernel_version: info.kernel_version,
+        load_performance: info.performance,
+    }
+}
+
+/// Serialize the envelope to a JSON value
+pub(crate) fn envelope_value(envelope: &ArtifactEnvelope) -> Value {
+    serde_json::to_value(envelope).unwrap_or_default()
+}
+
+/// Write the envelope to a `<uuid>.meta.json` sidecar file next to the artifact output file `uuid`
+pub(crate) fn write_envelope(
+    envelope: &ArtifactEnvelope,
+    output: &Output,
+    uuid: &str,
+) -> Result<(), OutputError> {
+    let data_result = serde_json::to_vec(envelope);
+    let data = match data_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[artemis-core] Failed to serialize metadata for {uuid}: {err:?}");
+            return Err(OutputError::Serialize);
+        }
+    };
+
+    // The sidecar is always uncompressed JSON
+    let mut meta_output = output.clone();
+    meta_output.format = String::from("json");
+    meta_output.compress = false;
+
+    final_output(&data, &meta_output, &format!("{uuid}.meta"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{artifact_envelope, envelope_value, write_envelope};
+    use crate::{
+        artifacts::os::systeminfo::info::get_info_metadata, filesystem::files::read_file,
+        structs::toml::Output,
+    };
+    use serde_json::Value;
+    use std::fs::remove_file;
+
+    fn output_options(name: &str) -> Output {
+        Output {
+            name: name.to_string(),
+            directory: String::from("./tmp"),
+            format: String::from("parquet"),
+            compress: true,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("envelope-endpoint"),
+            collection_id: 7,
+            output: String::from("local"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_artifact_envelope() {
+        let output = output_options("envelope_test");
+        let envelope = artifact_envelope(&output, "prefetch", &10);
+        let value = envelope_value(&envelope);
+
+        assert_eq!(value["artemis_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(value["endpoint_id"], "envelope-endpoint");
+        assert_eq!(value["id"], 7);
+        assert_eq!(value["artifact_name"], "prefetch");
+        assert_eq!(value["start_time"], 10);
+        assert!(!value["hostname"].as_str().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_artifact_envelope_hostname_override() {
+        let mut output = output_options("envelope_test");
+        let live = envelope_value(&artifact_envelope(&output, "prefetch", &10));
+        assert_eq!(live["hostname"], get_info_metadata().hostname.as_str());
+
+        output.hostname_override = Some(String::from("offline-image"));
+        let value = envelope_value(&artifact_envelope(&output, "prefetch", &10));
+        assert_eq!(value["hostname"], "offline-image");
+    }
+
+    #[test]
+    fn test_write_envelope() {
+        let output = output_options("envelope_write");
+        let envelope = artifact_envelope(&output, "prefetch", &10);
+        // Local output appends to existing files
+        let _ = remove_file("./tmp/envelope_write/sidecar.meta.json");
+        write_envelope(&envelope, &output, "sidecar").unwrap();
+
+        let data = read_file("./tmp/envelope_write/sidecar.meta.json").unwrap();
+        let value: Value = serde_json::from_slice(&data).unwrap();
+        assert_eq!(value["artemis_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(value["endpoint_id"], "envelope-endpoint");
+    }
+}
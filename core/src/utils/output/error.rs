@@ -0,0 +1,30 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum OutputError {
+    Serialize,
+    Io,
+    Compress,
+    Upload,
+    NotWritable,
+    Read,
+    Deserialize,
+    Transform,
+}
+
+impl std::error::Error for OutputError {}
+
+impl fmt::Display for OutputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputError::Serialize => write!(f, "Failed to serialize output data"),
+            OutputError::Io => write!(f, "Failed to write output data"),
+            OutputError::Compress => write!(f, "Failed to compress output data"),
+            OutputError::Upload => write!(f, "Failed to upload output data"),
+            OutputError::NotWritable => write!(f, "Output directory is not writable"),
+            OutputError::Read => write!(f, "Failed to read output data"),
+            OutputError::Deserialize => write!(f, "Failed to deserialize output data"),
+            OutputError::Transform => write!(f, "Invalid record transform"),
+        }
+    }
+}
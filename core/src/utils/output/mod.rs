@@ -0,0 +1,1063 @@
+pub(crate) mod envelope;
+pub(crate) mod error;
+pub(crate) mod ndjson;
+pub(crate) mod transform;
+
+use self::error::OutputError;
+use crate::utils::compression::compress::compress_output_zip;
+use crate::{
+    artifacts::since::since_field,
+    filesystem::files::{list_files, read_file},
+    output::{
+        local::output::local_output,
+        remote::{aws::aws_upload, azure::azure_upload, gcp::gcp_upload},
+    },
+    structs::toml::{Output, RecordTransform},
+    utils::{time::unixepoch_to_iso8601, uuid::generate_uuid},
+};
+use log::{error, info, warn};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    env::{current_dir, var},
+    fs::{create_dir_all, remove_dir, remove_file, write},
+    path::Path,
+    sync::{Mutex, MutexGuard},
+};
+
+/// Serializes staging and flushing of batched uploads. Workers collecting artifacts in parallel share the batch directory
+static BATCH_LOCK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    /// Fields to keep in the records of the artifact parser running on the current thread
+    static OUTPUT_FIELDS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+    /// Label appended to the output name of the artifact parser running on the current thread
+    static OUTPUT_LABEL: RefCell<Option<String>> = const { RefCell::new(None) };
+    /// Transforms applied to the records of the artifact parser running on the current thread
+    static OUTPUT_TRANSFORMS: RefCell<Option<Vec<RecordTransform>>> = const { RefCell::new(None) };
+}
+
+/// Output artifact data based on output type
+pub(crate) fn final_output(
+    artifact_data: &[u8],
+    output: &Output,
+    output_name: &str,
+) -> Result<(), OutputError> {
+    // Logs are uploaded at the end of the collection and are never batched
+    if batch_enabled(output) && !output_name.ends_with(".log") {
+        return batch_output(artifact_data, output, output_name);
+    }
+    send_output(artifact_data, output, output_name)
+}
+
+/// Send artifact data to the configured output
+fn send_output(
+    artifact_data: &[u8],
+    output: &Output,
+    output_name: &str,
+) -> Result<(), OutputError> {
+    // Check for supported output types. Can customize via Cargo.toml
+    match output.output.as_str() {
+        "local" => {
+            let local_result = local_output(artifact_data, output, output_name, &output.format);
+            match local_result {
+                Ok(_) => {}
+                Err(err) => {
+                    error!("[artemis-core] Failed to output to local system: {err:?}");
+                    return Err(OutputError::Io);
+                }
+            }
+        }
+        "gcp" => {
+            let gcp_result = gcp_upload(artifact_data, output, output_name);
+            match gcp_result {
+                Ok(_) => {}
+                Err(err) => {
+                    error!("[artemis-core] Failed to upload to Google Cloud Storage: {err:?}");
+                    return Err(OutputError::Upload);
+                }
+            }
+        }
+        "azure" => {
+            let azure_result = azure_upload(artifact_data, output, output_name);
+            match azure_result {
+                Ok(_) => {}
+                Err(err) => {
+                    error!("[artemis-core] Failed to upload to Azure Blog Storage: {err:?}");
+                    return Err(OutputError::Upload);
+                }
+            }
+        }
+        "aws" => {
+            let aws_result = aws_upload(artifact_data, output, output_name);
+            match aws_result {
+                Ok(_) => {}
+                Err(err) => {
+                    error!("[artemis-core] Failed to upload to AWS S3 Bucket: {err:?}");
+                    return Err(OutputError::Upload);
+                }
+            }
+        }
+        _ => {
+            warn!("Unknown output format: {}", output.format);
+        }
+    }
+    Ok(())
+}
+
+/**
+ * Check if remote uploads should be batched  
+ * Only `jsonl` output is batched. Concatenated `jsonl` data (and gzip members) is still valid and every line keeps its `artifact_name` in the metadata
+ */
+pub(crate) fn batch_enabled(output: &Output) -> bool {
+    output.output != "local"
+        && output.format == "jsonl"
+        && (output.batch_bytes.is_some() || output.batch_count.is_some())
+}
+
+/**
+ * Stage artifact data on disk until the batch thresholds are reached and then upload the batch as one request
+ * Staged data is only removed after a successful upload. A failed batch is retried on the next flush
+ */
+fn batch_output(
+    artifact_data: &[u8],
+    output: &Output,
+    output_name: &str,
+) -> Result<(), OutputError> {
+    // Hold the lock until the staged data is counted and flushed so parallel workers never reuse a staged name or upload twice
+    let _guard = batch_lock();
+
+    let batch_dir = format!("{}/{}/batch", output.directory, output.name);
+    let result = create_dir_all(&batch_dir);
+    match result {
+        Ok(_) => {}
+        Err(err) => {
+            error!(
+                "[artemis-core] Failed to create batch directory for {batch_dir}. Error: {err:?}"
+            );
+            return Err(OutputError::Io);
+        }
+    }
+
+    let staged = list_files(&batch_dir).unwrap_or_default();
+    // Prefix with the staged count so the batch keeps the output order
+    let staged_path = format!("{batch_dir}/{:08}_{output_name}", staged.len());
+    let write_result = write(&staged_path, artifact_data);
+    match write_result {
+        Ok(_) => {}
+        Err(err) => {
+            error!("[artemis-core] Failed to stage batch data at {staged_path}. Error: {err:?}");
+            return Err(OutputError::Io);
+        }
+    }
+
+    let staged = list_files(&batch_dir).unwrap_or_default();
+    let mut staged_size = 0;
+    for entry in &staged {
+        staged_size += get_staged_size(entry);
+    }
+
+    let count_reached = output
+        .batch_count
+        .is_some_and(|count| staged.len() as u64 >= count);
+    let bytes_reached = output.batch_bytes.is_some_and(|bytes| staged_size >= bytes);
+    if count_reached || bytes_reached {
+        return upload_staged(output, &batch_dir);
+    }
+    Ok(())
+}
+
+/// Get the size of a staged batch file
+fn get_staged_size(path: &str) -> u64 {
+    match std::fs::metadata(path) {
+        Ok(result) => result.len(),
+        Err(_) => 0,
+    }
+}
+
+/// Upload any staged batch data as a single concatenated request. Should be called at the end of a collection
+pub(crate) fn flush_batch(output: &Output) -> Result<(), OutputError> {
+    let _guard = batch_lock();
+    let batch_dir = format!("{}/{}/batch", output.directory, output.name);
+    upload_staged(output, &batch_dir)
+}
+
+/// Lock the batch directory. A worker that panicked while staging does not stop the remaining uploads
+fn batch_lock() -> MutexGuard<'static, ()> {
+    match BATCH_LOCK.lock() {
+        Ok(result) => result,
+        Err(err) => {
+            warn!("[artemis-core] Batch lock poisoned: {err:?}");
+            err.into_inner()
+        }
+    }
+}
+
+/// Upload the staged batch data. Caller must hold `BATCH_LOCK`
+fn upload_staged(output: &Output, batch_dir: &str) -> Result<(), OutputError> {
+    let mut staged = match list_files(batch_dir) {
+        Ok(result) => result,
+        // Nothing has been staged
+        Err(_) => return Ok(()),
+    };
+    if staged.is_empty() {
+        let _ = remove_dir(batch_dir);
+        return Ok(());
+    }
+    staged.sort();
+
+    let mut batch_data = Vec::new();
+    let mut output_names = Vec::new();
+    for entry in &staged {
+        let read_result = read_file(entry);
+        match read_result {
+            Ok(mut result) => batch_data.append(&mut result),
+            Err(err) => {
+                error!("[artemis-core] Failed to read staged batch file {entry}: {err:?}");
+                return Err(OutputError::Io);
+            }
+        }
+        output_names.push(staged_output_name(entry));
+    }
+
+    // A single staged output keeps its own name
+    let batch_name = if output_names.len() == 1 {
+        output_names[0].clone()
+    } else {
+        format!("batch_{}", generate_uuid())
+    };
+
+    let max_attempts = 3;
+    let mut attempt = 1;
+    while let Err(err) = send_output(&batch_data, output, &batch_name) {
+        if attempt >= max_attempts {
+            error!("[artemis-core] Failed to upload batch after {attempt} attempts: {err:?}. Staged data kept for next flush");
+            return Err(OutputError::Upload);
+        }
+        warn!(
+            "[artemis-core] Batch upload attempt {attempt} out of {max_attempts} failed: {err:?}"
+        );
+        attempt += 1;
+    }
+
+    info!(
+        "[artemis-core] Uploaded batch {batch_name} of {} outputs ({} bytes): {}",
+        staged.len(),
+        batch_data.len(),
+        output_names.join(", ")
+    );
+    for entry in staged {
+        let _ = remove_file(&entry);
+    }
+    let _ = remove_dir(batch_dir);
+    Ok(())
+}
+
+/// Get the original output name of a staged batch file by removing the order prefix
+fn staged_output_name(path: &str) -> String {
+    let name = Path::new(path)
+        .file_name()
+        .map(|value| value.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match name.split_once('_') {
+        Some((_order, output_name)) => output_name.to_string(),
+        None => name,
+    }
+}
+
+/**
+ * Copy the primary timestamp of each artifact record to `timestamp_field` in the `timestamp_format` format
+ * The original field is kept unless `timestamp_replace` is set. Artifacts without a mapped timestamp are returned unchanged
+ * If only `timestamp_format` is set the copy is written to `<field>_formatted`
+ * If `timestamp_iso` is set the primary timestamp is also added as ISO8601 to `<field>_iso`
+ * Arrays nested one level in an object (ex: `bits` and `carved_jobs`) are also transformed
+ */
+pub(crate) fn timestamp_transform(serde_data: &Value, output_name: &str, output: &Output) -> Value {
+    if output.timestamp_field.is_none()
+        && output.timestamp_format.is_none()
+        && !output.timestamp_iso.unwrap_or(false)
+    {
+        return serde_data.clone();
+    }
+    let field = match since_field(output_name) {
+        Some(result) => result,
+        None => return serde_data.clone(),
+    };
+
+    let mut transformed = serde_data.clone();
+    match &mut transformed {
+        Value::Array(records) => transform_records(records, field, output),
+        Value::Object(entries) => {
+            for value in entries.values_mut() {
+                if let Value::Array(records) = value {
+                    transform_records(records, field, output);
+                }
+            }
+        }
+        _ => {}
+    }
+    transformed
+}
+
+/// Transform the timestamp of each record. Records missing the field are left unchanged
+fn transform_records(records: &mut [Value], field: &str, output: &Output) {
+    let key = timestamp_key(field, output);
+    let mut format = output
+        .timestamp_format
+        .as_deref()
+        .unwrap_or("epoch_seconds");
+    if !["epoch_seconds", "epoch_millis", "iso8601"].contains(&format) {
+        warn!("[artemis-core] Unknown timestamp format {format}. Using epoch_seconds");
+        format = "epoch_seconds";
+    }
+    let replace = output.timestamp_replace.unwrap_or(false);
+    let iso = output.timestamp_iso.unwrap_or(false);
+
+    for record in records.iter_mut() {
+        let entries = match record {
+            Value::Object(result) => result,
+            _ => continue,
+        };
+        let timestamp = match entries.get(field).and_then(Value::as_i64) {
+            Some(result) => result,
+            None => continue,
+        };
+
+        if iso {
+            entries.insert(
+                iso_field(field),
+                Value::String(unixepoch_to_iso8601(&timestamp)),
+            );
+        }
+
+        let key = match &key {
+            Some(result) => result,
+            None => continue,
+        };
+        if replace && key != field {
+            entries.remove(field);
+        }
+        entries.insert(key.to_string(), format_timestamp(&timestamp, format));
+    }
+}
+
+/**
+ * Name of the field the formatted timestamp is written to. Returns `None` if no timestamp formatting is set  
+ * The original field is only overwritten if `timestamp_replace` is set
+ */
+pub(crate) fn timestamp_key(field: &str, output: &Output) -> Option<String> {
+    if let Some(key) = &output.timestamp_field {
+        return Some(key.clone());
+    }
+    output.timestamp_format.as_ref()?;
+    if output.timestamp_replace.unwrap_or(false) {
+        return Some(field.to_string());
+    }
+    Some(format!("{field}_formatted"))
+}
+
+/// Name of the field containing the ISO8601 copy of a timestamp. Ex: `created` becomes `created_iso`
+pub(crate) fn iso_field(field: &str) -> String {
+    format!("{field}_iso")
+}
+
+/// Format a unixepoch timestamp
+fn format_timestamp(timestamp: &i64, format: &str) -> Value {
+    match format {
+        "epoch_millis" => Value::from(timestamp.saturating_mul(1000)),
+        "iso8601" => Value::String(unixepoch_to_iso8601(timestamp)),
+        _ => Value::from(*timestamp),
+    }
+}
+
+/// Set the fields to keep for the artifact output on the current thread. `None` keeps all fields
+pub(crate) fn set_output_fields(fields: Option<Vec<String>>) {
+    OUTPUT_FIELDS.with(|current| *current.borrow_mut() = fields);
+}
+
+/// Get the fields to keep for the artifact output on the current thread
+pub(crate) fn output_fields() -> Option<Vec<String>> {
+    OUTPUT_FIELDS.with(|current| current.borrow().clone())
+}
+
+/// Set the record transforms for the artifact output on the current thread. `None` leaves records unchanged
+pub(crate) fn set_output_transforms(transforms: Option<Vec<RecordTransform>>) {
+    OUTPUT_TRANSFORMS.with(|current| *current.borrow_mut() = transforms);
+}
+
+/// Get the record transforms for the artifact output on the current thread
+pub(crate) fn output_transforms() -> Option<Vec<RecordTransform>> {
+    OUTPUT_TRANSFORMS.with(|current| current.borrow().clone())
+}
+
+/// Set the label for the artifact output on the current thread. `None` keeps the artifact name
+pub(crate) fn set_output_label(label: Option<&str>) {
+    OUTPUT_LABEL.with(|current| *current.borrow_mut() = label.map(str::to_string));
+}
+
+/// Append the label for the current thread to the output name. Ex: `shimdb` becomes `shimdb_custom`
+pub(crate) fn labeled_name(output_name: &str) -> String {
+    OUTPUT_LABEL.with(|current| label_output_name(output_name, current.borrow().as_deref()))
+}
+
+/// Append a sanitized label to the output name. Labels without any usable characters are ignored
+pub(crate) fn label_output_name(output_name: &str, label: Option<&str>) -> String {
+    let value = match label {
+        Some(result) => result,
+        None => return output_name.to_string(),
+    };
+    let sanitized = sanitize_label(value);
+    if sanitized.is_empty() {
+        warn!("[artemis-core] Output label {value} has no usable characters. Ignoring it");
+        return output_name.to_string();
+    }
+    format!("{output_name}_{sanitized}")
+}
+
+/// Only keep ASCII letters, numbers, `-`, and `_` so the label is safe to use in file and table names
+fn sanitize_label(label: &str) -> String {
+    let max_length = 64;
+    let sanitized: String = label
+        .trim()
+        .chars()
+        .map(|value| {
+            if value.is_ascii_alphanumeric() || value == '-' || value == '_' {
+                value
+            } else {
+                '_'
+            }
+        })
+        .take(max_length)
+        .collect();
+    sanitized.trim_matches('_').to_string()
+}
+
+/**
+ * Keep only the requested fields in each artifact record
+ * Field names that are not in any record are logged and ignored
+ * Arrays nested one level in an object (ex: `bits` and `carved_jobs`) are also projected
+ */
+pub(crate) fn project_fields(serde_data: &Value, output_name: &str, fields: &[String]) -> Value {
+    let mut projected = serde_data.clone();
+    let mut found = HashSet::new();
+    match &mut projected {
+        Value::Array(records) => project_records(records, fields, &mut found),
+        Value::Object(entries) => {
+            for value in entries.values_mut() {
+                if let Value::Array(records) = value {
+                    project_records(records, fields, &mut found);
+                }
+            }
+        }
+        _ => {}
+    }
+
+    for field in fields {
+        if !found.contains(field.as_str()) {
+            warn!("[artemis-core] Unknown field {field} for {output_name}. Ignoring it");
+        }
+    }
+    projected
+}
+
+/// Remove any fields not requested from each record. Tracks which requested fields were found
+fn project_records<'a>(records: &mut [Value], fields: &'a [String], found: &mut HashSet<&'a str>) {
+    for record in records.iter_mut() {
+        let entries = match record {
+            Value::Object(result) => result,
+            _ => continue,
+        };
+        entries.retain(|key, _| fields.contains(key));
+        for field in fields {
+            if entries.contains_key(field) {
+                found.insert(field.as_str());
+            }
+        }
+    }
+}
+
+/// Serialize artifact data to a JSON string
+pub(crate) fn serialize_output<T: Serialize>(data: &T) -> Result<String, OutputError> {
+    let serde_result = serde_json::to_string(data);
+    match serde_result {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("[artemis-core] Failed to serialize output data: {err:?}");
+            Err(OutputError::Serialize)
+        }
+    }
+}
+
+/**
+ * Resolve a relative output `directory` against the `output_base` option or the `ARTEMIS_OUTPUT_BASE` env var  
+ * Absolute directories are unchanged. Returns the absolute output directory
+ */
+pub(crate) fn resolve_output_dir(output: &mut Output) -> String {
+    let directory = Path::new(&output.directory);
+    if directory.is_relative() {
+        if let Some(base) = output_base(output) {
+            let relative = output
+                .directory
+                .trim_start_matches("./")
+                .trim_start_matches(".\\");
+            output.directory = Path::new(&base).join(relative).display().to_string();
+        }
+    }
+
+    let directory = Path::new(&output.directory);
+    if directory.is_absolute() {
+        return output.directory.clone();
+    }
+    match current_dir() {
+        Ok(result) => result.join(directory).display().to_string(),
+        Err(err) => {
+            warn!("[artemis-core] Could not get current directory: {err:?}");
+            output.directory.clone()
+        }
+    }
+}
+
+/// Get the output base directory. The TOML option takes precedence over the env var
+fn output_base(output: &Output) -> Option<String> {
+    if let Some(base) = &output.output_base {
+        return Some(base.clone());
+    }
+    var("ARTEMIS_OUTPUT_BASE")
+        .ok()
+        .filter(|base| !base.is_empty())
+}
+
+/**
+ * Verify the output directory can be written to before any artifacts are parsed  
+ * Creates the directory if needed and writes then deletes a probe file
+ */
+pub(crate) fn verify_output_dir(output: &Output) -> Result<(), OutputError> {
+    let output_dir = format!("{}/{}", output.directory, output.name);
+    if let Err(err) = create_dir_all(&output_dir) {
+        error!("[artemis-core] Could not create output directory {output_dir}: {err:?}");
+        return Err(OutputError::NotWritable);
+    }
+
+    let probe = format!("{output_dir}/.{}.probe", generate_uuid());
+    if let Err(err) = write(&probe, b"") {
+        error!("[artemis-core] Output directory {output_dir} is not writable: {err:?}");
+        return Err(OutputError::NotWritable);
+    }
+
+    if let Err(err) = remove_file(&probe) {
+        warn!("[artemis-core] Could not remove output probe file {probe}: {err:?}");
+    }
+    Ok(())
+}
+
+/// Compress the local output directory to a zip file and delete any log/jsonl/json files
+pub(crate) fn compress_final_output(output: &Output) -> Result<(), OutputError> {
+    let output_dir = format!("{}/{}", output.directory, output.name);
+    let zip_name = format!("{}/{}", output.directory, output.name);
+    let zip_result = compress_output_zip(&output_dir, &zip_name);
+    match zip_result {
+        Ok(_) => {}
+        Err(err) => {
+            error!(
+                "[artemis-core] Failed to zip output directory: {err:?}. DID NOT DELETE OUTPUT."
+            );
+            return Err(OutputError::Compress);
+        }
+    }
+
+    /*
+     * Now ready to delete output. Since we run in elevated privileges we need to be careful.
+     * To maximize safety we only delete:
+     *  - Files that end in .json, .jsonl, .log, .gz, or .parquet
+     *  - Only delete the output directory if its empty. Which means all the files above must be gone
+     */
+    let check = list_files(&output_dir);
+    match check {
+        Ok(results) => {
+            for entry in results {
+                if !entry.ends_with(".json")
+                    && !entry.ends_with(".log")
+                    && !entry.ends_with(".gz")
+                    && !entry.ends_with(".parquet")
+                {
+                    continue;
+                }
+                // Remove our files. Entry is the full path to the file
+                let _ = remove_file(&entry);
+            }
+        }
+        Err(err) => {
+            error!("[artemis-core] Failed to list files in output directory: {err:?}. DID NOT DELETE OUTPUT.");
+            return Err(OutputError::Io);
+        }
+    }
+    // Now remove directory if its empty
+    let remove_status = remove_dir(output_dir);
+    match remove_status {
+        Ok(_) => {}
+        Err(err) => {
+            error!("[artemis-core] Failed to remove output directory: {err:?}");
+            return Err(OutputError::Io);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        batch_enabled, compress_final_output, final_output, flush_batch, labeled_name,
+        output_fields, project_fields, resolve_output_dir, sanitize_label, serialize_output,
+        set_output_fields, set_output_label, staged_output_name, timestamp_key,
+        timestamp_transform, verify_output_dir,
+    };
+    use crate::{structs::toml::Output, utils::output::error::OutputError};
+    use httpmock::{Method::PUT, MockServer};
+    use serde_json::json;
+    use std::{
+        collections::HashMap, fs::remove_file, net::TcpListener, path::PathBuf, thread::spawn,
+    };
+
+    fn batch_options(name: &str, port: u16, batch_count: Option<u64>) -> Output {
+        Output {
+            name: name.to_string(),
+            directory: String::from("./tmp"),
+            format: String::from("jsonl"),
+            compress: false,
+            url: Some(format!(
+                "http://127.0.0.1:{port}/mycontainername?sp=rcw&st=2023-06-14T03:00:40Z"
+            )),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("azure"),
+            batch_count,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_final_output() {
+        let output = Output {
+            name: String::from("test_output"),
+            directory: String::from("./tmp"),
+            format: String::from("json"),
+            compress: false,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("local"),
+            filter_name: Some(String::new()),
+            filter_script: Some(String::new()),
+            logging: Some(String::new()),
+            ..Default::default()
+        };
+
+        let test = "A rust program";
+        let name = "output";
+        let result = final_output(test.as_bytes(), &output, name).unwrap();
+        assert_eq!(result, ());
+    }
+
+    #[test]
+    fn test_compress_final_output() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/system");
+
+        let output = Output {
+            name: String::from("files"),
+            directory: test_location.display().to_string(),
+            format: String::from("json"),
+            compress: false,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("local"),
+            filter_name: Some(String::new()),
+            filter_script: Some(String::new()),
+            logging: Some(String::new()),
+            ..Default::default()
+        };
+
+        let _ = compress_final_output(&output);
+        let _ = remove_file(format!("{}/files.zip", test_location.display().to_string())).unwrap();
+    }
+
+    #[test]
+    fn test_final_output_batch() {
+        let server = MockServer::start();
+        let mock_me = server.mock(|when, then| {
+            when.method(PUT);
+            then.status(201);
+        });
+
+        let output = batch_options("batch_upload_test", server.port(), Some(3));
+        for _ in 0..6 {
+            final_output(b"{\"test\":1}\n", &output, "output").unwrap();
+        }
+        flush_batch(&output).unwrap();
+
+        // Six outputs are sent as two requests
+        mock_me.assert_hits(2);
+    }
+
+    #[test]
+    fn test_flush_batch_retry() {
+        let server = MockServer::start();
+        let mock_me = server.mock(|when, then| {
+            when.method(PUT).body("abc");
+            then.status(201);
+        });
+
+        // Nothing is listening on the port so the upload fails
+        let closed_port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let mut bad_output = batch_options("batch_retry_test", closed_port, Some(10));
+        bad_output.upload_attempts = Some(1);
+        final_output(b"a", &bad_output, "output").unwrap();
+        final_output(b"b", &bad_output, "output").unwrap();
+        assert_eq!(flush_batch(&bad_output).unwrap_err(), OutputError::Upload);
+
+        // Staged data is kept and sent on the next flush
+        let output = batch_options("batch_retry_test", server.port(), Some(10));
+        final_output(b"c", &output, "output").unwrap();
+        flush_batch(&output).unwrap();
+        mock_me.assert_hits(1);
+    }
+
+    #[test]
+    fn test_final_output_batch_workers() {
+        let server = MockServer::start();
+        let mock_me = server.mock(|when, then| {
+            when.method(PUT);
+            then.status(201);
+        });
+
+        let output = batch_options("batch_workers_test", server.port(), Some(4));
+        let mut workers = Vec::new();
+        for worker in 0..4 {
+            let output = output.clone();
+            workers.push(spawn(move || {
+                for entry in 0..4 {
+                    let name = format!("output{worker}{entry}");
+                    final_output(b"{\"test\":1}\n", &output, &name).unwrap();
+                }
+            }));
+        }
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        flush_batch(&output).unwrap();
+
+        // Sixteen outputs are sent as exactly four requests
+        mock_me.assert_hits(4);
+    }
+
+    #[test]
+    fn test_final_output_batch_json() {
+        let server = MockServer::start();
+        let mock_me = server.mock(|when, then| {
+            when.method(PUT);
+            then.status(201);
+        });
+
+        // Concatenated JSON documents are not valid JSON. Only jsonl is batched
+        let mut output = batch_options("batch_json_test", server.port(), Some(3));
+        output.format = String::from("json");
+        assert!(!batch_enabled(&output));
+        final_output(b"{\"test\":1}", &output, "output").unwrap();
+        final_output(b"{\"test\":2}", &output, "output").unwrap();
+        mock_me.assert_hits(2);
+    }
+
+    #[test]
+    fn test_staged_output_name() {
+        assert_eq!(
+            staged_output_name("./tmp/test/batch/00000001_abcd-1234"),
+            "abcd-1234"
+        );
+        assert_eq!(staged_output_name("./tmp/test/batch/output"), "output");
+    }
+
+    #[test]
+    fn test_timestamp_transform() {
+        let mut output = batch_options("timestamp_test", 0, None);
+        output.timestamp_field = Some(String::from("@timestamp"));
+        let data = json!([{"path": "a", "last_run_time": 1620610795}, {"path": "b"}]);
+
+        let result = timestamp_transform(&data, "prefetch", &output);
+        assert_eq!(result[0]["@timestamp"], 1620610795);
+        assert_eq!(result[0]["last_run_time"], 1620610795);
+        assert!(result[1].get("@timestamp").is_none());
+
+        output.timestamp_format = Some(String::from("epoch_millis"));
+        output.timestamp_replace = Some(true);
+        let result = timestamp_transform(&data, "prefetch", &output);
+        assert_eq!(result[0]["@timestamp"], 1620610795000_i64);
+        assert!(result[0].get("last_run_time").is_none());
+    }
+
+    #[test]
+    fn test_project_fields() {
+        let data = json!([
+            {"path": "a", "last_run_time": 1620610795, "run_count": 2, "volume_serial": "abcd"},
+            {"path": "b", "run_count": 1},
+        ]);
+        let fields = vec![
+            String::from("path"),
+            String::from("last_run_time"),
+            String::from("unknown"),
+        ];
+
+        let result = project_fields(&data, "prefetch", &fields);
+        assert_eq!(
+            result,
+            json!([{"path": "a", "last_run_time": 1620610795}, {"path": "b"}])
+        );
+
+        let data =
+            json!({"bits": [{"job_id": "a", "created": 0}], "carved_jobs": [{"job_id": "b"}]});
+        let result = project_fields(&data, "bits", &[String::from("job_id")]);
+        assert_eq!(
+            result,
+            json!({"bits": [{"job_id": "a"}], "carved_jobs": [{"job_id": "b"}]})
+        );
+    }
+
+    #[test]
+    fn test_output_fields() {
+        set_output_fields(Some(vec![String::from("path")]));
+        assert_eq!(output_fields(), Some(vec![String::from("path")]));
+        set_output_fields(None);
+        assert_eq!(output_fields(), None);
+    }
+
+    #[test]
+    fn test_labeled_name() {
+        set_output_label(Some("NTUSER bob"));
+        assert_eq!(labeled_name("shimdb"), "shimdb_NTUSER_bob");
+        set_output_label(Some("../.."));
+        assert_eq!(labeled_name("shimdb"), "shimdb");
+        set_output_label(None);
+        assert_eq!(labeled_name("shimdb"), "shimdb");
+    }
+
+    #[test]
+    fn test_sanitize_label() {
+        assert_eq!(sanitize_label("custom-db_1"), "custom-db_1");
+        assert_eq!(sanitize_label(" C:\\Users\\bob "), "C__Users_bob");
+        assert_eq!(sanitize_label(&"a".repeat(100)).len(), 64);
+    }
+
+    #[test]
+    fn test_timestamp_transform_iso() {
+        let mut output = batch_options("timestamp_test", 0, None);
+        output.timestamp_iso = Some(true);
+        let data = json!({
+            "bits": [{"created": 1620610795}, {"url": "test"}],
+            "carved_jobs": [{"created": 0}],
+        });
+
+        let result = timestamp_transform(&data, "bits", &output);
+        assert_eq!(result["bits"][0]["created"], 1620610795);
+        assert_eq!(result["bits"][0]["created_iso"], "2021-05-10T01:39:55Z");
+        assert!(result["bits"][1].get("created_iso").is_none());
+        assert_eq!(result["carved_jobs"][0]["created"], 0);
+        assert_eq!(
+            result["carved_jobs"][0]["created_iso"],
+            "1970-01-01T00:00:00Z"
+        );
+
+        // The ISO8601 copy always uses the original epoch value
+        output.timestamp_field = Some(String::from("@timestamp"));
+        output.timestamp_format = Some(String::from("epoch_millis"));
+        let data = json!([{"last_run_time": 1620610795}]);
+        let result = timestamp_transform(&data, "prefetch", &output);
+        assert_eq!(result[0]["last_run_time"], 1620610795);
+        assert_eq!(result[0]["last_run_time_iso"], "2021-05-10T01:39:55Z");
+        assert_eq!(result[0]["@timestamp"], 1620610795000_i64);
+    }
+
+    #[test]
+    fn test_timestamp_transform_format_only() {
+        let mut output = batch_options("timestamp_test", 0, None);
+        output.timestamp_format = Some(String::from("iso8601"));
+        let data = json!({"bits": [{"created": 0}], "carved_jobs": [{"created": 1620610795}]});
+
+        let result = timestamp_transform(&data, "bits", &output);
+        assert_eq!(result["bits"][0]["created"], 0);
+        assert_eq!(
+            result["bits"][0]["created_formatted"],
+            "1970-01-01T00:00:00Z"
+        );
+        assert_eq!(
+            result["carved_jobs"][0]["created_formatted"],
+            "2021-05-10T01:39:55Z"
+        );
+
+        // The original field is only overwritten if timestamp_replace is set
+        output.timestamp_replace = Some(true);
+        let result = timestamp_transform(&data, "bits", &output);
+        assert_eq!(result["bits"][0]["created"], "1970-01-01T00:00:00Z");
+        assert!(result["bits"][0].get("created_formatted").is_none());
+    }
+
+    #[test]
+    fn test_timestamp_key() {
+        let mut output = batch_options("timestamp_test", 0, None);
+        assert!(timestamp_key("created", &output).is_none());
+
+        output.timestamp_format = Some(String::from("epoch_millis"));
+        assert_eq!(
+            timestamp_key("created", &output).unwrap(),
+            "created_formatted"
+        );
+        output.timestamp_replace = Some(true);
+        assert_eq!(timestamp_key("created", &output).unwrap(), "created");
+        output.timestamp_field = Some(String::from("@timestamp"));
+        assert_eq!(timestamp_key("created", &output).unwrap(), "@timestamp");
+    }
+
+    #[test]
+    fn test_timestamp_transform_unmapped() {
+        let mut output = batch_options("timestamp_test", 0, None);
+        output.timestamp_field = Some(String::from("time"));
+        output.timestamp_format = Some(String::from("bad"));
+        let data = json!([{"start_time": 1}]);
+        assert_eq!(timestamp_transform(&data, "processes", &output), data);
+
+        let data = json!([{"last_run_time": 10}]);
+        let result = timestamp_transform(&data, "prefetch", &output);
+        assert_eq!(result[0]["time"], 10);
+    }
+
+    #[test]
+    fn test_serialize_output() {
+        let result = serialize_output(&vec![1, 2, 3]).unwrap();
+        assert_eq!(result, "[1,2,3]");
+
+        // JSON object keys must be strings
+        let mut bad_data = HashMap::new();
+        bad_data.insert((1, 2), 3);
+        assert_eq!(
+            serialize_output(&bad_data).unwrap_err(),
+            OutputError::Serialize
+        );
+    }
+
+    #[test]
+    fn test_final_output_io_error() {
+        let mut output = batch_options("io_error_test", 0, None);
+        output.output = String::from("local");
+        // Cannot create an output directory under a file
+        output.directory = format!("{}/Cargo.toml", env!("CARGO_MANIFEST_DIR"));
+
+        let result = final_output(b"test", &output, "output").unwrap_err();
+        assert_eq!(result, OutputError::Io);
+    }
+
+    #[test]
+    fn test_compress_final_output_parquet() {
+        let mut output = batch_options("compress_parquet_test", 0, None);
+        output.output = String::from("local");
+        output.format = String::from("parquet");
+        output.compress = true;
+
+        let output_dir = format!("{}/{}", output.directory, output.name);
+        std::fs::create_dir_all(&output_dir).unwrap();
+        std::fs::write(format!("{output_dir}/test.parquet"), b"PAR1").unwrap();
+        std::fs::write(format!("{output_dir}/test.log"), b"test").unwrap();
+
+        compress_final_output(&output).unwrap();
+        assert!(!PathBuf::from(&output_dir).exists());
+        let _ = remove_file(format!("{output_dir}.zip")).unwrap();
+    }
+
+    #[test]
+    fn test_compress_final_output_error() {
+        let mut output = batch_options("compress_error_test", 0, None);
+        output.output = String::from("local");
+        output.directory = String::from("./tmp/does/not/exist");
+
+        let result = compress_final_output(&output).unwrap_err();
+        assert_eq!(result, OutputError::Compress);
+    }
+
+    #[test]
+    fn test_verify_output_dir() {
+        let mut output = batch_options("verify_output_test", 0, None);
+        output.output = String::from("local");
+        verify_output_dir(&output).unwrap();
+
+        // Only the directory is left behind
+        let entries = std::fs::read_dir("./tmp/verify_output_test").unwrap();
+        assert_eq!(entries.count(), 0);
+    }
+
+    #[test]
+    fn test_resolve_output_dir() {
+        let mut output = batch_options("resolve_output_test", 0, None);
+        let base = format!("{}/tmp", env!("CARGO_MANIFEST_DIR"));
+        output.output_base = Some(base.clone());
+        output.directory = String::from("./collections");
+
+        let result = resolve_output_dir(&mut output);
+        assert_eq!(output.directory, format!("{base}/collections"));
+        assert_eq!(result, output.directory);
+    }
+
+    #[test]
+    fn test_resolve_output_dir_absolute() {
+        let mut output = batch_options("resolve_output_test", 0, None);
+        let directory = format!("{}/tmp", env!("CARGO_MANIFEST_DIR"));
+        output.output_base = Some(String::from("/var/artemis"));
+        output.directory = directory.clone();
+
+        let result = resolve_output_dir(&mut output);
+        assert_eq!(output.directory, directory);
+        assert_eq!(result, directory);
+    }
+
+    #[test]
+    fn test_verify_output_dir_not_writable() {
+        let mut output = batch_options("not_writable_test", 0, None);
+        output.output = String::from("local");
+        // Cannot create an output directory under a file
+        output.directory = format!("{}/Cargo.toml", env!("CARGO_MANIFEST_DIR"));
+
+        let result = verify_output_dir(&output).unwrap_err();
+        assert_eq!(result, OutputError::NotWritable);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_verify_output_dir_read_only() {
+        use std::{
+            fs::{create_dir_all, set_permissions, Permissions},
+            os::unix::fs::PermissionsExt,
+        };
+
+        let path = "./tmp/read_only_output";
+        create_dir_all(path).unwrap();
+        set_permissions(path, Permissions::from_mode(0o555)).unwrap();
+
+        let mut output = batch_options("collection", 0, None);
+        output.output = String::from("local");
+        output.directory = path.to_string();
+
+        let result = verify_output_dir(&output);
+        set_permissions(path, Permissions::from_mode(0o755)).unwrap();
+        // Permissions are not enforced for root
+        if result.is_ok() {
+            return;
+        }
+        assert_eq!(result.unwrap_err(), OutputError::NotWritable);
+    }
+}
@@ -0,0 +1,158 @@
+use super::error::OutputError;
+use flate2::read::MultiGzDecoder;
+use log::error;
+use ruzstd::StreamingDecoder;
+use serde_json::Value;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+};
+
+/**
+ * Read NDJSON (jsonl) output one record per line. Files ending in `.gz` or `.zst` are decompressed while reading
+ * The file is streamed, only the current line is kept in memory. Empty lines are skipped
+ */
+pub(crate) fn read_ndjson(path: &str) -> impl Iterator<Item = Result<Value, OutputError>> {
+    let (reader, open_error) = match ndjson_reader(path) {
+        Ok(result) => (Some(result), None),
+        Err(err) => (None, Some(Err(err))),
+    };
+
+    let records = reader
+        .into_iter()
+        .flat_map(|reader| reader.lines())
+        .filter_map(|line_result| {
+            let line = match line_result {
+                Ok(result) => result,
+                Err(err) => {
+                    error!("[artemis-core] Could not read NDJSON line: {err:?}");
+                    return Some(Err(OutputError::Read));
+                }
+            };
+            if line.trim().is_empty() {
+                return None;
+            }
+            match serde_json::from_str(&line) {
+                Ok(result) => Some(Ok(result)),
+                Err(err) => {
+                    error!("[artemis-core] Could not deserialize NDJSON line: {err:?}");
+                    Some(Err(OutputError::Deserialize))
+                }
+            }
+        });
+
+    open_error.into_iter().chain(records)
+}
+
+/// Open the NDJSON file and decompress it based on the file extension
+fn ndjson_reader(path: &str) -> Result<Box<dyn BufRead>, OutputError> {
+    let file_result = File::open(path);
+    let file = match file_result {
+        Ok(result) => BufReader::new(result),
+        Err(err) => {
+            error!("[artemis-core] Could not open NDJSON file {path}: {err:?}");
+            return Err(OutputError::Read);
+        }
+    };
+
+    let reader: Box<dyn Read> = if path.ends_with(".gz") {
+        Box::new(MultiGzDecoder::new(file))
+    } else if path.ends_with(".zst") {
+        let decoder_result = StreamingDecoder::new(file);
+        match decoder_result {
+            Ok(result) => Box::new(result),
+            Err(err) => {
+                error!("[artemis-core] Could not decompress zstd NDJSON file {path}: {err:?}");
+                return Err(OutputError::Read);
+            }
+        }
+    } else {
+        return Ok(Box::new(file));
+    };
+
+    Ok(Box::new(BufReader::new(reader)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_ndjson;
+    use crate::{
+        output::formats::jsonl::jsonl_format,
+        structs::toml::Output,
+        utils::{output::error::OutputError, time::time_now},
+    };
+    use serde_json::{json, Value};
+    use std::fs::{create_dir_all, remove_file, write};
+
+    /// Create a zstd frame with a single uncompressed block. Data must be less than 256 bytes
+    fn zstd_raw_frame(data: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0x28, 0xb5, 0x2f, 0xfd, 0x20, data.len() as u8];
+        let block_header = 1 | ((data.len() as u32) << 3);
+        frame.extend_from_slice(&block_header.to_le_bytes()[..3]);
+        frame.extend_from_slice(data);
+        frame
+    }
+
+    #[test]
+    fn test_read_ndjson_gzip() {
+        let mut output = Output {
+            name: String::from("ndjson_test"),
+            directory: String::from("./tmp"),
+            format: String::from("jsonl"),
+            compress: true,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("local"),
+            combined_output: Some(true),
+            ..Default::default()
+        };
+        let path = "./tmp/ndjson_test/combined.jsonl.gz";
+        let _ = remove_file(path);
+        let start_time = time_now();
+
+        // Each write appends a separate gzip member to the file
+        let data = json!([{"pid": 1, "name": "systemd"}, {"pid": 2, "name": "kthreadd"}]);
+        jsonl_format(&data, "processes", &mut output, &start_time).unwrap();
+        let data = json!({"hostname": "test"});
+        jsonl_format(&data, "systeminfo", &mut output, &start_time).unwrap();
+
+        let results: Vec<Value> = read_ndjson(path).map(Result::unwrap).collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["artifact"], "processes");
+        assert_eq!(results[0]["data"], json!({"pid": 1, "name": "systemd"}));
+        assert_eq!(results[1]["data"]["name"], "kthreadd");
+        assert_eq!(results[2]["artifact"], "systeminfo");
+        assert_eq!(results[2]["data"]["hostname"], "test");
+    }
+
+    #[test]
+    fn test_read_ndjson_zstd() {
+        create_dir_all("./tmp/ndjson").unwrap();
+        let path = "./tmp/ndjson/users.jsonl.zst";
+        write(path, zstd_raw_frame(b"{\"uid\":0}\n\n{\"uid\":1000}\n")).unwrap();
+
+        let results: Vec<Value> = read_ndjson(path).map(Result::unwrap).collect();
+        assert_eq!(results, vec![json!({"uid": 0}), json!({"uid": 1000})]);
+    }
+
+    #[test]
+    fn test_read_ndjson_bad_line() {
+        create_dir_all("./tmp/ndjson").unwrap();
+        let path = "./tmp/ndjson/bad.jsonl";
+        write(path, "{\"uid\":0}\nnot json\n").unwrap();
+
+        let results: Vec<Result<Value, OutputError>> = read_ndjson(path).collect();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0], Ok(json!({"uid": 0})));
+        assert_eq!(results[1], Err(OutputError::Deserialize));
+    }
+
+    #[test]
+    fn test_read_ndjson_missing_file() {
+        let results: Vec<Result<Value, OutputError>> =
+            read_ndjson("./tmp/ndjson/missing.jsonl").collect();
+        assert_eq!(results, vec![Err(OutputError::Read)]);
+    }
+}
@@ -0,0 +1,231 @@
+/**
+ * Declarative transforms for artifact output records. Configured per artifact in the TOML `transforms` array
+ * Each transform is applied in order to every record before the output is written
+ */
+use super::error::OutputError;
+use crate::structs::toml::RecordTransform;
+use log::error;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+/// Check the transform options. Called when the TOML collection is parsed
+pub(crate) fn validate_transforms(transforms: &[RecordTransform]) -> Result<(), OutputError> {
+    for transform in transforms {
+        let valid = match transform {
+            RecordTransform::Rename { from, to } => {
+                !from.is_empty() && !to.is_empty() && from != to
+            }
+            RecordTransform::Drop { fields } => {
+                !fields.is_empty() && fields.iter().all(|field| !field.is_empty())
+            }
+            RecordTransform::AddConstant { field, .. } => !field.is_empty(),
+            RecordTransform::HashField { field, .. } => !field.is_empty(),
+        };
+        if !valid {
+            error!("[artemis-core] Invalid record transform: {transform:?}");
+            return Err(OutputError::Transform);
+        }
+    }
+    Ok(())
+}
+
+/**
+ * Apply the transforms in order to each artifact record
+ * Arrays nested one level in an object (ex: `bits` and `carved_jobs`) are also transformed
+ */
+pub(crate) fn apply_transforms(serde_data: &Value, transforms: &[RecordTransform]) -> Value {
+    let mut transformed = serde_data.clone();
+    match &mut transformed {
+        Value::Array(records) => transform_records(records, transforms),
+        Value::Object(entries) => {
+            for value in entries.values_mut() {
+                if let Value::Array(records) = value {
+                    transform_records(records, transforms);
+                }
+            }
+        }
+        _ => {}
+    }
+    transformed
+}
+
+/// Apply each transform to every record. Values that are not objects are left unchanged
+fn transform_records(records: &mut [Value], transforms: &[RecordTransform]) {
+    for record in records.iter_mut() {
+        let entries = match record {
+            Value::Object(result) => result,
+            _ => continue,
+        };
+        for transform in transforms {
+            transform_record(entries, transform);
+        }
+    }
+}
+
+/// Apply a single transform to a record. Transforms for fields not in the record do nothing
+fn transform_record(entries: &mut Map<String, Value>, transform: &RecordTransform) {
+    match transform {
+        RecordTransform::Rename { from, to } => {
+            if let Some(value) = entries.remove(from) {
+                entries.insert(to.clone(), value);
+            }
+        }
+        RecordTransform::Drop { fields } => {
+            for field in fields {
+                entries.remove(field);
+            }
+        }
+        RecordTransform::AddConstant { field, value } => {
+            entries.insert(field.clone(), value.clone());
+        }
+        RecordTransform::HashField { field, salt } => {
+            if let Some(value) = entries.get_mut(field) {
+                *value = Value::String(hash_value(value, salt.as_deref()));
+            }
+        }
+    }
+}
+
+/// SHA256 hash a field value. Strings are hashed as is, other values are hashed as JSON
+fn hash_value(value: &Value, salt: Option<&str>) -> String {
+    let mut sha256 = Sha256::new();
+    if let Some(salt) = salt {
+        sha256.update(salt);
+    }
+    match value {
+        Value::String(data) => sha256.update(data),
+        _ => sha256.update(value.to_string()),
+    }
+    let hash = sha256.finalize();
+    format!("{hash:x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_transforms, hash_value, validate_transforms};
+    use crate::{structs::toml::RecordTransform, utils::output::error::OutputError};
+    use serde_json::json;
+
+    #[test]
+    fn test_rename() {
+        let data = json!([{"pid": 1, "name": "systemd"}, {"pid": 2}]);
+        let transforms = vec![RecordTransform::Rename {
+            from: String::from("name"),
+            to: String::from("process_name"),
+        }];
+        let results = apply_transforms(&data, &transforms);
+        assert_eq!(
+            results,
+            json!([{"pid": 1, "process_name": "systemd"}, {"pid": 2}])
+        );
+    }
+
+    #[test]
+    fn test_drop() {
+        let data = json!({"bits": [{"url": "https://example.com", "guid": "abc", "size": 1}]});
+        let transforms = vec![RecordTransform::Drop {
+            fields: vec![String::from("guid"), String::from("size")],
+        }];
+        let results = apply_transforms(&data, &transforms);
+        assert_eq!(results, json!({"bits": [{"url": "https://example.com"}]}));
+    }
+
+    #[test]
+    fn test_add_constant() {
+        let data = json!([{"uid": 0}, {"uid": 1000, "case": "old"}]);
+        let transforms = vec![RecordTransform::AddConstant {
+            field: String::from("case"),
+            value: json!("IR-1234"),
+        }];
+        let results = apply_transforms(&data, &transforms);
+        assert_eq!(
+            results,
+            json!([{"uid": 0, "case": "IR-1234"}, {"uid": 1000, "case": "IR-1234"}])
+        );
+    }
+
+    #[test]
+    fn test_hash_field() {
+        let data = json!([{"username": "bob", "uid": 1000}, {"uid": 0}]);
+        let transforms = vec![
+            RecordTransform::HashField {
+                field: String::from("username"),
+                salt: None,
+            },
+            RecordTransform::HashField {
+                field: String::from("uid"),
+                salt: Some(String::from("pepper")),
+            },
+        ];
+        let results = apply_transforms(&data, &transforms);
+        assert_eq!(
+            results[0]["username"],
+            "81b637d8fcd2c6da6359e6963113a1170de795e4b725b84d1e0b4cfd9ec58ce9"
+        );
+        assert_eq!(
+            results[0]["uid"],
+            hash_value(&json!(1000), Some("pepper")).as_str()
+        );
+        assert_ne!(results[0]["uid"], results[1]["uid"]);
+        assert_eq!(hash_value(&json!("bob"), Some("pepper")).len(), 64);
+    }
+
+    #[test]
+    fn test_apply_transforms_order() {
+        let data = json!([{"path": "/etc/passwd", "inode": 1}]);
+        let transforms = vec![
+            RecordTransform::Rename {
+                from: String::from("path"),
+                to: String::from("full_path"),
+            },
+            RecordTransform::HashField {
+                field: String::from("path"),
+                salt: None,
+            },
+            RecordTransform::Drop {
+                fields: vec![String::from("inode")],
+            },
+        ];
+        let results = apply_transforms(&data, &transforms);
+        assert_eq!(results, json!([{"full_path": "/etc/passwd"}]));
+    }
+
+    #[test]
+    fn test_validate_transforms() {
+        let transforms = vec![
+            RecordTransform::Rename {
+                from: String::from("name"),
+                to: String::from("process_name"),
+            },
+            RecordTransform::AddConstant {
+                field: String::from("case"),
+                value: json!(1),
+            },
+        ];
+        assert!(validate_transforms(&transforms).is_ok());
+
+        let transforms = vec![RecordTransform::Drop { fields: Vec::new() }];
+        assert_eq!(
+            validate_transforms(&transforms),
+            Err(OutputError::Transform)
+        );
+
+        let transforms = vec![RecordTransform::Rename {
+            from: String::from("name"),
+            to: String::from("name"),
+        }];
+        assert_eq!(
+            validate_transforms(&transforms),
+            Err(OutputError::Transform)
+        );
+
+        let transforms = vec![RecordTransform::HashField {
+            field: String::new(),
+            salt: None,
+        }];
+        assert_eq!(
+            validate_transforms(&transforms),
+            Err(OutputError::Transform)
+        );
+    }
+}
@@ -0,0 +1,156 @@
+/**
+ * Attach the raw bytes of parsed structures to output records when `include_raw` is enabled
+ * Raw bytes are base64 encoded and stored under the `_raw` field. The total amount of raw data per artifact is limited
+ * Original FILETIME values can also be kept alongside converted timestamps when `keep_raw_filetime` is enabled. Only `prefetch` supports this
+ */
+use super::encoding::base64_encode_standard;
+use crate::structs::toml::Output;
+use log::warn;
+use serde_json::Value;
+
+/// Default limit of raw bytes to attach for a single artifact
+const RAW_BUDGET: usize = 64 * 1024 * 1024;
+/// Artifacts that attach the original FILETIME values when `keep_raw_filetime` is enabled
+const FILETIME_ARTIFACTS: [&str; 1] = ["prefetch"];
+
+/// Tracks how many raw bytes can still be attached to an artifact
+pub(crate) struct RawBudget {
+    remaining: usize,
+    exhausted: bool,
+}
+
+impl RawBudget {
+    /// Create a budget for an artifact. Uses `batch_bytes` if it is smaller than the default limit
+    pub(crate) fn new(output: &Output) -> RawBudget {
+        let remaining = match output.batch_bytes {
+            Some(bytes) if (bytes as usize) < RAW_BUDGET => bytes as usize,
+            _ => RAW_BUDGET,
+        };
+        RawBudget {
+            remaining,
+            exhausted: false,
+        }
+    }
+
+    /// Base64 encode raw bytes. Returns `None` once the budget has been used
+    pub(crate) fn encode(&mut self, data: &[u8]) -> Option<String> {
+        if data.len() > self.remaining {
+            if !self.exhausted {
+                warn!("[artemis-core] Raw data budget reached. Remaining records will not include raw data");
+                self.exhausted = true;
+            }
+            return None;
+        }
+        self.remaining -= data.len();
+        Some(base64_encode_standard(data))
+    }
+}
+
+/// Check if raw bytes should be attached to the output
+pub(crate) fn include_raw(output: &Output) -> bool {
+    output.include_raw.unwrap_or(false)
+}
+
+/// Attach raw data to a record under the `_raw` field
+pub(crate) fn attach_raw(record: &mut Value, raw: Value) {
+    if let Some(entry) = record.as_object_mut() {
+        entry.insert(String::from("_raw"), raw);
+    }
+}
+
+/// Check if original FILETIME values should be attached to the output
+pub(crate) fn keep_raw_filetime(output: &Output) -> bool {
+    output.keep_raw_filetime.unwrap_or(false)
+}
+
+/// Check if the artifact attaches the original FILETIME values. Other artifacts ignore `keep_raw_filetime`
+pub(crate) fn filetime_supported(artifact_name: &str) -> bool {
+    FILETIME_ARTIFACTS.contains(&artifact_name)
+}
+
+/// Attach the original FILETIME value(s) of a converted timestamp field under `<field>_filetime`
+pub(crate) fn attach_filetime(record: &mut Value, field: &str, filetime: Value) {
+    if let Some(entry) = record.as_object_mut() {
+        entry.insert(format!("{field}_filetime"), filetime);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        attach_filetime, attach_raw, filetime_supported, include_raw, keep_raw_filetime, RawBudget,
+    };
+    use crate::{
+        structs::toml::Output,
+        utils::{encoding::base64_decode_standard, time::filetime_to_unixepoch},
+    };
+    use serde_json::{json, Value};
+
+    fn output_options(batch_bytes: Option<u64>) -> Output {
+        Output {
+            name: String::from("raw_test"),
+            directory: String::from("./tmp"),
+            format: String::from("json"),
+            compress: false,
+            url: Some(String::new()),
+            api_key: Some(String::new()),
+            endpoint_id: String::from("abcd"),
+            collection_id: 0,
+            output: String::from("local"),
+            batch_bytes,
+            include_raw: Some(true),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_raw_round_trip() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut budget = RawBudget::new(&output_options(None));
+        let encoded = budget.encode(&data).unwrap();
+
+        let mut record = json!({"path": "test"});
+        attach_raw(&mut record, Value::String(encoded));
+
+        let decoded = base64_decode_standard(record["_raw"].as_str().unwrap()).unwrap();
+        assert_eq!(decoded, data);
+        assert!(include_raw(&output_options(None)));
+    }
+
+    #[test]
+    fn test_raw_budget() {
+        let mut budget = RawBudget::new(&output_options(Some(10)));
+        assert!(budget.encode(&[1; 6]).is_some());
+        assert!(budget.encode(&[1; 6]).is_none());
+        assert!(budget.encode(&[1; 4]).is_some());
+        assert!(budget.encode(&[1]).is_none());
+    }
+
+    #[test]
+    fn test_attach_raw_not_object() {
+        let mut record = json!([1, 2]);
+        attach_raw(&mut record, Value::String(String::from("AQI=")));
+        assert_eq!(record, json!([1, 2]));
+    }
+
+    #[test]
+    fn test_attach_filetime() {
+        let filetime: u64 = 132244766418940254;
+        let mut record = json!({"volume_creation": filetime_to_unixepoch(&filetime)});
+        attach_filetime(&mut record, "volume_creation", json!(filetime));
+
+        assert_eq!(record["volume_creation_filetime"], filetime);
+        assert_eq!(
+            record["volume_creation"],
+            filetime_to_unixepoch(&record["volume_creation_filetime"].as_u64().unwrap())
+        );
+
+        let mut options = output_options(None);
+        assert!(!keep_raw_filetime(&options));
+        options.keep_raw_filetime = Some(true);
+        assert!(keep_raw_filetime(&options));
+
+        assert!(filetime_supported("prefetch"));
+        assert!(!filetime_supported("amcache"));
+    }
+}
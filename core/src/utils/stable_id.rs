@@ -0,0 +1,50 @@
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/**
+ * Create a stable SHA256 ID for a record based on the provided key fields
+ * The same field values always produce the same ID across collections. Missing fields are treated as `null`
+ * If no fields are provided the whole record is used
+ */
+pub(crate) fn stable_id(record: &Value, fields: &[&str]) -> String {
+    let key = if fields.is_empty() {
+        record.to_string()
+    } else {
+        let values: Vec<Value> = fields
+            .iter()
+            .map(|field| record.get(field).cloned().unwrap_or(Value::Null))
+            .collect();
+        Value::Array(values).to_string()
+    };
+
+    let mut sha256 = Sha256::new();
+    sha256.update(key.as_bytes());
+    let hash = sha256.finalize();
+    format!("{hash:x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::stable_id;
+    use serde_json::json;
+
+    #[test]
+    fn test_stable_id() {
+        let record = json!({"path": "C:\\Windows\\notepad.exe", "run_count": 2});
+        let result = stable_id(&record, &["path"]);
+        assert_eq!(result.len(), 64);
+
+        let changed = json!({"path": "C:\\Windows\\notepad.exe", "run_count": 3});
+        assert_eq!(stable_id(&changed, &["path"]), result);
+        assert_ne!(stable_id(&changed, &[]), stable_id(&record, &[]));
+    }
+
+    #[test]
+    fn test_stable_id_missing_field() {
+        let record = json!({"path": "a"});
+        assert_eq!(
+            stable_id(&record, &["path", "missing"]),
+            stable_id(&json!({"path": "a", "missing": null}), &["path", "missing"])
+        );
+    }
+}
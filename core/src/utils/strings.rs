@@ -48,6 +48,31 @@ pub(crate) fn extract_utf16_string(data: &[u8]) -> String {
     result
 }
 
+/// Default maximum number of characters decoded by `extract_utf16_string_limit`
+pub(crate) const MAX_UTF16_CHARS: usize = 32768;
+
+/**
+ * Extract a UTF16 string but stop decoding after `max_chars` characters
+ * Returns the string and whether it was truncated. Protects against corrupt lengths or offsets that point to very large slices
+ */
+pub(crate) fn extract_utf16_string_limit(data: &[u8], max_chars: usize) -> (String, bool) {
+    let max_bytes = max_chars.saturating_mul(2);
+    let terminated = data
+        .chunks(2)
+        .take(max_chars.saturating_add(1))
+        .any(|wide_char| wide_char == [0, 0]);
+
+    let mut truncated = !terminated && data.len() > max_bytes;
+    let limited = if truncated { &data[..max_bytes] } else { data };
+    let mut value = extract_utf16_string(limited);
+    // Characters without zero bytes are decoded as two characters
+    if value.chars().count() > max_chars {
+        value = value.chars().take(max_chars).collect();
+        truncated = true;
+    }
+    (value, truncated)
+}
+
 /// Get UTF16 strings that have new lines
 pub(crate) fn extract_multiline_utf16_string(data: &[u8]) -> String {
     let mut utf16_data: Vec<u16> = Vec::new();
@@ -117,6 +142,56 @@ pub(crate) fn extract_ascii_utf16_string(data: &[u8]) -> String {
     }
 }
 
+/**
+ * Detect a byte-order-mark (BOM) and decode the string accordingly  
+ * Supports UTF8, UTF16LE, and UTF16BE BOMs. If no BOM is found the data is decoded as UTF16LE
+ */
+pub(crate) fn extract_string_auto(data: &[u8]) -> String {
+    let utf8_bom = [0xef, 0xbb, 0xbf];
+    let utf16_le_bom = [0xff, 0xfe];
+    let utf16_be_bom = [0xfe, 0xff];
+
+    if let Some(string_data) = data.strip_prefix(&utf8_bom) {
+        return extract_utf8_string(string_data);
+    }
+    if let Some(string_data) = data.strip_prefix(&utf16_le_bom) {
+        return extract_utf16_string(string_data);
+    }
+    if let Some(string_data) = data.strip_prefix(&utf16_be_bom) {
+        return extract_utf16_be_string(string_data);
+    }
+
+    extract_utf16_string(data)
+}
+
+/// Get a UTF16 big endian string from provided bytes data
+fn extract_utf16_be_string(data: &[u8]) -> String {
+    let mut utf16_data: Vec<u16> = Vec::new();
+    let min_byte_size = 2;
+    for wide_char in data.chunks(min_byte_size) {
+        if wide_char.len() < min_byte_size || wide_char == [0, 0] {
+            break;
+        }
+        utf16_data.push(u16::from_be_bytes([wide_char[0], wide_char[1]]));
+    }
+
+    let utf16_result = String::from_utf16(&utf16_data);
+    match utf16_result {
+        Ok(results) => results,
+        Err(err) => {
+            warn!("[strings] Failed to get UTF16 big endian string: {err:?}");
+
+            let max_size = 2097152;
+            let issue = if data.len() < max_size {
+                base64_encode_standard(data)
+            } else {
+                format!("Binary data size larger than 2MB, size: {}", data.len())
+            };
+            format!("Failed to get UTF16 big endian string: {}", issue)
+        }
+    }
+}
+
 /// Check if either string contains the other
 pub(crate) fn strings_contains(input1: &str, input2: &str) -> bool {
     if input1.contains(input2) || input2.contains(input1) {
@@ -128,7 +203,8 @@ pub(crate) fn strings_contains(input1: &str, input2: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use crate::utils::strings::{
-        extract_ascii_utf16_string, extract_multiline_utf16_string, extract_utf16_string,
+        extract_ascii_utf16_string, extract_multiline_utf16_string, extract_string_auto,
+        extract_utf16_be_string, extract_utf16_string, extract_utf16_string_limit,
         extract_utf8_string, strings_contains,
     };
 
@@ -150,6 +226,26 @@ mod tests {
         assert_eq!(extract_utf16_string(&test_data), "Kontrast #1 (extragroß)")
     }
 
+    #[test]
+    fn test_extract_utf16_string_limit() {
+        let test_data = [65, 0].repeat(40000);
+        let (value, truncated) = extract_utf16_string_limit(&test_data, 32768);
+        assert!(truncated);
+        assert_eq!(value.len(), 32768);
+
+        let test_data = vec![
+            79, 0, 83, 0, 81, 0, 85, 0, 69, 0, 82, 0, 89, 0, 68, 0, 46, 0, 69, 0, 88, 0, 69, 0, 0,
+            0, 65, 0, 65, 0,
+        ];
+        let (value, truncated) = extract_utf16_string_limit(&test_data, 12);
+        assert!(!truncated);
+        assert_eq!(value, "OSQUERYD.EXE");
+
+        let (value, truncated) = extract_utf16_string_limit(&test_data, 4);
+        assert!(truncated);
+        assert_eq!(value, "OSQU");
+    }
+
     #[test]
     fn test_extract_multiline_utf16_string() {
         let test_data = vec![
@@ -192,4 +288,43 @@ mod tests {
         let data = extract_utf16_string(&test);
         assert_eq!(data, "Windows 10Windows 10 LTSB");
     }
+
+    #[test]
+    fn test_extract_string_auto_utf8_bom() {
+        let test_data = vec![
+            0xef, 0xbb, 0xbf, 79, 83, 81, 85, 69, 82, 89, 68, 46, 69, 88, 69,
+        ];
+        assert_eq!(extract_string_auto(&test_data), "OSQUERYD.EXE")
+    }
+
+    #[test]
+    fn test_extract_string_auto_utf16_le_bom() {
+        let test_data = vec![
+            0xff, 0xfe, 79, 0, 83, 0, 81, 0, 85, 0, 69, 0, 82, 0, 89, 0, 68, 0, 0, 0,
+        ];
+        assert_eq!(extract_string_auto(&test_data), "OSQUERYD")
+    }
+
+    #[test]
+    fn test_extract_string_auto_utf16_be_bom() {
+        let test_data = vec![
+            0xfe, 0xff, 0, 79, 0, 83, 0, 81, 0, 85, 0, 69, 0, 82, 0, 89, 0, 68, 0, 0,
+        ];
+        assert_eq!(extract_string_auto(&test_data), "OSQUERYD")
+    }
+
+    #[test]
+    fn test_extract_string_auto_no_bom() {
+        let test_data = vec![
+            79, 0, 83, 0, 81, 0, 85, 0, 69, 0, 82, 0, 89, 0, 68, 0, 46, 0, 69, 0, 88, 0, 69, 0, 0,
+            0,
+        ];
+        assert_eq!(extract_string_auto(&test_data), "OSQUERYD.EXE")
+    }
+
+    #[test]
+    fn test_extract_utf16_be_string() {
+        let test_data = vec![0, 79, 0, 83, 0x4e, 0x2d];
+        assert_eq!(extract_utf16_be_string(&test_data), "OS中")
+    }
 }
@@ -23,6 +23,14 @@ pub(crate) fn cocoatime_to_unixepoch(cocoatime: &f64) -> i64 {
     (cocoatime + adjust_to_unix) as i64
 }
 
+/// Convert a `SystemTime` to unixepoch seconds. Times before 1970 are returned as negative values
+pub(crate) fn systemtime_to_unixepoch(time: &SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(result) => result.as_secs() as i64,
+        Err(err) => -(err.duration().as_secs() as i64),
+    }
+}
+
 /// Convert macOS HFS+ timestamp to unixepoch
 pub(crate) fn hfs_to_unixepoch(hfstime: &i64) -> i64 {
     let adjust_to_unix = 2082844800;
@@ -53,6 +61,16 @@ pub(crate) fn webkit_time_to_unixepoch(webkittime: &i64) -> i64 {
     webkittime - adjust_epoch
 }
 
+/// Convert unixepoch seconds to an ISO8601 UTC string. Returns an empty string if the time is out of range
+pub(crate) fn unixepoch_to_iso8601(epoch: &i64) -> String {
+    use chrono::{DateTime, SecondsFormat};
+
+    match DateTime::from_timestamp(*epoch, 0) {
+        Some(result) => result.to_rfc3339_opts(SecondsFormat::Secs, true),
+        None => String::new(),
+    }
+}
+
 /// Convert Windows FAT time (UTC) values to unixepoch
 pub(crate) fn fattime_utc_to_unixepoch(fattime: &[u8]) -> i64 {
     use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
@@ -134,8 +152,9 @@ mod tests {
     use super::{hfs_to_unixepoch, time_now, webkit_time_to_unixepoch};
     use crate::utils::time::{
         cocoatime_to_unixepoch, fattime_utc_to_unixepoch, filetime_to_unixepoch, get_fat_bits,
-        ole_automationtime_to_unixepoch,
+        ole_automationtime_to_unixepoch, systemtime_to_unixepoch, unixepoch_to_iso8601,
     };
+    use std::time::{Duration, UNIX_EPOCH};
 
     #[test]
     fn test_time_now() {
@@ -177,6 +196,29 @@ mod tests {
         assert_eq!(result, 978307210);
     }
 
+    #[test]
+    fn test_cocoatime_epoch() {
+        // Cocoa time starts at 2001-01-01 00:00:00 UTC
+        assert_eq!(cocoatime_to_unixepoch(&0.0), 978307200);
+        assert_eq!(
+            unixepoch_to_iso8601(&cocoatime_to_unixepoch(&0.0)),
+            "2001-01-01T00:00:00Z"
+        );
+        assert_eq!(cocoatime_to_unixepoch(&-978307200.0), 0);
+        assert_eq!(cocoatime_to_unixepoch(&700000000.5), 1678307200);
+    }
+
+    #[test]
+    fn test_systemtime_to_unixepoch() {
+        assert_eq!(systemtime_to_unixepoch(&UNIX_EPOCH), 0);
+
+        let test = UNIX_EPOCH + Duration::from_secs(978307200);
+        assert_eq!(systemtime_to_unixepoch(&test), 978307200);
+
+        let test = UNIX_EPOCH - Duration::from_secs(100);
+        assert_eq!(systemtime_to_unixepoch(&test), -100);
+    }
+
     #[test]
     fn test_webkit_to_unixepoch() {
         let test = 13289983960;
@@ -190,4 +232,11 @@ mod tests {
         let result = hfs_to_unixepoch(&test);
         assert_eq!(result, 1370276024);
     }
+
+    #[test]
+    fn test_unixepoch_to_iso8601() {
+        assert_eq!(unixepoch_to_iso8601(&1620610795), "2021-05-10T01:39:55Z");
+        assert_eq!(unixepoch_to_iso8601(&0), "1970-01-01T00:00:00Z");
+        assert_eq!(unixepoch_to_iso8601(&i64::MAX), "");
+    }
 }
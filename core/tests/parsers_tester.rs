@@ -0,0 +1,67 @@
+#[test]
+#[cfg(target_os = "windows")]
+fn test_parsers_prefetch() {
+    use core::{parsers::windows::grab_prefetch, structs::artifacts::os::windows::PrefetchOptions};
+    use std::path::PathBuf;
+
+    let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    test_location.push("tests/test_data/windows/prefetch/win10");
+
+    let options = PrefetchOptions {
+        alt_dir: Some(test_location.display().to_string()),
+        entropy: None,
+        max_last_run_times: None,
+    };
+    let results = grab_prefetch(&options).unwrap();
+    assert_eq!(results.len(), 272);
+
+    let entry = results
+        .iter()
+        .find(|entry| entry.path.contains("SHELLEXPERIENCEHOST.EXE-C83BCA53.pf"))
+        .unwrap();
+    assert_eq!(entry.filename, "SHELLEXPERIENCEHOST.EXE");
+    assert_eq!(entry.hash, "C83BCA53");
+    assert_eq!(entry.last_run_time, 1620610795);
+
+    // Results are plain structs. Callers serialize them however they want
+    let value = serde_json::to_value(&results).unwrap();
+    assert_eq!(value.as_array().unwrap().len(), 272);
+}
+
+#[test]
+#[cfg(target_os = "windows")]
+fn test_parsers_bits() {
+    use common::windows::BitsFormat;
+    use core::parsers::windows::grab_ese_bits;
+    use std::path::PathBuf;
+
+    let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    test_location.push("tests/test_data/windows/ese/win10/qmgr.db");
+
+    let results = grab_ese_bits(&test_location.display().to_string(), false).unwrap();
+    assert_eq!(results.bits.len(), 1);
+    assert_eq!(results.bits[0].source_format, BitsFormat::Ese);
+    assert!(results.carved_jobs.is_empty());
+    assert!(!results.partial);
+}
+
+#[test]
+#[cfg(target_os = "windows")]
+fn test_parsers_shortcuts() {
+    use core::{
+        parsers::windows::grab_shortcuts, structs::artifacts::os::windows::ShortcutOptions,
+    };
+    use std::path::PathBuf;
+
+    let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    test_location.push("tests/test_data/windows/lnk/win11");
+
+    let options = ShortcutOptions {
+        path: Some(test_location.display().to_string()),
+    };
+    let results = grab_shortcuts(&options).unwrap();
+    assert_eq!(results.len(), 5);
+    for entry in results {
+        assert!(entry.source_path.ends_with(".lnk"));
+    }
+}
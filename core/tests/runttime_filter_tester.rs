@@ -1,3 +1,5 @@
+#![cfg(feature = "scripting")]
+
 #[test]
 #[cfg(target_os = "macos")]
 fn test_runtime_filter_apps_info() {
@@ -37,6 +37,15 @@ class Encoding {
   extract_utf16_string = (data: Uint8Array) => {
     return core.ops.js_extract_utf16_string(data);
   };
+  /**
+   * Attempt to extract a string from raw bytes based on the byte-order-mark (BOM).
+   * Supports UTF8, UTF16LE, and UTF16BE BOMs. Defaults to UTF16LE if no BOM is found
+   * @param data Raw bytes to extract string from
+   * @returns An extracted string or empty value
+   */
+  extract_string_auto = (data: Uint8Array) => {
+    return core.ops.js_extract_string_auto(data);
+  };
   /**
    * Convert provided string to raw bytes
    * @param data String to convert to bytes
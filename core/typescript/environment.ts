@@ -13,12 +13,13 @@ class Environment {
         return core.ops.js_env();
     };
     /**
-     * Lookup a single Environment variable. Returns empty string if not found
+     * Lookup a single Environment variable. Returns empty string if not found  
+     * On Windows the system environment in the Registry is checked if the artemis process does not have the variable
      * @param key Environment variable to lookup
      * @returns Value of provided Environment variable
      */
     environmentValue = (key: string) => {
-        return core.ops.js_env_value(key);
+        return core.ops.environment_value(key);
     };
 }
 
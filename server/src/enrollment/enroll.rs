@@ -31,6 +31,7 @@ pub(crate) async fn enroll_endpoint(
         }
     };
 
+    state.metrics.enrolled();
     let enrolled = EnrollmentResponse { endpoint_id };
 
     Ok(Json(enrolled))
@@ -59,6 +60,8 @@ pub(crate) fn verify_enrollment(data: &str, ip: &str, path: &str) -> Result<(),
 #[cfg(test)]
 mod tests {
     use super::verify_enrollment;
+    use crate::metrics::registry::Metrics;
+    use crate::utils::events::EventLog;
     use crate::{
         enrollment::enroll::enroll_endpoint,
         server::ServerState,
@@ -108,7 +111,12 @@ mod tests {
             .unwrap();
 
         let command = Arc::new(RwLock::new(HashMap::new()));
-        let server_state = ServerState { config, command };
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
         let test2 = State(server_state);
 
         let result = enroll_endpoint(test2, test).await.unwrap();
@@ -152,7 +160,12 @@ mod tests {
             .unwrap();
 
         let command = Arc::new(RwLock::new(HashMap::new()));
-        let server_state = ServerState { config, command };
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
         let test2 = State(server_state);
 
         let result = enroll_endpoint(test2, test).await.unwrap();
@@ -10,6 +10,8 @@ pub(crate) fn enroll_routes(base: &str) -> Router<ServerState> {
 #[cfg(test)]
 mod tests {
     use super::enroll_routes;
+    use crate::metrics::registry::Metrics;
+    use crate::utils::events::EventLog;
     use crate::{server::ServerState, utils::config::read_config};
     use axum::{
         body::Body,
@@ -31,7 +33,12 @@ mod tests {
             .unwrap();
 
         let command = Arc::new(RwLock::new(HashMap::new()));
-        let server_state = ServerState { config, command };
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
 
         let res = route
             .with_state(server_state)
@@ -2,3 +2,5 @@ pub(crate) mod cache;
 pub(crate) mod endpoints;
 mod error;
 pub(crate) mod jobs;
+pub(crate) mod pending;
+pub(crate) mod uploads;
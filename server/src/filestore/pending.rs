@@ -0,0 +1,130 @@
+use super::error::StoreError;
+use crate::utils::filesystem::{is_file, read_file, write_file};
+use common::server::jobs::JobInfo;
+use log::error;
+use tokio::fs::remove_file;
+
+/**
+ * Queue a Job for an endpoint that could not receive it over websockets.
+ * Path is full path to endpoint **including** the endpoint ID
+ *
+ * Jobs are stored in the order they were queued and delivered when the endpoint reconnects
+ */
+pub(crate) async fn queue_pending(job: &JobInfo, path: &str) -> Result<(), StoreError> {
+    let mut jobs = get_pending(path).await?;
+    jobs.push(job.clone());
+
+    let serde_result = serde_json::to_vec(&jobs);
+    let value = match serde_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[server] Failed to serialize pending jobs: {err:?}");
+            return Err(StoreError::Serialize);
+        }
+    };
+
+    let pending_file = format!("{path}/pending.json");
+    let status = write_file(&value, &pending_file, false).await;
+    if status.is_err() {
+        error!("[server] Could not write pending jobs file");
+        return Err(StoreError::WriteFile);
+    }
+
+    Ok(())
+}
+
+/// Return all pending Jobs for endpoint in the order they were queued. Path is full path to endpoint **including** the endpoint ID
+pub(crate) async fn get_pending(path: &str) -> Result<Vec<JobInfo>, StoreError> {
+    let pending_file = format!("{path}/pending.json");
+    if !is_file(&pending_file) {
+        return Ok(Vec::new());
+    }
+
+    let value_result = read_file(&pending_file).await;
+    let value = match value_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[server] Failed to read {pending_file}: {err:?}");
+            return Err(StoreError::ReadFile);
+        }
+    };
+
+    let serde_value = serde_json::from_slice(&value);
+    let jobs: Vec<JobInfo> = match serde_value {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[server] Failed to deserialize pending jobs: {err:?}");
+            return Err(StoreError::Deserialize);
+        }
+    };
+
+    Ok(jobs)
+}
+
+/// Remove and return all pending Jobs for endpoint. Path is full path to endpoint **including** the endpoint ID
+pub(crate) async fn take_pending(path: &str) -> Result<Vec<JobInfo>, StoreError> {
+    let jobs = get_pending(path).await?;
+    if jobs.is_empty() {
+        return Ok(jobs);
+    }
+
+    let pending_file = format!("{path}/pending.json");
+    if let Err(err) = remove_file(&pending_file).await {
+        error!("[server] Could not remove pending jobs file: {err:?}");
+        return Err(StoreError::WriteFile);
+    }
+
+    Ok(jobs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_pending, queue_pending, take_pending};
+    use crate::utils::filesystem::create_dirs;
+    use common::server::jobs::{Action, JobInfo, JobType, Status};
+
+    fn test_job(name: &str) -> JobInfo {
+        JobInfo {
+            id: 1,
+            name: name.to_string(),
+            created: 10,
+            started: 0,
+            finished: 0,
+            status: Status::NotStarted,
+            duration: 0,
+            start_time: 0,
+            action: Action::Start,
+            job_type: JobType::Processes,
+            collection: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queue_pending() {
+        let path = "./tmp/pending";
+        create_dirs(path).await.unwrap();
+        let _ = take_pending(path).await.unwrap();
+
+        queue_pending(&test_job("first"), path).await.unwrap();
+        queue_pending(&test_job("second"), path).await.unwrap();
+
+        let result = get_pending(path).await.unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].name, "first");
+        assert_eq!(result[1].name, "second");
+    }
+
+    #[tokio::test]
+    async fn test_take_pending() {
+        let path = "./tmp/pending_take";
+        create_dirs(path).await.unwrap();
+        let _ = take_pending(path).await.unwrap();
+
+        queue_pending(&test_job("first"), path).await.unwrap();
+        let result = take_pending(path).await.unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "first");
+
+        assert!(take_pending(path).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,179 @@
+use super::error::StoreError;
+use crate::utils::filesystem::{is_file, read_file, write_file};
+use common::server::uploads::ManifestEntry;
+use log::error;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex, OnceLock},
+};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+/// Locks serializing `manifest.json` updates for each collection directory
+static MANIFEST_LOCKS: OnceLock<Mutex<HashMap<String, Arc<AsyncMutex<()>>>>> = OnceLock::new();
+
+/**
+ * Check if a file with the provided content hash was already received for the collection.
+ * Collection directory is the full path to the endpoint collection **including** the collection ID
+ */
+pub(crate) async fn upload_received(collection_dir: &str, hash: &str) -> Result<bool, StoreError> {
+    let _guard = manifest_lock(collection_dir).await;
+    let manifest = get_manifest(collection_dir).await?;
+    Ok(manifest.values().any(|entry| entry.sha256 == hash))
+}
+
+/// Record a received file in the `manifest.json` file of the collection directory. Updates are serialized per collection
+pub(crate) async fn record_manifest(
+    collection_dir: &str,
+    filename: &str,
+    entry: ManifestEntry,
+) -> Result<(), StoreError> {
+    // Concurrent uploads to the same collection would otherwise overwrite each other's entries
+    let _guard = manifest_lock(collection_dir).await;
+    let mut manifest = get_manifest(collection_dir).await?;
+    manifest.insert(filename.to_string(), entry);
+
+    let serde_result = serde_json::to_vec(&manifest);
+    let value = match serde_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[server] Failed to serialize collection manifest: {err:?}");
+            return Err(StoreError::Serialize);
+        }
+    };
+
+    let manifest_file = format!("{collection_dir}/manifest.json");
+    let status = write_file(&value, &manifest_file, false).await;
+    if status.is_err() {
+        error!("[server] Could not write collection manifest file");
+        return Err(StoreError::WriteFile);
+    }
+
+    Ok(())
+}
+
+/// Wait for the manifest lock of a collection directory
+async fn manifest_lock(collection_dir: &str) -> OwnedMutexGuard<()> {
+    let lock = {
+        let mut locks = match MANIFEST_LOCKS
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+        {
+            Ok(result) => result,
+            Err(err) => {
+                error!("[server] Manifest locks were poisoned: {err:?}");
+                err.into_inner()
+            }
+        };
+        locks
+            .entry(collection_dir.to_string())
+            .or_insert_with(|| Arc::new(AsyncMutex::new(())))
+            .clone()
+    };
+    lock.lock_owned().await
+}
+
+/// Get the received files in a collection directory
+pub(crate) async fn get_manifest(
+    collection_dir: &str,
+) -> Result<BTreeMap<String, ManifestEntry>, StoreError> {
+    let manifest_file = format!("{collection_dir}/manifest.json");
+    if !is_file(&manifest_file) {
+        return Ok(BTreeMap::new());
+    }
+
+    let value_result = read_file(&manifest_file).await;
+    let value = match value_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[server] Failed to read {manifest_file}: {err:?}");
+            return Err(StoreError::ReadFile);
+        }
+    };
+
+    let serde_value = serde_json::from_slice(&value);
+    match serde_value {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("[server] Failed to deserialize collection manifest: {err:?}");
+            Err(StoreError::Deserialize)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_manifest, record_manifest, upload_received};
+    use crate::utils::filesystem::create_dirs;
+    use common::server::uploads::ManifestEntry;
+    use tokio::fs::remove_file;
+
+    #[tokio::test]
+    async fn test_upload_received() {
+        let path = "./tmp/uploads_hashes/1";
+        create_dirs(path).await.unwrap();
+        let _ = remove_file(format!("{path}/manifest.json")).await;
+
+        assert!(!upload_received(path, "abcd").await.unwrap());
+        let entry = ManifestEntry {
+            sha256: String::from("abcd"),
+            size: 4,
+            verified: true,
+        };
+        record_manifest(path, "processes.jsonl.gz", entry)
+            .await
+            .unwrap();
+        assert!(upload_received(path, "abcd").await.unwrap());
+
+        // Hashes are tracked per collection
+        assert!(!upload_received("./tmp/uploads_hashes/2", "abcd")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_record_manifest() {
+        let path = "./tmp/uploads_manifest/1";
+        create_dirs(path).await.unwrap();
+        let _ = remove_file(format!("{path}/manifest.json")).await;
+
+        assert!(get_manifest(path).await.unwrap().is_empty());
+        let entry = ManifestEntry {
+            sha256: String::from("abcd"),
+            size: 4,
+            verified: true,
+        };
+        record_manifest(path, "processes.jsonl.gz", entry.clone())
+            .await
+            .unwrap();
+
+        let manifest = get_manifest(path).await.unwrap();
+        assert_eq!(manifest.get("processes.jsonl.gz"), Some(&entry));
+    }
+
+    #[tokio::test]
+    async fn test_record_manifest_concurrent() {
+        let path = "./tmp/uploads_manifest_concurrent/1";
+        create_dirs(path).await.unwrap();
+        let _ = remove_file(format!("{path}/manifest.json")).await;
+
+        let mut tasks = Vec::new();
+        for index in 0..16 {
+            tasks.push(tokio::spawn(async move {
+                let entry = ManifestEntry {
+                    sha256: format!("{index:064x}"),
+                    size: index,
+                    verified: true,
+                };
+                record_manifest(path, &format!("{index}.jsonl"), entry)
+                    .await
+                    .unwrap();
+            }));
+        }
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let manifest = get_manifest(path).await.unwrap();
+        assert_eq!(manifest.len(), 16);
+    }
+}
@@ -0,0 +1,353 @@
+use crate::{
+    server::ServerState,
+    uploads::{collections::list_collections, upload::check_id},
+    utils::filesystem::{is_file, read_file, read_lines},
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use common::server::webui::{ArtifactRecords, ArtifactRequest};
+use log::{error, warn};
+use serde_json::Value;
+use std::{cmp::Ordering, collections::BTreeMap};
+
+/// List the collected artifact files for an endpoint
+pub(crate) async fn endpoint_artifacts(
+    State(state): State<ServerState>,
+    data: String,
+) -> Result<Json<BTreeMap<String, Vec<String>>>, StatusCode> {
+    list_collections(State(state), Path(data.trim().to_string())).await
+}
+
+/// Get a page of filtered and sorted records from a collected artifact file
+pub(crate) async fn artifact_records(
+    State(state): State<ServerState>,
+    Json(data): Json<ArtifactRequest>,
+) -> Result<Json<ArtifactRecords>, StatusCode> {
+    check_id(&data.endpoint_id)?;
+    check_id(&data.collection_id)?;
+    check_id(&data.filename)?;
+
+    let path = format!(
+        "{}/{}/{}/{}",
+        state.config.endpoint_server.storage, data.endpoint_id, data.collection_id, data.filename
+    );
+    if !is_file(&path) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let mut page = RecordPage::new(&data);
+    if data.filename.ends_with(".jsonl") {
+        // Stream the lines so large artifacts are never fully loaded into memory
+        let mut lines = read_lines(&path).await.map_err(|err| {
+            error!("[server] Could not read artifact {path}: {err:?}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        while let Ok(Some(line)) = lines.next_line().await {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(value) => page.add(value),
+                Err(err) => warn!("[server] Skipping bad line in artifact {path}: {err:?}"),
+            }
+        }
+    } else if data.filename.ends_with(".json") {
+        let bytes = read_file(&path).await.map_err(|err| {
+            error!("[server] Could not read artifact {path}: {err:?}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        let value: Value = serde_json::from_slice(&bytes).map_err(|err| {
+            error!("[server] Could not parse artifact {path}: {err:?}");
+            StatusCode::UNPROCESSABLE_ENTITY
+        })?;
+
+        match artifact_record(value) {
+            Value::Array(entries) => entries.into_iter().for_each(|entry| page.add(entry)),
+            entry => page.add(entry),
+        }
+    } else {
+        error!("[server] Unsupported artifact file {path}. Only json and jsonl can be browsed");
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    Ok(Json(page.finish()))
+}
+
+/// Max records returned in a single page
+const MAX_PAGE: usize = 500;
+
+/// Tracks the records in the requested page while the artifact is read
+struct RecordPage<'a> {
+    request: &'a ArtifactRequest,
+    count: usize,
+    total: usize,
+    records: Vec<Value>,
+}
+
+impl<'a> RecordPage<'a> {
+    fn new(request: &'a ArtifactRequest) -> RecordPage<'a> {
+        RecordPage {
+            request,
+            count: request.count.clamp(1, MAX_PAGE),
+            total: 0,
+            records: Vec::new(),
+        }
+    }
+
+    /// Add a record if it matches the filter. Only enough records to fill the page are kept
+    fn add(&mut self, value: Value) {
+        let record = artifact_record(value);
+        if !record_matches(&record, &self.request.field, &self.request.filter) {
+            return;
+        }
+        self.total += 1;
+
+        if self.request.sort.is_empty() {
+            if self.total > self.request.offset && self.records.len() < self.count {
+                self.records.push(record);
+            }
+            return;
+        }
+
+        // Sorting needs every record. Trim periodically to keep memory bounded by the page end
+        let limit = self.request.offset + self.count;
+        self.records.push(record);
+        if self.records.len() >= limit * 2 {
+            self.sort();
+            self.records.truncate(limit);
+        }
+    }
+
+    fn sort(&mut self) {
+        let column = &self.request.sort;
+        let ascending = self.request.ascending;
+        self.records.sort_by(|a, b| {
+            let order = compare_values(
+                a.get(column).unwrap_or(&Value::Null),
+                b.get(column).unwrap_or(&Value::Null),
+            );
+            if ascending {
+                order
+            } else {
+                order.reverse()
+            }
+        });
+    }
+
+    fn finish(mut self) -> ArtifactRecords {
+        if !self.request.sort.is_empty() {
+            self.sort();
+            self.records = self
+                .records
+                .into_iter()
+                .skip(self.request.offset)
+                .take(self.count)
+                .collect();
+        }
+
+        let mut columns: Vec<String> = Vec::new();
+        for record in &self.records {
+            if let Value::Object(entries) = record {
+                for key in entries.keys() {
+                    if !columns.contains(key) {
+                        columns.push(key.clone());
+                    }
+                }
+            }
+        }
+
+        ArtifactRecords {
+            total: self.total,
+            columns,
+            records: self.records,
+        }
+    }
+}
+
+/// Artemis output wraps records with collection metadata. Only return the artifact data
+fn artifact_record(value: Value) -> Value {
+    match value {
+        Value::Object(mut entries) if entries.contains_key("metadata") => {
+            entries.remove("data").unwrap_or(Value::Null)
+        }
+        _ => value,
+    }
+}
+
+/// Check if a record field contains the filter value. An empty field searches the whole record
+fn record_matches(record: &Value, field: &str, filter: &str) -> bool {
+    if filter.is_empty() {
+        return true;
+    }
+    let filter = filter.to_lowercase();
+    if field.is_empty() {
+        return record.to_string().to_lowercase().contains(&filter);
+    }
+
+    match record.get(field) {
+        Some(Value::String(value)) => value.to_lowercase().contains(&filter),
+        Some(value) => value.to_string().to_lowercase().contains(&filter),
+        None => false,
+    }
+}
+
+/// Compare numbers by value and strings case insensitive
+fn compare_values(first: &Value, second: &Value) -> Ordering {
+    match (first, second) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+        _ => first.to_string().cmp(&second.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{artifact_records, compare_values, endpoint_artifacts, record_matches};
+    use crate::metrics::registry::Metrics;
+    use crate::utils::events::EventLog;
+    use crate::{
+        server::ServerState,
+        utils::{config::read_config, filesystem::create_dirs, filesystem::write_file},
+    };
+    use axum::{extract::State, http::StatusCode, Json};
+    use common::server::webui::ArtifactRequest;
+    use serde_json::json;
+    use std::{cmp::Ordering, collections::HashMap, path::PathBuf, sync::Arc};
+    use tokio::sync::RwLock;
+
+    async fn server_state() -> ServerState {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        }
+    }
+
+    /// Write a jsonl artifact with records wrapped in collection metadata
+    async fn write_artifact(state: &ServerState, endpoint_id: &str) {
+        let dir = format!("{}/{endpoint_id}/1", state.config.endpoint_server.storage);
+        create_dirs(&dir).await.unwrap();
+
+        let mut lines = String::new();
+        for (path, run_count) in [("notepad.exe", 3), ("calc.exe", 10), ("cmd.exe", 1)] {
+            let line = json!({"metadata": {"artifact_name": "prefetch"}, "data": {"path": path, "run_count": run_count}});
+            lines.push_str(&format!("{line}\n"));
+        }
+        write_file(lines.as_bytes(), &format!("{dir}/prefetch.jsonl"), false)
+            .await
+            .unwrap();
+    }
+
+    fn request(endpoint_id: &str) -> ArtifactRequest {
+        ArtifactRequest {
+            endpoint_id: endpoint_id.to_string(),
+            collection_id: String::from("1"),
+            filename: String::from("prefetch.jsonl"),
+            offset: 0,
+            count: 2,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_artifact_records() {
+        let state = server_state().await;
+        write_artifact(&state, "artifacts-test-records").await;
+
+        let result = artifact_records(State(state), Json(request("artifacts-test-records")))
+            .await
+            .unwrap();
+
+        assert_eq!(result.total, 3);
+        assert_eq!(result.records.len(), 2);
+        assert_eq!(result.columns, vec!["path", "run_count"]);
+        assert_eq!(result.records[0]["path"], "notepad.exe");
+    }
+
+    #[tokio::test]
+    async fn test_artifact_records_sort_page() {
+        let state = server_state().await;
+        write_artifact(&state, "artifacts-test-sort").await;
+
+        let mut data = request("artifacts-test-sort");
+        data.sort = String::from("run_count");
+        data.ascending = false;
+        data.offset = 1;
+        let result = artifact_records(State(state), Json(data)).await.unwrap();
+
+        assert_eq!(result.total, 3);
+        assert_eq!(result.records.len(), 2);
+        assert_eq!(result.records[0]["path"], "notepad.exe");
+        assert_eq!(result.records[1]["path"], "cmd.exe");
+    }
+
+    #[tokio::test]
+    async fn test_artifact_records_filter() {
+        let state = server_state().await;
+        write_artifact(&state, "artifacts-test-filter").await;
+
+        let mut data = request("artifacts-test-filter");
+        data.field = String::from("path");
+        data.filter = String::from("CALC");
+        let result = artifact_records(State(state), Json(data)).await.unwrap();
+
+        assert_eq!(result.total, 1);
+        assert_eq!(result.records[0]["run_count"], 10);
+    }
+
+    #[tokio::test]
+    async fn test_artifact_records_traversal() {
+        let state = server_state().await;
+        let mut data = request("artifacts-test-traversal");
+        data.filename = String::from("../../server.toml");
+        let result = artifact_records(State(state), Json(data)).await;
+
+        assert_eq!(result.unwrap_err(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_artifacts() {
+        let state = server_state().await;
+        write_artifact(&state, "artifacts-test-list").await;
+
+        let result = endpoint_artifacts(State(state), String::from("artifacts-test-list"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.get("1").unwrap(),
+            &vec![String::from("prefetch.jsonl")]
+        );
+    }
+
+    #[test]
+    fn test_record_matches() {
+        let record = json!({"path": "C:\\Windows\\cmd.exe", "run_count": 12});
+        assert!(record_matches(&record, "", ""));
+        assert!(record_matches(&record, "path", "CMD"));
+        assert!(record_matches(&record, "run_count", "12"));
+        assert!(record_matches(&record, "", "windows"));
+        assert!(!record_matches(&record, "missing", "cmd"));
+    }
+
+    #[test]
+    fn test_compare_values() {
+        assert_eq!(compare_values(&json!(2), &json!(10)), Ordering::Less);
+        assert_eq!(compare_values(&json!("b"), &json!("A")), Ordering::Greater);
+    }
+}
@@ -100,6 +100,8 @@ async fn endpoint_path(data: &str, state: &ServerState) -> Result<String, Status
 
 #[cfg(test)]
 mod tests {
+    use crate::metrics::registry::Metrics;
+    use crate::utils::events::EventLog;
     use crate::{
         enrollment::enroll::enroll_endpoint,
         frontend::endpoints::{
@@ -129,7 +131,12 @@ mod tests {
             .unwrap();
 
         let command = Arc::new(RwLock::new(HashMap::new()));
-        let server_state = ServerState { config, command };
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
         let test2 = State(server_state);
 
         let _ = endpoint_stats(test2, test).await.unwrap();
@@ -146,7 +153,12 @@ mod tests {
             .unwrap();
 
         let command = Arc::new(RwLock::new(HashMap::new()));
-        let server_state = ServerState { config, command };
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
         let test2 = State(server_state);
 
         let data = Json(EndpointRequest {
@@ -199,7 +211,12 @@ mod tests {
             .unwrap();
 
         let command = Arc::new(RwLock::new(HashMap::new()));
-        let server_state = ServerState { config, command };
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
 
         let result = endpoint_path("Darwin.123", &server_state).await.unwrap();
         assert!(result.contains("123"))
@@ -217,7 +234,12 @@ mod tests {
             .unwrap();
 
         let command = Arc::new(RwLock::new(HashMap::new()));
-        let server_state = ServerState { config, command };
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
         let test2 = State(server_state);
 
         let _ = endpoint_info(test2, "Darwin.123".to_string())
@@ -237,7 +259,12 @@ mod tests {
             .unwrap();
 
         let command = Arc::new(RwLock::new(HashMap::new()));
-        let server_state = ServerState { config, command };
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
         let test2 = State(server_state);
 
         let _ = endpoint_processes(test2, "Darwin.123".to_string())
@@ -0,0 +1,77 @@
+use crate::server::ServerState;
+use axum::{
+    extract::{
+        ws::{Message, WebSocket},
+        State, WebSocketUpgrade,
+    },
+    response::IntoResponse,
+};
+use common::server::webui::ServerEvent;
+use futures::{stream::SplitSink, SinkExt, StreamExt};
+use log::{error, warn};
+use std::ops::ControlFlow;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Stream recent and new server events to the `WebUI`
+pub(crate) async fn event_stream(
+    socket: WebSocketUpgrade,
+    State(state): State<ServerState>,
+) -> impl IntoResponse {
+    socket.on_upgrade(move |ws| send_events(ws, state))
+}
+
+/// Send all recent events then forward new events until the `WebUI` disconnects
+async fn send_events(socket: WebSocket, state: ServerState) {
+    let (mut sender, mut receiver) = socket.split();
+    let (recent, mut events) = state.events.subscribe().await;
+
+    for event in recent {
+        if send_event(&mut sender, &event).await.is_break() {
+            return;
+        }
+    }
+
+    loop {
+        tokio::select! {
+            event_result = events.recv() => {
+                match event_result {
+                    Ok(event) => {
+                        if send_event(&mut sender, &event).await.is_break() {
+                            return;
+                        }
+                    }
+                    Err(RecvError::Lagged(count)) => {
+                        warn!("[server] WebUI event stream skipped {count} events");
+                    }
+                    Err(RecvError::Closed) => return,
+                }
+            }
+            message = receiver.next() => {
+                match message {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+    }
+}
+
+/// Serialize and send a single event. Breaks if the socket is closed
+async fn send_event(
+    sender: &mut SplitSink<WebSocket, Message>,
+    event: &ServerEvent,
+) -> ControlFlow<()> {
+    let serde_result = serde_json::to_string(event);
+    let value = match serde_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[server] Could not serialize server event: {err:?}");
+            return ControlFlow::Continue(());
+        }
+    };
+
+    if sender.send(Message::Text(value)).await.is_err() {
+        return ControlFlow::Break(());
+    }
+    ControlFlow::Continue(())
+}
@@ -1,4 +1,7 @@
 pub(crate) mod about;
+pub(crate) mod artifacts;
 pub(crate) mod endpoints;
+pub(crate) mod events;
 pub(crate) mod uris;
+pub(crate) mod validate;
 pub(crate) mod webui;
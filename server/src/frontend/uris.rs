@@ -1,6 +1,9 @@
 use super::{
     about::about,
+    artifacts::{artifact_records, endpoint_artifacts},
     endpoints::{endpoint_info, endpoint_list, endpoint_processes, endpoint_stats},
+    events::event_stream,
+    validate::validate_toml,
     webui::webui,
 };
 use crate::server::ServerState;
@@ -16,6 +19,9 @@ pub(crate) fn setup_webui(base: &str) -> Router<ServerState> {
     frontend = frontend.merge(Router::new().route(&format!("{base}/about"), get(webui)));
     frontend = frontend.merge(Router::new().route(&format!("{base}/endpoints"), get(webui)));
     frontend = frontend.merge(Router::new().route(&format!("{base}/endpoints/info"), get(webui)));
+    frontend = frontend.merge(Router::new().route(&format!("{base}/collections"), get(webui)));
+    frontend =
+        frontend.merge(Router::new().route(&format!("{base}/endpoints/artifacts"), get(webui)));
 
     // Post requests for Endpoint info
     frontend = frontend
@@ -28,14 +34,31 @@ pub(crate) fn setup_webui(base: &str) -> Router<ServerState> {
         &format!("{base}/endpoints/processes"),
         post(endpoint_processes),
     ));
+    frontend = frontend.merge(Router::new().route(
+        &format!("{base}/endpoints/artifacts"),
+        post(endpoint_artifacts),
+    ));
+    frontend = frontend.merge(Router::new().route(
+        &format!("{base}/endpoints/artifacts/records"),
+        post(artifact_records),
+    ));
+
+    // Validate collection TOML
+    frontend = frontend
+        .merge(Router::new().route(&format!("{base}/collections/validate"), post(validate_toml)));
 
     // Server stats
     frontend = frontend.merge(Router::new().route(&format!("{base}/server/stats"), get(about)));
+    // Server events
+    frontend =
+        frontend.merge(Router::new().route(&format!("{base}/server/events"), get(event_stream)));
     frontend
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::metrics::registry::Metrics;
+    use crate::utils::events::EventLog;
     use crate::{frontend::uris::setup_webui, server::ServerState, utils::config::read_config};
     use axum::{
         body::Body,
@@ -58,7 +81,12 @@ mod tests {
             .unwrap();
 
         let command = Arc::new(RwLock::new(HashMap::new()));
-        let server_state = ServerState { config, command };
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
 
         let res = route
             .with_state(server_state)
@@ -74,4 +102,43 @@ mod tests {
 
         assert_eq!(res.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_setup_webui_events() {
+        let base = "/ui/v1";
+        let route = setup_webui(base);
+
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
+
+        let res = route
+            .with_state(server_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .header("connection", "upgrade")
+                    .header("upgrade", "websocket")
+                    .header("sec-websocket-version", "13")
+                    .header("sec-websocket-key", "13")
+                    .uri(format!("{base}/server/events"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::UPGRADE_REQUIRED);
+    }
 }
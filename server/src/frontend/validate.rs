@@ -0,0 +1,157 @@
+use axum::Json;
+use common::server::webui::TomlValidation;
+use toml::{Table, Value};
+
+/// Validate the structure of a collection TOML submitted by the `WebUI`
+pub(crate) async fn validate_toml(data: String) -> Json<TomlValidation> {
+    Json(check_toml(&data))
+}
+
+/// Check the collection TOML for required tables and keys
+pub(crate) fn check_toml(data: &str) -> TomlValidation {
+    let mut validation = TomlValidation::default();
+
+    let toml_result = data.parse::<Table>();
+    let collection = match toml_result {
+        Ok(result) => result,
+        Err(err) => {
+            validation
+                .errors
+                .push(format!("Could not parse TOML: {err}"));
+            return validation;
+        }
+    };
+
+    match collection.get("system") {
+        Some(Value::String(system)) => {
+            let supported = ["windows", "macos", "linux"];
+            if !supported.contains(&system.as_str()) {
+                validation
+                    .errors
+                    .push(format!("Unsupported system: {system}"));
+            }
+        }
+        _ => validation
+            .errors
+            .push(String::from("Missing string value: system")),
+    }
+
+    match collection.get("output") {
+        Some(Value::Table(output)) => check_output(output, &mut validation.errors),
+        _ => validation
+            .errors
+            .push(String::from("Missing table: output")),
+    }
+
+    let artifacts = match collection.get("artifacts") {
+        Some(Value::Array(result)) => result,
+        _ => {
+            validation
+                .errors
+                .push(String::from("Missing array of tables: artifacts"));
+            return validation;
+        }
+    };
+
+    for (index, artifact) in artifacts.iter().enumerate() {
+        match artifact.get("artifact_name") {
+            Some(Value::String(name)) => validation.artifacts.push(name.clone()),
+            _ => validation.errors.push(format!(
+                "Artifact {index} missing string value: artifact_name"
+            )),
+        }
+    }
+
+    validation
+}
+
+/// Check the required `output` keys and their types
+fn check_output(output: &Table, errors: &mut Vec<String>) {
+    let strings = ["name", "endpoint_id", "directory", "output", "format"];
+    for key in strings {
+        if !matches!(output.get(key), Some(Value::String(_))) {
+            errors.push(format!("Missing string value: output.{key}"));
+        }
+    }
+
+    if !matches!(output.get("collection_id"), Some(Value::Integer(_))) {
+        errors.push(String::from("Missing integer value: output.collection_id"));
+    }
+    if !matches!(output.get("compress"), Some(Value::Boolean(_))) {
+        errors.push(String::from("Missing boolean value: output.compress"));
+    }
+
+    if let Some(Value::String(format)) = output.get("format") {
+        let formats = ["json", "jsonl"];
+        if !formats.contains(&format.to_lowercase().as_str()) {
+            errors.push(format!("Unknown output format: {format}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_toml, validate_toml};
+
+    const COLLECTION: &str = r#"
+system = "windows"
+
+[output]
+name = "prefetch_collection"
+directory = "./tmp"
+format = "jsonl"
+compress = false
+endpoint_id = "abdc"
+collection_id = 1
+output = "local"
+
+[[artifacts]]
+artifact_name = "prefetch"
+[artifacts.prefetch]
+
+[[artifacts]]
+artifact_name = "bits"
+"#;
+
+    #[tokio::test]
+    async fn test_validate_toml() {
+        let result = validate_toml(COLLECTION.to_string()).await;
+        assert!(result.errors.is_empty());
+        assert_eq!(result.artifacts, vec!["prefetch", "bits"]);
+    }
+
+    #[test]
+    fn test_check_toml_bad_syntax() {
+        let result = check_toml("system = ");
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.artifacts.is_empty());
+    }
+
+    #[test]
+    fn test_check_toml_missing_values() {
+        let data = r#"
+system = "beos"
+
+[output]
+name = "test"
+format = "csv"
+
+[[artifacts]]
+filter = true
+"#;
+        let result = check_toml(data);
+        assert!(result.artifacts.is_empty());
+        assert!(result
+            .errors
+            .contains(&String::from("Unsupported system: beos")));
+        assert!(result
+            .errors
+            .contains(&String::from("Unknown output format: csv")));
+        assert!(result
+            .errors
+            .contains(&String::from("Missing integer value: output.collection_id")));
+        assert!(result.errors.contains(&String::from(
+            "Artifact 0 missing string value: artifact_name"
+        )));
+    }
+}
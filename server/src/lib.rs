@@ -77,6 +77,7 @@ mod artifacts;
 mod enrollment;
 mod filestore;
 mod frontend;
+mod metrics;
 mod routes;
 pub mod server;
 mod socket;
@@ -0,0 +1,82 @@
+use crate::server::ServerState;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::header::CONTENT_TYPE,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::time::Instant;
+
+/// Path metrics are served at if not set in the config
+pub(crate) const METRICS_PATH: &str = "/metrics";
+
+/// Serve the server metrics in the Prometheus text format
+pub(crate) async fn metrics_export(State(state): State<ServerState>) -> impl IntoResponse {
+    let connected = state.command.read().await.len();
+    (
+        [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(connected),
+    )
+}
+
+/// Record the latency and status of each request to a route
+pub(crate) async fn track_requests(
+    State(state): State<ServerState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let start = Instant::now();
+    let method = request.method().to_string();
+    // Use the route pattern so endpoint and collection IDs do not create new metrics
+    let route = match request.extensions().get::<MatchedPath>() {
+        Some(result) => result.as_str().to_string(),
+        None => request.uri().path().to_string(),
+    };
+
+    let response = next.run(request).await;
+    state.metrics.observe_request(
+        &route,
+        &method,
+        response.status().as_u16(),
+        start.elapsed().as_secs_f64(),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::metrics_export;
+    use crate::{
+        metrics::registry::Metrics,
+        server::ServerState,
+        utils::{config::read_config, events::EventLog},
+    };
+    use axum::{extract::State, response::IntoResponse};
+    use std::{collections::HashMap, path::PathBuf, sync::Arc};
+    use tokio::sync::RwLock;
+
+    #[tokio::test]
+    async fn test_metrics_export() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
+        server_state.metrics.uploaded(10);
+
+        let res = metrics_export(State(server_state)).await.into_response();
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "text/plain; version=0.0.4"
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub(crate) mod export;
+pub(crate) mod registry;
+pub(crate) mod uris;
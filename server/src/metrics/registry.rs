@@ -0,0 +1,194 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Upper bounds in seconds for the request latency histogram
+const LATENCY_BUCKETS: [f64; 10] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0];
+
+/// Server metrics shared by all requests. Created once when the server starts
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Metrics {
+    inner: Arc<ServerMetrics>,
+}
+
+#[derive(Debug, Default)]
+struct ServerMetrics {
+    enrollments: AtomicU64,
+    active_collections: AtomicU64,
+    upload_bytes: AtomicU64,
+    /**Request latency by route, method, and status code */
+    requests: Mutex<BTreeMap<RequestLabels, Histogram>>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct RequestLabels {
+    route: String,
+    method: String,
+    status: u16,
+}
+
+#[derive(Debug, Default)]
+struct Histogram {
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Metrics {
+    /// Count an endpoint that enrolled successfully
+    pub(crate) fn enrolled(&self) {
+        self.inner.enrollments.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count collections sent to endpoints
+    pub(crate) fn collections_started(&self, count: u64) {
+        self.inner
+            .active_collections
+            .fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Remove a collection that an endpoint reported as done. Never goes below zero
+    pub(crate) fn collection_finished(&self) {
+        let _ = self.inner.active_collections.fetch_update(
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+            |active| active.checked_sub(1),
+        );
+    }
+
+    /// Count bytes of collection data uploaded by endpoints
+    pub(crate) fn uploaded(&self, bytes: u64) {
+        self.inner.upload_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Record how long a request took
+    pub(crate) fn observe_request(&self, route: &str, method: &str, status: u16, seconds: f64) {
+        let labels = RequestLabels {
+            route: route.to_string(),
+            method: method.to_string(),
+            status,
+        };
+
+        // Lock is never held across an await
+        let mut requests = match self.inner.requests.lock() {
+            Ok(result) => result,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let histogram = requests.entry(labels).or_default();
+        for (bucket, bound) in histogram.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        histogram.sum += seconds;
+        histogram.count += 1;
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub(crate) fn render(&self, connected_endpoints: usize) -> String {
+        let mut text = String::new();
+        let _ = writeln!(
+            text,
+            "# HELP artemis_enrollments_total Endpoints enrolled since the server started\n# TYPE artemis_enrollments_total counter\nartemis_enrollments_total {}",
+            self.inner.enrollments.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            text,
+            "# HELP artemis_connected_endpoints Endpoints connected to the server\n# TYPE artemis_connected_endpoints gauge\nartemis_connected_endpoints {connected_endpoints}"
+        );
+        let _ = writeln!(
+            text,
+            "# HELP artemis_active_collections Collections sent to endpoints that have not finished\n# TYPE artemis_active_collections gauge\nartemis_active_collections {}",
+            self.inner.active_collections.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            text,
+            "# HELP artemis_upload_bytes_total Bytes of collection data uploaded by endpoints\n# TYPE artemis_upload_bytes_total counter\nartemis_upload_bytes_total {}",
+            self.inner.upload_bytes.load(Ordering::Relaxed)
+        );
+
+        let name = "artemis_request_duration_seconds";
+        let _ = writeln!(
+            text,
+            "# HELP {name} Time spent serving requests\n# TYPE {name} histogram"
+        );
+        let requests = match self.inner.requests.lock() {
+            Ok(result) => result,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        for (labels, histogram) in requests.iter() {
+            let RequestLabels {
+                route,
+                method,
+                status,
+            } = labels;
+            let label = format!("route=\"{route}\",method=\"{method}\",status=\"{status}\"");
+            for (count, bound) in histogram.buckets.iter().zip(LATENCY_BUCKETS) {
+                let _ = writeln!(text, "{name}_bucket{{{label},le=\"{bound}\"}} {count}");
+            }
+            let _ = writeln!(
+                text,
+                "{name}_bucket{{{label},le=\"+Inf\"}} {}",
+                histogram.count
+            );
+            let _ = writeln!(text, "{name}_sum{{{label}}} {}", histogram.sum);
+            let _ = writeln!(text, "{name}_count{{{label}}} {}", histogram.count);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Metrics;
+
+    #[test]
+    fn test_metrics_counters() {
+        let metrics = Metrics::default();
+        metrics.enrolled();
+        metrics.collections_started(3);
+        metrics.collection_finished();
+        metrics.uploaded(1024);
+
+        let text = metrics.render(2);
+        assert!(text.contains("artemis_enrollments_total 1\n"));
+        assert!(text.contains("artemis_connected_endpoints 2\n"));
+        assert!(text.contains("artemis_active_collections 2\n"));
+        assert!(text.contains("artemis_upload_bytes_total 1024\n"));
+    }
+
+    #[test]
+    fn test_metrics_collection_finished_floor() {
+        let metrics = Metrics::default();
+        metrics.collection_finished();
+        assert!(metrics.render(0).contains("artemis_active_collections 0\n"));
+    }
+
+    #[test]
+    fn test_observe_request() {
+        let metrics = Metrics::default();
+        let clone = metrics.clone();
+        clone.observe_request("/endpoint/v1/enroll", "POST", 400, 0.02);
+        metrics.observe_request("/endpoint/v1/enroll", "POST", 400, 3.0);
+
+        let text = metrics.render(0);
+        let label = r#"route="/endpoint/v1/enroll",method="POST",status="400""#;
+        assert!(text.contains(&format!(
+            "artemis_request_duration_seconds_bucket{{{label},le=\"0.01\"}} 0\n"
+        )));
+        assert!(text.contains(&format!(
+            "artemis_request_duration_seconds_bucket{{{label},le=\"0.025\"}} 1\n"
+        )));
+        assert!(text.contains(&format!(
+            "artemis_request_duration_seconds_bucket{{{label},le=\"+Inf\"}} 2\n"
+        )));
+        assert!(text.contains(&format!(
+            "artemis_request_duration_seconds_count{{{label}}} 2\n"
+        )));
+    }
+}
@@ -0,0 +1,65 @@
+use super::export::{metrics_export, METRICS_PATH};
+use crate::server::ServerState;
+use axum::{routing::get, Router};
+
+/// Setup the metrics route. The path can be changed with `metrics_path` in the server config
+pub(crate) fn metrics_routes(path: Option<&str>) -> Router<ServerState> {
+    Router::new().route(path.unwrap_or(METRICS_PATH), get(metrics_export))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::metrics_routes;
+    use crate::metrics::registry::Metrics;
+    use crate::utils::events::EventLog;
+    use crate::{server::ServerState, utils::config::read_config};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use std::{collections::HashMap, path::PathBuf, sync::Arc};
+    use tokio::sync::RwLock;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_metrics_routes_custom_path() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
+
+        let app = metrics_routes(Some("/monitoring/metrics")).with_state(server_state);
+        let res = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/monitoring/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = app
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+}
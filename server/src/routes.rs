@@ -1,14 +1,15 @@
 use crate::{
     enrollment::uris::enroll_routes,
     frontend::{uris::setup_webui, webui::webui_assets},
+    metrics::{export::track_requests, uris::metrics_routes},
     server::ServerState,
     socket::uris::socket_routes,
     uploads::uris::upload_routes,
 };
-use axum::{routing::get, Router};
+use axum::{middleware::from_fn_with_state, routing::get, Router};
 
-/// Setup all the server routes
-pub(crate) fn setup_routes() -> Router<ServerState> {
+/// Setup all the server routes. Requests to the endpoint routes are recorded in the server metrics
+pub(crate) fn setup_routes(state: &ServerState) -> Router<ServerState> {
     let mut app = Router::new();
 
     app = app.route("/", get(|| async { "Hello, World!" }));
@@ -16,9 +17,13 @@ pub(crate) fn setup_routes() -> Router<ServerState> {
     let version = "v1";
     let endpoint_base = format!("/endpoint/{version}");
 
-    app = app.merge(enroll_routes(&endpoint_base));
-    app = app.merge(socket_routes(&endpoint_base));
-    app = app.merge(upload_routes(&endpoint_base));
+    let mut endpoint = enroll_routes(&endpoint_base);
+    endpoint = endpoint.merge(socket_routes(&endpoint_base));
+    endpoint = endpoint.merge(upload_routes(&endpoint_base));
+    app = app.merge(endpoint.route_layer(from_fn_with_state(state.clone(), track_requests)));
+    app = app.merge(metrics_routes(
+        state.config.endpoint_server.metrics_path.as_deref(),
+    ));
 
     let webui_base = format!("/ui/{version}");
     app = app.merge(setup_webui(&webui_base));
@@ -30,10 +35,12 @@ pub(crate) fn setup_routes() -> Router<ServerState> {
 #[cfg(test)]
 mod tests {
     use super::setup_routes;
+    use crate::metrics::registry::Metrics;
+    use crate::utils::events::EventLog;
     use crate::{server::ServerState, utils::config::read_config};
     use axum::{
-        body::Body,
-        http::{Request, StatusCode},
+        body::{to_bytes, Body},
+        http::{Method, Request, StatusCode},
     };
     use std::{collections::HashMap, path::PathBuf, sync::Arc};
     use tokio::sync::RwLock;
@@ -49,9 +56,14 @@ mod tests {
             .unwrap();
 
         let command = Arc::new(RwLock::new(HashMap::new()));
-        let server_state = ServerState { config, command };
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
 
-        let app = setup_routes();
+        let app = setup_routes(&server_state);
         let res = app
             .with_state(server_state)
             .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
@@ -60,4 +72,55 @@ mod tests {
 
         assert_eq!(res.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_setup_routes_metrics() {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
+
+        let app = setup_routes(&server_state).with_state(server_state);
+        let res = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/endpoint/v1/enroll")
+                    .header("content-type", "application/json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let res = app
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(!text.is_empty());
+        assert!(text.contains(
+            r#"artemis_request_duration_seconds_count{route="/endpoint/v1/enroll",method="POST",status="400"} 1"#
+        ));
+    }
 }
@@ -1,21 +1,34 @@
 use crate::{
+    metrics::registry::Metrics,
     routes,
-    utils::{config::read_config, filesystem::create_dirs},
+    utils::{config::read_config, events::EventLog, filesystem::create_dirs},
 };
 use axum::extract::ws::Message;
-use common::server::config::ArtemisConfig;
-use log::error;
+use common::server::config::{ArtemisConfig, EndpointServer};
+use log::{error, info, warn};
+use socket2::{Domain, Protocol, Socket, Type};
 use std::{
     collections::HashMap,
+    io::Error,
     net::{IpAddr, Ipv4Addr, SocketAddr},
     sync::Arc,
 };
-use tokio::sync::{mpsc, RwLock};
+use tokio::{
+    net::TcpListener,
+    sync::{mpsc, RwLock},
+};
+
+/// Port the server listens on
+const SERVER_PORT: u16 = 8000;
 
 #[derive(Debug, Clone)]
 pub(crate) struct ServerState {
     pub(crate) config: ArtemisConfig,
     pub(crate) command: Arc<RwLock<HashMap<String, mpsc::Sender<Message>>>>,
+    /**Recent server events streamed to the `WebUI` */
+    pub(crate) events: EventLog,
+    /**Counters and histograms served at the metrics path */
+    pub(crate) metrics: Metrics,
 }
 
 #[tokio::main]
@@ -35,13 +48,7 @@ pub async fn start(path: &str) {
         return;
     }
 
-    let command = Arc::new(RwLock::new(HashMap::new()));
-    let server_state = ServerState { config, command };
-
-    let app = routes::setup_routes().with_state(server_state);
-    let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8000);
-
-    let listen = tokio::net::TcpListener::bind(&address).await;
+    let listen = bind_listener(&config.endpoint_server, SERVER_PORT);
     if listen.is_err() {
         error!(
             "[server] Failed to start artemis server. Could not listen on IP and port {:?}",
@@ -49,6 +56,16 @@ pub async fn start(path: &str) {
         );
         return;
     }
+
+    let command = Arc::new(RwLock::new(HashMap::new()));
+    let server_state = ServerState {
+        config,
+        command,
+        events: EventLog::default(),
+        metrics: Metrics::default(),
+    };
+
+    let app = routes::setup_routes(&server_state).with_state(server_state);
     let status = axum::serve(
         listen.unwrap(),
         app.into_make_service_with_connect_info::<SocketAddr>(),
@@ -63,10 +80,85 @@ pub async fn start(path: &str) {
     }
 }
 
+/// Get the IP address to listen on from the config. Falls back to the IPv4 loopback address if the address is not an IP
+fn bind_address(server: &EndpointServer) -> IpAddr {
+    match server.address.parse() {
+        Ok(result) => result,
+        Err(err) => {
+            warn!(
+                "[server] Config address {} is not an IP address: {err:?}. Using 127.0.0.1",
+                server.address
+            );
+            IpAddr::V4(Ipv4Addr::LOCALHOST)
+        }
+    }
+}
+
+/**
+ * Listen on the configured IPv4 or IPv6 address
+ * If `dual_stack` is enabled an IPv6 address also accepts IPv4 clients, where the platform allows
+ */
+fn bind_listener(server: &EndpointServer, port: u16) -> Result<TcpListener, Error> {
+    let address = SocketAddr::new(bind_address(server), port);
+    let socket = Socket::new(
+        Domain::for_address(address),
+        Type::STREAM,
+        Some(Protocol::TCP),
+    )?;
+
+    let mut family = if address.is_ipv6() { "IPv6" } else { "IPv4" };
+    if server.dual_stack.unwrap_or(false) {
+        if !address.is_ipv6() {
+            warn!("[server] Dual-stack requires an IPv6 address. Listening on {address} only");
+        } else if let Err(err) = socket.set_only_v6(false) {
+            warn!("[server] Platform does not allow dual-stack sockets: {err:?}. Listening on IPv6 only");
+        } else {
+            family = "IPv6 dual-stack";
+        }
+    } else if address.is_ipv6() {
+        // Platforms have different defaults. Only accept IPv6 clients unless dual-stack is enabled
+        socket.set_only_v6(true)?;
+    }
+
+    // Same socket options as tokio::net::TcpListener::bind
+    #[cfg(not(target_os = "windows"))]
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&address.into())?;
+    let backlog = 1024;
+    socket.listen(backlog)?;
+
+    let listener = TcpListener::from_std(socket.into())?;
+    info!(
+        "[server] Listening on {} ({family})",
+        listener.local_addr()?
+    );
+    Ok(listener)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::start;
-    use std::path::PathBuf;
+    use super::{bind_address, bind_listener, start};
+    use common::server::config::EndpointServer;
+    use std::{
+        net::{IpAddr, Ipv4Addr},
+        path::PathBuf,
+    };
+
+    fn endpoint_server(address: &str, dual_stack: Option<bool>) -> EndpointServer {
+        EndpointServer {
+            address: address.to_string(),
+            port: 8443,
+            cert: String::new(),
+            storage: String::from("./tmp"),
+            verify_ssl: true,
+            version: 1,
+            dual_stack,
+            broadcast_concurrency: None,
+            broadcast_timeout_secs: None,
+            metrics_path: None,
+        }
+    }
 
     #[test]
     #[ignore = "Spawns server"]
@@ -76,4 +168,37 @@ mod tests {
         let config_path = test_location.display().to_string();
         start(&config_path)
     }
+
+    #[test]
+    fn test_bind_address() {
+        let result = bind_address(&endpoint_server("::1", None));
+        assert!(result.is_ipv6());
+
+        let result = bind_address(&endpoint_server("artemis.example.com", None));
+        assert_eq!(result, IpAddr::V4(Ipv4Addr::LOCALHOST));
+    }
+
+    #[tokio::test]
+    async fn test_bind_listener_ipv4() {
+        let listener = bind_listener(&endpoint_server("127.0.0.1", None), 0).unwrap();
+        assert!(listener.local_addr().unwrap().is_ipv4());
+    }
+
+    #[tokio::test]
+    async fn test_bind_listener_ipv6() {
+        let listener = bind_listener(&endpoint_server("::1", None), 0).unwrap();
+        let address = listener.local_addr().unwrap();
+        assert!(address.is_ipv6());
+        assert!(address.ip().is_loopback());
+    }
+
+    #[tokio::test]
+    async fn test_bind_listener_dual_stack() {
+        let listener = bind_listener(&endpoint_server("::1", Some(true)), 0).unwrap();
+        assert!(listener.local_addr().unwrap().is_ipv6());
+
+        // IPv4 addresses ignore dual-stack
+        let listener = bind_listener(&endpoint_server("127.0.0.1", Some(true)), 0).unwrap();
+        assert!(listener.local_addr().unwrap().is_ipv4());
+    }
 }
@@ -0,0 +1,130 @@
+use axum::extract::ws::Message;
+use common::server::jobs::BroadcastResult;
+use futures::{stream, StreamExt};
+use log::{error, warn};
+use std::time::Duration;
+use tokio::{sync::mpsc, time::timeout};
+
+/// Default max endpoints sent a command at the same time
+pub(crate) const BROADCAST_CONCURRENCY: usize = 32;
+/// Default seconds to wait when sending a command to a single endpoint
+pub(crate) const BROADCAST_TIMEOUT_SECS: u64 = 5;
+
+enum SendStatus {
+    Succeeded,
+    Failed,
+    TimedOut,
+}
+
+/**
+ * Send a command to multiple endpoints concurrently. At most `concurrency` sends run at the same time
+ * A slow or stuck endpoint only delays its own send. Each send stops waiting after `wait`
+ */
+pub(crate) async fn broadcast(
+    senders: Vec<(String, mpsc::Sender<Message>)>,
+    message: Message,
+    concurrency: usize,
+    wait: Duration,
+) -> BroadcastResult {
+    let statuses: Vec<(String, SendStatus)> = stream::iter(senders)
+        .map(|(id, sender)| {
+            let message = message.clone();
+            async move {
+                let status = match timeout(wait, sender.send(message)).await {
+                    Ok(Ok(())) => SendStatus::Succeeded,
+                    Ok(Err(err)) => {
+                        error!("[server] Could not send broadcast command to {id}: {err:?}");
+                        SendStatus::Failed
+                    }
+                    Err(_) => {
+                        warn!("[server] Timed out sending broadcast command to {id}");
+                        SendStatus::TimedOut
+                    }
+                };
+                (id, status)
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut result = BroadcastResult::default();
+    for (id, status) in statuses {
+        match status {
+            SendStatus::Succeeded => result.succeeded.push(id),
+            SendStatus::Failed => result.failed.push(id),
+            SendStatus::TimedOut => result.timed_out.push(id),
+        }
+    }
+    result.succeeded.sort();
+    result.failed.sort();
+    result.timed_out.sort();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::broadcast;
+    use axum::extract::ws::Message;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_broadcast() {
+        let mut senders = Vec::new();
+        let mut receivers = Vec::new();
+        for id in ["endpoint-1", "endpoint-2", "endpoint-3"] {
+            let (send, recv) = mpsc::channel(5);
+            senders.push((id.to_string(), send));
+            receivers.push(recv);
+        }
+
+        // Channel is full and never read. The send waits until the timeout
+        let (stuck_send, _stuck_recv) = mpsc::channel(1);
+        stuck_send
+            .send(Message::Text(String::from("pending")))
+            .await
+            .unwrap();
+        senders.push((String::from("endpoint-stuck"), stuck_send));
+
+        // Endpoint task has stopped
+        let (closed_send, closed_recv) = mpsc::channel(1);
+        drop(closed_recv);
+        senders.push((String::from("endpoint-closed"), closed_send));
+
+        let result = broadcast(
+            senders,
+            Message::Text(String::from("collect")),
+            2,
+            Duration::from_millis(100),
+        )
+        .await;
+        assert_eq!(
+            result.succeeded,
+            vec!["endpoint-1", "endpoint-2", "endpoint-3"]
+        );
+        assert_eq!(result.failed, vec!["endpoint-closed"]);
+        assert_eq!(result.timed_out, vec!["endpoint-stuck"]);
+
+        for mut recv in receivers {
+            assert_eq!(
+                recv.recv().await.unwrap(),
+                Message::Text(String::from("collect"))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_empty() {
+        let result = broadcast(
+            Vec::new(),
+            Message::Text(String::from("collect")),
+            0,
+            Duration::from_millis(100),
+        )
+        .await;
+        assert!(result.succeeded.is_empty());
+        assert!(result.failed.is_empty());
+        assert!(result.timed_out.is_empty());
+    }
+}
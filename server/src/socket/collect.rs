@@ -0,0 +1,232 @@
+use super::broadcast::{broadcast, BROADCAST_CONCURRENCY, BROADCAST_TIMEOUT_SECS};
+use crate::{frontend::validate::check_toml, server::ServerState, uploads::upload::check_id};
+use axum::{
+    extract::{ws::Message, Path, State},
+    http::StatusCode,
+    Json,
+};
+use common::server::jobs::{BroadcastResult, EndpointCommand};
+use log::{error, info};
+use std::time::Duration;
+
+/**
+ * Send a collection TOML to a connected endpoint
+ * Returns `202` once the command is queued for the endpoint. Returns `404` if the endpoint is not connected
+ */
+pub(crate) async fn run_collection(
+    State(state): State<ServerState>,
+    Path(id): Path<String>,
+    data: String,
+) -> Result<StatusCode, StatusCode> {
+    check_id(&id)?;
+    let command_value = collection_command(data)?;
+
+    let channels = state.command.read().await;
+    let sender = match channels.get(&id) {
+        Some(result) => result,
+        None => return Err(StatusCode::NOT_FOUND),
+    };
+
+    if let Err(err) = sender.send(Message::Text(command_value)).await {
+        // The endpoint task has stopped. The endpoint is no longer connected
+        error!("[server] Could not send collection command to {id}: {err:?}");
+        return Err(StatusCode::NOT_FOUND);
+    }
+    state.metrics.collections_started(1);
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/**
+ * Send a collection TOML to all connected endpoints concurrently
+ * Returns which endpoints were sent the command, disconnected, or timed out
+ */
+pub(crate) async fn run_collection_all(
+    State(state): State<ServerState>,
+    data: String,
+) -> Result<Json<BroadcastResult>, StatusCode> {
+    let command_value = collection_command(data)?;
+
+    // Do not hold the lock while sending. Endpoints may connect or disconnect during the broadcast
+    let senders: Vec<_> = state
+        .command
+        .read()
+        .await
+        .iter()
+        .map(|(id, sender)| (id.clone(), sender.clone()))
+        .collect();
+
+    let server = &state.config.endpoint_server;
+    let concurrency = server
+        .broadcast_concurrency
+        .unwrap_or(BROADCAST_CONCURRENCY);
+    let wait = Duration::from_secs(
+        server
+            .broadcast_timeout_secs
+            .unwrap_or(BROADCAST_TIMEOUT_SECS),
+    );
+    let result = broadcast(senders, Message::Text(command_value), concurrency, wait).await;
+    info!(
+        "[server] Sent collection to {} endpoints. {} failed, {} timed out",
+        result.succeeded.len(),
+        result.failed.len(),
+        result.timed_out.len()
+    );
+    state
+        .metrics
+        .collections_started(result.succeeded.len() as u64);
+
+    Ok(Json(result))
+}
+
+/// Validate the collection TOML and serialize the command sent to endpoints
+fn collection_command(data: String) -> Result<String, StatusCode> {
+    let validation = check_toml(&data);
+    if !validation.errors.is_empty() {
+        error!(
+            "[server] Invalid collection for endpoint: {:?}",
+            validation.errors
+        );
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let command = EndpointCommand::RunCollection { collection: data };
+    let command_result = serde_json::to_string(&command);
+    match command_result {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("[server] Failed to serialize collection command: {err:?}");
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_collection, run_collection_all};
+    use crate::{
+        metrics::registry::Metrics,
+        server::ServerState,
+        utils::{config::read_config, events::EventLog},
+    };
+    use axum::{
+        extract::{ws::Message, Path, State},
+        http::StatusCode,
+    };
+    use common::server::jobs::EndpointCommand;
+    use std::{collections::HashMap, path::PathBuf, sync::Arc};
+    use tokio::sync::{mpsc, RwLock};
+
+    const COLLECTION: &str = r#"system = "windows"
+
+[output]
+name = "prefetch_collection"
+directory = "./tmp"
+format = "json"
+compress = false
+endpoint_id = "abdc"
+collection_id = 1
+output = "local"
+
+[[artifacts]]
+artifact_name = "prefetch"
+"#;
+
+    async fn test_state() -> ServerState {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        ServerState {
+            config,
+            command: Arc::new(RwLock::new(HashMap::new())),
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_collection() {
+        let state = test_state().await;
+        let (client_send, mut client_recv) = mpsc::channel(5);
+        state
+            .command
+            .write()
+            .await
+            .insert(String::from("collect-endpoint"), client_send);
+
+        let status = run_collection(
+            State(state),
+            Path(String::from("collect-endpoint")),
+            COLLECTION.to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::ACCEPTED);
+
+        let message = client_recv.recv().await.unwrap();
+        let command: EndpointCommand = match message {
+            Message::Text(text) => serde_json::from_str(&text).unwrap(),
+            _ => panic!("expected text message"),
+        };
+        assert_eq!(
+            command,
+            EndpointCommand::RunCollection {
+                collection: COLLECTION.to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_collection_bad_toml() {
+        let state = test_state().await;
+        let status = run_collection(
+            State(state),
+            Path(String::from("collect-endpoint")),
+            String::from("not toml ["),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_run_collection_all() {
+        let mut state = test_state().await;
+        state.config.endpoint_server.broadcast_timeout_secs = Some(1);
+
+        let (client_send, mut client_recv) = mpsc::channel(5);
+        let (stuck_send, _stuck_recv) = mpsc::channel(1);
+        stuck_send
+            .send(Message::Text(String::from("pending")))
+            .await
+            .unwrap();
+        {
+            let mut channels = state.command.write().await;
+            channels.insert(String::from("collect-all-endpoint"), client_send);
+            channels.insert(String::from("collect-all-stuck"), stuck_send);
+        }
+
+        let result = run_collection_all(State(state), COLLECTION.to_string())
+            .await
+            .unwrap();
+        assert_eq!(result.succeeded, vec!["collect-all-endpoint"]);
+        assert!(result.failed.is_empty());
+        assert_eq!(result.timed_out, vec!["collect-all-stuck"]);
+
+        let message = client_recv.recv().await.unwrap();
+        assert!(matches!(message, Message::Text(_)));
+    }
+
+    #[tokio::test]
+    async fn test_run_collection_all_bad_toml() {
+        let state = test_state().await;
+        let status = run_collection_all(State(state), String::from("not toml ["))
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
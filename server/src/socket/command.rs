@@ -1,6 +1,10 @@
-use crate::filestore::{endpoints::glob_paths, jobs::save_job};
+use crate::filestore::{
+    endpoints::glob_paths,
+    jobs::save_job,
+    pending::{queue_pending, take_pending},
+};
 use axum::extract::ws::Message;
-use common::server::jobs::{Command, JobType};
+use common::server::jobs::{Command, JobInfo, JobType};
 use log::error;
 use serde_json::Error;
 use std::collections::HashMap;
@@ -38,10 +42,14 @@ pub(crate) async fn parse_command(data: &str, path: &str) -> Result<(), Error> {
     Ok(())
 }
 
-/// Send jobs to client endpoints from server. The data is uploaded via websockets
+/**
+ * Send jobs to client endpoints from server. The data is uploaded via websockets
+ * If an endpoint is not connected the job is queued on disk and delivered when the endpoint reconnects
+ */
 pub(crate) async fn quick_jobs(
     data: &str,
     channels: &RwLockReadGuard<'_, HashMap<String, mpsc::Sender<Message>>>,
+    path: &str,
 ) -> Result<(), Error> {
     let command_result: Result<Command, Error> = serde_json::from_str(data);
     let command = match command_result {
@@ -61,31 +69,79 @@ pub(crate) async fn quick_jobs(
     for target in command.targets {
         // Check if target endpoint ID found in HashMap
         if let Some(sender) = channels.get(&target) {
-            let job_result = serde_json::to_string(&command.job);
-            let job = match job_result {
-                Ok(result) => result,
-                Err(err) => {
-                    error!("[server] Failed to serialize job: {err:?}");
-                    continue;
-                }
-            };
-            // Send job to the async client task. The job will only be sent the associated endpoint ID
-            let result = sender.send(Message::Text(job)).await;
-            if result.is_err() {
-                error!(
-                    "[server] Could not send quick job command: {:?}",
-                    result.unwrap_err()
-                );
+            if send_job(&command.job, sender).await {
+                continue;
             }
         }
+        queue_job(&command.job, &target, path).await;
     }
 
     Ok(())
 }
 
+/// Send any jobs queued while the endpoint was offline. Jobs are sent in the order they were queued
+pub(crate) async fn deliver_pending(sender: &mpsc::Sender<Message>, endpoint_path: &str) {
+    let pending_result = take_pending(endpoint_path).await;
+    let pending = match pending_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[server] Could not get pending jobs at {endpoint_path}: {err:?}");
+            return;
+        }
+    };
+
+    for (index, job) in pending.iter().enumerate() {
+        if send_job(job, sender).await {
+            continue;
+        }
+
+        // Endpoint disconnected again. Queue the remaining jobs for the next connection
+        for remaining in &pending[index..] {
+            if queue_pending(remaining, endpoint_path).await.is_err() {
+                error!("[server] Could not requeue pending job at {endpoint_path}");
+            }
+        }
+        return;
+    }
+}
+
+/// Send a job to the async client task. The job will only be sent the associated endpoint ID
+async fn send_job(job: &JobInfo, sender: &mpsc::Sender<Message>) -> bool {
+    let job_result = serde_json::to_string(job);
+    let job_value = match job_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("[server] Failed to serialize job: {err:?}");
+            // A job that cannot be serialized cannot be delivered later either
+            return true;
+        }
+    };
+
+    if let Err(err) = sender.send(Message::Text(job_value)).await {
+        error!("[server] Could not send quick job command: {err:?}");
+        return false;
+    }
+    true
+}
+
+/// Queue a job for an offline endpoint. The endpoint must be enrolled
+async fn queue_job(job: &JobInfo, target: &str, path: &str) {
+    let glob_path = glob_paths(&format!("{path}/*/{target}")).unwrap_or_default();
+    for endpoint_path in glob_path {
+        let status = queue_pending(job, &endpoint_path.full_path).await;
+        if status.is_err() {
+            error!(
+                "[server] Could not queue job for offline endpoint at {}",
+                endpoint_path.full_path
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::socket::command::{parse_command, quick_jobs};
+    use crate::filestore::pending::get_pending;
+    use crate::socket::command::{deliver_pending, parse_command, quick_jobs};
     use crate::utils::filesystem::create_dirs;
     use axum::extract::ws::Message;
     use std::collections::HashMap;
@@ -128,6 +184,31 @@ mod tests {
 
         let rw = RwLock::new(test);
 
-        quick_jobs(data, &rw.read().await).await.unwrap();
+        quick_jobs(data, &rw.read().await, "./tmp").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_quick_jobs_offline() {
+        let data = r#"{"targets":["5f7a3a37-8f3b-4c4a-9e0e-6b3f3a0c1d2e"],"job":{"id":1,"name":"processes","created":10000,"started":10001,"finished":20000,"status":"NotStarted","collection":"","duration":10,"start_time":100,"action":"Start","job_type":"Processes"}}"#;
+        let path = "./tmp/offline";
+        let endpoint_path = format!("{path}/Linux/5f7a3a37-8f3b-4c4a-9e0e-6b3f3a0c1d2e");
+        create_dirs(&endpoint_path).await.unwrap();
+
+        // Endpoint is not connected so the job is queued
+        let rw = RwLock::new(HashMap::new());
+        quick_jobs(data, &rw.read().await, path).await.unwrap();
+        assert_eq!(get_pending(&endpoint_path).await.unwrap().len(), 1);
+
+        // Endpoint connects and receives the queued job
+        let (client_send, mut client_recv) = mpsc::channel(5);
+        deliver_pending(&client_send, &endpoint_path).await;
+
+        let message = client_recv.recv().await.unwrap();
+        if let Message::Text(job) = message {
+            assert!(job.contains("\"name\":\"processes\""));
+        } else {
+            panic!("expected text message");
+        }
+        assert!(get_pending(&endpoint_path).await.unwrap().is_empty());
     }
 }
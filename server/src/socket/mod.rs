@@ -1,3 +1,5 @@
+mod broadcast;
+mod collect;
 pub(crate) mod command;
 mod heartbeat;
 mod jobs;
@@ -1,15 +1,29 @@
-use super::websocket::socket_connection;
+use super::{
+    collect::{run_collection, run_collection_all},
+    websocket::socket_connection,
+};
 use crate::server::ServerState;
-use axum::{routing::get, Router};
+use axum::{
+    routing::{get, post},
+    Router,
+};
 
 /// Setup `Web Socket` routes
 pub(crate) fn socket_routes(base: &str) -> Router<ServerState> {
-    Router::new().route(&format!("{base}/socket"), get(socket_connection))
+    let mut socket = Router::new().route(&format!("{base}/socket"), get(socket_connection));
+    socket =
+        socket.merge(Router::new().route(&format!("{base}/collect/:id"), post(run_collection)));
+    // Static segment takes priority over the endpoint ID
+    socket =
+        socket.merge(Router::new().route(&format!("{base}/collect/all"), post(run_collection_all)));
+    socket
 }
 
 #[cfg(test)]
 mod tests {
     use super::socket_routes;
+    use crate::metrics::registry::Metrics;
+    use crate::utils::events::EventLog;
     use crate::{server::ServerState, utils::config::read_config};
     use axum::{
         body::Body,
@@ -32,7 +46,12 @@ mod tests {
             .unwrap();
 
         let command = Arc::new(RwLock::new(HashMap::new()));
-        let server_state = ServerState { config, command };
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
 
         let res = route
             .with_state(server_state)
@@ -52,4 +71,105 @@ mod tests {
 
         assert_eq!(res.status(), StatusCode::UPGRADE_REQUIRED);
     }
+
+    #[tokio::test]
+    async fn test_socket_routes_collect_unknown() {
+        let base = "/endpoint/v1";
+        let route = socket_routes(base);
+
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
+
+        let collection = r#"system = "windows"
+
+[output]
+name = "prefetch_collection"
+directory = "./tmp"
+format = "json"
+compress = false
+endpoint_id = "abdc"
+collection_id = 1
+output = "local"
+
+[[artifacts]]
+artifact_name = "prefetch"
+"#;
+
+        let res = route
+            .with_state(server_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!("{base}/collect/unknown-endpoint"))
+                    .body(Body::from(collection))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_socket_routes_collect_all() {
+        let base = "/endpoint/v1";
+        let route = socket_routes(base);
+
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
+
+        let collection = r#"system = "windows"
+
+[output]
+name = "prefetch_collection"
+directory = "./tmp"
+format = "json"
+compress = false
+endpoint_id = "abdc"
+collection_id = 1
+output = "local"
+
+[[artifacts]]
+artifact_name = "prefetch"
+"#;
+
+        // No endpoints are connected. Nothing is sent
+        let res = route
+            .with_state(server_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(format!("{base}/collect/all"))
+                    .body(Body::from(collection))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+    }
 }
@@ -4,10 +4,13 @@ use super::jobs::parse_job;
 use crate::enrollment::enroll::verify_enrollment;
 use crate::filestore::jobs::get_jobs;
 use crate::server::ServerState;
-use crate::socket::command::quick_jobs;
+use crate::socket::command::{deliver_pending, quick_jobs};
+use crate::utils::events::EventLog;
 use axum::extract::ws::{Message, WebSocket};
 use axum::extract::{ConnectInfo, State, WebSocketUpgrade};
 use axum::response::IntoResponse;
+use common::server::jobs::CollectionEventMessage;
+use common::server::webui::LogLevel;
 use futures::{SinkExt, StreamExt};
 use log::{error, warn};
 use std::collections::HashMap;
@@ -28,9 +31,11 @@ pub(crate) async fn socket_connection(
 async fn handle_socket(socket: WebSocket, addr: SocketAddr, state: ServerState) {
     let (mut sender, mut receiver) = socket.split();
     let storage_path = state.config.endpoint_server.storage.clone();
+    let events = state.events.clone();
 
     let mut message_source = MessageSource::None;
     let mut id = String::new();
+    let mut platform = String::new();
     /*
      * When a system first connects over websockets we need to determine source of message:
      * 1. MessageSource::Client - Socket connection is remote system
@@ -40,7 +45,7 @@ async fn handle_socket(socket: WebSocket, addr: SocketAddr, state: ServerState)
      *  MessageSource::Server will return the entire message as the `socket_message`
      */
     while let Some(Ok(message)) = receiver.next().await {
-        let control = parse_message(&message, &addr, &storage_path).await;
+        let control = parse_message(&message, &addr, &storage_path, &events).await;
         if control.is_break() {
             break;
         }
@@ -48,6 +53,7 @@ async fn handle_socket(socket: WebSocket, addr: SocketAddr, state: ServerState)
         if let Continue(socket_message) = control {
             message_source = socket_message.source;
             id = socket_message.id;
+            platform = socket_message.platform;
             break;
         }
     }
@@ -57,6 +63,13 @@ async fn handle_socket(socket: WebSocket, addr: SocketAddr, state: ServerState)
         return;
     }
 
+    // The first Server message is a command. Send it now so it is not lost
+    if message_source == MessageSource::Server {
+        if let Err(err) = quick_jobs(&id, &state.command.read().await, &storage_path).await {
+            error!("[server] Could not issue quick job command: {err:?}");
+        }
+    }
+
     /*
      * Register new endpoint clients and use ID to track channels to send websocket commands from server
      * When client system first checks in setup an async task and channel.
@@ -79,6 +92,9 @@ async fn handle_socket(socket: WebSocket, addr: SocketAddr, state: ServerState)
             }
         });
 
+        // Deliver any jobs that were queued while the endpoint was offline
+        deliver_pending(&client_send, &format!("{storage_path}/{platform}/{id}")).await;
+
         // Register sender associated with endpoint client. Tracked via Endpoint ID
         state.command.write().await.insert(id.clone(), client_send);
     }
@@ -93,10 +109,13 @@ async fn handle_socket(socket: WebSocket, addr: SocketAddr, state: ServerState)
     let _recv_task = tokio::spawn(async move {
         while let Some(Ok(message)) = receiver.next().await {
             // Parse the websocket data
-            let control = parse_message(&message, &addr, &storage_path).await;
+            let control = parse_message(&message, &addr, &storage_path, &events).await;
             // If the client disconnects from us, we need to remove from our tracker. We can no longer send commands from server
             if control.is_break() {
                 state.command.write().await.remove(&id);
+                events
+                    .push(LogLevel::Warn, &id, "Endpoint disconnected")
+                    .await;
                 break;
             }
 
@@ -107,8 +126,12 @@ async fn handle_socket(socket: WebSocket, addr: SocketAddr, state: ServerState)
 
                 // If the source is the Server then the socket_data contains a command to be sent the client
                 if socket_message.source == MessageSource::Server {
-                    let send_result =
-                        quick_jobs(&socket_message.id, &state.command.read().await).await;
+                    let send_result = quick_jobs(
+                        &socket_message.id,
+                        &state.command.read().await,
+                        &storage_path,
+                    )
+                    .await;
                     if send_result.is_err() {
                         error!(
                             "[server] Could not issue quick job command: {:?}",
@@ -183,11 +206,12 @@ struct SocketMessage {
     source: MessageSource,
 }
 
-/// Parse websocket message. Currently messages are either Server messages (commands) or client messages (heartbeat)
+/// Parse websocket message. Currently messages are either Server messages (commands) or client messages (heartbeat, jobs, collection events)
 async fn parse_message(
     message: &Message,
     addr: &SocketAddr,
     path: &str,
+    events: &EventLog,
 ) -> ControlFlow<(), SocketMessage> {
     let ip = addr.ip().to_string();
     let mut socket_message = SocketMessage {
@@ -207,6 +231,7 @@ async fn parse_message(
                     return ControlFlow::Break(());
                 }
                 socket_message.source = MessageSource::Server;
+                socket_message.id = data.to_string();
                 // Send the command the to targets
                 return ControlFlow::Continue(socket_message);
             }
@@ -217,8 +242,29 @@ async fn parse_message(
                         "[server] Could not parse the job result: {:?}",
                         job.unwrap_err()
                     );
+                    events
+                        .push(LogLevel::Error, &ip, "Could not parse job result")
+                        .await;
                     return ControlFlow::Break(());
                 }
+                events
+                    .push(LogLevel::Info, &ip, "Received job result")
+                    .await;
+                return ControlFlow::Continue(socket_message);
+            }
+            if data.contains("\"collection_event\":") {
+                let event_result: Result<CollectionEventMessage, serde_json::Error> =
+                    serde_json::from_str(data);
+                match event_result {
+                    Ok(result) => {
+                        events
+                            .push_collection(&result.endpoint_id, &result.collection_event)
+                            .await;
+                    }
+                    Err(err) => {
+                        error!("[server] Could not parse collection event from {ip}: {err:?}");
+                    }
+                }
                 return ControlFlow::Continue(socket_message);
             }
 
@@ -252,6 +298,8 @@ mod tests {
     use super::parse_message;
     use crate::socket::websocket::Message::Text;
     use crate::socket::websocket::MessageSource;
+    use crate::utils::events::EventLog;
+    use common::server::webui::LogLevel;
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
     use std::ops::ControlFlow::Continue;
     use std::path::PathBuf;
@@ -267,10 +315,30 @@ mod tests {
         test_location.push("tests/test_data");
         let path = test_location.display().to_string();
 
-        let control = parse_message(&message, &address, &path).await;
+        let control = parse_message(&message, &address, &path, &EventLog::default()).await;
         if let Continue(socket_message) = control {
             assert_eq!(socket_message.id, "3482136c-3176-4272-9bd7-b79f025307d6");
             assert_eq!(socket_message.source, MessageSource::Client)
         }
     }
+
+    #[tokio::test]
+    async fn test_parse_message_collection_event() {
+        let message = Text(String::from(
+            r#"{"endpoint_id":"3482136c-3176-4272-9bd7-b79f025307d6","collection_event":{"ArtifactFinished":{"name":"processes"}}}"#,
+        ));
+        let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8000);
+        let events = EventLog::default();
+
+        let control = parse_message(&message, &address, "./tmp", &events).await;
+        if let Continue(socket_message) = control {
+            assert_eq!(socket_message.source, MessageSource::None)
+        }
+
+        let (recent, _) = events.subscribe().await;
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].level, LogLevel::Info);
+        assert_eq!(recent[0].source, "3482136c-3176-4272-9bd7-b79f025307d6");
+        assert_eq!(recent[0].message, "Finished artifact processes");
+    }
 }
@@ -0,0 +1,165 @@
+use super::upload::check_id;
+use crate::{
+    server::ServerState,
+    utils::filesystem::{is_directory, read_file},
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use log::error;
+use std::collections::BTreeMap;
+use tokio::fs::read_dir;
+
+/// List the collections uploaded by an endpoint. Returns a map of collection IDs to filenames
+pub(crate) async fn list_collections(
+    State(state): State<ServerState>,
+    Path(endpoint_id): Path<String>,
+) -> Result<Json<BTreeMap<String, Vec<String>>>, StatusCode> {
+    check_id(&endpoint_id)?;
+    let endpoint_dir = format!("{}/{endpoint_id}", state.config.endpoint_server.storage);
+    if !is_directory(&endpoint_dir) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let mut collections = BTreeMap::new();
+    let mut dirs = read_dir(&endpoint_dir).await.map_err(|err| {
+        error!("[server] Could not read endpoint directory {endpoint_dir}: {err:?}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    while let Ok(Some(entry)) = dirs.next_entry().await {
+        if !entry.path().is_dir() {
+            continue;
+        }
+        let collection_id = entry.file_name().to_string_lossy().to_string();
+
+        let mut files = Vec::new();
+        if let Ok(mut entries) = read_dir(entry.path()).await {
+            while let Ok(Some(file)) = entries.next_entry().await {
+                if file.path().is_file() {
+                    files.push(file.file_name().to_string_lossy().to_string());
+                }
+            }
+        }
+        files.sort();
+        collections.insert(collection_id, files);
+    }
+
+    Ok(Json(collections))
+}
+
+/// Download a single collection file uploaded by an endpoint
+pub(crate) async fn download_collection(
+    State(state): State<ServerState>,
+    Path((endpoint_id, collection_id, filename)): Path<(String, String, String)>,
+) -> Result<Vec<u8>, StatusCode> {
+    check_id(&endpoint_id)?;
+    check_id(&collection_id)?;
+    check_id(&filename)?;
+
+    let path = format!(
+        "{}/{endpoint_id}/{collection_id}/{filename}",
+        state.config.endpoint_server.storage
+    );
+
+    let data_result = read_file(&path).await;
+    match data_result {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("[server] Could not read collection {path}: {err:?}");
+            Err(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{download_collection, list_collections};
+    use crate::metrics::registry::Metrics;
+    use crate::utils::events::EventLog;
+    use crate::{
+        server::ServerState,
+        utils::{config::read_config, filesystem::create_dirs, filesystem::write_file},
+    };
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+    };
+    use std::{collections::HashMap, path::PathBuf, sync::Arc};
+    use tokio::sync::RwLock;
+
+    async fn server_state() -> ServerState {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_collections() {
+        let state = server_state().await;
+        let dir = format!(
+            "{}/collections-test-endpoint/2",
+            state.config.endpoint_server.storage
+        );
+        create_dirs(&dir).await.unwrap();
+        write_file(b"test", &format!("{dir}/test.jsonl"), false)
+            .await
+            .unwrap();
+
+        let result = list_collections(
+            State(state),
+            Path(String::from("collections-test-endpoint")),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.get("2").unwrap(), &vec![String::from("test.jsonl")]);
+    }
+
+    #[tokio::test]
+    async fn test_download_collection() {
+        let state = server_state().await;
+        let dir = format!(
+            "{}/collections-test-download/3",
+            state.config.endpoint_server.storage
+        );
+        create_dirs(&dir).await.unwrap();
+        write_file(b"test", &format!("{dir}/test.jsonl"), false)
+            .await
+            .unwrap();
+
+        let result = download_collection(
+            State(state),
+            Path((
+                String::from("collections-test-download"),
+                String::from("3"),
+                String::from("test.jsonl"),
+            )),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result, b"test");
+    }
+
+    #[tokio::test]
+    async fn test_list_collections_traversal() {
+        let state = server_state().await;
+        let result = list_collections(State(state), Path(String::from("../../etc"))).await;
+
+        assert_eq!(result.unwrap_err(), StatusCode::BAD_REQUEST);
+    }
+}
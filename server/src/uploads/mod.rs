@@ -1,2 +1,4 @@
+pub(crate) mod collections;
+pub(crate) mod reparse;
 pub(crate) mod upload;
 pub(crate) mod uris;
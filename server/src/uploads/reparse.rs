@@ -0,0 +1,244 @@
+use super::upload::check_id;
+use crate::{server::ServerState, utils::filesystem::is_directory};
+use artemis_core::core::parse_toml_from_str;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use common::server::uploads::{ReparseRequest, ReparseResult};
+use log::{error, info};
+use tokio::fs::{read_dir, remove_dir_all};
+
+/// Windows artifacts that can be parsed from raw files stored in a collection directory
+const REPARSE_ARTIFACTS: [&str; 2] = ["prefetch", "wmipersist"];
+
+/**
+ * Parse the raw files stored in a collection with the parsers in this version of artemis
+ * Output is written to `<storage>/<endpoint_id>/<collection_id>/reparsed_<artifact>/`. Output from a previous re-parse is replaced
+ */
+pub(crate) async fn reparse_collection(
+    State(state): State<ServerState>,
+    Path((endpoint_id, collection_id)): Path<(String, String)>,
+    Json(request): Json<ReparseRequest>,
+) -> Result<Json<ReparseResult>, StatusCode> {
+    check_id(&endpoint_id)?;
+    check_id(&collection_id)?;
+    if !REPARSE_ARTIFACTS.contains(&request.artifact.as_str()) {
+        error!(
+            "[server] Cannot re-parse unsupported artifact {}",
+            request.artifact
+        );
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let collection_dir = format!(
+        "{}/{endpoint_id}/{collection_id}",
+        state.config.endpoint_server.storage
+    );
+    if !is_directory(&collection_dir) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let name = format!("reparsed_{}", request.artifact);
+    let output_dir = format!("{collection_dir}/{name}");
+    if is_directory(&output_dir) {
+        if let Err(err) = remove_dir_all(&output_dir).await {
+            error!("[server] Could not remove previous re-parse output {output_dir}: {err:?}");
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+
+    let toml = reparse_toml(
+        &request.artifact,
+        &endpoint_id,
+        &collection_id,
+        &collection_dir,
+    )?;
+    let status = tokio::task::spawn_blocking(move || parse_toml_from_str(&toml)).await;
+    match status {
+        Ok(Ok(_)) => {}
+        Ok(Err(err)) => {
+            error!(
+                "[server] Could not re-parse {} for {collection_dir}: {err:?}",
+                request.artifact
+            );
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Err(err) => {
+            error!("[server] Re-parse task for {collection_dir} failed: {err:?}");
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+    info!(
+        "[server] Re-parsed {} for {collection_dir}",
+        request.artifact
+    );
+
+    let mut files = Vec::new();
+    if let Ok(mut entries) = read_dir(&output_dir).await {
+        while let Ok(Some(file)) = entries.next_entry().await {
+            if file.path().is_file() {
+                files.push(format!("{name}/{}", file.file_name().to_string_lossy()));
+            }
+        }
+    }
+    files.sort();
+
+    Ok(Json(ReparseResult {
+        artifact: request.artifact,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        files,
+    }))
+}
+
+/// Create a TOML collection that parses the raw files in the collection directory
+fn reparse_toml(
+    artifact: &str,
+    endpoint_id: &str,
+    collection_id: &str,
+    collection_dir: &str,
+) -> Result<String, StatusCode> {
+    // Paths are written as TOML literal strings
+    if collection_dir.contains('\'') {
+        error!("[server] Cannot re-parse collection at unsupported path {collection_dir}");
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    Ok(format!(
+        r#"system = "windows"
+
+[output]
+name = "reparsed_{artifact}"
+directory = '{collection_dir}'
+format = "jsonl"
+compress = false
+endpoint_id = "{endpoint_id}"
+collection_id = {}
+output = "local"
+
+[[artifacts]]
+artifact_name = "{artifact}"
+[artifacts.{artifact}]
+alt_dir = '{collection_dir}'
+"#,
+        collection_id.parse::<u64>().unwrap_or_default()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reparse_collection, reparse_toml};
+    use crate::metrics::registry::Metrics;
+    use crate::utils::events::EventLog;
+    use crate::{
+        server::ServerState,
+        utils::{
+            config::read_config,
+            filesystem::{create_dirs, read_file, write_file},
+        },
+    };
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        Json,
+    };
+    use common::server::uploads::ReparseRequest;
+    use std::{collections::HashMap, path::PathBuf, sync::Arc};
+    use tokio::sync::RwLock;
+
+    async fn server_state() -> ServerState {
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reparse_collection_prefetch() {
+        let state = server_state().await;
+        let dir = format!(
+            "{}/reparse-test-endpoint/4",
+            state.config.endpoint_server.storage
+        );
+        create_dirs(&dir).await.unwrap();
+
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/prefetch/DLLHOST.EXE-766398D2.pf");
+        let data = read_file(&test_location.display().to_string())
+            .await
+            .unwrap();
+        write_file(&data, &format!("{dir}/DLLHOST.EXE-766398D2.pf"), false)
+            .await
+            .unwrap();
+
+        let result = reparse_collection(
+            State(state),
+            Path((String::from("reparse-test-endpoint"), String::from("4"))),
+            Json(ReparseRequest {
+                artifact: String::from("prefetch"),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.artifact, "prefetch");
+        assert_eq!(result.version, env!("CARGO_PKG_VERSION"));
+
+        let output = result
+            .files
+            .iter()
+            .find(|file| file.ends_with(".jsonl"))
+            .unwrap();
+        let data = read_file(&format!("{dir}/{output}")).await.unwrap();
+        let text = String::from_utf8(data).unwrap();
+        assert!(text.contains("DLLHOST.EXE"));
+    }
+
+    #[tokio::test]
+    async fn test_reparse_collection_unsupported() {
+        let state = server_state().await;
+        let result = reparse_collection(
+            State(state),
+            Path((String::from("reparse-test-endpoint"), String::from("4"))),
+            Json(ReparseRequest {
+                artifact: String::from("processes"),
+            }),
+        )
+        .await;
+        assert_eq!(result.unwrap_err(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_reparse_collection_missing() {
+        let state = server_state().await;
+        let result = reparse_collection(
+            State(state),
+            Path((String::from("reparse-missing-endpoint"), String::from("1"))),
+            Json(ReparseRequest {
+                artifact: String::from("prefetch"),
+            }),
+        )
+        .await;
+        assert_eq!(result.unwrap_err(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_reparse_toml() {
+        let toml = reparse_toml("prefetch", "abcd", "10", "./tmp/abcd/10").unwrap();
+        assert!(toml.contains("alt_dir = './tmp/abcd/10'"));
+        assert!(toml.contains("collection_id = 10"));
+        assert!(toml.contains("[artifacts.prefetch]"));
+
+        assert!(reparse_toml("prefetch", "abcd", "10", "./tmp/it's").is_err());
+    }
+}
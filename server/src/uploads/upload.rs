@@ -1,24 +1,41 @@
 use crate::{
-    filestore::jobs::update_job,
+    filestore::{
+        jobs::update_job,
+        uploads::{record_manifest, upload_received},
+    },
     server::ServerState,
     utils::{
-        filesystem::{create_dirs, write_file},
+        filesystem::{create_dirs, is_safe_name, write_file},
         uuid::generate_uuid,
     },
 };
 use axum::{
     extract::{Multipart, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
+};
+use common::server::{
+    jobs::{JobInfo, Status},
+    uploads::{ManifestEntry, CONTENT_HASH_HEADER},
 };
-use common::server::jobs::JobInfo;
-use log::{error, warn};
+use log::{error, info, warn};
+use sha2::{Digest, Sha256};
+use std::path::Path;
 
-/// Process uploaded data
+/**
+ * Process uploaded data. Collections are stored at `<storage>/<endpoint_id>/<collection_id>/`
+ * Each `collection` part may set its own `x-artemis-content-hash` header. The request header only applies to a single `collection` part
+ * If the hash matches a file in the collection `manifest.json` the upload is skipped
+ * Otherwise the received file must match the hash or the upload is rejected with `422`. Hashes are recorded in the collection `manifest.json`
+ */
 pub(crate) async fn upload_collection(
     State(state): State<ServerState>,
+    headers: HeaderMap,
     mut multipart: Multipart,
 ) -> Result<(), StatusCode> {
+    let request_hash = upload_hash(&headers)?;
+    let mut request_hash_used = false;
     let mut endpoint_id = String::new();
+    let mut collection_id = String::new();
     let path = state.config.endpoint_server.storage;
 
     while let Some(field) = multipart.next_field().await.unwrap() {
@@ -26,29 +43,139 @@ pub(crate) async fn upload_collection(
 
         if name == "endpoint-id" {
             endpoint_id = field.text().await.unwrap_or_default();
+            check_id(&endpoint_id)?;
+        } else if name == "collection-id" {
+            collection_id = field.text().await.unwrap_or_default();
+            check_id(&collection_id)?;
         } else if name == "job-info" {
+            check_id(&endpoint_id)?;
             let data = field.text().await.unwrap_or_default();
             let endpoint_path = format!("{path}/{endpoint_id}");
-            update_job_file(&endpoint_path, &data).await?;
+            let job = update_job_file(&endpoint_path, &data).await?;
+            if matches!(
+                job.status,
+                Status::Finished | Status::Failed | Status::Cancelled
+            ) {
+                state.metrics.collection_finished();
+            }
+            // Use the Job ID if the collection ID was not provided
+            if collection_id.is_empty() {
+                collection_id = job.id.to_string();
+            }
         } else if name == "collection" {
+            check_id(&endpoint_id)?;
+            check_id(&collection_id)?;
+
             let filename_option = field.file_name();
             let filename = if let Some(result) = filename_option {
-                result.to_string()
+                // Only keep the filename component of the uploaded name
+                let name = Path::new(result)
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_str()
+                    .unwrap_or_default();
+                if !is_safe_name(name) || is_reserved_name(name) {
+                    error!("[server] Received unsafe collection filename: {result}");
+                    return Err(StatusCode::BAD_REQUEST);
+                }
+                name.to_string()
             } else {
                 warn!("[server] Filename not provided in upload. Generated a random one!");
                 format!("{}.jsonl.gz", generate_uuid())
             };
 
+            let content_hash = match upload_hash(field.headers())? {
+                Some(result) => Some(result),
+                None if request_hash.is_some() => {
+                    // The request hash cannot describe more than one file
+                    if request_hash_used {
+                        error!("[server] Received multiple collection files from {endpoint_id} with a single content hash");
+                        return Err(StatusCode::BAD_REQUEST);
+                    }
+                    request_hash_used = true;
+                    request_hash.clone()
+                }
+                None => None,
+            };
+
+            let collection_dir = format!("{path}/{endpoint_id}/{collection_id}");
+            if let Some(hash) = &content_hash {
+                if upload_received(&collection_dir, hash)
+                    .await
+                    .unwrap_or(false)
+                {
+                    info!("[server] Already received {filename} for {endpoint_id}. Skipping duplicate upload");
+                    continue;
+                }
+            }
+
             let data = field.bytes().await.unwrap_or_default();
-            let endpoint_dir = format!("{path}/{endpoint_id}");
-            write_collection(&endpoint_dir, &filename, &data).await?;
+            let sha256 = data_hash(&data);
+            if let Some(hash) = &content_hash {
+                if hash != &sha256 {
+                    error!("[server] Upload {filename} from {endpoint_id} does not match content hash {hash}. Received {sha256}");
+                    return Err(StatusCode::UNPROCESSABLE_ENTITY);
+                }
+            }
+
+            write_collection(&collection_dir, &filename, &data).await?;
+            let entry = ManifestEntry {
+                sha256,
+                size: data.len() as u64,
+                verified: content_hash.is_some(),
+            };
+            if let Err(err) = record_manifest(&collection_dir, &filename, entry).await {
+                error!("[server] Could not record {filename} in collection manifest for {endpoint_id}: {err:?}");
+            }
+            state.metrics.uploaded(data.len() as u64);
         }
     }
     Ok(())
 }
 
+/// Verify a client provided ID can be safely used as a directory name
+pub(crate) fn check_id(id: &str) -> Result<(), StatusCode> {
+    if !is_safe_name(id) {
+        error!("[server] Received invalid ID in request: {id}");
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    Ok(())
+}
+
+/// Check if an uploaded filename would overwrite the collection metadata. Compressed uploads are written without the `.gz` extension
+fn is_reserved_name(name: &str) -> bool {
+    let reserved = ["manifest.json"];
+    let decom_name = name.trim_end_matches(".gz");
+    reserved
+        .iter()
+        .any(|entry| entry.eq_ignore_ascii_case(name) || entry.eq_ignore_ascii_case(decom_name))
+}
+
+/// Get the optional content hash of the upload. The hash must be a hex encoded SHA256 hash
+fn upload_hash(headers: &HeaderMap) -> Result<Option<String>, StatusCode> {
+    let value = match headers.get(CONTENT_HASH_HEADER) {
+        Some(result) => result.to_str().unwrap_or_default(),
+        None => return Ok(None),
+    };
+
+    let sha256_size = 64;
+    if value.len() != sha256_size || !value.chars().all(|char| char.is_ascii_hexdigit()) {
+        error!("[server] Received invalid upload content hash: {value}");
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(Some(value.to_lowercase()))
+}
+
+/// Get the hex encoded SHA256 hash of the received file
+fn data_hash(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
 /// Update the Job DB using the uploaded job-info data
-async fn update_job_file(path: &str, data: &str) -> Result<(), StatusCode> {
+async fn update_job_file(path: &str, data: &str) -> Result<JobInfo, StatusCode> {
     if path.is_empty() {
         error!("[server] No endpoint path provided cannot update jobs.json");
         return Err(StatusCode::BAD_REQUEST);
@@ -72,18 +199,16 @@ async fn update_job_file(path: &str, data: &str) -> Result<(), StatusCode> {
         return Err(StatusCode::BAD_REQUEST);
     }
 
-    Ok(())
+    Ok(job)
 }
 
-/// Write data to endpoint storage directory
+/// Write data to the endpoint collection directory
 async fn write_collection(
-    endpoint_dir: &str,
+    collections: &str,
     filename: &str,
     data: &[u8],
 ) -> Result<(), StatusCode> {
-    // Endpoint storage directory should have been created upon enrollment. But check in case
-    let collections = format!("{endpoint_dir}/collections");
-    let status = create_dirs(&collections).await;
+    let status = create_dirs(collections).await;
     if status.is_err() {
         error!(
             "[server] Could not create {collections} storage directory: {:?}",
@@ -130,12 +255,16 @@ async fn write_collection(
 mod tests {
     use common::server::jobs::{Action, JobInfo, JobType, Status};
 
-    use crate::uploads::upload::write_collection;
+    use crate::uploads::upload::{
+        check_id, data_hash, is_reserved_name, upload_hash, write_collection,
+    };
     use crate::utils::filesystem::{create_dirs, write_file};
     use crate::{
         uploads::upload::update_job_file,
         utils::{config::read_config, uuid::generate_uuid},
     };
+    use axum::http::{HeaderMap, HeaderValue};
+    use common::server::uploads::CONTENT_HASH_HEADER;
     use std::collections::HashMap;
     use std::path::PathBuf;
 
@@ -187,7 +316,7 @@ mod tests {
             .unwrap();
         let endpoint_id = generate_uuid();
 
-        let path = format!("{}/{endpoint_id}", config.endpoint_server.storage);
+        let path = format!("{}/{endpoint_id}/1", config.endpoint_server.storage);
         let filename = "test.jsonl.gz";
         let data = [
             31, 139, 8, 0, 89, 135, 7, 101, 0, 255, 5, 128, 177, 9, 0, 32, 16, 3, 87, 209, 27, 195,
@@ -197,4 +326,65 @@ mod tests {
 
         write_collection(&path, filename, &data).await.unwrap();
     }
+
+    #[test]
+    fn test_check_id() {
+        check_id("0998b365-b60d-4c0c-a629-f631afa83d2c").unwrap();
+        check_id("1").unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "400")]
+    fn test_check_id_traversal() {
+        check_id("../../etc").unwrap();
+    }
+
+    #[test]
+    fn test_upload_hash() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(upload_hash(&headers).unwrap(), None);
+
+        headers.insert(
+            CONTENT_HASH_HEADER,
+            HeaderValue::from_static(
+                "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08",
+            ),
+        );
+        assert_eq!(
+            upload_hash(&headers).unwrap().unwrap(),
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "400")]
+    fn test_upload_hash_short() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_HASH_HEADER, HeaderValue::from_static("abcd1234"));
+        upload_hash(&headers).unwrap();
+    }
+
+    #[test]
+    fn test_is_reserved_name() {
+        assert!(is_reserved_name("manifest.json"));
+        assert!(is_reserved_name("Manifest.JSON"));
+        assert!(is_reserved_name("manifest.json.gz"));
+        assert!(!is_reserved_name("processes.jsonl.gz"));
+    }
+
+    #[test]
+    #[should_panic(expected = "400")]
+    fn test_upload_hash_invalid() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_HASH_HEADER, HeaderValue::from_static("../etc"));
+        upload_hash(&headers).unwrap();
+    }
+
+    #[test]
+    fn test_data_hash() {
+        assert_eq!(
+            data_hash(b"test"),
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        );
+    }
 }
@@ -1,20 +1,48 @@
-use super::upload::upload_collection;
+use super::{
+    collections::{download_collection, list_collections},
+    reparse::reparse_collection,
+    upload::upload_collection,
+};
 use crate::server::ServerState;
-use axum::{routing::post, Router};
+use axum::{
+    routing::{get, post},
+    Router,
+};
 
 /// Setup upload routes
 pub(crate) fn upload_routes(base: &str) -> Router<ServerState> {
-    Router::new().route(&format!("{base}/upload"), post(upload_collection))
+    let mut upload = Router::new().route(&format!("{base}/upload"), post(upload_collection));
+    upload = upload.merge(Router::new().route(
+        &format!("{base}/collections/:endpoint_id"),
+        get(list_collections),
+    ));
+    upload = upload.merge(Router::new().route(
+        &format!("{base}/collections/:endpoint_id/:collection_id/:filename"),
+        get(download_collection),
+    ));
+    upload = upload.merge(Router::new().route(
+        &format!("{base}/reparse/:endpoint_id/:collection_id"),
+        post(reparse_collection),
+    ));
+    upload
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::metrics::registry::Metrics;
+    use crate::utils::events::EventLog;
     use crate::{server::ServerState, uploads::uris::upload_routes, utils::config::read_config};
     use axum::{
         body::Body,
         http::{Method, Request, StatusCode},
     };
-    use std::{collections::HashMap, path::PathBuf, sync::Arc};
+    use common::server::uploads::CONTENT_HASH_HEADER;
+    use std::{
+        collections::HashMap,
+        fs::{read_to_string, remove_file},
+        path::PathBuf,
+        sync::Arc,
+    };
     use tokio::sync::RwLock;
     use tower::util::ServiceExt;
 
@@ -31,7 +59,12 @@ mod tests {
             .unwrap();
 
         let command = Arc::new(RwLock::new(HashMap::new()));
-        let server_state = ServerState { config, command };
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
 
         let res = route
             .with_state(server_state)
@@ -47,4 +80,243 @@ mod tests {
 
         assert_eq!(res.status(), StatusCode::BAD_REQUEST);
     }
+
+    #[tokio::test]
+    async fn test_collections_route_traversal() {
+        let base = "/endpoint/v1";
+        let route = upload_routes(base);
+
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
+
+        let res = route
+            .with_state(server_state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!("{base}/collections/..%2F..%2Fetc"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    /// SHA256 hash of the collection file in `upload_request`
+    const UPLOAD_HASH: &str = "1da06016289bd76a5ada4f52fc805ae0c394612f17ec6d0f0c29b636473c8a9d";
+
+    /// Upload request containing a single collection file
+    fn upload_request(base: &str, endpoint_id: &str, hash: &str) -> Request<Body> {
+        upload_request_data(base, endpoint_id, hash, r#"{"test":1}"#)
+    }
+
+    /// Upload request containing a single collection file with the provided data
+    fn upload_request_data(base: &str, endpoint_id: &str, hash: &str, data: &str) -> Request<Body> {
+        let boundary = "artemis-test";
+        let body = format!("--{boundary}\r\nContent-Disposition: form-data; name=\"endpoint-id\"\r\n\r\n{endpoint_id}\r\n--{boundary}\r\nContent-Disposition: form-data; name=\"collection-id\"\r\n\r\n1\r\n--{boundary}\r\nContent-Disposition: form-data; name=\"collection\"; filename=\"duplicate.jsonl\"\r\n\r\n{data}\r\n--{boundary}--\r\n");
+
+        Request::builder()
+            .method(Method::POST)
+            .uri(format!("{base}/upload"))
+            .header(
+                "content-type",
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .header(CONTENT_HASH_HEADER, hash)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_upload_routes_duplicate() {
+        let base = "/endpoint/v1";
+        let route = upload_routes(base);
+
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+        let endpoint_id = "duplicate-upload-endpoint";
+        let endpoint_path = format!("{}/{endpoint_id}", config.endpoint_server.storage);
+        let _ = remove_file(format!("{endpoint_path}/1/manifest.json"));
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
+        let app = route.with_state(server_state);
+
+        let res = app
+            .clone()
+            .oneshot(upload_request(base, endpoint_id, UPLOAD_HASH))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let collection = format!("{endpoint_path}/1/duplicate.jsonl");
+        remove_file(&collection).unwrap();
+
+        // Same content hash is a no-op. The file is not written again
+        let res = app
+            .oneshot(upload_request(base, endpoint_id, UPLOAD_HASH))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(!PathBuf::from(&collection).exists());
+    }
+
+    #[tokio::test]
+    async fn test_upload_routes_hash_mismatch() {
+        let base = "/endpoint/v1";
+        let route = upload_routes(base);
+
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+        let endpoint_id = "tampered-upload-endpoint";
+        let endpoint_path = format!("{}/{endpoint_id}", config.endpoint_server.storage);
+        let _ = remove_file(format!("{endpoint_path}/1/manifest.json"));
+        let _ = remove_file(format!("{endpoint_path}/1/duplicate.jsonl"));
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
+        let app = route.with_state(server_state);
+
+        // Body was changed after the hash was created
+        let res = app
+            .clone()
+            .oneshot(upload_request_data(
+                base,
+                endpoint_id,
+                UPLOAD_HASH,
+                r#"{"test":2}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let collection = format!("{endpoint_path}/1/duplicate.jsonl");
+        assert!(!PathBuf::from(&collection).exists());
+
+        let res = app
+            .oneshot(upload_request(base, endpoint_id, UPLOAD_HASH))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(PathBuf::from(&collection).exists());
+
+        let manifest = read_to_string(format!("{endpoint_path}/1/manifest.json")).unwrap();
+        assert_eq!(
+            manifest,
+            format!(
+                r#"{{"duplicate.jsonl":{{"sha256":"{UPLOAD_HASH}","size":10,"verified":true}}}}"#
+            )
+        );
+    }
+
+    /// Upload request containing two collection files. Per part hashes are only set if `part_hashes` is true
+    fn upload_request_multiple(
+        base: &str,
+        endpoint_id: &str,
+        hash: &str,
+        part_hashes: bool,
+    ) -> Request<Body> {
+        let boundary = "artemis-test";
+        let part_hash = if part_hashes {
+            format!("{CONTENT_HASH_HEADER}: {UPLOAD_HASH}\r\n")
+        } else {
+            String::new()
+        };
+        let body = format!("--{boundary}\r\nContent-Disposition: form-data; name=\"endpoint-id\"\r\n\r\n{endpoint_id}\r\n--{boundary}\r\nContent-Disposition: form-data; name=\"collection-id\"\r\n\r\n1\r\n--{boundary}\r\nContent-Disposition: form-data; name=\"collection\"; filename=\"first.jsonl\"\r\n{part_hash}\r\n{{\"test\":1}}\r\n--{boundary}\r\nContent-Disposition: form-data; name=\"collection\"; filename=\"second.jsonl\"\r\n{part_hash}\r\n{{\"test\":1}}\r\n--{boundary}--\r\n");
+
+        Request::builder()
+            .method(Method::POST)
+            .uri(format!("{base}/upload"))
+            .header(
+                "content-type",
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .header(CONTENT_HASH_HEADER, hash)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_upload_routes_multiple_collections() {
+        let base = "/endpoint/v1";
+        let route = upload_routes(base);
+
+        let mut test_location = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_location.push("tests/test_data/server.toml");
+
+        let config = read_config(&test_location.display().to_string())
+            .await
+            .unwrap();
+        let endpoint_id = "multiple-upload-endpoint";
+        let endpoint_path = format!("{}/{endpoint_id}", config.endpoint_server.storage);
+        let _ = remove_file(format!("{endpoint_path}/1/manifest.json"));
+        let _ = remove_file(format!("{endpoint_path}/1/second.jsonl"));
+
+        let command = Arc::new(RwLock::new(HashMap::new()));
+        let server_state = ServerState {
+            config,
+            command,
+            events: EventLog::default(),
+            metrics: Metrics::default(),
+        };
+        let app = route.with_state(server_state);
+
+        // One request hash cannot cover two collection files
+        let res = app
+            .clone()
+            .oneshot(upload_request_multiple(
+                base,
+                endpoint_id,
+                UPLOAD_HASH,
+                false,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert!(!PathBuf::from(format!("{endpoint_path}/1/second.jsonl")).exists());
+
+        let _ = remove_file(format!("{endpoint_path}/1/manifest.json"));
+        let res = app
+            .oneshot(upload_request_multiple(
+                base,
+                endpoint_id,
+                UPLOAD_HASH,
+                true,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(PathBuf::from(format!("{endpoint_path}/1/first.jsonl")).exists());
+    }
 }
@@ -18,6 +18,10 @@ pub(crate) fn generate_config() -> ArtemisConfig {
         storage: String::new(),
         verify_ssl: true,
         version: 1,
+        dual_stack: None,
+        broadcast_concurrency: None,
+        broadcast_timeout_secs: None,
+        metrics_path: None,
     };
 
     ArtemisConfig {
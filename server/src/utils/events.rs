@@ -0,0 +1,123 @@
+use super::time::time_now;
+use common::server::{
+    jobs::CollectionEvent,
+    webui::{LogLevel, ServerEvent},
+};
+use std::{collections::VecDeque, sync::Arc};
+use tokio::sync::{broadcast, RwLock};
+
+/// Max number of recent events kept in memory
+const MAX_EVENTS: usize = 500;
+
+/// Recent server events and a channel to stream new events to the `WebUI`
+#[derive(Debug, Clone)]
+pub(crate) struct EventLog {
+    recent: Arc<RwLock<VecDeque<ServerEvent>>>,
+    sender: broadcast::Sender<ServerEvent>,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(MAX_EVENTS);
+        EventLog {
+            recent: Arc::new(RwLock::new(VecDeque::new())),
+            sender,
+        }
+    }
+}
+
+impl EventLog {
+    /// Record a new event and send it to any listeners
+    pub(crate) async fn push(&self, level: LogLevel, source: &str, message: &str) {
+        let event = ServerEvent {
+            timestamp: time_now(),
+            level,
+            source: source.to_string(),
+            message: message.to_string(),
+        };
+
+        let mut recent = self.recent.write().await;
+        if recent.len() == MAX_EVENTS {
+            recent.pop_front();
+        }
+        recent.push_back(event.clone());
+        // Sending only fails if nobody is listening
+        let _ = self.sender.send(event);
+    }
+
+    /// Get the recent events and subscribe to new events. No events are missed or repeated between the two
+    pub(crate) async fn subscribe(&self) -> (Vec<ServerEvent>, broadcast::Receiver<ServerEvent>) {
+        let recent = self.recent.read().await;
+        (recent.iter().cloned().collect(), self.sender.subscribe())
+    }
+
+    /// Record a collection event from an endpoint
+    pub(crate) async fn push_collection(&self, endpoint_id: &str, event: &CollectionEvent) {
+        let (level, message) = match event {
+            CollectionEvent::ArtifactStarted { name } => {
+                (LogLevel::Info, format!("Started artifact {name}"))
+            }
+            CollectionEvent::RecordsEmitted { name, count } => (
+                LogLevel::Info,
+                format!("Artifact {name} emitted {count} records"),
+            ),
+            CollectionEvent::ArtifactFinished { name } => {
+                (LogLevel::Info, format!("Finished artifact {name}"))
+            }
+            CollectionEvent::ArtifactFailed { name, error } => {
+                (LogLevel::Error, format!("Artifact {name} failed: {error}"))
+            }
+        };
+        self.push(level, endpoint_id, &message).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventLog, MAX_EVENTS};
+    use common::server::{jobs::CollectionEvent, webui::LogLevel};
+
+    #[tokio::test]
+    async fn test_event_log_push() {
+        let log = EventLog::default();
+        for count in 0..MAX_EVENTS + 10 {
+            log.push(LogLevel::Info, "server", &format!("event {count}"))
+                .await;
+        }
+
+        let (recent, _) = log.subscribe().await;
+        assert_eq!(recent.len(), MAX_EVENTS);
+        assert_eq!(recent[0].message, "event 10");
+    }
+
+    #[tokio::test]
+    async fn test_event_log_subscribe() {
+        let log = EventLog::default();
+        log.push(LogLevel::Warn, "server", "old event").await;
+
+        let (recent, mut receiver) = log.subscribe().await;
+        log.push(LogLevel::Info, "server", "new event").await;
+
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].level, LogLevel::Warn);
+        assert_eq!(receiver.recv().await.unwrap().message, "new event");
+    }
+
+    #[tokio::test]
+    async fn test_push_collection() {
+        let log = EventLog::default();
+        log.push_collection(
+            "abcd",
+            &CollectionEvent::ArtifactFailed {
+                name: String::from("prefetch"),
+                error: String::from("bad file"),
+            },
+        )
+        .await;
+
+        let (recent, _) = log.subscribe().await;
+        assert_eq!(recent[0].level, LogLevel::Error);
+        assert_eq!(recent[0].source, "abcd");
+        assert_eq!(recent[0].message, "Artifact prefetch failed: bad file");
+    }
+}
@@ -94,6 +94,16 @@ pub(crate) async fn append_file(data: &str, path: &str, limit: &u64) -> Result<(
     Ok(())
 }
 
+/// Check if a client provided name is safe to use as a single path component
+pub(crate) fn is_safe_name(name: &str) -> bool {
+    if name.is_empty() || name.starts_with('.') {
+        return false;
+    }
+
+    name.chars()
+        .all(|value| value.is_ascii_alphanumeric() || value == '-' || value == '_' || value == '.')
+}
+
 /// Create a directory and all its parents
 pub(crate) async fn create_dirs(path: &str) -> Result<(), UtilServerError> {
     let result = create_dir_all(path).await;
@@ -111,7 +121,9 @@ pub(crate) async fn create_dirs(path: &str) -> Result<(), UtilServerError> {
 #[cfg(test)]
 mod tests {
     use super::read_file;
-    use crate::utils::filesystem::{create_dirs, is_directory, is_file, read_lines, write_file};
+    use crate::utils::filesystem::{
+        create_dirs, is_directory, is_file, is_safe_name, read_lines, write_file,
+    };
     use std::path::PathBuf;
 
     #[tokio::test]
@@ -185,4 +197,15 @@ mod tests {
 
         let _ = read_lines(&test_location.to_str().unwrap()).await.unwrap();
     }
+
+    #[test]
+    fn test_is_safe_name() {
+        assert!(is_safe_name("0998b365-b60d-4c0c-a629-f631afa83d2c"));
+        assert!(is_safe_name("processes.jsonl.gz"));
+        assert!(!is_safe_name("../../etc"));
+        assert!(!is_safe_name(".."));
+        assert!(!is_safe_name("a/b"));
+        assert!(!is_safe_name("a\\b"));
+        assert!(!is_safe_name(""));
+    }
 }
@@ -1,5 +1,6 @@
 pub(crate) mod config;
 mod error;
+pub(crate) mod events;
 pub(crate) mod filesystem;
 pub(crate) mod info;
 pub(crate) mod time;
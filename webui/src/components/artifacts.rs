@@ -0,0 +1,349 @@
+use crate::web::server::request_server;
+use common::server::webui::{ArtifactRecords, ArtifactRequest};
+use leptos::{
+    component, create_node_ref, create_resource, create_signal, html, logging::error, store_value,
+    view, CollectView, IntoView, ReadSignal, Show, SignalGet, SignalSet, SignalUpdate, Transition,
+    WriteSignal,
+};
+use reqwest::Method;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[component]
+/// List the artifacts collected from an endpoint and browse the selected artifact
+pub(crate) fn EndpointArtifacts(endpoint_id: String) -> impl IntoView {
+    let endpoint = store_value(endpoint_id);
+    let collections = create_resource(move || endpoint.get_value(), list_artifacts);
+    let (selected, set_selected) = create_signal(None::<ArtifactRequest>);
+
+    view! {
+      <div class="col-span-full m-2 mb-16">
+        <Transition fallback=move || {
+            view! { <p>"Loading..."</p> }
+        }>
+          {move || {
+              collections
+                  .get()
+                  .map(|res| {
+                      if res.is_empty() {
+                          return view! { <p class="m-2">"No collected artifacts found"</p> }
+                              .into_view();
+                      }
+                      view! {
+                        <ul class="menu menu-horizontal bg-base-200 rounded-box">
+                          {res
+                              .into_iter()
+                              .map(|(collection_id, files)| {
+                                  view! {
+                                    <li>
+                                      <details>
+                                        <summary>{format!("Collection {collection_id}")}</summary>
+                                        <ul class="z-[1]">
+                                          {files
+                                              .into_iter()
+                                              .map(|filename| {
+                                                  let request = ArtifactRequest {
+                                                      endpoint_id: endpoint.get_value(),
+                                                      collection_id: collection_id.clone(),
+                                                      filename: filename.clone(),
+                                                      count: 50,
+                                                      ascending: true,
+                                                      ..Default::default()
+                                                  };
+                                                  view! {
+                                                    <li>
+                                                      <a on:click=move |_| {
+                                                          set_selected.set(Some(request.clone()))
+                                                      }>{filename}</a>
+                                                    </li>
+                                                  }
+                                              })
+                                              .collect_view()}
+                                        </ul>
+                                      </details>
+                                    </li>
+                                  }
+                              })
+                              .collect_view()}
+                        </ul>
+                      }
+                          .into_view()
+                  })
+          }}
+
+        </Transition>
+        {move || selected.get().map(|request| view! { <ArtifactTable request/> })}
+      </div>
+    }
+}
+
+#[component]
+/// Display a page of artifact records. Filtering, sorting, and paging are done by the server
+fn ArtifactTable(request: ArtifactRequest) -> impl IntoView {
+    let (request_get, request_set) = create_signal(request);
+    let records = create_resource(move || request_get.get(), artifact_records);
+
+    view! {
+      <div class="mt-4">
+        <p class="font-semibold m-2">{move || request_get.get().filename}</p>
+        <FilterArtifact request_get request_set/>
+        <Transition fallback=move || {
+            view! { <p>"Loading..."</p> }
+        }>
+          {move || {
+              records
+                  .get()
+                  .map(|res| {
+                      let res = res.unwrap_or_default();
+                      let current = request_get.get();
+                      let last = (current.offset + res.records.len()).min(res.total);
+                      let next_disabled = current.offset + current.count >= res.total;
+                      view! {
+                        <div class="flex items-center gap-2 m-2">
+                          <span>
+                            {format!(
+                                "Showing {}-{last} of {}",
+                                if res.total == 0 { 0 } else { current.offset + 1 },
+                                res.total,
+                            )}
+
+                          </span>
+                          <button
+                            class="join-item btn btn-sm btn-outline"
+                            disabled=current.offset == 0
+                            on:click=move |_| {
+                                request_set
+                                    .update(|request| {
+                                        request.offset = request.offset.saturating_sub(request.count)
+                                    })
+                            }
+                          >
+
+                            Previous
+                          </button>
+                          <button
+                            class="join-item btn btn-sm btn-outline"
+                            disabled=next_disabled
+                            on:click=move |_| {
+                                request_set.update(|request| request.offset += request.count)
+                            }
+                          >
+
+                            Next
+                          </button>
+                        </div>
+                        <div class="overflow-x-auto">
+                          <table class="table table-zebra border">
+                            <thead>
+                              <tr>
+                                {res
+                                    .columns
+                                    .clone()
+                                    .into_iter()
+                                    .map(|column| {
+                                        view! { <SortHeader column request_get request_set/> }
+                                    })
+                                    .collect_view()}
+                              </tr>
+                            </thead>
+                            <tbody>
+                              {res
+                                  .records
+                                  .into_iter()
+                                  .map(|record| {
+                                      view! {
+                                        <tr>
+                                          {res
+                                              .columns
+                                              .iter()
+                                              .map(|column| {
+                                                  view! {
+                                                    <td class="break-all">
+                                                      {value_text(record.get(column))}
+                                                    </td>
+                                                  }
+                                              })
+                                              .collect_view()}
+                                        </tr>
+                                      }
+                                  })
+                                  .collect_view()}
+                            </tbody>
+                          </table>
+                        </div>
+                      }
+                  })
+          }}
+
+        </Transition>
+      </div>
+    }
+}
+
+#[component]
+/// Filter artifact records by a field value and change the page size
+fn FilterArtifact(
+    request_get: ReadSignal<ArtifactRequest>,
+    request_set: WriteSignal<ArtifactRequest>,
+) -> impl IntoView {
+    let counts = vec![20, 50, 100];
+    let field_form = create_node_ref::<html::Input>();
+    let filter_form = create_node_ref::<html::Input>();
+
+    let filter_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let field = field_form.get().unwrap().value();
+        let filter = filter_form.get().unwrap().value();
+        request_set.update(|request| {
+            request.field = field;
+            request.filter = filter;
+            request.offset = 0;
+        });
+    };
+
+    view! {
+      <div class="flex items-center gap-2 p-2">
+        <form class="flex gap-2" on:submit=filter_submit>
+          <input
+            type="text"
+            class="input input-sm input-bordered"
+            node_ref=field_form
+            placeholder="Field (optional)"
+          />
+          <input
+            type="text"
+            class="input input-sm input-bordered"
+            node_ref=filter_form
+            placeholder="Filter value"
+          />
+          <button class="btn btn-sm btn-outline btn-primary">"Filter"</button>
+        </form>
+        <div class="dropdown">
+          <div tabindex="0" role="button" class="btn btn-sm">
+            "Limit: "
+            {move || request_get.get().count}
+          </div>
+          <ul
+            tabindex="0"
+            class="dropdown-content z-[1] menu p-2 shadow bg-base-100 rounded-box w-52"
+          >
+            {counts
+                .into_iter()
+                .map(|count| {
+                    view! {
+                      <li>
+                        <a on:click=move |_| {
+                            request_set
+                                .update(|request| {
+                                    request.count = count;
+                                    request.offset = 0;
+                                })
+                        }>{count}</a>
+                      </li>
+                    }
+                })
+                .collect_view()}
+          </ul>
+        </div>
+      </div>
+    }
+}
+
+#[component]
+/// Column header that sorts the records. Clicking the sorted column again reverses the order
+fn SortHeader(
+    column: String,
+    request_get: ReadSignal<ArtifactRequest>,
+    request_set: WriteSignal<ArtifactRequest>,
+) -> impl IntoView {
+    let name = store_value(column.clone());
+    let sorted = move || request_get.get().sort == name.get_value();
+
+    view! {
+      <th
+        class="cursor-pointer"
+        on:click=move |_| {
+            request_set
+                .update(|request| {
+                    if request.sort == name.get_value() {
+                        request.ascending = !request.ascending;
+                    } else {
+                        request.sort = name.get_value();
+                        request.ascending = true;
+                    }
+                    request.offset = 0;
+                })
+        }
+      >
+
+        <p class="flex items-center justify-between gap-2 leading-none">
+          {column}
+          <Show when=sorted>
+            {move || if request_get.get().ascending { "▲" } else { "▼" }}
+          </Show>
+        </p>
+      </th>
+    }
+}
+
+/// Display strings without quotes. Other JSON values are displayed as JSON
+fn value_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(value) => value.to_string(),
+    }
+}
+
+/// Get the collected artifact files for an endpoint
+async fn list_artifacts(endpoint_id: String) -> BTreeMap<String, Vec<String>> {
+    let res_result = request_server("endpoints/artifacts", endpoint_id, Method::POST).await;
+    let response = match res_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("Failed to send request for artifact list: {err:?}");
+            return BTreeMap::new();
+        }
+    };
+
+    if !response.status().is_success() {
+        error!("Server returned error status: {}", response.status());
+        return BTreeMap::new();
+    }
+
+    let result_json = response.json().await;
+    match result_json {
+        Ok(result) => result,
+        Err(err) => {
+            error!("Failed to get artifact list: {err:?}");
+            BTreeMap::new()
+        }
+    }
+}
+
+/// Get a page of artifact records from the server
+async fn artifact_records(request: ArtifactRequest) -> Option<ArtifactRecords> {
+    let body = serde_json::to_string(&request).unwrap_or_default();
+    let res_result = request_server("endpoints/artifacts/records", body, Method::POST).await;
+    let response = match res_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("Failed to send request for artifact records: {err:?}");
+            return None;
+        }
+    };
+
+    if !response.status().is_success() {
+        error!("Server returned error status: {}", response.status());
+        return None;
+    }
+
+    let result_json = response.json().await;
+    match result_json {
+        Ok(result) => result,
+        Err(err) => {
+            error!("Failed to get artifact records: {err:?}");
+            None
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use crate::web::{server::server_info, time::unixepoch_to_rfc};
+use common::server::webui::{LogLevel, ServerEvent};
+use leptos::{
+    component, create_effect, create_node_ref, create_signal, html::Div, logging::error,
+    on_cleanup, set_timeout, spawn_local, view, For, IntoView, NodeRef, SignalGet, SignalSet,
+    SignalUpdate, SignalWith, WriteSignal,
+};
+use std::{cell::RefCell, collections::VecDeque, rc::Rc, time::Duration};
+use web_sys::{
+    wasm_bindgen::{closure::Closure, JsCast},
+    CloseEvent, MessageEvent, WebSocket,
+};
+
+/// Max number of lines kept in the console
+const MAX_LINES: usize = 1000;
+/// Seconds to wait before reconnecting to the server
+const RECONNECT_SECONDS: u64 = 3;
+
+/// Current connection to the server event stream
+#[derive(Clone)]
+struct EventSocket {
+    socket: Rc<RefCell<Option<WebSocket>>>,
+    /// Set to false when the console is removed from the page
+    active: Rc<RefCell<bool>>,
+    lines: WriteSignal<VecDeque<ConsoleLine>>,
+    connected: WriteSignal<bool>,
+}
+
+/// Server event shown in the console
+#[derive(Clone)]
+struct ConsoleLine {
+    /// Increasing line number used to track lines that are still shown
+    id: u64,
+    event: ServerEvent,
+}
+
+#[component]
+/// Console showing recent server and collection events
+pub(crate) fn Console() -> impl IntoView {
+    let (lines_get, lines_set) = create_signal(VecDeque::<ConsoleLine>::new());
+    let (connected_get, connected_set) = create_signal(false);
+    let console: NodeRef<Div> = create_node_ref();
+
+    let events = EventSocket {
+        socket: Rc::new(RefCell::new(None)),
+        active: Rc::new(RefCell::new(true)),
+        lines: lines_set,
+        connected: connected_set,
+    };
+    connect_events(events.clone());
+    on_cleanup(move || {
+        *events.active.borrow_mut() = false;
+        if let Some(socket) = events.socket.borrow_mut().take() {
+            let _ = socket.close();
+        }
+    });
+
+    // Keep the newest line in view
+    create_effect(move |_| {
+        lines_get.with(|_| ());
+        if let Some(div) = console.get() {
+            div.set_scroll_top(div.scroll_height());
+        }
+    });
+
+    view! {
+      <div class="col-span-full m-2 mb-14">
+        <div class="flex items-center gap-2 mb-2">
+          <h2 class="text-lg font-bold">"Server Console"</h2>
+          {move || {
+              if connected_get.get() {
+                  view! { <span class="badge badge-success">"Connected"</span> }
+              } else {
+                  view! { <span class="badge badge-warning">"Reconnecting"</span> }
+              }
+          }}
+
+        </div>
+        <div
+          node_ref=console
+          class="bg-neutral text-neutral-content font-mono text-sm h-96 overflow-y-auto p-2 rounded"
+        >
+          <For
+            each=move || lines_get.get()
+            key=|line| line.id
+            children=move |line| {
+                view! {
+                  <p class=level_class(&line.event.level)>
+                    {format!(
+                        "{} [{}] {}: {}",
+                        unixepoch_to_rfc(line.event.timestamp as i64),
+                        level_name(&line.event.level),
+                        line.event.source,
+                        line.event.message,
+                    )}
+
+                  </p>
+                }
+            }
+          />
+
+        </div>
+      </div>
+    }
+}
+
+/// Open a websocket to the server event stream. Reconnects if the connection drops
+fn connect_events(events: EventSocket) {
+    spawn_local(async move {
+        let server_result = server_info().await;
+        let (server, port) = match server_result {
+            Ok(result) => result,
+            Err(err) => {
+                error!("Failed to get server IP: {err:?}");
+                return;
+            }
+        };
+
+        let socket_result = WebSocket::new(&format!("ws://{server}:{port}/ui/v1/server/events"));
+        let socket = match socket_result {
+            Ok(result) => result,
+            Err(err) => {
+                error!("Failed to connect to server events: {err:?}");
+                reconnect_events(events);
+                return;
+            }
+        };
+
+        let open_events = events.clone();
+        let on_open = Closure::<dyn FnMut()>::new(move || {
+            // The server sends all recent events again after connecting
+            open_events.lines.set(VecDeque::new());
+            open_events.connected.set(true);
+        });
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        on_open.forget();
+
+        let lines = events.lines;
+        let on_message = Closure::<dyn FnMut(_)>::new(move |message: MessageEvent| {
+            let text = match message.data().as_string() {
+                Some(result) => result,
+                None => return,
+            };
+            let event_result = serde_json::from_str(&text);
+            match event_result {
+                Ok(event) => lines.update(|lines| push_line(lines, event)),
+                Err(err) => error!("Failed to parse server event: {err:?}"),
+            }
+        });
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        on_message.forget();
+
+        let close_events = events.clone();
+        let on_close = Closure::<dyn FnMut(_)>::new(move |_: CloseEvent| {
+            if !*close_events.active.borrow() {
+                return;
+            }
+            close_events.connected.set(false);
+            reconnect_events(close_events.clone());
+        });
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+        on_close.forget();
+
+        *events.socket.borrow_mut() = Some(socket);
+    });
+}
+
+/// Try connecting again after a delay, unless the console was removed
+fn reconnect_events(events: EventSocket) {
+    if !*events.active.borrow() {
+        return;
+    }
+    set_timeout(
+        move || {
+            if *events.active.borrow() {
+                connect_events(events);
+            }
+        },
+        Duration::from_secs(RECONNECT_SECONDS),
+    );
+}
+
+/// Add a line to the console and drop the oldest lines over the limit
+fn push_line(lines: &mut VecDeque<ConsoleLine>, event: ServerEvent) {
+    let id = lines.back().map(|line| line.id + 1).unwrap_or_default();
+    if lines.len() == MAX_LINES {
+        lines.pop_front();
+    }
+    lines.push_back(ConsoleLine { id, event });
+}
+
+/// Color console lines by severity
+fn level_class(level: &LogLevel) -> &'static str {
+    match level {
+        LogLevel::Info => "text-info",
+        LogLevel::Warn => "text-warning",
+        LogLevel::Error => "text-error",
+    }
+}
+
+fn level_name(level: &LogLevel) -> &'static str {
+    match level {
+        LogLevel::Info => "INFO",
+        LogLevel::Warn => "WARN",
+        LogLevel::Error => "ERROR",
+    }
+}
@@ -31,6 +31,15 @@ pub(crate) fn EndpointDetails(beat: Heartbeat) -> impl IntoView {
         <div>
           <p class="font-semibold">{format!("Jobs Running: {}", beat.jobs_running)}</p>
         </div>
+        <div class="divider divider-horizontal"></div>
+        <div>
+          <a
+            class="link font-semibold"
+            href=format!("/ui/v1/endpoints/artifacts?id={}", beat.endpoint_id)
+          >
+            "Collected Artifacts"
+          </a>
+        </div>
       </div>
       <br/>
       <div class="p-8 m-4 border-2 rounded-lg col-span-full shadow-xl flex place-content-around">
@@ -1,3 +1,5 @@
+pub(crate) mod artifacts;
+pub(crate) mod console;
 pub(crate) mod enrollment;
 pub(crate) mod footer;
 pub(crate) mod host;
@@ -6,3 +8,4 @@ pub(crate) mod jobs;
 pub(crate) mod menu;
 pub(crate) mod rust;
 pub(crate) mod stats;
+pub(crate) mod validate;
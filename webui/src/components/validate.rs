@@ -0,0 +1,111 @@
+use crate::web::server::request_server;
+use common::server::webui::TomlValidation;
+use leptos::{
+    component, create_action, create_signal, event_target_value, logging::error, view, IntoView,
+    Show, SignalGet, SignalSet,
+};
+use reqwest::Method;
+
+#[component]
+/// Validate a collection TOML before sending it to endpoints
+pub(crate) fn ValidateToml() -> impl IntoView {
+    let (toml_get, toml_set) = create_signal(String::new());
+    let validate = create_action(|data: &String| {
+        let data = data.clone();
+        async move { validate_toml(data).await }
+    });
+    let pending = validate.pending();
+    let result = validate.value();
+
+    view! {
+      <div class="col-span-full m-2 mb-14">
+        <textarea
+          class="textarea textarea-bordered w-full h-96 font-mono"
+          placeholder="Paste collection TOML"
+          prop:value=move || toml_get.get()
+          on:input=move |ev| toml_set.set(event_target_value(&ev))
+        ></textarea>
+        <button
+          class="btn btn-sm btn-outline btn-primary mt-2"
+          disabled=move || pending.get() || toml_get.get().trim().is_empty()
+          on:click=move |_| validate.dispatch(toml_get.get())
+        >
+          "Validate"
+        </button>
+        <Show when=move || pending.get()>
+          <p class="mt-2">"Validating..."</p>
+        </Show>
+        {move || {
+            result
+                .get()
+                .map(|status| match status {
+                    Err(err) => {
+                        view! {
+                          <div role="alert" class="alert alert-error mt-2">
+                            <span>{err}</span>
+                          </div>
+                        }
+                            .into_view()
+                    }
+                    Ok(info) if info.errors.is_empty() => {
+                        view! {
+                          <div role="alert" class="alert alert-success mt-2">
+                            <span>"Collection TOML is valid"</span>
+                          </div>
+                          <ul class="list-disc m-4">
+                            {info
+                                .artifacts
+                                .into_iter()
+                                .map(|name| view! { <li>{name}</li> })
+                                .collect::<Vec<_>>()}
+                          </ul>
+                        }
+                            .into_view()
+                    }
+                    Ok(info) => {
+                        view! {
+                          <div role="alert" class="alert alert-warning mt-2">
+                            <span>"Collection TOML has errors"</span>
+                          </div>
+                          <ul class="list-disc m-4">
+                            {info
+                                .errors
+                                .into_iter()
+                                .map(|err| view! { <li>{err}</li> })
+                                .collect::<Vec<_>>()}
+                          </ul>
+                        }
+                            .into_view()
+                    }
+                })
+        }}
+
+      </div>
+    }
+}
+
+/// Send the collection TOML to the server for validation
+async fn validate_toml(data: String) -> Result<TomlValidation, String> {
+    let res_result = request_server("collections/validate", data, Method::POST).await;
+    let response = match res_result {
+        Ok(result) => result,
+        Err(err) => {
+            error!("Failed to validate collection TOML: {err:?}");
+            return Err(String::from("Could not reach server"));
+        }
+    };
+
+    if !response.status().is_success() {
+        error!("Server returned error status: {}", response.status());
+        return Err(format!("Server returned status {}", response.status()));
+    }
+
+    let result_json = response.json().await;
+    match result_json {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            error!("Failed to get validation results: {err:?}");
+            Err(String::from("Could not read server response"))
+        }
+    }
+}
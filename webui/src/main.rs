@@ -4,7 +4,8 @@ mod web;
 
 use crate::ui::{
     about::About,
-    endpoints::{EndpointInfo, Endpoints},
+    collections::Collections,
+    endpoints::{Artifacts, EndpointInfo, Endpoints},
     home::Home,
 };
 use leptos::{component, mount_to_body, view, IntoView};
@@ -23,8 +24,10 @@ fn App() -> impl IntoView {
         <Routes>
           <Route path="/ui/v1/about" view=About/>
           <Route path="/ui/v1/home" view=Home/>
+          <Route path="/ui/v1/collections" view=Collections/>
           <Route path="/ui/v1/endpoints" view=Endpoints/>
           <Route path="/ui/v1/endpoints/info" view=EndpointInfo/>
+          <Route path="/ui/v1/endpoints/artifacts" view=Artifacts/>
         </Routes>
       </Router>
     }
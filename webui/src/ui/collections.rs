@@ -0,0 +1,17 @@
+use crate::components::{console::Console, footer::Footer, menu::Menu, validate::ValidateToml};
+use leptos::{component, view, IntoView};
+use leptos_meta::Stylesheet;
+
+#[component]
+/// Collections page
+pub(crate) fn Collections() -> impl IntoView {
+    view! {
+      <Stylesheet id="leptos" href="/pkg/tailwind.css"/>
+      <div class="grid grid-cols-3">
+        <Menu/>
+        <ValidateToml/>
+        <Console/>
+      </div>
+      <Footer/>
+    }
+}
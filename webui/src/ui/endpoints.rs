@@ -1,10 +1,12 @@
 use crate::components::{
+    artifacts::EndpointArtifacts,
     enrollment::{Enrollment, GetInfo},
     footer::Footer,
     menu::Menu,
 };
-use leptos::{component, view, IntoView};
+use leptos::{component, view, IntoView, SignalGet};
 use leptos_meta::Stylesheet;
+use leptos_router::use_query_map;
 
 #[component]
 /// List endpoints page
@@ -30,3 +32,20 @@ pub(crate) fn EndpointInfo() -> impl IntoView {
       </div>
     }
 }
+
+#[component]
+/// Endpoint collected artifacts page
+pub(crate) fn Artifacts() -> impl IntoView {
+    let query = use_query_map();
+    // endpoint ID stored as ?id=
+    let endpoint_id = move || query.get().get("id").cloned().unwrap_or_default();
+
+    view! {
+      <Stylesheet id="leptos" href="/pkg/tailwind.css"/>
+      <div class="grid grid-cols-3">
+        <Menu/>
+        {move || view! { <EndpointArtifacts endpoint_id=endpoint_id()/> }}
+      </div>
+      <Footer/>
+    }
+}
@@ -1,3 +1,4 @@
 pub(crate) mod about;
+pub(crate) mod collections;
 pub(crate) mod endpoints;
 pub(crate) mod home;